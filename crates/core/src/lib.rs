@@ -2,6 +2,7 @@ pub mod types;
 
 // Re-export commonly used types
 pub use types::{
-    BundleRequest, BundleResult, ColorMode, CssVariableMode, Declaration, Diagnostic,
-    DiagnosticLevel, NamingMode, UnknownClassMode,
+    BreakpointStyle, BreakpointUnit, BundleRequest, BundleResult, ColorMode, ContainerStyle,
+    CssVariableMode, CssVariableOverrides, Declaration, Diagnostic, DiagnosticLevel, HashVersion,
+    MediaQuerySyntax, NamingMode, TailwindVersion, Targets, UnitConversion, UnknownClassMode,
 };
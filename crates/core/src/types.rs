@@ -33,8 +33,22 @@ pub enum NamingMode {
     Semantic,
 }
 
-/// CSS 变量模式
+/// Hash 命名算法版本
+///
+/// 同一版本下，相同输入在任何平台、任何发布版本中都产生相同的类名，
+/// 构建缓存和多机 CI 可以依赖这一点。算法变更只会以新版本的形式引入。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HashVersion {
+    /// blake3(类名按原顺序以空格连接)，取十六进制前 12 位；忽略盐，类名顺序敏感
+    #[default]
+    V1,
+    /// 类名排序去重后以 `\n` 连接，追加 `\0` + 盐，blake3 后取十六进制前 12 位；
+    /// 与类名书写顺序无关
+    V2,
+}
+
+/// CSS 变量模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum CssVariableMode {
     /// 使用 CSS 变量引用: `font-size: var(--text-3xl)`
     /// 需要引入 Tailwind 的 @layer theme
@@ -45,8 +59,34 @@ pub enum CssVariableMode {
     Inline,
 }
 
+/// 按主题类别覆盖 `CssVariableMode`，未设置的类别沿用全局模式
+///
+/// 例如全局 `Inline`、只把 `shadow` 设为 `Var`，阴影保留 `var(--shadow-md)` 供设计系统统一调整，
+/// 其余主题值内联。颜色由 `ColorMode::Var`、间距由 `resolve_spacing` 单独控制，不在此列。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct CssVariableOverrides {
+    /// 字号及其默认行高：`--text-*`、`--text-*--line-height`
+    pub font_size: Option<CssVariableMode>,
+    /// 字体族：`--font-*`
+    pub font_family: Option<CssVariableMode>,
+    /// 阴影：`--shadow-*`、`--inset-shadow-*`、`--text-shadow-*`
+    pub shadow: Option<CssVariableMode>,
+    /// 模糊：`--blur-*`
+    pub blur: Option<CssVariableMode>,
+    /// 宽高比：`--aspect-video`
+    pub aspect: Option<CssVariableMode>,
+}
+
+impl CssVariableOverrides {
+    /// 是否有类别指定为 `Var`（全局 `Inline` 时也需要 `:root` 定义）
+    pub fn any_var(&self) -> bool {
+        [self.font_size, self.font_family, self.shadow, self.blur, self.aspect]
+            .contains(&Some(CssVariableMode::Var))
+    }
+}
+
 /// 颜色输出模式
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum ColorMode {
     /// Hex 颜色值：#3b82f6
     #[default]
@@ -59,6 +99,109 @@ pub enum ColorMode {
     Var,
 }
 
+/// 断点长度单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum BreakpointUnit {
+    /// rem（Tailwind v4）：`48rem`
+    #[default]
+    Rem,
+    /// px（Tailwind v3），按 1rem = 16px 换算：`768px`
+    Px,
+}
+
+/// 媒体查询 / 容器查询的条件写法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum MediaQuerySyntax {
+    /// 范围语法（Tailwind v4）：`(width >= 48rem)`、`(width < 48rem)`
+    #[default]
+    Range,
+    /// 传统语法：`(min-width: 48rem)`、`not all and (min-width: 48rem)`
+    Legacy,
+}
+
+/// 断点 at-rule 的输出风格
+///
+/// 转换器与打包器共用同一套断点输出，`md:` 在任何路径下都得到相同的媒体查询。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct BreakpointStyle {
+    pub unit: BreakpointUnit,
+    pub syntax: MediaQuerySyntax,
+}
+
+/// 输出值中长度单位的统一换算
+///
+/// 作用于所有生成的声明值和 `:root` 中的主题变量，按根字号（默认 16px）换算。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum UnitConversion {
+    /// 不换算（默认）
+    #[default]
+    None,
+    /// px 换算为 rem：`24px` → `1.5rem`
+    PxToRem,
+    /// rem 换算为 px：`1.5rem` → `24px`
+    RemToPx,
+}
+
+/// 需要支持的最低浏览器主版本，决定生成的 CSS 中补充哪些厂商前缀与颜色回退值
+///
+/// 未设置的浏览器不参与判断，全部未设置时不做任何兼容处理（默认）。Edge 按 Chrome 处理，iOS 按 Safari 处理。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Targets {
+    pub chrome: Option<u32>,
+    pub firefox: Option<u32>,
+    pub safari: Option<u32>,
+}
+
+impl Targets {
+    /// 是否未设置任何浏览器
+    pub fn is_empty(&self) -> bool {
+        self.chrome.is_none() && self.firefox.is_none() && self.safari.is_none()
+    }
+
+    /// 是否有目标浏览器低于给定的主版本（即不支持从这些版本起才支持的特性）
+    pub fn below(&self, chrome: u32, firefox: u32, safari: u32) -> bool {
+        let below = |target: Option<u32>, since: u32| target.is_some_and(|version| version < since);
+        below(self.chrome, chrome) || below(self.firefox, firefox) || below(self.safari, safari)
+    }
+}
+
+/// `container` 类的附加样式，对应 Tailwind v3 配置中的 `theme.container.center` / `padding`
+///
+/// 默认只输出 `width: 100%` 与各断点的 `max-width`，与 Tailwind 内置的 `container` 一致。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct ContainerStyle {
+    /// 是否水平居中（`margin-inline: auto`）
+    pub center: bool,
+    /// 水平内边距（如 `"2rem"`），None 时不设置
+    pub padding: Option<String>,
+}
+
+/// 目标 Tailwind 主版本
+///
+/// 两个版本的部分命名尺寸取值不同：v4 把 v3 的 `shadow-sm` / `rounded-sm` / `blur-sm` 改名为 `*-xs`，
+/// 原来不带尺寸的 `shadow` / `rounded` / `blur` 改名为 `*-sm`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum TailwindVersion {
+    /// Tailwind v3：不使用主题变量，命名尺寸输出具体值
+    V3,
+    /// Tailwind v4（默认）
+    #[default]
+    V4,
+}
+
+impl TailwindVersion {
+    /// 由版本号解析主版本：`"3"`、`"3.4"`、`"v3.4.17"`、`"4.0"`
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+        match version.split('.').next()? {
+            "3" => Some(TailwindVersion::V3),
+            "4" => Some(TailwindVersion::V4),
+            _ => None,
+        }
+    }
+}
+
 /// 未知类名处理模式
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum UnknownClassMode {
@@ -108,10 +251,19 @@ impl Diagnostic {
             message: message.into(),
         }
     }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            level: DiagnosticLevel::Info,
+            message: message.into(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DiagnosticLevel {
+    /// 提示信息（如按注释跳过的区域），不影响结果
+    Info,
     Warning,
     Error,
 }
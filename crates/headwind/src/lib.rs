@@ -0,0 +1,94 @@
+//! Headwind 的稳定公共 API
+//!
+//! 工作区内部拆分为 core / tw_parse / tw_index / transform 多个 crate，
+//! 其模块结构会随重构变化。下游 Rust 用户应只依赖本 crate：
+//! 这里只重新导出经过挑选的类型和函数，内部 crate 调整时保持路径不变。
+//!
+//! # Feature
+//!
+//! | feature | 内容 |
+//! |---------|------|
+//! | `parse` | [`parser`]：Tailwind 类名解析 |
+//! | `bundle` | [`bundler`]、[`naming`]、[`theme`]：类名 → CSS（含 `parse`） |
+//! | `transform`（默认） | [`transform`]：JSX / HTML 源码变换（含 `bundle`） |
+//!
+//! 只需要解析时：`headwind = { version = "0.1", default-features = false, features = ["parse"] }`
+//!
+//! # 示例
+//!
+//! ```
+//! # #[cfg(feature = "transform")]
+//! # {
+//! use headwind::{transform_jsx, TransformOptions};
+//!
+//! let source = r#"const App = () => <div className="p-4">Hi</div>;"#;
+//! let result = transform_jsx(source, "App.tsx", TransformOptions::default()).unwrap();
+//! assert!(result.css.contains("padding: 1rem"));
+//! # }
+//! ```
+
+pub use headwind_core::{
+    BreakpointStyle, BreakpointUnit, ColorMode, ContainerStyle, CssVariableMode,
+    CssVariableOverrides, Declaration, Diagnostic, DiagnosticLevel, HashVersion, MediaQuerySyntax,
+    NamingMode, TailwindVersion, Targets, UnitConversion, UnknownClassMode,
+};
+
+/// Tailwind 类名解析
+#[cfg(feature = "parse")]
+pub mod parser {
+    pub use headwind_tw_parse::{
+        parse_class, parse_classes, parse_classes_with_spans, ArbitraryValue, CssVariableValue,
+        Modifier, ParseError, ParsedClass, ParsedValue, SpannedClass,
+    };
+}
+
+/// 类名 → CSS 转换与打包
+#[cfg(feature = "bundle")]
+pub mod bundler {
+    pub use headwind_tw_index::{
+        load_index, load_versioned_index, Bundler, CacheStats, ClassContext, ConversionCache,
+        Converter, CssRule, RuleGroup, Suggestion, TailwindIndex, VersionedIndex,
+    };
+}
+
+/// 生成类名的命名策略
+#[cfg(feature = "bundle")]
+pub mod naming {
+    pub use headwind_tw_index::naming::{
+        class_hash, create_naming_strategy, create_versioned_naming_strategy, NamingStrategy,
+    };
+}
+
+/// 主题取值（调色板与内置主题变量）
+#[cfg(feature = "bundle")]
+pub mod theme {
+    pub use headwind_tw_index::palette::get_color;
+    pub use headwind_tw_index::theme_values::{BLUR_SIZE, FONT_FAMILY, TEXT_LINE_HEIGHT, TEXT_SIZE};
+}
+
+/// JSX / HTML 源码变换
+#[cfg(feature = "transform")]
+pub mod transform {
+    pub use headwind_transform::{
+        css_module_dts, extract_css_class_names, is_html_document, merge_css_module, merge_outputs,
+        style_module_code, sweep_css_module, transform_css_module_incremental, transform_file,
+        transform_html, transform_jsx, transform_many, transform_markdown, AstContext, AstExtension,
+        BatchProgress, BatchResult, CancelToken, ClassAttrMerge, ClassAttrName, ClassHook,
+        ClassOverride, CollectedClass, ConfigSession, CssDedup, CssModuleExtension,
+        CssModulesAccess, CssRule, EmitOptions, FileStatus, Formatter, HeadwindConfig, HtmlWrapper,
+        ImportOptions, ImportPosition, ImportQuery, MergedOutput, NameCollision, NameScope,
+        NamingStats, OutputMode, ProseClassMode, QuoteStyle, ResolvedConfig, RuleHook,
+        RuntimeHelper, StyleSheetFormat, SweepReport, SweptRule, SyntaxOptions, Timings,
+        TransformOptions, TransformResult,
+    };
+    pub use headwind_transform::components::{
+        self, ComponentAnalysis, ComponentOptions, ComponentSuggestion,
+    };
+    pub use headwind_transform::report::{self, FileReport};
+}
+
+#[cfg(feature = "transform")]
+pub use transform::{
+    transform_file, transform_html, transform_jsx, transform_markdown, HeadwindConfig,
+    TransformOptions, TransformResult,
+};
@@ -0,0 +1,31 @@
+//! 公共 API 路径的冒烟测试：内部 crate 重构时这些路径必须保持可用
+
+#[cfg(feature = "parse")]
+#[test]
+fn test_parse_path() {
+    let parsed = headwind::parser::parse_class("hover:p-4").unwrap();
+    assert_eq!(parsed.modifiers().len(), 1);
+}
+
+#[cfg(feature = "bundle")]
+#[test]
+fn test_bundle_path() {
+    let bundler = headwind::bundler::Bundler::new();
+    let css = bundler.bundle_to_css("c_1", "p-4", "  ").unwrap();
+    assert!(css.contains(".c_1 {"));
+    assert_eq!(
+        headwind::naming::class_hash(&["p-4".to_string(), "m-2".to_string()], "", headwind::HashVersion::V1),
+        "c_728afc693773"
+    );
+}
+
+#[cfg(feature = "transform")]
+#[test]
+fn test_transform_path() {
+    let result = headwind::transform_html(
+        r#"<div class="p-4">Hi</div>"#,
+        headwind::TransformOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(result.class_map.len(), 1);
+}
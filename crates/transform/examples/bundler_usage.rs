@@ -0,0 +1,49 @@
+//! 直接使用 Bundler：不经过源码变换，把一组 Tailwind 类打包为一条 CSS 规则
+//!
+//! 运行示例：
+//! ```bash
+//! cargo run --example bundler_usage -p headwind-transform
+//! ```
+
+use headwind_core::ColorMode;
+use headwind_tw_index::Bundler;
+
+fn main() {
+    let bundler = Bundler::new();
+
+    // 1. 按修饰符分组
+    let group = bundler.bundle("p-4 text-center hover:text-left md:p-8").unwrap();
+    println!("base: {:?}", group.base);
+    assert_eq!(group.base.len(), 2);
+    assert_eq!(group.pseudo_classes["hover"].base[0].value, "left");
+    assert_eq!(group.responsive["md"].base[0].value, "2rem");
+
+    // 2. 直接生成 CSS
+    let css = bundler
+        .bundle_to_css("card", "rounded-lg p-4 shadow hover:shadow-lg md:p-8", "  ")
+        .unwrap();
+    println!("\n=== .card ===\n{}", css);
+    assert!(css.starts_with(".card {\n"));
+    assert!(css.contains("@media (hover: hover) {\n  .card:hover {"));
+    assert!(css.contains("@media (width >= 48rem) {\n  .card {\n    padding: 2rem;"));
+
+    // 3. 自定义基础选择器
+    let css = bundler
+        .bundle_to_css_with_selector("button", r#"[data-hw="button"]"#, "px-4 py-2", "  ")
+        .unwrap();
+    println!("=== [data-hw] ===\n{}", css);
+    assert!(css.starts_with(r#"[data-hw="button"] {"#));
+
+    // 4. 颜色输出为主题变量
+    let css = Bundler::new()
+        .with_color_mode(ColorMode::Var)
+        .bundle_to_css("brand", "bg-blue-500 text-white", "  ")
+        .unwrap();
+    println!("=== ColorMode::Var ===\n{}", css);
+    assert!(css.contains("background: var(--color-blue-500);"));
+
+    // 5. 识别与修复建议
+    assert!(bundler.is_recognized("hover:p-4"));
+    assert!(!bundler.is_recognized("not-a-utility"));
+    println!("pading-4: {:?}", bundler.suggest("pading-4"));
+}
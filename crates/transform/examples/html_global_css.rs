@@ -0,0 +1,45 @@
+//! HTML → 全局 CSS：把 `class="..."` 中的 Tailwind 类替换为生成的类名，输出一份全局样式
+//!
+//! 运行示例：
+//! ```bash
+//! cargo run --example html_global_css -p headwind-transform
+//! ```
+
+use headwind_transform::{transform_html, NamingMode, TransformOptions};
+
+const SOURCE: &str = r#"<!DOCTYPE html>
+<html>
+<body>
+    <main class="mx-auto max-w-2xl p-4">
+        <h1 class="text-2xl font-bold">Hello</h1>
+        <p class="text-gray-600 dark:text-gray-300">World</p>
+        <a class="text-blue-500 hover:underline" href="/">Home</a>
+    </main>
+</body>
+</html>
+"#;
+
+fn main() {
+    let options = TransformOptions {
+        naming_mode: NamingMode::Readable,
+        ..Default::default()
+    };
+    let result = transform_html(SOURCE, options).expect("transform failed");
+
+    println!("=== index.html ===\n{}", result.code);
+    println!("=== styles.css ===\n{}", result.css);
+
+    // 完整文档原样保留外壳，只替换 class 属性
+    assert!(result.code.starts_with("<!DOCTYPE html>"));
+    assert_eq!(result.class_map.len(), 4);
+    for (classes, name) in &result.class_map {
+        println!("{:<40} -> {}", classes, name);
+        assert!(result.code.contains(&format!("class=\"{}\"", name)), "{}", result.code);
+        assert!(result.css.contains(&format!(".{} {{", name)), "{}", result.css);
+    }
+    assert!(!result.code.contains("max-w-2xl"));
+
+    let link = &result.class_map["text-blue-500 hover:underline"];
+    assert!(result.css.contains(&format!(".{}:hover {{", link)));
+    assert!(result.css.contains("text-decoration-line: underline;"));
+}
@@ -0,0 +1,49 @@
+//! JSX → CSS Modules：把 className 中的 Tailwind 类替换为 `styles.xxx`，生成样式文件与类型声明
+//!
+//! 运行示例：
+//! ```bash
+//! cargo run --example jsx_css_modules -p headwind-transform
+//! ```
+
+use headwind_transform::{transform_jsx, NamingMode, OutputMode, TransformOptions};
+
+const SOURCE: &str = r#"export default function App() {
+    return (
+        <div className="flex items-center gap-4 p-4 md:p-8">
+            <button className="rounded bg-blue-500 px-4 py-2 text-white hover:bg-blue-700">
+                Save
+            </button>
+        </div>
+    );
+}
+"#;
+
+fn main() {
+    let options = TransformOptions {
+        naming_mode: NamingMode::CamelCase,
+        output_mode: OutputMode::css_modules(),
+        css_module_dts: true,
+        ..Default::default()
+    };
+    let result = transform_jsx(SOURCE, "src/App.tsx", options).expect("transform failed");
+
+    println!("=== App.tsx ===\n{}", result.code);
+    println!("=== {} ===\n{}", result.style_path.as_deref().unwrap_or("App.module.css"), result.css);
+
+    // 样式文件与源文件同名，import 注入在文件头部
+    assert!(result.code.contains("import styles from \"./App.module.css\""));
+    assert_eq!(result.class_map.len(), 2);
+    for name in result.class_map.values() {
+        assert!(result.code.contains(&format!("styles.{}", name)), "{}", result.code);
+        assert!(result.css.contains(&format!(".{} {{", name)), "{}", result.css);
+    }
+    // 类名全部移出源码
+    assert!(!result.code.contains("items-center"));
+    assert!(result.css.contains("@media (width >= 48rem)"));
+    assert!(result.css.contains("@media (hover: hover)"));
+
+    let (path, dts) = result.dts.expect("css_module_dts is enabled");
+    println!("=== {} ===\n{}", path, dts);
+    assert_eq!(path, "./App.module.css.d.ts");
+    assert!(dts.ends_with("export default styles;\n"));
+}
@@ -0,0 +1,43 @@
+//! 主题覆盖：配置文件中的 `[theme]` / `[darkTheme]` 与颜色变量输出
+//!
+//! 运行示例：
+//! ```bash
+//! cargo run --example theme_overrides -p headwind-transform
+//! ```
+
+use headwind_transform::{transform_html, HeadwindConfig};
+
+const CONFIG: &str = r##"
+namingMode = "readable"
+colorMode = "var"
+darkThemeSelector = ".dark"
+safelist = ["hidden"]
+
+[theme]
+"--color-blue-500" = "#1d4ed8"
+
+[darkTheme]
+"--color-white" = "#09090b"
+"--color-blue-500" = "#60a5fa"
+"##;
+
+const SOURCE: &str = r#"<div class="bg-white p-4"><a class="text-blue-500 hover:underline">Docs</a></div>"#;
+
+fn main() {
+    let config = HeadwindConfig::from_toml_str(CONFIG).expect("invalid config");
+
+    // 主题变量与 safelist：整个项目输出一次
+    let prelude = config.prelude_css();
+    println!("=== prelude.css ===\n{}", prelude);
+    assert!(prelude.starts_with(":root {\n  --color-blue-500: #1d4ed8;\n}\n"));
+    assert!(prelude.contains(".dark {\n  --color-white: #09090b;\n  --color-blue-500: #60a5fa;\n}"));
+    assert!(prelude.contains("display: none;"));
+
+    // 每个文件的输出引用主题变量，暗色块只包含用到的变量
+    let result = transform_html(SOURCE, config.to_transform_options()).expect("transform failed");
+    println!("=== index.html ===\n{}\n", result.code);
+    println!("=== styles.css ===\n{}", result.css);
+    assert!(result.css.contains("background: var(--color-white);"));
+    assert!(result.css.contains("color: var(--color-blue-500);"));
+    assert!(result.css.contains(".dark {\n"));
+}
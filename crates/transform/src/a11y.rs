@@ -0,0 +1,255 @@
+//! 无障碍检查
+//!
+//! 基于元素树检查与类用法相关的常见问题，结果作为警告诊断输出：
+//! - 可聚焦元素（`button`、`a`、`input` 等）使用 `hidden` 且没有任何变体让它重新显示
+//! - 文字颜色与背景色（取最近设置背景的祖先）的对比度低于 WCAG AA（普通文字 4.5:1，大字 3:1）
+//! - 字号小于 12px 的文字（如 `text-[8px]`）
+//!
+//! 颜色从调色板或任意值（hex、`rgb()` 等）计算；带透明度、CSS 变量或无法识别的类不参与对比度检查。
+
+use crate::element_tree::ElementNode;
+use crate::inheritance::utility_of;
+use headwind_core::{ColorMode, Declaration, Diagnostic};
+use headwind_tw_index::fallback::parse_color;
+use headwind_tw_index::palette::get_color;
+
+/// 可聚焦的元素
+const FOCUSABLE_ELEMENTS: &[&str] = &["a", "button", "input", "select", "textarea", "summary"];
+
+/// 小于该字号（px）的文字难以阅读
+const MIN_FONT_SIZE_PX: f64 = 12.0;
+
+/// 大字（24px 及以上，或粗体 18.66px 及以上）的工具类，对比度要求放宽到 3:1
+const LARGE_TEXT: &[&str] = &[
+    "text-2xl", "text-3xl", "text-4xl", "text-5xl", "text-6xl", "text-7xl", "text-8xl", "text-9xl",
+];
+
+/// 从祖先继承的状态：颜色为 None 表示未设置或无法计算
+#[derive(Clone, Default)]
+struct Context {
+    /// (RGB, 设置颜色的类)
+    color: Option<([u8; 3], String)>,
+    background: Option<([u8; 3], String)>,
+    large: bool,
+    bold: bool,
+}
+
+/// 检查元素树，`declarations` 返回单个不带变体的类的声明（无法识别时为 None）
+pub(crate) fn audit(
+    roots: &[ElementNode],
+    declarations: &dyn Fn(&str) -> Option<Vec<Declaration>>,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for root in roots {
+        walk(root, &Context::default(), declarations, &mut diagnostics);
+    }
+    diagnostics
+}
+
+fn walk(
+    node: &ElementNode,
+    inherited: &Context,
+    declarations: &dyn Fn(&str) -> Option<Vec<Declaration>>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let classes: Vec<_> = node
+        .classes
+        .split_whitespace()
+        .map(|class| (class, declarations(utility_of(class))))
+        .collect();
+    let sets = |decls: &Option<Vec<Declaration>>, property: &str| {
+        decls.iter().flatten().any(|d| d.property == property)
+    };
+
+    if node.classes.split_whitespace().any(|class| class == "hidden")
+        && FOCUSABLE_ELEMENTS.contains(&node.tag.as_str())
+        && !classes
+            .iter()
+            .any(|(class, decls)| *class != utility_of(class) && sets(decls, "display"))
+    {
+        diagnostics.push(Diagnostic::warning(format!(
+            "无障碍: 可聚焦元素 <{}> 使用 `hidden`，在所有断点和状态下都无法访问；只需视觉隐藏时改用 `sr-only`",
+            node.tag
+        )));
+    }
+
+    for (class, decls) in &classes {
+        let size = decls
+            .iter()
+            .flatten()
+            .filter(|d| d.property == "font-size")
+            .find_map(|d| font_size_px(&d.value));
+        if let Some(size) = size.filter(|size| *size < MIN_FONT_SIZE_PX) {
+            diagnostics.push(Diagnostic::warning(format!(
+                "无障碍: <{}> 的 `{}` 字号为 {}px，小于 {}px 难以阅读",
+                node.tag, class, size, MIN_FONT_SIZE_PX
+            )));
+        }
+    }
+
+    // 组件与动态类名的样式未知，其子树不沿用祖先的颜色
+    let opaque = node.tag.starts_with(|c: char| c.is_ascii_uppercase())
+        || classes.iter().any(|(_, decls)| decls.is_none());
+    let mut context = if opaque { Context::default() } else { inherited.clone() };
+    let mut sets_colors = false;
+    for (class, decls) in classes.iter().filter(|(class, _)| *class == utility_of(class)) {
+        if sets(decls, "color") {
+            context.color = resolve_color(class, "text-").map(|rgb| (rgb, class.to_string()));
+            sets_colors = true;
+        }
+        // `bg-*` 颜色输出为 `background` 简写
+        if (sets(decls, "background") || sets(decls, "background-color"))
+            && *class != "bg-transparent"
+        {
+            context.background = resolve_color(class, "bg-").map(|rgb| (rgb, class.to_string()));
+            sets_colors = true;
+        }
+        if sets(decls, "font-size") {
+            context.large = LARGE_TEXT.contains(class)
+                || decls.iter().flatten().any(|d| {
+                    d.property == "font-size" && font_size_px(&d.value).is_some_and(|px| px >= 24.0)
+                });
+        }
+        if sets(decls, "font-weight") {
+            context.bold = matches!(*class, "font-bold" | "font-extrabold" | "font-black");
+        }
+    }
+
+    if let (true, Some((fg, fg_class)), Some((bg, bg_class))) =
+        (sets_colors, &context.color, &context.background)
+    {
+        let large = context.large || (context.bold && has_class(node, "text-xl"));
+        let required = if large { 3.0 } else { 4.5 };
+        let ratio = contrast_ratio(*fg, *bg);
+        if ratio < required {
+            diagnostics.push(Diagnostic::warning(format!(
+                "无障碍: <{}> 的文字颜色 `{}` 与背景 `{}` 对比度为 {:.2}:1，低于 {}:1",
+                node.tag, fg_class, bg_class, ratio, required
+            )));
+        }
+    }
+
+    for child in &node.children {
+        walk(child, &context, declarations, diagnostics);
+    }
+}
+
+fn has_class(node: &ElementNode, class: &str) -> bool {
+    node.classes.split_whitespace().any(|c| c == class)
+}
+
+/// 颜色类的 RGB：调色板颜色或任意值（hex、`rgb()` 等，`_` 视为空格）；带透明度等无法计算时为 None
+fn resolve_color(class: &str, prefix: &str) -> Option<[u8; 3]> {
+    let name = class.strip_prefix(prefix)?;
+    if name.contains('/') {
+        return None;
+    }
+    let (rgb, alpha) = match name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+        Some(arbitrary) => parse_color(&arbitrary.replace('_', " "))?,
+        None => parse_color(&get_color(name, ColorMode::Hex)?)?,
+    };
+    (alpha >= 1.0).then_some(rgb)
+}
+
+/// px / rem 字号换算为 px（按 16px 根字号），其他单位返回 None
+fn font_size_px(value: &str) -> Option<f64> {
+    if let Some(px) = value.strip_suffix("px") {
+        return px.trim().parse().ok();
+    }
+    let rem: f64 = value.strip_suffix("rem")?.trim().parse().ok()?;
+    Some(rem * 16.0)
+}
+
+/// WCAG 2 对比度
+fn contrast_ratio(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (light, dark) = if la > lb { (la, lb) } else { (lb, la) };
+    (light + 0.05) / (dark + 0.05)
+}
+
+fn relative_luminance(rgb: [u8; 3]) -> f64 {
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(rgb[0]) + 0.7152 * channel(rgb[1]) + 0.0722 * channel(rgb[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::build_html_element_tree;
+
+    fn declarations(class: &str) -> Option<Vec<Declaration>> {
+        let decl = |property: &str, value: &str| vec![Declaration::new(property, value)];
+        Some(match class {
+            "hidden" => decl("display", "none"),
+            "block" => decl("display", "block"),
+            "text-[8px]" => decl("font-size", "8px"),
+            "text-sm" => decl("font-size", "0.875rem"),
+            "text-3xl" => decl("font-size", "1.875rem"),
+            "bg-white" | "bg-gray-200" | "bg-gray-900" | "bg-transparent" => {
+                decl("background", "…")
+            }
+            "text-gray-300" | "text-gray-500" | "text-gray-900" | "text-white" => {
+                decl("color", "…")
+            }
+            _ => return None,
+        })
+    }
+
+    fn messages(html: &str) -> Vec<String> {
+        audit(&build_html_element_tree(html), &declarations)
+            .into_iter()
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_hidden_focusable() {
+        let found = messages(r#"<button class="hidden">Menu</button><div class="hidden"></div>"#);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].contains("<button>"), "{:?}", found);
+        assert!(messages(r#"<button class="hidden md:block">Menu</button>"#).is_empty());
+    }
+
+    #[test]
+    fn test_tiny_text() {
+        let found = messages(r#"<p class="text-[8px]">a</p><p class="text-sm">b</p>"#);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].contains("`text-[8px]` 字号为 8px"), "{:?}", found);
+    }
+
+    #[test]
+    fn test_contrast() {
+        // 背景来自祖先；透明背景不遮挡祖先的背景
+        let found = messages(
+            r#"<div class="bg-white"><p class="text-gray-300">a</p><p class="text-gray-900">b</p><span class="bg-transparent text-gray-300">c</span></div>"#,
+        );
+        assert_eq!(found.len(), 2, "{:?}", found);
+        assert!(found[0].contains("`text-gray-300` 与背景 `bg-white`"), "{:?}", found);
+
+        // 大字只需 3:1；不知道背景时不检查
+        assert!(messages(r#"<div class="bg-gray-200"><h1 class="text-3xl text-gray-500">a</h1></div>"#).is_empty());
+        assert_eq!(messages(r#"<div class="bg-gray-200"><p class="text-gray-500">a</p></div>"#).len(), 1);
+        assert!(messages(r#"<p class="text-gray-300">a</p>"#).is_empty());
+        assert!(messages(r#"<div class="bg-gray-900"><p class="text-white">a</p></div>"#).is_empty());
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio([0, 0, 0], [255, 255, 255]) - 21.0).abs() < 0.01);
+        assert_eq!(resolve_color("bg-white", "bg-"), Some([255, 255, 255]));
+        assert_eq!(resolve_color("bg-[#fff]", "bg-"), Some([255, 255, 255]));
+        assert_eq!(resolve_color("bg-[#ffff]", "bg-"), Some([255, 255, 255]));
+        assert_eq!(resolve_color("text-[#111827ff]", "text-"), Some([17, 24, 39]));
+        assert_eq!(resolve_color("text-[rgb(17_24_39)]", "text-"), Some([17, 24, 39]));
+        // 半透明颜色的实际颜色取决于背后的内容
+        assert_eq!(resolve_color("bg-[#00000080]", "bg-"), None);
+        assert_eq!(resolve_color("bg-[#12345]", "bg-"), None);
+    }
+}
@@ -0,0 +1,124 @@
+//! 类名别名
+//!
+//! 许多项目用 `@apply` 定义自己的简写（如 `.btn { @apply px-4 py-2 rounded-md; }`），
+//! 迁移时希望源码中的 `btn` 照常可用。别名在命名与生成 CSS 之前展开：
+//! `"btn mt-2"` 按 `"px-4 py-2 rounded-md mt-2"` 转换，类名映射的键仍是源码中的原始字符串。
+//! 带变体的别名逐个套用变体（`md:btn` → `md:px-4 md:py-2 md:rounded-md`），
+//! 别名的展开中也可以引用其他别名。
+
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// 已展开的别名表：别名 → 工具类
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClassAliases {
+    expansions: HashMap<String, Vec<String>>,
+}
+
+impl ClassAliases {
+    /// 由配置的别名（别名 → 空格分隔的类）构建，展开嵌套的别名；循环引用的别名按普通类保留
+    pub(crate) fn new(aliases: &IndexMap<String, String>) -> Self {
+        let mut expansions = HashMap::new();
+        for alias in aliases.keys() {
+            let mut classes = Vec::new();
+            let mut visiting = HashSet::from([alias.as_str()]);
+            expand_into(aliases, &aliases[alias], &mut visiting, &mut classes);
+            expansions.insert(alias.clone(), classes);
+        }
+        Self { expansions }
+    }
+
+    /// 展开类字符串中的别名，重复的类只保留第一次出现；没有别名时借用原字符串
+    ///
+    /// ```text
+    /// btn = "px-4 py-2"
+    /// "btn hover:btn px-4" → "px-4 py-2 hover:px-4 hover:py-2"
+    /// ```
+    pub(crate) fn expand<'a>(&self, classes: &'a str) -> Cow<'a, str> {
+        let has_alias = classes
+            .split_whitespace()
+            .any(|class| self.expansions.contains_key(split_variants(class).1));
+        if !has_alias {
+            return Cow::Borrowed(classes);
+        }
+        let mut seen = HashSet::new();
+        let mut expanded: Vec<String> = Vec::new();
+        for class in classes.split_whitespace() {
+            let (variants, base) = split_variants(class);
+            let classes = match self.expansions.get(base) {
+                Some(utilities) => utilities
+                    .iter()
+                    .map(|utility| format!("{}{}", variants, utility))
+                    .collect(),
+                None => vec![class.to_string()],
+            };
+            for class in classes {
+                if seen.insert(class.clone()) {
+                    expanded.push(class);
+                }
+            }
+        }
+        Cow::Owned(expanded.join(" "))
+    }
+}
+
+/// 把 `classes` 中的类追加到 `out`，其中的别名递归展开
+fn expand_into<'a>(
+    aliases: &'a IndexMap<String, String>,
+    classes: &'a str,
+    visiting: &mut HashSet<&'a str>,
+    out: &mut Vec<String>,
+) {
+    for class in classes.split_whitespace() {
+        let (variants, base) = split_variants(class);
+        match aliases.get_key_value(base) {
+            Some((alias, nested)) if visiting.insert(alias.as_str()) => {
+                let mut inner = Vec::new();
+                expand_into(aliases, nested, visiting, &mut inner);
+                visiting.remove(alias.as_str());
+                out.extend(inner.into_iter().map(|utility| format!("{}{}", variants, utility)));
+            }
+            _ => out.push(class.to_string()),
+        }
+    }
+}
+
+/// `md:hover:btn` → (`md:hover:`, `btn`)；方括号与圆括号内的 `:` 不算变体分隔符
+fn split_variants(class: &str) -> (&str, &str) {
+    let mut depth = 0i32;
+    let mut split = 0;
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ':' if depth == 0 => split = i + 1,
+            _ => {}
+        }
+    }
+    class.split_at(split)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_aliases() {
+        let aliases = ClassAliases::new(&IndexMap::from([
+            ("btn".to_string(), "px-4 py-2 rounded-md".to_string()),
+            ("btn-primary".to_string(), "btn bg-blue-500 hover:bg-blue-600".to_string()),
+            ("loop-a".to_string(), "loop-b m-1".to_string()),
+            ("loop-b".to_string(), "loop-a m-2".to_string()),
+        ]));
+        assert!(matches!(aliases.expand("p-4 text-center"), Cow::Borrowed(_)));
+        assert_eq!(aliases.expand("btn mt-2 px-4"), "px-4 py-2 rounded-md mt-2");
+        assert_eq!(
+            aliases.expand("md:btn-primary"),
+            "md:px-4 md:py-2 md:rounded-md md:bg-blue-500 md:hover:bg-blue-600"
+        );
+        assert_eq!(aliases.expand("bg-[url(a:btn)]"), "bg-[url(a:btn)]");
+        // 循环引用的别名按普通类保留
+        assert_eq!(aliases.expand("loop-a"), "loop-a m-2 m-1");
+    }
+}
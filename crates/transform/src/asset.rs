@@ -0,0 +1,76 @@
+//! 样式文件命名与内容哈希
+//!
+//! 样式文件路径（显式的 `import_path` 或 `ImportOptions::file_name` 模板）可以包含占位符：
+//! `[name]` 为源文件名（不含扩展名），`[contenthash]` 为生成 CSS 的内容哈希（默认 8 位，
+//! `[contenthash:12]` 指定位数）。CSS 变化时路径随之变化，构建工具可以把样式文件作为不可变资源
+//! 长期缓存；计算出的哈希与最终路径见 `TransformResult::css_hash` / `TransformResult::style_path`。
+
+/// `[contenthash]` 的默认位数
+pub const DEFAULT_HASH_LENGTH: usize = 8;
+
+/// 替换路径中的 `[name]`：`./[name].[contenthash].css` + `src/App.tsx` → `./App.[contenthash].css`
+pub fn apply_name(path: &str, filename: &str) -> String {
+    let base = filename.rsplit('/').next().unwrap_or(filename);
+    let stem = base.rsplit_once('.').map(|(name, _)| name).unwrap_or(base);
+    path.replace("[name]", stem)
+}
+
+/// 路径是否包含 `[contenthash]` 占位符
+pub fn has_content_hash(path: &str) -> bool {
+    path.contains("[contenthash]") || path.contains("[contenthash:")
+}
+
+/// 替换路径中的 `[contenthash]` / `[contenthash:N]`，返回新路径与第一个占位符处的哈希
+///
+/// 哈希为 CSS 的 blake3 十六进制摘要前缀，位数限制在 1..=64；没有占位符时不计算哈希。
+///
+/// ```
+/// use headwind_transform::asset::apply_content_hash;
+///
+/// let (path, hash) = apply_content_hash("./App.[contenthash].css", ".a { color: red; }\n");
+/// let hash = hash.unwrap();
+/// assert_eq!(hash.len(), 8);
+/// assert_eq!(path, format!("./App.{}.css", hash));
+///
+/// let (path, long) = apply_content_hash("./App.[contenthash:12].css", ".a { color: red; }\n");
+/// assert!(long.unwrap().starts_with(&hash));
+/// assert_eq!(path.len(), "./App..css".len() + 12);
+///
+/// assert_eq!(apply_content_hash("./App.css", ""), ("./App.css".to_string(), None));
+/// ```
+pub fn apply_content_hash(path: &str, css: &str) -> (String, Option<String>) {
+    if !has_content_hash(path) {
+        return (path.to_string(), None);
+    }
+    let digest = blake3::hash(css.as_bytes()).to_hex();
+    let mut out = String::with_capacity(path.len());
+    let mut first = None;
+    let mut rest = path;
+    while let Some(start) = rest.find("[contenthash") {
+        let tail = &rest[start + "[contenthash".len()..];
+        let placeholder = match tail.split_once(']') {
+            Some(("", after)) => Some((DEFAULT_HASH_LENGTH, after)),
+            Some((length, after)) => length
+                .strip_prefix(':')
+                .and_then(|length| length.parse::<usize>().ok())
+                .map(|length| (length.clamp(1, digest.len()), after)),
+            None => None,
+        };
+        out.push_str(&rest[..start]);
+        match placeholder {
+            Some((length, after)) => {
+                let hash = &digest[..length];
+                first.get_or_insert_with(|| hash.to_string());
+                out.push_str(hash);
+                rest = after;
+            }
+            // 不完整的占位符原样保留
+            None => {
+                out.push('[');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    (out, first)
+}
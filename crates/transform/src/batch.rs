@@ -0,0 +1,214 @@
+//! 批量转换
+//!
+//! 编辑器集成一次迁移大量文件时使用：每处理完一个文件回调一次进度，
+//! 其他线程可以随时通过 [`CancelToken`] 取消，正在转换的文件完成后即停止，
+//! 已处理文件的结果照常返回。单个文件失败只记录在结果中，不会中断批处理。
+
+use crate::config::HeadwindConfig;
+use crate::merge::{merge_outputs, MergedOutput};
+use crate::session::ConfigSession;
+use crate::TransformResult;
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// 取消标记，克隆后可交给其他线程
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 请求取消：正在转换的文件完成后停止
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// 单个文件的处理结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// 已转换
+    Transformed,
+    /// 被配置的 include / exclude 排除
+    Excluded,
+    /// 读取或转换失败，错误信息见 [`BatchResult::files`]
+    Failed,
+}
+
+/// 处理完一个文件时的进度
+#[derive(Debug, Clone, Copy)]
+pub struct BatchProgress<'a> {
+    /// 刚处理完的文件
+    pub path: &'a Path,
+    /// 该文件的处理结果
+    pub status: FileStatus,
+    /// 已处理的文件数（含当前文件）
+    pub completed: usize,
+    /// 文件总数
+    pub total: usize,
+}
+
+/// 批量转换的结果
+#[derive(Default)]
+pub struct BatchResult {
+    /// 已处理的文件 → 转换结果（被排除时为 `Ok(None)`），按处理顺序
+    pub files: IndexMap<PathBuf, Result<Option<TransformResult>, String>>,
+    /// 是否因取消而提前结束；此时 `files` 只包含取消前处理的文件
+    pub cancelled: bool,
+}
+
+impl BatchResult {
+    /// 转换失败的文件及错误信息
+    pub fn errors(&self) -> impl Iterator<Item = (&Path, &str)> {
+        self.files.iter().filter_map(|(path, result)| match result {
+            Err(error) => Some((path.as_path(), error.as_str())),
+            Ok(_) => None,
+        })
+    }
+
+    /// 按文件路径顺序合并已转换文件的类名映射与 CSS（见 [`merge_outputs`]）
+    pub fn merged(&self) -> MergedOutput {
+        merge_outputs(self.files.iter().filter_map(|(path, result)| {
+            let result = result.as_ref().ok()?.as_ref()?;
+            Some((path.as_path(), result))
+        }))
+    }
+}
+
+impl ConfigSession {
+    /// 依次转换 `files`，每处理完一个文件调用一次 `progress`
+    ///
+    /// 每个文件开始前检查 `cancel`，已取消时停止并把 `cancelled` 置为 true。
+    pub fn transform_many<P: AsRef<Path>>(
+        &mut self,
+        files: &[P],
+        mut progress: impl FnMut(&BatchProgress),
+        cancel: &CancelToken,
+    ) -> BatchResult {
+        let mut batch = BatchResult::default();
+        for (i, path) in files.iter().enumerate() {
+            if cancel.is_cancelled() {
+                batch.cancelled = true;
+                break;
+            }
+            let path = path.as_ref();
+            let result = self.transform_file(path);
+            let status = match &result {
+                Ok(Some(_)) => FileStatus::Transformed,
+                Ok(None) => FileStatus::Excluded,
+                Err(_) => FileStatus::Failed,
+            };
+            batch.files.insert(path.to_path_buf(), result);
+            progress(&BatchProgress {
+                path,
+                status,
+                completed: i + 1,
+                total: files.len(),
+            });
+        }
+        batch
+    }
+}
+
+/// 在新会话中批量转换文件，`overrides` 对所有文件生效（见 [`ConfigSession::transform_many`]）
+///
+/// ```
+/// use headwind_transform::{transform_many, CancelToken, HeadwindConfig};
+///
+/// let dir = std::env::temp_dir().join("headwind-doc-transform-many");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let app = dir.join("App.tsx");
+/// std::fs::write(&app, "export const App = () => <div className=\"p-4\" />;\n").unwrap();
+/// let missing = dir.join("Missing.tsx");
+///
+/// let cancel = CancelToken::new();
+/// let mut completed = Vec::new();
+/// let batch = transform_many(
+///     &[&app, &missing],
+///     HeadwindConfig::default(),
+///     |p| completed.push((p.completed, p.total)),
+///     &cancel,
+/// )
+/// .unwrap();
+/// assert_eq!(completed, [(1, 2), (2, 2)]);
+///
+/// // 单个文件失败只记录在结果中
+/// let result = batch.files[&app].as_ref().unwrap().as_ref().unwrap();
+/// assert!(result.css.contains("padding"));
+/// let errors: Vec<_> = batch.errors().map(|(path, _)| path).collect();
+/// assert_eq!(errors, [missing.as_path()]);
+/// ```
+pub fn transform_many<P: AsRef<Path>>(
+    files: &[P],
+    overrides: HeadwindConfig,
+    progress: impl FnMut(&BatchProgress),
+    cancel: &CancelToken,
+) -> Result<BatchResult, String> {
+    let mut session = ConfigSession::new(overrides)?;
+    Ok(session.transform_many(files, progress, cancel))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_errors_and_cancellation() {
+        let root = std::env::temp_dir().join(format!("headwind-batch-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join("headwind.toml"),
+            "root = true\nexclude = [\"skip.html\"]\n",
+        )
+        .unwrap();
+        for name in ["a.html", "skip.html", "c.html"] {
+            std::fs::write(root.join(name), "<p class=\"p-4\">x</p>\n").unwrap();
+        }
+        let files = [
+            root.join("a.html"),
+            root.join("missing.html"),
+            root.join("skip.html"),
+            root.join("c.html"),
+        ];
+
+        let mut seen = Vec::new();
+        let batch = transform_many(
+            &files,
+            HeadwindConfig::default(),
+            |p| seen.push((p.completed, p.total, p.status)),
+            &CancelToken::new(),
+        )
+        .unwrap();
+        assert!(!batch.cancelled);
+        assert_eq!(
+            seen,
+            [
+                (1, 4, FileStatus::Transformed),
+                (2, 4, FileStatus::Failed),
+                (3, 4, FileStatus::Excluded),
+                (4, 4, FileStatus::Transformed),
+            ]
+        );
+        let errors: Vec<&Path> = batch.errors().map(|(path, _)| path).collect();
+        assert_eq!(errors, [files[1].as_path()]);
+        assert_eq!(batch.merged().class_map.len(), 1);
+
+        // 回调中取消：当前文件照常完成，后续文件不再处理
+        let cancel = CancelToken::new();
+        let mut session = ConfigSession::new(HeadwindConfig::default()).unwrap();
+        let batch = session.transform_many(&files, |_| cancel.cancel(), &cancel);
+        assert!(batch.cancelled);
+        assert_eq!(batch.files.len(), 1);
+        assert!(matches!(batch.files[&files[0]], Ok(Some(_))));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,178 @@
+//! 按层叠角色拆分 CSS
+//!
+//! 生成的 CSS 由三部分按顺序组成：主题变量（`:root` 及暗色主题块）、基础样式（preflight）
+//! 与工具类规则。开启 `split_css` 时三部分另外以独立字段返回，构建工具可以分别控制加载顺序与缓存：
+//! 变量与基础样式很少变化，工具类规则随源码变化。
+
+use serde::Serialize;
+
+/// 精简的 preflight：与 Tailwind v4 的基础样式一致，主题变量替换为具体值
+pub const PREFLIGHT: &str = r#"*,
+::after,
+::before,
+::backdrop,
+::file-selector-button {
+  box-sizing: border-box;
+  margin: 0;
+  padding: 0;
+  border: 0 solid;
+}
+
+html,
+:host {
+  line-height: 1.5;
+  -webkit-text-size-adjust: 100%;
+  tab-size: 4;
+  font-family: ui-sans-serif, system-ui, sans-serif, "Apple Color Emoji", "Segoe UI Emoji", "Segoe UI Symbol", "Noto Color Emoji";
+  -webkit-tap-highlight-color: transparent;
+}
+
+hr {
+  height: 0;
+  color: inherit;
+  border-top-width: 1px;
+}
+
+abbr:where([title]) {
+  text-decoration: underline dotted;
+}
+
+h1,
+h2,
+h3,
+h4,
+h5,
+h6 {
+  font-size: inherit;
+  font-weight: inherit;
+}
+
+a {
+  color: inherit;
+  text-decoration: inherit;
+}
+
+b,
+strong {
+  font-weight: bolder;
+}
+
+code,
+kbd,
+samp,
+pre {
+  font-family: ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, "Liberation Mono", "Courier New", monospace;
+  font-size: 1em;
+}
+
+small {
+  font-size: 80%;
+}
+
+table {
+  text-indent: 0;
+  border-color: inherit;
+  border-collapse: collapse;
+}
+
+ol,
+ul,
+menu {
+  list-style: none;
+}
+
+img,
+svg,
+video,
+canvas,
+audio,
+iframe,
+embed,
+object {
+  display: block;
+  vertical-align: middle;
+}
+
+img,
+video {
+  max-width: 100%;
+  height: auto;
+}
+
+button,
+input,
+select,
+optgroup,
+textarea,
+::file-selector-button {
+  font: inherit;
+  font-feature-settings: inherit;
+  font-variation-settings: inherit;
+  letter-spacing: inherit;
+  color: inherit;
+  border-radius: 0;
+  background-color: transparent;
+  opacity: 1;
+}
+
+::placeholder {
+  opacity: 1;
+}
+
+textarea {
+  resize: vertical;
+}
+
+button,
+input:where([type="button"], [type="reset"], [type="submit"]),
+::file-selector-button {
+  appearance: button;
+}
+
+[hidden]:where(:not([hidden="until-found"])) {
+  display: none !important;
+}"#;
+
+/// 按层叠角色拆分的 CSS，见 `TransformOptions::split_css`
+///
+/// 三部分依次拼接（非空部分之间以换行分隔）即为 `TransformResult::css`。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CssOutputs {
+    /// 主题变量：`:root` 定义、oklch 增强块与暗色主题块
+    pub variables: String,
+    /// 基础样式（开启 `preflight` 时为 preflight），否则为空
+    pub base: String,
+    /// 生成的工具类规则
+    pub utilities: String,
+}
+
+impl CssOutputs {
+    /// 按顺序拼接三部分
+    ///
+    /// ```
+    /// use headwind_transform::CssOutputs;
+    ///
+    /// let outputs = CssOutputs {
+    ///     variables: ":root {\n  --spacing: 0.25rem;\n}".to_string(),
+    ///     base: String::new(),
+    ///     utilities: ".a {\n  padding: calc(var(--spacing) * 4);\n}\n".to_string(),
+    /// };
+    /// assert_eq!(
+    ///     outputs.combined(),
+    ///     ":root {\n  --spacing: 0.25rem;\n}\n.a {\n  padding: calc(var(--spacing) * 4);\n}\n"
+    /// );
+    /// ```
+    pub fn combined(&self) -> String {
+        let mut css = String::with_capacity(
+            self.variables.len() + self.base.len() + self.utilities.len() + 2,
+        );
+        for part in [&self.variables, &self.base] {
+            if !part.is_empty() {
+                css.push_str(part);
+                css.push('\n');
+            }
+        }
+        css.push_str(&self.utilities);
+        css
+    }
+}
@@ -1,23 +1,111 @@
-use headwind_core::{ColorMode, CssVariableMode, NamingMode, UnknownClassMode};
-use headwind_tw_index::naming::{create_naming_strategy, NamingStrategy};
-use headwind_tw_index::Bundler;
+use crate::alias::ClassAliases;
+use crate::cascade::{CssOutputs, PREFLIGHT};
+use crate::dedup::{CssDedup, RuleGroups, SIGNATURE_NAME};
+use crate::hooks::{ClassHook, CollectedClass, RuleHook};
+use crate::naming::{class_set_key, NameCollision, NamingStats};
+use crate::profile::{Phase, PhaseTimer, Timings};
+use crate::react_native::{convert_declarations, NativeStyle};
+use crate::theme::DarkTheme;
+use crate::{ClassOverride, ProseClassMode};
+use headwind_core::{
+    BreakpointStyle, ColorMode, ContainerStyle, CssVariableMode, CssVariableOverrides, Declaration,
+    Diagnostic, HashVersion, NamingMode, TailwindVersion, Targets, UnitConversion, UnknownClassMode,
+};
+use headwind_tw_index::naming::{
+    create_naming_strategy, create_versioned_naming_strategy, NamingStrategy, ScopedNaming,
+    DEFAULT_HASH_LENGTH, MAX_HASH_LENGTH,
+};
+use headwind_tw_index::{
+    Bundler, ClassContext, ConversionCache, CustomUtility, CustomVariant, TailwindIndex,
+};
 use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
 
 /// 类名收集器 —— 收集源码中所有 Tailwind 类字符串，
 /// 生成唯一类名，并产出对应的 CSS。
 pub struct ClassCollector {
     bundler: Bundler,
+    naming_mode: NamingMode,
     naming: Box<dyn NamingStrategy>,
+    /// Hash 命名的最短 hash 位数，冲突时自动加长
+    hash_length: usize,
+    /// 生成名 -> 类组合（排序去重），用于发现不同类组合得到同一名称的冲突
+    allocated: IndexMap<String, String>,
+    /// 已消解的生成名冲突
+    collisions: Vec<NameCollision>,
     /// 原始类字符串 -> 生成的类名
     class_map: IndexMap<String, String>,
-    /// 所有生成的 CSS 片段
-    css_entries: Vec<String>,
+    /// 原始类字符串 -> 生成名与原样保留的类（`class_map` 的值由两者拼接而成）
+    class_parts: HashMap<String, ClassParts>,
+    /// 原始类字符串 -> 出现次数（每处元素或字符串各计一次）
+    class_usage: IndexMap<String, usize>,
+    /// 尚未 flush 的 CSS：各生成名的规则依次追加到同一缓冲区，块之间空一行
+    css: String,
+    /// 产出过 CSS 的生成名数量
+    css_entries: usize,
+    /// 已 flush 的 CSS 字节数
+    flushed_bytes: usize,
+    /// 相同规则体的去重方式
+    css_dedup: CssDedup,
+    /// GroupSelectors 模式下尚未 flush 的规则块（此时 `css` 始终为空）
+    rule_groups: RuleGroups,
+    /// ShareClass 模式：规则签名 → 最先产出该规则的生成名
+    signatures: HashMap<String, String>,
+    /// 已 flush 的规则引用到的主题变量，`:root` 仍需定义它们
+    flushed_variables: BTreeSet<String>,
     /// CSS 缩进
     indent: String,
     /// CSS 变量模式
     css_variables: CssVariableMode,
+    /// 按主题类别覆盖 CSS 变量模式
+    css_variable_overrides: CssVariableOverrides,
     /// 未知类名处理模式
     unknown_class_mode: UnknownClassMode,
+    /// typography 插件 `prose` 系列类的处理方式
+    prose_mode: ProseClassMode,
+    /// 已报告过的 prose 类，每个类只记录一条诊断
+    reported_prose: HashSet<String>,
+    /// 无法转换的类 → 修复建议，按首次出现顺序，每个类只记录一条诊断
+    unconverted: IndexMap<String, Option<String>>,
+    /// 颜色输出模式（Var 模式下颜色也以变量引用输出）
+    color_mode: ColorMode,
+    /// 暗色主题变量，在 `:root` 之后输出覆盖块
+    dark_theme: DarkTheme,
+    /// 是否在变量与工具类规则之间输出 preflight
+    preflight: bool,
+    /// 是否把间距倍数解析为具体 rem 值；否则输出 `calc(var(--spacing) * n)` 并在 `:root` 中定义 `--spacing`
+    resolve_spacing: bool,
+    /// 项目中已存在的类名，生成名不得与之冲突
+    reserved_names: HashSet<String>,
+    /// 手动固定的生成名：完整类字符串 → 生成名
+    pinned_names: HashMap<String, String>,
+    /// 是否合并连续空白、删除重复的类后再命名（`class_map` 的键也取规范形式）
+    normalize_classes: bool,
+    /// 类名别名，命名与生成 CSS 之前展开
+    class_aliases: ClassAliases,
+    /// 命名、生成 CSS 之前调用的类字符串钩子
+    class_hook: Option<ClassHook>,
+    /// 每条 CSS 规则写出前调用的钩子
+    rule_hook: Option<RuleHook>,
+    /// 收集过程中产生的诊断信息
+    diagnostics: Vec<Diagnostic>,
+    /// 属性选择器模式的属性名（如 `data-hw`）。Some 时 CSS 选择器为 `[data-hw="c_xxx"]`
+    data_attribute: Option<String>,
+    /// React Native 模式：生成名 -> 样式对象。Some 时不产出 CSS
+    native_styles: Option<IndexMap<String, NativeStyle>>,
+    /// CSS Modules 模式：`.group` / `.peer` 需包裹为 `:global(...)`，避免被模块化改名
+    css_modules: bool,
+    /// 是否在每个规则块前加来源注释
+    css_comments: bool,
+    /// 来源文件名，写入规则块注释
+    source: Option<String>,
+    /// 正在处理的元素标签名，写入规则块注释
+    element: Option<String>,
+    /// 类名转换累计耗时（仅 `tracing` feature 下记录）
+    timings: Timings,
 }
 
 impl ClassCollector {
@@ -37,104 +125,769 @@ impl ClassCollector {
         .with_color_mix(color_mix);
         Self {
             bundler,
+            naming_mode,
             naming,
+            hash_length: DEFAULT_HASH_LENGTH,
+            allocated: IndexMap::new(),
+            collisions: Vec::new(),
             class_map: IndexMap::new(),
-            css_entries: Vec::new(),
+            class_parts: HashMap::new(),
+            class_usage: IndexMap::new(),
+            css: String::new(),
+            css_entries: 0,
+            flushed_bytes: 0,
+            css_dedup: CssDedup::None,
+            rule_groups: RuleGroups::default(),
+            signatures: HashMap::new(),
+            flushed_variables: BTreeSet::new(),
             indent: "  ".to_string(),
             css_variables,
+            css_variable_overrides: CssVariableOverrides::default(),
             unknown_class_mode,
+            prose_mode: ProseClassMode::Unknown,
+            reported_prose: HashSet::new(),
+            unconverted: IndexMap::new(),
+            color_mode,
+            dark_theme: DarkTheme::default(),
+            preflight: false,
+            resolve_spacing: false,
+            reserved_names: HashSet::new(),
+            pinned_names: HashMap::new(),
+            normalize_classes: true,
+            class_aliases: ClassAliases::default(),
+            class_hook: None,
+            rule_hook: None,
+            diagnostics: Vec::new(),
+            data_attribute: None,
+            native_styles: None,
+            css_modules: false,
+            css_comments: false,
+            source: None,
+            element: None,
+            timings: Timings::default(),
+        }
+    }
+
+    /// 生成时把 `calc(var(--spacing) * n)` 解析为具体 rem 值，不再依赖 `--spacing` 定义
+    pub fn with_resolve_spacing(mut self, enabled: bool) -> Self {
+        self.bundler = self.bundler.with_resolve_spacing(enabled);
+        self.resolve_spacing = enabled;
+        self
+    }
+
+    /// 按主题类别覆盖 CSS 变量模式，未设置的类别沿用 `css_variables`
+    pub fn with_css_variable_overrides(mut self, overrides: CssVariableOverrides) -> Self {
+        self.bundler = self.bundler.with_variable_overrides(overrides);
+        self.css_variable_overrides = overrides;
+        self
+    }
+
+    /// 设置暗色主题变量，`:root` 之后追加输出中用到的变量的暗色值
+    pub fn with_dark_theme(mut self, theme: DarkTheme) -> Self {
+        self.dark_theme = theme;
+        self
+    }
+
+    /// 在 `:root` 与工具类规则之间输出 preflight 基础样式
+    pub fn with_preflight(mut self, enabled: bool) -> Self {
+        self.preflight = enabled;
+        self
+    }
+
+    /// 设置断点与容器查询的单位和写法
+    pub fn with_breakpoints(mut self, style: BreakpointStyle) -> Self {
+        self.bundler = self.bundler.with_breakpoints(style);
+        self
+    }
+
+    /// 设置 `container` 的居中与水平内边距
+    pub fn with_container(mut self, style: ContainerStyle) -> Self {
+        self.bundler = self.bundler.with_container(style);
+        self
+    }
+
+    /// 设置输出值的 px / rem 换算与根字号
+    pub fn with_unit_conversion(mut self, conversion: UnitConversion, root_font_size: u32) -> Self {
+        self.bundler = self.bundler.with_unit_conversion(conversion, root_font_size);
+        self
+    }
+
+    /// 设置目标浏览器，生成的 CSS 按需补充厂商前缀与颜色回退值
+    pub fn with_targets(mut self, targets: Targets) -> Self {
+        self.bundler = self.bundler.with_targets(targets);
+        self
+    }
+
+    /// 设置 typography 插件 `prose` 系列类的处理方式（默认按未知类处理）
+    pub fn with_prose_mode(mut self, mode: ProseClassMode) -> Self {
+        self.prose_mode = mode;
+        self
+    }
+
+    /// 使用共享的类级转换缓存
+    pub fn with_conversion_cache(mut self, cache: Arc<ConversionCache>) -> Self {
+        self.bundler = self.bundler.with_cache(cache);
+        self
+    }
+
+    /// 设置目标 Tailwind 版本（影响 shadow、rounded、blur 等尺寸表）
+    pub fn with_target_version(mut self, version: TailwindVersion) -> Self {
+        self.bundler = self.bundler.with_target_version(version);
+        self
+    }
+
+    /// 设置回退索引，规则无法识别的类到索引中查找
+    pub fn with_fallback_index(mut self, index: Arc<TailwindIndex>) -> Self {
+        self.bundler = self.bundler.with_fallback_index(index);
+        self
+    }
+
+    /// 生成的 CSS 作为 CSS Module 使用
+    pub fn with_css_modules(mut self) -> Self {
+        self.css_modules = true;
+        self
+    }
+
+    /// 每个规则块前加注释，标明来源文件、元素和原始类字符串，如 `/* App.tsx <div> "p-4" */`
+    /// `source` 为来源文件名，没有文件名时（如 `transform_html`）省略
+    pub fn with_css_comments(mut self, source: Option<&str>) -> Self {
+        self.css_comments = true;
+        self.source = source.map(str::to_string);
+        self
+    }
+
+    /// 设置正在处理的元素标签名（写入规则块注释），返回之前的值，嵌套元素处理完后用于恢复
+    pub fn set_element(&mut self, element: Option<String>) -> Option<String> {
+        std::mem::replace(&mut self.element, element)
+    }
+
+    /// 产出 React Native 样式对象而非 CSS（实验性）
+    pub fn with_react_native(mut self) -> Self {
+        self.native_styles = Some(IndexMap::new());
+        self
+    }
+
+    /// React Native 模式下收集的样式对象（生成名 -> 样式）
+    pub fn native_styles(&self) -> Option<&IndexMap<String, NativeStyle>> {
+        self.native_styles.as_ref()
+    }
+
+    /// 使用属性选择器 `[attr="c_xxx"]` 代替类选择器生成 CSS
+    pub fn with_data_attribute(mut self, attr: impl Into<String>) -> Self {
+        self.data_attribute = Some(attr.into());
+        self
+    }
+
+    /// 设置 Hash 命名的算法版本与盐（其他命名模式不受影响）
+    pub fn with_hash_options(mut self, version: HashVersion, salt: &str) -> Self {
+        self.naming = create_versioned_naming_strategy(self.naming_mode, version, salt);
+        self
+    }
+
+    /// 设置 Hash 命名的最短 hash 位数（默认 12，范围 1-64）；
+    /// 不同类组合得到同一名称时，后者的 hash 每次加长 2 位直到不再冲突
+    pub fn with_hash_length(mut self, length: usize) -> Self {
+        self.hash_length = length.clamp(1, MAX_HASH_LENGTH);
+        self
+    }
+
+    /// 将生成名限定在某个作用域（通常是文件路径）内：
+    /// 相同类组合在不同作用域中得到不同名称，需在 `with_hash_options` 之后调用
+    pub fn with_name_scope(self, scope: &str) -> Self {
+        Self {
+            naming: Box::new(ScopedNaming::new(self.naming, scope)),
+            ..self
+        }
+    }
+
+    /// 设置项目中已存在的类名（如遗留全局 CSS 中的类）。
+    /// 生成名与其冲突时会加盐重新 hash，并记录一条诊断。
+    pub fn with_reserved_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.reserved_names = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 设置手动覆盖：固定完整类字符串的生成名，替换单个工具类的声明
+    pub fn with_class_overrides(mut self, overrides: &IndexMap<String, ClassOverride>) -> Self {
+        self.pinned_names = overrides
+            .iter()
+            .filter_map(|(classes, o)| Some((classes.trim().to_string(), o.name.clone()?)))
+            .collect();
+        let declarations = overrides.iter().filter_map(|(class, o)| {
+            let css = o.css.as_ref()?;
+            let declarations = css
+                .iter()
+                .map(|(property, value)| Declaration::new(property.clone(), value.clone()))
+                .collect();
+            Some((class.trim().to_string(), declarations))
+        });
+        self.bundler = self.bundler.with_class_overrides(declarations);
+        self
+    }
+
+    /// 设置是否规范化类字符串（默认开启），见 `TransformOptions::normalize_classes`
+    pub fn with_normalize_classes(mut self, enabled: bool) -> Self {
+        self.normalize_classes = enabled;
+        self
+    }
+
+    /// 设置类名别名（别名 → 空格分隔的类），见 `TransformOptions::class_aliases`
+    pub fn with_class_aliases(mut self, aliases: &IndexMap<String, String>) -> Self {
+        self.class_aliases = ClassAliases::new(aliases);
+        self
+    }
+
+    /// 设置变体别名（别名 → 展开的变体），见 `TransformOptions::variant_aliases`
+    pub fn with_variant_aliases(mut self, aliases: &IndexMap<String, Vec<String>>) -> Self {
+        self.bundler = self
+            .bundler
+            .with_variant_aliases(aliases.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self
+    }
+
+    /// 设置 `@custom-variant` 定义的变体，见 `TransformOptions::custom_variants`
+    pub fn with_custom_variants(mut self, variants: &IndexMap<String, CustomVariant>) -> Self {
+        self.bundler = self
+            .bundler
+            .with_custom_variants(variants.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self
+    }
+
+    /// 设置 `@utility` 定义的工具类，见 `TransformOptions::custom_utilities`
+    pub fn with_custom_utilities(mut self, utilities: &IndexMap<String, CustomUtility>) -> Self {
+        self.bundler = self
+            .bundler
+            .with_custom_utilities(utilities.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self
+    }
+
+    /// 设置相同规则体的去重方式（见 [`CssDedup`]）；设置了规则钩子时不去重
+    pub fn with_css_dedup(mut self, dedup: CssDedup) -> Self {
+        self.css_dedup = dedup;
+        self
+    }
+
+    /// 设置流水线钩子（见 `hooks` 模块）
+    pub fn with_hooks(mut self, class_hook: Option<ClassHook>, rule_hook: Option<RuleHook>) -> Self {
+        self.class_hook = class_hook;
+        self.rule_hook = rule_hook;
+        self
+    }
+
+    /// 为一组类生成不与其他类组合、保留类名冲突的名称
+    fn allocate_name(&mut self, classes: &[String]) -> String {
+        let key = class_set_key(classes);
+        let name = self
+            .naming
+            .generate_name_with_hash_length(classes, self.hash_length);
+        let name = self.resolve_collision(name, classes, &key);
+        let name = self.avoid_reserved(name, classes);
+        self.allocated.entry(name.clone()).or_insert(key);
+        name
+    }
+
+    /// 名称已被其他类组合占用时，Hash 命名逐步加长 hash，无法加长（或其他命名模式）时加盐后缀
+    fn resolve_collision(&mut self, name: String, classes: &[String], key: &str) -> String {
+        let taken = |allocated: &IndexMap<String, String>, name: &str| {
+            allocated.get(name).is_some_and(|existing| existing != key)
+        };
+        if !taken(&self.allocated, &name) {
+            return name;
+        }
+        let mut length = self.hash_length;
+        let mut salt = 0u32;
+        let mut resolved = name.clone();
+        while taken(&self.allocated, &resolved) {
+            let longer = (length < MAX_HASH_LENGTH).then(|| {
+                length = (length + 2).min(MAX_HASH_LENGTH);
+                self.naming.generate_name_with_hash_length(classes, length)
+            });
+            resolved = match longer.filter(|longer| *longer != resolved) {
+                Some(longer) => longer,
+                None => {
+                    salt += 1;
+                    salted_name(&name, salt)
+                }
+            };
+        }
+        let existing = self.allocated[&name].clone();
+        self.diagnostics.push(Diagnostic::warning(format!(
+            "生成的类名 `{}` 已用于 `{}`，`{}` 改用 `{}`",
+            name, existing, key, resolved
+        )));
+        self.collisions.push(NameCollision {
+            name,
+            existing,
+            classes: key.to_string(),
+            resolved: Some(resolved.clone()),
+        });
+        resolved
+    }
+
+    /// 名称与保留类名冲突时，以「原名 + 序号」为盐重新 hash，结果只取决于输入，保持稳定
+    fn avoid_reserved(&mut self, name: String, classes: &[String]) -> String {
+        if !self.reserved_names.contains(&name) {
+            return name;
+        }
+        let mut salt = 1u32;
+        let resolved = loop {
+            let candidate = salted_name(&name, salt);
+            if !self.reserved_names.contains(&candidate) {
+                break candidate;
+            }
+            salt += 1;
+        };
+        self.diagnostics.push(Diagnostic::warning(format!(
+            "生成的类名 `{}` 与已有类名冲突（{}），已改为 `{}`",
+            name,
+            classes.join(" "),
+            resolved
+        )));
+        resolved
+    }
+
+    /// 去重签名：以占位名渲染的规则，签名相同的类组合除选择器外产出完全相同的规则。
+    ///
+    /// 未开启去重、React Native 模式（不产出 CSS）或设置了规则钩子（钩子可能按选择器改写规则）时返回 None
+    fn rule_signature(&mut self, classes: &str) -> Option<String> {
+        if self.css_dedup == CssDedup::None
+            || self.native_styles.is_some()
+            || self.rule_hook.is_some()
+        {
+            return None;
+        }
+        let context = self.bundle(SIGNATURE_NAME, classes)?;
+        let context = match &self.data_attribute {
+            Some(attr) => context.with_selector(format!("[{}=\"{}\"]", attr, SIGNATURE_NAME)),
+            None => context,
+        };
+        let signature = context.to_css(&self.indent);
+        (!signature.is_empty()).then_some(signature)
+    }
+
+    /// 打包一组类并报告其中无法转换的类；无法解析时逐个检查找出原因
+    fn bundle(&mut self, name: &str, classes: &str) -> Option<ClassContext> {
+        match self.bundler.bundle_to_context(name, classes) {
+            Ok(context) => {
+                for class in context.unconverted() {
+                    self.report_unconverted(class);
+                }
+                Some(context)
+            }
+            Err(_) => {
+                for class in classes.split_whitespace() {
+                    if !self.bundler.is_recognized(class) {
+                        self.report_unconverted(class);
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// 为生成名产出 CSS，直接追加到 CSS 缓冲区；`original` 为注释中的原始类字符串。
+    ///
+    /// 带 `signature` 时记录去重信息：GroupSelectors 模式把规则移入 `rule_groups`，
+    /// 签名已出现过时并入已有规则块
+    fn push_css(&mut self, name: &str, classes: &str, original: &str, signature: Option<String>) {
+        if self.native_styles.is_some() {
+            self.push_native_style(name, classes);
+            return;
+        }
+        let Some(context) = self.bundle(name, classes) else {
+            return;
+        };
+        let context = match &self.data_attribute {
+            Some(attr) => context.with_selector(format!("[{}=\"{}\"]", attr, name)),
+            None => context,
+        };
+
+        let start = self.css.len();
+        if !self.css.is_empty() {
+            self.css.push('\n');
+        }
+        let comment_start = self.css.len();
+        if self.css_comments {
+            let comment = css_comment(self.source.as_deref(), self.element.as_deref(), original);
+            self.css.push_str(&comment);
+        }
+        let body = self.css.len();
+        match &self.rule_hook {
+            Some(hook) => context.write_css_with(&mut self.css, &self.indent, &mut |rule| hook(rule)),
+            None => context.write_css(&mut self.css, &self.indent),
+        }
+        if self.css.len() == body {
+            self.css.truncate(start);
+            return;
+        }
+        if self.css_modules {
+            let wrapped = global_markers(&self.css[body..]);
+            self.css.truncate(body);
+            self.css.push_str(&wrapped);
+        }
+        self.css_entries += 1;
+
+        let Some(signature) = signature else {
+            return;
+        };
+        match self.css_dedup {
+            CssDedup::GroupSelectors => {
+                let rule = self.css.split_off(body);
+                let comment = self.css.split_off(comment_start);
+                self.css.truncate(start);
+                self.rule_groups.push(signature, comment, rule);
+            }
+            CssDedup::ShareClass => {
+                self.signatures.entry(signature).or_insert_with(|| name.to_string());
+            }
+            CssDedup::None => {}
+        }
+    }
+
+    /// 逐个工具类转换为 React Native 样式，无法表达的类与声明记为诊断
+    fn push_native_style(&mut self, name: &str, classes: &str) {
+        let mut style = NativeStyle::new();
+        for class in classes.split_whitespace() {
+            let group = match self.bundler.bundle(class) {
+                Ok(group) => group,
+                Err(_) => {
+                    self.diagnostics.push(Diagnostic::warning(format!(
+                        "`{}` 无法解析，已跳过",
+                        class
+                    )));
+                    continue;
+                }
+            };
+            let has_variants = !group.pseudo_classes.is_empty()
+                || !group.pseudo_elements.is_empty()
+                || !group.responsive.is_empty()
+                || !group.states.is_empty();
+            if has_variants {
+                self.diagnostics.push(Diagnostic::warning(format!(
+                    "`{}`：React Native 不支持变体，已跳过",
+                    class
+                )));
+                continue;
+            }
+            if group.base.is_empty() {
+                self.diagnostics.push(Diagnostic::warning(format!(
+                    "`{}` 无法转换为 React Native 样式，已跳过",
+                    class
+                )));
+                continue;
+            }
+            let (converted, unsupported) = convert_declarations(&group.base);
+            for decl in unsupported {
+                self.diagnostics.push(Diagnostic::warning(format!(
+                    "`{}`：React Native 不支持 `{}: {}`，已跳过",
+                    class, decl.property, decl.value
+                )));
+            }
+            style.extend(converted);
+        }
+        if let Some(styles) = self.native_styles.as_mut() {
+            styles.insert(name.to_string(), style);
         }
     }
 
     /// 处理一组 Tailwind 类，返回生成的类名。
-    /// 如果该类组合已处理过，直接返回缓存结果。
+    /// 如果该类组合已处理过，直接返回缓存结果；开启规范化时只差空白或重复类的字符串视为同一组合。
     ///
     /// Preserve 模式下，未识别的类名会保留在输出中：
     /// - 全部未识别 → 原样返回
     /// - 部分识别 → `"生成名 unknown1 unknown2"`
+    ///
+    /// `group` / `peer`（含 `group/item` 等具名形式）本身没有样式，但子元素的
+    /// `group-*` / `peer-*` 变体依赖它们出现在 DOM 中，因此在任何模式下都原样保留。
+    /// `prose` 系列类在 `ProseClassMode::Preserve` 下同样原样保留，并记录一条 Info 诊断
+    ///
+    /// 无法转换的类记录一条 Warning 诊断，能找到拼写相近的类、所需插件或主题条目时附带建议；
+    /// Preserve 模式下这些类本就原样保留，只在有建议时报告
     pub fn process_classes(&mut self, classes: &str) -> String {
+        self.process_class_parts(classes).joined()
+    }
+
+    /// 与 `process_classes` 相同，但分别返回生成名与原样保留的类
+    ///
+    /// 生成名可能与某个输入类同名（Readable 模式下 `"flex card"` 中 `card` 未识别时生成 `flex`），
+    /// 因此不能从拼接后的结果反推两者。
+    pub(crate) fn process_class_parts(&mut self, classes: &str) -> ClassParts {
         let trimmed = classes.trim();
         if trimmed.is_empty() {
-            return String::new();
+            return ClassParts::default();
         }
+        let normalized = if self.normalize_classes {
+            normalize_classes(trimmed)
+        } else {
+            Cow::Borrowed(trimmed)
+        };
+        let key = normalized.as_ref();
+
+        *self.class_usage.entry(key.to_string()).or_insert(0) += 1;
 
         // 缓存命中
-        if let Some(name) = self.class_map.get(trimmed) {
-            return name.clone();
-        }
-
-        if self.unknown_class_mode == UnknownClassMode::Preserve {
-            // 分离已识别和未识别的类
-            let mut recognized = Vec::new();
-            let mut unrecognized = Vec::new();
-            for class in trimmed.split_whitespace() {
-                if self.bundler.is_recognized(class) {
-                    recognized.push(class.to_string());
-                } else {
-                    unrecognized.push(class.to_string());
-                }
-            }
+        if let Some(parts) = self.class_parts.get(key) {
+            return parts.clone();
+        }
+        let timer = PhaseTimer::start(Phase::Convert);
 
-            // 全部未识别 → 原样返回
-            if recognized.is_empty() {
-                self.class_map.insert(trimmed.to_string(), trimmed.to_string());
-                return trimmed.to_string();
+        let preserve_prose = self.prose_mode == ProseClassMode::Preserve;
+        let expanded = self.class_aliases.expand(key);
+        let (markers, utilities): (Vec<&str>, Vec<&str>) = expanded
+            .split_whitespace()
+            .partition(|class| {
+                is_marker_class(class) || (preserve_prose && is_prose_class(class))
+            });
+        for class in markers.iter().filter(|class| is_prose_class(class)) {
+            if self.reported_prose.insert(class.to_string()) {
+                self.diagnostics.push(Diagnostic::info(format!(
+                    "`{}` 属于 @tailwindcss/typography 插件，已原样保留，样式需由该插件提供",
+                    class
+                )));
             }
+        }
 
-            // 仅从已识别的类生成名称和 CSS
-            let recognized_str = recognized.join(" ");
-            let new_name = self.naming.generate_name(&recognized);
-
-            match self.bundler.bundle_to_css(&new_name, &recognized_str, &self.indent) {
-                Ok(css) if !css.is_empty() => {
-                    self.css_entries.push(css);
-                }
-                _ => {}
+        let (recognized, mut kept): (Vec<&str>, Vec<&str>) =
+            if self.unknown_class_mode == UnknownClassMode::Preserve {
+                // 分离已识别和未识别的类
+                utilities
+                    .into_iter()
+                    .partition(|class| self.bundler.is_recognized(class))
+            } else {
+                // Remove 模式：全部参与命名，未识别的类不产出 CSS，在打包时报告
+                (utilities, Vec::new())
+            };
+        if self.native_styles.is_none() {
+            // React Native 模式在生成样式对象时另行报告
+            for class in &kept {
+                self.report_unconverted(class);
             }
+        }
+        kept.extend(markers);
 
-            // 合并：生成名 + 未识别类
-            let result = if unrecognized.is_empty() {
-                new_name
-            } else {
-                format!("{} {}", new_name, unrecognized.join(" "))
+        // 没有可生成的类 → 原样返回
+        if recognized.is_empty() {
+            let parts = ClassParts {
+                generated: String::new(),
+                kept: key.to_string(),
             };
+            self.insert_parts(key, &parts);
+            timer.stop(&mut self.timings);
+            return parts;
+        }
 
-            self.class_map.insert(trimmed.to_string(), result.clone());
-            result
-        } else {
-            // Remove 模式：原始行为
-            let class_list: Vec<String> = trimmed.split_whitespace().map(|s| s.to_string()).collect();
-            let new_name = self.naming.generate_name(&class_list);
+        let mut collected = CollectedClass {
+            original: key.to_string(),
+            classes: recognized.iter().map(|s| s.to_string()).collect(),
+            name: self.pinned_names.get(key).cloned(),
+        };
+        if let Some(hook) = &self.class_hook {
+            hook(&mut collected);
+        }
+        // 钩子删除了全部类 → 只保留原样保留的类
+        if collected.classes.is_empty() {
+            let parts = ClassParts {
+                generated: String::new(),
+                kept: kept.join(" "),
+            };
+            self.insert_parts(key, &parts);
+            timer.stop(&mut self.timings);
+            return parts;
+        }
 
-            match self.bundler.bundle_to_css(&new_name, trimmed, &self.indent) {
-                Ok(css) if !css.is_empty() => {
-                    self.css_entries.push(css);
-                }
-                _ => {}
+        let classes = collected.classes.join(" ");
+        let signature = self.rule_signature(&classes);
+        // ShareClass：规则与已有生成名完全相同时直接复用，不再命名、产出 CSS
+        let shared = match (&collected.name, &signature) {
+            (None, Some(signature)) if self.css_dedup == CssDedup::ShareClass => {
+                self.signatures.get(signature).cloned()
+            }
+            _ => None,
+        };
+        let new_name = match shared {
+            Some(name) => name,
+            None => {
+                let name = match collected.name {
+                    Some(name) => name,
+                    None => self.allocate_name(&collected.classes),
+                };
+                self.push_css(&name, &classes, key, signature);
+                name
             }
+        };
+
+        let parts = ClassParts {
+            generated: new_name,
+            kept: kept.join(" "),
+        };
+        self.insert_parts(key, &parts);
+        timer.stop(&mut self.timings);
+        parts
+    }
+
+    /// 缓存处理结果，`class_map` 记录拼接后的类字符串
+    fn insert_parts(&mut self, key: &str, parts: &ClassParts) {
+        self.class_map.insert(key.to_string(), parts.joined());
+        self.class_parts.insert(key.to_string(), parts.clone());
+    }
+
+    /// 记录无法转换的类，每个类只报告一次
+    fn report_unconverted(&mut self, class: &str) {
+        if self.unconverted.contains_key(class) {
+            return;
+        }
+        let suggestion = self.bundler.suggest(class).map(|s| s.to_string());
+        self.unconverted.insert(class.to_string(), suggestion.clone());
+        let message = match (suggestion, self.unknown_class_mode) {
+            (Some(suggestion), _) => format!("`{}` 无法转换为 CSS：{}", class, suggestion),
+            (None, UnknownClassMode::Remove) => format!("`{}` 无法转换为 CSS", class),
+            (None, UnknownClassMode::Preserve) => return,
+        };
+        self.diagnostics.push(Diagnostic::warning(message));
+    }
 
-            self.class_map.insert(trimmed.to_string(), new_name.clone());
-            new_name
+    /// 字符串中是否至少有一个可识别的 Tailwind 类
+    ///
+    /// 用于判断 JSX 类名属性以外的普通字符串（如 cva 的变体取值 `"primary"`）是否为类名。
+    pub fn has_recognized(&self, classes: &str) -> bool {
+        self.class_aliases
+            .expand(classes)
+            .split_whitespace()
+            .any(|class| self.bundler.is_recognized(class))
+    }
+
+    /// 生成名的空间占用与冲突统计（见 [`NamingStats`]）
+    pub fn naming_stats(&self) -> NamingStats {
+        NamingStats {
+            names: self
+                .allocated
+                .iter()
+                .map(|(name, classes)| (classes.clone(), name.clone()))
+                .collect(),
+            hash_length: (self.naming_mode == NamingMode::Hash).then_some(self.hash_length),
+            collisions: self.collisions.clone(),
         }
     }
 
     /// 返回合并后的 CSS 输出
     ///
-    /// Var 模式下自动在顶部插入 `:root { ... }` 主题变量定义。
+    /// CSS 变量为 Var 模式、颜色为 Var 模式或未解析间距倍数时，自动在顶部插入 `:root { ... }`
+    /// 定义所有引用到的主题变量，输出无需再依赖 Tailwind 的主题样式。
+    /// 调用过 `flush_css` 时只包含其后生成的规则，`:root` 仍覆盖全部规则。
+    /// 开启 preflight 时基础样式位于 `:root` 与工具类规则之间。
     pub fn combined_css(&self) -> String {
-        let css = self.css_entries.join("\n");
-        if self.css_variables == CssVariableMode::Var && !css.is_empty() {
-            let root = self.bundler.generate_root_css(&css);
-            if root.is_empty() {
-                css
-            } else {
-                format!("{}\n{}", root, css)
+        let root = self.root_css();
+        let base = self.base_css();
+        let pending = self.pending_css();
+        let mut css = String::with_capacity(root.len() + base.len() + 2 + pending.len());
+        for part in [&root, &base] {
+            if !part.is_empty() {
+                css.push_str(part);
+                css.push('\n');
+            }
+        }
+        css.push_str(&pending);
+        css
+    }
+
+    /// 与 `combined_css` 相同，但直接写入 `out`，不额外拼接字符串
+    pub fn write_css(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        for part in [self.root_css(), self.base_css()] {
+            if !part.is_empty() {
+                out.write_str(&part)?;
+                out.write_char('\n')?;
             }
+        }
+        out.write_str(&self.pending_css())
+    }
+
+    /// 按层叠角色拆分的 CSS（主题变量、基础样式、尚未 flush 的工具类规则），拼接后与 `combined_css` 相同
+    pub fn css_outputs(&self) -> CssOutputs {
+        CssOutputs {
+            variables: self.root_css(),
+            base: self.base_css(),
+            utilities: self.pending_css().into_owned(),
+        }
+    }
+
+    /// 基础样式：开启 preflight 时为 preflight（React Native 模式除外），否则为空字符串
+    fn base_css(&self) -> String {
+        if self.preflight && self.native_styles.is_none() {
+            PREFLIGHT.to_string()
         } else {
-            css
+            String::new()
         }
     }
 
+    /// 尚未 flush 的规则；GroupSelectors 模式下由规则块渲染
+    fn pending_css(&self) -> Cow<'_, str> {
+        if self.rule_groups.is_empty() {
+            Cow::Borrowed(&self.css)
+        } else {
+            Cow::Owned(self.rule_groups.render())
+        }
+    }
+
+    /// 写出上次 flush 之后生成的规则（不含 `:root`），并释放这部分缓冲
+    ///
+    /// 依次拼接每次 flush 的输出，与不 flush 时 `combined_css` 中的规则部分相同；
+    /// 全部 flush 完后用 `root_css` 补上主题变量定义。
+    /// 例外是 GroupSelectors 去重：已 flush 的规则块无法再并入选择器，之后相同的规则另起一组。
+    pub fn flush_css(&mut self, out: &mut impl fmt::Write) -> fmt::Result {
+        if !self.rule_groups.is_empty() {
+            self.css = self.rule_groups.render();
+            self.rule_groups.clear();
+        }
+        if self.css.is_empty() {
+            return Ok(());
+        }
+        if self.flushed_bytes > 0 {
+            out.write_char('\n')?;
+        }
+        out.write_str(&self.css)?;
+        if self.uses_variables() {
+            self.flushed_variables
+                .extend(self.bundler.theme_variables(&self.css));
+        }
+        self.flushed_bytes += self.css.len();
+        self.css.clear();
+        Ok(())
+    }
+
+    /// 所有已生成规则（含已 flush 的）引用到的主题变量的 `:root` 定义及暗色覆盖块，无需定义时为空字符串
+    pub fn root_css(&self) -> String {
+        if !self.uses_variables() {
+            return String::new();
+        }
+        let pending = self.pending_css();
+        if self.flushed_variables.is_empty() && self.dark_theme.is_empty() {
+            return self.bundler.generate_root_css(&pending);
+        }
+        let mut variables = self.flushed_variables.clone();
+        variables.extend(self.bundler.theme_variables(&pending));
+        let mut root = self.bundler.root_css_for(&variables);
+        let dark = self.dark_theme.css(|name| variables.contains(name));
+        if !dark.is_empty() {
+            if !root.is_empty() {
+                root.push('\n');
+            }
+            root.push_str(&dark);
+        }
+        root
+    }
+
+    /// 输出中是否引用主题变量（需要 `:root` 定义）
+    fn uses_variables(&self) -> bool {
+        self.css_variables == CssVariableMode::Var
+            || self.css_variable_overrides.any_var()
+            || self.color_mode == ColorMode::Var
+            || !self.resolve_spacing
+    }
+
     /// 返回类名映射表（原始 -> 生成）
     pub fn class_map(&self) -> &IndexMap<String, String> {
         &self.class_map
@@ -144,6 +897,169 @@ impl ClassCollector {
     pub fn into_class_map(self) -> IndexMap<String, String> {
         self.class_map
     }
+
+    /// 单个类不带变体时的声明，无法识别时为 None（供继承分析比较祖先与子元素的声明）
+    pub(crate) fn base_declarations(&self, class: &str) -> Option<Vec<Declaration>> {
+        if !self.bundler.is_recognized(class) {
+            return None;
+        }
+        self.bundler.bundle(class).ok().map(|group| group.base)
+    }
+
+    /// 记录一条诊断信息（供 visitor 报告源码层面的问题）
+    pub fn push_diagnostic(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// 返回收集过程中产生的诊断信息
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// 各阶段耗时，收集器只记录类名转换（`convert`）
+    pub fn timings(&self) -> Timings {
+        self.timings
+    }
+
+    /// 原始类字符串 → 出现次数（按首次出现顺序）
+    pub fn class_usage(&self) -> &IndexMap<String, usize> {
+        &self.class_usage
+    }
+
+    /// 无法转换的类 → 修复建议（按首次出现顺序）
+    pub fn unconverted_classes(&self) -> &IndexMap<String, Option<String>> {
+        &self.unconverted
+    }
+
+    /// 消费 self，返回类名映射表和诊断信息
+    pub fn into_parts(self) -> (IndexMap<String, String>, Vec<Diagnostic>) {
+        (self.class_map, self.diagnostics)
+    }
+}
+
+/// `name` 加上以「原名 + 序号」计算的 6 位 hash 后缀
+fn salted_name(name: &str, salt: u32) -> String {
+    let hex = format!("{}", blake3::hash(format!("{}#{}", name, salt).as_bytes()));
+    format!("{}_{}", name, &hex[..6])
+}
+
+/// 规则块来源注释：`/* App.tsx <div> "p-4 text-center" */`，缺少的部分省略
+fn css_comment(source: Option<&str>, element: Option<&str>, classes: &str) -> String {
+    let mut comment = String::from("/*");
+    if let Some(source) = source {
+        comment.push_str(&format!(" {}", source));
+    }
+    if let Some(element) = element {
+        comment.push_str(&format!(" <{}>", element));
+    }
+    // 类中的 `*/`（如任意值）会提前结束注释
+    comment.push_str(&format!(" \"{}\" */\n", classes.replace("*/", "*\\/")));
+    comment
+}
+
+/// `group` / `peer` 标记类（含 `group/item` 等具名形式）
+fn is_marker_class(class: &str) -> bool {
+    let name = class.split_once('/').map_or(class, |(name, _)| name);
+    matches!(name, "group" | "peer")
+}
+
+/// typography 插件的类：`prose`、`prose-lg`、`not-prose`，以及带 `prose-headings:` 等
+/// 元素变体或以 `prose` 系列类为主体的类（如 `dark:prose-invert`）
+fn is_prose_class(class: &str) -> bool {
+    let is_prose = |segment: &str| {
+        let segment = segment.trim_start_matches('!');
+        segment == "prose" || segment == "not-prose" || segment.starts_with("prose-")
+    };
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => {
+                if is_prose(&class[start..i]) {
+                    return true;
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    is_prose(&class[start..])
+}
+
+/// 将 CSS 中的 `.group` / `.peer`（含具名形式 `.group\/item`）包裹为 `:global(...)`
+fn global_markers(css: &str) -> String {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii();
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(pos) = rest.find('.') {
+        let preceded_by_ident = rest[..pos]
+            .chars()
+            .last()
+            .or_else(|| out.chars().last())
+            .is_some_and(is_ident);
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos + 1..];
+        let marker_len = ["group", "peer"]
+            .into_iter()
+            .find(|marker| tail.starts_with(marker))
+            .map(|marker| {
+                let mut len = marker.len();
+                if let Some(name) = tail[len..].strip_prefix("\\/") {
+                    len += 2 + name.find(|c: char| !is_ident(c)).unwrap_or(name.len());
+                }
+                len
+            })
+            .filter(|&len| !tail[len..].starts_with(is_ident) && !preceded_by_ident);
+        match marker_len {
+            Some(len) => {
+                out.push_str(&format!(":global(.{})", &tail[..len]));
+                rest = &tail[len..];
+            }
+            None => {
+                out.push('.');
+                rest = tail;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 合并连续空白并删除重复的类（保留第一次出现）；已是规范形式时借用原字符串
+fn normalize_classes(classes: &str) -> Cow<'_, str> {
+    let mut seen = HashSet::new();
+    let normalized = classes
+        .split_whitespace()
+        .filter(|class| seen.insert(*class))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if normalized == classes {
+        Cow::Borrowed(classes)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// `process_class_parts` 的结果：生成名与原样保留的类（未识别的类、`group` / `peer` 标记等）
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ClassParts {
+    /// 生成的类名，没有可生成的类时为空
+    pub generated: String,
+    /// 原样保留的类，空格分隔
+    pub kept: String,
+}
+
+impl ClassParts {
+    /// 生成名在前、保留的类在后，即 `process_classes` 的返回值
+    pub fn joined(&self) -> String {
+        match (self.generated.is_empty(), self.kept.is_empty()) {
+            (_, true) => self.generated.clone(),
+            (true, false) => self.kept.clone(),
+            (false, false) => format!("{} {}", self.generated, self.kept),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -165,7 +1081,9 @@ mod tests {
         let name2 = collector.process_classes("p-4 m-2");
         assert_eq!(name1, name2);
         // CSS 应该只生成一次
-        assert_eq!(collector.css_entries.len(), 1);
+        assert_eq!(collector.css_entries, 1);
+        // 出现次数照常累计
+        assert_eq!(collector.class_usage().get("p-4 m-2"), Some(&2));
     }
 
     #[test]
@@ -174,7 +1092,59 @@ mod tests {
         let name1 = collector.process_classes("p-4");
         let name2 = collector.process_classes("m-2");
         assert_ne!(name1, name2);
-        assert_eq!(collector.css_entries.len(), 2);
+        assert_eq!(collector.css_entries, 2);
+    }
+
+    #[test]
+    fn test_flush_css_streams_rules() {
+        let classes = ["p-4 text-xl", "hover:bg-blue-500 m-2", "shadow-md"];
+        let new_collector = || {
+            ClassCollector::new(
+                NamingMode::Hash,
+                CssVariableMode::Var,
+                UnknownClassMode::Remove,
+                ColorMode::Var,
+                false,
+            )
+        };
+        let mut whole = new_collector();
+        for class in classes {
+            whole.process_classes(class);
+        }
+        let mut written = String::new();
+        whole.write_css(&mut written).unwrap();
+        assert_eq!(written, whole.combined_css());
+
+        let mut streamed = new_collector();
+        let mut out = String::new();
+        streamed.process_classes(classes[0]);
+        streamed.flush_css(&mut out).unwrap();
+        streamed.process_classes(classes[1]);
+        streamed.process_classes(classes[2]);
+        streamed.flush_css(&mut out).unwrap();
+        streamed.flush_css(&mut out).unwrap();
+
+        // 分批输出的规则拼接后与一次性输出相同，`:root` 仍包含首批规则引用的变量
+        let root = streamed.root_css();
+        assert!(root.contains("--text-xl:") && root.contains("--color-blue-500:"), "{}", root);
+        assert_eq!(format!("{}\n{}", root, out), whole.combined_css());
+        assert_eq!(streamed.combined_css(), format!("{}\n", root));
+    }
+
+    #[test]
+    fn test_normalize_classes() {
+        let mut collector = ClassCollector::new(NamingMode::Hash, CssVariableMode::Var, UnknownClassMode::Preserve, ColorMode::default(), false);
+        let name = collector.process_classes("p-4 text-center my-widget");
+        assert_eq!(collector.process_classes(" p-4  text-center\n my-widget p-4 "), name);
+        assert_eq!(collector.class_map().len(), 1);
+        assert_eq!(collector.class_usage()["p-4 text-center my-widget"], 2);
+        assert_eq!(collector.combined_css().matches("padding:").count(), 1);
+
+        let mut raw = ClassCollector::new(NamingMode::Hash, CssVariableMode::Var, UnknownClassMode::Preserve, ColorMode::default(), false)
+            .with_normalize_classes(false);
+        raw.process_classes("p-4 text-center");
+        raw.process_classes("p-4  text-center");
+        assert_eq!(raw.class_map().len(), 2);
     }
 
     #[test]
@@ -190,4 +1160,374 @@ mod tests {
         let name = collector.process_classes("p-4 m-2");
         assert_eq!(name, "p4_m2");
     }
+
+    #[test]
+    fn test_reserved_name_collision() {
+        let mut plain = ClassCollector::new(NamingMode::Readable, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false);
+        assert_eq!(plain.process_classes("p-4 m-2"), "p4_m2");
+
+        let mut collector = ClassCollector::new(NamingMode::Readable, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false)
+            .with_reserved_names(["p4_m2"]);
+        let name = collector.process_classes("p-4 m-2");
+        assert_ne!(name, "p4_m2");
+        assert!(name.starts_with("p4_m2_"));
+        assert!(collector.combined_css().contains(&format!(".{} {{", name)));
+        assert_eq!(collector.diagnostics().len(), 1);
+
+        // 重新 hash 结果稳定
+        let mut again = ClassCollector::new(NamingMode::Readable, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false)
+            .with_reserved_names(["p4_m2".to_string()]);
+        assert_eq!(again.process_classes("p-4 m-2"), name);
+    }
+
+    #[test]
+    fn test_name_collisions_grow_hash() {
+        // 1 位 hash 只有 16 个名称，40 组类必然冲突
+        let mut collector = ClassCollector::new(NamingMode::Hash, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false)
+            .with_hash_length(1);
+        let names: Vec<String> = (1..=40)
+            .map(|n| collector.process_classes(&format!("p-{}", n)))
+            .collect();
+        let unique: HashSet<&String> = names.iter().collect();
+        assert_eq!(unique.len(), names.len());
+        // 同一组类再次分配到同一名称不算冲突
+        let first = collector.process_classes("p-4 m-2");
+        assert_eq!(collector.process_classes("p-4  m-2"), first);
+
+        let stats = collector.naming_stats();
+        assert_eq!(stats.names.len(), 41);
+        assert_eq!(stats.hash_length, Some(1));
+        assert!(!stats.collisions.is_empty());
+        for collision in &stats.collisions {
+            let resolved = collision.resolved.as_ref().unwrap();
+            assert!(resolved.len() > collision.name.len(), "{:?}", collision);
+            assert_eq!(&stats.names[&collision.classes], resolved);
+        }
+        assert_eq!(collector.diagnostics().len(), stats.collisions.len());
+        assert!(stats.space_usage().unwrap() > 1.0);
+
+        // Readable 命名截断后相同（`bgblue50`）时加盐后缀
+        let mut readable = ClassCollector::new(NamingMode::Readable, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false);
+        assert_eq!(readable.process_classes("bg-blue-500"), "bgblue50");
+        let second = readable.process_classes("bg-blue-50");
+        assert!(second.starts_with("bgblue50_"), "{}", second);
+        let stats = readable.naming_stats();
+        assert_eq!(stats.hash_length, None);
+        assert_eq!(stats.collisions[0].existing, "bg-blue-500");
+    }
+
+    #[test]
+    fn test_css_dedup_modes() {
+        let classes = ["flex items-center", "p-4", "flex items-center foo", "p-[1rem]"];
+        let run = |dedup| {
+            let mut collector = ClassCollector::new(
+                NamingMode::Hash,
+                CssVariableMode::Inline,
+                UnknownClassMode::Remove,
+                ColorMode::Hex,
+                false,
+            )
+            .with_resolve_spacing(true)
+            .with_css_dedup(dedup);
+            let names: Vec<String> = classes.iter().map(|c| collector.process_classes(c)).collect();
+            (names, collector.combined_css())
+        };
+
+        let (names, css) = run(CssDedup::None);
+        assert_eq!(css.matches("align-items: center;").count(), 2);
+        assert_ne!(names[0], names[2]);
+
+        let (grouped, css) = run(CssDedup::GroupSelectors);
+        assert_eq!(grouped, names);
+        assert_eq!(css.matches("align-items: center;").count(), 1);
+        assert!(
+            css.starts_with(&format!(".{}, .{} {{\n", names[0], names[2])),
+            "{}",
+            css
+        );
+        assert!(
+            css.contains(&format!("\n\n.{}, .{} {{\n  padding: 1rem;", names[1], names[3])),
+            "{}",
+            css
+        );
+
+        let (shared, css) = run(CssDedup::ShareClass);
+        assert_eq!(shared[..2], names[..2]);
+        assert_eq!((&shared[2], &shared[3]), (&names[0], &names[1]));
+        assert_eq!(css.matches("align-items: center;").count(), 1);
+        assert!(!css.contains(&names[2]) && !css.contains(&names[3]));
+    }
+
+    #[test]
+    fn test_data_attribute_selector() {
+        let mut collector = ClassCollector::new(
+            NamingMode::Hash,
+            CssVariableMode::Var,
+            UnknownClassMode::Preserve,
+            ColorMode::default(),
+            false,
+        )
+        .with_data_attribute("data-hw");
+        let ClassParts { generated, kept } = collector.process_class_parts("p-4 my-widget");
+        assert_eq!(kept, "my-widget");
+        assert!(collector
+            .combined_css()
+            .starts_with(&format!("[data-hw=\"{}\"] {{", generated)));
+    }
+
+    #[test]
+    fn test_group_marker_preserved() {
+        let mut collector = ClassCollector::new(
+            NamingMode::Hash,
+            CssVariableMode::Var,
+            UnknownClassMode::Remove,
+            ColorMode::default(),
+            false,
+        )
+        .with_css_modules();
+        assert_eq!(collector.process_classes("group/item"), "group/item");
+
+        let ClassParts { generated, kept } = collector.process_class_parts("group p-4");
+        assert_eq!(kept, "group");
+
+        let name = collector.process_classes("md:group-hover/item:p-2 peer-focus:m-2");
+        let css = collector.combined_css();
+        assert!(css.contains(&format!(":global(.group\\/item):hover .{}", name)), "{}", css);
+        assert!(css.contains(&format!(":global(.peer):focus ~ .{}", name)), "{}", css);
+        assert!(css.contains(&format!(".{} {{", generated)));
+    }
+
+    #[test]
+    fn test_pipeline_hooks() {
+        let class_hook: ClassHook = Arc::new(|collected| {
+            collected.classes.retain(|class| !class.starts_with("legacy-"));
+            if collected.original == "p-4 text-center" {
+                collected.name = Some("centered".to_string());
+            }
+        });
+        let rule_hook: RuleHook = Arc::new(|rule| {
+            if rule.selector.ends_with(":hover") {
+                rule.declarations.clear();
+            }
+        });
+        let mut collector = ClassCollector::new(
+            NamingMode::Hash,
+            CssVariableMode::Inline,
+            UnknownClassMode::Remove,
+            ColorMode::Hex,
+            false,
+        )
+        .with_hooks(Some(class_hook), Some(rule_hook));
+
+        assert_eq!(collector.process_classes("p-4 text-center"), "centered");
+        // 钩子删除全部类时只留下原样保留的类
+        assert_eq!(collector.process_classes("group legacy-box"), "group");
+        assert_eq!(collector.class_map()["group legacy-box"], "group");
+
+        let name = collector.process_classes("m-2 hover:m-4");
+        let css = collector.combined_css();
+        assert!(css.contains(".centered {\n  padding: 1rem;\n  text-align: center;\n}"), "{}", css);
+        assert!(css.contains(&format!(".{} {{\n  margin: 0.5rem;", name)), "{}", css);
+        assert!(!css.contains(":hover"), "{}", css);
+    }
+
+    #[test]
+    fn test_color_var_mode_emits_root_palette() {
+        let mut collector = ClassCollector::new(
+            NamingMode::Hash,
+            CssVariableMode::Inline,
+            UnknownClassMode::Remove,
+            ColorMode::Var,
+            false,
+        );
+        collector.process_classes("bg-blue-500 text-white text-lg");
+        let css = collector.combined_css();
+        assert!(css.starts_with(":root {\n"), "{}", css);
+        assert!(css.contains("  --color-blue-500: oklch("), "{}", css);
+        assert!(css.contains("  --color-white: oklch(1 0 0);"), "{}", css);
+        // Inline 模式下字号已内联，不再定义
+        assert!(!css.contains("--text-lg"), "{}", css);
+    }
+
+    #[test]
+    fn test_dark_theme_root() {
+        let dark_theme = DarkTheme {
+            variables: [
+                ("--color-white", "#09090b"),
+                ("--color-blue-500", "#60a5fa"),
+                ("--color-red-500", "#f87171"),
+            ]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+            selector: None,
+        };
+        let mut collector = ClassCollector::new(
+            NamingMode::Hash,
+            CssVariableMode::Inline,
+            UnknownClassMode::Remove,
+            ColorMode::Var,
+            false,
+        )
+        .with_dark_theme(dark_theme.clone());
+        collector.process_classes("bg-blue-500 text-white");
+        let css = collector.combined_css();
+        // 暗色块在 :root 之后，只覆盖用到的变量
+        let dark = "@media (prefers-color-scheme: dark) {\n  :root {\n    --color-white: #09090b;\n    --color-blue-500: #60a5fa;\n  }\n}";
+        assert!(css.contains(dark), "{}", css);
+        assert!(css.find(":root {\n").unwrap() < css.find(dark).unwrap());
+        assert!(!css.contains("--color-red-500"), "{}", css);
+
+        // 颜色内联时不引用变量，也就没有暗色块
+        let mut hex = ClassCollector::new(
+            NamingMode::Hash,
+            CssVariableMode::Inline,
+            UnknownClassMode::Remove,
+            ColorMode::Hex,
+            false,
+        )
+        .with_resolve_spacing(true)
+        .with_dark_theme(dark_theme);
+        hex.process_classes("bg-blue-500");
+        assert!(!hex.combined_css().contains("prefers-color-scheme"));
+    }
+
+    #[test]
+    fn test_css_outputs_with_preflight() {
+        let mut collector = ClassCollector::new(
+            NamingMode::Hash,
+            CssVariableMode::Var,
+            UnknownClassMode::Remove,
+            ColorMode::Hex,
+            false,
+        )
+        .with_preflight(true);
+        collector.process_classes("p-4 text-lg");
+        let outputs = collector.css_outputs();
+        // `text-lg` 引用的主题变量定义在 variables，规则本身在 utilities
+        assert!(outputs.variables.starts_with(":root {\n"), "{:?}", outputs);
+        assert!(outputs.variables.contains("  --text-lg: 1.125rem;\n"), "{:?}", outputs);
+        assert!(!outputs.variables.contains("padding"), "{:?}", outputs);
+        assert_eq!(outputs.base, PREFLIGHT);
+        assert!(outputs.utilities.contains("font-size: var(--text-lg);"), "{:?}", outputs);
+        assert!(outputs.utilities.contains("padding: 1rem;"), "{:?}", outputs);
+        assert!(!outputs.utilities.contains(":root"));
+
+        // 拼接顺序：变量、基础样式、工具类规则
+        let css = collector.combined_css();
+        assert_eq!(css, outputs.combined());
+        let mut written = String::new();
+        collector.write_css(&mut written).unwrap();
+        assert_eq!(written, css);
+        let position = |needle: &str| css.find(needle).unwrap();
+        assert!(position(":root {") < position("box-sizing"), "{}", css);
+        assert!(position("box-sizing") < position("padding: 1rem;"), "{}", css);
+    }
+
+    #[test]
+    fn test_css_variable_overrides_root() {
+        let mut collector = ClassCollector::new(
+            NamingMode::Hash,
+            CssVariableMode::Inline,
+            UnknownClassMode::Remove,
+            ColorMode::Hex,
+            false,
+        )
+        .with_resolve_spacing(true)
+        .with_css_variable_overrides(CssVariableOverrides {
+            shadow: Some(CssVariableMode::Var),
+            ..Default::default()
+        });
+        collector.process_classes("shadow-md text-lg");
+        let css = collector.combined_css();
+        // 只有阴影保留变量，:root 中也只定义阴影
+        assert!(css.starts_with(":root {\n  --shadow-md: 0 4px 6px"), "{}", css);
+        assert!(css.contains("box-shadow: var(--shadow-md);"), "{}", css);
+        assert!(css.contains("font-size: 1.125rem;"), "{}", css);
+        assert!(!css.contains("--text-lg"), "{}", css);
+    }
+
+    #[test]
+    fn test_spacing_root_or_resolved() {
+        let collector = || {
+            ClassCollector::new(
+                NamingMode::Hash,
+                CssVariableMode::Inline,
+                UnknownClassMode::Remove,
+                ColorMode::Hex,
+                false,
+            )
+        };
+        let mut emitted = collector();
+        emitted.process_classes("leading-6");
+        let css = emitted.combined_css();
+        assert!(css.starts_with(":root {\n  --spacing: 0.25rem;\n}"), "{}", css);
+        assert!(css.contains("line-height: calc(var(--spacing) * 6);"), "{}", css);
+
+        let mut resolved = collector().with_resolve_spacing(true);
+        resolved.process_classes("leading-6");
+        let css = resolved.combined_css();
+        assert!(!css.contains(":root"), "{}", css);
+        assert!(css.contains("line-height: 1.5rem;"), "{}", css);
+    }
+
+    #[test]
+    fn test_global_markers_only_wraps_marker_classes() {
+        assert_eq!(
+            global_markers(".group:hover .grouped, .c.peer, .peer-x ~ .c { margin: 0.5rem; }"),
+            ":global(.group):hover .grouped, .c.peer, .peer-x ~ .c { margin: 0.5rem; }"
+        );
+    }
+
+    #[test]
+    fn test_prose_classes_preserved_and_reported() {
+        let mut collector = ClassCollector::new(NamingMode::Readable, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false)
+            .with_prose_mode(ProseClassMode::Preserve);
+        assert_eq!(
+            collector.process_classes("prose lg:prose-xl dark:prose-invert prose-headings:underline p-4"),
+            "p4 prose lg:prose-xl dark:prose-invert prose-headings:underline"
+        );
+        assert_eq!(collector.process_classes("not-prose m-2"), "m2 not-prose");
+        // 同一个类只报告一次
+        collector.process_classes("prose mt-4");
+        assert_eq!(collector.diagnostics().len(), 5);
+        assert!(collector.diagnostics().iter().all(|d| d.level == headwind_core::DiagnosticLevel::Info));
+
+        // 按未知类处理时照常删除，并提示所需插件
+        let mut removed = ClassCollector::new(NamingMode::Readable, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false);
+        assert_eq!(removed.process_classes("prose p-4"), "prose_p4");
+        assert_eq!(removed.diagnostics().len(), 1);
+        assert!(removed.diagnostics()[0].message.contains("@tailwindcss/typography"));
+
+        assert!(is_prose_class("prose-a:text-blue-600"));
+        assert!(!is_prose_class("proseless"));
+        assert!(!is_prose_class("[&_.prose]:p-4"));
+    }
+
+    #[test]
+    fn test_unconverted_classes_reported_with_suggestions() {
+        let mut collector = ClassCollector::new(NamingMode::Readable, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false);
+        collector.process_classes("p-4 bg-bleu-500 card");
+        // 同一个类只报告一次
+        collector.process_classes("m-2 bg-bleu-500");
+        let messages: Vec<&str> = collector.diagnostics().iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "`bg-bleu-500` 无法转换为 CSS：是否想使用 `bg-blue-500`？",
+                "`card` 无法转换为 CSS",
+            ]
+        );
+        assert!(collector.diagnostics().iter().all(|d| d.level == headwind_core::DiagnosticLevel::Warning));
+
+        // Preserve 模式：自定义类原样保留，只报告有建议的类
+        let mut preserved = ClassCollector::new(NamingMode::Readable, CssVariableMode::Var, UnknownClassMode::Preserve, ColorMode::default(), false);
+        assert_eq!(preserved.process_classes("p-4 card itms-center"), "p4 card itms-center");
+        assert_eq!(preserved.diagnostics().len(), 1);
+        assert!(preserved.diagnostics()[0].message.contains("`items-center`"));
+        assert_eq!(
+            preserved.unconverted_classes().keys().collect::<Vec<_>>(),
+            ["card", "itms-center"]
+        );
+    }
 }
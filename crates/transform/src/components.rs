@@ -0,0 +1,289 @@
+//! 组件类提取建议
+//!
+//! 统计会话内每个元素的类名组合，找出在多个元素、多个文件中重复出现的组合，
+//! 建议提取为具名的组件类。候选组合取自各元素的完整类集合及两两之间的交集，
+//! 只保留“闭合”的组合：若更大的组合出现次数相同，较小的组合不再单独列出。
+
+use crate::report::FileReport;
+use crate::session::ConfigSession;
+use headwind_core::NamingMode;
+use headwind_tw_index::naming::create_naming_strategy;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// 分析参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentOptions {
+    /// 组合至少包含的类名数（小于 2 时按 2 处理）
+    pub min_classes: usize,
+    /// 组合至少出现在多少个元素上
+    pub min_occurrences: usize,
+    /// 最多返回的建议数
+    pub max_suggestions: usize,
+}
+
+impl Default for ComponentOptions {
+    fn default() -> Self {
+        Self {
+            min_classes: 3,
+            min_occurrences: 3,
+            max_suggestions: 20,
+        }
+    }
+}
+
+/// 一个可提取的组件类
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentSuggestion {
+    /// 建议的组件类名（Readable 命名）
+    pub name: String,
+    /// 组合中的类名，按字母序
+    pub classes: Vec<String>,
+    /// 包含该组合的元素数
+    pub occurrences: usize,
+    /// 出现该组合的文件，按路径顺序
+    pub files: Vec<PathBuf>,
+}
+
+/// 分析结果，按可节省的类名数（出现次数 × 组合大小）降序排列
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ComponentAnalysis {
+    pub suggestions: Vec<ComponentSuggestion>,
+}
+
+impl ComponentAnalysis {
+    /// JSON 输出
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("ComponentAnalysis 序列化不会失败")
+    }
+
+    /// 文本摘要，每条建议一行
+    pub fn summary(&self) -> String {
+        if self.suggestions.is_empty() {
+            return "没有发现重复出现的类组合\n".to_string();
+        }
+        let mut text = format!("发现 {} 个可提取的类组合：\n", self.suggestions.len());
+        for (i, suggestion) in self.suggestions.iter().enumerate() {
+            text.push_str(&format!(
+                "{}. .{}（{} 个元素，{} 个文件）：{}\n",
+                i + 1,
+                suggestion.name,
+                suggestion.occurrences,
+                suggestion.files.len(),
+                suggestion.classes.join(" ")
+            ));
+        }
+        text
+    }
+}
+
+/// 分析会话中已转换的所有文件（按路径顺序，结果与转换顺序无关）
+pub fn find_components(session: &ConfigSession, options: ComponentOptions) -> ComponentAnalysis {
+    analyze(&session.reports_by_path(), options)
+}
+
+/// 同一文件中类集合相同的元素合并计数
+struct Element<'a> {
+    classes: BTreeSet<&'a str>,
+    count: usize,
+    file: &'a Path,
+}
+
+fn analyze(reports: &[&FileReport], options: ComponentOptions) -> ComponentAnalysis {
+    let min_classes = options.min_classes.max(2);
+    let elements = collect_elements(reports, min_classes);
+
+    // 候选：各元素的完整集合及两两交集
+    let mut candidates: HashSet<BTreeSet<&str>> = HashSet::new();
+    for (i, a) in elements.iter().enumerate() {
+        candidates.insert(a.classes.clone());
+        for b in &elements[i + 1..] {
+            let common: BTreeSet<&str> = a.classes.intersection(&b.classes).copied().collect();
+            if common.len() >= min_classes {
+                candidates.insert(common);
+            }
+        }
+    }
+
+    let frequent: Vec<(BTreeSet<&str>, usize, Vec<PathBuf>)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let mut occurrences = 0;
+            let mut files: Vec<PathBuf> = Vec::new();
+            for element in elements.iter().filter(|e| candidate.is_subset(&e.classes)) {
+                occurrences += element.count;
+                if !files.iter().any(|file| file == element.file) {
+                    files.push(element.file.to_path_buf());
+                }
+            }
+            (occurrences >= options.min_occurrences).then_some((candidate, occurrences, files))
+        })
+        .collect();
+
+    // 去掉被出现次数相同的更大组合覆盖的组合
+    let mut closed: Vec<(BTreeSet<&str>, usize, Vec<PathBuf>)> = frequent
+        .iter()
+        .filter(|(set, occurrences, _)| {
+            !frequent.iter().any(|(other, other_occurrences, _)| {
+                other_occurrences == occurrences && other.len() > set.len() && set.is_subset(other)
+            })
+        })
+        .cloned()
+        .collect();
+
+    closed.sort_by(|(a, a_count, _), (b, b_count, _)| {
+        (b_count * b.len())
+            .cmp(&(a_count * a.len()))
+            .then_with(|| b.len().cmp(&a.len()))
+            .then_with(|| a.cmp(b))
+    });
+    closed.truncate(options.max_suggestions);
+
+    let naming = create_naming_strategy(NamingMode::Readable);
+    let suggestions = closed
+        .into_iter()
+        .map(|(set, occurrences, files)| {
+            let classes: Vec<String> = set.into_iter().map(str::to_string).collect();
+            ComponentSuggestion {
+                name: naming.generate_name(&classes),
+                classes,
+                occurrences,
+                files,
+            }
+        })
+        .collect();
+    ComponentAnalysis { suggestions }
+}
+
+/// 提取各元素中实际转换了的类（排除无法转换和原样保留的类）
+fn collect_elements<'a>(reports: &[&'a FileReport], min_classes: usize) -> Vec<Element<'a>> {
+    let mut elements: Vec<Element<'a>> = Vec::new();
+    for report in reports {
+        let mut index: HashMap<BTreeSet<&str>, usize> = HashMap::new();
+        for (original, generated) in &report.class_map {
+            let kept: HashSet<&str> = generated.split_whitespace().collect();
+            let classes: BTreeSet<&str> = original
+                .split_whitespace()
+                .filter(|class| !kept.contains(class))
+                .filter(|class| !report.unknown_classes.contains_key(*class))
+                .collect();
+            if classes.len() < min_classes {
+                continue;
+            }
+            let count = report.class_usage.get(original).copied().unwrap_or(1);
+            match index.get(&classes) {
+                Some(&i) => elements[i].count += count,
+                None => {
+                    index.insert(classes.clone(), elements.len());
+                    elements.push(Element {
+                        classes,
+                        count,
+                        file: &report.path,
+                    });
+                }
+            }
+        }
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+
+    fn file_report(path: &str, elements: &[(&str, &str, usize)]) -> FileReport {
+        FileReport {
+            path: PathBuf::from(path),
+            classes_before: 0,
+            classes_after: 0,
+            unknown_classes: IndexMap::new(),
+            css_bytes: 0,
+            element_tree: None,
+            class_map: elements
+                .iter()
+                .map(|(original, generated, _)| (original.to_string(), generated.to_string()))
+                .collect(),
+            class_usage: elements
+                .iter()
+                .map(|(original, _, count)| (original.to_string(), *count))
+                .collect(),
+            naming_stats: Default::default(),
+            components: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_repeated_combinations_across_files() {
+        let a = file_report(
+            "a.tsx",
+            &[
+                ("px-4 py-2 rounded bg-blue-500", "c_1", 2),
+                ("px-4 py-2 rounded bg-red-500 group", "c_2 group", 1),
+                ("flex items-center gap-2", "c_3", 1),
+            ],
+        );
+        let b = file_report(
+            "b.tsx",
+            &[
+                ("rounded px-4 py-2 bg-blue-500", "c_1", 1),
+                ("flex items-center gap-2 p-4", "c_4", 1),
+            ],
+        );
+        let analysis = analyze(&[&a, &b], ComponentOptions::default());
+
+        let classes: Vec<Vec<&str>> = analysis
+            .suggestions
+            .iter()
+            .map(|s| s.classes.iter().map(String::as_str).collect())
+            .collect();
+        // 按钮组合出现 4 次；带 bg-blue-500 的完整组合只出现 3 次，同样保留；
+        // `group` 原样保留，不参与组合
+        assert_eq!(
+            classes,
+            [
+                vec!["bg-blue-500", "px-4", "py-2", "rounded"],
+                vec!["px-4", "py-2", "rounded"],
+            ]
+        );
+        assert_eq!(analysis.suggestions[1].occurrences, 4);
+        assert_eq!(
+            analysis.suggestions[1].files,
+            [PathBuf::from("a.tsx"), PathBuf::from("b.tsx")]
+        );
+        assert_eq!(analysis.suggestions[1].name, "px4_py2_rounded");
+
+        // flex 组合只出现 2 次，低于默认阈值
+        let loose = analyze(
+            &[&a, &b],
+            ComponentOptions {
+                min_occurrences: 2,
+                ..Default::default()
+            },
+        );
+        assert!(loose
+            .suggestions
+            .iter()
+            .any(|s| s.classes == ["flex", "gap-2", "items-center"]));
+    }
+
+    #[test]
+    fn test_json_and_summary_output() {
+        let a = file_report("a.tsx", &[("p-4 m-2 text-center", "c_1", 3)]);
+        let analysis = analyze(&[&a], ComponentOptions::default());
+
+        let json: serde_json::Value = serde_json::from_str(&analysis.to_json()).unwrap();
+        assert_eq!(json["suggestions"][0]["occurrences"], 3);
+        assert_eq!(json["suggestions"][0]["classes"][0], "m-2");
+        assert_eq!(
+            analysis.summary(),
+            "发现 1 个可提取的类组合：\n1. .m2_p4_textcent（3 个元素，1 个文件）：m-2 p-4 text-center\n"
+        );
+        assert_eq!(
+            ComponentAnalysis::default().summary(),
+            "没有发现重复出现的类组合\n"
+        );
+    }
+}
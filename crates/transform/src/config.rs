@@ -0,0 +1,1691 @@
+//! 配置文件支持（`headwind.toml` / `headwind.json`）
+//!
+//! `HeadwindConfig` 与 `TransformOptions` 一一对应（格式化钩子除外），另含主题变量、
+//! safelist 与 include / exclude glob。字段名与 WASM 的 options 对象一致（camelCase），
+//! 同一份 JSON 可以直接传给 `transformJsx`。
+//!
+//! 多个来源按「配置文件 < 环境变量 < 调用方传入」逐层覆盖，未设置的字段沿用下层的值。
+//!
+//! ```toml
+//! namingMode = "readable"
+//! include = ["src/**/*.tsx"]
+//! safelist = ["hidden", "p-4 text-center"]
+//!
+//! [outputMode]
+//! type = "cssModules"
+//! access = "bracket"
+//!
+//! [theme]
+//! "--color-brand" = "#ff5500"
+//!
+//! [darkTheme]
+//! "--color-brand" = "#ffa366"
+//! ```
+
+use crate::session::ConfigSession;
+use crate::{
+    ClassAttrMerge, ClassAttrName, ClassOverride, CssDedup, DarkTheme, HtmlWrapper, CssModuleExtension, CssModulesAccess,
+    EmitOptions, ImportOptions, ImportPosition, ImportQuery, NameScope, OutputMode, ProseClassMode,
+    QuoteStyle, RuntimeHelper, StyleSheetFormat, SyntaxOptions, TransformOptions,
+};
+use headwind_core::{
+    BreakpointStyle, BreakpointUnit, ColorMode, ContainerStyle, CssVariableMode,
+    CssVariableOverrides, HashVersion, MediaQuerySyntax, NamingMode, TailwindVersion, Targets,
+    UnitConversion, UnknownClassMode,
+};
+use headwind_tw_index::{load_versioned_index, CustomUtility, CustomVariant, VersionedIndex};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// 自动发现时查找的配置文件名（同一目录下按顺序优先）
+pub const CONFIG_FILE_NAMES: [&str; 2] = ["headwind.toml", "headwind.json"];
+
+/// 环境变量前缀，如 `HEADWIND_NAMING_MODE=readable`
+pub const ENV_PREFIX: &str = "HEADWIND_";
+
+/// 显式指定配置文件路径的环境变量
+pub const ENV_CONFIG_PATH: &str = "HEADWIND_CONFIG";
+
+/// 可由环境变量覆盖的字段（camelCase 键名）
+const ENV_KEYS: &[&str] = &[
+    "namingMode",
+    "hashVersion",
+    "hashSalt",
+    "hashLength",
+    "nameScope",
+    "outputMode",
+    "cssModuleDts",
+    "cssVariables",
+    "unknownClasses",
+    "normalizeClasses",
+    "colorMode",
+    "colorMix",
+    "resolveSpacing",
+    "breakpointUnit",
+    "mediaQuerySyntax",
+    "containerCenter",
+    "containerPadding",
+    "unitConversion",
+    "rootFontSize",
+    "elementTree",
+    "htmlWrapper",
+    "cssComments",
+    "cssDedup",
+    "preserveFormatting",
+    "recoverParseErrors",
+    "decorators",
+    "importAttributes",
+    "jsxPragma",
+    "quoteStyle",
+    "jsxQuoteStyle",
+    "importQuoteStyle",
+    "importQuery",
+    "cssModuleExtension",
+    "importPosition",
+    "cssFileName",
+    "reservedClassNames",
+    "classFunctions",
+    "classAttrMerge",
+    "componentClassAttrs",
+    "classAttrName",
+    "proseClasses",
+    "spreadMergeHelper",
+    "fallbackIndex",
+    "targetTailwindVersion",
+    "safelist",
+    "include",
+    "exclude",
+];
+
+/// Headwind 配置
+///
+/// 所有转换选项均为 `Option`，`None` 表示未设置、由下层来源或默认值决定。
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(from = "RawConfig")]
+pub struct HeadwindConfig {
+    pub naming_mode: Option<NamingMode>,
+    pub hash_version: Option<HashVersion>,
+    pub hash_salt: Option<String>,
+    pub hash_length: Option<usize>,
+    pub name_scope: Option<NameScope>,
+    pub output_mode: Option<OutputMode>,
+    pub css_module_dts: Option<bool>,
+    pub css_variables: Option<CssVariableMode>,
+    pub css_variable_overrides: Option<CssVariableOverrides>,
+    pub unknown_classes: Option<UnknownClassMode>,
+    pub normalize_classes: Option<bool>,
+    pub color_mode: Option<ColorMode>,
+    pub color_mix: Option<bool>,
+    pub preflight: Option<bool>,
+    pub split_css: Option<bool>,
+    pub resolve_spacing: Option<bool>,
+    pub breakpoint_unit: Option<BreakpointUnit>,
+    pub media_query_syntax: Option<MediaQuerySyntax>,
+    pub container_center: Option<bool>,
+    pub container_padding: Option<String>,
+    pub unit_conversion: Option<UnitConversion>,
+    pub root_font_size: Option<u32>,
+    pub targets: Option<Targets>,
+    pub element_tree: Option<bool>,
+    pub inheritance_report: Option<bool>,
+    pub safe_inheritance_optimization: Option<bool>,
+    pub a11y_audit: Option<bool>,
+    pub html_wrapper: Option<HtmlWrapper>,
+    pub css_comments: Option<bool>,
+    pub css_dedup: Option<CssDedup>,
+    pub preserve_formatting: Option<bool>,
+    pub recover_parse_errors: Option<bool>,
+    pub decorators: Option<bool>,
+    pub import_attributes: Option<bool>,
+    pub jsx_pragma: Option<bool>,
+    pub quote_style: Option<QuoteStyle>,
+    pub jsx_quote_style: Option<QuoteStyle>,
+    pub import_quote_style: Option<QuoteStyle>,
+    pub import_query: Option<ImportQuery>,
+    pub css_module_extension: Option<CssModuleExtension>,
+    pub import_position: Option<ImportPosition>,
+    /// 样式文件名模板（见 `ImportOptions::file_name`），如 `"[name].[contenthash].module.css"`
+    pub css_file_name: Option<String>,
+    /// 注入 import 的路径别名（见 `ImportOptions::aliases`），目标目录相对配置文件所在目录
+    ///
+    /// ```toml
+    /// [importAliases]
+    /// "@styles/*" = "src/styles/*"
+    /// ```
+    pub import_aliases: IndexMap<String, String>,
+    pub reserved_class_names: Option<Vec<String>>,
+    pub class_functions: Option<Vec<String>>,
+    pub class_attr_merge: Option<ClassAttrMerge>,
+    pub component_class_attrs: Option<bool>,
+    pub class_attr_name: Option<ClassAttrName>,
+    pub prose_classes: Option<ProseClassMode>,
+    pub spread_merge_helper: Option<String>,
+    pub runtime_helper: Option<RuntimeHelper>,
+    /// 按类手动覆盖生成名或 CSS（见 `TransformOptions::class_overrides`）
+    ///
+    /// ```toml
+    /// [classOverrides."px-4 py-2 rounded"]
+    /// name = "btn"
+    ///
+    /// [classOverrides.shadow-card.css]
+    /// box-shadow = "0 1px 3px #0002"
+    /// ```
+    pub class_overrides: IndexMap<String, ClassOverride>,
+    /// 类名别名（见 `TransformOptions::class_aliases`）
+    ///
+    /// ```toml
+    /// [classAliases]
+    /// btn = "px-4 py-2 rounded-md font-semibold"
+    /// ```
+    pub class_aliases: IndexMap<String, String>,
+    /// 变体别名（见 `TransformOptions::variant_aliases`）
+    ///
+    /// ```toml
+    /// [variantAliases]
+    /// hocus = ["hover", "focus"]
+    /// ```
+    pub variant_aliases: IndexMap<String, Vec<String>>,
+    /// `@custom-variant` 定义的变体（见 `TransformOptions::custom_variants`），
+    /// 值为 `@custom-variant <name>` 之后的定义
+    ///
+    /// ```toml
+    /// [customVariants]
+    /// dark = "&:where(.dark, .dark *)"
+    /// touch = "@media (hover: none)"
+    /// ```
+    pub custom_variants: IndexMap<String, CustomVariant>,
+    /// `@utility` 定义的工具类（见 `TransformOptions::custom_utilities`），值为花括号内的声明
+    ///
+    /// ```toml
+    /// [customUtilities]
+    /// content-auto = "content-visibility: auto"
+    /// "tab-*" = "tab-size: --value(integer)"
+    /// ```
+    pub custom_utilities: IndexMap<String, CustomUtility>,
+    /// 回退索引数据集（JSON）路径，配置文件中的相对路径相对该文件所在目录
+    ///
+    /// 只在读取文件的接口（`transform_file`、`ConfigSession`）中加载，见 `load_fallback_index()`
+    pub fallback_index: Option<PathBuf>,
+    /// 目标 Tailwind 版本（`"3"`、`"3.4"`、`4` 等），未设置时沿用回退索引数据包声明的版本，否则为 v4
+    pub target_tailwind_version: Option<TailwindVersion>,
+    /// 主题变量覆盖（`"--color-brand" = "#ff5500"`），由 `theme_css()` 输出为 `:root` 规则
+    pub theme: IndexMap<String, String>,
+    /// 暗色主题变量（见 `TransformOptions::dark_theme`），`theme_css()` 在 `:root` 之后输出全部暗色值
+    ///
+    /// ```toml
+    /// darkThemeSelector = ".dark"
+    ///
+    /// [darkTheme]
+    /// "--color-white" = "#09090b"
+    /// ```
+    pub dark_theme: IndexMap<String, String>,
+    /// 暗色块的选择器，未设置时使用 `@media (prefers-color-scheme: dark)`
+    pub dark_theme_selector: Option<String>,
+    /// 无论源码中是否出现都要生成的类组合，每项对应一个生成类名
+    pub safelist: Vec<String>,
+    /// 参与转换的文件 glob，相对配置文件所在目录；为空时全部参与
+    pub include: Vec<String>,
+    /// 排除的文件 glob，优先于 `include`
+    pub exclude: Vec<String>,
+    /// 为 true 时不再继承上级目录的配置文件（monorepo 中独立的包）
+    pub root: bool,
+    /// 加载来源的配置文件路径（程序内构造时为 None）；嵌套合并后为最近的配置文件
+    pub source: Option<PathBuf>,
+}
+
+impl HeadwindConfig {
+    /// 解析 TOML 文本
+    pub fn from_toml_str(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| format!("TOML 配置解析失败: {}", e))
+    }
+
+    /// 解析 JSON 文本
+    pub fn from_json_str(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| format!("JSON 配置解析失败: {}", e))
+    }
+
+    /// 读取配置文件，按扩展名选择格式（`.json` 为 JSON，其余按 TOML 解析）
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("无法读取配置文件 {}: {}", path.display(), e))?;
+        let config = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json_str(&text),
+            _ => Self::from_toml_str(&text),
+        }
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        Ok(Self {
+            fallback_index: config.fallback_index.as_deref().map(|index| dir.join(index)),
+            source: Some(path.to_path_buf()),
+            ..config
+        })
+    }
+
+    /// 从 `start`（文件或目录）所在目录开始向上查找配置文件
+    pub fn discover(start: &Path) -> Option<PathBuf> {
+        let dir = if start.is_file() { start.parent()? } else { start };
+        dir.ancestors().find_map(|dir| {
+            CONFIG_FILE_NAMES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.is_file())
+        })
+    }
+
+    /// 读取当前进程的 `HEADWIND_*` 环境变量
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_env_vars(std::env::vars())
+    }
+
+    /// 由 `HEADWIND_*` 键值对构造配置，其余变量忽略
+    ///
+    /// 布尔值接受 `true` / `false`，列表以逗号分隔，
+    /// `HEADWIND_OUTPUT_MODE` 只能指定模式类型（如 `cssModules`），其余参数取默认值。
+    pub fn from_env_vars<I>(vars: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut map = serde_json::Map::new();
+        for (name, value) in vars {
+            let Some(key) = name.strip_prefix(ENV_PREFIX).map(env_key) else {
+                continue;
+            };
+            if !ENV_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            let json = match key.as_str() {
+                "colorMix" | "cssModuleDts" | "normalizeClasses" | "componentClassAttrs" | "resolveSpacing" | "containerCenter" | "elementTree" | "cssComments" | "preserveFormatting" | "recoverParseErrors"
+                | "decorators" | "importAttributes" | "jsxPragma" => value
+                    .parse::<bool>()
+                    .map(serde_json::Value::Bool)
+                    .map_err(|_| format!("环境变量 {} 应为 true 或 false", name))?,
+                "reservedClassNames" | "classFunctions" | "safelist" | "include" | "exclude" => value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| serde_json::Value::String(item.to_string()))
+                    .collect(),
+                "rootFontSize" | "hashLength" => value
+                    .parse::<u32>()
+                    .map(serde_json::Value::from)
+                    .map_err(|_| format!("环境变量 {} 应为正整数", name))?,
+                "outputMode" => serde_json::json!({ "type": value }),
+                _ => serde_json::Value::String(value),
+            };
+            map.insert(key, json);
+        }
+        serde_json::from_value(serde_json::Value::Object(map))
+            .map_err(|e| format!("环境变量配置无效: {}", e))
+    }
+
+    /// 以 `overrides` 覆盖当前配置：已设置的字段胜出，两套主题、`class_overrides` 与各类别名按键合并，
+    /// `safelist` 取并集
+    pub fn merge(self, overrides: HeadwindConfig) -> HeadwindConfig {
+        let mut theme = self.theme;
+        theme.extend(overrides.theme);
+        let mut dark_theme = self.dark_theme;
+        dark_theme.extend(overrides.dark_theme);
+        let mut class_overrides = self.class_overrides;
+        class_overrides.extend(overrides.class_overrides);
+        let mut class_aliases = self.class_aliases;
+        class_aliases.extend(overrides.class_aliases);
+        let mut import_aliases = self.import_aliases;
+        import_aliases.extend(overrides.import_aliases);
+        let mut variant_aliases = self.variant_aliases;
+        variant_aliases.extend(overrides.variant_aliases);
+        let mut custom_variants = self.custom_variants;
+        custom_variants.extend(overrides.custom_variants);
+        let mut custom_utilities = self.custom_utilities;
+        custom_utilities.extend(overrides.custom_utilities);
+        let mut safelist = self.safelist;
+        for entry in overrides.safelist {
+            if !safelist.contains(&entry) {
+                safelist.push(entry);
+            }
+        }
+        HeadwindConfig {
+            naming_mode: overrides.naming_mode.or(self.naming_mode),
+            hash_version: overrides.hash_version.or(self.hash_version),
+            hash_salt: overrides.hash_salt.or(self.hash_salt),
+            hash_length: overrides.hash_length.or(self.hash_length),
+            name_scope: overrides.name_scope.or(self.name_scope),
+            output_mode: overrides.output_mode.or(self.output_mode),
+            css_module_dts: overrides.css_module_dts.or(self.css_module_dts),
+            css_variables: overrides.css_variables.or(self.css_variables),
+            css_variable_overrides: overrides.css_variable_overrides.or(self.css_variable_overrides),
+            unknown_classes: overrides.unknown_classes.or(self.unknown_classes),
+            normalize_classes: overrides.normalize_classes.or(self.normalize_classes),
+            color_mode: overrides.color_mode.or(self.color_mode),
+            color_mix: overrides.color_mix.or(self.color_mix),
+            preflight: overrides.preflight.or(self.preflight),
+            split_css: overrides.split_css.or(self.split_css),
+            resolve_spacing: overrides.resolve_spacing.or(self.resolve_spacing),
+            breakpoint_unit: overrides.breakpoint_unit.or(self.breakpoint_unit),
+            media_query_syntax: overrides.media_query_syntax.or(self.media_query_syntax),
+            container_center: overrides.container_center.or(self.container_center),
+            container_padding: overrides.container_padding.or(self.container_padding),
+            unit_conversion: overrides.unit_conversion.or(self.unit_conversion),
+            root_font_size: overrides.root_font_size.or(self.root_font_size),
+            targets: overrides.targets.or(self.targets),
+            element_tree: overrides.element_tree.or(self.element_tree),
+            inheritance_report: overrides.inheritance_report.or(self.inheritance_report),
+            safe_inheritance_optimization: overrides
+                .safe_inheritance_optimization
+                .or(self.safe_inheritance_optimization),
+            a11y_audit: overrides.a11y_audit.or(self.a11y_audit),
+            html_wrapper: overrides.html_wrapper.or(self.html_wrapper),
+            css_comments: overrides.css_comments.or(self.css_comments),
+            css_dedup: overrides.css_dedup.or(self.css_dedup),
+            preserve_formatting: overrides.preserve_formatting.or(self.preserve_formatting),
+            recover_parse_errors: overrides.recover_parse_errors.or(self.recover_parse_errors),
+            decorators: overrides.decorators.or(self.decorators),
+            import_attributes: overrides.import_attributes.or(self.import_attributes),
+            jsx_pragma: overrides.jsx_pragma.or(self.jsx_pragma),
+            quote_style: overrides.quote_style.or(self.quote_style),
+            jsx_quote_style: overrides.jsx_quote_style.or(self.jsx_quote_style),
+            import_quote_style: overrides.import_quote_style.or(self.import_quote_style),
+            import_query: overrides.import_query.or(self.import_query),
+            css_module_extension: overrides.css_module_extension.or(self.css_module_extension),
+            import_position: overrides.import_position.or(self.import_position),
+            css_file_name: overrides.css_file_name.or(self.css_file_name),
+            import_aliases,
+            reserved_class_names: overrides.reserved_class_names.or(self.reserved_class_names),
+            class_functions: overrides.class_functions.or(self.class_functions),
+            class_attr_merge: overrides.class_attr_merge.or(self.class_attr_merge),
+            component_class_attrs: overrides.component_class_attrs.or(self.component_class_attrs),
+            class_attr_name: overrides.class_attr_name.or(self.class_attr_name),
+            prose_classes: overrides.prose_classes.or(self.prose_classes),
+            spread_merge_helper: overrides.spread_merge_helper.or(self.spread_merge_helper),
+            runtime_helper: overrides.runtime_helper.or(self.runtime_helper),
+            class_overrides,
+            class_aliases,
+            variant_aliases,
+            custom_variants,
+            custom_utilities,
+            fallback_index: overrides.fallback_index.or(self.fallback_index),
+            target_tailwind_version: overrides
+                .target_tailwind_version
+                .or(self.target_tailwind_version),
+            theme,
+            dark_theme,
+            dark_theme_selector: overrides.dark_theme_selector.or(self.dark_theme_selector),
+            safelist,
+            include: non_empty_or(overrides.include, self.include),
+            exclude: non_empty_or(overrides.exclude, self.exclude),
+            root: self.root,
+            source: overrides.source.or(self.source),
+        }
+    }
+
+    /// 解析 `file` 适用的配置：配置文件 < 环境变量 < `overrides`
+    ///
+    /// 配置文件依次取 `config_path`、`HEADWIND_CONFIG`；都未指定时从 `file` 向上查找，
+    /// 沿途的配置文件由外到内逐层合并（见 `ConfigSession`）。找不到配置文件不算错误。
+    pub fn resolve(
+        file: &Path,
+        config_path: Option<&Path>,
+        overrides: HeadwindConfig,
+    ) -> Result<Self, String> {
+        match config_path {
+            Some(path) => Ok(Self::load(path)?.merge(Self::from_env()?).merge(overrides)),
+            None => Ok(ConfigSession::new(overrides)?.resolve(file)?.config),
+        }
+    }
+
+    /// 转换为 `TransformOptions`，未设置的字段取默认值
+    pub fn to_transform_options(&self) -> TransformOptions {
+        let defaults = TransformOptions::default();
+        TransformOptions {
+            naming_mode: self.naming_mode.unwrap_or(defaults.naming_mode),
+            hash_version: self.hash_version.unwrap_or(defaults.hash_version),
+            hash_salt: self.hash_salt.clone().unwrap_or(defaults.hash_salt),
+            hash_length: self.hash_length.unwrap_or(defaults.hash_length),
+            name_scope: self.name_scope.unwrap_or(defaults.name_scope),
+            output_mode: self.output_mode.clone().unwrap_or(defaults.output_mode),
+            css_module_dts: self.css_module_dts.unwrap_or(defaults.css_module_dts),
+            css_variables: self.css_variables.unwrap_or(defaults.css_variables),
+            css_variable_overrides: self
+                .css_variable_overrides
+                .unwrap_or(defaults.css_variable_overrides),
+            unknown_classes: self.unknown_classes.unwrap_or(defaults.unknown_classes),
+            normalize_classes: self.normalize_classes.unwrap_or(defaults.normalize_classes),
+            color_mode: self.color_mode.unwrap_or(defaults.color_mode),
+            color_mix: self.color_mix.unwrap_or(defaults.color_mix),
+            preflight: self.preflight.unwrap_or(defaults.preflight),
+            split_css: self.split_css.unwrap_or(defaults.split_css),
+            dark_theme: self.dark_theme(),
+            resolve_spacing: self.resolve_spacing.unwrap_or(defaults.resolve_spacing),
+            breakpoints: BreakpointStyle {
+                unit: self.breakpoint_unit.unwrap_or(defaults.breakpoints.unit),
+                syntax: self
+                    .media_query_syntax
+                    .unwrap_or(defaults.breakpoints.syntax),
+            },
+            container: ContainerStyle {
+                center: self.container_center.unwrap_or(defaults.container.center),
+                padding: self.container_padding.clone().or(defaults.container.padding),
+            },
+            unit_conversion: self.unit_conversion.unwrap_or(defaults.unit_conversion),
+            root_font_size: self.root_font_size.unwrap_or(defaults.root_font_size),
+            targets: self.targets.unwrap_or(defaults.targets),
+            element_tree: self.element_tree.unwrap_or(defaults.element_tree),
+            inheritance_report: self.inheritance_report.unwrap_or(defaults.inheritance_report),
+            safe_inheritance_optimization: self
+                .safe_inheritance_optimization
+                .unwrap_or(defaults.safe_inheritance_optimization),
+            a11y_audit: self.a11y_audit.unwrap_or(defaults.a11y_audit),
+            html_wrapper: self.html_wrapper.unwrap_or(defaults.html_wrapper),
+            css_comments: self.css_comments.unwrap_or(defaults.css_comments),
+            css_dedup: self.css_dedup.unwrap_or(defaults.css_dedup),
+            preserve_formatting: self
+                .preserve_formatting
+                .unwrap_or(defaults.preserve_formatting),
+            emit: EmitOptions {
+                quote_style: self.quote_style.unwrap_or(defaults.emit.quote_style),
+                jsx_quote_style: self.jsx_quote_style.unwrap_or(defaults.emit.jsx_quote_style),
+            },
+            imports: ImportOptions {
+                quote_style: self.import_quote_style.or(defaults.imports.quote_style),
+                query: self.import_query.unwrap_or(defaults.imports.query),
+                css_module_extension: self
+                    .css_module_extension
+                    .unwrap_or(defaults.imports.css_module_extension),
+                position: self.import_position.unwrap_or(defaults.imports.position),
+                file_name: self.css_file_name.clone().or(defaults.imports.file_name),
+                aliases: self.import_aliases.clone(),
+            },
+            formatter: None,
+            on_class_collected: None,
+            on_rule_generated: None,
+            #[cfg(feature = "ecmascript")]
+            ast_extensions: Vec::new(),
+            reserved_class_names: self
+                .reserved_class_names
+                .clone()
+                .unwrap_or(defaults.reserved_class_names),
+            class_functions: self
+                .class_functions
+                .clone()
+                .unwrap_or(defaults.class_functions),
+            class_attr_merge: self.class_attr_merge.unwrap_or(defaults.class_attr_merge),
+            component_class_attrs: self
+                .component_class_attrs
+                .unwrap_or(defaults.component_class_attrs),
+            class_attr_name: self.class_attr_name.unwrap_or(defaults.class_attr_name),
+            prose_classes: self.prose_classes.unwrap_or(defaults.prose_classes),
+            spread_merge_helper: self.spread_merge_helper.clone(),
+            runtime_helper: self.runtime_helper.clone(),
+            class_overrides: self.class_overrides.clone(),
+            class_aliases: self.class_aliases.clone(),
+            variant_aliases: self.variant_aliases.clone(),
+            custom_variants: self.custom_variants.clone(),
+            custom_utilities: self.custom_utilities.clone(),
+            conversion_cache: None,
+            fallback_index: None,
+            target_tailwind_version: self
+                .target_tailwind_version
+                .unwrap_or(defaults.target_tailwind_version),
+            recover_parse_errors: self
+                .recover_parse_errors
+                .unwrap_or(defaults.recover_parse_errors),
+            syntax: SyntaxOptions {
+                decorators: self.decorators.unwrap_or(defaults.syntax.decorators),
+                import_attributes: self
+                    .import_attributes
+                    .unwrap_or(defaults.syntax.import_attributes),
+                jsx_pragma: self.jsx_pragma.unwrap_or(defaults.syntax.jsx_pragma),
+            },
+        }
+    }
+
+    /// 读取 `fallback_index` 指定的数据集，未配置时返回 None
+    ///
+    /// 数据集可以是 `class` + `declarations` 或官方映射的 `class` + `css` 格式的数组，
+    /// 也可以是带 `tailwindVersion` 的数据包（见 `load_versioned_index`）
+    pub fn load_fallback_index(&self) -> Result<Option<VersionedIndex>, String> {
+        let Some(path) = &self.fallback_index else {
+            return Ok(None);
+        };
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("无法读取回退索引 {}: {}", path.display(), e))?;
+        let index = load_versioned_index(&text)
+            .map_err(|e| format!("回退索引 {} 解析失败: {}", path.display(), e))?;
+        Ok(Some(index))
+    }
+
+    /// 带上回退索引的转换选项：配置未指定目标版本时使用数据包声明的版本
+    pub(crate) fn with_fallback_index(
+        &self,
+        options: TransformOptions,
+        fallback_index: Option<VersionedIndex>,
+    ) -> TransformOptions {
+        let Some(artifact) = fallback_index else {
+            return options;
+        };
+        let target_tailwind_version = self
+            .target_tailwind_version
+            .or(artifact.version)
+            .unwrap_or(options.target_tailwind_version);
+        TransformOptions {
+            fallback_index: Some(artifact.index),
+            target_tailwind_version,
+            ..options
+        }
+    }
+
+    /// 主题变量的 `:root` 规则及暗色主题块，未配置时为空字符串
+    pub fn theme_css(&self) -> String {
+        let mut css = String::new();
+        if !self.theme.is_empty() {
+            css.push_str(":root {\n");
+            for (name, value) in &self.theme {
+                css.push_str(&format!("  {}: {};\n", name, value));
+            }
+            css.push_str("}\n");
+        }
+        let dark = self.dark_theme().css(|_| true);
+        if !dark.is_empty() {
+            css.push_str(&dark);
+            css.push('\n');
+        }
+        css
+    }
+
+    fn dark_theme(&self) -> DarkTheme {
+        DarkTheme {
+            variables: self.dark_theme.clone(),
+            selector: self.dark_theme_selector.clone(),
+        }
+    }
+
+    /// 主题变量与 safelist 的 CSS，应与各文件的转换结果一起输出一次
+    pub fn prelude_css(&self) -> String {
+        let mut css = self.theme_css();
+        if !self.safelist.is_empty() {
+            // 回退索引读取失败时转换文件会报告错误，这里只跳过索引
+            let options = self.with_fallback_index(
+                self.to_transform_options(),
+                self.load_fallback_index().ok().flatten(),
+            );
+            let mut collector = crate::create_collector(&options, None);
+            for classes in &self.safelist {
+                collector.process_classes(classes);
+            }
+            if !css.is_empty() {
+                css.push('\n');
+            }
+            css.push_str(&collector.combined_css());
+        }
+        css
+    }
+
+    /// `file` 是否应参与转换（按 include / exclude 匹配）
+    ///
+    /// 路径相对最近的配置文件所在目录匹配；不在该目录下或没有来源文件时按原路径匹配。
+    pub fn is_included(&self, file: &Path) -> bool {
+        let path = self.relative_path(file);
+        if self.exclude.iter().any(|pattern| glob_match(pattern, &path)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|pattern| glob_match(pattern, &path))
+    }
+
+    /// `file` 相对配置文件所在目录的路径（`/` 分隔），用于 glob 匹配和 PerFile 命名，
+    /// 保证不同机器上的检出目录不影响结果
+    pub fn relative_path(&self, file: &Path) -> String {
+        let relative = self
+            .source
+            .as_deref()
+            .and_then(Path::parent)
+            .and_then(|root| file.strip_prefix(root).ok())
+            .unwrap_or(file);
+        relative.to_string_lossy().replace('\\', "/")
+    }
+}
+
+fn non_empty_or(overrides: Vec<String>, base: Vec<String>) -> Vec<String> {
+    if overrides.is_empty() {
+        base
+    } else {
+        overrides
+    }
+}
+
+/// `NAMING_MODE` → `namingMode`
+fn env_key(name: &str) -> String {
+    let mut key = String::with_capacity(name.len());
+    let mut upper = false;
+    for ch in name.chars() {
+        if ch == '_' {
+            upper = true;
+        } else if upper {
+            key.push(ch.to_ascii_uppercase());
+            upper = false;
+        } else {
+            key.push(ch.to_ascii_lowercase());
+        }
+    }
+    key
+}
+
+/// 简单 glob 匹配：`*` 与 `?` 不跨越 `/`，`**/` 匹配零或多级目录
+fn glob_match(pattern: &str, path: &str) -> bool {
+    fn matches(p: &[u8], s: &[u8]) -> bool {
+        match p {
+            [] => s.is_empty(),
+            [b'*', b'*', b'/', rest @ ..] => {
+                (0..=s.len()).any(|i| (i == 0 || s[i - 1] == b'/') && matches(rest, &s[i..]))
+            }
+            [b'*', b'*', rest @ ..] => (0..=s.len()).any(|i| matches(rest, &s[i..])),
+            [b'*', rest @ ..] => (0..=s.len())
+                .take_while(|&i| i == 0 || s[i - 1] != b'/')
+                .any(|i| matches(rest, &s[i..])),
+            [b'?', rest @ ..] => matches!(s.first(), Some(&c) if c != b'/') && matches(rest, &s[1..]),
+            [c, rest @ ..] => s.first() == Some(c) && matches(rest, &s[1..]),
+        }
+    }
+    matches(pattern.as_bytes(), path.as_bytes())
+}
+
+// ── serde 镜像类型（camelCase，与 WASM options 对象一致）──────────
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConfig {
+    naming_mode: Option<RawNamingMode>,
+    hash_version: Option<RawHashVersion>,
+    hash_salt: Option<String>,
+    hash_length: Option<usize>,
+    name_scope: Option<RawNameScope>,
+    output_mode: Option<RawOutputMode>,
+    css_module_dts: Option<bool>,
+    css_variables: Option<RawCssVariableMode>,
+    css_variable_overrides: Option<RawCssVariableOverrides>,
+    unknown_classes: Option<RawUnknownClassMode>,
+    normalize_classes: Option<bool>,
+    color_mode: Option<RawColorMode>,
+    color_mix: Option<bool>,
+    preflight: Option<bool>,
+    split_css: Option<bool>,
+    resolve_spacing: Option<bool>,
+    breakpoint_unit: Option<RawBreakpointUnit>,
+    media_query_syntax: Option<RawMediaQuerySyntax>,
+    container_center: Option<bool>,
+    container_padding: Option<String>,
+    unit_conversion: Option<RawUnitConversion>,
+    root_font_size: Option<u32>,
+    targets: Option<Targets>,
+    element_tree: Option<bool>,
+    inheritance_report: Option<bool>,
+    safe_inheritance_optimization: Option<bool>,
+    a11y_audit: Option<bool>,
+    html_wrapper: Option<RawHtmlWrapper>,
+    css_comments: Option<bool>,
+    css_dedup: Option<RawCssDedup>,
+    preserve_formatting: Option<bool>,
+    recover_parse_errors: Option<bool>,
+    decorators: Option<bool>,
+    import_attributes: Option<bool>,
+    jsx_pragma: Option<bool>,
+    quote_style: Option<RawQuoteStyle>,
+    jsx_quote_style: Option<RawQuoteStyle>,
+    import_quote_style: Option<RawQuoteStyle>,
+    import_query: Option<RawImportQuery>,
+    css_module_extension: Option<RawCssModuleExtension>,
+    import_position: Option<RawImportPosition>,
+    css_file_name: Option<String>,
+    #[serde(default)]
+    import_aliases: IndexMap<String, String>,
+    reserved_class_names: Option<Vec<String>>,
+    class_functions: Option<Vec<String>>,
+    class_attr_merge: Option<RawClassAttrMerge>,
+    component_class_attrs: Option<bool>,
+    class_attr_name: Option<RawClassAttrName>,
+    prose_classes: Option<RawProseClassMode>,
+    spread_merge_helper: Option<String>,
+    runtime_helper: Option<RawRuntimeHelper>,
+    #[serde(default)]
+    class_overrides: IndexMap<String, RawClassOverride>,
+    #[serde(default)]
+    class_aliases: IndexMap<String, String>,
+    #[serde(default)]
+    variant_aliases: IndexMap<String, Vec<String>>,
+    #[serde(default)]
+    custom_variants: IndexMap<String, RawCustomVariant>,
+    #[serde(default)]
+    custom_utilities: IndexMap<String, RawCustomUtility>,
+    fallback_index: Option<PathBuf>,
+    target_tailwind_version: Option<RawTailwindVersion>,
+    #[serde(default)]
+    theme: IndexMap<String, String>,
+    #[serde(default)]
+    dark_theme: IndexMap<String, String>,
+    dark_theme_selector: Option<String>,
+    #[serde(default)]
+    safelist: Vec<String>,
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    root: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawNamingMode {
+    Hash,
+    Readable,
+    CamelCase,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawHashVersion {
+    V1,
+    V2,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawNameScope {
+    Global,
+    PerFile,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum RawOutputMode {
+    #[serde(rename_all = "camelCase")]
+    Global {
+        #[serde(default)]
+        import_path: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    CssModules {
+        #[serde(default = "default_binding")]
+        binding_name: String,
+        #[serde(default)]
+        import_path: Option<String>,
+        #[serde(default)]
+        access: RawCssModulesAccess,
+    },
+    #[serde(rename_all = "camelCase")]
+    DataAttribute {
+        #[serde(default = "default_data_attr")]
+        attr: String,
+        #[serde(default)]
+        import_path: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ShadowDom {
+        #[serde(default)]
+        format: RawStyleSheetFormat,
+        #[serde(default = "default_sheet_binding")]
+        binding_name: String,
+        #[serde(default)]
+        import_path: Option<String>,
+        #[serde(default)]
+        adopt: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ReactNative {
+        #[serde(default = "default_binding")]
+        binding_name: String,
+    },
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+enum RawStyleSheetFormat {
+    #[default]
+    Constructable,
+    StyleElement,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+enum RawCssModulesAccess {
+    #[default]
+    Dot,
+    Bracket,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawCssVariableMode {
+    Var,
+    Inline,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCssVariableOverrides {
+    font_size: Option<RawCssVariableMode>,
+    font_family: Option<RawCssVariableMode>,
+    shadow: Option<RawCssVariableMode>,
+    blur: Option<RawCssVariableMode>,
+    aspect: Option<RawCssVariableMode>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawUnknownClassMode {
+    Remove,
+    Preserve,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawColorMode {
+    Hex,
+    Oklch,
+    Hsl,
+    Var,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawBreakpointUnit {
+    Rem,
+    Px,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawMediaQuerySyntax {
+    Range,
+    Legacy,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawUnitConversion {
+    None,
+    PxToRem,
+    RemToPx,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawQuoteStyle {
+    Double,
+    Single,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawImportQuery {
+    None,
+    Inline,
+    Url,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawCssModuleExtension {
+    Css,
+    Scss,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawImportPosition {
+    Top,
+    AfterImports,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawClassAttrMerge {
+    PreferClassName,
+    PreferClass,
+    Separate,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawHtmlWrapper {
+    Preserve,
+    Document,
+    Fragment,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawCssDedup {
+    None,
+    GroupSelectors,
+    ShareClass,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawClassAttrName {
+    Preserve,
+    ClassName,
+    Class,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum RawProseClassMode {
+    Preserve,
+    Unknown,
+}
+
+/// 版本号可以写成字符串（`"3.4"`、`"v4"`）或数字（`3`）
+#[derive(Deserialize)]
+#[serde(try_from = "RawVersionValue")]
+struct RawTailwindVersion(TailwindVersion);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawVersionValue {
+    Text(String),
+    Number(f64),
+}
+
+impl TryFrom<RawVersionValue> for RawTailwindVersion {
+    type Error = String;
+
+    fn try_from(value: RawVersionValue) -> Result<Self, String> {
+        let text = match value {
+            RawVersionValue::Text(text) => text,
+            RawVersionValue::Number(number) => number.to_string(),
+        };
+        TailwindVersion::parse(&text)
+            .map(RawTailwindVersion)
+            .ok_or_else(|| format!("不支持的 Tailwind 版本: {}", text))
+    }
+}
+
+/// 自定义变体写成 `@custom-variant` 的定义字符串，读取时解析
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct RawCustomVariant(CustomVariant);
+
+impl TryFrom<String> for RawCustomVariant {
+    type Error = String;
+
+    fn try_from(definition: String) -> Result<Self, String> {
+        CustomVariant::parse(&definition)
+            .map(RawCustomVariant)
+            .map_err(|e| format!("无效的自定义变体 {:?}: {}", definition, e))
+    }
+}
+
+/// 自定义工具类写成 `@utility` 花括号内的声明字符串，读取时解析
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct RawCustomUtility(CustomUtility);
+
+impl TryFrom<String> for RawCustomUtility {
+    type Error = String;
+
+    fn try_from(body: String) -> Result<Self, String> {
+        CustomUtility::parse(&body)
+            .map(RawCustomUtility)
+            .map_err(|e| format!("无效的自定义工具类 {:?}: {}", body, e))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRuntimeHelper {
+    #[serde(default = "default_helper_name")]
+    name: String,
+    #[serde(default = "default_helper_path")]
+    import_path: String,
+}
+
+#[derive(Deserialize)]
+struct RawClassOverride {
+    name: Option<String>,
+    css: Option<IndexMap<String, String>>,
+}
+
+fn default_helper_name() -> String {
+    RuntimeHelper::default().name
+}
+
+fn default_helper_path() -> String {
+    RuntimeHelper::default().import_path
+}
+
+fn default_binding() -> String {
+    "styles".to_string()
+}
+
+fn default_data_attr() -> String {
+    "data-hw".to_string()
+}
+
+fn default_sheet_binding() -> String {
+    "sheet".to_string()
+}
+
+// ── 类型转换 ──────────────────────────────────────────────────
+
+impl From<RawConfig> for HeadwindConfig {
+    fn from(raw: RawConfig) -> Self {
+        HeadwindConfig {
+            naming_mode: raw.naming_mode.map(Into::into),
+            hash_version: raw.hash_version.map(Into::into),
+            hash_salt: raw.hash_salt,
+            hash_length: raw.hash_length,
+            name_scope: raw.name_scope.map(Into::into),
+            output_mode: raw.output_mode.map(Into::into),
+            css_module_dts: raw.css_module_dts,
+            css_variables: raw.css_variables.map(Into::into),
+            css_variable_overrides: raw.css_variable_overrides.map(Into::into),
+            unknown_classes: raw.unknown_classes.map(Into::into),
+            normalize_classes: raw.normalize_classes,
+            color_mode: raw.color_mode.map(Into::into),
+            color_mix: raw.color_mix,
+            preflight: raw.preflight,
+            split_css: raw.split_css,
+            resolve_spacing: raw.resolve_spacing,
+            breakpoint_unit: raw.breakpoint_unit.map(Into::into),
+            media_query_syntax: raw.media_query_syntax.map(Into::into),
+            container_center: raw.container_center,
+            container_padding: raw.container_padding,
+            unit_conversion: raw.unit_conversion.map(Into::into),
+            root_font_size: raw.root_font_size,
+            targets: raw.targets,
+            element_tree: raw.element_tree,
+            inheritance_report: raw.inheritance_report,
+            safe_inheritance_optimization: raw.safe_inheritance_optimization,
+            a11y_audit: raw.a11y_audit,
+            html_wrapper: raw.html_wrapper.map(Into::into),
+            css_comments: raw.css_comments,
+            css_dedup: raw.css_dedup.map(Into::into),
+            preserve_formatting: raw.preserve_formatting,
+            recover_parse_errors: raw.recover_parse_errors,
+            decorators: raw.decorators,
+            import_attributes: raw.import_attributes,
+            jsx_pragma: raw.jsx_pragma,
+            quote_style: raw.quote_style.map(Into::into),
+            jsx_quote_style: raw.jsx_quote_style.map(Into::into),
+            import_quote_style: raw.import_quote_style.map(Into::into),
+            import_query: raw.import_query.map(Into::into),
+            css_module_extension: raw.css_module_extension.map(Into::into),
+            import_position: raw.import_position.map(Into::into),
+            css_file_name: raw.css_file_name,
+            import_aliases: raw.import_aliases,
+            reserved_class_names: raw.reserved_class_names,
+            class_functions: raw.class_functions,
+            class_attr_merge: raw.class_attr_merge.map(Into::into),
+            component_class_attrs: raw.component_class_attrs,
+            class_attr_name: raw.class_attr_name.map(Into::into),
+            prose_classes: raw.prose_classes.map(Into::into),
+            spread_merge_helper: raw.spread_merge_helper,
+            runtime_helper: raw.runtime_helper.map(|h| RuntimeHelper {
+                name: h.name,
+                import_path: h.import_path,
+            }),
+            class_overrides: raw
+                .class_overrides
+                .into_iter()
+                .map(|(classes, o)| {
+                    let o = ClassOverride {
+                        name: o.name,
+                        css: o.css,
+                    };
+                    (classes, o)
+                })
+                .collect(),
+            class_aliases: raw.class_aliases,
+            variant_aliases: raw.variant_aliases,
+            custom_variants: raw
+                .custom_variants
+                .into_iter()
+                .map(|(name, variant)| (name, variant.0))
+                .collect(),
+            custom_utilities: raw
+                .custom_utilities
+                .into_iter()
+                .map(|(name, utility)| (name, utility.0))
+                .collect(),
+            fallback_index: raw.fallback_index,
+            target_tailwind_version: raw.target_tailwind_version.map(|v| v.0),
+            theme: raw.theme,
+            dark_theme: raw.dark_theme,
+            dark_theme_selector: raw.dark_theme_selector,
+            safelist: raw.safelist,
+            include: raw.include,
+            exclude: raw.exclude,
+            root: raw.root,
+            source: None,
+        }
+    }
+}
+
+impl From<RawNamingMode> for NamingMode {
+    fn from(m: RawNamingMode) -> Self {
+        match m {
+            RawNamingMode::Hash => NamingMode::Hash,
+            RawNamingMode::Readable => NamingMode::Readable,
+            RawNamingMode::CamelCase => NamingMode::CamelCase,
+        }
+    }
+}
+
+impl From<RawHashVersion> for HashVersion {
+    fn from(v: RawHashVersion) -> Self {
+        match v {
+            RawHashVersion::V1 => HashVersion::V1,
+            RawHashVersion::V2 => HashVersion::V2,
+        }
+    }
+}
+
+impl From<RawNameScope> for NameScope {
+    fn from(s: RawNameScope) -> Self {
+        match s {
+            RawNameScope::Global => NameScope::Global,
+            RawNameScope::PerFile => NameScope::PerFile,
+        }
+    }
+}
+
+impl From<RawCssModulesAccess> for CssModulesAccess {
+    fn from(a: RawCssModulesAccess) -> Self {
+        match a {
+            RawCssModulesAccess::Dot => CssModulesAccess::Dot,
+            RawCssModulesAccess::Bracket => CssModulesAccess::Bracket,
+        }
+    }
+}
+
+impl From<RawOutputMode> for OutputMode {
+    fn from(m: RawOutputMode) -> Self {
+        match m {
+            RawOutputMode::Global { import_path } => OutputMode::Global { import_path },
+            RawOutputMode::CssModules {
+                binding_name,
+                import_path,
+                access,
+            } => OutputMode::CssModules {
+                binding_name,
+                import_path,
+                access: access.into(),
+            },
+            RawOutputMode::DataAttribute { attr, import_path } => {
+                OutputMode::DataAttribute { attr, import_path }
+            }
+            RawOutputMode::ShadowDom {
+                format,
+                binding_name,
+                import_path,
+                adopt,
+            } => OutputMode::ShadowDom {
+                format: format.into(),
+                binding_name,
+                import_path,
+                adopt,
+            },
+            RawOutputMode::ReactNative { binding_name } => OutputMode::ReactNative { binding_name },
+        }
+    }
+}
+
+impl From<RawStyleSheetFormat> for StyleSheetFormat {
+    fn from(f: RawStyleSheetFormat) -> Self {
+        match f {
+            RawStyleSheetFormat::Constructable => StyleSheetFormat::Constructable,
+            RawStyleSheetFormat::StyleElement => StyleSheetFormat::StyleElement,
+        }
+    }
+}
+
+impl From<RawCssVariableMode> for CssVariableMode {
+    fn from(m: RawCssVariableMode) -> Self {
+        match m {
+            RawCssVariableMode::Var => CssVariableMode::Var,
+            RawCssVariableMode::Inline => CssVariableMode::Inline,
+        }
+    }
+}
+
+impl From<RawCssVariableOverrides> for CssVariableOverrides {
+    fn from(o: RawCssVariableOverrides) -> Self {
+        CssVariableOverrides {
+            font_size: o.font_size.map(Into::into),
+            font_family: o.font_family.map(Into::into),
+            shadow: o.shadow.map(Into::into),
+            blur: o.blur.map(Into::into),
+            aspect: o.aspect.map(Into::into),
+        }
+    }
+}
+
+impl From<RawUnknownClassMode> for UnknownClassMode {
+    fn from(m: RawUnknownClassMode) -> Self {
+        match m {
+            RawUnknownClassMode::Remove => UnknownClassMode::Remove,
+            RawUnknownClassMode::Preserve => UnknownClassMode::Preserve,
+        }
+    }
+}
+
+impl From<RawColorMode> for ColorMode {
+    fn from(m: RawColorMode) -> Self {
+        match m {
+            RawColorMode::Hex => ColorMode::Hex,
+            RawColorMode::Oklch => ColorMode::Oklch,
+            RawColorMode::Hsl => ColorMode::Hsl,
+            RawColorMode::Var => ColorMode::Var,
+        }
+    }
+}
+
+impl From<RawBreakpointUnit> for BreakpointUnit {
+    fn from(u: RawBreakpointUnit) -> Self {
+        match u {
+            RawBreakpointUnit::Rem => BreakpointUnit::Rem,
+            RawBreakpointUnit::Px => BreakpointUnit::Px,
+        }
+    }
+}
+
+impl From<RawUnitConversion> for UnitConversion {
+    fn from(c: RawUnitConversion) -> Self {
+        match c {
+            RawUnitConversion::None => UnitConversion::None,
+            RawUnitConversion::PxToRem => UnitConversion::PxToRem,
+            RawUnitConversion::RemToPx => UnitConversion::RemToPx,
+        }
+    }
+}
+
+impl From<RawMediaQuerySyntax> for MediaQuerySyntax {
+    fn from(s: RawMediaQuerySyntax) -> Self {
+        match s {
+            RawMediaQuerySyntax::Range => MediaQuerySyntax::Range,
+            RawMediaQuerySyntax::Legacy => MediaQuerySyntax::Legacy,
+        }
+    }
+}
+
+impl From<RawQuoteStyle> for QuoteStyle {
+    fn from(q: RawQuoteStyle) -> Self {
+        match q {
+            RawQuoteStyle::Double => QuoteStyle::Double,
+            RawQuoteStyle::Single => QuoteStyle::Single,
+        }
+    }
+}
+
+impl From<RawImportQuery> for ImportQuery {
+    fn from(q: RawImportQuery) -> Self {
+        match q {
+            RawImportQuery::None => ImportQuery::None,
+            RawImportQuery::Inline => ImportQuery::Inline,
+            RawImportQuery::Url => ImportQuery::Url,
+        }
+    }
+}
+
+impl From<RawCssModuleExtension> for CssModuleExtension {
+    fn from(e: RawCssModuleExtension) -> Self {
+        match e {
+            RawCssModuleExtension::Css => CssModuleExtension::Css,
+            RawCssModuleExtension::Scss => CssModuleExtension::Scss,
+        }
+    }
+}
+
+impl From<RawImportPosition> for ImportPosition {
+    fn from(p: RawImportPosition) -> Self {
+        match p {
+            RawImportPosition::Top => ImportPosition::Top,
+            RawImportPosition::AfterImports => ImportPosition::AfterImports,
+        }
+    }
+}
+
+impl From<RawClassAttrMerge> for ClassAttrMerge {
+    fn from(m: RawClassAttrMerge) -> Self {
+        match m {
+            RawClassAttrMerge::PreferClassName => ClassAttrMerge::PreferClassName,
+            RawClassAttrMerge::PreferClass => ClassAttrMerge::PreferClass,
+            RawClassAttrMerge::Separate => ClassAttrMerge::Separate,
+        }
+    }
+}
+
+impl From<RawHtmlWrapper> for HtmlWrapper {
+    fn from(w: RawHtmlWrapper) -> Self {
+        match w {
+            RawHtmlWrapper::Preserve => HtmlWrapper::Preserve,
+            RawHtmlWrapper::Document => HtmlWrapper::Document,
+            RawHtmlWrapper::Fragment => HtmlWrapper::Fragment,
+        }
+    }
+}
+
+impl From<RawCssDedup> for CssDedup {
+    fn from(d: RawCssDedup) -> Self {
+        match d {
+            RawCssDedup::None => CssDedup::None,
+            RawCssDedup::GroupSelectors => CssDedup::GroupSelectors,
+            RawCssDedup::ShareClass => CssDedup::ShareClass,
+        }
+    }
+}
+
+impl From<RawClassAttrName> for ClassAttrName {
+    fn from(n: RawClassAttrName) -> Self {
+        match n {
+            RawClassAttrName::Preserve => ClassAttrName::Preserve,
+            RawClassAttrName::ClassName => ClassAttrName::ClassName,
+            RawClassAttrName::Class => ClassAttrName::Class,
+        }
+    }
+}
+
+impl From<RawProseClassMode> for ProseClassMode {
+    fn from(m: RawProseClassMode) -> Self {
+        match m {
+            RawProseClassMode::Preserve => ProseClassMode::Preserve,
+            RawProseClassMode::Unknown => ProseClassMode::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_toml_and_json() {
+        let toml = HeadwindConfig::from_toml_str(
+            r##"
+namingMode = "readable"
+colorMix = true
+splitCss = true
+cssFileName = "[name].[contenthash].module.css"
+normalizeClasses = false
+resolveSpacing = true
+breakpointUnit = "px"
+mediaQuerySyntax = "legacy"
+containerCenter = true
+containerPadding = "2rem"
+safelist = ["hidden"]
+targets = { safari = 13, chrome = 90 }
+cssVariableOverrides = { fontSize = "inline", shadow = "var" }
+
+[outputMode]
+type = "cssModules"
+access = "bracket"
+
+[theme]
+"--color-brand" = "#ff5500"
+
+[darkTheme]
+"--color-brand" = "#ffa366"
+
+[classOverrides."px-4 py-2"]
+name = "btn"
+
+[classOverrides.shadow-card.css]
+box-shadow = "0 1px 3px #0002"
+
+[classAliases]
+btn = "px-4 py-2 rounded-md"
+
+[variantAliases]
+hocus = ["hover", "focus"]
+
+[importAliases]
+"@styles/*" = "src/styles/*"
+
+[customVariants]
+dark = "&:where(.dark, .dark *)"
+touch = "(@media (hover: none))"
+
+[customUtilities]
+content-auto = "content-visibility: auto;"
+"tab-*" = "tab-size: --value(integer)"
+"##,
+        )
+        .unwrap();
+        assert_eq!(toml.naming_mode, Some(NamingMode::Readable));
+        assert_eq!(toml.color_mix, Some(true));
+        assert!(toml.to_transform_options().split_css);
+        assert!(!toml.to_transform_options().normalize_classes);
+        assert_eq!(toml.resolve_spacing, Some(true));
+        assert_eq!(
+            toml.to_transform_options().css_variable_overrides,
+            CssVariableOverrides {
+                font_size: Some(CssVariableMode::Inline),
+                shadow: Some(CssVariableMode::Var),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            toml.targets,
+            Some(Targets {
+                chrome: Some(90),
+                firefox: None,
+                safari: Some(13),
+            })
+        );
+        assert_eq!(
+            toml.to_transform_options().breakpoints,
+            BreakpointStyle {
+                unit: BreakpointUnit::Px,
+                syntax: MediaQuerySyntax::Legacy,
+            }
+        );
+        assert_eq!(
+            toml.to_transform_options().container,
+            ContainerStyle {
+                center: true,
+                padding: Some("2rem".to_string()),
+            }
+        );
+        assert!(matches!(
+            toml.output_mode,
+            Some(OutputMode::CssModules { ref binding_name, access: CssModulesAccess::Bracket, .. })
+                if binding_name == "styles"
+        ));
+        assert_eq!(
+            toml.theme_css(),
+            ":root {\n  --color-brand: #ff5500;\n}\n@media (prefers-color-scheme: dark) {\n  :root {\n    --color-brand: #ffa366;\n  }\n}\n"
+        );
+        assert_eq!(
+            toml.to_transform_options().dark_theme.variables["--color-brand"],
+            "#ffa366"
+        );
+        assert_eq!(toml.class_overrides["px-4 py-2"].name.as_deref(), Some("btn"));
+        let css = toml.class_overrides["shadow-card"].css.as_ref().unwrap();
+        assert_eq!(css["box-shadow"], "0 1px 3px #0002");
+        assert_eq!(toml.to_transform_options().class_aliases["btn"], "px-4 py-2 rounded-md");
+        assert_eq!(toml.variant_aliases["hocus"], ["hover", "focus"]);
+        assert_eq!(toml.to_transform_options().imports.aliases["@styles/*"], "src/styles/*");
+        assert_eq!(
+            toml.to_transform_options().imports.file_name.as_deref(),
+            Some("[name].[contenthash].module.css")
+        );
+        let custom_variants = toml.to_transform_options().custom_variants;
+        assert_eq!(custom_variants["dark"].apply(".a"), ".a:where(.dark, .dark *)");
+        assert_eq!(custom_variants["touch"].at_rules, ["@media (hover: none)"]);
+        assert!(HeadwindConfig::from_json_str(r#"{"customVariants": {"x": ".no-amp"}}"#).is_err());
+        let custom_utilities = toml.to_transform_options().custom_utilities;
+        assert_eq!(custom_utilities["content-auto"].declarations.len(), 1);
+        assert_eq!(custom_utilities["tab-*"].declarations[0].value, "--value(integer)");
+        assert!(HeadwindConfig::from_json_str(r#"{"customUtilities": {"x": "no-colon"}}"#).is_err());
+
+        let json = HeadwindConfig::from_json_str(
+            r#"{"namingMode": "camelCase", "importQuoteStyle": "single", "importQuery": "url", "cssModuleExtension": "scss", "importPosition": "afterImports", "classAttrName": "className", "cssDedup": "groupSelectors", "htmlWrapper": "document"}"#,
+        )
+        .unwrap();
+        assert_eq!(json.naming_mode, Some(NamingMode::CamelCase));
+        assert_eq!(
+            json.to_transform_options().imports,
+            ImportOptions {
+                quote_style: Some(QuoteStyle::Single),
+                query: ImportQuery::Url,
+                css_module_extension: CssModuleExtension::Scss,
+                position: ImportPosition::AfterImports,
+                ..Default::default()
+            }
+        );
+        assert_eq!(json.class_attr_name, Some(ClassAttrName::ClassName));
+        assert_eq!(json.css_dedup, Some(CssDedup::GroupSelectors));
+        assert_eq!(json.html_wrapper, Some(HtmlWrapper::Document));
+        assert!(json.output_mode.is_none());
+        assert!(HeadwindConfig::from_json_str(r#"{"namingMode": "nope"}"#).is_err());
+
+        let v3 = HeadwindConfig::from_toml_str("targetTailwindVersion = \"3.4\"").unwrap();
+        assert_eq!(v3.target_tailwind_version, Some(TailwindVersion::V3));
+        let v3 = HeadwindConfig::from_json_str(r#"{"targetTailwindVersion": 3}"#).unwrap();
+        assert_eq!(v3.to_transform_options().target_tailwind_version, TailwindVersion::V3);
+        assert!(HeadwindConfig::from_json_str(r#"{"targetTailwindVersion": "2"}"#).is_err());
+    }
+
+    #[test]
+    fn test_layered_merge() {
+        let file = HeadwindConfig::from_toml_str(
+            "namingMode = \"readable\"\ncolorMix = true\nunitConversion = \"pxToRem\"\ninclude = [\"src/**\"]",
+        )
+        .unwrap();
+        let env = HeadwindConfig::from_env_vars([
+            ("HEADWIND_NAMING_MODE".to_string(), "camelCase".to_string()),
+            ("HEADWIND_ROOT_FONT_SIZE".to_string(), "10".to_string()),
+            ("HEADWIND_HASH_LENGTH".to_string(), "8".to_string()),
+            ("HEADWIND_OUTPUT_MODE".to_string(), "cssModules".to_string()),
+            ("HEADWIND_CSS_MODULE_DTS".to_string(), "true".to_string()),
+            ("HEADWIND_COMPONENT_CLASS_ATTRS".to_string(), "false".to_string()),
+            ("HEADWIND_UNRELATED".to_string(), "x".to_string()),
+            ("PATH".to_string(), "/usr/bin".to_string()),
+        ])
+        .unwrap();
+        let programmatic = HeadwindConfig {
+            color_mix: Some(false),
+            ..Default::default()
+        };
+
+        let options = file.merge(env).merge(programmatic).to_transform_options();
+        assert_eq!(options.naming_mode, NamingMode::CamelCase);
+        assert!(!options.color_mix);
+        assert!(matches!(options.output_mode, OutputMode::CssModules { .. }));
+        assert!(options.css_module_dts);
+        assert!(!options.component_class_attrs);
+        assert_eq!(options.unit_conversion, UnitConversion::PxToRem);
+        assert_eq!(options.root_font_size, 10);
+        assert_eq!(options.hash_length, 8);
+
+        let err = HeadwindConfig::from_env_vars([(
+            "HEADWIND_COLOR_MIX".to_string(),
+            "yes".to_string(),
+        )]);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_discover_and_resolve() {
+        let root = std::env::temp_dir().join(format!("headwind-config-{}", std::process::id()));
+        let nested = root.join("src/components");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            root.join("headwind.json"),
+            r#"{"namingMode": "readable", "exclude": ["**/*.test.tsx"]}"#,
+        )
+        .unwrap();
+        let file = nested.join("Button.tsx");
+        std::fs::write(&file, "").unwrap();
+
+        assert_eq!(HeadwindConfig::discover(&file), Some(root.join("headwind.json")));
+        let config = HeadwindConfig::resolve(
+            &file,
+            None,
+            HeadwindConfig {
+                hash_salt: Some("salt".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(config.naming_mode, Some(NamingMode::Readable));
+        assert_eq!(config.hash_salt.as_deref(), Some("salt"));
+        assert!(config.is_included(&file));
+        assert!(!config.is_included(&nested.join("Button.test.tsx")));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_fallback_index_relative_to_config() {
+        let root = std::env::temp_dir().join(format!("headwind-index-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("data")).unwrap();
+        std::fs::write(
+            root.join("headwind.toml"),
+            "fallbackIndex = \"data/mappings.json\"\nsafelist = [\"scrollbar-thin\"]",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("data/mappings.json"),
+            r#"[{ "class": "scrollbar-thin", "css": "scrollbar-width: thin" }]"#,
+        )
+        .unwrap();
+
+        let config = HeadwindConfig::load(&root.join("headwind.toml")).unwrap();
+        assert_eq!(config.fallback_index, Some(root.join("data/mappings.json")));
+        let index = config.load_fallback_index().unwrap().unwrap();
+        assert_eq!(index.version, None);
+        assert_eq!(index.index.lookup("scrollbar-thin").unwrap()[0].value, "thin");
+        assert!(config.prelude_css().contains("scrollbar-width: thin"));
+
+        let missing = HeadwindConfig {
+            fallback_index: Some(root.join("missing.json")),
+            ..Default::default()
+        };
+        assert!(missing.load_fallback_index().is_err());
+        assert!(HeadwindConfig::default().load_fallback_index().unwrap().is_none());
+
+        // 数据包声明的版本在配置未指定时生效
+        std::fs::write(
+            root.join("data/v3.json"),
+            r#"{ "tailwindVersion": "3.4.17", "classes": [] }"#,
+        )
+        .unwrap();
+        let versioned = HeadwindConfig {
+            fallback_index: Some(root.join("data/v3.json")),
+            ..Default::default()
+        };
+        let artifact = versioned.load_fallback_index().unwrap();
+        let options = versioned.with_fallback_index(versioned.to_transform_options(), artifact);
+        assert_eq!(options.target_tailwind_version, TailwindVersion::V3);
+        let pinned = HeadwindConfig {
+            target_tailwind_version: Some(TailwindVersion::V4),
+            ..versioned
+        };
+        let artifact = pinned.load_fallback_index().unwrap();
+        let options = pinned.with_fallback_index(pinned.to_transform_options(), artifact);
+        assert_eq!(options.target_tailwind_version, TailwindVersion::V4);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("src/**/*.tsx", "src/App.tsx"));
+        assert!(glob_match("src/**/*.tsx", "src/a/b/App.tsx"));
+        assert!(!glob_match("src/*.tsx", "src/a/App.tsx"));
+        assert!(glob_match("**/?.js", "lib/a.js"));
+        assert!(!glob_match("src/**", "lib/App.tsx"));
+    }
+
+    #[test]
+    fn test_prelude_css_includes_safelist() {
+        let config = HeadwindConfig {
+            safelist: vec!["hidden".to_string()],
+            ..Default::default()
+        };
+        let css = config.prelude_css();
+        assert!(css.contains("display: none"));
+    }
+}
@@ -0,0 +1,315 @@
+//! 已有 CSS 文件处理
+//!
+//! - 当源文件已经 `import styles from './App.module.css'` 时，
+//!   新生成的规则应合并进同一个模块文件，而不是另起一个文件。
+//! - 扫描项目已有 CSS 中的类名，作为生成名的保留集合。
+//! - 多轮构建时删除已失效生成名的规则块（见 `ConfigSession::sweep_css`）。
+//! - 增量接入：已有上一轮的 `styles.xxx` 引用、又新增了 Tailwind 类的文件再次转换
+//!   （见 [`transform_css_module_incremental`]）。
+
+use std::collections::HashSet;
+#[cfg(feature = "ecmascript")]
+use crate::{css_module_dts, transform_jsx, OutputMode, TransformOptions, TransformResult};
+
+/// 将生成的 CSS 合并进已有 CSS Module 文件内容
+///
+/// 按顶层规则块切分，已存在的相同规则块（忽略空白差异）不会重复追加，
+/// 其余规则按生成顺序追加到文件末尾。
+///
+/// ```
+/// use headwind_transform::merge_css_module;
+///
+/// let existing = ".foo {\n  color: red;\n}\n";
+/// let generated = ".foo { color: red; }\n.c_1 {\n  padding: 1rem;\n}\n";
+/// let merged = merge_css_module(existing, generated);
+/// assert_eq!(merged, ".foo {\n  color: red;\n}\n\n.c_1 {\n  padding: 1rem;\n}\n");
+/// ```
+pub fn merge_css_module(existing: &str, generated: &str) -> String {
+    let known: Vec<String> = split_top_level_blocks(existing)
+        .into_iter()
+        .map(normalize_block)
+        .collect();
+
+    let mut merged = existing.trim_end().to_string();
+    for block in split_top_level_blocks(generated) {
+        if known.contains(&normalize_block(block)) {
+            continue;
+        }
+        if !merged.is_empty() {
+            merged.push_str("\n\n");
+        }
+        merged.push_str(block.trim());
+    }
+    if !merged.is_empty() {
+        merged.push('\n');
+    }
+    merged
+}
+
+/// 再次转换已接入 CSS Modules 的文件
+///
+/// 源码中已有上一轮生成的 `styles.xxx` 引用、之后又写了新的 Tailwind 类时，只转换新的类字符串：
+/// 已有引用原样保留，新引用沿用文件中已有的样式 import，生成的规则经 [`merge_css_module`]
+/// 合并进 `existing_css`（样式文件的当前内容），结果的 `css` 即样式文件的新内容。
+///
+/// `existing_css` 中的类名视为保留类名，但与本次生成的规则完全相同的生成名除外：
+/// 同一组类再次出现时沿用原来的名称。只有同名而规则不同（如计数命名的 `c_1` 已分给其他类，
+/// 或手写了同名类）时才改用加盐的名称。对同一份源码与样式文件重复调用，结果不再变化。
+///
+/// 输出模式不是 CssModules 时返回错误。
+#[cfg(feature = "ecmascript")]
+pub fn transform_css_module_incremental(
+    source: &str,
+    filename: &str,
+    existing_css: &str,
+    options: TransformOptions,
+) -> Result<TransformResult, String> {
+    if !matches!(options.output_mode, OutputMode::CssModules { .. }) {
+        return Err("增量转换只支持 CssModules 输出模式".to_string());
+    }
+    let existing_names = extract_css_class_names(existing_css);
+    let mut result = transform_jsx(source, filename, options.clone())?;
+
+    // 本次生成的规则块不在样式文件中、却用了样式文件里的类名：该名称已被其他规则占用
+    let known: HashSet<String> = split_top_level_blocks(existing_css)
+        .into_iter()
+        .map(normalize_block)
+        .collect();
+    let (matched, conflicting): (Vec<&str>, Vec<&str>) = split_top_level_blocks(&result.css)
+        .into_iter()
+        .partition(|block| known.contains(&normalize_block(block)));
+    let conflicts: HashSet<String> = conflicting
+        .into_iter()
+        .flat_map(extract_css_class_names)
+        .filter(|name| existing_names.contains(name))
+        .collect();
+    if !conflicts.is_empty() {
+        let matched: HashSet<String> = matched.into_iter().flat_map(extract_css_class_names).collect();
+        let mut options = options;
+        options.reserved_class_names.extend(
+            existing_names
+                .iter()
+                .filter(|name| conflicts.contains(*name) || !matched.contains(*name))
+                .cloned(),
+        );
+        result = transform_jsx(source, filename, options)?;
+    }
+
+    result.css = merge_css_module(existing_css, &result.css);
+    // 声明文件覆盖整个样式文件，包括上一轮生成的类名
+    if let Some((_, dts)) = &mut result.dts {
+        *dts = css_module_dts(extract_css_class_names(&result.css).iter().map(String::as_str));
+    }
+    Ok(result)
+}
+
+/// 删除只属于已失效生成名的顶层规则块
+///
+/// 规则块的选择器引用了 `dead` 中的类名、且没有引用 `live` 中的类名时删除；
+/// `:root`、手写规则等不涉及失效类名的块原样保留。没有可删除的块时返回原文，
+/// 否则与 `merge_css_module` 一样以空行分隔剩余规则块。返回清理后的 CSS 和被删除的规则块。
+///
+/// ```
+/// use headwind_transform::sweep_css_module;
+/// use std::collections::HashSet;
+///
+/// let css = ".c_1 { padding: 1rem; }\n\n.c_2 { margin: 0; }\n";
+/// let dead = HashSet::from(["c_2".to_string()]);
+/// let (swept, removed) = sweep_css_module(css, &dead, &HashSet::new());
+/// assert_eq!(swept, ".c_1 { padding: 1rem; }\n");
+/// assert_eq!(removed, vec![".c_2 { margin: 0; }"]);
+/// ```
+pub fn sweep_css_module(
+    css: &str,
+    dead: &HashSet<String>,
+    live: &HashSet<String>,
+) -> (String, Vec<String>) {
+    let (removed, kept): (Vec<&str>, Vec<&str>) =
+        split_top_level_blocks(css).into_iter().partition(|block| {
+            let names = extract_css_class_names(block);
+            names.iter().any(|name| dead.contains(name))
+                && !names.iter().any(|name| live.contains(name))
+        });
+    if removed.is_empty() {
+        return (css.to_string(), Vec::new());
+    }
+    let mut swept = kept.join("\n\n");
+    if !swept.is_empty() {
+        swept.push('\n');
+    }
+    (swept, removed.into_iter().map(str::to_string).collect())
+}
+
+/// 扫描 CSS 文本中选择器用到的类名（用于冲突检测的保留类名）
+///
+/// 只检查规则 prelude（`{` 之前的片段），声明值中的 `.5rem`、`url(a.png)` 不会被误识别；
+/// `@media` 等 at-rule prelude 会被跳过，注释会被忽略。
+///
+/// ```
+/// use headwind_transform::extract_css_class_names;
+///
+/// let css = ".btn, .btn-primary:hover { margin: .5rem; }\n@media (width >= 40rem) { .card > .title { color: red } }";
+/// assert_eq!(extract_css_class_names(css), vec!["btn", "btn-primary", "card", "title"]);
+/// ```
+pub fn extract_css_class_names(css: &str) -> Vec<String> {
+    let css = strip_comments(css);
+    let mut names: Vec<String> = Vec::new();
+    let mut segment_start = 0;
+    for (i, ch) in css.char_indices() {
+        match ch {
+            '{' => {
+                let prelude = css[segment_start..i].trim();
+                if !prelude.starts_with('@') {
+                    for name in selector_class_names(prelude) {
+                        if !names.contains(&name) {
+                            names.push(name);
+                        }
+                    }
+                }
+                segment_start = i + 1;
+            }
+            '}' | ';' => segment_start = i + 1,
+            _ => {}
+        }
+    }
+    names
+}
+
+/// 提取单个选择器中的 `.class`（支持 `\:` 等转义字符）
+fn selector_class_names(selector: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut chars = selector.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '.' {
+            continue;
+        }
+        let mut name = String::new();
+        while let Some(&next) = chars.peek() {
+            if next == '\\' {
+                chars.next();
+                if let Some(escaped) = chars.next() {
+                    name.push(escaped);
+                }
+            } else if next.is_alphanumeric() || next == '-' || next == '_' || !next.is_ascii() {
+                name.push(next);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        if !name.is_empty() && !name.starts_with(|c: char| c.is_ascii_digit()) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// 去掉 `/* ... */` 注释
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        match rest[start + 2..].find("*/") {
+            Some(end) => rest = &rest[start + 2 + end + 2..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 按花括号深度切分顶层规则块（含块前的选择器 / at-rule prelude）
+pub(crate) fn split_top_level_blocks(css: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, ch) in css.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    let block = css[start..=i].trim();
+                    if !block.is_empty() {
+                        blocks.push(block);
+                    }
+                    start = i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// 去掉所有空白和注释，用于比较规则块是否相同
+pub(crate) fn normalize_block(block: &str) -> String {
+    let mut normalized = String::with_capacity(block.len());
+    let mut rest = block;
+    while let Some(start) = rest.find("/*") {
+        normalized.extend(rest[..start].chars().filter(|c| !c.is_whitespace()));
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    normalized.extend(rest.chars().filter(|c| !c.is_whitespace()));
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_into_empty_file() {
+        let generated = ".c_1 {\n  padding: 1rem;\n}\n";
+        assert_eq!(merge_css_module("", generated), generated);
+    }
+
+    #[test]
+    fn test_merge_nested_blocks() {
+        let existing = ".a {\n  color: red;\n}\n";
+        let generated = "@media (width >= 40rem) {\n  .c_1 {\n    padding: 1rem;\n  }\n}\n";
+        let merged = merge_css_module(existing, generated);
+        assert!(merged.starts_with(".a {"));
+        assert!(merged.contains("@media (width >= 40rem) {\n  .c_1 {"));
+        // 再合并一次不会重复追加，带来源注释的相同规则也视为已存在
+        assert_eq!(merge_css_module(&merged, generated), merged);
+        let commented = format!("/* App.tsx <div> \"sm:p-4\" */\n{}", generated);
+        assert_eq!(merge_css_module(&merged, &commented), merged);
+    }
+
+    #[test]
+    fn test_sweep_keeps_live_and_unrelated_blocks() {
+        let css = ":root {\n  --spacing: 0.25rem;\n}\n\n.c_1 { padding: 1rem; }\n\n\
+                   @media (width >= 40rem) {\n  .c_2 { margin: 0; }\n}\n\n\
+                   .c_2, .c_1 { color: red; }\n\n.group:hover .c_2 { color: blue; }\n";
+        let dead = HashSet::from(["c_2".to_string()]);
+        let live = HashSet::from(["c_1".to_string()]);
+        let (swept, removed) = sweep_css_module(css, &dead, &live);
+        assert_eq!(
+            swept,
+            ":root {\n  --spacing: 0.25rem;\n}\n\n.c_1 { padding: 1rem; }\n\n.c_2, .c_1 { color: red; }\n"
+        );
+        assert_eq!(
+            removed,
+            vec![
+                "@media (width >= 40rem) {\n  .c_2 { margin: 0; }\n}",
+                ".group:hover .c_2 { color: blue; }"
+            ]
+        );
+        // 没有失效的规则时原样返回
+        assert_eq!(sweep_css_module(css, &HashSet::new(), &live), (css.to_string(), Vec::new()));
+    }
+
+    #[test]
+    fn test_extract_class_names_escaped_and_comments() {
+        let css = "/* .ignored { } */\n.md\\:p-4 { padding: 1rem }\n.a.b{}";
+        assert_eq!(extract_css_class_names(css), vec!["md:p-4", "a", "b"]);
+    }
+}
@@ -0,0 +1,139 @@
+//! 相同规则体的去重
+//!
+//! 大型项目中许多类组合产出完全相同的规则（如 `p-4` 与 `p-[1rem]`，或 Remove 模式下
+//! 只差一个未识别类的组合），逐个输出会重复大量声明。声明顺序不同的规则视为不同。
+//! 收集器以占位名渲染每个类组合的规则作为签名，签名相同即除选择器外完全一致：
+//! 可以把后来者的选择器并入先出现的规则块，也可以让后来者直接复用先出现的生成名。
+
+use std::collections::HashMap;
+
+/// 生成 CSS 的去重方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CssDedup {
+    /// 不去重，每个生成名各自输出规则（默认）
+    #[default]
+    None,
+    /// 规则体相同的生成名合并为一组选择器：`.c_a, .c_b { ... }`，标记中的类名不变
+    GroupSelectors,
+    /// 规则体与已有生成名相同的类组合直接使用该生成名，不再产出新的类名与规则
+    ShareClass,
+}
+
+/// 计算签名时代替生成名的占位名
+pub(crate) const SIGNATURE_NAME: &str = "__headwind_dedup__";
+
+/// `GroupSelectors` 模式下尚未输出的规则块，按首次出现的顺序排列
+#[derive(Debug, Default)]
+pub(crate) struct RuleGroups {
+    groups: Vec<RuleGroup>,
+    /// 签名 → 规则块下标
+    index: HashMap<String, usize>,
+}
+
+#[derive(Debug)]
+struct RuleGroup {
+    /// 各成员的来源注释（未开启注释时为空串）
+    comments: Vec<String>,
+    /// 各成员以自身生成名渲染的规则
+    bodies: Vec<String>,
+}
+
+impl RuleGroups {
+    /// 加入一个生成名的规则；签名已出现过时并入该规则块并返回 true
+    pub(crate) fn push(&mut self, signature: String, comment: String, body: String) -> bool {
+        if let Some(&i) = self.index.get(&signature) {
+            self.groups[i].comments.push(comment);
+            self.groups[i].bodies.push(body);
+            return true;
+        }
+        self.index.insert(signature, self.groups.len());
+        self.groups.push(RuleGroup {
+            comments: vec![comment],
+            bodies: vec![body],
+        });
+        false
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// 已输出的规则块无法再并入选择器，flush 后清空，之后相同的规则另起一组
+    pub(crate) fn clear(&mut self) {
+        self.groups.clear();
+        self.index.clear();
+    }
+
+    /// 渲染全部规则块，块之间空一行（与不去重时的输出格式相同）
+    pub(crate) fn render(&self) -> String {
+        let mut css = String::new();
+        for group in &self.groups {
+            if !css.is_empty() {
+                css.push('\n');
+            }
+            for comment in &group.comments {
+                css.push_str(comment);
+            }
+            css.push_str(&group_selectors(&group.bodies));
+        }
+        css
+    }
+}
+
+/// 合并签名相同的规则：逐行比较，不同的行只可能是选择器行，依次并为一组选择器
+///
+/// ```text
+/// .c_a:hover {        .c_b:hover {        .c_a:hover, .c_b:hover {
+///   color: red;   +     color: red;   →     color: red;
+/// }                   }                   }
+/// ```
+fn group_selectors(bodies: &[String]) -> String {
+    let Some((first, rest)) = bodies.split_first() else {
+        return String::new();
+    };
+    if rest.is_empty() {
+        return first.clone();
+    }
+    let mut others: Vec<_> = rest.iter().map(|body| body.lines()).collect();
+    let mut css = String::with_capacity(first.len() * bodies.len());
+    for line in first.lines() {
+        let variants: Vec<&str> = others.iter_mut().filter_map(Iterator::next).collect();
+        if variants.iter().all(|other| *other == line) {
+            css.push_str(line);
+        } else {
+            css.push_str(line.trim_end_matches(" {"));
+            for other in variants {
+                css.push_str(", ");
+                css.push_str(other.trim().trim_end_matches(" {"));
+            }
+            css.push_str(" {");
+        }
+        css.push('\n');
+    }
+    css
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_selectors_merges_selector_lines() {
+        let body = |name: &str| {
+            format!(
+                ".{0} {{\n  display: flex;\n}}\n@media (width >= 48rem) {{\n  .{0}:hover {{\n    color: red;\n  }}\n}}\n",
+                name
+            )
+        };
+        let mut groups = RuleGroups::default();
+        assert!(!groups.push("sig".into(), String::new(), body("c_a")));
+        assert!(!groups.push("other".into(), String::new(), ".c_x {\n  margin: 0;\n}\n".into()));
+        assert!(groups.push("sig".into(), String::new(), body("c_b")));
+        assert_eq!(
+            groups.render(),
+            ".c_a, .c_b {\n  display: flex;\n}\n@media (width >= 48rem) {\n  .c_a:hover, .c_b:hover {\n    color: red;\n  }\n}\n\n.c_x {\n  margin: 0;\n}\n"
+        );
+        groups.clear();
+        assert!(groups.is_empty());
+    }
+}
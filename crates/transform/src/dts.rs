@@ -0,0 +1,55 @@
+//! CSS Modules 类型声明
+//!
+//! 严格模式的 TS 项目中，`import styles from './App.module.css'` 需要对应的声明文件，
+//! 否则 `styles.textCenterP4` 无法通过类型检查。开启 `css_module_dts` 后，
+//! CssModules 模式随结果产出 `App.module.css.d.ts`，为每个生成的类名声明一个只读键。
+
+use crate::emit::{is_identifier, QuoteStyle};
+use std::collections::HashSet;
+
+/// 由生成的类名产出声明文件内容，按传入顺序排列，重复的类名只声明一次
+///
+/// 不是合法标识符的类名（如 Readable 命名中的 `hover:`）以带引号的键声明。
+///
+/// ```
+/// use headwind_transform::css_module_dts;
+///
+/// let dts = css_module_dts(["textCenterP4", "hover:p-2"]);
+/// assert_eq!(
+///     dts,
+///     "declare const styles: {\n  readonly textCenterP4: string;\n  readonly \"hover:p-2\": string;\n};\nexport default styles;\n"
+/// );
+/// ```
+pub fn css_module_dts<'a>(class_names: impl IntoIterator<Item = &'a str>) -> String {
+    let mut seen = HashSet::new();
+    let mut dts = String::from("declare const styles: {\n");
+    for name in class_names {
+        if !seen.insert(name) {
+            continue;
+        }
+        let key = if is_identifier(name) {
+            name.to_string()
+        } else {
+            QuoteStyle::Double.quote(name)
+        };
+        dts.push_str(&format!("  readonly {}: string;\n", key));
+    }
+    dts.push_str("};\nexport default styles;\n");
+    dts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dts_dedupes_class_names() {
+        let dts = css_module_dts(["c_1", "c_2", "c_1"]);
+        assert_eq!(dts.matches("readonly c_1: string;").count(), 1);
+        assert!(dts.contains("  readonly c_2: string;\n"));
+        assert_eq!(
+            css_module_dts([]),
+            "declare const styles: {\n};\nexport default styles;\n"
+        );
+    }
+}
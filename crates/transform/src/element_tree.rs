@@ -1,4 +1,10 @@
+use indexmap::IndexMap;
+use std::ops::Range;
+#[cfg(feature = "ecmascript")]
+use swc_core::common::{BytePos, Span};
+#[cfg(feature = "ecmascript")]
 use swc_core::ecma::ast::*;
+#[cfg(feature = "ecmascript")]
 use swc_core::ecma::visit::{Visit, VisitWith};
 
 /// 元素树节点
@@ -10,6 +16,8 @@ pub struct ElementNode {
     pub classes: String,
     /// 直接文本内容
     pub text: String,
+    /// class 属性值在源码中的字节区间（不含引号）；动态类名、没有 class 属性或未记录位置时为 None
+    pub class_span: Option<Range<usize>>,
     /// 子元素
     pub children: Vec<ElementNode>,
 }
@@ -90,6 +98,29 @@ pub fn format_component_trees(components: &[ComponentTree]) -> String {
     output
 }
 
+/// 各组件内元素的类字符串，按出现顺序去重；未命名的分组与没有类的组件跳过
+pub fn component_classes(components: &[ComponentTree]) -> IndexMap<String, Vec<String>> {
+    fn collect(node: &ElementNode, classes: &mut Vec<String>) {
+        let value = node.classes.trim();
+        if !value.is_empty() && !classes.iter().any(|c| c == value) {
+            classes.push(value.to_string());
+        }
+        for child in &node.children {
+            collect(child, classes);
+        }
+    }
+
+    let mut result: IndexMap<String, Vec<String>> = IndexMap::new();
+    for component in components.iter().filter(|c| !c.name.is_empty()) {
+        let classes = result.entry(component.name.clone()).or_default();
+        for root in &component.roots {
+            collect(root, classes);
+        }
+    }
+    result.retain(|_, classes| !classes.is_empty());
+    result
+}
+
 fn format_node(node: &ElementNode, depth: usize, output: &mut String, counter: &mut usize) {
     let indent = "  ".repeat(depth);
     *counter += 1;
@@ -128,24 +159,41 @@ fn format_node(node: &ElementNode, depth: usize, output: &mut String, counter: &
 // ── JSX 树构建 ──────────────────────────────────────────────────
 
 /// 从 SWC Module AST 构建按组件分组的元素树
+#[cfg(feature = "ecmascript")]
 pub fn build_jsx_element_tree(module: &Module) -> Vec<ComponentTree> {
+    build_jsx_tree(module, None)
+}
+
+/// 与 `build_jsx_element_tree` 相同，并按文件起始位置 `base` 记录各元素 class 值的字节区间
+#[cfg(feature = "ecmascript")]
+pub(crate) fn build_jsx_element_tree_with_spans(module: &Module, base: BytePos) -> Vec<ComponentTree> {
+    build_jsx_tree(module, Some(base))
+}
+
+#[cfg(feature = "ecmascript")]
+fn build_jsx_tree(module: &Module, base: Option<BytePos>) -> Vec<ComponentTree> {
     let mut builder = JsxTreeBuilder {
         components: Vec::new(),
         current_fn: None,
         stack: Vec::new(),
+        base,
     };
     module.visit_with(&mut builder);
     builder.components
 }
 
+#[cfg(feature = "ecmascript")]
 struct JsxTreeBuilder {
     components: Vec<ComponentTree>,
     /// 当前所在的函数/组件名
     current_fn: Option<String>,
     /// JSX 元素嵌套栈
     stack: Vec<Vec<ElementNode>>,
+    /// 文件起始位置，为 None 时不记录 class 值的区间
+    base: Option<BytePos>,
 }
 
+#[cfg(feature = "ecmascript")]
 impl JsxTreeBuilder {
     fn add_root(&mut self, node: ElementNode) {
         let name = self.current_fn.clone().unwrap_or_default();
@@ -160,6 +208,7 @@ impl JsxTreeBuilder {
     }
 }
 
+#[cfg(feature = "ecmascript")]
 impl Visit for JsxTreeBuilder {
     // ── 跟踪组件名 ──
 
@@ -214,7 +263,10 @@ impl Visit for JsxTreeBuilder {
 
     fn visit_jsx_element(&mut self, el: &JSXElement) {
         let tag = jsx_tag_name(&el.opening.name);
-        let classes = jsx_class_attr(&el.opening.attrs);
+        let (classes, class_span) = jsx_class_attr(&el.opening.attrs);
+        let class_span = self.base.zip(class_span).map(|(base, span)| {
+            (span.lo.0 - base.0) as usize..(span.hi.0 - base.0) as usize
+        });
 
         self.stack.push(Vec::new());
 
@@ -251,6 +303,7 @@ impl Visit for JsxTreeBuilder {
             tag,
             classes,
             text,
+            class_span,
             children,
         };
 
@@ -268,7 +321,8 @@ impl Visit for JsxTreeBuilder {
     }
 }
 
-fn jsx_tag_name(name: &JSXElementName) -> String {
+#[cfg(feature = "ecmascript")]
+pub(crate) fn jsx_tag_name(name: &JSXElementName) -> String {
     match name {
         JSXElementName::Ident(id) => id.sym.to_string(),
         JSXElementName::JSXMemberExpr(m) => jsx_member_expr(m),
@@ -277,6 +331,7 @@ fn jsx_tag_name(name: &JSXElementName) -> String {
     }
 }
 
+#[cfg(feature = "ecmascript")]
 fn jsx_member_expr(m: &JSXMemberExpr) -> String {
     let obj = match &m.obj {
         JSXObject::Ident(id) => id.sym.to_string(),
@@ -286,7 +341,9 @@ fn jsx_member_expr(m: &JSXMemberExpr) -> String {
     format!("{}.{}", obj, m.prop.sym)
 }
 
-fn jsx_class_attr(attrs: &[JSXAttrOrSpread]) -> String {
+/// class 属性的值与其内容（不含引号）的 span；动态类名的 span 为 None
+#[cfg(feature = "ecmascript")]
+fn jsx_class_attr(attrs: &[JSXAttrOrSpread]) -> (String, Option<Span>) {
     for attr in attrs {
         if let JSXAttrOrSpread::JSXAttr(a) = attr {
             let is_class = match &a.name {
@@ -299,24 +356,28 @@ fn jsx_class_attr(attrs: &[JSXAttrOrSpread]) -> String {
             if !is_class {
                 continue;
             }
+            let literal = |s: &Str| {
+                let inner = Span::new(s.span.lo + BytePos(1), s.span.hi - BytePos(1));
+                (s.value.as_str().unwrap_or_default().to_string(), Some(inner))
+            };
             return match &a.value {
-                Some(JSXAttrValue::Str(s)) => s.value.as_str().unwrap_or_default().to_string(),
+                Some(JSXAttrValue::Str(s)) => literal(s),
                 Some(JSXAttrValue::JSXExprContainer(c)) => match &c.expr {
                     JSXExpr::Expr(expr) => match expr.as_ref() {
-                        Expr::Lit(Lit::Str(s)) => s.value.as_str().unwrap_or_default().to_string(),
+                        Expr::Lit(Lit::Str(s)) => literal(s),
                         Expr::Tpl(tpl) if tpl.exprs.is_empty() && tpl.quasis.len() == 1 => {
                             let raw: &str = &tpl.quasis[0].raw;
-                            raw.to_string()
+                            (raw.to_string(), Some(tpl.quasis[0].span))
                         }
-                        _ => "{...}".to_string(),
+                        _ => ("{...}".to_string(), None),
                     },
-                    _ => String::new(),
+                    _ => (String::new(), None),
                 },
-                _ => String::new(),
+                _ => (String::new(), None),
             };
         }
     }
-    String::new()
+    (String::new(), None)
 }
 
 // ── HTML 树构建 ──────────────────────────────────────────────────
@@ -327,7 +388,16 @@ const VOID_ELEMENTS: &[&str] = &[
     "track", "wbr",
 ];
 
+/// 省略闭合标签时由下一个同名元素隐式闭合的元素（`<li>one<li>two` 是两个兄弟）
+const SIBLING_CLOSED_ELEMENTS: &[&str] = &["li", "p", "dt", "dd", "option", "tr", "td", "th"];
+
+/// 内容不按标签解析的元素：`script` / `style` 的内容跳过，`textarea` / `title` 的内容作为文本
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
 /// 从 HTML 源码构建元素树
+///
+/// 输入可以是完整文档，也可以是没有 `<html>` / `<body>` 的片段（多个根元素依次作为根节点）。
+/// 缺少闭合标签的元素在父元素闭合或输入结束时隐式闭合，没有对应开标签的闭合标签忽略。
 pub fn build_html_element_tree(source: &str) -> Vec<ElementNode> {
     let mut parser = HtmlTreeParser::new(source);
     parser.parse();
@@ -357,11 +427,13 @@ impl<'a> HtmlTreeParser<'a> {
 
         while self.pos < len {
             if bytes[self.pos] == b'<' {
-                if self.pos + 4 < len && &bytes[self.pos..self.pos + 4] == b"<!--" {
-                    if let Some(end) = self.src[self.pos..].find("-->") {
-                        self.pos += end + 3;
-                        continue;
-                    }
+                if self.src[self.pos..].starts_with("<!--") {
+                    // 未闭合的注释延续到输入结束
+                    self.pos = match self.src[self.pos..].find("-->") {
+                        Some(end) => self.pos + end + 3,
+                        None => len,
+                    };
+                    continue;
                 }
                 if self.pos + 2 < len && bytes[self.pos + 1] == b'!' {
                     if let Some(end) = self.src[self.pos..].find('>') {
@@ -373,30 +445,44 @@ impl<'a> HtmlTreeParser<'a> {
                     self.parse_closing_tag();
                     continue;
                 }
-                self.parse_opening_tag();
-            } else {
-                let start = self.pos;
-                while self.pos < len && bytes[self.pos] != b'<' {
-                    self.pos += 1;
-                }
-                let text = self.src[start..self.pos].trim();
-                if !text.is_empty() {
-                    if let Some(parent) = self.stack.last_mut() {
-                        if !parent.text.is_empty() {
-                            parent.text.push(' ');
-                        }
-                        parent.text.push_str(text);
-                    }
+                if self.pos + 1 < len && bytes[self.pos + 1].is_ascii_alphabetic() {
+                    self.parse_opening_tag();
+                    continue;
                 }
             }
+            // 文本，包括不构成标签的 `<`（如 `a < b`）
+            let start = self.pos;
+            self.pos += 1;
+            while self.pos < len && bytes[self.pos] != b'<' {
+                self.pos += 1;
+            }
+            self.push_text(start, self.pos);
         }
 
         while let Some(node) = self.stack.pop() {
-            if let Some(parent) = self.stack.last_mut() {
-                parent.children.push(node);
-            } else {
-                self.roots.push(node);
+            self.attach(node);
+        }
+    }
+
+    /// 把 `[start, end)` 的文本追加到当前元素；片段顶层的文本不属于任何元素，忽略
+    fn push_text(&mut self, start: usize, end: usize) {
+        let text = self.src[start..end].trim();
+        if text.is_empty() {
+            return;
+        }
+        if let Some(parent) = self.stack.last_mut() {
+            if !parent.text.is_empty() {
+                parent.text.push(' ');
             }
+            parent.text.push_str(text);
+        }
+    }
+
+    /// 已闭合的元素并入当前父元素，没有父元素时作为根节点
+    fn attach(&mut self, node: ElementNode) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => self.roots.push(node),
         }
     }
 
@@ -420,6 +506,7 @@ impl<'a> HtmlTreeParser<'a> {
         }
 
         let mut classes = String::new();
+        let mut class_span = None;
         let mut self_closing = false;
 
         while self.pos < len && bytes[self.pos] != b'>' {
@@ -461,11 +548,12 @@ impl<'a> HtmlTreeParser<'a> {
                         self.pos += 1;
                     }
                     let value = &self.src[val_start..self.pos];
-                    if self.pos < len {
-                        self.pos += 1;
-                    }
                     if attr_name == "class" {
                         classes = value.to_string();
+                        class_span = Some(val_start..self.pos);
+                    }
+                    if self.pos < len {
+                        self.pos += 1;
                     }
                 } else {
                     let val_start = self.pos;
@@ -478,6 +566,7 @@ impl<'a> HtmlTreeParser<'a> {
                     let value = &self.src[val_start..self.pos];
                     if attr_name == "class" {
                         classes = value.to_string();
+                        class_span = Some(val_start..self.pos);
                     }
                 }
             }
@@ -488,21 +577,37 @@ impl<'a> HtmlTreeParser<'a> {
         }
 
         let is_void = VOID_ELEMENTS.contains(&tag.as_str());
+        let is_raw_text = RAW_TEXT_ELEMENTS.contains(&tag.as_str());
         let node = ElementNode {
             tag,
             classes,
             text: String::new(),
+            class_span,
             children: Vec::new(),
         };
 
         if self_closing || is_void {
-            if let Some(parent) = self.stack.last_mut() {
-                parent.children.push(node);
-            } else {
-                self.roots.push(node);
+            self.attach(node);
+            return;
+        }
+        if SIBLING_CLOSED_ELEMENTS.contains(&node.tag.as_str())
+            && self.stack.last().is_some_and(|open| open.tag == node.tag)
+        {
+            let open = self.stack.pop().unwrap();
+            self.attach(open);
+        }
+        let closing = format!("</{}", node.tag);
+        self.stack.push(node);
+        if is_raw_text {
+            // 内容中的 `<`（如脚本里的 `a < b`）不是标签，直接跳到闭合标签；没有闭合标签时到输入结束
+            let start = self.pos;
+            self.pos = self.src[start..]
+                .to_ascii_lowercase()
+                .find(&closing)
+                .map_or(len, |end| start + end);
+            if !matches!(closing.as_str(), "</script" | "</style") {
+                self.push_text(start, self.pos);
             }
-        } else {
-            self.stack.push(node);
         }
     }
 
@@ -525,42 +630,14 @@ impl<'a> HtmlTreeParser<'a> {
             self.pos += 1;
         }
 
-        if let Some(node) = self.stack.pop() {
-            if node.tag == tag {
-                if let Some(parent) = self.stack.last_mut() {
-                    parent.children.push(node);
-                } else {
-                    self.roots.push(node);
-                }
-            } else {
-                let mut unmatched = vec![node];
-                let mut found = false;
-                while let Some(n) = self.stack.pop() {
-                    if n.tag == tag {
-                        let mut matched_node = n;
-                        for um in unmatched.drain(..).rev() {
-                            matched_node.children.push(um);
-                        }
-                        if let Some(parent) = self.stack.last_mut() {
-                            parent.children.push(matched_node);
-                        } else {
-                            self.roots.push(matched_node);
-                        }
-                        found = true;
-                        break;
-                    }
-                    unmatched.push(n);
-                }
-                if !found {
-                    for um in unmatched.into_iter().rev() {
-                        if let Some(parent) = self.stack.last_mut() {
-                            parent.children.push(um);
-                        } else {
-                            self.roots.push(um);
-                        }
-                    }
-                }
-            }
+        // 没有对应开标签的闭合标签（如片段末尾多余的 `</div>`）忽略，不影响已打开的元素
+        let Some(depth) = self.stack.iter().rposition(|node| node.tag == tag) else {
+            return;
+        };
+        // 其间缺少闭合标签的元素（如 `<li>`、`<p>`）在此隐式闭合，逐层并入各自的父元素
+        while self.stack.len() > depth {
+            let node = self.stack.pop().unwrap();
+            self.attach(node);
         }
     }
 }
@@ -575,31 +652,37 @@ mod tests {
             tag: "div".to_string(),
             classes: "w-full h-20 border".to_string(),
             text: String::new(),
+            class_span: None,
             children: vec![
                 ElementNode {
                     tag: "h2".to_string(),
                     classes: "text-xl text-red-500".to_string(),
                     text: String::new(),
+                    class_span: None,
                     children: vec![],
                 },
                 ElementNode {
                     tag: "p".to_string(),
                     classes: String::new(),
                     text: "xxxx".to_string(),
+                    class_span: None,
                     children: vec![],
                 },
                 ElementNode {
                     tag: "div".to_string(),
                     classes: String::new(),
                     text: "yyyy".to_string(),
+                    class_span: None,
                     children: vec![ElementNode {
                         tag: "p".to_string(),
                         classes: "text-lg text-blue-500".to_string(),
                         text: String::new(),
+                        class_span: None,
                         children: vec![ElementNode {
                             tag: "span".to_string(),
                             classes: "text-sm".to_string(),
                             text: String::new(),
+                            class_span: None,
                             children: vec![],
                         }],
                     }],
@@ -651,7 +734,9 @@ mod tests {
         assert_eq!(tree.len(), 1);
         assert_eq!(tree[0].tag, "div");
         assert_eq!(tree[0].classes, "p-4 m-2");
+        assert_eq!(&html[tree[0].class_span.clone().unwrap()], "p-4 m-2");
         assert_eq!(tree[0].children.len(), 2);
+        assert_eq!(tree[0].children[0].class_span, None);
         assert_eq!(tree[0].children[0].tag, "p");
         assert_eq!(tree[0].children[0].text, "Hello");
         assert_eq!(tree[0].children[1].tag, "span");
@@ -696,4 +781,26 @@ mod tests {
         println!("{}", result);
         assert!(result.contains("div p-4"));
     }
+
+    #[test]
+    fn test_html_tree_fragment_and_malformed() {
+        let html = r#"intro <p class="mt-2">a < b</p><ul><li class="p-1">one<li>two<em>!</ul></div>
+<script>if (a <b) {}</script><section class="flex"><span>tail"#;
+        let tree = build_html_element_tree(html);
+
+        // 片段的多个根元素依次作为根节点，多余的 `</div>` 忽略
+        let tags: Vec<&str> = tree.iter().map(|node| node.tag.as_str()).collect();
+        assert_eq!(tags, ["p", "ul", "script", "section"]);
+        assert_eq!(tree[0].text, "a < b");
+        // 未闭合的 `<li>` 由下一个 `<li>` 闭合，`<em>` 在 `</ul>` 处闭合
+        let items = &tree[1].children;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].classes, "p-1");
+        assert_eq!(items[0].text, "one");
+        assert_eq!(items[1].children[0].tag, "em");
+        assert!(tree[2].children.is_empty() && tree[2].text.is_empty());
+        // 输入结束时未闭合的元素保留嵌套
+        assert_eq!(tree[3].classes, "flex");
+        assert_eq!(tree[3].children[0].text, "tail");
+    }
 }
@@ -0,0 +1,248 @@
+//! 代码输出选项
+//!
+//! 整体重新打印（非 `preserve_formatting`）时，新生成的字符串字面量
+//! 默认使用双引号。通过 `EmitOptions` 可以对齐项目的 Prettier 配置
+//! （`singleQuote` / `jsxSingleQuote`），其余格式差异交给 `Formatter` 钩子处理。
+//! 注入的 import 语句另有 `ImportOptions` 控制路径写法与插入位置。
+
+use indexmap::IndexMap;
+use std::sync::Arc;
+#[cfg(feature = "ecmascript")]
+use swc_core::ecma::ast::Str;
+
+/// 字符串引号风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuoteStyle {
+    /// `"..."`（SWC codegen 默认）
+    #[default]
+    Double,
+    /// `'...'`
+    Single,
+}
+
+impl QuoteStyle {
+    /// 引号字符
+    pub fn quote_char(self) -> char {
+        match self {
+            QuoteStyle::Double => '"',
+            QuoteStyle::Single => '\'',
+        }
+    }
+
+    /// 将字符串值包上引号，转义反斜杠和同类引号
+    ///
+    /// ```
+    /// use headwind_transform::QuoteStyle;
+    ///
+    /// assert_eq!(QuoteStyle::Single.quote("it's"), r"'it\'s'");
+    /// assert_eq!(QuoteStyle::Double.quote("p-4"), "\"p-4\"");
+    /// ```
+    pub fn quote(self, value: &str) -> String {
+        let q = self.quote_char();
+        let mut out = String::with_capacity(value.len() + 2);
+        out.push(q);
+        for ch in value.chars() {
+            if ch == '\\' || ch == q {
+                out.push('\\');
+            }
+            out.push(ch);
+        }
+        out.push(q);
+        out
+    }
+
+    /// 构造带 raw 的 Str 节点，使 codegen 按指定引号输出
+    #[cfg(feature = "ecmascript")]
+    pub(crate) fn str_lit(self, span: swc_core::common::Span, value: &str) -> Str {
+        Str {
+            span,
+            value: value.into(),
+            raw: Some(self.quote(value).into()),
+        }
+    }
+}
+
+/// 是否为合法的 JS 标识符（可用于 `obj.key` 访问和对象字面量裸键名）
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// 生成代码的格式选项（对应 Prettier 的同名配置）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EmitOptions {
+    /// JS 字符串（import 路径、表达式中的类名）的引号风格，对应 `singleQuote`
+    pub quote_style: QuoteStyle,
+    /// JSX 属性值的引号风格，对应 `jsxSingleQuote`
+    pub jsx_quote_style: QuoteStyle,
+}
+
+/// 注入 import 的插入位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportPosition {
+    /// 文件顶部：指令序言与文件头注释之后、第一条语句之前
+    #[default]
+    Top,
+    /// 最后一条已有 import 之后；文件中没有 import 时同 `Top`
+    AfterImports,
+}
+
+/// 样式 import 路径追加的查询后缀（Vite）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportQuery {
+    /// 不追加
+    #[default]
+    None,
+    /// `?inline`：导入 CSS 文本而不注入页面
+    Inline,
+    /// `?url`：导入处理后的资源 URL
+    Url,
+}
+
+impl ImportQuery {
+    /// 为路径追加后缀；路径中已有查询参数时原样返回
+    ///
+    /// ```
+    /// use headwind_transform::ImportQuery;
+    ///
+    /// assert_eq!(ImportQuery::Inline.apply("./App.css"), "./App.css?inline");
+    /// assert_eq!(ImportQuery::Url.apply("./App.css?raw"), "./App.css?raw");
+    /// ```
+    pub fn apply(self, path: &str) -> String {
+        let suffix = match self {
+            ImportQuery::None => return path.to_string(),
+            ImportQuery::Inline => "?inline",
+            ImportQuery::Url => "?url",
+        };
+        if path.contains('?') {
+            path.to_string()
+        } else {
+            format!("{}{}", path, suffix)
+        }
+    }
+}
+
+/// CSS Modules 自动推导的样式文件扩展名
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CssModuleExtension {
+    /// `./App.module.css`
+    #[default]
+    Css,
+    /// `./App.module.scss`
+    Scss,
+}
+
+impl CssModuleExtension {
+    /// 扩展名（不含点）
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CssModuleExtension::Css => "css",
+            CssModuleExtension::Scss => "scss",
+        }
+    }
+}
+
+/// 注入 import 语句的选项
+///
+/// 只影响新注入的 import；文件中已有的同路径样式 import 按最终路径（含查询后缀）匹配并复用。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ImportOptions {
+    /// import 路径的引号风格，None 时跟随 `EmitOptions::quote_style`
+    pub quote_style: Option<QuoteStyle>,
+    /// 样式 import 路径追加的查询后缀（默认不追加），显式指定的 `import_path` 同样生效
+    pub query: ImportQuery,
+    /// CSS Modules 未指定 `import_path` 时推导路径使用的扩展名（默认 `.module.css`）
+    pub css_module_extension: CssModuleExtension,
+    /// 插入位置（默认文件顶部）
+    pub position: ImportPosition,
+    /// CssModules / ShadowDom 未指定 `import_path` 时的样式文件名模板（默认按扩展名推导）
+    ///
+    /// 相对源文件所在目录，可以使用 `[name]` 与 `[contenthash]` 占位符（见 `asset` 模块），
+    /// 如 `"[name].[contenthash].module.css"` → `./App.3f2a9c1e.module.css`。
+    /// 显式指定的 `import_path` 同样支持这两个占位符。
+    pub file_name: Option<String>,
+    /// 路径别名，与 tsconfig `paths` 写法相同：`"@styles/*"` → `"src/styles/*"`（默认空）
+    ///
+    /// 目标目录相对项目根目录（即 `filename` 的基准目录）。样式文件位于某个目标目录下时，
+    /// 注入的 import 改用别名路径，多个目标匹配时取最长的目标。
+    pub aliases: IndexMap<String, String>,
+}
+
+impl ImportOptions {
+    /// 把相对 `filename` 的样式路径改写为别名路径；非相对路径或不在任何目标目录下时原样返回
+    ///
+    /// ```
+    /// use headwind_transform::ImportOptions;
+    ///
+    /// let imports = ImportOptions {
+    ///     aliases: [("@styles/*".to_string(), "src/styles/*".to_string())].into_iter().collect(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     imports.alias_path("./App.module.css", "src/styles/App.tsx"),
+    ///     "@styles/App.module.css"
+    /// );
+    /// assert_eq!(imports.alias_path("../styles/theme.css", "src/pages/Home.tsx"), "@styles/theme.css");
+    /// assert_eq!(imports.alias_path("./App.css", "src/App.tsx"), "./App.css");
+    /// ```
+    pub fn alias_path(&self, path: &str, filename: &str) -> String {
+        let resolved = (path.starts_with("./") || path.starts_with("../"))
+            .then(|| resolve_relative(filename, path))
+            .flatten();
+        let Some(resolved) = resolved else {
+            return path.to_string();
+        };
+        self.aliases
+            .iter()
+            .filter_map(|(alias, target)| {
+                let target = alias_prefix(target.trim_start_matches("./"));
+                let rest = if target.is_empty() {
+                    resolved.as_str()
+                } else {
+                    resolved.strip_prefix(target)?.strip_prefix('/')?
+                };
+                let alias = alias_prefix(alias);
+                let aliased = if alias.is_empty() {
+                    rest.to_string()
+                } else {
+                    format!("{}/{}", alias, rest)
+                };
+                Some((target.len(), aliased))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, aliased)| aliased)
+            .unwrap_or_else(|| path.to_string())
+    }
+}
+
+/// 别名或目标去掉末尾的 `/*`：`@styles/*` → `@styles`，`./*` → ``
+fn alias_prefix(pattern: &str) -> &str {
+    let pattern = pattern.trim_end_matches('*').trim_end_matches('/');
+    if pattern == "." {
+        ""
+    } else {
+        pattern
+    }
+}
+
+/// 相对 `filename` 所在目录解析路径并规范化，越过根目录时返回 None
+fn resolve_relative(filename: &str, path: &str) -> Option<String> {
+    let mut parts: Vec<&str> = filename.trim_start_matches("./").split('/').collect();
+    parts.pop();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop().filter(|p| !p.is_empty() && *p != "..")?;
+            }
+            _ => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// 用户格式化钩子：`(code, filename) -> formatted code`
+///
+/// 在输出代码返回前调用，可接入 Prettier / dprint 等外部格式化器。
+pub type Formatter = Arc<dyn Fn(&str, &str) -> Result<String, String>>;
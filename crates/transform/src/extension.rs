@@ -0,0 +1,84 @@
+//! AST 扩展
+//!
+//! 集成方常在迁移类名的同时做其他 codemod（改 import、删测试属性等）。为免每个文件再解析一遍，
+//! 可以在 `TransformOptions::ast_extensions` 中提供自己的 `VisitMut`：它与类名访问器在同一次
+//! 解析、输出中运行，共享 SourceMap 与注释，改动随转换结果一起输出。
+
+use indexmap::IndexMap;
+use std::sync::Arc;
+use swc_core::common::comments::SingleThreadedComments;
+use swc_core::common::sync::Lrc;
+use swc_core::common::SourceMap;
+use swc_core::ecma::ast::Module;
+use swc_core::ecma::visit::VisitMut;
+
+/// 扩展访问器可以使用的当前文件信息
+pub struct AstContext<'a> {
+    /// 传给 `transform_jsx` 的文件名
+    pub filename: &'a str,
+    /// 本次解析使用的 SourceMap，可用于由 span 查询行号
+    pub source_map: &'a Lrc<SourceMap>,
+    /// 解析时收集的注释；新增的注释随代码一起输出
+    pub comments: &'a SingleThreadedComments,
+    /// 本文件的类名映射：类名替换之前运行时为空，之后运行时为完整映射
+    pub class_map: &'a IndexMap<String, String>,
+}
+
+/// 与类名访问器在同一遍中运行的 AST 扩展
+///
+/// ```
+/// use headwind_transform::{transform_jsx, AstContext, AstExtension, TransformOptions};
+/// use std::sync::Arc;
+/// use swc_core::ecma::ast::{JSXAttrName, JSXAttrOrSpread, JSXOpeningElement};
+/// use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+///
+/// /// 删除 `data-testid` 属性
+/// struct StripTestIds;
+///
+/// impl VisitMut for StripTestIds {
+///     fn visit_mut_jsx_opening_element(&mut self, el: &mut JSXOpeningElement) {
+///         el.attrs.retain(|attr| !matches!(
+///             attr,
+///             JSXAttrOrSpread::JSXAttr(a)
+///                 if matches!(&a.name, JSXAttrName::Ident(n) if &*n.sym == "data-testid")
+///         ));
+///         el.visit_mut_children_with(self);
+///     }
+/// }
+///
+/// impl AstExtension for StripTestIds {
+///     fn visitor<'a>(&self, _: &AstContext<'a>) -> Box<dyn VisitMut + 'a> {
+///         Box::new(StripTestIds)
+///     }
+/// }
+///
+/// let options = TransformOptions {
+///     ast_extensions: vec![Arc::new(StripTestIds)],
+///     ..Default::default()
+/// };
+/// let source = r#"const App = () => <div className="p-4" data-testid="app" />;"#;
+/// let result = transform_jsx(source, "App.tsx", options).unwrap();
+/// assert!(!result.code.contains("data-testid"));
+/// assert!(result.code.contains(&result.class_map["p-4"]));
+/// ```
+pub trait AstExtension {
+    /// 为一个文件创建访问器，可以借用上下文中的 SourceMap 与注释
+    fn visitor<'a>(&self, context: &AstContext<'a>) -> Box<dyn VisitMut + 'a>;
+
+    /// 是否在类名替换之前运行（默认之后，此时看到的是已替换的类名）
+    fn before_classes(&self) -> bool {
+        false
+    }
+}
+
+/// 依次运行 `before_classes()` 与 `before` 一致的扩展
+pub(crate) fn run_extensions(
+    extensions: &[Arc<dyn AstExtension>],
+    before: bool,
+    module: &mut Module,
+    context: &AstContext<'_>,
+) {
+    for extension in extensions.iter().filter(|e| e.before_classes() == before) {
+        extension.visitor(context).visit_mut_module(module);
+    }
+}
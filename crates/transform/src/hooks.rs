@@ -0,0 +1,26 @@
+//! 转换流水线钩子
+//!
+//! 集成方可以在转换过程中改写结果，而不必事后处理最终的字符串：
+//! `on_class_collected` 在为类字符串命名、生成 CSS 之前调用，可以改名、删除或替换其中的类；
+//! `on_rule_generated` 在每条 CSS 规则写出前调用，可以改写选择器与声明，清空声明即丢弃该规则。
+
+use std::sync::Arc;
+
+pub use headwind_tw_index::CssRule;
+
+/// 收集到的一个类字符串，交给 `on_class_collected` 钩子处理
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectedClass {
+    /// 源码中的原始类字符串（去掉首尾空白，开启规范化时合并空白、删除重复的类），即 `class_map` 的键
+    pub original: String,
+    /// 参与生成的工具类；清空后不生成类名与 CSS，只保留原样保留的类
+    pub classes: Vec<String>,
+    /// 生成的类名，None 时由命名策略生成；设置后直接使用，不参与保留类名的冲突检查
+    pub name: Option<String>,
+}
+
+/// 类字符串收集钩子，见 [`CollectedClass`]
+pub type ClassHook = Arc<dyn Fn(&mut CollectedClass)>;
+
+/// CSS 规则生成钩子：收到最终选择器（如 `.c_1:hover`）与声明，包裹规则的 `@media` 等不受影响
+pub type RuleHook = Arc<dyn Fn(&mut CssRule)>;
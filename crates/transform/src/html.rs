@@ -1,17 +1,104 @@
-use crate::collector::ClassCollector;
+use crate::collector::{ClassCollector, ClassParts};
+use crate::ignore::IgnoreDirectives;
+use headwind_core::Diagnostic;
+use std::ops::Range;
+
+/// `transform_html` 输出的文档外壳
+///
+/// CMS 片段等输入没有 `<html>` / `<body>`，默认原样输出片段；需要独立可打开的页面时补上外壳，
+/// 反过来也可以只取完整文档 `<body>` 的内容嵌入其他页面。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HtmlWrapper {
+    /// 与输入一致：片段输出片段，完整文档输出完整文档（默认）
+    #[default]
+    Preserve,
+    /// 片段补上 `<!DOCTYPE html>`、`<html>`、`<head>`、`<body>` 外壳，完整文档不变
+    Document,
+    /// 完整文档只输出 `<body>` 的内容（没有 `<body>` 时不变），片段不变
+    Fragment,
+}
+
+/// 是否为完整文档：跳过开头的空白与注释后，以 doctype、`<html>`、`<head>` 或 `<body>` 开头
+pub fn is_html_document(source: &str) -> bool {
+    let mut rest = source.trim_start();
+    while let Some(comment) = rest.strip_prefix("<!--") {
+        rest = match comment.find("-->") {
+            Some(end) => comment[end + 3..].trim_start(),
+            None => return false,
+        };
+    }
+    ["<!doctype", "<html", "<head", "<body"].iter().any(|prefix| {
+        rest.get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+            && rest[prefix.len()..]
+                .starts_with(|c: char| c == '>' || c.is_ascii_whitespace())
+    })
+}
+
+/// 完整文档中 `<body>` 的内容；缺少 `</body>` 时到 `</html>` 或输入结束
+pub(crate) fn body_content(source: &str) -> Option<&str> {
+    let lower = source.to_ascii_lowercase();
+    let open = lower.match_indices("<body").map(|(i, _)| i).find(|&i| {
+        lower[i + 5..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace())
+    })?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = lower[start..]
+        .rfind("</body")
+        .or_else(|| lower[start..].rfind("</html"))
+        .map_or(source.len(), |end| start + end);
+    Some(&source[start..end])
+}
+
+/// 为片段补上最小的文档外壳
+pub(crate) fn wrap_document(fragment: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        fragment.trim()
+    )
+}
 
 /// HTML 转换器 —— 扫描 HTML 源码中的 class="..." 属性，
 /// 将 Tailwind 类替换为生成的类名。
 ///
 /// 使用简单的状态机解析，避免引入正则依赖。
-/// 支持双引号和单引号。
+/// 支持双引号和单引号；`<!-- headwind-ignore -->` 等注释指令覆盖的 class 保持原样。
 pub fn transform_html_source(source: &str, collector: &mut ClassCollector) -> String {
+    rewrite_class_attrs(source, collector, None, &[])
+}
+
+/// DataAttribute 模式：`class="p-4"` → `data-hw="c_xxx"`，
+/// 未识别的类（Preserve 模式）保留在 `class` 中
+pub fn transform_html_source_to_data_attr(
+    source: &str,
+    collector: &mut ClassCollector,
+    data_attr: &str,
+) -> String {
+    rewrite_class_attrs(source, collector, Some(data_attr), &[])
+}
+
+/// 改写 `class` 属性；`skip` 中的区间（按起始位置排列）原样输出，如 Markdown 中的代码块
+pub(crate) fn rewrite_class_attrs(
+    source: &str,
+    collector: &mut ClassCollector,
+    data_attr: Option<&str>,
+    skip: &[Range<usize>],
+) -> String {
     let bytes = source.as_bytes();
     let len = bytes.len();
     let mut result = String::with_capacity(len);
+    let directives = IgnoreDirectives::from_html(source);
+    let mut skip = skip.iter().peekable();
     let mut i = 0;
 
     while i < len {
+        if let Some(range) = skip.next_if(|range| range.start <= i) {
+            if i < range.end {
+                result.push_str(&source[i..range.end]);
+                i = range.end;
+            }
+            continue;
+        }
+
         // 查找 "class" 关键字
         if i + 5 < len && matches_class_attr(bytes, i) {
             // 跳过 "class"
@@ -47,9 +134,38 @@ pub fn transform_html_source(source: &str, collector: &mut ClassCollector) -> St
                         let class_value = &source[value_start..i];
                         i += 1; // 跳过闭合引号
 
+                        if directives.is_ignored(attr_start) {
+                            collector.push_diagnostic(Diagnostic::info(format!(
+                                "第 {} 行的 class 按 headwind-ignore 保持原样",
+                                directives.line_of(attr_start)
+                            )));
+                            result.push_str(&source[attr_start..i]);
+                            continue;
+                        }
+
                         // 处理类值
-                        let new_class = collector.process_classes(class_value);
-                        if !new_class.is_empty() {
+                        collector.set_element(enclosing_tag(source, attr_start).map(str::to_string));
+                        let parts = collector.process_class_parts(class_value);
+                        let new_class = parts.joined();
+                        if let (Some(attr), false) = (data_attr, new_class.is_empty()) {
+                            let ClassParts { generated, kept } = parts;
+                            if generated.is_empty() {
+                                result.push_str(&source[attr_start..i]);
+                            } else {
+                                if !kept.is_empty() {
+                                    result.push_str("class=");
+                                    result.push(quote as char);
+                                    result.push_str(&kept);
+                                    result.push(quote as char);
+                                    result.push(' ');
+                                }
+                                result.push_str(attr);
+                                result.push('=');
+                                result.push(quote as char);
+                                result.push_str(&generated);
+                                result.push(quote as char);
+                            }
+                        } else if !new_class.is_empty() {
                             result.push_str("class=");
                             result.push(quote as char);
                             result.push_str(&new_class);
@@ -75,6 +191,16 @@ pub fn transform_html_source(source: &str, collector: &mut ClassCollector) -> St
     result
 }
 
+/// 属性所在元素的标签名：向前找到最近的 `<`，取其后的标签名
+fn enclosing_tag(source: &str, attr_start: usize) -> Option<&str> {
+    let open = source[..attr_start].rfind('<')?;
+    let name = &source[open + 1..attr_start];
+    let end = name
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .unwrap_or(name.len());
+    (end > 0).then(|| &name[..end])
+}
+
 /// 检查位置 i 是否为 class 属性开头
 /// 匹配 "class" 后面跟空白或 '='（区别于 className 等）
 fn matches_class_attr(bytes: &[u8], i: usize) -> bool {
@@ -107,6 +233,19 @@ mod tests {
     use super::*;
     use headwind_core::{ColorMode, CssVariableMode, NamingMode, UnknownClassMode};
 
+    #[test]
+    fn test_html_document_detection() {
+        assert!(is_html_document("\n<!-- generated -->\n<!doctype html><p>x</p>"));
+        assert!(is_html_document("<HTML lang=\"en\"><body></body></HTML>"));
+        assert!(!is_html_document("<p class=\"p-4\">x</p><p>y</p>"));
+        assert!(!is_html_document("<header class=\"p-4\"></header>"));
+
+        let doc = "<html><head><title>t</title></head><BODY class=\"m-0\">\n<p>x</p>\n</BODY></html>";
+        assert_eq!(body_content(doc), Some("\n<p>x</p>\n"));
+        assert_eq!(body_content("<html><body><p>unclosed</html>"), Some("<p>unclosed"));
+        assert_eq!(body_content("<p>fragment</p>"), None);
+    }
+
     #[test]
     fn test_html_basic() {
         let mut collector = ClassCollector::new(NamingMode::Hash, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false);
@@ -160,4 +299,42 @@ mod tests {
         assert!(result.contains("className=\"p-4\""));
         assert!(collector.class_map().is_empty());
     }
+
+    #[test]
+    fn test_html_data_attribute() {
+        let mut collector = ClassCollector::new(NamingMode::Readable, CssVariableMode::Var, UnknownClassMode::Preserve, ColorMode::default(), false)
+            .with_data_attribute("data-hw");
+        let html = r#"<div class="p-4 card">content</div>"#;
+        let result = transform_html_source_to_data_attr(html, &mut collector, "data-hw");
+
+        assert_eq!(result, r#"<div class="card" data-hw="p4">content</div>"#);
+        assert!(collector.combined_css().contains("[data-hw=\"p4\"] {"));
+    }
+
+    #[test]
+    fn test_html_ignore_comments() {
+        let mut collector = ClassCollector::new(NamingMode::Hash, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false);
+        let html = "<!-- headwind-ignore -->\n<div class=\"p-4\"><span class=\"m-2\"></span></div>\n<p class=\"p-2\"></p>";
+        let result = transform_html_source(html, &mut collector);
+
+        assert!(result.contains("<div class=\"p-4\"><span class=\"m-2\">"));
+        assert!(!result.contains("class=\"p-2\""));
+        assert_eq!(collector.class_map().len(), 1);
+        assert_eq!(collector.diagnostics().len(), 2);
+        assert!(collector.diagnostics()[0].message.contains("第 2 行"));
+    }
+
+    #[test]
+    fn test_html_css_comments() {
+        let html = r#"<div class="p-4 m-2"><my-card data-x="1" class="p-4 m-2"></my-card><p class='text-sm'>x</p></div>"#;
+        let mut collector = ClassCollector::new(NamingMode::Readable, CssVariableMode::Inline, UnknownClassMode::Remove, ColorMode::default(), false)
+            .with_resolve_spacing(true)
+            .with_css_comments(Some("index.html"));
+        transform_html_source(html, &mut collector);
+        let css = collector.combined_css();
+        // 规则只在首次出现处生成，注释标明该处的元素
+        assert!(css.starts_with("/* index.html <div> \"p-4 m-2\" */\n.p4_m2 {"), "{}", css);
+        assert!(css.contains("\n\n/* index.html <p> \"text-sm\" */\n.textsm {"), "{}", css);
+        assert_eq!(css.matches("/*").count(), 2);
+    }
 }
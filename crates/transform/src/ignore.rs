@@ -0,0 +1,214 @@
+//! `headwind-ignore` 注释指令
+//!
+//! - `headwind-ignore-next-line`：下一行中的类名保持原样
+//! - `headwind-ignore`：下一个元素（含子元素）保持原样
+//! - `headwind-disable` / `headwind-enable`：两者之间的区域保持原样；
+//!   没有对应的 `headwind-enable` 时一直持续到文件末尾，写在文件开头即禁用整个文件
+//!
+//! JSX 中写作 `{/* headwind-ignore */}`，HTML 中写作 `<!-- headwind-ignore -->`。
+//! 指令后可以跟说明文字，如 `headwind-ignore-next-line -- 第三方组件依赖原类名`。
+
+use std::ops::Range;
+
+const IGNORE_NEXT_LINE: &str = "headwind-ignore-next-line";
+const IGNORE: &str = "headwind-ignore";
+const DISABLE: &str = "headwind-disable";
+const ENABLE: &str = "headwind-enable";
+
+/// HTML 空元素（没有闭合标签）
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source",
+    "track", "wbr",
+];
+
+/// 从源码注释中解析出的跳过区域，偏移均为源码字节偏移
+#[derive(Debug, Default)]
+pub struct IgnoreDirectives {
+    /// 跳过的源码区间
+    ranges: Vec<Range<usize>>,
+    /// `headwind-ignore` 注释的结束位置（升序），作用于其后的第一个元素
+    pending_elements: Vec<usize>,
+    /// 每行的起始偏移，用于诊断中的行号
+    line_starts: Vec<usize>,
+}
+
+impl IgnoreDirectives {
+    /// 由注释列表构建，每项为注释在源码中的区间和去掉定界符后的文本
+    pub fn from_comments<'s, I>(source: &str, comments: I) -> Self
+    where
+        I: IntoIterator<Item = (Range<usize>, &'s str)>,
+    {
+        let mut comments: Vec<_> = comments.into_iter().collect();
+        comments.sort_by_key(|(span, _)| span.start);
+        comments.dedup_by_key(|(span, _)| span.start);
+
+        let mut directives = Self {
+            line_starts: std::iter::once(0)
+                .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+            ..Default::default()
+        };
+        let mut disabled_from = None;
+        for (span, text) in comments {
+            match text.split_whitespace().next() {
+                Some(IGNORE_NEXT_LINE) => directives.ranges.push(next_line(source, span.end)),
+                Some(IGNORE) => directives.pending_elements.push(span.end),
+                Some(DISABLE) if disabled_from.is_none() => disabled_from = Some(span.start),
+                Some(ENABLE) => {
+                    if let Some(start) = disabled_from.take() {
+                        directives.ranges.push(start..span.end);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = disabled_from {
+            directives.ranges.push(start..source.len());
+        }
+        directives
+    }
+
+    /// 扫描 HTML 注释 `<!-- ... -->`；`headwind-ignore` 直接解析为下一个元素（含子元素）的区间
+    pub fn from_html(source: &str) -> Self {
+        let mut comments = Vec::new();
+        let mut from = 0;
+        while let Some(open) = source[from..].find("<!--") {
+            let start = from + open;
+            let Some(close) = source[start + 4..].find("-->") else {
+                break;
+            };
+            let end = start + 4 + close + 3;
+            comments.push((start..end, &source[start + 4..end - 3]));
+            from = end;
+        }
+        let mut directives = Self::from_comments(source, comments);
+        for pos in std::mem::take(&mut directives.pending_elements) {
+            if let Some(range) = next_html_element(source, pos) {
+                directives.ranges.push(range);
+            }
+        }
+        directives
+    }
+
+    /// `offset` 是否处于跳过区间内
+    pub fn is_ignored(&self, offset: usize) -> bool {
+        self.ranges.iter().any(|range| range.contains(&offset))
+    }
+
+    /// 元素起始于 `offset` 时消费其前面尚未生效的 `headwind-ignore`，返回该元素是否被跳过。
+    /// 需按源码顺序调用
+    pub fn take_element(&mut self, offset: usize) -> bool {
+        let count = self
+            .pending_elements
+            .iter()
+            .take_while(|&&pos| pos <= offset)
+            .count();
+        self.pending_elements.drain(..count);
+        count > 0
+    }
+
+    /// `offset` 所在的行号（从 1 开始）
+    pub fn line_of(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset)
+    }
+}
+
+/// `offset` 之后的下一整行（不含换行符）
+fn next_line(source: &str, offset: usize) -> Range<usize> {
+    let start = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |i| offset + i + 1);
+    let end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+    start..end
+}
+
+/// `from` 之后第一个 HTML 元素的区间（开始标签到匹配的闭合标签）
+fn next_html_element(source: &str, from: usize) -> Option<Range<usize>> {
+    let bytes = source.as_bytes();
+    let mut i = from;
+    let start = loop {
+        let lt = i + source[i..].find('<')?;
+        if bytes.get(lt + 1).is_some_and(u8::is_ascii_alphabetic) {
+            break lt;
+        }
+        i = lt + 1;
+    };
+    let name_len = source[start + 1..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .unwrap_or(source.len() - start - 1);
+    let name = &source[start + 1..start + 1 + name_len];
+    let tag_end = start + source[start..].find('>')? + 1;
+    if source[..tag_end].ends_with("/>")
+        || VOID_ELEMENTS.iter().any(|void| void.eq_ignore_ascii_case(name))
+    {
+        return Some(start..tag_end);
+    }
+
+    // 按同名标签的嵌套深度查找闭合标签
+    let mut depth = 1;
+    let mut i = tag_end;
+    while let Some(lt) = source[i..].find('<') {
+        let lt = i + lt;
+        let rest = &source[lt + 1..];
+        if let Some(closing) = rest.strip_prefix('/') {
+            if starts_with_tag(closing, name) {
+                depth -= 1;
+                if depth == 0 {
+                    let end = source[lt..].find('>').map_or(source.len(), |j| lt + j + 1);
+                    return Some(start..end);
+                }
+            }
+        } else if starts_with_tag(rest, name) {
+            depth += 1;
+        }
+        i = lt + 1;
+    }
+    Some(start..source.len())
+}
+
+/// `rest` 是否以标签名 `name` 开头（名称完整匹配，不区分大小写）
+fn starts_with_tag(rest: &str, name: &str) -> bool {
+    rest.get(..name.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(name))
+        && !rest[name.len()..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_line_and_disable_ranges() {
+        let source = "// headwind-ignore-next-line\na\nb\n/* headwind-disable */\nc\n/* headwind-enable */\nd\n";
+        let directives = IgnoreDirectives::from_comments(
+            source,
+            [
+                (0..28, " headwind-ignore-next-line"),
+                (33..55, " headwind-disable "),
+                (58..79, " headwind-enable "),
+            ],
+        );
+        let line = |text: &str| source.find(&format!("\n{}\n", text)).unwrap() + 1;
+        assert!(directives.is_ignored(line("a")));
+        assert!(!directives.is_ignored(line("b")));
+        assert!(directives.is_ignored(line("c")));
+        assert!(!directives.is_ignored(line("d")));
+        assert_eq!(directives.line_of(line("b")), 3);
+    }
+
+    #[test]
+    fn test_html_ignore_next_element() {
+        let source = "<!-- headwind-ignore --><div class=\"a\"><div class=\"b\"></div></div><p class=\"c\"></p>";
+        let directives = IgnoreDirectives::from_html(source);
+        assert!(directives.is_ignored(source.find("class=\"a\"").unwrap()));
+        assert!(directives.is_ignored(source.find("class=\"b\"").unwrap()));
+        assert!(!directives.is_ignored(source.find("class=\"c\"").unwrap()));
+    }
+
+    #[test]
+    fn test_unterminated_disable_covers_rest_of_file() {
+        let source = "/* headwind-disable */\nconst a = 1;\n";
+        let directives = IgnoreDirectives::from_comments(source, [(0..22, " headwind-disable ")]);
+        assert!(directives.is_ignored(source.len() - 1));
+    }
+}
@@ -0,0 +1,300 @@
+//! 继承分析
+//!
+//! 子元素上 `text-gray-700`、`font-medium` 这类只设置可继承属性的类，
+//! 与祖先元素生成的声明完全相同时可以省略，由 CSS 继承得到同样的结果。
+//! 分析基于元素树：祖先的类按声明逐层向下传递，遇到组件、动态类名或无法识别的类时中断，
+//! 带变体的类（`hover:text-red-500`）使对应属性不再确定，保证删除后渲染结果不变。
+
+use crate::element_tree::ElementNode;
+use headwind_core::Declaration;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// 可由继承得到、可以省略的类
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InheritedClass {
+    /// 元素标签
+    pub element: String,
+    /// 可以省略的类
+    pub class: String,
+    /// 提供相同声明的祖先元素标签
+    pub ancestor: String,
+}
+
+/// 分析结果：报告与删除可继承类后的 class 值编辑
+#[derive(Debug, Default)]
+pub(crate) struct InheritanceAnalysis {
+    pub report: Vec<InheritedClass>,
+    /// (class 值的字节区间, 删除后的 class 值)，按出现顺序排列
+    pub edits: Vec<(Range<usize>, String)>,
+}
+
+/// 可继承的 CSS 属性（自定义属性同样可继承）
+const INHERITED_PROPERTIES: &[&str] = &[
+    "color",
+    "cursor",
+    "direction",
+    "font-family",
+    "font-feature-settings",
+    "font-size",
+    "font-stretch",
+    "font-style",
+    "font-variant-numeric",
+    "font-variation-settings",
+    "font-weight",
+    "hyphens",
+    "letter-spacing",
+    "line-height",
+    "list-style-position",
+    "list-style-type",
+    "overflow-wrap",
+    "tab-size",
+    "text-align",
+    "text-indent",
+    "text-shadow",
+    "text-transform",
+    "text-wrap",
+    "visibility",
+    "white-space",
+    "word-break",
+    "word-spacing",
+    "-webkit-font-smoothing",
+    "-moz-osx-font-smoothing",
+];
+
+fn is_inherited(declaration: &Declaration) -> bool {
+    declaration.property.starts_with("--")
+        || INHERITED_PROPERTIES.contains(&declaration.property.as_str())
+}
+
+/// 相对父元素计算的值（`1.5em`、`120%`）在子元素上重新计算，不能靠继承代替
+fn is_relative(value: &str) -> bool {
+    value.contains('%') || value.replace("rem", "").contains("em")
+}
+
+/// 祖先传下来的属性值：Some((值, 祖先标签))，None 表示带变体或无法确定
+type Inherited = HashMap<String, Option<(String, String)>>;
+
+/// 元素上的一个类及其声明，带变体时声明为 None
+type ResolvedClass<'a> = (&'a str, Option<Vec<Declaration>>);
+
+/// 分析元素树，`declarations` 返回单个不带变体的类的声明（无法识别时为 None）
+pub(crate) fn analyze(
+    roots: &[ElementNode],
+    declarations: &dyn Fn(&str) -> Option<Vec<Declaration>>,
+) -> InheritanceAnalysis {
+    let mut analysis = InheritanceAnalysis::default();
+    for root in roots {
+        walk(root, &Inherited::new(), declarations, &mut analysis);
+    }
+    analysis
+}
+
+fn walk(
+    node: &ElementNode,
+    inherited: &Inherited,
+    declarations: &dyn Fn(&str) -> Option<Vec<Declaration>>,
+    analysis: &mut InheritanceAnalysis,
+) {
+    // 组件把 className 交给未知的内部元素，动态类名无法静态求值：其子树重新开始
+    let opaque = node.tag.starts_with(|c: char| c.is_ascii_uppercase())
+        || node.tag.contains('.')
+        || (node.class_span.is_none() && !node.classes.trim().is_empty());
+    let resolved = if opaque { None } else { resolve_classes(&node.classes, declarations) };
+    let Some(classes) = resolved else {
+        for child in &node.children {
+            walk(child, &Inherited::new(), declarations, analysis);
+        }
+        return;
+    };
+
+    let mut removed = Vec::new();
+    for (i, (class, decls)) in classes.iter().enumerate() {
+        let Some(decls) = decls else { continue };
+        // 同一元素上其他类设置了相同属性时，结果取决于规则顺序，保留
+        let contested = classes.iter().enumerate().any(|(j, (_, other))| {
+            j != i && decls.iter().any(|d| property_set(other, &d.property))
+        });
+        let ancestor = (!contested && !class.contains('!'))
+            .then(|| inherited_from(decls, inherited))
+            .flatten();
+        if let Some(ancestor) = ancestor {
+            analysis.report.push(InheritedClass {
+                element: node.tag.clone(),
+                class: class.to_string(),
+                ancestor,
+            });
+            removed.push(i);
+        }
+    }
+    if let Some(span) = node.class_span.as_ref().filter(|_| !removed.is_empty()) {
+        let kept: Vec<_> = classes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !removed.contains(i))
+            .map(|(_, (class, _))| *class)
+            .collect();
+        analysis.edits.push((span.clone(), kept.join(" ")));
+    }
+
+    // 与祖先相同的值（包括刚删除的类）不更新来源，报告中始终指向实际保留声明的祖先
+    let mut passed = inherited.clone();
+    for (_, decls) in &classes {
+        for declaration in decls.iter().flatten().filter(|d| is_inherited(d)) {
+            let unchanged = matches!(
+                passed.get(&declaration.property),
+                Some(Some((value, _))) if *value == declaration.value
+            );
+            if !unchanged {
+                passed.insert(
+                    declaration.property.clone(),
+                    Some((declaration.value.clone(), node.tag.clone())),
+                );
+            }
+        }
+    }
+    // 带变体的类在某些状态下改变属性，子元素不能依赖该属性的继承值
+    for class in node.classes.split_whitespace().filter(|class| is_variant(class)) {
+        for declaration in declarations(utility_of(class)).into_iter().flatten() {
+            passed.insert(declaration.property, None);
+        }
+    }
+    for child in &node.children {
+        walk(child, &passed, declarations, analysis);
+    }
+}
+
+/// 元素上不带变体的类及其声明；带变体的类声明记为 None。存在无法识别的类时返回 None
+fn resolve_classes<'a>(
+    classes: &'a str,
+    declarations: &dyn Fn(&str) -> Option<Vec<Declaration>>,
+) -> Option<Vec<ResolvedClass<'a>>> {
+    classes
+        .split_whitespace()
+        .map(|class| {
+            let decls = declarations(utility_of(class))?;
+            Some((class, (!is_variant(class)).then_some(decls)))
+        })
+        .collect()
+}
+
+/// 所有声明都是可继承属性且与祖先传下来的值相同时，返回提供这些值的最近祖先
+fn inherited_from(decls: &[Declaration], inherited: &Inherited) -> Option<String> {
+    let mut ancestor = None;
+    for declaration in decls {
+        if !is_inherited(declaration) || is_relative(&declaration.value) {
+            return None;
+        }
+        let (value, tag) = inherited.get(&declaration.property)?.as_ref()?;
+        if *value != declaration.value {
+            return None;
+        }
+        ancestor.get_or_insert_with(|| tag.clone());
+    }
+    ancestor
+}
+
+fn property_set(decls: &Option<Vec<Declaration>>, property: &str) -> bool {
+    decls
+        .iter()
+        .flatten()
+        .any(|declaration| declaration.property == property)
+}
+
+fn is_variant(class: &str) -> bool {
+    utility_of(class).len() != class.len()
+}
+
+/// 去掉变体前缀后的工具类：`md:hover:text-red-500` → `text-red-500`，忽略方括号内的冒号
+pub(crate) fn utility_of(class: &str) -> &str {
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => start = i + 1,
+            _ => {}
+        }
+    }
+    &class[start..]
+}
+
+/// 按编辑删除源码中的可继承类，编辑按区间起始位置排列
+pub(crate) fn apply_edits(source: &str, edits: &[(Range<usize>, String)]) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut last = 0;
+    for (range, text) in edits {
+        output.push_str(&source[last..range.start]);
+        output.push_str(text);
+        last = range.end;
+    }
+    output.push_str(&source[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::build_html_element_tree;
+
+    fn declarations(class: &str) -> Option<Vec<Declaration>> {
+        let decl = |property: &str, value: &str| Declaration::new(property, value);
+        Some(match class {
+            "text-gray-700" => vec![decl("color", "#374151")],
+            "text-red-500" => vec![decl("color", "#ef4444")],
+            "font-bold" => vec![decl("--tw-font-weight", "700"), decl("font-weight", "700")],
+            "text-[1.5em]" => vec![decl("font-size", "1.5em")],
+            "p-4" => vec![decl("padding", "1rem")],
+            _ => return None,
+        })
+    }
+
+    fn analyze_html(html: &str) -> (Vec<String>, String) {
+        let analysis = analyze(&build_html_element_tree(html), &declarations);
+        let report = analysis
+            .report
+            .iter()
+            .map(|c| format!("{} {} <- {}", c.element, c.class, c.ancestor))
+            .collect();
+        (report, apply_edits(html, &analysis.edits))
+    }
+
+    #[test]
+    fn test_inherited_classes() {
+        let (report, html) = analyze_html(
+            r#"<div class="text-gray-700 font-bold"><p class="text-gray-700 p-4"><span class="font-bold">a</span></p></div>"#,
+        );
+        assert_eq!(report, ["p text-gray-700 <- div", "span font-bold <- div"]);
+        assert_eq!(
+            html,
+            r#"<div class="text-gray-700 font-bold"><p class="p-4"><span class="">a</span></p></div>"#
+        );
+
+        // 不同的值、相对单位、非继承属性都保留
+        let (report, _) = analyze_html(
+            r#"<div class="text-gray-700 text-[1.5em] p-4"><p class="text-red-500 text-[1.5em] p-4"></p></div>"#,
+        );
+        assert!(report.is_empty(), "{:?}", report);
+    }
+
+    #[test]
+    fn test_inheritance_barriers() {
+        // 祖先带变体、有无法识别的类，或元素上其他类设置相同属性时不删除
+        for html in [
+            r#"<div class="text-gray-700 hover:text-red-500"><p class="text-gray-700"></p></div>"#,
+            r#"<div class="text-gray-700"><section class="prose"><p class="text-gray-700"></p></section></div>"#,
+            r#"<div class="text-gray-700"><p class="text-gray-700 text-red-500"></p></div>"#,
+            r#"<div class="text-gray-700"><p class="!text-gray-700"></p></div>"#,
+        ] {
+            let (report, edited) = analyze_html(html);
+            assert!(report.is_empty(), "{}: {:?}", html, report);
+            assert_eq!(edited, html);
+        }
+
+        // 没有 class 的中间元素不影响继承
+        let (report, _) =
+            analyze_html(r#"<div class="text-gray-700"><section><p class="text-gray-700"></p></section></div>"#);
+        assert_eq!(report, ["p text-gray-700 <- div"]);
+    }
+}
@@ -1,8 +1,15 @@
-use crate::collector::ClassCollector;
-use crate::CssModulesAccess;
-use swc_core::common::{Span, DUMMY_SP};
+use crate::collector::{ClassCollector, ClassParts};
+use crate::element_tree::jsx_tag_name;
+use crate::emit::{is_identifier, EmitOptions, QuoteStyle};
+use crate::ignore::IgnoreDirectives;
+use crate::runtime::RuntimeHelper;
+use crate::span_edit::{EditRecorder, SpanEdit};
+use crate::{ClassAttrMerge, ClassAttrName, CssModulesAccess};
+use headwind_core::Diagnostic;
+use swc_core::common::{BytePos, Span, Spanned, DUMMY_SP};
 use swc_core::ecma::ast::*;
-use swc_core::ecma::visit::{VisitMut, VisitMutWith};
+use swc_core::ecma::visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+use std::collections::HashSet;
 
 /// JSX/TSX 遍历器 —— 使用 SWC VisitMut 遍历 AST，
 /// 找到 className / class 属性中的字符串字面量，
@@ -12,10 +19,53 @@ use swc_core::ecma::visit::{VisitMut, VisitMutWith};
 /// - Global:              `className="c_hash123"`
 /// - CssModules + Dot:    `className={styles.textCenterP4}`
 /// - CssModules + Bracket:`className={styles["c_hash123"]}`
+/// - DataAttribute:       `data-hw="c_hash123"`
+/// - ReactNative:         `style={styles.c_hash123}`
+///
+/// Solid 的 `classList={{ "p-4": active }}` 只替换对象的键，值保持原样。
+///
+/// 开启 `with_tw_macro` 后还处理 twin.macro / tailwind-styled-components 的写法：
+/// - twin.macro：`tw="..."` 与 `` css={tw`...`} `` 属性并入 className，
+///   `` tw.div`...` `` / `` tw(Button)`...` `` 改写为渲染普通元素的组件
+/// - tailwind-styled-components：`` tw.div`...` `` / `` tw(Button)`...` `` 只替换模板内容
 pub struct JsxClassVisitor<'a> {
     collector: &'a mut ClassCollector,
     /// CSS Modules 配置。None = Global 模式
     css_modules: Option<CssModulesConfig>,
+    /// span 编辑记录。Some 时额外记录每处替换对应的原始源码区间和新文本
+    edits: Option<EditRecorder>,
+    /// 新生成字符串字面量的引号风格
+    emit: EmitOptions,
+    /// 同一元素上 class 与 className 并存时的合并策略
+    class_attr_merge: ClassAttrMerge,
+    /// 输出中类名属性的名称
+    class_attr_name: ClassAttrName,
+    /// spread 合并辅助函数名（如 `cn`）。None 时只报告诊断
+    spread_merge_helper: Option<String>,
+    /// 当前元素上参与合并的 spread 对象：(表达式, 源码文本)
+    pending_spreads: Vec<(Box<Expr>, String)>,
+    /// 混合静态/动态类名的运行时合并辅助函数。None 时跳过动态表达式
+    runtime_helper: Option<RuntimeHelper>,
+    /// 是否已生成对运行时辅助函数的调用（决定是否注入 import）
+    helper_used: bool,
+    /// DataAttribute 模式的属性名。Some 时静态类名改写为该属性
+    data_attribute: Option<String>,
+    /// ReactNative 模式：处理后的类名属性改名为 `style`
+    style_attr: bool,
+    /// `headwind-ignore` 等注释指令覆盖的区域
+    ignore: IgnoreDirectives,
+    /// 源文件在 SourceMap 中的起始位置，用于把 span 换算为指令的源码偏移
+    ignore_base: BytePos,
+    /// `tw` 标签模板的本地绑定名；None 时不处理 `tw` 属性和标签模板
+    tw_tags: Option<TwTags>,
+    /// 参数中的字符串按类名处理的函数名（如 `cva`、`cn`）
+    class_functions: Vec<String>,
+    /// 带 `/* tw */` 标记注释的表达式起点
+    class_markers: HashSet<BytePos>,
+    /// 是否处理组件（`<Button>`、`<UI.Card>`）上的类名属性
+    component_class_attrs: bool,
+    /// 当前元素是组件且不处理其类名属性
+    skip_class_attrs: bool,
 }
 
 struct CssModulesConfig {
@@ -34,9 +84,183 @@ impl<'a> JsxClassVisitor<'a> {
                 binding_name: b.to_string(),
                 access: a,
             }),
+            edits: None,
+            emit: EmitOptions::default(),
+            class_attr_merge: ClassAttrMerge::default(),
+            class_attr_name: ClassAttrName::default(),
+            spread_merge_helper: None,
+            pending_spreads: Vec::new(),
+            runtime_helper: None,
+            helper_used: false,
+            data_attribute: None,
+            style_attr: false,
+            ignore: IgnoreDirectives::default(),
+            ignore_base: BytePos(0),
+            tw_tags: None,
+            class_functions: Vec::new(),
+            class_markers: HashSet::new(),
+            component_class_attrs: true,
+            skip_class_attrs: false,
         }
     }
 
+    /// 设置是否处理组件上的类名属性：关闭后只处理 DOM 元素（小写标签名），
+    /// 组件的 `class` / `className` / `classList` 原样传给组件
+    pub fn with_component_class_attrs(mut self, enabled: bool) -> Self {
+        self.component_class_attrs = enabled;
+        self
+    }
+
+    /// 设置类名函数：调用参数中含可识别类的字符串按类名处理
+    pub fn with_class_functions(mut self, functions: Vec<String>) -> Self {
+        self.class_functions = functions;
+        self
+    }
+
+    /// 设置 `/* tw */` 标记的位置：从这些位置开始的表达式中的字符串按类名处理
+    pub fn with_class_markers(mut self, markers: HashSet<BytePos>) -> Self {
+        self.class_markers = markers;
+        self
+    }
+
+    /// 开启 twin.macro / tailwind-styled-components 支持，`tags` 为 `tw` 的本地绑定名
+    /// （见 `tw_macro_tags`）；为空时只处理 `tw` 属性
+    pub fn with_tw_macro(mut self, tags: TwTags) -> Self {
+        self.tw_tags = Some(tags);
+        self
+    }
+
+    /// 设置注释指令：被覆盖的元素 / 类名属性保持原样，并记录提示诊断
+    pub fn with_ignore_directives(mut self, directives: IgnoreDirectives, base: BytePos) -> Self {
+        self.ignore = directives;
+        self.ignore_base = base;
+        self
+    }
+
+    /// 开启 ReactNative 模式：`className="p-4"` → `style={styles.c_xxx}`，
+    /// 需同时以 CssModules 方式传入样式对象的绑定名
+    pub fn with_style_attr(mut self) -> Self {
+        self.style_attr = true;
+        self
+    }
+
+    /// 开启 DataAttribute 模式：`className="p-4"` → `data-hw="c_xxx"`
+    pub fn with_data_attribute(mut self, attr: impl Into<String>) -> Self {
+        self.data_attribute = Some(attr.into());
+        self
+    }
+
+    /// 开启运行时合并：带插值的模板字面量中静态部分生成类名，动态部分交给辅助函数
+    pub fn with_runtime_helper(mut self, helper: RuntimeHelper) -> Self {
+        self.runtime_helper = Some(helper);
+        self
+    }
+
+    /// 是否生成了运行时辅助函数调用
+    pub fn helper_used(&self) -> bool {
+        self.helper_used
+    }
+
+    /// 开启 spread 合并：`<div {...props} className="p-4">` 输出
+    /// `className={helper("c_xxx", props.className)}`，保留运行时传入的类名
+    pub fn with_spread_merge_helper(mut self, helper: impl Into<String>) -> Self {
+        self.spread_merge_helper = Some(helper.into());
+        self
+    }
+
+    /// 设置 class / className 并存时的合并策略
+    pub fn with_class_attr_merge(mut self, merge: ClassAttrMerge) -> Self {
+        self.class_attr_merge = merge;
+        self
+    }
+
+    /// 设置输出中类名属性的名称：统一为 `className` / `class`，或保持原样
+    pub fn with_class_attr_name(mut self, name: ClassAttrName) -> Self {
+        self.class_attr_name = name;
+        self
+    }
+
+    /// 设置输出格式选项（引号风格）
+    pub fn with_emit_options(mut self, emit: EmitOptions) -> Self {
+        self.emit = emit;
+        self
+    }
+
+    /// 开启 span 编辑记录，`base` 为源文件在 SourceMap 中的起始位置
+    pub fn with_span_edits(mut self, base: BytePos) -> Self {
+        self.edits = Some(EditRecorder::new(base));
+        self
+    }
+
+    /// 取出已记录的 span 编辑（未开启时为空）
+    pub fn take_edits(&mut self) -> Vec<SpanEdit> {
+        self.edits
+            .take()
+            .map(EditRecorder::into_edits)
+            .unwrap_or_default()
+    }
+
+    /// 记录字符串字面量（含引号）的替换。
+    ///
+    /// Global 模式只替换引号内的内容以保留原引号风格；
+    /// CssModules 模式替换整个字面量，`in_jsx_attr` 时外层补花括号。
+    fn record_str_edit(&mut self, span: Span, new_class: &str, in_jsx_attr: bool, attr_name: &str) {
+        if self.edits.is_none() {
+            return;
+        }
+        let merged = self.spread_merge_code(new_class, attr_name);
+        let Some(recorder) = self.edits.as_mut() else {
+            return;
+        };
+        if let Some(call) = merged {
+            if in_jsx_attr {
+                recorder.replace(span, format!("{{{}}}", call));
+            } else {
+                recorder.replace(span, call);
+            }
+            return;
+        }
+        match &self.css_modules {
+            Some(config) => {
+                let code = css_modules_expr_code(
+                    &config.binding_name,
+                    new_class,
+                    config.access,
+                    self.emit.quote_style,
+                );
+                if in_jsx_attr {
+                    recorder.replace(span, format!("{{{}}}", code));
+                } else {
+                    recorder.replace(span, code);
+                }
+            }
+            None => recorder.replace_inner(span, new_class),
+        }
+    }
+
+    /// spread 合并调用的源码文本：`helper(<生成值>, props.className)`
+    fn spread_merge_code(&self, new_class: &str, attr_name: &str) -> Option<String> {
+        let helper = self.spread_merge_helper.as_ref()?;
+        if self.pending_spreads.is_empty() {
+            return None;
+        }
+        let value = match &self.css_modules {
+            Some(config) => css_modules_expr_code(
+                &config.binding_name,
+                new_class,
+                config.access,
+                self.emit.quote_style,
+            ),
+            None => self.emit.quote_style.quote(new_class),
+        };
+        let spreads: Vec<String> = self
+            .pending_spreads
+            .iter()
+            .map(|(_, code)| format!("{}.{}", code, attr_name))
+            .collect();
+        Some(format!("{}({}, {})", helper, value, spreads.join(", ")))
+    }
+
     /// 判断 JSX 属性名是否为 class 相关属性
     fn is_class_attr(name: &JSXAttrName) -> bool {
         #[allow(unreachable_patterns)]
@@ -63,6 +287,7 @@ impl<'a> JsxClassVisitor<'a> {
                     &config.binding_name,
                     new_class,
                     config.access,
+                    self.emit.quote_style,
                 );
                 JSXAttrValue::JSXExprContainer(JSXExprContainer {
                     span,
@@ -71,37 +296,123 @@ impl<'a> JsxClassVisitor<'a> {
             }
             None => {
                 // Global: "xxx"
-                JSXAttrValue::Str(Str {
-                    span,
-                    value: new_class.into(),
-                    raw: None,
-                })
+                JSXAttrValue::Str(self.emit.jsx_quote_style.str_lit(span, new_class))
             }
         }
     }
 }
 
 impl<'a> VisitMut for JsxClassVisitor<'a> {
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        expr.visit_mut_children_with(self);
+        self.rewrite_tw_template(expr);
+        self.rewrite_marked_strings(expr);
+    }
+
+    fn visit_mut_jsx_element(&mut self, el: &mut JSXElement) {
+        let offset = self.source_offset(el.span.lo);
+        if self.ignore.take_element(offset) {
+            self.collector.push_diagnostic(Diagnostic::info(format!(
+                "第 {} 行的 <{}> 及其子元素按 headwind-ignore 保持原样",
+                self.ignore.line_of(offset),
+                jsx_tag_name(&el.opening.name)
+            )));
+            return;
+        }
+        el.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_jsx_opening_element(&mut self, el: &mut JSXOpeningElement) {
+        let offset = self.source_offset(el.span.lo);
+        if self.ignore.is_ignored(offset) {
+            if el.attrs.iter().any(|attr| class_attr(attr).is_some()) {
+                self.collector.push_diagnostic(Diagnostic::info(format!(
+                    "第 {} 行的 <{}> 按 headwind-ignore 保持原样",
+                    self.ignore.line_of(offset),
+                    jsx_tag_name(&el.name)
+                )));
+            }
+            return;
+        }
+        let skip = !self.component_class_attrs && is_component(&el.name);
+        if skip {
+            let outer_skip = std::mem::replace(&mut self.skip_class_attrs, true);
+            el.visit_mut_children_with(self);
+            self.skip_class_attrs = outer_skip;
+            return;
+        }
+        // 属性值中嵌套的元素会覆盖当前元素，处理完后恢复
+        let outer_element = self.collector.set_element(Some(jsx_tag_name(&el.name)));
+        let outer_skip = std::mem::replace(&mut self.skip_class_attrs, false);
+        self.rewrite_tw_attr(el);
+        // 元素级改写（合并、数据属性）会同时改动多个属性，任一类名属性被忽略时整体跳过
+        let attr_ignored = el.attrs.iter().filter_map(class_attr).any(|attr| {
+            self.ignore.is_ignored(self.source_offset(attr.span.lo))
+        });
+        if !attr_ignored {
+            self.merge_class_attrs(el);
+            self.rewrite_data_attribute(el);
+        }
+        let spreads = self.check_spreads(el);
+        // 属性值中可能嵌套其他 JSX 元素，遍历结束后恢复外层状态
+        let saved = std::mem::replace(&mut self.pending_spreads, spreads);
+        el.visit_mut_children_with(self);
+        self.pending_spreads = saved;
+        // 改名放在属性处理之后，spread 合并仍按源码中的属性名读取 props
+        if !attr_ignored {
+            self.normalize_class_attr_name(el);
+        }
+        self.skip_class_attrs = outer_skip;
+        self.collector.set_element(outer_element);
+    }
+
     fn visit_mut_jsx_attr(&mut self, attr: &mut JSXAttr) {
-        if !Self::is_class_attr(&attr.name) {
+        if self.skip_class_attrs {
             attr.visit_mut_children_with(self);
             return;
         }
+        if jsx_attr_name(attr) == Some("classList") {
+            self.rewrite_class_list(attr);
+            attr.visit_mut_children_with(self);
+            return;
+        }
+        // DataAttribute 模式下类名属性已在元素层面改写
+        if !Self::is_class_attr(&attr.name) || self.data_attribute.is_some() {
+            attr.visit_mut_children_with(self);
+            return;
+        }
+
+        let offset = self.source_offset(attr.span.lo);
+        if self.ignore.is_ignored(offset) {
+            self.collector.push_diagnostic(Diagnostic::info(format!(
+                "第 {} 行的 `{}` 属性按 headwind-ignore 保持原样",
+                self.ignore.line_of(offset),
+                jsx_attr_name(attr).unwrap_or("className")
+            )));
+            return;
+        }
 
+        let attr_name = if jsx_attr_name(attr) == Some("class") {
+            "class"
+        } else {
+            "className"
+        };
+        let mut processed = false;
         match &mut attr.value {
             // className="p-4 m-2"
             Some(JSXAttrValue::Str(str_lit)) => {
                 let original = Self::str_value(str_lit);
-                if !original.trim().is_empty() {
-                    let new_class = self.collector.process_classes(&original);
+                if let Some(new_class) = self.process_static(&original) {
                     let span = str_lit.span;
+                    self.record_str_edit(span, &new_class, true, attr_name);
                     attr.value = Some(self.build_attr_value(&new_class, span));
+                    processed = true;
                 }
             }
             // className={"p-4 m-2"} 或 className={`p-4 m-2`}
             Some(JSXAttrValue::JSXExprContainer(container)) => {
                 if let JSXExpr::Expr(expr) = &mut container.expr {
-                    self.visit_class_expr(expr, container.span);
+                    processed = self.visit_class_expr(expr, container.span, attr_name);
                     // CSS Modules 模式下，如果内部已转为 member expr，
                     // 上层 container 保持不变即可（已经是 JSXExprContainer）
                 }
@@ -109,74 +420,1556 @@ impl<'a> VisitMut for JsxClassVisitor<'a> {
             _ => {}
         }
 
+        if processed {
+            self.wrap_with_spread_merge(attr, attr_name);
+            if self.style_attr {
+                let span = attr.name.span();
+                if let Some(recorder) = self.edits.as_mut() {
+                    recorder.replace(span, "style");
+                }
+                attr.name = JSXAttrName::Ident(IdentName {
+                    span,
+                    sym: "style".into(),
+                });
+            }
+        }
+
         attr.visit_mut_children_with(self);
     }
 }
 
 impl<'a> JsxClassVisitor<'a> {
-    /// 处理花括号内的表达式
-    fn visit_class_expr(&mut self, expr: &mut Box<Expr>, _container_span: Span) {
+    /// span 起点对应的源码字节偏移（合成节点的 span 为 0，按文件开头处理）
+    fn source_offset(&self, pos: BytePos) -> usize {
+        pos.0.saturating_sub(self.ignore_base.0) as usize
+    }
+
+    /// Solid 的 `classList={{ "p-4 m-2": active, flex: open }}`：逐个替换对象字面量的键，
+    /// CssModules 模式改为计算属性键 `[styles.c_xxx]`。属性值不是对象字面量时保持原样
+    fn rewrite_class_list(&mut self, attr: &mut JSXAttr) {
+        let Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) = &mut attr.value
+        else {
+            return;
+        };
+        let Expr::Object(object) = expr.as_mut() else {
+            return;
+        };
+        for prop in &mut object.props {
+            if let PropOrSpread::Prop(prop) = prop {
+                if let Prop::KeyValue(kv) = prop.as_mut() {
+                    self.replace_class_key(&mut kv.key);
+                }
+            }
+        }
+    }
+
+    /// 合并同一元素上的 `class` 与 `className`。
+    ///
+    /// 仅当两者都是静态字符串时合并：优先属性保留，其类排在后面（冲突时胜出），
+    /// 另一个属性被移除。任一为动态表达式时保持原样，分别处理。
+    fn merge_class_attrs(&mut self, el: &mut JSXOpeningElement) {
+        let preferred_name = match self.class_attr_merge {
+            ClassAttrMerge::Separate => return,
+            ClassAttrMerge::PreferClassName => "className",
+            ClassAttrMerge::PreferClass => "class",
+        };
+        let other_name = if preferred_name == "className" {
+            "class"
+        } else {
+            "className"
+        };
+
+        let find = |name: &str| {
+            el.attrs.iter().position(|attr| match attr {
+                JSXAttrOrSpread::JSXAttr(a) => jsx_attr_name(a) == Some(name),
+                JSXAttrOrSpread::SpreadElement(_) => false,
+            })
+        };
+        if let (Some(preferred_idx), Some(other_idx)) = (find(preferred_name), find(other_name)) {
+            self.merge_static_attr(el, preferred_idx, other_idx);
+        }
+    }
+
+    /// 把 `other_idx` 处属性的静态类并入 `keep_idx` 处属性并移除前者。
+    ///
+    /// 被并入的类排在前面，冲突时保留的属性胜出。任一属性不是静态字符串时
+    /// 保持原样并返回 false。
+    fn merge_static_attr(&mut self, el: &mut JSXOpeningElement, keep_idx: usize, other_idx: usize) -> bool {
+        let (Some(kept), Some(other)) = (
+            static_class_value(&el.attrs[keep_idx]),
+            static_class_value(&el.attrs[other_idx]),
+        ) else {
+            return false;
+        };
+
+        let merged = format!("{} {}", other.trim(), kept.trim());
+        if let JSXAttrOrSpread::JSXAttr(attr) = &mut el.attrs[keep_idx] {
+            set_static_class_value(attr, merged.trim());
+        }
+
+        // span 编辑模式：删除被合并的属性（连同它前面的空白）
+        if let Some(recorder) = self.edits.as_mut() {
+            let prev_end = if other_idx == 0 {
+                el.name.span().hi
+            } else {
+                el.attrs[other_idx - 1].span().hi
+            };
+            recorder.replace(Span::new(prev_end, el.attrs[other_idx].span().hi), "");
+        }
+        el.attrs.remove(other_idx);
+        true
+    }
+
+    /// 按 `class_attr_name` 统一元素上类名属性的名称。
+    ///
+    /// 目标名称的属性已存在（两者并存且未合并）时改名会产生重复属性，保持原样并报告诊断。
+    fn normalize_class_attr_name(&mut self, el: &mut JSXOpeningElement) {
+        let target = match self.class_attr_name {
+            ClassAttrName::Preserve => return,
+            ClassAttrName::ClassName => "className",
+            ClassAttrName::Class => "class",
+        };
+        let names: Vec<&str> = el
+            .attrs
+            .iter()
+            .filter_map(class_attr)
+            .filter_map(jsx_attr_name)
+            .collect();
+        if !names.iter().any(|name| *name != target) {
+            return;
+        }
+        if names.contains(&target) {
+            self.collector.push_diagnostic(Diagnostic::warning(format!(
+                "<{}> 同时包含未合并的 class 与 className，无法统一为 `{}`",
+                jsx_tag_name(&el.name),
+                target
+            )));
+            return;
+        }
+        for attr in &mut el.attrs {
+            let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                continue;
+            };
+            if !Self::is_class_attr(&attr.name) {
+                continue;
+            }
+            let span = attr.name.span();
+            if let Some(recorder) = self.edits.as_mut() {
+                recorder.replace(span, target);
+            }
+            attr.name = JSXAttrName::Ident(IdentName {
+                span,
+                sym: target.into(),
+            });
+        }
+    }
+
+    /// twin.macro 的 `tw="..."` 属性：元素上没有类名属性时改名为 `className`
+    /// （`class_attr_name` 为 `Class` 时改名为 `class`），
+    /// 已有静态类名属性时并入其中，之后按普通类名属性处理。
+    ///
+    /// 类名属性为动态表达式时无法合并，保持原样并报告诊断。
+    fn rewrite_tw_attr(&mut self, el: &mut JSXOpeningElement) {
+        if self.tw_tags.is_none() {
+            return;
+        }
+        self.unwrap_css_tw_attr(el);
+        let Some(tw_idx) = el.attrs.iter().position(|attr| {
+            matches!(attr, JSXAttrOrSpread::JSXAttr(a) if jsx_attr_name(a) == Some("tw"))
+        }) else {
+            return;
+        };
+        if self.ignore.is_ignored(self.source_offset(el.attrs[tw_idx].span().lo)) {
+            return;
+        }
+        if static_class_value(&el.attrs[tw_idx]).is_none() {
+            return;
+        }
+        match el.attrs.iter().position(|attr| class_attr(attr).is_some()) {
+            Some(class_idx) => {
+                if !self.merge_static_attr(el, class_idx, tw_idx) {
+                    self.collector.push_diagnostic(Diagnostic::warning(format!(
+                        "<{}> 的 `tw` 属性无法并入动态类名属性，保持原样",
+                        jsx_tag_name(&el.name)
+                    )));
+                }
+            }
+            None => {
+                let name = match self.class_attr_name {
+                    ClassAttrName::Class => "class",
+                    ClassAttrName::Preserve | ClassAttrName::ClassName => "className",
+                };
+                if let JSXAttrOrSpread::JSXAttr(attr) = &mut el.attrs[tw_idx] {
+                    let span = attr.name.span();
+                    if let Some(recorder) = self.edits.as_mut() {
+                        recorder.replace(span, name);
+                    }
+                    attr.name = JSXAttrName::Ident(IdentName {
+                        span,
+                        sym: name.into(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// twin.macro 的 `` css={tw`p-4`} ``：`` tw`...` `` 求值为样式对象，无法作为类名值，
+    /// 去掉标签后改名为 `tw` 属性，随后按 `tw` 属性并入类名。
+    ///
+    /// 仅处理无插值、含可识别类的模板；元素上已有 `tw` 属性时保持原样。
+    fn unwrap_css_tw_attr(&mut self, el: &mut JSXOpeningElement) {
+        let Some(tags) = &self.tw_tags else {
+            return;
+        };
+        if el.attrs.iter().any(|attr| {
+            matches!(attr, JSXAttrOrSpread::JSXAttr(a) if jsx_attr_name(a) == Some("tw"))
+        }) {
+            return;
+        }
+        let Some(attr) = el.attrs.iter_mut().find_map(|attr| match attr {
+            JSXAttrOrSpread::JSXAttr(a) if jsx_attr_name(a) == Some("css") => Some(a),
+            _ => None,
+        }) else {
+            return;
+        };
+        let Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) = attr.value.as_mut()
+        else {
+            return;
+        };
+        let Expr::TaggedTpl(tagged) = expr.as_mut() else {
+            return;
+        };
+        if !matches!(tagged.tag.as_ref(), Expr::Ident(ident) if tags.twin.iter().any(|t| *t == *ident.sym))
+            || !tagged.tpl.exprs.is_empty()
+            || tagged.tpl.quasis.len() != 1
+        {
+            return;
+        }
+        if self.ignore.is_ignored(self.source_offset(attr.span.lo)) {
+            return;
+        }
+        let original = tagged.tpl.quasis[0].raw.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !self.collector.has_recognized(&original) {
+            return;
+        }
+        // span 编辑模式：删除标签，模板内容随后按 `` {`...`} `` 类名值替换
+        if let Some(recorder) = self.edits.as_mut() {
+            recorder.replace(Span::new(tagged.span.lo, tagged.tpl.span.lo), "");
+        }
+        let mut tpl = std::mem::take(&mut *tagged.tpl);
+        tpl.quasis[0].raw = original.as_str().into();
+        tpl.quasis[0].cooked = Some(original.as_str().into());
+        **expr = Expr::Tpl(tpl);
+        attr.name = JSXAttrName::Ident(IdentName {
+            span: attr.name.span(),
+            sym: "tw".into(),
+        });
+    }
+
+    /// `tw` 标签模板。
+    ///
+    /// tailwind-styled-components 的模板内容即类名：`` tw.div`p-4` `` / `` tw(Button)`p-4` ``
+    /// 保留标签、只替换模板内容。
+    ///
+    /// twin.macro 的 `` tw.div`p-4` `` / `` tw(Button)`p-4` `` 改写为渲染普通元素的组件
+    /// （见 `rewrite_twin_component`）；单独的 `` tw`p-4` `` 求值为样式对象，保持原样。
+    ///
+    /// 仅处理无插值的模板。
+    fn rewrite_tw_template(&mut self, expr: &mut Expr) {
+        let Some(tags) = &self.tw_tags else {
+            return;
+        };
+        let Expr::TaggedTpl(tagged) = expr else {
+            return;
+        };
+        let is_twin = |e: &Expr| matches!(e, Expr::Ident(ident) if tags.twin.iter().any(|t| *t == *ident.sym));
+        if is_twin(&tagged.tag) {
+            let offset = self.source_offset(tagged.span.lo);
+            if !self.ignore.is_ignored(offset) {
+                self.collector.push_diagnostic(Diagnostic::info(format!(
+                    "第 {} 行的 twin.macro `tw` 模板求值为样式对象，只有 `css={{tw`...`}}` 属性会改写为类名，保持原样",
+                    self.ignore.line_of(offset)
+                )));
+            }
+            return;
+        }
+        let twin_component = match tagged.tag.as_ref() {
+            Expr::Member(member) => is_twin(&member.obj),
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                ..
+            }) => is_twin(callee),
+            _ => false,
+        };
+        if twin_component {
+            self.rewrite_twin_component(expr);
+            return;
+        }
+        let is_tw = |e: &Expr| matches!(e, Expr::Ident(ident) if tags.styled.iter().any(|t| *t == *ident.sym));
+        let whole = is_tw(&tagged.tag);
+        let styled = match tagged.tag.as_ref() {
+            Expr::Member(member) => is_tw(&member.obj),
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                ..
+            }) => is_tw(callee),
+            _ => false,
+        };
+        if !whole && !styled {
+            return;
+        }
+        let tpl = &mut tagged.tpl;
+        if !tpl.exprs.is_empty() || tpl.quasis.len() != 1 {
+            return;
+        }
+        let offset = self.source_offset(tagged.span.lo);
+        if self.ignore.is_ignored(offset) {
+            self.collector.push_diagnostic(Diagnostic::info(format!(
+                "第 {} 行的 `tw` 模板按 headwind-ignore 保持原样",
+                self.ignore.line_of(offset)
+            )));
+            return;
+        }
+        if self.data_attribute.is_some() {
+            self.collector.push_diagnostic(Diagnostic::warning(format!(
+                "第 {} 行的 `tw` 模板无法改写为数据属性，保持原样",
+                self.ignore.line_of(offset)
+            )));
+            return;
+        }
+        // 样式组件常把类名分多行书写
+        let original = tpl.quasis[0].raw.split_whitespace().collect::<Vec<_>>().join(" ");
+        let Some(new_class) = self.process_static(&original) else {
+            return;
+        };
+        let quote = self.emit.quote_style;
+        let value = self.css_modules.as_ref().map(|config| {
+            (
+                create_css_modules_expr(&config.binding_name, &new_class, config.access, quote),
+                css_modules_expr_code(&config.binding_name, &new_class, config.access, quote),
+            )
+        });
+
+        if whole {
+            let span = tagged.span;
+            let (replacement, code) = value.unwrap_or_else(|| {
+                (Expr::Lit(Lit::Str(quote.str_lit(span, &new_class))), quote.quote(&new_class))
+            });
+            if let Some(recorder) = self.edits.as_mut() {
+                recorder.replace(span, code);
+            }
+            *expr = replacement;
+            return;
+        }
+
+        // 样式组件：CssModules 模式以插值引用 styles 对象
+        let tpl_span = tpl.span;
+        let quasi_span = tpl.quasis[0].span;
+        let empty_quasi = |span: Span, tail: bool| TplElement {
+            span,
+            tail,
+            cooked: Some("".into()),
+            raw: "".into(),
+        };
+        let inner = match value {
+            Some((replacement, code)) => {
+                tpl.quasis = vec![empty_quasi(quasi_span, false), empty_quasi(quasi_span, true)];
+                tpl.exprs = vec![Box::new(replacement)];
+                format!("${{{}}}", code)
+            }
+            None => {
+                tpl.quasis[0].raw = new_class.as_str().into();
+                tpl.quasis[0].cooked = Some(new_class.as_str().into());
+                new_class
+            }
+        };
+        if let Some(recorder) = self.edits.as_mut() {
+            recorder.replace_inner(tpl_span, inner);
+        }
+    }
+
+    /// twin.macro 的样式组件：`` tw.div`p-4` `` 改写为
+    /// `(props) => <div {...props} className={["c_xxx", props.className].filter(Boolean).join(" ")} />`，
+    /// 调用方传入的类名追加在生成名之后。
+    ///
+    /// `tw(Button)` 只处理标识符形式的组件，其余形式与 DataAttribute 模式下保持原样并报告诊断。
+    fn rewrite_twin_component(&mut self, expr: &mut Expr) {
+        let Expr::TaggedTpl(tagged) = expr else {
+            return;
+        };
+        let tpl = &tagged.tpl;
+        if !tpl.exprs.is_empty() || tpl.quasis.len() != 1 {
+            return;
+        }
+        let offset = self.source_offset(tagged.span.lo);
+        if self.ignore.is_ignored(offset) {
+            self.collector.push_diagnostic(Diagnostic::info(format!(
+                "第 {} 行的 `tw` 模板按 headwind-ignore 保持原样",
+                self.ignore.line_of(offset)
+            )));
+            return;
+        }
+        let element = match tagged.tag.as_ref() {
+            Expr::Member(MemberExpr {
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => Some(prop.sym.to_string()),
+            Expr::Call(call) => match call.args.as_slice() {
+                [ExprOrSpread { spread: None, expr }] => match expr.as_ref() {
+                    Expr::Ident(ident) => Some(ident.sym.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(element) = element else {
+            self.collector.push_diagnostic(Diagnostic::warning(format!(
+                "第 {} 行的 twin.macro 样式组件只支持 `tw.div` 与 `tw(Component)` 形式，保持原样",
+                self.ignore.line_of(offset)
+            )));
+            return;
+        };
+        if self.data_attribute.is_some() {
+            self.collector.push_diagnostic(Diagnostic::warning(format!(
+                "第 {} 行的 `tw` 模板无法改写为数据属性，保持原样",
+                self.ignore.line_of(offset)
+            )));
+            return;
+        }
+        let original = tpl.quasis[0].raw.split_whitespace().collect::<Vec<_>>().join(" ");
+        let Some(new_class) = self.process_static(&original) else {
+            return;
+        };
+        let quote = self.emit.quote_style;
+        let (value, value_code) = match &self.css_modules {
+            Some(config) => (
+                create_css_modules_expr(&config.binding_name, &new_class, config.access, quote),
+                css_modules_expr_code(&config.binding_name, &new_class, config.access, quote),
+            ),
+            None => (
+                Expr::Lit(Lit::Str(quote.str_lit(DUMMY_SP, &new_class))),
+                quote.quote(&new_class),
+            ),
+        };
+        let attr = match self.class_attr_name {
+            ClassAttrName::Class => "class",
+            ClassAttrName::Preserve | ClassAttrName::ClassName => "className",
+        };
+        if let Some(recorder) = self.edits.as_mut() {
+            recorder.replace(
+                tagged.span,
+                format!(
+                    "(props) => <{} {{...props}} {}={{[{}, props.{}].filter(Boolean).join({})}} />",
+                    element,
+                    attr,
+                    value_code,
+                    attr,
+                    quote.quote(" ")
+                ),
+            );
+        }
+        *expr = twin_component_expr(&element, attr, value, quote);
+    }
+
+    /// 检查元素上的 spread 属性。
+    ///
+    /// 与类名属性并存时，运行时传入的 className 可能覆盖生成的类名（或被其覆盖）：
+    /// 未开启合并时报告诊断；开启时返回可合并的 spread 对象，
+    /// 过于复杂（非标识符 / 成员访问）的 spread 表达式仍只报告诊断。
+    fn check_spreads(&mut self, el: &JSXOpeningElement) -> Vec<(Box<Expr>, String)> {
+        let has_class_attr = el.attrs.iter().any(|attr| {
+            matches!(attr, JSXAttrOrSpread::JSXAttr(a) if Self::is_class_attr(&a.name))
+        });
+        let spreads: Vec<&SpreadElement> = el
+            .attrs
+            .iter()
+            .filter_map(|attr| match attr {
+                JSXAttrOrSpread::SpreadElement(spread) => Some(spread),
+                JSXAttrOrSpread::JSXAttr(_) => None,
+            })
+            .collect();
+        if !has_class_attr || spreads.is_empty() {
+            return Vec::new();
+        }
+
+        let tag = match &el.name {
+            JSXElementName::Ident(ident) => ident.sym.to_string(),
+            _ => "JSX".to_string(),
+        };
+        let Some(helper) = self.spread_merge_helper.clone() else {
+            self.collector.push_diagnostic(Diagnostic::warning(format!(
+                "<{}> 同时包含 spread 属性和类名属性，运行时传入的 className 可能与生成的类名互相覆盖",
+                tag
+            )));
+            return Vec::new();
+        };
+
+        let mut mergeable = Vec::new();
+        for spread in spreads {
+            match simple_expr_code(&spread.expr) {
+                Some(code) => mergeable.push((spread.expr.clone(), code)),
+                None => self.collector.push_diagnostic(Diagnostic::warning(format!(
+                    "<{}> 的 spread 表达式无法静态引用，未用 `{}` 合并其 className",
+                    tag, helper
+                ))),
+            }
+        }
+        mergeable
+    }
+
+    /// DataAttribute 模式：静态类名改写为 `data-hw="c_xxx"`。
+    ///
+    /// Preserve 模式下未识别的类留在原属性中，数据属性紧随其后插入；
+    /// 动态表达式无法映射到单个属性值，保持原样并报告诊断。
+    fn rewrite_data_attribute(&mut self, el: &mut JSXOpeningElement) {
+        let Some(data_attr) = self.data_attribute.clone() else {
+            return;
+        };
+        let quote = self.emit.jsx_quote_style;
+        let mut i = 0;
+        while i < el.attrs.len() {
+            let JSXAttrOrSpread::JSXAttr(attr) = &el.attrs[i] else {
+                i += 1;
+                continue;
+            };
+            if !Self::is_class_attr(&attr.name) {
+                i += 1;
+                continue;
+            }
+            let attr_name = jsx_attr_name(attr).unwrap_or("className").to_string();
+            let span = attr.span;
+            let has_value = attr.value.is_some();
+            let Some(original) = static_class_value(&el.attrs[i]) else {
+                if has_value {
+                    self.collector.push_diagnostic(Diagnostic::warning(format!(
+                        "DataAttribute 模式仅支持静态类名，动态 `{}` 保持原样",
+                        attr_name
+                    )));
+                }
+                i += 1;
+                continue;
+            };
+
+            let ClassParts { generated, kept } = self.collector.process_class_parts(&original);
+            if generated.is_empty() {
+                i += 1;
+                continue;
+            }
+
+            let data = JSXAttrOrSpread::JSXAttr(JSXAttr {
+                span,
+                name: JSXAttrName::Ident(IdentName {
+                    span,
+                    sym: data_attr.as_str().into(),
+                }),
+                value: Some(JSXAttrValue::Str(quote.str_lit(span, &generated))),
+            });
+            let mut text = format!("{}={}", data_attr, quote.quote(&generated));
+            if kept.is_empty() {
+                el.attrs[i] = data;
+                i += 1;
+            } else {
+                if let JSXAttrOrSpread::JSXAttr(attr) = &mut el.attrs[i] {
+                    attr.value = Some(JSXAttrValue::Str(quote.str_lit(span, &kept)));
+                }
+                el.attrs.insert(i + 1, data);
+                text = format!("{}={} {}", attr_name, quote.quote(&kept), text);
+                i += 2;
+            }
+            if let Some(recorder) = self.edits.as_mut() {
+                recorder.replace(span, text);
+            }
+        }
+    }
+
+    /// 将已替换的类名属性值包装为 `helper(<生成值>, props.className)`
+    fn wrap_with_spread_merge(&mut self, attr: &mut JSXAttr, attr_name: &str) {
+        if self.pending_spreads.is_empty() {
+            return;
+        }
+        let Some(helper) = self.spread_merge_helper.clone() else {
+            return;
+        };
+        let (span, generated) = match attr.value.take() {
+            Some(JSXAttrValue::Str(s)) => {
+                let value = Self::str_value(&s);
+                (
+                    s.span,
+                    Box::new(Expr::Lit(Lit::Str(self.emit.quote_style.str_lit(s.span, &value)))),
+                )
+            }
+            Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                span,
+                expr: JSXExpr::Expr(expr),
+            })) => (span, expr),
+            other => {
+                attr.value = other;
+                return;
+            }
+        };
+
+        let mut args = vec![*generated];
+        for (spread, _) in &self.pending_spreads {
+            args.push(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: spread.clone(),
+                prop: MemberProp::Ident(IdentName {
+                    span: DUMMY_SP,
+                    sym: attr_name.into(),
+                }),
+            }));
+        }
+
+        let call = create_call_expr(&helper, args);
+        attr.value = Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            span,
+            expr: JSXExpr::Expr(Box::new(call)),
+        }));
+    }
+
+    /// 处理静态类字符串；没有生成任何类（空串、只有 `group` / `peer` 标记、
+    /// Preserve 模式下全部未识别）时返回 None，属性保持原样
+    fn process_static(&mut self, original: &str) -> Option<String> {
+        let parts = self.collector.process_class_parts(original);
+        (!parts.generated.is_empty()).then(|| parts.joined())
+    }
+
+    /// 处理花括号内的表达式，返回是否进行了替换
+    fn visit_class_expr(&mut self, expr: &mut Box<Expr>, _container_span: Span, attr_name: &str) -> bool {
         match expr.as_mut() {
             // className={"p-4 m-2"}
             Expr::Lit(Lit::Str(str_lit)) => {
                 let original = Self::str_value(str_lit);
-                if !original.trim().is_empty() {
-                    let new_class = self.collector.process_classes(&original);
+                if let Some(new_class) = self.process_static(&original) {
+                    self.record_str_edit(str_lit.span, &new_class, false, attr_name);
                     match &self.css_modules {
                         Some(config) => {
                             **expr = create_css_modules_expr(
                                 &config.binding_name,
                                 &new_class,
                                 config.access,
+                                self.emit.quote_style,
                             );
                         }
                         None => {
-                            str_lit.value = new_class.into();
-                            str_lit.raw = None;
+                            *str_lit = self.emit.quote_style.str_lit(str_lit.span, &new_class);
                         }
                     }
+                    return true;
                 }
+                false
             }
             // className={`p-4 m-2`} — 无插值模板字面量
             Expr::Tpl(tpl) if tpl.exprs.is_empty() && tpl.quasis.len() == 1 => {
                 if let Some(quasi) = tpl.quasis.first() {
                     let original: &str = &quasi.raw;
-                    if !original.trim().is_empty() {
-                        let new_class = self.collector.process_classes(original);
+                    if let Some(new_class) = self.process_static(original) {
+                        // 模板字面量同样保留反引号
+                        self.record_str_edit(tpl.span, &new_class, false, attr_name);
                         match &self.css_modules {
                             Some(config) => {
                                 **expr = create_css_modules_expr(
                                     &config.binding_name,
                                     &new_class,
                                     config.access,
+                                    self.emit.quote_style,
                                 );
                             }
                             None => {
-                                **expr = Expr::Lit(Lit::Str(Str {
-                                    span: tpl.span,
-                                    value: new_class.into(),
-                                    raw: None,
-                                }));
+                                **expr = Expr::Lit(Lit::Str(
+                                    self.emit.quote_style.str_lit(tpl.span, &new_class),
+                                ));
                             }
                         }
+                        return true;
                     }
                 }
+                false
             }
+            // className={`p-4 ${active ? "ring" : ""}`} — 静态部分生成类名，动态部分运行时合并
+            Expr::Tpl(_) if self.runtime_helper.is_some() => self.rewrite_mixed_template(expr),
+            // className={"p-4 " + (active ? "ring" : "")} — 字面量部分生成类名，动态部分保留
+            Expr::Bin(BinExpr {
+                op: BinaryOp::Add, ..
+            }) if !self.style_attr => self.rewrite_concat(expr),
             _ => {
                 // 动态表达式暂不处理
+                false
+            }
+        }
+    }
+
+    /// 类名函数的参数、`/* tw */` 标记的表达式中的字符串按类名处理
+    fn rewrite_marked_strings(&mut self, expr: &mut Expr) {
+        if self.class_markers.contains(&expr.span().lo) {
+            self.rewrite_class_strings(expr);
+            return;
+        }
+        let Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            args,
+            ..
+        }) = expr
+        else {
+            return;
+        };
+        let is_class_function = simple_expr_code(callee)
+            .is_some_and(|name| self.class_functions.contains(&name));
+        if is_class_function {
+            for arg in args.iter_mut().filter(|arg| arg.spread.is_none()) {
+                self.rewrite_class_strings(&mut arg.expr);
+            }
+        }
+    }
+
+    /// 递归处理表达式中的类名字符串：数组元素、对象的键和值、条件与逻辑表达式的分支。
+    ///
+    /// 对象中 cva 的 `defaultVariants` 整体跳过，`compoundVariants` 只处理 `class` / `className`。
+    /// 嵌套的函数调用不展开（类名函数由 `rewrite_marked_strings` 单独处理）。
+    fn rewrite_class_strings(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => self.replace_class_literal(expr),
+            Expr::Array(array) => {
+                for elem in array.elems.iter_mut().flatten() {
+                    if elem.spread.is_none() {
+                        self.rewrite_class_strings(&mut elem.expr);
+                    }
+                }
+            }
+            Expr::Object(object) => {
+                for prop in &mut object.props {
+                    let PropOrSpread::Prop(prop) = prop else {
+                        continue;
+                    };
+                    let Prop::KeyValue(kv) = prop.as_mut() else {
+                        continue;
+                    };
+                    match prop_key_name(&kv.key).as_deref() {
+                        Some("defaultVariants") => {}
+                        Some("compoundVariants") => self.rewrite_compound_variants(&mut kv.value),
+                        _ => {
+                            self.replace_class_key(&mut kv.key);
+                            self.rewrite_class_strings(&mut kv.value);
+                        }
+                    }
+                }
+            }
+            Expr::Cond(cond) => {
+                self.rewrite_class_strings(&mut cond.cons);
+                self.rewrite_class_strings(&mut cond.alt);
+            }
+            Expr::Bin(bin) => match bin.op {
+                BinaryOp::LogicalAnd => self.rewrite_class_strings(&mut bin.right),
+                BinaryOp::LogicalOr | BinaryOp::NullishCoalescing => {
+                    self.rewrite_class_strings(&mut bin.left);
+                    self.rewrite_class_strings(&mut bin.right);
+                }
+                _ => {}
+            },
+            Expr::Paren(paren) => self.rewrite_class_strings(&mut paren.expr),
+            _ => {}
+        }
+    }
+
+    /// cva 的 `compoundVariants: [{ intent: "primary", class: "uppercase" }]`
+    fn rewrite_compound_variants(&mut self, expr: &mut Expr) {
+        let Expr::Array(array) = expr else {
+            return;
+        };
+        for elem in array.elems.iter_mut().flatten() {
+            let Expr::Object(object) = elem.expr.as_mut() else {
+                continue;
+            };
+            for prop in &mut object.props {
+                if let PropOrSpread::Prop(prop) = prop {
+                    if let Prop::KeyValue(kv) = prop.as_mut() {
+                        if matches!(prop_key_name(&kv.key).as_deref(), Some("class" | "className")) {
+                            self.rewrite_class_strings(&mut kv.value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// JSX 属性以外的类名字符串应替换成的值；不含可识别的类、被忽略或
+    /// DataAttribute 模式（值不经过类名属性）时返回 None
+    fn marked_class_value(&mut self, span: Span, original: &str) -> Option<String> {
+        if !self.collector.has_recognized(original) {
+            return None;
+        }
+        let offset = self.source_offset(span.lo);
+        if self.ignore.is_ignored(offset) {
+            self.collector.push_diagnostic(Diagnostic::info(format!(
+                "第 {} 行的类名字符串按 headwind-ignore 保持原样",
+                self.ignore.line_of(offset)
+            )));
+            return None;
+        }
+        if self.data_attribute.is_some() {
+            self.collector.push_diagnostic(Diagnostic::warning(format!(
+                "第 {} 行的类名字符串无法改写为数据属性，保持原样",
+                self.ignore.line_of(offset)
+            )));
+            return None;
+        }
+        self.process_static(original)
+    }
+
+    /// 替换字符串 / 无插值模板字面量
+    fn replace_class_literal(&mut self, expr: &mut Expr) {
+        let (span, original) = match &*expr {
+            Expr::Lit(Lit::Str(s)) => (s.span, Self::str_value(s)),
+            Expr::Tpl(tpl) if tpl.exprs.is_empty() && tpl.quasis.len() == 1 => {
+                (tpl.span, tpl.quasis[0].raw.to_string())
+            }
+            _ => return,
+        };
+        let Some(new_class) = self.marked_class_value(span, &original) else {
+            return;
+        };
+        let quote = self.emit.quote_style;
+        match &self.css_modules {
+            Some(config) => {
+                let code = css_modules_expr_code(&config.binding_name, &new_class, config.access, quote);
+                *expr = create_css_modules_expr(&config.binding_name, &new_class, config.access, quote);
+                if let Some(recorder) = self.edits.as_mut() {
+                    recorder.replace(span, code);
+                }
+            }
+            None => {
+                *expr = Expr::Lit(Lit::Str(quote.str_lit(span, &new_class)));
+                if let Some(recorder) = self.edits.as_mut() {
+                    recorder.replace_inner(span, new_class);
+                }
+            }
+        }
+    }
+
+    /// 替换对象键（clsx 风格的 `{ "p-4": active }`）
+    fn replace_class_key(&mut self, key: &mut PropName) {
+        let (span, original) = match &*key {
+            PropName::Str(s) => (s.span, Self::str_value(s)),
+            PropName::Ident(ident) => (ident.span, ident.sym.to_string()),
+            _ => return,
+        };
+        let Some(new_class) = self.marked_class_value(span, &original) else {
+            return;
+        };
+        let quote = self.emit.quote_style;
+        let code = match &self.css_modules {
+            Some(config) => {
+                *key = PropName::Computed(ComputedPropName {
+                    span,
+                    expr: Box::new(create_css_modules_expr(
+                        &config.binding_name,
+                        &new_class,
+                        config.access,
+                        quote,
+                    )),
+                });
+                format!(
+                    "[{}]",
+                    css_modules_expr_code(&config.binding_name, &new_class, config.access, quote)
+                )
+            }
+            None => {
+                *key = PropName::Str(quote.str_lit(span, &new_class));
+                quote.quote(&new_class)
+            }
+        };
+        if let Some(recorder) = self.edits.as_mut() {
+            recorder.replace(span, code);
+        }
+    }
+
+    /// 将字符串拼接 `"p-4 " + x + " m-2"` 中字面量部分的类生成类名，动态部分原样保留。
+    ///
+    /// 与动态部分紧贴的片段（如 `"text-" + color`）属于动态部分。输出为字符串时就地改写：
+    /// 生成值放在第一个静态类的位置，其余静态类删除（`"c_abc " + x + " "`），不需要运行时辅助函数；
+    /// CSS Modules 模式下与插值模板字面量一样改写为 `helper(styles.c_abc, x)`，未设置辅助函数时不处理。
+    fn rewrite_concat(&mut self, expr: &mut Box<Expr>) -> bool {
+        let helper = match (&self.css_modules, &self.runtime_helper) {
+            (None, _) => None,
+            (Some(_), Some(helper)) => Some(helper.name.clone()),
+            (Some(_), None) => return false,
+        };
+        let span = expr.span();
+        let mut operands = Vec::new();
+        concat_operands(expr, &mut operands);
+        let last = operands.len() - 1;
+        let literals: Vec<ConcatLiteral> = operands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, operand)| ConcatLiteral::parse(i, last, operand))
+            .collect();
+        let static_classes: Vec<&str> = literals
+            .iter()
+            .flat_map(|literal| literal.static_classes())
+            .collect();
+        if static_classes.is_empty() {
+            return false;
+        }
+        let joined = static_classes.join(" ");
+        let Some(new_class) = self.process_static(&joined) else {
+            return false;
+        };
+
+        let quote = self.emit.quote_style;
+        // 字符串输出时生成值写回第一个含静态类的字面量，CSS Modules 时全部交给辅助函数
+        let mut replacement = helper.is_none().then_some(new_class.as_str());
+        for literal in &literals {
+            if literal.static_classes().next().is_none() {
+                continue;
+            }
+            let value = literal.rebuild(replacement.take());
+            if let Some(recorder) = self.edits.as_mut() {
+                recorder.replace_inner(literal.span, value.clone());
+            }
+            *operands[literal.index] = Expr::Lit(Lit::Str(quote.str_lit(literal.span, &value)));
+        }
+        let Some(helper) = helper else {
+            return true;
+        };
+
+        // 只剩一个动态操作数（其余字面量只有空白）时直接作为辅助函数的参数
+        let remaining: Vec<&Expr> = operands
+            .iter()
+            .map(|operand| &**operand)
+            .filter(|operand| !is_blank_str(operand))
+            .collect();
+        let single = match remaining.as_slice() {
+            [single] => Some((*single).clone()),
+            _ => None,
+        };
+        let dynamic = single.unwrap_or_else(|| (**expr).clone());
+        let Some(config) = &self.css_modules else {
+            return false;
+        };
+        if let Some(recorder) = self.edits.as_mut() {
+            let value_code = css_modules_expr_code(&config.binding_name, &new_class, config.access, quote);
+            recorder.replace(Span::new(span.lo, span.lo), format!("{}({}, ", helper, value_code));
+            recorder.replace(Span::new(span.hi, span.hi), ")");
+        }
+        let generated = create_css_modules_expr(&config.binding_name, &new_class, config.access, quote);
+        **expr = create_call_expr(&helper, vec![generated, dynamic]);
+        self.helper_used = true;
+        true
+    }
+
+    /// 将带插值的模板字面量改写为 `helper(<生成值>, <剩余模板>)`。
+    ///
+    /// 与插值紧贴的片段（如 `` text-${color} ``）属于动态部分，原样保留。
+    fn rewrite_mixed_template(&mut self, expr: &mut Box<Expr>) -> bool {
+        let Some(helper) = self.runtime_helper.as_ref().map(|h| h.name.clone()) else {
+            return false;
+        };
+        let Expr::Tpl(tpl) = expr.as_mut() else {
+            return false;
+        };
+
+        let ranges = static_template_tokens(tpl);
+        let static_classes: Vec<&str> = tpl
+            .quasis
+            .iter()
+            .zip(&ranges)
+            .flat_map(|(quasi, rs)| rs.iter().map(move |&(s, e)| &quasi.raw[s..e]))
+            .collect();
+        if static_classes.is_empty() {
+            return false;
+        }
+        let joined = static_classes.join(" ");
+        let Some(new_class) = self.process_static(&joined) else {
+            return false;
+        };
+
+        let value_code = match &self.css_modules {
+            Some(config) => css_modules_expr_code(
+                &config.binding_name,
+                &new_class,
+                config.access,
+                self.emit.quote_style,
+            ),
+            None => self.emit.quote_style.quote(&new_class),
+        };
+        if let Some(recorder) = self.edits.as_mut() {
+            let (lo, hi) = (tpl.span.lo, tpl.span.hi);
+            recorder.replace(Span::new(lo, lo), format!("{}({}, ", helper, value_code));
+            for (quasi, rs) in tpl.quasis.iter().zip(&ranges) {
+                for &(s, e) in rs {
+                    let start = BytePos(quasi.span.lo.0 + s as u32);
+                    let end = BytePos(quasi.span.lo.0 + e as u32);
+                    recorder.replace(Span::new(start, end), "");
+                }
+            }
+            recorder.replace(Span::new(hi, hi), ")");
+        }
+
+        for (quasi, rs) in tpl.quasis.iter_mut().zip(&ranges) {
+            if rs.is_empty() {
+                continue;
+            }
+            let raw = remove_ranges(&quasi.raw, rs);
+            quasi.cooked = Some(raw.as_str().into());
+            quasi.raw = raw.into();
+        }
+        let dynamic = if tpl.exprs.len() == 1 && tpl.quasis.iter().all(|q| q.raw.trim().is_empty()) {
+            *tpl.exprs[0].clone()
+        } else {
+            Expr::Tpl(tpl.clone())
+        };
+        let generated = match &self.css_modules {
+            Some(config) => create_css_modules_expr(
+                &config.binding_name,
+                &new_class,
+                config.access,
+                self.emit.quote_style,
+            ),
+            None => Expr::Lit(Lit::Str(self.emit.quote_style.str_lit(DUMMY_SP, &new_class))),
+        };
+        **expr = create_call_expr(&helper, vec![generated, dynamic]);
+        self.helper_used = true;
+        true
+    }
+}
+
+/// 字符串拼接中的一个字面量操作数
+struct ConcatLiteral {
+    /// 在拼接操作数中的下标
+    index: usize,
+    span: Span,
+    leading: String,
+    trailing: String,
+    /// 类名及是否可静态提取（与相邻操作数紧贴的首尾片段不可）
+    tokens: Vec<(String, bool)>,
+}
+
+impl ConcatLiteral {
+    /// 字符串字面量或无插值、无转义的模板字面量；其他操作数属于动态部分
+    fn parse(index: usize, last: usize, operand: &Expr) -> Option<Self> {
+        let (span, value) = match operand {
+            Expr::Lit(Lit::Str(s)) => (s.span, s.value.as_str().unwrap_or_default().to_string()),
+            Expr::Tpl(tpl)
+                if tpl.exprs.is_empty() && tpl.quasis.len() == 1 && !tpl.quasis[0].raw.contains('\\') =>
+            {
+                (tpl.span, tpl.quasis[0].raw.to_string())
+            }
+            _ => return None,
+        };
+        let body = value.trim();
+        let start = value.len() - value.trim_start().len();
+        let leading = value[..start].to_string();
+        let trailing = value[start + body.len()..].to_string();
+        let classes: Vec<&str> = body.split_whitespace().collect();
+        let tokens = classes
+            .iter()
+            .enumerate()
+            .map(|(i, class)| {
+                let glued_before = index > 0 && i == 0 && leading.is_empty();
+                let glued_after = index < last && i == classes.len() - 1 && trailing.is_empty();
+                (class.to_string(), !glued_before && !glued_after)
+            })
+            .collect();
+        Some(Self {
+            index,
+            span,
+            leading,
+            trailing,
+            tokens,
+        })
+    }
+
+    fn static_classes(&self) -> impl Iterator<Item = &str> {
+        self.tokens
+            .iter()
+            .filter(|(_, is_static)| *is_static)
+            .map(|(class, _)| class.as_str())
+    }
+
+    /// 删除静态类后的值，`replacement` 放在第一个静态类的位置
+    fn rebuild(&self, mut replacement: Option<&str>) -> String {
+        let mut classes = Vec::new();
+        for (class, is_static) in &self.tokens {
+            if !is_static {
+                classes.push(class.as_str());
+            } else if let Some(generated) = replacement.take() {
+                classes.push(generated);
+            }
+        }
+        format!("{}{}{}", self.leading, classes.join(" "), self.trailing)
+    }
+}
+
+/// 只含空白的字符串字面量
+fn is_blank_str(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(Lit::Str(s)) if s.value.as_str().is_some_and(|v| v.trim().is_empty()))
+}
+
+/// 展开 `a + b + c` 的操作数（左结合的链），括号内的加法视为一个操作数
+fn concat_operands<'e>(expr: &'e mut Expr, out: &mut Vec<&'e mut Expr>) {
+    if !matches!(expr, Expr::Bin(BinExpr { op: BinaryOp::Add, .. })) {
+        out.push(expr);
+        return;
+    }
+    if let Expr::Bin(bin) = expr {
+        concat_operands(&mut bin.left, out);
+        concat_operands(&mut bin.right, out);
+    }
+}
+
+/// 模板字面量每个 quasi 中可静态提取的类名区间（相对 raw 的字节偏移）。
+///
+/// 与插值紧贴（中间没有空白）的首尾片段以及含转义的片段不计入。
+fn static_template_tokens(tpl: &Tpl) -> Vec<Vec<(usize, usize)>> {
+    let last = tpl.quasis.len().saturating_sub(1);
+    tpl.quasis
+        .iter()
+        .enumerate()
+        .map(|(i, quasi)| {
+            let raw: &str = &quasi.raw;
+            let mut tokens = Vec::new();
+            let mut start = None;
+            for (idx, ch) in raw.char_indices().chain(std::iter::once((raw.len(), ' '))) {
+                if ch.is_whitespace() {
+                    if let Some(s) = start.take() {
+                        tokens.push((s, idx));
+                    }
+                } else if start.is_none() {
+                    start = Some(idx);
+                }
+            }
+            tokens.retain(|&(s, e)| {
+                let glued_before = i > 0 && s == 0;
+                let glued_after = i < last && e == raw.len();
+                !glued_before && !glued_after && !raw[s..e].contains('\\')
+            });
+            tokens
+        })
+        .collect()
+}
+
+/// 从字符串中删除若干（有序、不重叠的）字节区间
+fn remove_ranges(raw: &str, ranges: &[(usize, usize)]) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut cursor = 0;
+    for &(s, e) in ranges {
+        out.push_str(&raw[cursor..s]);
+        cursor = e;
+    }
+    out.push_str(&raw[cursor..]);
+    out
+}
+
+/// 创建 `callee(arg1, arg2, ...)` 调用表达式
+fn create_call_expr(callee: &str, args: Vec<Expr>) -> Expr {
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        ctxt: Default::default(),
+        callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+            span: DUMMY_SP,
+            ctxt: Default::default(),
+            sym: callee.into(),
+            optional: false,
+        }))),
+        args: args
+            .into_iter()
+            .map(|expr| ExprOrSpread {
+                spread: None,
+                expr: Box::new(expr),
+            })
+            .collect(),
+        type_args: None,
+    })
+}
+
+/// `(props) => <element {...props} attr={[value, props.attr].filter(Boolean).join(" ")} />`
+fn twin_component_expr(element: &str, attr: &str, value: Expr, quote: QuoteStyle) -> Expr {
+    let ident = |sym: &str| Ident {
+        span: DUMMY_SP,
+        ctxt: Default::default(),
+        sym: sym.into(),
+        optional: false,
+    };
+    let member = |obj: Expr, prop: &str| {
+        Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: Box::new(obj),
+            prop: MemberProp::Ident(IdentName {
+                span: DUMMY_SP,
+                sym: prop.into(),
+            }),
+        })
+    };
+    let call = |callee: Expr, arg: Expr| {
+        Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            ctxt: Default::default(),
+            callee: Callee::Expr(Box::new(callee)),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: Box::new(arg),
+            }],
+            type_args: None,
+        })
+    };
+    let classes = Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: [value, member(Expr::Ident(ident("props")), attr)]
+            .into_iter()
+            .map(|expr| {
+                Some(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(expr),
+                })
+            })
+            .collect(),
+    });
+    let filtered = call(member(classes, "filter"), Expr::Ident(ident("Boolean")));
+    let joined = call(
+        member(filtered, "join"),
+        Expr::Lit(Lit::Str(quote.str_lit(DUMMY_SP, " "))),
+    );
+    let element = JSXElement {
+        span: DUMMY_SP,
+        opening: JSXOpeningElement {
+            name: JSXElementName::Ident(ident(element)),
+            span: DUMMY_SP,
+            attrs: vec![
+                JSXAttrOrSpread::SpreadElement(SpreadElement {
+                    dot3_token: DUMMY_SP,
+                    expr: Box::new(Expr::Ident(ident("props"))),
+                }),
+                JSXAttrOrSpread::JSXAttr(JSXAttr {
+                    span: DUMMY_SP,
+                    name: JSXAttrName::Ident(IdentName {
+                        span: DUMMY_SP,
+                        sym: attr.into(),
+                    }),
+                    value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                        span: DUMMY_SP,
+                        expr: JSXExpr::Expr(Box::new(joined)),
+                    })),
+                }),
+            ],
+            self_closing: true,
+            type_args: None,
+        },
+        children: Vec::new(),
+        closing: None,
+    };
+    Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        ctxt: Default::default(),
+        params: vec![Pat::Ident(BindingIdent {
+            id: ident("props"),
+            type_ann: None,
+        })],
+        body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::JSXElement(Box::new(element))))),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    })
+}
+
+/// 简单表达式（标识符、this 和点号成员访问链）的源码文本，其余返回 None
+fn simple_expr_code(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::This(_) => Some("this".to_string()),
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => Some(format!("{}.{}", simple_expr_code(obj)?, prop.sym)),
+        Expr::Paren(paren) => simple_expr_code(&paren.expr),
+        _ => None,
+    }
+}
+
+/// 标签名是否为组件：首字母大写的标识符或成员访问（`<UI.Card>`）；
+/// 小写标签（含 `my-element` 等自定义元素）和命名空间标签按 DOM 元素处理
+fn is_component(name: &JSXElementName) -> bool {
+    match name {
+        JSXElementName::Ident(ident) => ident.sym.starts_with(|c: char| c.is_ascii_uppercase()),
+        JSXElementName::JSXMemberExpr(_) => true,
+        _ => false,
+    }
+}
+
+/// 对象属性的静态键名（标识符或字符串）
+fn prop_key_name(key: &PropName) -> Option<String> {
+    match key {
+        PropName::Ident(ident) => Some(ident.sym.to_string()),
+        PropName::Str(s) => s.value.as_str().map(str::to_string),
+        _ => None,
+    }
+}
+
+/// JSX 属性名（命名空间属性返回 None）
+/// 类名属性（`className` / `class`），spread 与其他属性返回 None
+fn class_attr(attr: &JSXAttrOrSpread) -> Option<&JSXAttr> {
+    match attr {
+        JSXAttrOrSpread::JSXAttr(attr) if JsxClassVisitor::is_class_attr(&attr.name) => Some(attr),
+        _ => None,
+    }
+}
+
+fn jsx_attr_name(attr: &JSXAttr) -> Option<&str> {
+    match &attr.name {
+        JSXAttrName::Ident(ident) => Some(&*ident.sym),
+        _ => None,
+    }
+}
+
+/// 读取属性的静态类字符串：`"..."`、`{"..."}`、`` {`...`} ``（无插值）
+fn static_class_value(attr: &JSXAttrOrSpread) -> Option<String> {
+    let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+        return None;
+    };
+    match attr.value.as_ref()? {
+        JSXAttrValue::Str(s) => s.value.as_str().map(str::to_string),
+        JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        }) => match expr.as_ref() {
+            Expr::Lit(Lit::Str(s)) => s.value.as_str().map(str::to_string),
+            Expr::Tpl(tpl) if tpl.exprs.is_empty() && tpl.quasis.len() == 1 => {
+                Some(tpl.quasis[0].raw.to_string())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 原位替换静态类字符串，保留原节点 span，后续替换与 span 编辑照常进行
+fn set_static_class_value(attr: &mut JSXAttr, value: &str) {
+    match attr.value.as_mut() {
+        Some(JSXAttrValue::Str(s)) => {
+            s.value = value.into();
+            s.raw = None;
+        }
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) => match expr.as_mut() {
+            Expr::Lit(Lit::Str(s)) => {
+                s.value = value.into();
+                s.raw = None;
+            }
+            Expr::Tpl(tpl) => {
+                if let Some(quasi) = tpl.quasis.first_mut() {
+                    quasi.raw = value.into();
+                    quasi.cooked = Some(value.into());
+                }
             }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+/// 查找模块中已有的 CSS Module import，返回其本地绑定名。
+///
+/// 匹配 `import styles from '<path>'` 与 `import * as styles from '<path>'`，
+/// 用于复用已有绑定，避免重复注入同路径的 import。
+pub fn find_css_module_binding(module: &Module, import_path: &str) -> Option<String> {
+    module.body.iter().find_map(|item| {
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else {
+            return None;
+        };
+        if import.type_only || import.src.value.as_str() != Some(import_path) {
+            return None;
+        }
+        import.specifiers.iter().find_map(|spec| match spec {
+            ImportSpecifier::Default(default) => Some(default.local.sym.to_string()),
+            ImportSpecifier::Namespace(ns) => Some(ns.local.sym.to_string()),
+            ImportSpecifier::Named(_) => None,
+        })
+    })
+}
+
+const TWIN_MACRO: &str = "twin.macro";
+
+/// twin.macro / tailwind-styled-components 的 import 中 `tw` 的本地绑定名，按来源区分
+#[derive(Debug, Clone, Default)]
+pub struct TwTags {
+    /// twin.macro：`` tw`...` `` 求值为样式对象
+    pub twin: Vec<String>,
+    /// tailwind-styled-components：模板内容即类名
+    pub styled: Vec<String>,
+}
+
+/// twin.macro / tailwind-styled-components 的 import 中 `tw` 的本地绑定名。
+///
+/// 文件未从这两个包导入时返回 None；只有 `import "twin.macro"` 等无绑定导入时
+/// 返回空列表（仍处理 `tw` 属性）。
+pub fn tw_macro_tags(module: &Module) -> Option<TwTags> {
+    let mut found = false;
+    let mut tags = TwTags::default();
+    for item in &module.body {
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else {
+            continue;
+        };
+        if import.type_only {
+            continue;
         }
+        let target = match import.src.value.as_str() {
+            Some(TWIN_MACRO) => &mut tags.twin,
+            Some("tailwind-styled-components") => &mut tags.styled,
+            _ => continue,
+        };
+        found = true;
+        target.extend(import.specifiers.iter().filter_map(|spec| match spec {
+            ImportSpecifier::Default(default) => Some(default.local.sym.to_string()),
+            ImportSpecifier::Named(ImportNamedSpecifier {
+                local, imported, ..
+            }) => {
+                let imported = match imported {
+                    Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+                    Some(ModuleExportName::Str(s)) => s.value.as_str().unwrap_or_default().to_string(),
+                    None => local.sym.to_string(),
+                };
+                (imported == "tw").then(|| local.sym.to_string())
+            }
+            ImportSpecifier::Namespace(_) => None,
+        }));
+    }
+    found.then_some(tags)
+}
+
+/// 所有 twin.macro 写法都已改写时删除其 import，返回被删除的 import 声明的 span。
+///
+/// babel-plugin-macros 会按 Tailwind 类校验宏参数，残留的 import 会让生成名在构建时报错；
+/// 仍有 `tw` 属性或对 `tw` 绑定的引用（带插值的模板、单独的 `` tw`...` `` 等）时保留。
+pub fn remove_unused_twin_import(module: &mut Module) -> Vec<Span> {
+    let Some(tags) = tw_macro_tags(module) else {
+        return Vec::new();
+    };
+    let mut usage = TwinUsage {
+        tags: &tags.twin,
+        used: false,
+    };
+    module.visit_with(&mut usage);
+    if usage.used {
+        return Vec::new();
     }
+    let mut removed = Vec::new();
+    module.body.retain(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+            if !import.type_only && import.src.value.as_str() == Some(TWIN_MACRO) =>
+        {
+            removed.push(import.span);
+            false
+        }
+        _ => true,
+    });
+    removed
+}
+
+/// 查找 import 声明以外对 twin.macro 的使用
+struct TwinUsage<'a> {
+    tags: &'a [String],
+    used: bool,
+}
+
+impl Visit for TwinUsage<'_> {
+    fn visit_import_decl(&mut self, _: &ImportDecl) {}
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.used |= self.tags.iter().any(|tag| *tag == *ident.sym);
+    }
+
+    fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+        self.used |= jsx_attr_name(attr) == Some("tw");
+        attr.visit_children_with(self);
+    }
+}
+
+/// 判断某个名字是否已被任意 import 声明绑定
+pub fn has_import_binding(module: &Module, name: &str) -> bool {
+    module.body.iter().any(|item| {
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else {
+            return false;
+        };
+        import.specifiers.iter().any(|spec| {
+            let local = match spec {
+                ImportSpecifier::Default(default) => &default.local,
+                ImportSpecifier::Namespace(ns) => &ns.local,
+                ImportSpecifier::Named(named) => &named.local,
+            };
+            &*local.sym == name
+        })
+    })
+}
+
+/// 判断某个名字是否已被 import 或顶层声明（变量、函数、类）绑定
+pub fn has_top_level_binding(module: &Module, name: &str) -> bool {
+    has_import_binding(module, name)
+        || module.body.iter().any(|item| {
+            let decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+                _ => return false,
+            };
+            match decl {
+                Decl::Var(var) => var.decls.iter().any(|d| pat_binds(&d.name, name)),
+                Decl::Fn(f) => &*f.ident.sym == name,
+                Decl::Class(c) => &*c.ident.sym == name,
+                _ => false,
+            }
+        })
+}
+
+/// 解构模式中是否声明了 `name`
+fn pat_binds(pat: &Pat, name: &str) -> bool {
+    match pat {
+        Pat::Ident(ident) => &*ident.id.sym == name,
+        Pat::Array(array) => array.elems.iter().flatten().any(|p| pat_binds(p, name)),
+        Pat::Object(object) => object.props.iter().any(|prop| match prop {
+            ObjectPatProp::KeyValue(kv) => pat_binds(&kv.value, name),
+            ObjectPatProp::Assign(assign) => &*assign.key.sym == name,
+            ObjectPatProp::Rest(rest) => pat_binds(&rest.arg, name),
+        }),
+        Pat::Rest(rest) => pat_binds(&rest.arg, name),
+        Pat::Assign(assign) => pat_binds(&assign.left, name),
+        _ => false,
+    }
+}
+
+/// 为注入的绑定挑选未被占用的名称：`name` 已被 import 或顶层声明占用时
+/// 依次尝试 `name$1`、`name$2`…
+pub fn fresh_binding(module: &Module, name: &str) -> String {
+    if !has_top_level_binding(module, name) {
+        return name.to_string();
+    }
+    (1..)
+        .map(|n| format!("{}${}", name, n))
+        .find(|candidate| !has_top_level_binding(module, candidate))
+        .expect("unbounded candidates")
 }
 
 /// CSS Modules 表达式，处理 preserved unknown classes。
 ///
 /// - `"c_abc123"` → `styles.c_abc123`
 /// - `"c_abc123 my-custom foo"` → `styles.c_abc123 + " my-custom foo"`
-fn create_css_modules_expr(binding: &str, new_class: &str, access: CssModulesAccess) -> Expr {
+fn create_css_modules_expr(
+    binding: &str,
+    new_class: &str,
+    access: CssModulesAccess,
+    quote: QuoteStyle,
+) -> Expr {
     let parts: Vec<&str> = new_class.splitn(2, ' ').collect();
     let generated_name = parts[0];
-    let access_expr = create_access_expr(binding, generated_name, access);
+    let access_expr = create_access_expr(binding, generated_name, access, quote);
 
     if parts.len() > 1 {
         // styles.xxx + " unknown1 unknown2"
@@ -184,19 +1977,37 @@ fn create_css_modules_expr(binding: &str, new_class: &str, access: CssModulesAcc
             span: DUMMY_SP,
             op: BinaryOp::Add,
             left: Box::new(access_expr),
-            right: Box::new(Expr::Lit(Lit::Str(Str {
-                span: DUMMY_SP,
-                value: format!(" {}", parts[1]).into(),
-                raw: None,
-            }))),
+            right: Box::new(Expr::Lit(Lit::Str(
+                quote.str_lit(DUMMY_SP, &format!(" {}", parts[1])),
+            ))),
         })
     } else {
         access_expr
     }
 }
 
+/// 与 `create_css_modules_expr` 对应的源码文本，用于 span 编辑模式
+fn css_modules_expr_code(
+    binding: &str,
+    new_class: &str,
+    access: CssModulesAccess,
+    quote: QuoteStyle,
+) -> String {
+    let parts: Vec<&str> = new_class.splitn(2, ' ').collect();
+    let access_code = match access {
+        CssModulesAccess::Dot if is_identifier(parts[0]) => format!("{}.{}", binding, parts[0]),
+        CssModulesAccess::Dot => format!("{}[{}]", binding, quote.quote(parts[0])),
+        CssModulesAccess::Bracket => format!("{}[{}]", binding, quote.quote(parts[0])),
+    };
+    if parts.len() > 1 {
+        format!("{} + {}", access_code, quote.quote(&format!(" {}", parts[1])))
+    } else {
+        access_code
+    }
+}
+
 /// 根据 access 模式创建 `binding.prop` 或 `binding["prop"]` 表达式
-fn create_access_expr(binding: &str, prop: &str, access: CssModulesAccess) -> Expr {
+fn create_access_expr(binding: &str, prop: &str, access: CssModulesAccess, quote: QuoteStyle) -> Expr {
     let obj = Box::new(Expr::Ident(Ident {
         span: DUMMY_SP,
         ctxt: Default::default(),
@@ -205,22 +2016,19 @@ fn create_access_expr(binding: &str, prop: &str, access: CssModulesAccess) -> Ex
     }));
 
     let member_prop = match access {
-        CssModulesAccess::Dot => {
+        CssModulesAccess::Dot if is_identifier(prop) => {
             // styles.textCenterP4
             MemberProp::Ident(IdentName {
                 span: DUMMY_SP,
                 sym: prop.into(),
             })
         }
-        CssModulesAccess::Bracket => {
+        // 非法标识符（如 Readable 命名中的 `hover:`）同样退回方括号访问
+        CssModulesAccess::Dot | CssModulesAccess::Bracket => {
             // styles["c_hash123"]
             MemberProp::Computed(ComputedPropName {
                 span: DUMMY_SP,
-                expr: Box::new(Expr::Lit(Lit::Str(Str {
-                    span: DUMMY_SP,
-                    value: prop.into(),
-                    raw: None,
-                }))),
+                expr: Box::new(Expr::Lit(Lit::Str(quote.str_lit(DUMMY_SP, prop)))),
             })
         }
     };
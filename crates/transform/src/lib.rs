@@ -1,22 +1,104 @@
+mod a11y;
+mod alias;
+pub mod asset;
+pub mod batch;
+pub mod cascade;
 pub mod collector;
+pub mod components;
+pub mod config;
+pub mod css_module;
+pub mod dedup;
+pub mod dts;
 pub mod element_tree;
+pub mod emit;
+#[cfg(feature = "ecmascript")]
+pub mod extension;
+pub mod hooks;
 pub mod html;
+pub mod ignore;
+mod inheritance;
+#[cfg(feature = "ecmascript")]
 pub mod jsx_visitor;
-
+pub mod markdown;
+pub mod merge;
+pub mod naming;
+pub mod profile;
+#[cfg(feature = "ecmascript")]
+mod prologue;
+pub mod react_native;
+pub mod report;
+pub mod runtime;
+pub mod session;
+pub mod shadow;
+#[cfg(feature = "ecmascript")]
+pub mod span_edit;
+pub mod storybook;
+pub mod syntax;
+pub mod theme;
+
+use headwind_tw_index::naming::DEFAULT_HASH_LENGTH;
+use element_tree::ElementNode;
 use indexmap::IndexMap;
-use jsx_visitor::JsxClassVisitor;
-use swc_core::common::comments::SingleThreadedComments;
-use swc_core::common::sync::Lrc;
-use swc_core::common::{FileName, Globals, SourceMap, DUMMY_SP, GLOBALS};
-use swc_core::ecma::ast::*;
-use swc_core::ecma::codegen::text_writer::JsWriter;
-use swc_core::ecma::codegen::{Config as CodegenConfig, Emitter};
-use swc_core::ecma::parser::{parse_file_as_module, EsSyntax, Syntax, TsSyntax};
-use swc_core::ecma::visit::VisitMutWith;
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::Arc;
+use profile::{Phase, PhaseTimer};
+// JSX/TSX 流水线（`ecmascript` feature）
+#[cfg(feature = "ecmascript")]
+use {
+    ignore::IgnoreDirectives,
+    jsx_visitor::JsxClassVisitor,
+    std::collections::HashSet,
+    swc_core::common::comments::SingleThreadedComments,
+    swc_core::common::sync::Lrc,
+    swc_core::common::{BytePos, FileName, Globals, SourceMap, Spanned, DUMMY_SP, GLOBALS},
+    swc_core::ecma::ast::*,
+    swc_core::ecma::codegen::text_writer::JsWriter,
+    swc_core::ecma::codegen::{Config as CodegenConfig, Emitter},
+    swc_core::ecma::parser::error::Error as ParseError,
+    swc_core::ecma::parser::parse_file_as_module,
+    swc_core::ecma::visit::VisitMutWith,
+};
 
 // Re-exports
+pub use batch::{transform_many, BatchProgress, BatchResult, CancelToken, FileStatus};
+pub use cascade::CssOutputs;
 pub use collector::ClassCollector;
-pub use headwind_core::{ColorMode, CssVariableMode, NamingMode, UnknownClassMode};
+pub use components::{ComponentAnalysis, ComponentOptions, ComponentSuggestion};
+pub use config::HeadwindConfig;
+pub use css_module::{extract_css_class_names, merge_css_module, sweep_css_module};
+#[cfg(feature = "ecmascript")]
+pub use css_module::transform_css_module_incremental;
+pub use dedup::CssDedup;
+pub use dts::css_module_dts;
+pub use emit::{
+    CssModuleExtension, EmitOptions, Formatter, ImportOptions, ImportPosition, ImportQuery,
+    QuoteStyle,
+};
+#[cfg(feature = "ecmascript")]
+pub use extension::{AstContext, AstExtension};
+pub use hooks::{ClassHook, CollectedClass, CssRule, RuleHook};
+pub use html::{is_html_document, HtmlWrapper};
+pub use inheritance::InheritedClass;
+pub use merge::{merge_outputs, MergedOutput};
+pub use naming::{NameCollision, NamingStats};
+pub use profile::Timings;
+pub use report::FileReport;
+pub use runtime::RuntimeHelper;
+pub use session::{ClassRename, ConfigSession, ResolvedConfig, SweepReport, SweptRule};
+pub use shadow::{style_module_code, StyleSheetFormat};
+pub use storybook::{StoryComponent, StorybookManifest};
+pub use syntax::SyntaxOptions;
+pub use theme::DarkTheme;
+pub use headwind_tw_index::{
+    load_custom_utilities, load_custom_variants, load_index, load_versioned_index, CacheStats,
+    ConversionCache, CustomUtility, CustomVariant, TailwindIndex, VersionedIndex,
+};
+pub use headwind_core::{
+    BreakpointStyle, BreakpointUnit, ColorMode, ContainerStyle, CssVariableMode,
+    CssVariableOverrides, Diagnostic, DiagnosticLevel, HashVersion, MediaQuerySyntax, NamingMode,
+    TailwindVersion, Targets, UnitConversion, UnknownClassMode,
+};
 
 /// CSS Modules 属性访问方式
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,6 +117,66 @@ impl Default for CssModulesAccess {
     }
 }
 
+/// 同一元素上 `class` 与 `className` 并存时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClassAttrMerge {
+    /// 合并为一个 `className`，className 中的类排在后面（冲突时胜出）
+    PreferClassName,
+    /// 合并为一个 `class`，class 中的类排在后面（冲突时胜出）
+    PreferClass,
+    /// 不合并，两个属性各自生成类名（默认）
+    #[default]
+    Separate,
+}
+
+/// JSX 输出中类名属性的名称
+///
+/// Preact（含 preact/compat）同时接受 `class` 与 `className`，从 HTML 迁移来的代码常混用两者；
+/// 统一后同一项目中的元素只使用一种写法。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClassAttrName {
+    /// 保持源码中的属性名（默认）
+    #[default]
+    Preserve,
+    /// 统一为 `className`
+    ClassName,
+    /// 统一为 `class`
+    Class,
+}
+
+/// `@tailwindcss/typography` 插件类（`prose`、`prose-lg`、`dark:prose-invert`、
+/// `prose-headings:underline`、`not-prose` 等）的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProseClassMode {
+    /// 原样保留在输出中并记录一条 Info 诊断（默认），样式仍由项目中的 typography 插件提供
+    #[default]
+    Preserve,
+    /// 与其他无法识别的类一样，按 `unknown_classes` 处理
+    Unknown,
+}
+
+/// 单个类字符串或工具类的手动覆盖，见 `TransformOptions::class_overrides`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassOverride {
+    /// 固定的生成类名，键为源码中完整的类字符串（如 `"px-4 py-2 rounded"` → `"btn"`）时生效
+    pub name: Option<String>,
+    /// 替换转换结果的声明（属性 → 值），键为不带变体的单个工具类（如 `shadow-card`）时生效，
+    /// `hover:shadow-card` 等变体照常套用
+    pub css: Option<IndexMap<String, String>>,
+}
+
+/// 生成类名的复用范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameScope {
+    /// 相同类组合在所有文件中生成相同类名（默认）
+    #[default]
+    Global,
+    /// 文件名参与命名，相同类组合在不同文件中生成不同类名。
+    /// 适合每个文件的 CSS Module 单独按需加载、单独 purge 的场景。
+    /// 文件名应使用相对路径，以保证不同机器上结果一致。
+    PerFile,
+}
+
 /// 输出模式
 #[derive(Debug, Clone)]
 pub enum OutputMode {
@@ -56,6 +198,41 @@ pub enum OutputMode {
         /// 属性访问方式（默认 Dot）
         access: CssModulesAccess,
     },
+    /// 属性模式：类名属性改写为数据属性，CSS 使用属性选择器
+    /// `className="p-4"` → `data-hw="c_abc123"`，`[data-hw="c_abc123"] { ... }`
+    ///
+    /// 适合 class 属性只允许语义化类名的设计系统。仅处理静态类名，
+    /// Preserve 模式下未识别的类保留在原属性中。
+    DataAttribute {
+        /// 数据属性名（默认 "data-hw"）
+        attr: String,
+        /// CSS import 路径，语义同 Global 模式
+        import_path: Option<String>,
+    },
+    /// Shadow DOM 模式：类名照常替换为字符串，CSS 包装为 JS 样式模块
+    /// （见 `TransformResult::style_module`），组件中注入
+    /// `import sheet from './App.styles.js'`，并可追加一段 adopt 代码。
+    ShadowDom {
+        /// 样式模块的导出形式（默认 Constructable）
+        format: StyleSheetFormat,
+        /// import 绑定名（默认 "sheet"）
+        binding_name: String,
+        /// 样式模块 import 路径。
+        /// None 时自动从文件名推导：`App.tsx` → `./App.styles.js`
+        import_path: Option<String>,
+        /// 追加到文件末尾的 adopt 代码，`{sheet}` 会替换为绑定名。
+        /// 如 `MyElement.styles = [{sheet}];`。None 时只注入 import
+        adopt: Option<String>,
+    },
+    /// React Native 模式（实验性）：工具类转换为 `StyleSheet.create({...})`，
+    /// 追加到文件末尾，`className="p-4"` → `style={styles.c_abc123}`。
+    ///
+    /// 属性名转为 camelCase、长度转为数值；变体与仅 Web 支持的工具类跳过并报告诊断。
+    /// 该模式强制使用内联值与十六进制颜色，未识别的类不保留。仅适用于 `transform_jsx`。
+    ReactNative {
+        /// 样式对象的变量名（默认 "styles"）
+        binding_name: String,
+    },
 }
 
 impl Default for OutputMode {
@@ -83,6 +260,31 @@ impl OutputMode {
         }
     }
 
+    /// 快捷构造 DataAttribute 模式（`data-hw`，不注入 import）
+    pub fn data_attribute() -> Self {
+        OutputMode::DataAttribute {
+            attr: "data-hw".to_string(),
+            import_path: None,
+        }
+    }
+
+    /// 快捷构造 ShadowDom 模式（constructable stylesheet，binding = "sheet"）
+    pub fn shadow_dom() -> Self {
+        OutputMode::ShadowDom {
+            format: StyleSheetFormat::Constructable,
+            binding_name: "sheet".to_string(),
+            import_path: None,
+            adopt: None,
+        }
+    }
+
+    /// 快捷构造 ReactNative 模式（binding = "styles"）
+    pub fn react_native() -> Self {
+        OutputMode::ReactNative {
+            binding_name: "styles".to_string(),
+        }
+    }
+
     /// CssModules 模式 + 自定义 import 路径
     pub fn css_modules_with_path(path: impl Into<String>) -> Self {
         OutputMode::CssModules {
@@ -94,36 +296,447 @@ impl OutputMode {
 }
 
 /// 转换选项
+#[derive(Clone)]
 pub struct TransformOptions {
     /// 类名生成策略（默认 Hash）
     pub naming_mode: NamingMode,
+    /// Hash 命名算法版本（默认 V1）
+    ///
+    /// 同一版本保证跨平台、跨发布版本生成相同类名，
+    /// 算法见 `headwind_tw_index::naming::class_hash`。
+    pub hash_version: HashVersion,
+    /// Hash 命名的盐（默认空，仅 V2 及以上生效）
+    ///
+    /// 可放入影响 CSS 输出的选项（如 `"inline"`），使不同配置产出的类名互不冲突。
+    pub hash_salt: String,
+    /// Hash 命名的最短 hash 位数（默认 12，范围 1-64）
+    ///
+    /// 同一文件中不同类组合得到同一名称时，后者的 hash 自动加长直到不再冲突，
+    /// 冲突与空间占用见 `TransformResult.naming_stats`。
+    pub hash_length: usize,
+    /// 类名复用范围（默认 Global）。`transform_html` 没有文件名，始终按 Global 处理
+    pub name_scope: NameScope,
     /// 输出模式（默认 Global）
     pub output_mode: OutputMode,
+    /// CssModules 模式下是否同时产出样式文件的类型声明（默认 false）
+    ///
+    /// 开启后 `TransformResult.dts` 为 `("./App.module.css.d.ts", 内容)`，
+    /// 为每个生成的类名声明只读键，使严格模式的 TS 项目通过类型检查。
+    pub css_module_dts: bool,
     /// CSS 变量模式（默认 Var）
     pub css_variables: CssVariableMode,
+    /// 按主题类别（字号、字体族、阴影、模糊、宽高比）覆盖 `css_variables`（默认都不覆盖）
+    ///
+    /// 如全局 `Inline`、只有 `shadow` 为 `Var` 时，阴影保留 `var(--shadow-*)` 并在 `:root` 中定义，
+    /// 其余主题值内联。颜色变量用 `color_mode: ColorMode::Var`。
+    pub css_variable_overrides: CssVariableOverrides,
     /// 未知类名处理模式（默认 Remove）
     pub unknown_classes: UnknownClassMode,
+    /// 命名与生成 CSS 之前是否规范化类字符串（默认 true）
+    ///
+    /// 合并连续空白并删除重复的类（保留第一次出现），`"p-4  text-center p-4"` 与 `"p-4 text-center"`
+    /// 共用同一个生成名与规则，`class_map` 的键也取规范化后的字符串。关闭时只去掉首尾空白。
+    pub normalize_classes: bool,
     /// 颜色输出模式（默认 Hex）
     pub color_mode: ColorMode,
     /// 是否使用 color-mix() 函数处理颜色透明度（默认 false）
     pub color_mix: bool,
+    /// 暗色主题变量（默认空）
+    ///
+    /// 输出引用主题变量（如 `color_mode: ColorMode::Var`）时，在 `:root` 之后追加暗色块，
+    /// 只包含输出中用到的变量。
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, ColorMode, DarkTheme, TransformOptions};
+    ///
+    /// let options = TransformOptions {
+    ///     color_mode: ColorMode::Var,
+    ///     dark_theme: DarkTheme {
+    ///         variables: [("--color-white".to_string(), "#09090b".to_string())].into_iter().collect(),
+    ///         selector: Some(".dark".to_string()),
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let result = transform_html(r#"<div class="bg-white"></div>"#, options).unwrap();
+    /// assert!(result.css.contains(".dark {\n  --color-white: #09090b;\n}"));
+    /// ```
+    pub dark_theme: DarkTheme,
+    /// 是否在主题变量与工具类规则之间输出 preflight 基础样式（默认 false）
+    ///
+    /// 与 Tailwind v4 的 preflight 一致，React Native 模式下不生效。
+    /// 多个文件的输出经 `merge_outputs` 合并后只保留一份。
+    pub preflight: bool,
+    /// 是否把生成的 CSS 按层叠角色拆分到 `TransformResult::css_outputs`（默认 false）
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, ColorMode, TransformOptions};
+    ///
+    /// let options = TransformOptions {
+    ///     color_mode: ColorMode::Var,
+    ///     preflight: true,
+    ///     split_css: true,
+    ///     ..Default::default()
+    /// };
+    /// let result = transform_html(r#"<div class="p-4 text-blue-500"></div>"#, options).unwrap();
+    /// let outputs = result.css_outputs.unwrap();
+    /// // 变量定义与引用它们的规则分开
+    /// assert!(outputs.variables.starts_with(":root {\n  --color-blue-500: "));
+    /// assert!(outputs.base.contains("box-sizing: border-box;"));
+    /// assert!(outputs.utilities.contains("color: var(--color-blue-500);"));
+    /// assert!(outputs.utilities.contains("padding: 1rem;"));
+    /// assert!(!outputs.utilities.contains(":root"));
+    /// assert_eq!(outputs.combined(), result.css);
+    /// ```
+    pub split_css: bool,
+    /// 是否在生成时把 `calc(var(--spacing) * n)` 解析为具体 rem 值（默认 false）
+    ///
+    /// 关闭时保留 `calc()` 写法，并在 CSS 顶部的 `:root` 中自动定义 `--spacing`。
+    pub resolve_spacing: bool,
+    /// 断点与容器查询的单位（rem / px）和写法（范围 / 传统），默认 `(width >= 48rem)`
+    pub breakpoints: BreakpointStyle,
+    /// `container` 的居中与水平内边距（默认都不设置）
+    ///
+    /// `container` 总是输出 `width: 100%` 和各断点的 `max-width`，断点单位跟随 `breakpoints`。
+    pub container: ContainerStyle,
+    /// 输出值中 px / rem 长度的统一换算（默认不换算）
+    ///
+    /// 作用于所有生成的声明（含任意值）和 `:root` 中的主题变量，断点单位由 `breakpoints` 单独控制。
+    /// React Native 模式下不生效。
+    pub unit_conversion: UnitConversion,
+    /// 单位换算使用的根字号（px，默认 16）
+    pub root_font_size: u32,
+    /// 需要支持的最低浏览器版本（默认不设置，不加前缀）
+    ///
+    /// 设置后为 `backdrop-filter`、`user-select`、`mask-*`、`position: sticky` 等声明补充
+    /// 目标浏览器需要的 `-webkit-` / `-moz-` 前缀；目标浏览器不支持 `oklch()` / `color-mix()` 时，
+    /// 在这类声明前插入预先计算的 hex 回退值，`:root` 中的颜色变量改用 `@supports` 覆盖。
+    pub targets: Targets,
     /// 是否生成元素树（默认 false）
     ///
     /// 开启后 `TransformResult.element_tree` 会包含结构化的元素树文本，
     /// 每个元素附带 `[ref=eN]` 引用标识，方便传给 AI 做二次处理。
     pub element_tree: bool,
+    /// 是否分析可由继承得到的类（默认 false）
+    ///
+    /// 子元素上只设置可继承属性（颜色、字体、行高等）的类，与祖先元素的类生成的值完全相同时，
+    /// 记入 `TransformResult.inherited_classes`。只报告，不改变输出；支持 HTML 与 JSX。
+    pub inheritance_report: bool,
+    /// 是否删除可由继承得到的类（默认 false），同时生成 `inheritance_report` 的报告
+    ///
+    /// 祖先带变体、使用组件或动态类名、有无法识别的类时不删除，保证渲染结果不变。
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, TransformOptions};
+    ///
+    /// let options = TransformOptions {
+    ///     safe_inheritance_optimization: true,
+    ///     ..Default::default()
+    /// };
+    /// let html = r#"<div class="text-gray-700"><p class="text-gray-700 p-4">Hi</p></div>"#;
+    /// let result = transform_html(html, options).unwrap();
+    /// assert_eq!(result.inherited_classes[0].class, "text-gray-700");
+    /// assert!(result.class_map.contains_key("p-4"));
+    /// ```
+    pub safe_inheritance_optimization: bool,
+    /// 是否检查与类用法相关的无障碍问题（默认 false），结果作为警告记入 `diagnostics`
+    ///
+    /// 检查可聚焦元素上的 `hidden`、文字与背景色对比度不足（按调色板计算，低于 WCAG AA）、
+    /// 小于 12px 的字号（如 `text-[8px]`）。支持 HTML、Markdown 与 JSX。
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, TransformOptions};
+    ///
+    /// let options = TransformOptions {
+    ///     a11y_audit: true,
+    ///     ..Default::default()
+    /// };
+    /// let html = r#"<div class="bg-white"><p class="text-gray-200">Hi</p></div>"#;
+    /// let result = transform_html(html, options).unwrap();
+    /// assert!(result.diagnostics[0].message.contains("对比度"));
+    /// ```
+    pub a11y_audit: bool,
+    /// `transform_html` 输出的文档外壳（默认与输入一致）
+    ///
+    /// 输入可以是没有 `<html>` / `<body>` 的片段，也可以有多个根元素或缺少闭合标签，
+    /// 类名按原样替换，其余字节不变。`Document` 为片段补上完整的文档外壳，
+    /// `Fragment` 只输出完整文档 `<body>` 的内容（元素树也只包含这部分）。
+    pub html_wrapper: HtmlWrapper,
+    /// 是否在生成的每个规则块前加注释（默认 false）
+    ///
+    /// 注释标明规则首次出现的文件、元素和原始类字符串，如 `/* App.tsx <div> "p-4 text-center" */`，
+    /// 便于迁移期间审阅生成的样式表。
+    pub css_comments: bool,
+    /// 相同规则体的去重方式（默认不去重）
+    ///
+    /// 许多类组合产出相同的规则时（如 `p-4` 与 `p-[1rem]`），
+    /// `GroupSelectors` 输出一条 `.c_a, .c_b { ... }`，`ShareClass` 让后者直接复用前者的类名。
+    /// 设置了 `on_rule_generated` 时不去重。
+    pub css_dedup: CssDedup,
+    /// 是否保留原始格式（默认 false）
+    ///
+    /// 开启后不再经过 SWC codegen 重新打印整个模块，
+    /// 只替换 className 字符串/表达式所在的字节区间，并以最小文本编辑插入 import，
+    /// 其余字节与输入完全一致，适合在已有代码库上做 codemod。
+    pub preserve_formatting: bool,
+    /// 输出格式选项（引号风格，默认与 SWC codegen 一致）
+    pub emit: EmitOptions,
+    /// 注入 import 的引号、路径后缀与插入位置（默认引号跟随 `emit`，插在文件顶部）
+    pub imports: ImportOptions,
+    /// 用户格式化钩子（默认 None）
+    ///
+    /// `transform_jsx` 返回前对输出代码调用一次，使结果与项目的 Prettier 等配置一致，
+    /// 避免后续再格式化一次产生额外 diff。
+    pub formatter: Option<Formatter>,
+    /// 类字符串收集钩子（默认 None）
+    ///
+    /// 每个含可识别类的类字符串在命名、生成 CSS 之前调用一次（相同字符串只调用一次），
+    /// 可以改写 `classes` 或指定 `name`，见 [`CollectedClass`]。
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, TransformOptions};
+    /// use std::sync::Arc;
+    ///
+    /// let options = TransformOptions {
+    ///     on_class_collected: Some(Arc::new(|collected| {
+    ///         collected.classes.retain(|class| class != "debug-outline");
+    ///         collected.name = Some(format!("ui-{}", collected.classes.len()));
+    ///     })),
+    ///     on_rule_generated: Some(Arc::new(|rule| {
+    ///         rule.selector = format!(":where({})", rule.selector);
+    ///     })),
+    ///     ..Default::default()
+    /// };
+    /// let result = transform_html(r#"<p class="p-4 m-2 debug-outline"></p>"#, options).unwrap();
+    /// assert_eq!(result.code, r#"<p class="ui-2"></p>"#);
+    /// assert!(result.css.contains(":where(.ui-2) {"));
+    /// ```
+    pub on_class_collected: Option<ClassHook>,
+    /// CSS 规则生成钩子（默认 None）
+    ///
+    /// 每条规则写出前调用，可以改写选择器与声明，清空声明即丢弃该规则。
+    /// React Native 模式不产出 CSS，不调用。
+    pub on_rule_generated: Option<RuleHook>,
+    /// 与类名访问器在同一遍中运行的 AST 扩展（默认空，见 [`AstExtension`]）
+    ///
+    /// 集成方的其他 codemod 可以借此共用一次解析与输出。需要重新打印整个模块，
+    /// `preserve_formatting`（含解析错误恢复时自动切换的情形）下不运行，并记录一条 Warning 诊断。
+    #[cfg(feature = "ecmascript")]
+    pub ast_extensions: Vec<Arc<dyn AstExtension>>,
+    /// 项目中已存在的类名（默认空）
+    ///
+    /// 生成名与其冲突时会加盐重新 hash，并在 `TransformResult.diagnostics` 中报告。
+    /// 可以用 `extract_css_class_names` 从已有 CSS 文件中扫描得到。
+    pub reserved_class_names: Vec<String>,
+    /// 按类手动覆盖生成名或 CSS（默认空）
+    ///
+    /// 转换器的结果不符合项目需要时的逃生口：键为完整类字符串时可固定生成名，
+    /// 键为单个工具类时可替换其声明，无需等待上游修复。固定的生成名不参与保留类名的冲突检查。
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, ClassOverride, TransformOptions};
+    ///
+    /// let mut options = TransformOptions::default();
+    /// options.class_overrides.insert(
+    ///     "p-4 shadow-card".into(),
+    ///     ClassOverride { name: Some("card".into()), css: None },
+    /// );
+    /// options.class_overrides.insert(
+    ///     "shadow-card".into(),
+    ///     ClassOverride {
+    ///         name: None,
+    ///         css: Some([("box-shadow".to_string(), "0 1px 3px #0002".to_string())].into()),
+    ///     },
+    /// );
+    /// let result = transform_html(r#"<div class="p-4 shadow-card"></div>"#, options).unwrap();
+    /// assert_eq!(result.code, r#"<div class="card"></div>"#);
+    /// assert!(result.css.contains("box-shadow: 0 1px 3px #0002;"));
+    /// ```
+    pub class_overrides: IndexMap<String, ClassOverride>,
+    /// 类名别名：别名 → 空格分隔的类（默认空）
+    ///
+    /// 源码中出现别名时先展开再转换，保留项目基于 `@apply` 的简写习惯；带变体的别名逐个套用变体，
+    /// 展开中可以引用其他别名。类名映射的键仍是源码中的原始字符串。
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, TransformOptions};
+    ///
+    /// let mut options = TransformOptions::default();
+    /// options.class_aliases.insert("btn".into(), "px-4 py-2 rounded-md font-semibold".into());
+    /// let result = transform_html(r#"<button class="btn md:btn"></button>"#, options).unwrap();
+    /// assert!(result.css.contains("font-weight: 600;"));
+    /// assert!(result.css.contains("@media (width >= 48rem)"));
+    /// assert!(result.class_map.contains_key("btn md:btn"));
+    /// ```
+    pub class_aliases: IndexMap<String, String>,
+    /// 变体别名：别名 → 展开的变体（默认空）
+    ///
+    /// 对应设计系统用插件定义的组合变体，如 `hocus` → `["hover", "focus"]`：
+    /// `hocus:underline` 同时产出 `:hover` 与 `:focus` 两条规则，可与其他变体叠加（`md:hocus:…`）。
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, TransformOptions};
+    ///
+    /// let mut options = TransformOptions::default();
+    /// options.variant_aliases.insert("hocus".into(), vec!["hover".into(), "focus".into()]);
+    /// let result = transform_html(r#"<a class="hocus:underline"></a>"#, options).unwrap();
+    /// assert!(result.css.contains(":hover {"));
+    /// assert!(result.css.contains(":focus {"));
+    /// ```
+    pub variant_aliases: IndexMap<String, Vec<String>>,
+    /// `@custom-variant` 定义的变体：变体名 → 定义（默认空）
+    ///
+    /// 与内置变体同名时覆盖内置行为，如基于类名的暗色模式；
+    /// 可以用 `load_custom_variants` 从项目的 Tailwind CSS 中读取。
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, CustomVariant, TransformOptions};
+    ///
+    /// let mut options = TransformOptions::default();
+    /// options.custom_variants.insert(
+    ///     "dark".into(),
+    ///     CustomVariant::parse("&:where(.dark, .dark *)").unwrap(),
+    /// );
+    /// let result = transform_html(r#"<p class="dark:underline"></p>"#, options).unwrap();
+    /// assert!(result.css.contains(":where(.dark, .dark *) {"));
+    /// assert!(!result.css.contains("prefers-color-scheme"));
+    /// ```
+    pub custom_variants: IndexMap<String, CustomVariant>,
+    /// `@utility` 定义的工具类：名称模式 → 定义（默认空）
+    ///
+    /// 名称以 `-*` 结尾的是函数式工具类，声明中的 `--value(…)` 按类名中的值展开；
+    /// 与内置工具类同名时以自定义定义为准。可以用 `load_custom_utilities` 从项目的 Tailwind CSS 中读取。
+    ///
+    /// ```
+    /// use headwind_transform::{transform_html, CustomUtility, TransformOptions};
+    ///
+    /// let mut options = TransformOptions::default();
+    /// options.custom_utilities.insert(
+    ///     "tab-*".into(),
+    ///     CustomUtility::parse("tab-size: --value(integer)").unwrap(),
+    /// );
+    /// let result = transform_html(r#"<pre class="tab-4"></pre>"#, options).unwrap();
+    /// assert!(result.css.contains("tab-size: 4;"));
+    /// ```
+    pub custom_utilities: IndexMap<String, CustomUtility>,
+    /// 参数中的字符串按类名处理的函数名（默认空）
+    ///
+    /// 如 `["cva", "cn"]`：`cva("px-4", { variants: { size: { sm: "text-sm" } } })` 中
+    /// 含可识别类的字符串（包括数组元素、对象的值和键）按 className 同样的方式替换，
+    /// cva 的 `defaultVariants` 与 `compoundVariants` 中的变体取值不受影响。
+    /// 不在这些调用中的字符串可以用 `/* tw */` 注释标记，如 `const base = /* tw */ "p-4";`。
+    pub class_functions: Vec<String>,
+    /// `class` 与 `className` 并存时的合并策略（默认不合并）
+    pub class_attr_merge: ClassAttrMerge,
+    /// 是否处理组件上的类名属性（默认 true）
+    ///
+    /// Solid / Qwik 的组件常把 `class` 原样转发给内部元素，默认与 DOM 元素一样转换；
+    /// 组件自行解释 `class`（如按名称查找变体）时关闭，只处理小写标签的 DOM 元素。
+    pub component_class_attrs: bool,
+    /// 输出中类名属性的名称（默认保持原样）
+    ///
+    /// 统一为 `className` 或 `class` 时，处理过的元素上的类名属性（包括 `tw` 属性并入后的）
+    /// 一并改名；同一元素上两者并存且未合并（`Separate` 或含动态表达式）时保持原样并报告诊断。
+    pub class_attr_name: ClassAttrName,
+    /// typography 插件的 `prose` 系列类的处理方式（默认保留并报告）
+    ///
+    /// 这些类的样式来自 `@tailwindcss/typography` 生成的大段样式表，不做转换；
+    /// 保留时不参与命名，和 `group` / `peer` 一样原样留在类名中。
+    pub prose_classes: ProseClassMode,
+    /// spread 属性合并辅助函数名（默认 None，仅报告诊断）
+    ///
+    /// 例如设为 `"cn"` 时，`<div {...props} className="p-4">` 输出
+    /// `className={cn("c_xxx", props.className)}`，保留运行时传入的类名。
+    /// 辅助函数需在作用域内可用。
+    pub spread_merge_helper: Option<String>,
+    /// 运行时类名合并辅助函数（默认 None，跳过静态 + 动态混合的类名）
+    ///
+    /// 开启后 `` className={`p-4 ${cls}`} `` 输出 `className={mergeClasses("c_xxx", cls)}`，
+    /// 并注入 `import { mergeClasses } from "<import_path>"`。
+    /// 辅助函数模块源码由 `RuntimeHelper::source()` 生成。
+    pub runtime_helper: Option<RuntimeHelper>,
+    /// 类级转换缓存（默认 None）
+    ///
+    /// 多次转换共用同一个缓存时，重复出现的类只解析、转换一次；`ConfigSession` 会自动为所有文件设置。
+    /// 缓存按类名与颜色、变量等选项分别保存，不同配置的文件可以共用。内存受限的环境（如 WASM）保持 None。
+    pub conversion_cache: Option<Arc<ConversionCache>>,
+    /// 回退索引（默认 None）
+    ///
+    /// 基于规则的转换器无法识别的类再到索引中按类名查找，如用 `load_index` 加载的官方映射数据集。
+    /// 规则转换优先；配置文件中通过 `fallbackIndex` 指定数据集路径。
+    pub fallback_index: Option<Arc<TailwindIndex>>,
+    /// 目标 Tailwind 版本（默认 V4）
+    ///
+    /// V3 时 `shadow-sm`、`rounded-sm`、`blur-sm` 等按 v3 的尺寸表输出具体值；
+    /// 版本化的索引产物（`load_versioned_index`）自带版本时，配置未指定则沿用产物的版本。
+    pub target_tailwind_version: TailwindVersion,
+    /// 容错解析（默认 false，遇到任何语法错误都返回 `Err`）
+    ///
+    /// 开启后 `transform_jsx` 使用 SWC 的错误恢复：可恢复的错误记为 Warning 诊断，
+    /// 并改用保留格式模式只替换类名区间，避免把恢复出的节点重新打印成代码；
+    /// 无法恢复时原样返回源码并附带 Error 诊断。适合整个项目迁移时不因单个文件中断。
+    pub recover_parse_errors: bool,
+    /// 解析器语法特性（装饰器、import attributes、JSX pragma），默认全部关闭
+    pub syntax: SyntaxOptions,
 }
 
 impl Default for TransformOptions {
     fn default() -> Self {
         Self {
             naming_mode: NamingMode::Hash,
+            hash_version: HashVersion::default(),
+            hash_salt: String::new(),
+            hash_length: DEFAULT_HASH_LENGTH,
+            name_scope: NameScope::default(),
             output_mode: OutputMode::default(),
+            css_module_dts: false,
             css_variables: CssVariableMode::Var,
+            css_variable_overrides: CssVariableOverrides::default(),
             unknown_classes: UnknownClassMode::Remove,
+            normalize_classes: true,
             color_mode: ColorMode::default(),
             color_mix: false,
+            dark_theme: DarkTheme::default(),
+            preflight: false,
+            split_css: false,
+            resolve_spacing: false,
+            breakpoints: BreakpointStyle::default(),
+            container: ContainerStyle::default(),
+            unit_conversion: UnitConversion::None,
+            root_font_size: 16,
+            targets: Targets::default(),
             element_tree: false,
+            inheritance_report: false,
+            safe_inheritance_optimization: false,
+            a11y_audit: false,
+            html_wrapper: HtmlWrapper::Preserve,
+            css_comments: false,
+            css_dedup: CssDedup::None,
+            preserve_formatting: false,
+            emit: EmitOptions::default(),
+            imports: ImportOptions::default(),
+            formatter: None,
+            on_class_collected: None,
+            on_rule_generated: None,
+            #[cfg(feature = "ecmascript")]
+            ast_extensions: Vec::new(),
+            reserved_class_names: Vec::new(),
+            class_overrides: IndexMap::new(),
+            class_aliases: IndexMap::new(),
+            variant_aliases: IndexMap::new(),
+            custom_variants: IndexMap::new(),
+            custom_utilities: IndexMap::new(),
+            class_functions: Vec::new(),
+            class_attr_merge: ClassAttrMerge::default(),
+            component_class_attrs: true,
+            class_attr_name: ClassAttrName::default(),
+            prose_classes: ProseClassMode::default(),
+            spread_merge_helper: None,
+            runtime_helper: None,
+            conversion_cache: None,
+            fallback_index: None,
+            target_tailwind_version: TailwindVersion::default(),
+            recover_parse_errors: false,
+            syntax: SyntaxOptions::default(),
         }
     }
 }
@@ -134,6 +747,8 @@ pub struct TransformResult {
     pub code: String,
     /// 生成的 CSS
     pub css: String,
+    /// 按层叠角色拆分的 CSS，仅在开启 `split_css` 时生成，三部分拼接后与 `css` 相同
+    pub css_outputs: Option<CssOutputs>,
     /// 类名映射（原始类字符串 -> 生成的类名）
     pub class_map: IndexMap<String, String>,
     /// 元素树文本（仅当 `TransformOptions.element_tree == true` 时生成）
@@ -145,6 +760,150 @@ pub struct TransformResult {
     ///   - p: xxxx [ref=e3]
     /// ```
     pub element_tree: Option<String>,
+    /// 组件名 → 组件内元素的原始类字符串（按出现顺序去重），仅在 JSX 开启 `element_tree` 时生成
+    pub components: IndexMap<String, Vec<String>>,
+    /// 转换过程中的诊断信息（如类名冲突）
+    pub diagnostics: Vec<Diagnostic>,
+    /// 可由继承得到的类（仅当开启 `inheritance_report` 或 `safe_inheritance_optimization` 时分析）
+    pub inherited_classes: Vec<InheritedClass>,
+    /// 无法转换的类 → 修复建议（按首次出现顺序，没有建议时为 None）
+    pub unknown_classes: IndexMap<String, Option<String>>,
+    /// 原始类字符串 → 出现次数（同一组合用在多个元素上时各计一次）
+    pub class_usage: IndexMap<String, usize>,
+    /// 各阶段耗时，仅在开启 `tracing` feature 时为 Some
+    pub timings: Option<Timings>,
+    /// ShadowDom 模式下的样式模块源码，应写入注入的 import 路径
+    pub style_module: Option<String>,
+    /// 生成名的空间占用与冲突统计
+    pub naming_stats: NamingStats,
+    /// CssModules 模式下的类型声明（路径，内容），仅在开启 `css_module_dts` 时生成
+    ///
+    /// 路径与 import 路径一样相对源文件所在目录，如 `./App.module.css.d.ts`
+    pub dts: Option<(String, String)>,
+    /// 生成的 CSS 应写入的样式文件路径（相对源文件所在目录，占位符已替换），
+    /// 仅在输出模式带样式文件且有生成的类时为 Some
+    pub style_path: Option<String>,
+    /// 样式文件路径中 `[contenthash]` 替换成的内容哈希，路径不含该占位符时为 None
+    pub css_hash: Option<String>,
+}
+
+impl TransformResult {
+    /// 由收集器的产出组装结果
+    fn from_collector(
+        code: String,
+        collector: ClassCollector,
+        element_tree: Option<String>,
+        split_css: bool,
+    ) -> Self {
+        let css = collector.combined_css();
+        let css_outputs = split_css.then(|| collector.css_outputs());
+        let unknown_classes = collector.unconverted_classes().clone();
+        let class_usage = collector.class_usage().clone();
+        let timings = profile::recorded(collector.timings());
+        let naming_stats = collector.naming_stats();
+        let (class_map, diagnostics) = collector.into_parts();
+        Self {
+            code,
+            css,
+            css_outputs,
+            class_map,
+            element_tree,
+            components: IndexMap::new(),
+            diagnostics,
+            inherited_classes: Vec::new(),
+            unknown_classes,
+            class_usage,
+            timings,
+            style_module: None,
+            naming_stats,
+            dts: None,
+            style_path: None,
+            css_hash: None,
+        }
+    }
+
+    /// 无法转换时原样返回源码
+    #[cfg(feature = "ecmascript")]
+    fn unchanged(source: &str, diagnostics: Vec<Diagnostic>) -> Self {
+        Self {
+            code: source.to_string(),
+            css: String::new(),
+            css_outputs: None,
+            class_map: IndexMap::new(),
+            element_tree: None,
+            components: IndexMap::new(),
+            diagnostics,
+            inherited_classes: Vec::new(),
+            unknown_classes: IndexMap::new(),
+            class_usage: IndexMap::new(),
+            timings: None,
+            style_module: None,
+            naming_stats: NamingStats::default(),
+            dts: None,
+            style_path: None,
+            css_hash: None,
+        }
+    }
+
+    /// ShadowDom 模式下由生成的 CSS 产出样式模块
+    fn with_style_module(mut self, output_mode: &OutputMode) -> Self {
+        if let OutputMode::ShadowDom { format, .. } = output_mode {
+            if !self.css.is_empty() {
+                self.style_module = Some(style_module_code(&self.css, *format));
+            }
+        }
+        self
+    }
+
+    /// 记录样式文件路径与内容哈希（没有生成的类时不记录）
+    #[cfg(feature = "ecmascript")]
+    fn with_style_path(mut self, path: Option<String>, hash: Option<String>) -> Self {
+        if !self.class_map.is_empty() {
+            self.style_path = path;
+            self.css_hash = hash;
+        }
+        self
+    }
+
+    /// CssModules 模式下开启 `css_module_dts` 时，为样式文件产出类型声明
+    #[cfg(feature = "ecmascript")]
+    fn with_css_module_dts(mut self, options: &TransformOptions) -> Self {
+        if !options.css_module_dts
+            || self.class_map.is_empty()
+            || !matches!(options.output_mode, OutputMode::CssModules { .. })
+        {
+            return self;
+        }
+        if let Some(path) = &self.style_path {
+            let names = self
+                .class_map
+                .values()
+                .filter_map(|name| name.split_whitespace().next());
+            self.dts = Some((format!("{}.d.ts", path), css_module_dts(names)));
+        }
+        self
+    }
+
+    #[cfg(feature = "ecmascript")]
+    fn with_components(mut self, components: IndexMap<String, Vec<String>>) -> Self {
+        self.components = components;
+        self
+    }
+
+    fn with_inherited_classes(mut self, classes: Vec<InheritedClass>) -> Self {
+        self.inherited_classes = classes;
+        self
+    }
+
+    /// 合并解析、遍历、输出阶段的耗时；遍历耗时扣除其中的类名转换
+    fn with_timings(mut self, phases: Timings) -> Self {
+        if let Some(timings) = &mut self.timings {
+            timings.parse = phases.parse;
+            timings.visit = phases.visit.saturating_sub(timings.convert);
+            timings.emit = phases.emit;
+        }
+        self
+    }
 }
 
 /// 转换 JSX/TSX 源码
@@ -155,12 +914,12 @@ pub struct TransformResult {
 /// # 参数
 ///
 /// - `source`: JSX/TSX 源码字符串
-/// - `filename`: 文件名（用于判断语法类型：.tsx/.jsx/.ts/.js）
+/// - `filename`: 文件名（用于判断语法类型：.tsx/.ts/.mts/.cts 按 TypeScript，其余按 JSX）
 /// - `options`: 转换选项
 ///
 /// # 示例
 ///
-/// ```no_run
+/// ```
 /// use headwind_transform::{transform_jsx, TransformOptions};
 ///
 /// let source = r#"
@@ -170,37 +929,42 @@ pub struct TransformResult {
 /// "#;
 ///
 /// let result = transform_jsx(source, "App.tsx", TransformOptions::default()).unwrap();
-/// println!("Code:\n{}", result.code);
-/// println!("CSS:\n{}", result.css);
-/// println!("Mappings: {:?}", result.class_map);
+/// let name = &result.class_map["p-4 text-center hover:text-left"];
+/// assert!(result.code.contains(&format!("className=\"{}\"", name)));
+/// assert!(!result.code.contains("text-center"));
+/// assert!(result.css.contains(&format!(".{} {{", name)));
+/// assert!(result.css.contains("text-align: center;"));
+/// assert!(result.css.contains(&format!(".{}:hover {{", name)));
 /// ```
+#[cfg(feature = "ecmascript")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(source, options)))]
 pub fn transform_jsx(
     source: &str,
     filename: &str,
     options: TransformOptions,
 ) -> Result<TransformResult, String> {
+    // 继承分析单独解析一次原始源码，删除可继承类后再按原流程转换
+    if options.inheritance_report || options.safe_inheritance_optimization {
+        let (source, inherited) = analyze_jsx_inheritance(source, filename, &options);
+        let options = TransformOptions {
+            inheritance_report: false,
+            safe_inheritance_optimization: false,
+            ..options
+        };
+        return Ok(transform_jsx(&source, filename, options)?.with_inherited_classes(inherited));
+    }
+
     // 根据文件名选择语法
-    let syntax = if filename.ends_with(".tsx") {
-        Syntax::Typescript(TsSyntax {
-            tsx: true,
-            ..Default::default()
-        })
-    } else if filename.ends_with(".ts") {
-        Syntax::Typescript(TsSyntax {
-            tsx: false,
-            ..Default::default()
-        })
+    let syntax = syntax::syntax_for(filename, source, &options.syntax);
+
+    // 用占位符注释保留空行位置，防止 SWC parse→emit 吞掉空行。
+    // span 编辑模式直接在原文上拼接，不需要占位符。
+    let preserved_source = if options.preserve_formatting {
+        source.to_string()
     } else {
-        // .jsx / .js 默认支持 JSX
-        Syntax::Es(EsSyntax {
-            jsx: true,
-            ..Default::default()
-        })
+        preserve_empty_lines(source)
     };
 
-    // 用占位符注释保留空行位置，防止 SWC parse→emit 吞掉空行
-    let preserved_source = preserve_empty_lines(source);
-
     let cm: Lrc<SourceMap> = Default::default();
     let fm = cm.new_source_file(
         FileName::Custom(filename.to_string()).into(),
@@ -208,89 +972,328 @@ pub fn transform_jsx(
     );
 
     // 解析（保留注释）
+    let mut timings = Timings::default();
     let comments = SingleThreadedComments::default();
     let mut errors = vec![];
-    let mut module = parse_file_as_module(&fm, syntax, EsVersion::latest(), Some(&comments), &mut errors)
-        .map_err(|e| format!("解析错误: {:?}", e))?;
-
-    if !errors.is_empty() {
-        return Err(format!("解析警告: {:?}", errors));
-    }
+    let timer = PhaseTimer::start(Phase::Parse);
+    let parsed = parse_file_as_module(&fm, syntax, EsVersion::latest(), Some(&comments), &mut errors);
+    timer.stop(&mut timings);
+    let mut module = match parsed {
+        Ok(module) if errors.is_empty() => module,
+        Ok(_) if !options.recover_parse_errors => {
+            return Err(format!("解析警告: {:?}", errors));
+        }
+        // 恢复出的 AST 可能含无效节点，不能重新打印；改为只替换类名区间
+        Ok(_) if !options.preserve_formatting => {
+            return transform_jsx(
+                source,
+                filename,
+                TransformOptions {
+                    preserve_formatting: true,
+                    ..options
+                },
+            );
+        }
+        Ok(module) => module,
+        Err(error) if options.recover_parse_errors => {
+            let diagnostics = errors
+                .iter()
+                .map(|e| parse_diagnostic(&cm, e, DiagnosticLevel::Warning))
+                .chain(std::iter::once(parse_diagnostic(&cm, &error, DiagnosticLevel::Error)))
+                .collect();
+            return Ok(TransformResult::unchanged(source, diagnostics));
+        }
+        Err(error) => return Err(format!("解析错误: {:?}", error)),
+    };
 
     // 生成元素树（在 AST 变更前遍历）
-    let tree_text = if options.element_tree {
+    let (tree_text, component_classes) = if options.element_tree {
         let components = element_tree::build_jsx_element_tree(&module);
-        if components.is_empty() {
+        let text = if components.is_empty() {
             None
         } else {
             Some(element_tree::format_component_trees(&components))
-        }
+        };
+        (text, element_tree::component_classes(&components))
     } else {
-        None
+        (None, IndexMap::new())
     };
 
     // 遍历并替换
-    let mut collector = ClassCollector::new(options.naming_mode, options.css_variables, options.unknown_classes, options.color_mode, options.color_mix);
+    let mut collector = create_collector(&options, Some(filename));
+    if options.a11y_audit {
+        let roots: Vec<_> = element_tree::build_jsx_element_tree(&module)
+            .into_iter()
+            .flat_map(|component| component.roots)
+            .collect();
+        audit_accessibility(&roots, &mut collector);
+    }
+    for error in &errors {
+        collector.push_diagnostic(parse_diagnostic(&cm, error, DiagnosticLevel::Warning));
+    }
+    // AST 扩展：保留格式模式只按区间编辑源码，扩展对 AST 的改动无法输出
+    let run_extensions = !options.ast_extensions.is_empty() && !options.preserve_formatting;
+    if !options.ast_extensions.is_empty() && options.preserve_formatting {
+        collector.push_diagnostic(Diagnostic::warning("保留格式模式下不运行 AST 扩展"));
+    }
+    let run_extension_stage =
+        |before: bool, module: &mut Module, class_map: &IndexMap<String, String>| {
+            if !run_extensions {
+                return;
+            }
+            let context = AstContext {
+                filename,
+                source_map: &cm,
+                comments: &comments,
+                class_map,
+            };
+            // 与 codegen 共用 SWC 全局状态，扩展中可以创建 Mark
+            EMIT_GLOBALS.with(|globals| {
+                GLOBALS.set(globals, || {
+                    extension::run_extensions(&options.ast_extensions, before, module, &context)
+                })
+            });
+        };
+    run_extension_stage(true, &mut module, collector.class_map());
+    // CSS Modules：文件中已有同路径 import 时复用其绑定名，不再重复注入
+    // 路径含 `[contenthash]` 时哈希取决于本次生成的 CSS，不会与已有 import 相同
+    let existing_binding = match &options.output_mode {
+        OutputMode::CssModules { .. } | OutputMode::ShadowDom { .. } => {
+            style_file_path(&options, filename)
+                .filter(|path| !asset::has_content_hash(path))
+                .and_then(|path| {
+                    let path = style_import_path(&options, filename, &path);
+                    jsx_visitor::find_css_module_binding(&module, &path)
+                })
+        }
+        _ => None,
+    };
+    // 注入的样式 import 的绑定名：已被其他 import 或顶层声明占用时改用 `styles$1` 等未占用的名称
+    let injected_binding = match &options.output_mode {
+        _ if existing_binding.is_some() => None,
+        OutputMode::CssModules { binding_name, .. } | OutputMode::ShadowDom { binding_name, .. } => {
+            Some(jsx_visitor::fresh_binding(&module, binding_name))
+        }
+        _ => None,
+    };
+    // ShadowDom 模式追加到文件末尾的 adopt 代码
+    let adopt_code = match &options.output_mode {
+        OutputMode::ShadowDom {
+            adopt: Some(adopt), ..
+        } => existing_binding
+            .as_deref()
+            .or(injected_binding.as_deref())
+            .map(|sheet| adopt.replace("{sheet}", sheet)),
+        _ => None,
+    };
     let css_modules_config = match &options.output_mode {
-        OutputMode::CssModules {
-            binding_name,
-            access,
-            ..
-        } => Some((binding_name.clone(), *access)),
-        OutputMode::Global { .. } => None,
+        OutputMode::CssModules { access, .. } => existing_binding
+            .clone()
+            .or_else(|| injected_binding.clone())
+            .map(|binding| (binding, *access)),
+        // 追加的 `const styles = StyleSheet.create(...)` 同样避开已有的 import 与顶层声明
+        OutputMode::ReactNative { binding_name } => Some((
+            jsx_visitor::fresh_binding(&module, binding_name),
+            CssModulesAccess::Dot,
+        )),
+        _ => None,
     };
-    {
+    let timer = PhaseTimer::start(Phase::Visit);
+    let (edits, helper_used) = {
         let mut visitor = JsxClassVisitor::new(
             &mut collector,
             css_modules_config
                 .as_ref()
                 .map(|(b, a)| (b.as_str(), *a)),
         );
-        module.visit_mut_with(&mut visitor);
-    }
-
-    // 注入 import 语句（仅在有类名映射时）
-    if !collector.class_map().is_empty() {
+        visitor = visitor
+            .with_emit_options(options.emit)
+            .with_class_attr_merge(options.class_attr_merge)
+            .with_component_class_attrs(options.component_class_attrs)
+            .with_class_attr_name(options.class_attr_name);
+        if let Some(helper) = &options.spread_merge_helper {
+            visitor = visitor.with_spread_merge_helper(helper);
+        }
+        if let Some(helper) = &options.runtime_helper {
+            visitor = visitor.with_runtime_helper(helper.clone());
+        }
+        if let Some(tags) = jsx_visitor::tw_macro_tags(&module) {
+            visitor = visitor.with_tw_macro(tags);
+        }
+        visitor = visitor
+            .with_class_functions(options.class_functions.clone())
+            .with_class_markers(class_marker_positions(&comments));
         match &options.output_mode {
-            OutputMode::Global {
-                import_path: Some(path),
-            } => {
-                let import = create_side_effect_import(path);
-                module.body.insert(0, import);
+            OutputMode::DataAttribute { attr, .. } => {
+                visitor = visitor.with_data_attribute(attr);
             }
-            OutputMode::CssModules {
-                binding_name,
-                import_path,
-                ..
-            } => {
-                let path = import_path
-                    .clone()
-                    .unwrap_or_else(|| derive_css_module_path(filename));
-                let import = create_css_module_import(binding_name, &path);
-                module.body.insert(0, import);
+            OutputMode::ReactNative { .. } => {
+                visitor = visitor.with_style_attr();
             }
             _ => {}
         }
+        if options.preserve_formatting {
+            visitor = visitor.with_span_edits(fm.start_pos);
+        }
+        visitor = visitor.with_ignore_directives(
+            ignore_directives(&fm.src, &comments, fm.start_pos),
+            fm.start_pos,
+        );
+        module.visit_mut_with(&mut visitor);
+        (visitor.take_edits(), visitor.helper_used())
+    };
+    let removed_imports = jsx_visitor::remove_unused_twin_import(&mut module);
+    run_extension_stage(false, &mut module, collector.class_map());
+    timer.stop(&mut timings);
+    // 运行时辅助函数 import（文件中已绑定同名标识符时不重复注入）
+    let helper_import = options
+        .runtime_helper
+        .as_ref()
+        .filter(|h| helper_used && !jsx_visitor::has_import_binding(&module, &h.name));
+    // React Native 模式：追加到文件末尾的 StyleSheet.create 代码
+    let native_code = match (&css_modules_config, collector.native_styles()) {
+        (Some((binding, _)), Some(styles)) if !styles.is_empty() => Some(
+            react_native::style_sheet_code(binding, styles, options.emit.quote_style),
+        ),
+        _ => None,
+    };
+    let import_style_sheet =
+        native_code.is_some() && !jsx_visitor::has_import_binding(&module, "StyleSheet");
+    let import_quote = options.imports.quote_style.unwrap_or(options.emit.quote_style);
+    let import_position = options.imports.position;
+    // 样式文件路径中的 `[contenthash]` 按本次生成的 CSS 替换
+    let (style_path, css_hash) = match style_file_path(&options, filename) {
+        Some(path) if asset::has_content_hash(&path) => {
+            let (path, hash) = asset::apply_content_hash(&path, &collector.combined_css());
+            (Some(path), hash)
+        }
+        path => (path, None),
+    };
+    let import_path = style_path
+        .as_deref()
+        .map(|path| style_import_path(&options, filename, path));
+
+    if options.preserve_formatting {
+        let mut edits = edits;
+        // 删除的 import 连同其后的换行一起移除
+        for span in removed_imports {
+            let start = (span.lo - fm.start_pos).0 as usize;
+            let end = (span.hi - fm.start_pos).0 as usize;
+            let rest = &source[end..];
+            let end = end + rest.len() - rest.strip_prefix('\n').or_else(|| rest.strip_prefix("\r\n")).unwrap_or(rest).len();
+            edits.push(span_edit::SpanEdit {
+                start,
+                end,
+                text: String::new(),
+            });
+        }
+        let import_offset = prologue::insertion_offset(
+            &module,
+            &comments,
+            &fm.src,
+            fm.start_pos,
+            import_position,
+        );
+        if !collector.class_map().is_empty() && existing_binding.is_none() {
+            if let Some(path) = &import_path {
+                let import = import_statement_code(injected_binding.as_deref(), path, import_quote);
+                edits.push(span_edit::SpanEdit::insert(import_offset, import));
+            }
+        }
+        if let Some(helper) = helper_import {
+            edits.push(span_edit::SpanEdit::insert(
+                import_offset,
+                format!(
+                    "import {{ {} }} from {};\n",
+                    helper.name,
+                    import_quote.quote(&helper.import_path)
+                ),
+            ));
+        }
+        if let Some(adopt) = adopt_code.as_ref().filter(|_| !collector.class_map().is_empty()) {
+            edits.push(span_edit::SpanEdit::insert(source.len(), format!("\n{}\n", adopt)));
+        }
+        if import_style_sheet {
+            edits.push(span_edit::SpanEdit::insert(
+                import_offset,
+                format!(
+                    "import {{ StyleSheet }} from {};\n",
+                    import_quote.quote("react-native")
+                ),
+            ));
+        }
+        if let Some(native) = &native_code {
+            edits.push(span_edit::SpanEdit::insert(source.len(), format!("\n{}", native)));
+        }
+        let timer = PhaseTimer::start(Phase::Emit);
+        let code = span_edit::apply_edits(source, &edits);
+        let code = apply_formatter(&options, code, filename)?;
+        timer.stop(&mut timings);
+        return Ok(TransformResult::from_collector(code, collector, tree_text, options.split_css)
+            .with_components(component_classes)
+            .with_style_module(&options.output_mode)
+            .with_style_path(style_path, css_hash)
+            .with_css_module_dts(&options)
+            .with_timings(timings));
+    }
+
+    // 注入 import 语句（样式 import 仅在有类名映射且尚无同路径 import 时）
+    let mut imports = Vec::new();
+    if !collector.class_map().is_empty() && existing_binding.is_none() {
+        if let Some(path) = &import_path {
+            let import = match &injected_binding {
+                Some(binding) => create_css_module_import(binding, path, import_quote),
+                None => create_side_effect_import(path, import_quote),
+            };
+            imports.push(import);
+        }
     }
+    if import_style_sheet {
+        imports.push(create_named_import("StyleSheet", "react-native", import_quote));
+    }
+    if let Some(helper) = helper_import {
+        imports.push(create_named_import(&helper.name, &helper.import_path, import_quote));
+    }
+    prologue::insert_imports(
+        &mut module,
+        &comments,
+        &fm.src,
+        fm.start_pos,
+        imports,
+        import_position,
+    );
 
     // 输出代码（携带注释）
-    let code = GLOBALS.set(&Globals::new(), || emit_module(&cm, &module, Some(&comments)))?;
+    let timer = PhaseTimer::start(Phase::Emit);
+    let code = EMIT_GLOBALS
+        .with(|globals| GLOBALS.set(globals, || emit_module(&cm, &module, Some(&comments))))?;
 
     // 还原空行占位符
     let code = restore_empty_lines(&code);
-
-    Ok(TransformResult {
-        code,
-        css: collector.combined_css(),
-        class_map: collector.into_class_map(),
-        element_tree: tree_text,
-    })
+    let code = match adopt_code.filter(|_| !collector.class_map().is_empty()) {
+        Some(adopt) => format!("{}\n\n{}\n", code.trim_end(), adopt),
+        None => code,
+    };
+    let code = match native_code {
+        Some(native) => format!("{}\n\n{}", code.trim_end(), native),
+        None => code,
+    };
+    let code = apply_formatter(&options, code, filename)?;
+    timer.stop(&mut timings);
+
+    Ok(TransformResult::from_collector(code, collector, tree_text, options.split_css)
+        .with_components(component_classes)
+        .with_style_module(&options.output_mode)
+        .with_style_path(style_path, css_hash)
+        .with_css_module_dts(&options)
+        .with_timings(timings))
 }
 
 /// 转换 HTML 源码
 ///
 /// 扫描 HTML 中的 `class="..."` 属性，
 /// 将 Tailwind 类替换为生成的类名，并产出对应的 CSS。
+/// 输入可以是完整文档或片段，输出的外壳由 `TransformOptions::html_wrapper` 控制。
 ///
 /// # 参数
 ///
@@ -299,7 +1302,7 @@ pub fn transform_jsx(
 ///
 /// # 示例
 ///
-/// ```no_run
+/// ```
 /// use headwind_transform::{transform_html, TransformOptions};
 ///
 /// let html = r#"
@@ -309,10 +1312,19 @@ pub fn transform_jsx(
 /// "#;
 ///
 /// let result = transform_html(html, TransformOptions::default()).unwrap();
-/// println!("HTML:\n{}", result.code);
-/// println!("CSS:\n{}", result.css);
+/// assert_eq!(result.class_map.len(), 2);
+/// let span = &result.class_map["text-red-500 hover:text-blue-500"];
+/// assert!(result.code.contains(&format!("<span class=\"{}\">Hello</span>", span)));
+/// assert!(result.css.contains(&format!(".{}:hover {{", span)));
 /// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn transform_html(source: &str, options: TransformOptions) -> Result<TransformResult, String> {
+    let is_document = html::is_html_document(source);
+    let source = match options.html_wrapper {
+        HtmlWrapper::Fragment if is_document => html::body_content(source).unwrap_or(source),
+        _ => source,
+    };
+
     // 生成元素树（在转换前）
     let tree_text = if options.element_tree {
         let nodes = element_tree::build_html_element_tree(source);
@@ -325,75 +1337,448 @@ pub fn transform_html(source: &str, options: TransformOptions) -> Result<Transfo
         None
     };
 
-    let mut collector = ClassCollector::new(options.naming_mode, options.css_variables, options.unknown_classes, options.color_mode, options.color_mix);
-    let code = html::transform_html_source(source, &mut collector);
+    let mut timings = Timings::default();
+    let mut collector = create_collector(&options, None);
+    let (source, inherited) =
+        if options.inheritance_report || options.safe_inheritance_optimization {
+            let nodes = element_tree::build_html_element_tree(source);
+            analyze_inheritance(source, &nodes, &options, &collector)
+        } else {
+            (Cow::Borrowed(source), Vec::new())
+        };
+    if options.a11y_audit {
+        audit_accessibility(&element_tree::build_html_element_tree(&source), &mut collector);
+    }
+    let timer = PhaseTimer::start(Phase::Visit);
+    let code = match &options.output_mode {
+        OutputMode::DataAttribute { attr, .. } => {
+            html::transform_html_source_to_data_attr(&source, &mut collector, attr)
+        }
+        _ => html::transform_html_source(&source, &mut collector),
+    };
+    let code = match options.html_wrapper {
+        HtmlWrapper::Document if !is_document => html::wrap_document(&code),
+        _ => code,
+    };
+    timer.stop(&mut timings);
 
-    Ok(TransformResult {
-        code,
-        css: collector.combined_css(),
-        class_map: collector.into_class_map(),
-        element_tree: tree_text,
-    })
+    Ok(TransformResult::from_collector(code, collector, tree_text, options.split_css)
+        .with_inherited_classes(inherited)
+        .with_style_module(&options.output_mode)
+        .with_timings(timings))
 }
 
-/// 从文件名推导 CSS Module 的 import 路径
-/// `App.tsx` → `./App.module.css`
-fn derive_css_module_path(filename: &str) -> String {
-    let base = filename.rsplit('/').next().unwrap_or(filename);
-    let stem = base.rsplit_once('.').map(|(name, _)| name).unwrap_or(base);
-    format!("./{}.module.css", stem)
+/// 分析元素树中可由继承得到的类；开启 `safe_inheritance_optimization` 时返回删除这些类后的源码
+fn analyze_inheritance<'a>(
+    source: &'a str,
+    roots: &[ElementNode],
+    options: &TransformOptions,
+    collector: &ClassCollector,
+) -> (Cow<'a, str>, Vec<InheritedClass>) {
+    let analysis = inheritance::analyze(roots, &|class| collector.base_declarations(class));
+    let source = if options.safe_inheritance_optimization && !analysis.edits.is_empty() {
+        Cow::Owned(inheritance::apply_edits(source, &analysis.edits))
+    } else {
+        Cow::Borrowed(source)
+    };
+    (source, analysis.report)
 }
 
-/// 创建 side-effect import 声明 AST 节点
-/// `import './App.css'`
-fn create_side_effect_import(import_path: &str) -> ModuleItem {
-    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-        span: DUMMY_SP,
-        specifiers: vec![],
-        src: Box::new(Str {
-            span: DUMMY_SP,
-            value: import_path.into(),
-            raw: None,
-        }),
-        type_only: false,
-        with: None,
-        phase: Default::default(),
-    }))
+/// 无障碍检查（`a11y_audit`），结果作为警告记入收集器的诊断信息
+fn audit_accessibility(roots: &[ElementNode], collector: &mut ClassCollector) {
+    let diagnostics = a11y::audit(roots, &|class| collector.base_declarations(class));
+    for diagnostic in diagnostics {
+        collector.push_diagnostic(diagnostic);
+    }
 }
 
-/// 创建 CSS Module 的 import 声明 AST 节点
-/// `import styles from './App.module.css'`
-fn create_css_module_import(binding_name: &str, import_path: &str) -> ModuleItem {
-    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-        span: DUMMY_SP,
-        specifiers: vec![ImportSpecifier::Default(ImportDefaultSpecifier {
-            span: DUMMY_SP,
-            local: Ident {
-                span: DUMMY_SP,
-                ctxt: Default::default(),
-                sym: binding_name.into(),
-                optional: false,
-            },
-        })],
-        src: Box::new(Str {
-            span: DUMMY_SP,
-            value: import_path.into(),
-            raw: None,
-        }),
-        type_only: false,
-        with: None,
-        phase: Default::default(),
-    }))
+/// JSX 的继承分析：无法解析时不分析，交给后续转换报告错误
+#[cfg(feature = "ecmascript")]
+fn analyze_jsx_inheritance<'a>(
+    source: &'a str,
+    filename: &str,
+    options: &TransformOptions,
+) -> (Cow<'a, str>, Vec<InheritedClass>) {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Custom(filename.to_string()).into(), source.to_string());
+    let syntax = syntax::syntax_for(filename, source, &options.syntax);
+    let Ok(module) = parse_file_as_module(&fm, syntax, EsVersion::latest(), None, &mut vec![]) else {
+        return (Cow::Borrowed(source), Vec::new());
+    };
+    let roots: Vec<_> = element_tree::build_jsx_element_tree_with_spans(&module, fm.start_pos)
+        .into_iter()
+        .flat_map(|component| component.roots)
+        .collect();
+    let collector = create_collector(options, Some(filename));
+    analyze_inheritance(source, &roots, options, &collector)
 }
 
-/// 空行占位符
+/// 转换 Markdown / MDX 源码
 ///
-/// SWC 的 AST 不保留空行信息，parse → emit 后空行会被吞掉。
-/// 解法：在解析前把空行替换为注释占位符（SWC 会保留注释），
+/// 只改写文档中 HTML 块与行内 HTML 的 `class` 属性，Markdown 正文、front matter、
+/// 代码块与行内代码原样保留，整篇文档共用一个收集器。
+/// `filename` 以 `.mdx` 结尾时按 MDX 处理（没有缩进代码块），同时用于 PerFile 命名作用域和规则块注释。
+///
+/// ```
+/// use headwind_transform::{transform_markdown, TransformOptions};
+///
+/// let source = "# Card\n\n<div class=\"p-4\">Hello</div>\n\n`<div class=\"p-4\">`\n";
+/// let result = transform_markdown(source, "docs/card.md", TransformOptions::default()).unwrap();
+/// let name = &result.class_map["p-4"];
+/// assert!(result.code.starts_with(&format!("# Card\n\n<div class=\"{}\">", name)));
+/// assert!(result.code.ends_with("`<div class=\"p-4\">`\n"));
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn transform_markdown(
+    source: &str,
+    filename: &str,
+    options: TransformOptions,
+) -> Result<TransformResult, String> {
+    let mdx = filename.ends_with(".mdx");
+    let tree_text = if options.element_tree {
+        let nodes = element_tree::build_html_element_tree(&markdown::html_content(source, mdx));
+        if nodes.is_empty() {
+            None
+        } else {
+            Some(element_tree::format_element_tree(&nodes))
+        }
+    } else {
+        None
+    };
+
+    let mut timings = Timings::default();
+    let mut collector = create_collector(&options, Some(filename));
+    if options.a11y_audit {
+        let nodes = element_tree::build_html_element_tree(&markdown::html_content(source, mdx));
+        audit_accessibility(&nodes, &mut collector);
+    }
+    let timer = PhaseTimer::start(Phase::Visit);
+    let code = match &options.output_mode {
+        OutputMode::DataAttribute { attr, .. } => {
+            markdown::transform_markdown_source_to_data_attr(source, &mut collector, attr, mdx)
+        }
+        _ => markdown::transform_markdown_source(source, &mut collector, mdx),
+    };
+    timer.stop(&mut timings);
+
+    Ok(TransformResult::from_collector(code, collector, tree_text, options.split_css)
+        .with_style_module(&options.output_mode)
+        .with_timings(timings))
+}
+
+/// 转换磁盘上的文件，选项由配置文件、环境变量与 `overrides` 逐层解析
+///
+/// `config_path` 未指定时从文件所在目录向上查找 `headwind.toml` / `headwind.json` 并逐层合并。
+/// 批量转换多个文件时使用 `ConfigSession`，目录配置只读取一次。
+/// 文件被配置的 include / exclude 排除时返回 `Ok(None)`。
+/// `.html` / `.htm` 按 HTML 处理，`.md` / `.markdown` / `.mdx` 只处理其中的 HTML，其余按 JSX/TSX 处理（未开启 `ecmascript` feature 时返回错误）。
+pub fn transform_file(
+    path: &Path,
+    config_path: Option<&Path>,
+    overrides: HeadwindConfig,
+) -> Result<Option<TransformResult>, String> {
+    let config = HeadwindConfig::resolve(path, config_path, overrides)?;
+    let fallback_index = config.load_fallback_index()?;
+    transform_file_with_config(path, &config, None, fallback_index)
+}
+
+/// 按已解析的配置转换文件（`transform_file` 与 `ConfigSession` 共用）
+fn transform_file_with_config(
+    path: &Path,
+    config: &HeadwindConfig,
+    cache: Option<Arc<ConversionCache>>,
+    fallback_index: Option<VersionedIndex>,
+) -> Result<Option<TransformResult>, String> {
+    if !config.is_included(path) {
+        return Ok(None);
+    }
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("无法读取 {}: {}", path.display(), e))?;
+    let options = config.with_fallback_index(
+        TransformOptions {
+            conversion_cache: cache,
+            ..config.to_transform_options()
+        },
+        fallback_index,
+    );
+    let result = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("html") | Some("htm") => transform_html(&source, options)?,
+        Some("md") | Some("markdown") | Some("mdx") => {
+            transform_markdown(&source, &config.relative_path(path), options)?
+        }
+        // 以相对配置目录的路径作为文件名，PerFile 命名不受检出位置影响
+        #[cfg(feature = "ecmascript")]
+        _ => transform_jsx(&source, &config.relative_path(path), options)?,
+        #[cfg(not(feature = "ecmascript"))]
+        _ => {
+            return Err(format!(
+                "{} 不是 HTML 或 Markdown 文件，转换 JSX/TSX 需要开启 ecmascript feature",
+                path.display()
+            ))
+        }
+    };
+    Ok(Some(result))
+}
+
+/// SWC 解析错误转换为带行号的诊断
+#[cfg(feature = "ecmascript")]
+fn parse_diagnostic(cm: &SourceMap, error: &ParseError, level: DiagnosticLevel) -> Diagnostic {
+    let line = cm.lookup_char_pos(error.span().lo).line;
+    Diagnostic {
+        level,
+        message: format!("第 {} 行解析错误：{}", line, error.kind().msg()),
+    }
+}
+
+/// 从解析时收集的注释中提取 `headwind-ignore` 等指令
+#[cfg(feature = "ecmascript")]
+fn ignore_directives(
+    source: &str,
+    comments: &SingleThreadedComments,
+    base: BytePos,
+) -> IgnoreDirectives {
+    let (leading, trailing) = comments.borrow_all();
+    IgnoreDirectives::from_comments(
+        source,
+        leading
+            .values()
+            .chain(trailing.values())
+            .flatten()
+            .map(|c| {
+                let start = (c.span.lo.0 - base.0) as usize;
+                let end = (c.span.hi.0 - base.0) as usize;
+                (start..end, &*c.text)
+            }),
+    )
+}
+
+/// 带 `/* tw */` 注释的表达式起点（注释作为前导注释挂在其后的第一个 token 上）
+#[cfg(feature = "ecmascript")]
+fn class_marker_positions(comments: &SingleThreadedComments) -> HashSet<BytePos> {
+    let (leading, _) = comments.borrow_all();
+    leading
+        .iter()
+        .filter(|(_, list)| list.iter().any(|c| c.text.trim() == "tw"))
+        .map(|(pos, _)| *pos)
+        .collect()
+}
+
+/// 按转换选项创建类名收集器，`filename` 用于 PerFile 命名作用域和规则块注释
+fn create_collector(options: &TransformOptions, filename: Option<&str>) -> ClassCollector {
+    let collector = match options.output_mode {
+        // React Native 没有 CSS 变量与 oklch，未识别的类也无法保留
+        OutputMode::ReactNative { .. } => ClassCollector::new(
+            options.naming_mode,
+            CssVariableMode::Inline,
+            UnknownClassMode::Remove,
+            ColorMode::Hex,
+            false,
+        )
+        .with_resolve_spacing(true),
+        _ => ClassCollector::new(
+            options.naming_mode,
+            options.css_variables,
+            options.unknown_classes,
+            options.color_mode,
+            options.color_mix,
+        )
+        .with_resolve_spacing(options.resolve_spacing)
+        .with_css_variable_overrides(options.css_variable_overrides)
+        .with_breakpoints(options.breakpoints)
+        .with_container(options.container.clone())
+        .with_unit_conversion(options.unit_conversion, options.root_font_size)
+        .with_targets(options.targets)
+        .with_prose_mode(options.prose_classes),
+    }
+    .with_hash_options(options.hash_version, &options.hash_salt)
+    .with_hash_length(options.hash_length)
+    .with_target_version(options.target_tailwind_version);
+    let collector = match (options.name_scope, filename) {
+        (NameScope::PerFile, Some(filename)) => collector.with_name_scope(filename),
+        _ => collector,
+    };
+    let collector = match &options.output_mode {
+        OutputMode::CssModules { .. } => collector.with_css_modules(),
+        OutputMode::DataAttribute { attr, .. } => collector.with_data_attribute(attr),
+        OutputMode::ReactNative { .. } => collector.with_react_native(),
+        _ => collector,
+    };
+    let collector = if options.css_comments {
+        collector.with_css_comments(filename)
+    } else {
+        collector
+    };
+    let collector = match &options.conversion_cache {
+        Some(cache) => collector.with_conversion_cache(cache.clone()),
+        None => collector,
+    };
+    let collector = match &options.fallback_index {
+        Some(index) => collector.with_fallback_index(index.clone()),
+        None => collector,
+    };
+    collector
+        .with_reserved_names(options.reserved_class_names.iter().cloned())
+        .with_class_overrides(&options.class_overrides)
+        .with_normalize_classes(options.normalize_classes)
+        .with_class_aliases(&options.class_aliases)
+        .with_variant_aliases(&options.variant_aliases)
+        .with_custom_variants(&options.custom_variants)
+        .with_custom_utilities(&options.custom_utilities)
+        .with_css_dedup(options.css_dedup)
+        .with_dark_theme(options.dark_theme.clone())
+        .with_preflight(options.preflight)
+        .with_hooks(
+            options.on_class_collected.clone(),
+            options.on_rule_generated.clone(),
+        )
+}
+
+/// 从文件名推导 CSS Module 的 import 路径
+/// `App.tsx` → `./App.module.css`（`Scss` 时为 `./App.module.scss`）
+#[cfg(feature = "ecmascript")]
+fn derive_css_module_path(filename: &str, extension: CssModuleExtension) -> String {
+    let base = filename.rsplit('/').next().unwrap_or(filename);
+    let stem = base.rsplit_once('.').map(|(name, _)| name).unwrap_or(base);
+    format!("./{}.module.{}", stem, extension.as_str())
+}
+
+/// 从文件名推导 Shadow DOM 样式模块的 import 路径
+/// `App.tsx` → `./App.styles.js`
+#[cfg(feature = "ecmascript")]
+fn derive_style_module_path(filename: &str) -> String {
+    let base = filename.rsplit('/').next().unwrap_or(filename);
+    let stem = base.rsplit_once('.').map(|(name, _)| name).unwrap_or(base);
+    format!("./{}.styles.js", stem)
+}
+
+/// 样式文件路径：显式指定的 `import_path`、`ImportOptions::file_name` 模板或由文件名推导，
+/// `[name]` 已替换，`[contenthash]` 留待生成 CSS 后替换
+#[cfg(feature = "ecmascript")]
+fn style_file_path(options: &TransformOptions, filename: &str) -> Option<String> {
+    let template = options.imports.file_name.as_ref().map(|name| format!("./{}", name));
+    let path = match &options.output_mode {
+        OutputMode::Global {
+            import_path: Some(path),
+        }
+        | OutputMode::DataAttribute {
+            import_path: Some(path),
+            ..
+        } => path.clone(),
+        OutputMode::CssModules { import_path, .. } => {
+            import_path.clone().or(template).unwrap_or_else(|| {
+                derive_css_module_path(filename, options.imports.css_module_extension)
+            })
+        }
+        OutputMode::ShadowDom { import_path, .. } => import_path
+            .clone()
+            .or(template)
+            .unwrap_or_else(|| derive_style_module_path(filename)),
+        _ => return None,
+    };
+    Some(asset::apply_name(&path, filename))
+}
+
+/// 注入的样式 import 路径：样式文件路径按别名改写后追加查询后缀
+#[cfg(feature = "ecmascript")]
+fn style_import_path(options: &TransformOptions, filename: &str, path: &str) -> String {
+    let path = options.imports.alias_path(path, filename);
+    options.imports.query.apply(&path)
+}
+
+/// span 编辑模式下注入的样式 import 语句文本（含换行），`binding` 为 None 时是无绑定的 side-effect import。
+/// 与 codegen 输出保持一致：带分号，引号风格跟随 `ImportOptions` / `EmitOptions`。
+#[cfg(feature = "ecmascript")]
+fn import_statement_code(binding: Option<&str>, import_path: &str, quote: QuoteStyle) -> String {
+    let path = quote.quote(import_path);
+    match binding {
+        Some(binding) => format!("import {} from {};\n", binding, path),
+        None => format!("import {};\n", path),
+    }
+}
+
+/// 创建 side-effect import 声明 AST 节点
+/// `import './App.css'`
+#[cfg(feature = "ecmascript")]
+fn create_side_effect_import(import_path: &str, quote: QuoteStyle) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        span: DUMMY_SP,
+        specifiers: vec![],
+        src: Box::new(quote.str_lit(DUMMY_SP, import_path)),
+        type_only: false,
+        with: None,
+        phase: Default::default(),
+    }))
+}
+
+/// 创建 CSS Module 的 import 声明 AST 节点
+/// `import styles from './App.module.css'`
+#[cfg(feature = "ecmascript")]
+fn create_css_module_import(binding_name: &str, import_path: &str, quote: QuoteStyle) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        span: DUMMY_SP,
+        specifiers: vec![ImportSpecifier::Default(ImportDefaultSpecifier {
+            span: DUMMY_SP,
+            local: Ident {
+                span: DUMMY_SP,
+                ctxt: Default::default(),
+                sym: binding_name.into(),
+                optional: false,
+            },
+        })],
+        src: Box::new(quote.str_lit(DUMMY_SP, import_path)),
+        type_only: false,
+        with: None,
+        phase: Default::default(),
+    }))
+}
+
+/// 调用用户格式化钩子（未设置时原样返回）
+#[cfg(feature = "ecmascript")]
+fn apply_formatter(
+    options: &TransformOptions,
+    code: String,
+    filename: &str,
+) -> Result<String, String> {
+    match &options.formatter {
+        Some(formatter) => formatter(&code, filename).map_err(|e| format!("格式化错误: {}", e)),
+        None => Ok(code),
+    }
+}
+
+/// 创建具名 import 声明 AST 节点
+/// `import { mergeClasses } from './headwind-runtime.js'`
+#[cfg(feature = "ecmascript")]
+fn create_named_import(name: &str, import_path: &str, quote: QuoteStyle) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        span: DUMMY_SP,
+        specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+            span: DUMMY_SP,
+            local: Ident {
+                span: DUMMY_SP,
+                ctxt: Default::default(),
+                sym: name.into(),
+                optional: false,
+            },
+            imported: None,
+            is_type_only: false,
+        })],
+        src: Box::new(quote.str_lit(DUMMY_SP, import_path)),
+        type_only: false,
+        with: None,
+        phase: Default::default(),
+    }))
+}
+
+/// 空行占位符
+///
+/// SWC 的 AST 不保留空行信息，parse → emit 后空行会被吞掉。
+/// 解法：在解析前把空行替换为注释占位符（SWC 会保留注释），
 /// 代码生成后再把占位符还原为空行。
+#[cfg(feature = "ecmascript")]
 const EMPTY_LINE_MARKER: &str = "// __HEADWIND_EMPTY_LINE__";
 
 /// 将源码中的空行替换为占位符注释，使 SWC 保留空行位置
+#[cfg(feature = "ecmascript")]
 fn preserve_empty_lines(source: &str) -> String {
     let lines: Vec<&str> = source.lines().collect();
 
@@ -416,6 +1801,7 @@ fn preserve_empty_lines(source: &str) -> String {
 }
 
 /// 将占位符注释还原为空行
+#[cfg(feature = "ecmascript")]
 fn restore_empty_lines(code: &str) -> String {
     code.lines()
         .map(|line| {
@@ -429,7 +1815,14 @@ fn restore_empty_lines(code: &str) -> String {
         .join("\n")
 }
 
+#[cfg(feature = "ecmascript")]
+thread_local! {
+    /// codegen 与 AST 扩展使用的 SWC 全局状态：每个线程首次使用时创建，之后复用
+    static EMIT_GLOBALS: Globals = Globals::new();
+}
+
 /// 使用 SWC codegen 输出 JS/TS 模块代码
+#[cfg(feature = "ecmascript")]
 fn emit_module(
     cm: &Lrc<SourceMap>,
     module: &swc_core::ecma::ast::Module,
@@ -451,7 +1844,7 @@ fn emit_module(
     String::from_utf8(buf).map_err(|e| format!("UTF-8 编码错误: {:?}", e))
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "ecmascript"))]
 mod tests {
     use super::*;
 
@@ -807,12 +2200,17 @@ mod tests {
 
     #[test]
     fn test_derive_css_module_path() {
-        assert_eq!(derive_css_module_path("App.tsx"), "./App.module.css");
+        let css = CssModuleExtension::Css;
+        assert_eq!(derive_css_module_path("App.tsx", css), "./App.module.css");
         assert_eq!(
-            derive_css_module_path("src/components/Button.jsx"),
+            derive_css_module_path("src/components/Button.jsx", css),
             "./Button.module.css"
         );
-        assert_eq!(derive_css_module_path("index.ts"), "./index.module.css");
+        assert_eq!(derive_css_module_path("index.ts", css), "./index.module.css");
+        assert_eq!(
+            derive_css_module_path("App.tsx", CssModuleExtension::Scss),
+            "./App.module.scss"
+        );
     }
 
     // === Bracket 访问模式测试 ===
@@ -986,6 +2384,30 @@ mod tests {
         assert!(result.code.contains(&pattern));
     }
 
+    #[test]
+    fn test_camel_case_unicode_class() {
+        let source = r#"function App() {
+    return <div className="font-[family-name:'思源黑体'] md:p-4">Hello</div>;
+}"#;
+
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                naming_mode: NamingMode::CamelCase,
+                output_mode: OutputMode::css_modules(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // 非 ASCII 字符保留在类名中，丢弃方括号与引号后追加 hash；不是 JS 标识符，退回方括号访问
+        let class_name = &result.class_map["font-[family-name:'思源黑体'] md:p-4"];
+        assert!(class_name.starts_with("fontFamilyName思源黑体MdP4"), "{}", class_name);
+        assert!(result.css.contains(&format!(".{} {{", class_name)), "{}", result.css);
+        assert!(result.code.contains("styles["), "{}", result.code);
+    }
+
     #[test]
     fn test_hash_with_css_modules_bracket() {
         let source = r#"function App() {
@@ -1195,6 +2617,36 @@ mod tests {
         assert!(tree.contains("  - p text-gray-500"));
     }
 
+    #[test]
+    fn test_html_fragment_wrapper() {
+        let fragment = "<p class=\"p-4\">one</p>\n<p class=\"p-4\">two";
+        let options = |html_wrapper| TransformOptions {
+            html_wrapper,
+            element_tree: true,
+            ..Default::default()
+        };
+
+        let result = transform_html(fragment, options(HtmlWrapper::Preserve)).unwrap();
+        let name = &result.class_map["p-4"];
+        assert_eq!(result.code, fragment.replace("p-4", name));
+        let tree = result.element_tree.unwrap();
+        assert!(tree.contains("- p p-4 \"one\" [ref=e1]"), "{}", tree);
+        assert!(tree.contains("- p p-4 \"two\" [ref=e2]"), "{}", tree);
+
+        let wrapped = transform_html(fragment, options(HtmlWrapper::Document)).unwrap();
+        assert!(wrapped.code.starts_with("<!DOCTYPE html>\n<html>\n<head>\n"));
+        assert!(wrapped.code.contains(&format!("<body>\n{}\n</body>", result.code)));
+        // 完整文档不重复包裹
+        let document = format!("<!doctype html>\n<html>\n<body class=\"m-0\">\n{}\n</body>\n</html>\n", fragment);
+        let again = transform_html(&document, options(HtmlWrapper::Document)).unwrap();
+        assert_eq!(again.code.replace(&again.class_map["m-0"], "m-0"), document.replace("p-4", name));
+
+        let unwrapped = transform_html(&document, options(HtmlWrapper::Fragment)).unwrap();
+        assert_eq!(unwrapped.code.trim(), result.code);
+        assert!(!unwrapped.class_map.contains_key("m-0"));
+        assert!(!unwrapped.element_tree.unwrap().contains("body"));
+    }
+
     #[test]
     fn test_element_tree_multi_component() {
         let source = r#"function Header() {
@@ -1263,4 +2715,2007 @@ export default function App() {
         let result = transform_jsx(source, "App.tsx", TransformOptions::default()).unwrap();
         assert!(result.element_tree.is_none());
     }
+
+    #[test]
+    fn test_inheritance_optimization_jsx() {
+        let source = r#"function App() {
+    return <div className="text-gray-700 font-bold"><p className={"text-gray-700 p-4"}>Hi</p><Card className="text-gray-700" /></div>;
+}"#;
+        let report = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                inheritance_report: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let classes: Vec<_> = report
+            .inherited_classes
+            .iter()
+            .map(|c| (c.element.as_str(), c.class.as_str(), c.ancestor.as_str()))
+            .collect();
+        assert_eq!(classes, [("p", "text-gray-700", "div")]);
+        // 只报告时输出不变
+        assert!(report.class_map.contains_key("text-gray-700 p-4"));
+
+        let optimized = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                safe_inheritance_optimization: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(optimized.inherited_classes, report.inherited_classes);
+        assert!(optimized.class_map.contains_key("p-4"));
+        assert!(!optimized.class_map.contains_key("text-gray-700 p-4"));
+    }
+
+    #[test]
+    fn test_css_comments() {
+        let source = r#"function App() {
+    return <div className="p-4 text-center"><Card.Body className="m-2" /></div>;
+}
+const base = cn("p-4 text-center", "flex");"#;
+
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                css_comments: true,
+                class_functions: vec!["cn".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let css = &result.css;
+        assert!(css.contains("/* App.tsx <div> \"p-4 text-center\" */\n."), "{}", css);
+        assert!(css.contains("/* App.tsx <Card.Body> \"m-2\" */\n."), "{}", css);
+        // 元素外的字符串没有元素名；已生成的规则不重复注释
+        assert!(css.contains("/* App.tsx \"flex\" */\n."), "{}", css);
+        assert_eq!(css.matches("/*").count(), 3, "{}", css);
+
+        let plain = transform_jsx(source, "App.tsx", TransformOptions::default()).unwrap();
+        assert!(!plain.css.contains("/*"));
+    }
+
+    // === 保留格式（span 编辑）模式测试 ===
+
+    #[test]
+    fn test_preserve_formatting_only_touches_class_ranges() {
+        let source = "export default function App() {\n  const x = 'single'\n\n  return <div   className='p-4 m-2' id=\"a\">{x}</div>\n}\n";
+
+        let result = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("p-4 m-2").unwrap();
+        let expected = source.replace("p-4 m-2", name);
+        assert_eq!(result.code, expected);
+    }
+
+    #[test]
+    fn test_preserve_formatting_template_and_expr() {
+        let source = "function App() {\n  return <p className={`p-4`}><b className={ \"m-2\" } /></p>\n}";
+
+        let result = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let p4 = result.class_map.get("p-4").unwrap();
+        let m2 = result.class_map.get("m-2").unwrap();
+        let expected = source
+            .replace("`p-4`", &format!("`{}`", p4))
+            .replace("\"m-2\"", &format!("\"{}\"", m2));
+        assert_eq!(result.code, expected);
+    }
+
+    #[test]
+    fn test_preserve_formatting_css_modules_import() {
+        let source = "const App = () => <div className=\"p-4\">Hi</div>;\n";
+
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                output_mode: OutputMode::css_modules_bracket(),
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("p-4").unwrap();
+        assert_eq!(
+            result.code,
+            format!(
+                "import styles from \"./App.module.css\";\nconst App = () => <div className={{styles[\"{}\"]}}>Hi</div>;\n",
+                name
+            )
+        );
+    }
+
+    // === 输出格式选项测试 ===
+
+    #[test]
+    fn test_emit_single_quotes() {
+        let source = r#"function App() {
+    return <div className="p-4">Hello</div>;
+}"#;
+
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                output_mode: OutputMode::css_modules_bracket(),
+                emit: EmitOptions {
+                    quote_style: QuoteStyle::Single,
+                    jsx_quote_style: QuoteStyle::Double,
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        println!("=== Single Quote Code ===\n{}", result.code);
+        assert!(result.code.contains("import styles from './App.module.css'"));
+        let name = result.class_map.get("p-4").unwrap();
+        assert!(result.code.contains(&format!("styles['{}']", name)));
+    }
+
+    #[test]
+    fn test_emit_jsx_single_quotes() {
+        let source = r#"function App() {
+    return <div className="p-4">Hello</div>;
+}"#;
+
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                emit: EmitOptions {
+                    jsx_quote_style: QuoteStyle::Single,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("p-4").unwrap();
+        assert!(result.code.contains(&format!("className='{}'", name)));
+    }
+
+    #[test]
+    fn test_ast_extensions() {
+        use std::sync::Mutex;
+        use swc_core::ecma::visit::VisitMut;
+
+        /// 类名替换之前把 `legacy-card` 展开为工具类，之后记录类名映射的大小
+        struct Migrate {
+            before: bool,
+            seen: Arc<Mutex<Vec<usize>>>,
+        }
+
+        impl VisitMut for Migrate {
+            fn visit_mut_str(&mut self, s: &mut Str) {
+                if self.before && s.value.as_str() == Some("legacy-card") {
+                    *s = QuoteStyle::Double.str_lit(s.span, "p-4 m-2");
+                }
+            }
+        }
+
+        impl AstExtension for Migrate {
+            fn visitor<'a>(&self, context: &AstContext<'a>) -> Box<dyn VisitMut + 'a> {
+                assert_eq!(context.filename, "App.tsx");
+                self.seen.lock().unwrap().push(context.class_map.len());
+                Box::new(Migrate {
+                    before: self.before,
+                    seen: self.seen.clone(),
+                })
+            }
+
+            fn before_classes(&self) -> bool {
+                self.before
+            }
+        }
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let options = |preserve_formatting| TransformOptions {
+            preserve_formatting,
+            ast_extensions: vec![
+                Arc::new(Migrate { before: false, seen: seen.clone() }),
+                Arc::new(Migrate { before: true, seen: seen.clone() }),
+            ],
+            ..Default::default()
+        };
+        let source = r#"const App = () => <div className="legacy-card">Hi</div>;"#;
+
+        let result = transform_jsx(source, "App.tsx", options(false)).unwrap();
+        let name = &result.class_map["p-4 m-2"];
+        assert!(result.code.contains(&format!("className=\"{}\"", name)));
+        // 前置扩展先运行（映射为空），后置扩展看到完整映射
+        assert_eq!(*seen.lock().unwrap(), [0, 1]);
+
+        let preserved = transform_jsx(source, "App.tsx", options(true)).unwrap();
+        assert!(preserved.class_map.contains_key("legacy-card"));
+        assert!(preserved
+            .diagnostics
+            .iter()
+            .any(|d| d.level == DiagnosticLevel::Warning && d.message.contains("AST 扩展")));
+        assert_eq!(seen.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_formatter_hook() {
+        let source = r#"function App() {
+    return <div className="p-4">Hello</div>;
+}"#;
+
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                formatter: Some(Arc::new(|code: &str, filename: &str| -> Result<String, String> {
+                    Ok(format!("// {}\n{}", filename, code))
+                })),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(result.code.starts_with("// App.tsx\n"));
+
+        let err = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                formatter: Some(Arc::new(|_: &str, _: &str| -> Result<String, String> {
+                    Err("boom".to_string())
+                })),
+                ..Default::default()
+            },
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_css_module_dts() {
+        let source = r#"export const App = () => (
+    <div className="p-4 text-center">
+        <p className="p-4 text-center">a</p>
+        <p className="hover:p-2">b</p>
+    </div>
+);
+"#;
+        let options = |css_module_dts| TransformOptions {
+            naming_mode: NamingMode::CamelCase,
+            output_mode: OutputMode::css_modules(),
+            css_module_dts,
+            preserve_formatting: true,
+            ..Default::default()
+        };
+        let result = transform_jsx(source, "src/App.tsx", options(true)).unwrap();
+        let (path, dts) = result.dts.unwrap();
+        assert_eq!(path, "./App.module.css.d.ts");
+        assert!(dts.starts_with("declare const styles: {\n"), "{}", dts);
+        assert!(dts.ends_with("};\nexport default styles;\n"), "{}", dts);
+        // 每个生成的类名声明一次
+        assert_eq!(dts.matches("readonly").count(), result.class_map.len(), "{}", dts);
+        for name in result.class_map.values() {
+            assert!(dts.contains(&format!(" {}: string;", name)), "{}", dts);
+        }
+
+        assert!(transform_jsx(source, "src/App.tsx", options(false)).unwrap().dts.is_none());
+        // 只在 CssModules 模式下生成
+        let global = TransformOptions {
+            output_mode: OutputMode::default(),
+            ..options(true)
+        };
+        assert!(transform_jsx(source, "src/App.tsx", global).unwrap().dts.is_none());
+    }
+
+    // === 复用已有 CSS Module import 测试 ===
+
+    #[test]
+    fn test_css_modules_reuse_existing_import() {
+        let source = r#"import css from "./App.module.css";
+
+export default function App() {
+    return <div className={css.legacy}><p className="p-4">Hi</p></div>;
+}"#;
+
+        let result = transform_jsx(source, "App.tsx", TransformOptions {
+            output_mode: OutputMode::css_modules(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        println!("=== Reuse Import Code ===\n{}", result.code);
+        // 只有一个 import，且使用已有绑定名
+        assert_eq!(result.code.matches("App.module.css").count(), 1);
+        assert!(!result.code.contains("styles."));
+        let name = result.class_map.get("p-4").unwrap();
+        assert!(result.code.contains(&format!("css.{}", name)));
+    }
+
+    #[test]
+    fn test_css_modules_incremental_adoption() {
+        let options = || TransformOptions {
+            output_mode: OutputMode::css_modules(),
+            ..Default::default()
+        };
+        let first = transform_jsx(
+            r#"export default function App() {
+    return <div className="p-4">Hi</div>;
+}"#,
+            "App.tsx",
+            options(),
+        )
+        .unwrap();
+        let p4 = first.class_map["p-4"].clone();
+
+        // 上一轮的产出中又写了新的类，其中 `p-4` 与已有引用相同
+        let source = first.code.replace(
+            "Hi",
+            r#"<span className="m-2">New</span><b className="p-4">Again</b>"#,
+        );
+        let second =
+            transform_css_module_incremental(&source, "App.tsx", &first.css, options()).unwrap();
+        println!("=== Incremental Code ===\n{}", second.code);
+        assert_eq!(second.code.matches("App.module.css").count(), 1);
+        assert_eq!(second.code.matches(&format!("styles.{}", p4)).count(), 2);
+        assert_eq!(second.class_map["p-4"], p4);
+        assert!(second.code.contains(&format!("styles.{}", second.class_map["m-2"])));
+        assert!(second.css.starts_with(first.css.trim_end()));
+        assert_eq!(second.css.matches(&format!(".{} {{", p4)).count(), 1);
+        assert!(second.diagnostics.is_empty());
+
+        // 对产出再次转换，结果不再变化
+        let third =
+            transform_css_module_incremental(&second.code, "App.tsx", &second.css, options())
+                .unwrap();
+        assert_eq!(third.code, second.code);
+        assert_eq!(third.css, second.css);
+
+        // 样式文件中的同名类规则不同：改用加盐的名称，已有规则不受影响
+        let taken = transform_jsx(r#"const a = <i className="m-2" />;"#, "App.tsx", options())
+            .unwrap()
+            .class_map["m-2"]
+            .clone();
+        let existing = format!("{}\n.{} {{\n  color: red;\n}}\n", first.css, taken);
+        let renamed =
+            transform_css_module_incremental(&source, "App.tsx", &existing, options()).unwrap();
+        assert_ne!(renamed.class_map["m-2"], taken);
+        assert_eq!(renamed.class_map["p-4"], p4);
+        assert!(renamed.css.contains(&format!(".{} {{\n  color: red;\n}}", taken)));
+
+        assert!(transform_css_module_incremental(&source, "App.tsx", "", TransformOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_css_modules_binding_conflict() {
+        let source = r#"import styles from "./theme.module.css";
+
+function App() {
+    return <div className="p-4">Hi</div>;
+}"#;
+
+        // 绑定名已被占用时改用未占用的名称
+        for preserve_formatting in [false, true] {
+            let result = transform_jsx(source, "App.tsx", TransformOptions {
+                output_mode: OutputMode::css_modules(),
+                preserve_formatting,
+                ..Default::default()
+            })
+            .unwrap();
+            let name = &result.class_map["p-4"];
+            assert!(result.code.contains("import styles from \"./theme.module.css\""), "{}", result.code);
+            assert!(result.code.contains("import styles$1 from \"./App.module.css\""), "{}", result.code);
+            assert!(result.code.contains(&format!("className={{styles$1.{}}}", name)), "{}", result.code);
+        }
+
+        let source = "import styles from \"./theme.module.css\";\nconst styles$1 = {};\nexport const A = () => <p className=\"p-4\" />;\n";
+        let result = transform_jsx(source, "App.tsx", TransformOptions {
+            output_mode: OutputMode::css_modules(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(result.code.contains("import styles$2 from"), "{}", result.code);
+    }
+
+    #[test]
+    fn test_css_modules_binding_conflict_without_classes() {
+        // 没有类名时不注入 import，已有的同名绑定不影响转换
+        let source = "import styles from \"./x.module.css\";\nexport const a = 1;\n";
+        let result = transform_jsx(source, "App.tsx", TransformOptions {
+            output_mode: OutputMode::css_modules(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(result.code.trim_end(), source.trim_end());
+        assert!(result.class_map.is_empty());
+    }
+
+    // === 类名冲突检测测试 ===
+
+    #[test]
+    fn test_reserved_class_names_from_existing_css() {
+        let source = r#"function App() {
+    return <div className="p-4 m-2">Hello</div>;
+}"#;
+        let legacy_css = ".p4_m2 { color: red; }\n.header { margin: 0 }";
+
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                naming_mode: NamingMode::Readable,
+                reserved_class_names: extract_css_class_names(legacy_css),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("p-4 m-2").unwrap();
+        assert_ne!(name, "p4_m2");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].level, DiagnosticLevel::Warning);
+    }
+
+    // === class + className 合并测试 ===
+
+    #[test]
+    fn test_merge_class_and_class_name() {
+        let source = r#"function App() {
+    return <div class="m-2" className="p-4">Hello</div>;
+}"#;
+
+        let result = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                class_attr_merge: ClassAttrMerge::PreferClassName,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        println!("=== Merged Attr Code ===\n{}", result.code);
+        assert_eq!(result.class_map.len(), 1);
+        assert!(result.class_map.contains_key("m-2 p-4"));
+        assert!(result.code.contains("className="));
+        assert!(!result.code.contains("class="));
+    }
+
+    #[test]
+    fn test_merge_prefer_class() {
+        let source = r#"function App() {
+    return <div className="p-4" class={`m-2`}>Hello</div>;
+}"#;
+
+        let result = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                class_attr_merge: ClassAttrMerge::PreferClass,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(result.class_map.contains_key("p-4 m-2"));
+        assert!(!result.code.contains("className"));
+    }
+
+    #[test]
+    fn test_merge_separate_and_dynamic() {
+        let separate = r#"function App() {
+    return <div class="m-2" className="p-4">Hello</div>;
+}"#;
+        // 默认不合并，两个属性保持原样
+        let result = transform_jsx(separate, "App.jsx", TransformOptions::default()).unwrap();
+        assert_eq!(result.class_map.len(), 2);
+        assert!(result.class_map.contains_key("m-2"));
+        assert!(result.class_map.contains_key("p-4"));
+        assert!(result.code.contains("class=") && result.code.contains("className="));
+
+        // 任一为动态表达式时不合并
+        let dynamic = r#"function App({ a }) {
+    return <div class={a} className="p-4">Hello</div>;
+}"#;
+        let options = TransformOptions {
+            class_attr_merge: ClassAttrMerge::PreferClassName,
+            ..Default::default()
+        };
+        let result = transform_jsx(dynamic, "App.jsx", options).unwrap();
+        assert!(result.code.contains("class={a}"));
+        assert!(result.class_map.contains_key("p-4"));
+    }
+
+    #[test]
+    fn test_class_attr_name_normalization() {
+        let source = r#"function App({ a }) {
+    return (
+        <main class="p-4">
+            <h1 className="text-lg">Hi</h1>
+            <p class={a} className="flex" />
+        </main>
+    );
+}"#;
+        for preserve_formatting in [false, true] {
+            let result = transform_jsx(
+                source,
+                "App.jsx",
+                TransformOptions {
+                    class_attr_name: ClassAttrName::ClassName,
+                    preserve_formatting,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let main = &result.class_map["p-4"];
+            assert!(result.code.contains(&format!("<main className=\"{}\"", main)), "{}", result.code);
+            assert!(!result.code.contains("<main class="), "{}", result.code);
+            // class 与动态 className 并存时无法改名
+            assert!(result.code.contains("class={a}"), "{}", result.code);
+            assert!(result
+                .diagnostics
+                .iter()
+                .any(|d| d.message.contains("<p> 同时包含未合并的 class 与 className")));
+        }
+
+        let source = "import tw from \"twin.macro\";\nexport const A = () => <div className=\"p-4\"><span tw=\"m-2\" /></div>;\n";
+        let result = transform_jsx(
+            source,
+            "A.jsx",
+            TransformOptions {
+                class_attr_name: ClassAttrName::Class,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(!result.code.contains("className"), "{}", result.code);
+        assert!(result.code.contains(&format!("<div class=\"{}\"", result.class_map["p-4"])));
+        assert!(result.code.contains(&format!("<span class=\"{}\"", result.class_map["m-2"])));
+    }
+
+    #[test]
+    fn test_solid_class_list() {
+        let source = r#"function App(props) {
+    return <div class="p-4" classList={{ "m-2 flex": props.open, active: props.active }}>Hi</div>;
+}"#;
+        let result = transform_jsx(source, "App.jsx", TransformOptions::default()).unwrap();
+        let generated = &result.class_map["m-2 flex"];
+        assert!(
+            result.code.contains(&format!("\"{}\": props.open", generated)),
+            "{}",
+            result.code
+        );
+        // 不含可识别类的键与值保持原样
+        assert!(result.code.contains("active: props.active"));
+        assert!(!result.class_map.contains_key("active"));
+
+        let css_modules = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                output_mode: OutputMode::css_modules(),
+                naming_mode: NamingMode::CamelCase,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let generated = &css_modules.class_map["m-2 flex"];
+        assert!(
+            css_modules.code.contains(&format!("[styles.{}]: props.open", generated)),
+            "{}",
+            css_modules.code
+        );
+
+        let preserved = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let generated = &preserved.class_map["m-2 flex"];
+        assert!(
+            preserved.code.contains(&format!(
+                "classList={{{{ \"{}\": props.open, active: props.active }}}}",
+                generated
+            )),
+            "{}",
+            preserved.code
+        );
+    }
+
+    #[test]
+    fn test_component_class_attrs() {
+        let source = r#"function App() {
+    return (
+        <Card class="p-4" classList={{ "m-2": true }}>
+            <UI.Title className="text-lg" />
+            <div class="flex" />
+        </Card>
+    );
+}"#;
+        let result = transform_jsx(source, "App.tsx", TransformOptions::default()).unwrap();
+        for classes in ["p-4", "m-2", "text-lg", "flex"] {
+            assert!(result.class_map.contains_key(classes), "{}", classes);
+        }
+
+        let dom_only = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                component_class_attrs: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(dom_only.class_map.keys().collect::<Vec<_>>(), ["flex"]);
+        for kept in [r#"<Card class="p-4""#, r#""m-2": true"#, r#"className="text-lg""#] {
+            assert!(dom_only.code.contains(kept), "{}", dom_only.code);
+        }
+    }
+
+    #[test]
+    fn test_merge_class_attrs_preserve_formatting() {
+        let source = "const A = () => <div  class='m-2'\n  className=\"p-4\" id=\"x\" />;\n";
+
+        let result = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                preserve_formatting: true,
+                class_attr_merge: ClassAttrMerge::PreferClassName,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("m-2 p-4").unwrap();
+        assert_eq!(
+            result.code,
+            format!("const A = () => <div\n  className=\"{}\" id=\"x\" />;\n", name)
+        );
+    }
+
+    // === spread 属性测试 ===
+
+    #[test]
+    fn test_spread_attr_diagnostic() {
+        let source = r#"function Btn(props) {
+    return <button {...props} className="p-4">Go</button>;
+}"#;
+
+        let result = transform_jsx(source, "Btn.jsx", TransformOptions::default()).unwrap();
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(result.diagnostics[0].message.contains("<button>"));
+        // 默认不改写
+        assert!(!result.code.contains("props.className"));
+    }
+
+    #[test]
+    fn test_spread_merge_helper() {
+        let source = r#"function Btn(props) {
+    return <button {...props} className="p-4">Go</button>;
+}"#;
+
+        let result = transform_jsx(
+            source,
+            "Btn.jsx",
+            TransformOptions {
+                spread_merge_helper: Some("cn".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        println!("=== Spread Merge Code ===\n{}", result.code);
+        let name = result.class_map.get("p-4").unwrap();
+        assert!(result.code.contains(&format!("cn(\"{}\", props.className)", name)));
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_spread_merge_helper_preserve_formatting() {
+        let source = "const A = (p) => <a {...p.link} className='p-4' />;\n";
+
+        let result = transform_jsx(
+            source,
+            "A.jsx",
+            TransformOptions {
+                output_mode: OutputMode::css_modules_with_path("./a.module.css"),
+                spread_merge_helper: Some("cn".to_string()),
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("p-4").unwrap();
+        assert_eq!(
+            result.code,
+            format!(
+                "import styles from \"./a.module.css\";\nconst A = (p) => <a {{...p.link}} className={{cn(styles.{}, p.link.className)}} />;\n",
+                name
+            )
+        );
+    }
+
+    // === 运行时合并辅助函数测试 ===
+
+    #[test]
+    fn test_runtime_helper_mixed_template() {
+        let source = r#"function App({ active, color }) {
+    return <div className={`p-4 m-2 ${active ? "ring" : ""} text-${color}`}>Hi</div>;
+}"#;
+
+        let result = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                runtime_helper: Some(RuntimeHelper::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        println!("=== Runtime Helper Code ===\n{}", result.code);
+        let name = result.class_map.get("p-4 m-2").unwrap();
+        assert!(result.code.contains("import { mergeClasses } from \"./headwind-runtime.js\""));
+        assert!(result.code.contains(&format!("mergeClasses(\"{}\", `", name)));
+        // 与插值紧贴的片段保持动态
+        assert!(result.code.contains("text-${color}"));
+    }
+
+    #[test]
+    fn test_runtime_helper_single_interpolation() {
+        let source = r#"function App({ cls }) {
+    return <div className={`p-4 ${cls}`}>Hi</div>;
+}"#;
+
+        let result = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                output_mode: OutputMode::css_modules(),
+                runtime_helper: Some(RuntimeHelper::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("p-4").unwrap();
+        assert!(result.code.contains(&format!("mergeClasses(styles.{}, cls)", name)));
+    }
+
+    #[test]
+    fn test_runtime_helper_preserve_formatting() {
+        let source = "const A = ({ c }) => <p className={`p-4 ${c}`} />;\n";
+
+        let result = transform_jsx(
+            source,
+            "A.jsx",
+            TransformOptions {
+                runtime_helper: Some(RuntimeHelper::default()),
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("p-4").unwrap();
+        assert_eq!(
+            result.code,
+            format!(
+                "import {{ mergeClasses }} from \"./headwind-runtime.js\";\nconst A = ({{ c }}) => <p className={{mergeClasses(\"{}\", ` ${{c}}`)}} />;\n",
+                name
+            )
+        );
+    }
+
+    #[test]
+    fn test_string_concatenation() {
+        let source = r#"function App({ active, color }) {
+    return <div className={"p-4 m-2 " + (active ? "ring" : "") + " text-" + color + " flex"}>Hi</div>;
+}"#;
+
+        let result = transform_jsx(source, "App.jsx", TransformOptions::default()).unwrap();
+
+        println!("=== Concatenation Code ===\n{}", result.code);
+        let name = result.class_map.get("p-4 m-2 flex").unwrap();
+        assert!(result.code.contains(&format!("\"{} \" + (active", name)));
+        // 与动态部分紧贴的片段保持动态
+        assert!(result.code.contains("\" text-\" + color + \" \""));
+
+        // CSS Modules 模式与插值模板字面量一样需要运行时辅助函数
+        let source = "const A = ({ extra }) => <p className={\"p-4 \" + extra} />;\n";
+        let css_modules = |runtime_helper| TransformOptions {
+            output_mode: OutputMode::css_modules(),
+            runtime_helper,
+            ..Default::default()
+        };
+        let result =
+            transform_jsx(source, "A.jsx", css_modules(Some(RuntimeHelper::default()))).unwrap();
+        let name = result.class_map.get("p-4").unwrap();
+        assert!(result.code.contains(&format!("mergeClasses(styles.{}, extra)", name)));
+        assert!(transform_jsx(source, "A.jsx", css_modules(None))
+            .unwrap()
+            .class_map
+            .is_empty());
+
+        let result = transform_jsx(
+            source,
+            "A.jsx",
+            TransformOptions {
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let name = result.class_map.get("p-4").unwrap();
+        assert_eq!(result.code, source.replace("p-4", name));
+    }
+
+    #[test]
+    fn test_runtime_helper_disabled_skips_dynamic() {
+        let source = r#"function App({ cls }) {
+    return <div className={`p-4 ${cls}`}>Hi</div>;
+}"#;
+
+        let result = transform_jsx(source, "App.jsx", TransformOptions::default()).unwrap();
+        assert!(result.class_map.is_empty());
+        assert!(result.code.contains("p-4 ${cls}"));
+    }
+
+    // === Hash 版本测试 ===
+
+    #[test]
+    fn test_hash_version_golden() {
+        let source = r#"function App() {
+    return <div className="p-4 m-2">Hi</div>;
+}"#;
+
+        let v1 = transform_jsx(source, "App.jsx", TransformOptions::default()).unwrap();
+        assert_eq!(v1.class_map.get("p-4 m-2").unwrap(), "c_728afc693773");
+
+        let v2 = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                hash_version: HashVersion::V2,
+                hash_salt: "inline".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(v2.class_map.get("p-4 m-2").unwrap(), "c_c2a6d93a266f");
+    }
+
+    // === 命名作用域测试 ===
+
+    #[test]
+    fn test_name_scope_per_file() {
+        let source = r#"function App() {
+    return <div className="p-4 m-2">Hi</div>;
+}"#;
+        let per_file = |filename: &str| {
+            transform_jsx(
+                source,
+                filename,
+                TransformOptions {
+                    name_scope: NameScope::PerFile,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .class_map
+            .get("p-4 m-2")
+            .unwrap()
+            .clone()
+        };
+
+        let a = per_file("src/A.tsx");
+        let b = per_file("src/B.tsx");
+        assert_ne!(a, b);
+        assert_eq!(a, per_file("src/A.tsx"));
+
+        // Global 下与文件名无关
+        let global = transform_jsx(source, "src/A.tsx", TransformOptions::default()).unwrap();
+        let global_b = transform_jsx(source, "src/B.tsx", TransformOptions::default()).unwrap();
+        assert_eq!(global.class_map, global_b.class_map);
+    }
+
+    // === DataAttribute 输出模式测试 ===
+
+    #[test]
+    fn test_data_attribute_mode() {
+        let source = r#"function App() {
+    return <div className="p-4 m-2">Hi</div>;
+}"#;
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                output_mode: OutputMode::data_attribute(),
+                naming_mode: NamingMode::Readable,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(result.code.contains(r#"<div data-hw="p4_m2">"#), "{}", result.code);
+        assert!(!result.code.contains("className"));
+        assert!(result.css.contains(r#"[data-hw="p4_m2"] {"#));
+        assert!(!result.css.contains(".p4_m2"));
+    }
+
+    #[test]
+    fn test_data_attribute_preserve_formatting_keeps_unknown() {
+        let source = "const a = <div id='x' className='p-4 card'>Hi</div>;\n";
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                output_mode: OutputMode::DataAttribute {
+                    attr: "data-ui".to_string(),
+                    import_path: None,
+                },
+                naming_mode: NamingMode::Readable,
+                unknown_classes: UnknownClassMode::Preserve,
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            result.code,
+            "const a = <div id='x' className=\"card\" data-ui=\"p4\">Hi</div>;\n"
+        );
+    }
+
+    #[test]
+    fn test_data_attribute_generated_name_matches_input_class() {
+        // Readable 模式下 `flex` 的生成名也是 `flex`，仍应移入数据属性
+        let options = || TransformOptions {
+            output_mode: OutputMode::data_attribute(),
+            naming_mode: NamingMode::Readable,
+            unknown_classes: UnknownClassMode::Preserve,
+            ..Default::default()
+        };
+        let jsx = transform_jsx(r#"const a = <div className="flex card">Hi</div>;"#, "App.tsx", options()).unwrap();
+        assert!(
+            jsx.code.contains(r#"className="card" data-hw="flex""#),
+            "{}",
+            jsx.code
+        );
+        assert!(jsx.css.contains(r#"[data-hw="flex"] {"#));
+
+        let html = transform_html(r#"<div class="flex card">Hi</div>"#, options()).unwrap();
+        assert_eq!(html.code, r#"<div class="card" data-hw="flex">Hi</div>"#);
+        assert!(html.css.contains(r#"[data-hw="flex"] {"#));
+    }
+
+    #[test]
+    fn test_data_attribute_dynamic_reports_diagnostic() {
+        let source = r#"const a = <div className={active ? "p-4" : "m-2"}>Hi</div>;"#;
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                output_mode: OutputMode::data_attribute(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(result.code.contains("active ? \"p-4\" : \"m-2\""));
+        assert_eq!(result.diagnostics.len(), 1);
+    }
+
+    // === Shadow DOM 输出模式测试 ===
+
+    #[test]
+    fn test_shadow_dom_mode() {
+        let source = r#"class MyCard extends LitElement {
+    render() {
+        return html`<slot></slot>`;
+    }
+}
+const view = <div className="p-4">Hi</div>;"#;
+        let result = transform_jsx(
+            source,
+            "src/MyCard.tsx",
+            TransformOptions {
+                output_mode: OutputMode::ShadowDom {
+                    format: StyleSheetFormat::Constructable,
+                    binding_name: "sheet".to_string(),
+                    import_path: None,
+                    adopt: Some("MyCard.styles = [{sheet}];".to_string()),
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(result.code.starts_with("import sheet from \"./MyCard.styles.js\";"));
+        assert!(result.code.trim_end().ends_with("MyCard.styles = [sheet];"));
+        assert!(result.code.contains("className=\"c_"));
+        let module = result.style_module.unwrap();
+        assert!(module.starts_with("const sheet = new CSSStyleSheet();"));
+        assert!(module.contains("padding: 1rem"));
+    }
+
+    #[test]
+    fn test_shadow_dom_reuses_import_binding() {
+        let source = r#"import styles from "./Card.styles.js";
+const view = <div className="p-4">Hi</div>;
+"#;
+        let result = transform_jsx(
+            source,
+            "Card.tsx",
+            TransformOptions {
+                output_mode: OutputMode::ShadowDom {
+                    format: StyleSheetFormat::StyleElement,
+                    binding_name: "sheet".to_string(),
+                    import_path: None,
+                    adopt: Some("root.innerHTML = {sheet};".to_string()),
+                },
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(result.code.matches("import ").count(), 1);
+        assert!(result.code.ends_with("\nroot.innerHTML = styles;\n"));
+        assert!(result.style_module.unwrap().starts_with("export default `<style>"));
+    }
+
+    #[test]
+    fn test_shadow_dom_binding_conflict() {
+        let options = || TransformOptions {
+            output_mode: OutputMode::ShadowDom {
+                format: StyleSheetFormat::Constructable,
+                binding_name: "sheet".to_string(),
+                import_path: None,
+                adopt: Some("Card.styles = [{sheet}];".to_string()),
+            },
+            ..Default::default()
+        };
+        // 没有类名时不注入 import 与 adopt 代码
+        let source = "import sheet from \"./theme.js\";\nexport const a = 1;\n";
+        let result = transform_jsx(source, "Card.tsx", options()).unwrap();
+        assert_eq!(result.code.trim_end(), source.trim_end());
+
+        let source = "import sheet from \"./theme.js\";\nconst view = <div className=\"p-4\">Hi</div>;\n";
+        let result = transform_jsx(source, "Card.tsx", options()).unwrap();
+        assert!(result.code.contains("import sheet$1 from \"./Card.styles.js\";"), "{}", result.code);
+        assert!(result.code.trim_end().ends_with("Card.styles = [sheet$1];"), "{}", result.code);
+    }
+
+    // === React Native 输出模式测试 ===
+
+    #[test]
+    fn test_react_native_mode() {
+        let source = r#"export function Card() {
+    return <View className="p-4 bg-blue-500 rounded-lg">Hi</View>;
+}"#;
+        let result = transform_jsx(
+            source,
+            "Card.tsx",
+            TransformOptions {
+                output_mode: OutputMode::react_native(),
+                naming_mode: NamingMode::CamelCase,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("p-4 bg-blue-500 rounded-lg").unwrap();
+        assert!(result.code.starts_with("import { StyleSheet } from \"react-native\";"));
+        assert!(result.code.contains(&format!("style={{styles.{}}}", name)), "{}", result.code);
+        assert!(!result.code.contains("className"));
+        assert!(result.code.contains("const styles = StyleSheet.create({"));
+        assert!(result.code.contains("    padding: 16,\n"));
+        assert!(result.code.contains("    backgroundColor: \"#2b7fff\",\n"));
+        assert!(result.code.contains("    borderRadius: 8,\n"));
+        assert!(result.css.is_empty());
+        assert!(result.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_react_native_binding_conflict() {
+        let options = || TransformOptions {
+            output_mode: OutputMode::react_native(),
+            ..Default::default()
+        };
+        // 不追加 StyleSheet.create 时已有的同名绑定不影响转换
+        let source = "import styles from \"./theme\";\nexport const a = 1;\n";
+        let result = transform_jsx(source, "Card.tsx", options()).unwrap();
+        assert_eq!(result.code.trim_end(), source.trim_end());
+
+        // 顶层声明同样占用绑定名
+        for preserve_formatting in [false, true] {
+            let source = "import styles from \"./theme\";\nconst styles$1 = {};\nexport const A = () => <View className=\"p-4\" />;\n";
+            let result = transform_jsx(source, "Card.tsx", TransformOptions {
+                preserve_formatting,
+                ..options()
+            })
+            .unwrap();
+            let name = &result.class_map["p-4"];
+            assert!(result.code.contains(&format!("style={{styles$2.{}}}", name)), "{}", result.code);
+            assert!(result.code.contains("const styles$2 = StyleSheet.create({"), "{}", result.code);
+        }
+    }
+
+    #[test]
+    fn test_react_native_reports_web_only_utilities() {
+        let source = "import { StyleSheet, View } from 'react-native';\nconst a = <View className='grid p-2 hover:p-4'/>;\n";
+        let result = transform_jsx(
+            source,
+            "A.tsx",
+            TransformOptions {
+                output_mode: OutputMode::react_native(),
+                preserve_formatting: true,
+                naming_mode: NamingMode::CamelCase,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let name = result.class_map.get("grid p-2 hover:p-4").unwrap();
+        assert!(result.code.starts_with("import { StyleSheet, View } from 'react-native';\n"));
+        assert!(result.code.contains(&format!("<View style={{styles.{}}}/>", name)));
+        assert!(result.code.contains("    padding: 8,\n"));
+        assert_eq!(result.diagnostics.len(), 2);
+    }
+
+    // === headwind-ignore 注释测试 ===
+
+    #[test]
+    fn test_ignore_comments_in_jsx() {
+        let source = r#"const a = (
+  <div className="p-4">
+    {/* headwind-ignore-next-line */}
+    <span className="m-2" />
+    {/* headwind-ignore */}
+    <section className="p-2">
+      <p className="p-8" />
+    </section>
+    <b className="m-4" />
+  </div>
+);
+"#;
+        let result = transform_jsx(
+            source,
+            "A.tsx",
+            TransformOptions {
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(result.code.contains(r#"<span className="m-2" />"#));
+        assert!(result.code.contains(r#"<section className="p-2">"#));
+        assert!(result.code.contains(r#"<p className="p-8" />"#));
+        assert!(!result.code.contains(r#"className="p-4""#));
+        assert!(!result.code.contains(r#"className="m-4""#));
+        assert_eq!(result.class_map.len(), 2);
+        assert_eq!(result.diagnostics.len(), 2);
+        assert!(result.diagnostics.iter().all(|d| d.level == DiagnosticLevel::Info));
+        assert!(result.diagnostics[0].message.contains("第 4 行的 <span>"));
+        assert!(result.diagnostics[1].message.contains("第 6 行的 <section> 及其子元素"));
+    }
+
+    #[test]
+    fn test_file_level_disable() {
+        let source = "/* headwind-disable */\nconst a = <div className=\"p-4\" />;\n";
+        let result = transform_jsx(source, "A.tsx", TransformOptions::default()).unwrap();
+        assert!(result.code.contains("className=\"p-4\""));
+        assert!(result.css.is_empty());
+        assert!(result.class_map.is_empty());
+    }
+
+    // === 变体组合测试 ===
+
+    #[test]
+    fn test_group_and_dark_variants_with_breakpoints() {
+        let source = r#"function Card() {
+    return <div className="group p-4">
+        <p className="md:group-hover:text-red-500 lg:dark:border-white">Hi</p>
+    </div>;
+}"#;
+        let result = transform_jsx(source, "Card.tsx", TransformOptions::default()).unwrap();
+
+        // group 标记类保留在 DOM 中
+        let card = &result.class_map["group p-4"];
+        assert!(result.code.contains(&format!("className=\"{}\"", card)), "{}", result.code);
+        assert!(card.ends_with(" group"));
+
+        let text = &result.class_map["md:group-hover:text-red-500 lg:dark:border-white"];
+        assert!(
+            result.css.contains(&format!("@media (width >= 48rem) {{\n  .group:hover .{} {{", text)),
+            "{}",
+            result.css
+        );
+        assert!(
+            result.css.contains(&format!(
+                "@media (width >= 64rem) {{\n  @media (prefers-color-scheme: dark) {{\n    .{} {{",
+                text
+            )),
+            "{}",
+            result.css
+        );
+    }
+
+    #[test]
+    fn test_named_group_and_peer_in_css_modules() {
+        let source = r#"function Field() {
+    return <label className="group/item peer">
+        <span className="peer-checked:md:underline md:group-hover/item:aria-selected:p-4 data-active:p-2">x</span>
+    </label>;
+}"#;
+        let result = transform_jsx(
+            source,
+            "Field.tsx",
+            TransformOptions {
+                output_mode: OutputMode::css_modules(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // 只有标记类的属性保持原样，不会变成 styles.xxx
+        assert!(result.code.contains(r#"className="group/item peer""#), "{}", result.code);
+        // CSS Module 中标记类包裹为 :global(...)，避免被改名
+        assert!(result.css.contains(":global(.peer):checked ~ ."), "{}", result.css);
+        assert!(
+            result.css.contains(r#":global(.group\/item):hover ."#),
+            "{}",
+            result.css
+        );
+        assert!(result.css.contains(r#"[aria-selected="true"]"#));
+        assert!(result.css.contains("[data-active] {"));
+        assert!(!result.css.contains(" .group"));
+    }
+
+    #[test]
+    fn test_legacy_px_breakpoints() {
+        let source = r#"<div className="p-2 md:p-4 max-lg:p-1 @sm:p-3" />;"#;
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                breakpoints: BreakpointStyle {
+                    unit: BreakpointUnit::Px,
+                    syntax: MediaQuerySyntax::Legacy,
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(result.css.contains("@media (min-width: 768px) {"), "{}", result.css);
+        assert!(result.css.contains("@media not all and (min-width: 1024px) {"), "{}", result.css);
+        assert!(result.css.contains("@container (min-width: 384px) {"), "{}", result.css);
+        assert!(!result.css.contains("width >="), "{}", result.css);
+    }
+
+    // === 配置文件测试 ===
+
+    #[test]
+    fn test_transform_file_with_discovered_config() {
+        let root = std::env::temp_dir().join(format!("headwind-transform-file-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("headwind.toml"),
+            "exclude = [\"**/*.stories.tsx\"]\n\n[outputMode]\ntype = \"cssModules\"\n",
+        )
+        .unwrap();
+        let file = root.join("src/App.tsx");
+        std::fs::write(&file, "const a = <div className=\"p-4\"/>;\n").unwrap();
+        let stories = root.join("src/App.stories.tsx");
+        std::fs::write(&stories, "const a = <div className=\"p-4\"/>;\n").unwrap();
+
+        let result = transform_file(&file, None, HeadwindConfig::default())
+            .unwrap()
+            .unwrap();
+        assert!(result.code.contains("./App.module.css"), "{}", result.code);
+        assert!(result.css.contains("padding: 1rem"));
+        assert!(transform_file(&stories, None, HeadwindConfig::default())
+            .unwrap()
+            .is_none());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    // === 回退索引测试 ===
+
+    #[test]
+    fn test_fallback_index_resolves_unknown_classes() {
+        let index = load_index(
+            r#"[{ "class": "scrollbar-thin", "css": "scrollbar-width: thin" }]"#,
+        )
+        .unwrap();
+        let source = r#"<div className="p-4 hover:scrollbar-thin" />;"#;
+
+        let without = transform_jsx(source, "App.tsx", TransformOptions::default()).unwrap();
+        assert!(!without.css.contains("scrollbar-width"), "{}", without.css);
+
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                fallback_index: Some(Arc::new(index)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(result.css.contains("padding: 1rem"), "{}", result.css);
+        assert!(result.css.contains(":hover {\n    scrollbar-width: thin;"), "{}", result.css);
+    }
+
+    #[test]
+    fn test_target_tailwind_version() {
+        let source = r#"<div className="shadow-sm rounded-sm" />;"#;
+
+        let v4 = transform_jsx(source, "App.tsx", TransformOptions::default()).unwrap();
+        assert!(v4.css.contains("box-shadow: var(--shadow-sm)"), "{}", v4.css);
+        assert!(v4.css.contains("border-radius: 0.25rem"), "{}", v4.css);
+
+        let v3 = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                target_tailwind_version: TailwindVersion::V3,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(v3.css.contains("box-shadow: 0 1px 2px 0 rgb(0 0 0 / 0.05)"), "{}", v3.css);
+        assert!(v3.css.contains("border-radius: 0.125rem"), "{}", v3.css);
+    }
+
+    // === typography prose 类测试 ===
+
+    #[test]
+    fn test_prose_classes_preserved_and_reported() {
+        let source = r#"<article className="prose dark:prose-invert prose-headings:underline p-4" />;"#;
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                naming_mode: NamingMode::Readable,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(
+            result
+                .code
+                .contains(r#"className="p4 prose dark:prose-invert prose-headings:underline""#),
+            "{}",
+            result.code
+        );
+        assert_eq!(result.diagnostics.len(), 3);
+        assert!(result.diagnostics.iter().all(|d| d.level == DiagnosticLevel::Info));
+
+        let unknown = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                naming_mode: NamingMode::Readable,
+                prose_classes: ProseClassMode::Unknown,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(!unknown.code.contains("prose-invert"), "{}", unknown.code);
+        // 按未知类处理时改为提示所需插件
+        assert_eq!(unknown.diagnostics.len(), 2);
+        assert!(unknown
+            .diagnostics
+            .iter()
+            .all(|d| d.level == DiagnosticLevel::Warning
+                && d.message.contains("@tailwindcss/typography")));
+    }
+
+    // === tw 宏测试 ===
+
+    #[test]
+    fn test_twin_macro_tw_prop_and_css_prop() {
+        let source = r#"import tw from "twin.macro";
+
+export const Card = () => <div css={tw`flex m-2`} className="shadow"><span css={tw`p-2`} /></div>;
+export const Title = () => <h1 tw="text-center">Hi</h1>;
+"#;
+        for preserve_formatting in [false, true] {
+            let result = transform_jsx(
+                source,
+                "Card.tsx",
+                TransformOptions {
+                    preserve_formatting,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            // 全部改写后不再需要宏，import 一并删除
+            assert!(!result.code.contains("twin.macro"), "{}", result.code);
+            assert!(!result.code.contains("tw"), "{}", result.code);
+            let merged = result.class_map.get("flex m-2 shadow").unwrap();
+            let span = result.class_map.get("p-2").unwrap();
+            let title = result.class_map.get("text-center").unwrap();
+            assert!(result.code.contains(&format!("<div className=\"{}\">", merged)), "{}", result.code);
+            assert!(result.code.contains("<span className={"), "{}", result.code);
+            assert!(result.code.contains(span.as_str()), "{}", result.code);
+            assert!(result.code.contains(&format!("<h1 className=\"{}\">", title)), "{}", result.code);
+        }
+    }
+
+    #[test]
+    fn test_twin_macro_styled_elements() {
+        let source = "import tw from \"twin.macro\";\nconst Button = tw.button`\n  p-4\n  text-white\n`;\nconst Link = tw(NextLink)`underline`;\n";
+        for preserve_formatting in [false, true] {
+            let result = transform_jsx(
+                source,
+                "Button.tsx",
+                TransformOptions {
+                    output_mode: OutputMode::css_modules(),
+                    preserve_formatting,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let code: String = result.code.split_whitespace().collect();
+            let button = result.class_map.get("p-4 text-white").unwrap();
+            let link = result.class_map.get("underline").unwrap();
+            assert!(!code.contains("twin.macro"), "{}", result.code);
+            assert!(
+                code.contains(&format!(
+                    "<button{{...props}}className={{[styles.{},props.className].filter(Boolean).join(",
+                    button
+                )),
+                "{}",
+                result.code
+            );
+            assert!(
+                code.contains(&format!("<NextLink{{...props}}className={{[styles.{},", link)),
+                "{}",
+                result.code
+            );
+        }
+    }
+
+    #[test]
+    fn test_twin_macro_keeps_style_object_templates() {
+        // 单独的 tw`...` 是样式对象，保持原样，import 随之保留
+        let source = "import tw from \"twin.macro\";\nconst card = tw`p-4`;\nexport const A = () => <div tw=\"m-2\" css={card} />;\n";
+        let result = transform_jsx(source, "A.tsx", TransformOptions::default()).unwrap();
+        assert!(result.code.contains("import tw from \"twin.macro\""), "{}", result.code);
+        assert!(result.code.contains("tw`p-4`"), "{}", result.code);
+        assert!(!result.class_map.contains_key("p-4"));
+        assert!(result.code.contains(&format!("className=\"{}\"", result.class_map["m-2"])));
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.level == DiagnosticLevel::Info && d.message.contains("样式对象")));
+    }
+
+    #[test]
+    fn test_tailwind_styled_components_css_modules() {
+        let source = "import tw from \"tailwind-styled-components\";\nexport const Button = tw.button`\n  p-4\n  text-white\n`;\nexport const Wrapper = tw(Box)`flex`;\n";
+        for preserve_formatting in [false, true] {
+            let result = transform_jsx(
+                source,
+                "Button.tsx",
+                TransformOptions {
+                    output_mode: OutputMode::css_modules(),
+                    preserve_formatting,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let button = result.class_map.get("p-4 text-white").unwrap();
+            let wrapper = result.class_map.get("flex").unwrap();
+            assert!(
+                result.code.contains(&format!("tw.button`${{styles.{}}}`", button)),
+                "{}",
+                result.code
+            );
+            assert!(
+                result.code.contains(&format!("tw(Box)`${{styles.{}}}`", wrapper)),
+                "{}",
+                result.code
+            );
+        }
+    }
+
+    #[test]
+    fn test_tw_ignored_without_macro_import() {
+        let source = "const tw = (s) => s;\nconst x = tw`p-4`;\nexport const A = () => <div tw=\"p-4\" />;\n";
+        let result = transform_jsx(source, "A.jsx", TransformOptions::default()).unwrap();
+        assert!(result.class_map.is_empty());
+        assert!(result.code.contains("tw`p-4`"), "{}", result.code);
+    }
+
+    // === 类名函数测试 ===
+
+    #[test]
+    fn test_class_functions_cva() {
+        let source = r#"import { cva } from "class-variance-authority";
+
+const button = cva("rounded px-4", {
+    variants: {
+        intent: { primary: "bg-blue-500 text-white", secondary: ["bg-white", "text-gray-900"] },
+        size: { sm: "text-sm", lg: "text-lg" },
+    },
+    compoundVariants: [{ intent: "primary", size: "lg", class: "uppercase" }],
+    defaultVariants: { intent: "primary", size: "sm" },
+});
+"#;
+        let result = transform_jsx(
+            source,
+            "button.ts",
+            TransformOptions {
+                class_functions: vec!["cva".into()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        for class in ["rounded px-4", "bg-blue-500 text-white", "bg-white", "text-gray-900", "text-sm", "text-lg", "uppercase"] {
+            let name = result.class_map.get(class).unwrap_or_else(|| panic!("{} 未处理", class));
+            assert!(result.code.contains(&format!("\"{}\"", name)), "{}", result.code);
+        }
+        // 变体取值与键名保持原样
+        assert!(result.code.contains("intent: \"primary\""), "{}", result.code);
+        assert!(result.code.contains("size: \"sm\""), "{}", result.code);
+        assert!(result.code.contains("primary:"), "{}", result.code);
+    }
+
+    #[test]
+    fn test_class_functions_object_keys_and_css_modules() {
+        let source = "const cls = clsx(\"p-4\", { \"font-bold\": active, custom: on });\nconst other = cn(\"m-2\");\n";
+        for preserve_formatting in [false, true] {
+            let result = transform_jsx(
+                source,
+                "cls.js",
+                TransformOptions {
+                    output_mode: OutputMode::css_modules(),
+                    class_functions: vec!["clsx".into()],
+                    preserve_formatting,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let p4 = result.class_map.get("p-4").unwrap();
+            let bold = result.class_map.get("font-bold").unwrap();
+            assert!(result.code.contains(&format!("clsx(styles.{}", p4)), "{}", result.code);
+            assert!(result.code.contains(&format!("[styles.{}]: active", bold)), "{}", result.code);
+            assert!(result.code.contains("custom: on"), "{}", result.code);
+            // 未配置的函数不处理
+            assert!(result.code.contains("cn(\"m-2\")"), "{}", result.code);
+        }
+    }
+
+    #[test]
+    fn test_tw_comment_marker() {
+        let source = "export const tokens = {\n    card: /* tw */ \"p-4 shadow\",\n    sizes: /*tw*/ [\"text-sm\", \"text-lg\"],\n    label: \"p-4\",\n};\n";
+        let result = transform_jsx(source, "tokens.ts", TransformOptions::default()).unwrap();
+        let card = result.class_map.get("p-4 shadow").unwrap();
+        assert!(result.code.contains(&format!("\"{}\"", card)), "{}", result.code);
+        assert!(result.class_map.contains_key("text-sm") && result.class_map.contains_key("text-lg"));
+        assert!(result.code.contains("label: \"p-4\""), "{}", result.code);
+    }
+
+    // === import 插入位置测试 ===
+
+    #[test]
+    fn test_import_after_use_client_directive() {
+        // Next.js app router 客户端组件
+        let source = r#""use client";
+
+import { useState } from "react";
+
+export default function Page() {
+    const [open] = useState(false);
+    return <main className="p-4">{open}</main>;
+}
+"#;
+        for preserve_formatting in [false, true] {
+            let result = transform_jsx(
+                source,
+                "app/page.tsx",
+                TransformOptions {
+                    output_mode: OutputMode::css_modules(),
+                    preserve_formatting,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert!(
+                result
+                    .code
+                    .starts_with("\"use client\";\nimport styles from \"./page.module.css\";\n"),
+                "{}",
+                result.code
+            );
+            assert!(result.code.contains(";\n\nimport { useState }"), "{}", result.code);
+        }
+    }
+
+    #[test]
+    fn test_import_below_file_header_comment() {
+        let source = r#"/**
+ * @license MIT
+ */
+
+// eslint-disable-next-line import/no-unresolved
+import Link from "next/link";
+
+export const Nav = () => <Link className="p-4" href="/">Home</Link>;
+"#;
+        for preserve_formatting in [false, true] {
+            let result = transform_jsx(
+                source,
+                "Nav.tsx",
+                TransformOptions {
+                    output_mode: OutputMode::Global {
+                        import_path: Some("./nav.css".into()),
+                    },
+                    preserve_formatting,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let position = |needle: &str| result.code.find(needle).unwrap();
+            assert!(position("@license") < position("import \"./nav.css\""), "{}", result.code);
+            assert!(position("import \"./nav.css\"") < position("// eslint"), "{}", result.code);
+            assert!(position("// eslint") < position("import Link"), "{}", result.code);
+            if preserve_formatting {
+                assert!(result.code.starts_with("/**\n * @license MIT\n */\n\nimport \"./nav.css\";\n"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_import_after_shebang_and_server_directive() {
+        let source = "#!/usr/bin/env node\n'use server';\nrender(<Box className=\"p-4\" />);\n";
+        let result = transform_jsx(
+            source,
+            "cli.jsx",
+            TransformOptions {
+                output_mode: OutputMode::Global {
+                    import_path: Some("./cli.css".into()),
+                },
+                preserve_formatting: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(
+            result
+                .code
+                .starts_with("#!/usr/bin/env node\n'use server';\nimport \"./cli.css\";\nrender("),
+            "{}",
+            result.code
+        );
+    }
+
+    #[test]
+    fn test_import_options() {
+        let source = r#"// @ts-check
+import React from "react";
+import { Card } from "./Card";
+
+export const App = () => <Card className="p-4" />;
+"#;
+        for preserve_formatting in [false, true] {
+            let result = transform_jsx(
+                source,
+                "src/App.tsx",
+                TransformOptions {
+                    output_mode: OutputMode::css_modules(),
+                    emit: EmitOptions {
+                        quote_style: QuoteStyle::Double,
+                        ..Default::default()
+                    },
+                    imports: ImportOptions {
+                        quote_style: Some(QuoteStyle::Single),
+                        query: ImportQuery::Inline,
+                        css_module_extension: CssModuleExtension::Scss,
+                        position: ImportPosition::AfterImports,
+                        ..Default::default()
+                    },
+                    preserve_formatting,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let import = "import styles from './App.module.scss?inline';";
+            let position = |needle: &str| result.code.find(needle).unwrap();
+            assert!(position("import { Card }") < position(import), "{}", result.code);
+            assert!(position(import) < position("export const App"), "{}", result.code);
+            if preserve_formatting {
+                assert!(
+                    result.code.contains(
+                        "from \"./Card\";\nimport styles from './App.module.scss?inline';\n\nexport"
+                    ),
+                    "{}",
+                    result.code
+                );
+            }
+        }
+
+        // 已有同路径（含后缀）的 import 时复用绑定，不再注入；没有 import 时仍插在顶部
+        let existing = r#"import css from "./App.module.scss?inline";
+export const App = () => <p className="p-4" />;
+"#;
+        let options = || TransformOptions {
+            output_mode: OutputMode::css_modules(),
+            imports: ImportOptions {
+                query: ImportQuery::Inline,
+                css_module_extension: CssModuleExtension::Scss,
+                position: ImportPosition::AfterImports,
+                ..Default::default()
+            },
+            preserve_formatting: true,
+            ..Default::default()
+        };
+        let result = transform_jsx(existing, "App.tsx", options()).unwrap();
+        assert_eq!(result.code.matches("import ").count(), 1, "{}", result.code);
+        assert!(result.code.contains("className={css."), "{}", result.code);
+        let source = "'use client';\nexport const A = () => <p className=\"m-2\" />;\n";
+        let result = transform_jsx(source, "A.tsx", options()).unwrap();
+        assert!(
+            result
+                .code
+                .starts_with("'use client';\nimport styles from \"./A.module.scss?inline\";\n"),
+            "{}",
+            result.code
+        );
+    }
+
+    #[test]
+    fn test_import_aliases() {
+        let aliases: IndexMap<String, String> = [
+            ("@/*".to_string(), "./src/*".to_string()),
+            ("@styles/*".to_string(), "src/styles/*".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let imports = ImportOptions {
+            aliases,
+            ..Default::default()
+        };
+        let source = "export const App = () => <div className=\"p-4\" />;\n";
+
+        // 最长的目标目录胜出；d.ts 路径仍相对源文件
+        for preserve_formatting in [false, true] {
+            let options = TransformOptions {
+                output_mode: OutputMode::css_modules(),
+                imports: imports.clone(),
+                css_module_dts: true,
+                preserve_formatting,
+                ..Default::default()
+            };
+            let result = transform_jsx(source, "src/styles/App.tsx", options).unwrap();
+            assert!(
+                result.code.contains("import styles from \"@styles/App.module.css\";"),
+                "{}",
+                result.code
+            );
+            assert_eq!(result.dts.unwrap().0, "./App.module.css.d.ts");
+        }
+
+        // 显式的 Global import_path 同样按别名改写；不在目标目录下的路径保持相对
+        let global = |filename: &str| {
+            let options = TransformOptions {
+                output_mode: OutputMode::Global {
+                    import_path: Some("../theme.css".to_string()),
+                },
+                imports: imports.clone(),
+                ..Default::default()
+            };
+            transform_jsx(source, filename, options).unwrap().code
+        };
+        assert!(global("src/pages/Home.tsx").contains("import \"@/theme.css\";"));
+        assert!(global("pages/Home.tsx").contains("import \"../theme.css\";"));
+    }
+
+    #[test]
+    fn test_content_hash_file_name() {
+        let imports = ImportOptions {
+            file_name: Some("[name].[contenthash].module.css".to_string()),
+            ..Default::default()
+        };
+        let transform = |source: &str, preserve_formatting: bool| {
+            let options = TransformOptions {
+                output_mode: OutputMode::css_modules(),
+                imports: imports.clone(),
+                css_module_dts: true,
+                preserve_formatting,
+                ..Default::default()
+            };
+            transform_jsx(source, "src/App.tsx", options).unwrap()
+        };
+        let source = "export const App = () => <div className=\"p-4\" />;\n";
+
+        for preserve_formatting in [false, true] {
+            let result = transform(source, preserve_formatting);
+            let hash = result.css_hash.clone().unwrap();
+            assert_eq!(hash.len(), asset::DEFAULT_HASH_LENGTH);
+            let (expected, _) = asset::apply_content_hash("[contenthash]", &result.css);
+            assert_eq!(hash, expected);
+            let path = format!("./App.{}.module.css", hash);
+            assert_eq!(result.style_path.as_deref(), Some(path.as_str()));
+            assert!(
+                result.code.contains(&format!("import styles from \"{}\";", path)),
+                "{}",
+                result.code
+            );
+            assert_eq!(result.dts.unwrap().0, format!("{}.d.ts", path));
+        }
+
+        // CSS 变化时哈希随之变化；没有生成的类时不给出路径
+        let other = transform("export const App = () => <div className=\"p-8\" />;\n", false);
+        assert_ne!(other.css_hash, transform(source, false).css_hash);
+        let empty = transform("export const App = () => <div />;\n", false);
+        assert!(empty.style_path.is_none() && empty.css_hash.is_none());
+
+        // 显式的 import_path 同样替换占位符；不含 [contenthash] 时没有哈希
+        let options = TransformOptions {
+            output_mode: OutputMode::Global {
+                import_path: Some("./[name].[contenthash:12].css".to_string()),
+            },
+            ..Default::default()
+        };
+        let result = transform_jsx(source, "App.tsx", options).unwrap();
+        assert_eq!(result.css_hash.as_ref().map(String::len), Some(12));
+        let options = TransformOptions {
+            output_mode: OutputMode::css_modules(),
+            ..Default::default()
+        };
+        let result = transform_jsx(source, "App.tsx", options).unwrap();
+        assert_eq!(result.style_path.as_deref(), Some("./App.module.css"));
+        assert!(result.css_hash.is_none());
+    }
+
+    // === 语法选项测试 ===
+
+    #[test]
+    fn test_module_extensions_and_syntax_options() {
+        let ts = "export const size: number = 4;\nexport const cls = \"p-4\";\n";
+        assert!(transform_jsx(ts, "tokens.mts", TransformOptions::default()).is_ok());
+        assert!(transform_jsx(ts, "tokens.cts", TransformOptions::default()).is_ok());
+
+        let decorated = "@customElement(\"x-card\")\nexport class Card {\n  render() {\n    return <div className=\"p-4\" />;\n  }\n}\n";
+        let attributes = "import data from \"./data.json\" with { type: \"json\" };\nexport const App = () => <div className=\"p-4\">{data.title}</div>;\n";
+        assert!(transform_jsx(decorated, "Card.tsx", TransformOptions::default()).is_err());
+
+        let options = || TransformOptions {
+            syntax: SyntaxOptions {
+                decorators: true,
+                import_attributes: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = transform_jsx(decorated, "Card.tsx", options()).unwrap();
+        assert!(result.code.contains("@customElement"), "{}", result.code);
+        assert!(result.css.contains("padding"));
+        let result = transform_jsx(attributes, "App.mjs", options()).unwrap();
+        assert!(result.code.contains("with"), "{}", result.code);
+        assert!(result.css.contains("padding"));
+    }
+
+    #[test]
+    fn test_jsx_pragma_in_ts_file() {
+        let source = "/** @jsxImportSource preact */\nexport const App = () => <div class=\"p-4\" />;\n";
+        assert!(transform_jsx(source, "App.ts", TransformOptions::default()).is_err());
+
+        let options = TransformOptions {
+            syntax: SyntaxOptions {
+                jsx_pragma: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = transform_jsx(source, "App.ts", options).unwrap();
+        assert!(!result.code.contains("\"p-4\""), "{}", result.code);
+    }
+
+    // === 容错解析测试 ===
+
+    #[test]
+    fn test_recover_parse_errors_transforms_rest_of_file() {
+        // 模块默认严格模式，旧式八进制字面量是可恢复的语法错误
+        let source = "const mode = 010;\nexport const App = () => <div className=\"p-4\">Hi</div>;\n";
+        assert!(transform_jsx(source, "App.jsx", TransformOptions::default()).is_err());
+
+        let result = transform_jsx(
+            source,
+            "App.jsx",
+            TransformOptions {
+                recover_parse_errors: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        // 只替换类名区间，其余源码保持原样
+        assert!(result.code.starts_with("const mode = 010;\n"), "{}", result.code);
+        assert!(!result.code.contains("\"p-4\""), "{}", result.code);
+        assert!(result.css.contains("padding"));
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.level == DiagnosticLevel::Warning && d.message.starts_with("第 1 行解析错误")));
+    }
+
+    #[test]
+    fn test_recover_parse_errors_returns_source_on_fatal_error() {
+        let source = "export const App = () => <div className=\"p-4\">;\n";
+        let result = transform_jsx(
+            source,
+            "App.tsx",
+            TransformOptions {
+                recover_parse_errors: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(result.code, source);
+        assert!(result.css.is_empty() && result.class_map.is_empty());
+        assert_eq!(result.diagnostics.last().unwrap().level, DiagnosticLevel::Error);
+    }
+
+    // === 属性测试 ===
+
+    /// 由代码行与空行组成、以非空行结尾的源码（`lines()` 不保留末尾空行）
+    fn source_with_blank_lines() -> impl proptest::strategy::Strategy<Value = String> {
+        use proptest::prelude::*;
+        let line = prop_oneof!["", "[ \\t]{0,4}[a-z{}();=<>/\"][a-z0-9 {}();=<>/\"]{0,20}"];
+        (prop::collection::vec(line, 0..12), "[a-z][a-z0-9 ;]{0,10}").prop_map(|(mut lines, last)| {
+            lines.push(last);
+            lines.join("\n")
+        })
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_empty_line_markers_roundtrip(source in source_with_blank_lines()) {
+            let preserved = preserve_empty_lines(&source);
+            proptest::prop_assert!(preserved.lines().all(|line| !line.is_empty()));
+            proptest::prop_assert_eq!(restore_empty_lines(&preserved), source);
+        }
+
+        #[test]
+        fn prop_transform_jsx_never_panics(classes in "[^\"{}<>]{0,40}") {
+            let source = format!("const A = () => <div className=\"{}\">x</div>;\n", classes);
+            let _ = transform_jsx(&source, "A.tsx", TransformOptions::default());
+            let _ = transform_html(&format!("<div class=\"{}\"></div>", classes), TransformOptions::default());
+        }
+    }
 }
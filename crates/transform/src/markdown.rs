@@ -0,0 +1,255 @@
+//! Markdown / MDX 中的 HTML
+//!
+//! 文档站点的 `.md` / `.mdx` 常直接写 HTML 块（`<div class="grid gap-4">`）或行内 HTML。
+//! 转换时只改写这些 HTML 的 `class` 属性，Markdown 正文原样保留；front matter、围栏代码块、
+//! 缩进代码块（MDX 没有缩进代码块）与行内代码中的示例不改写。整篇文档共用一个收集器，
+//! 相同的类组合得到同一个生成名。
+
+use crate::collector::ClassCollector;
+use crate::html::rewrite_class_attrs;
+use std::ops::Range;
+
+/// 改写 Markdown 中 HTML 的 `class` 属性，代码块与行内代码保持原样
+pub fn transform_markdown_source(source: &str, collector: &mut ClassCollector, mdx: bool) -> String {
+    rewrite_class_attrs(source, collector, None, &code_ranges(source, mdx))
+}
+
+/// DataAttribute 模式，见 `html::transform_html_source_to_data_attr`
+pub fn transform_markdown_source_to_data_attr(
+    source: &str,
+    collector: &mut ClassCollector,
+    data_attr: &str,
+    mdx: bool,
+) -> String {
+    rewrite_class_attrs(source, collector, Some(data_attr), &code_ranges(source, mdx))
+}
+
+/// 去掉代码区间后的内容，用于构建元素树
+pub(crate) fn html_content(source: &str, mdx: bool) -> String {
+    let mut content = String::with_capacity(source.len());
+    let mut pos = 0;
+    for range in code_ranges(source, mdx) {
+        content.push_str(&source[pos..range.start]);
+        pos = range.end;
+    }
+    content.push_str(&source[pos..]);
+    content
+}
+
+/// 不应改写的区间（按起始位置排列，互不重叠）：front matter、围栏代码块、缩进代码块与行内代码
+///
+/// HTML 块从以 `<` 开头的行开始、到空行结束，其中的缩进行与反引号不按代码处理。
+pub(crate) fn code_ranges(source: &str, mdx: bool) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut offset = front_matter_end(source);
+    if offset > 0 {
+        ranges.push(0..offset);
+    }
+    // 未闭合的围栏：（起始位置，围栏字符，长度）
+    let mut fence: Option<(usize, u8, usize)> = None;
+    let mut indented: Option<Range<usize>> = None;
+    let mut prev_blank = true;
+    let mut in_html = false;
+
+    for line in source[offset..].split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let text = line.trim_end_matches(['\n', '\r']);
+        if let Some((fence_start, marker, len)) = fence {
+            if is_fence_close(text, marker, len) {
+                ranges.push(fence_start..offset);
+                fence = None;
+            }
+            continue;
+        }
+
+        let blank = text.trim().is_empty();
+        let (indent, rest) = split_indent(text);
+        if !mdx && !in_html && !blank && indent >= 4 && (prev_blank || indented.is_some()) {
+            match &mut indented {
+                Some(range) => range.end = offset,
+                None => indented = Some(start..offset),
+            }
+            continue;
+        }
+        if blank {
+            // 缩进代码块可以包含空行，遇到下一个非缩进行才结束
+            prev_blank = true;
+            in_html = false;
+            continue;
+        }
+        if let Some(range) = indented.take() {
+            ranges.push(range);
+        }
+        prev_blank = false;
+        if indent < 4 {
+            if let Some((marker, len)) = fence_open(rest) {
+                fence = Some((start, marker, len));
+                continue;
+            }
+            if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!')
+            {
+                in_html = true;
+            }
+        }
+        if !in_html {
+            push_code_spans(text, start, &mut ranges);
+        }
+    }
+
+    if let Some((fence_start, _, _)) = fence {
+        ranges.push(fence_start..source.len());
+    }
+    if let Some(range) = indented {
+        ranges.push(range);
+    }
+    ranges
+}
+
+/// 以 `---` 开头、到下一个 `---` 行结束的 front matter 的结束位置，没有时为 0
+fn front_matter_end(source: &str) -> usize {
+    let Some(body) = source
+        .strip_prefix("---\n")
+        .or_else(|| source.strip_prefix("---\r\n"))
+    else {
+        return 0;
+    };
+    let mut offset = source.len() - body.len();
+    for line in body.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end() == "---" {
+            return offset;
+        }
+    }
+    0
+}
+
+/// 行首缩进宽度（制表符按 4 计）与其余部分
+fn split_indent(line: &str) -> (usize, &str) {
+    let mut width = 0;
+    for (i, c) in line.char_indices() {
+        match c {
+            ' ' => width += 1,
+            '\t' => width += 4 - width % 4,
+            _ => return (width, &line[i..]),
+        }
+    }
+    (width, "")
+}
+
+/// 围栏开头：至少 3 个 `` ` `` 或 `~`；反引号围栏的信息串不能含反引号
+fn fence_open(rest: &str) -> Option<(u8, usize)> {
+    let marker = *rest.as_bytes().first().filter(|b| matches!(b, b'`' | b'~'))?;
+    let len = rest.bytes().take_while(|&b| b == marker).count();
+    if len < 3 || (marker == b'`' && rest[len..].contains('`')) {
+        return None;
+    }
+    Some((marker, len))
+}
+
+/// 围栏结尾：缩进不超过 3，至少与开头一样长，其后只有空白
+fn is_fence_close(text: &str, marker: u8, len: usize) -> bool {
+    let (indent, rest) = split_indent(text);
+    let count = rest.bytes().take_while(|&b| b == marker).count();
+    indent < 4 && count >= len && rest[count..].trim().is_empty()
+}
+
+/// 一行中的行内代码：n 个反引号开始，到下一处恰好 n 个反引号结束；没有结束的反引号按普通字符处理
+fn push_code_spans(text: &str, base: usize, ranges: &mut Vec<Range<usize>>) {
+    let bytes = text.as_bytes();
+    let run_at = |i: usize| bytes[i..].iter().take_while(|&&b| b == b'`').count();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'`' {
+            i += 1;
+            continue;
+        }
+        let open = run_at(i);
+        let mut j = i + open;
+        let mut close = None;
+        while j < bytes.len() {
+            if bytes[j] != b'`' {
+                j += 1;
+                continue;
+            }
+            let run = run_at(j);
+            if run == open {
+                close = Some(j + run);
+                break;
+            }
+            j += run;
+        }
+        match close {
+            Some(end) => {
+                ranges.push(base + i..base + end);
+                i = end;
+            }
+            None => i += open,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use headwind_core::{ColorMode, CssVariableMode, NamingMode, UnknownClassMode};
+
+    const DOC: &str = r#"---
+title: "<b class="p-4">"
+---
+# Cards
+
+Use `<div class="p-4">` or the grid:
+
+<div class="grid gap-4">
+    <p class="p-4">indented inside HTML</p>
+</div>
+
+    <div class="p-4">indented code</div>
+
+```html
+<div class="p-4">fenced</div>
+```
+
+Inline <span class="p-4">html</span> text.
+"#;
+
+    #[test]
+    fn test_markdown_rewrites_html_outside_code() {
+        let mut collector = ClassCollector::new(NamingMode::Hash, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false);
+        let result = transform_markdown_source(DOC, &mut collector, false);
+        let name = collector.class_map()["p-4"].clone();
+
+        assert_eq!(collector.class_map().len(), 2);
+        assert!(result.contains(&format!(r#"<div class="{}">"#, collector.class_map()["grid gap-4"])));
+        assert!(result.contains(&format!(r#"<p class="{}">indented inside HTML"#, name)));
+        assert!(result.contains(&format!(r#"Inline <span class="{}">html"#, name)));
+        for example in [
+            r#"title: "<b class="p-4">""#,
+            r#"`<div class="p-4">`"#,
+            r#"    <div class="p-4">indented code"#,
+            r#"<div class="p-4">fenced"#,
+        ] {
+            assert!(result.contains(example), "{}", example);
+        }
+
+        // MDX 没有缩进代码块
+        let mut mdx = ClassCollector::new(NamingMode::Hash, CssVariableMode::Var, UnknownClassMode::Remove, ColorMode::default(), false);
+        let result = transform_markdown_source(DOC, &mut mdx, true);
+        assert!(result.contains(&format!(r#"    <div class="{}">indented code"#, name)));
+    }
+
+    #[test]
+    fn test_code_ranges_edge_cases() {
+        // 更长的结束围栏也能闭合，未闭合的围栏延续到结尾
+        let source = "~~~~\n<i class=\"a\">\n~~~~~\ntext ``a ` b`` and `c\n```\nopen";
+        let ranges: Vec<&str> = code_ranges(source, false)
+            .into_iter()
+            .map(|range| &source[range])
+            .collect();
+        assert_eq!(ranges, ["~~~~\n<i class=\"a\">\n~~~~~\n", "``a ` b``", "```\nopen"]);
+        assert_eq!(front_matter_end("---\nunclosed"), 0);
+        assert_eq!(html_content("a `b` c", false), "a  c");
+    }
+}
@@ -0,0 +1,146 @@
+//! 多文件输出合并
+//!
+//! 并行或乱序转换多个文件时，结果到达的顺序取决于调度。合并时先按文件路径排序，
+//! 再按文件内的出现顺序处理：类名映射与 CSS 规则块都保留首次出现的一份，
+//! 各文件的 `:root` 合并为一个块并按变量名排序。因此同一组文件无论以何种顺序转换，
+//! 合并结果都逐字节相同，CI 产物的 diff 保持干净。
+
+use crate::css_module::{normalize_block, split_top_level_blocks};
+use crate::TransformResult;
+use indexmap::IndexMap;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// 合并后的类名映射与 CSS
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergedOutput {
+    /// 原始类字符串 → 生成的类名，按（文件路径，文件内出现顺序）排列
+    ///
+    /// 同一类字符串在不同文件中映射不同（如 PerFile 命名）时保留路径最靠前的文件的映射
+    pub class_map: IndexMap<String, String>,
+    /// 合并后的 CSS：`:root` 在最前，其余规则块去重后以空行分隔
+    pub css: String,
+}
+
+/// 按文件路径顺序合并多个文件的转换结果，与传入顺序无关
+///
+/// ```
+/// use headwind_transform::{merge_outputs, transform_html, TransformOptions};
+/// use std::path::Path;
+///
+/// let a = transform_html(r#"<p class="p-4">a</p>"#, TransformOptions::default()).unwrap();
+/// let b = transform_html(r#"<p class="m-2">b</p>"#, TransformOptions::default()).unwrap();
+/// let merged = merge_outputs([(Path::new("b.html"), &b), (Path::new("a.html"), &a)]);
+/// assert_eq!(merged.class_map.keys().collect::<Vec<_>>(), ["p-4", "m-2"]);
+/// ```
+pub fn merge_outputs<'a>(
+    files: impl IntoIterator<Item = (&'a Path, &'a TransformResult)>,
+) -> MergedOutput {
+    let mut files: Vec<(&Path, &TransformResult)> = files.into_iter().collect();
+    files.sort_by_key(|(path, _)| *path);
+
+    let mut class_map = IndexMap::new();
+    let mut root: BTreeMap<String, String> = BTreeMap::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut blocks: Vec<&str> = Vec::new();
+    for (_, result) in &files {
+        for (original, generated) in &result.class_map {
+            class_map
+                .entry(original.clone())
+                .or_insert_with(|| generated.clone());
+        }
+        for block in split_top_level_blocks(&result.css) {
+            match root_declarations(block) {
+                Some(declarations) => {
+                    for (name, value) in declarations {
+                        root.entry(name.to_string())
+                            .or_insert_with(|| value.to_string());
+                    }
+                }
+                None => {
+                    if seen.insert(normalize_block(block)) {
+                        blocks.push(block);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut css = String::new();
+    if !root.is_empty() {
+        css.push_str(":root {\n");
+        for (name, value) in &root {
+            css.push_str(&format!("  {}: {};\n", name, value));
+        }
+        css.push('}');
+    }
+    for block in blocks {
+        if !css.is_empty() {
+            css.push_str("\n\n");
+        }
+        css.push_str(block);
+    }
+    if !css.is_empty() {
+        css.push('\n');
+    }
+    MergedOutput { class_map, css }
+}
+
+/// `:root { ... }` 块中的声明（变量名，值）；不是 `:root` 块时返回 None
+fn root_declarations(block: &str) -> Option<Vec<(&str, &str)>> {
+    let (prelude, body) = block.split_once('{')?;
+    if prelude.trim() != ":root" {
+        return None;
+    }
+    let body = body.trim_end().strip_suffix('}')?;
+    Some(
+        body.split(';')
+            .filter_map(|declaration| declaration.split_once(':'))
+            .map(|(name, value)| (name.trim(), value.trim()))
+            .filter(|(name, _)| !name.is_empty())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transform_html, TransformOptions};
+
+    #[test]
+    fn test_merge_is_independent_of_input_order() {
+        let transform =
+            |source: &str| transform_html(source, TransformOptions::default()).unwrap();
+        let a = transform(r#"<p class="p-4 text-xl">a</p><p class="shadow-md">a</p>"#);
+        let b = transform(r#"<p class="m-2 text-lg">b</p><p class="p-4 text-xl">b</p>"#);
+        let (path_a, path_b) = (Path::new("src/a.html"), Path::new("src/b.html"));
+
+        let forward = merge_outputs([(path_a, &a), (path_b, &b)]);
+        let backward = merge_outputs([(path_b, &b), (path_a, &a)]);
+        assert_eq!(forward, backward);
+
+        assert_eq!(
+            forward.class_map.keys().collect::<Vec<_>>(),
+            ["p-4 text-xl", "shadow-md", "m-2 text-lg"]
+        );
+        // 两个文件的 `:root` 合并为一个，变量按名称排序，共有规则只出现一次
+        let css = &forward.css;
+        assert!(css.starts_with(":root {\n"), "{}", css);
+        assert_eq!(css.matches(":root").count(), 1, "{}", css);
+        let text_lg = css.find("--text-lg:").unwrap();
+        let text_xl = css.find("--text-xl:").unwrap();
+        assert!(css.find("--shadow-md:").unwrap() < text_lg && text_lg < text_xl, "{}", css);
+        let name = &forward.class_map["p-4 text-xl"];
+        assert_eq!(css.matches(&format!(".{} {{", name)).count(), 1, "{}", css);
+        assert!(css.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_root_declarations() {
+        assert_eq!(
+            root_declarations(":root {\n  --a: 1px;\n  --b: url(x:y);\n}"),
+            Some(vec![("--a", "1px"), ("--b", "url(x:y)")])
+        );
+        assert_eq!(root_declarations(".c_1 { color: red; }"), None);
+    }
+}
@@ -0,0 +1,134 @@
+//! 生成名的空间占用与冲突统计
+//!
+//! Hash 命名把类组合映射到 `16^hash_length` 个名称之一，类组合越多，两组不同的类得到同一名称的
+//! 概率越高。收集器记录每个生成名对应的类组合：发现冲突时 Hash 命名逐步加长后来者的 hash，
+//! 其他命名模式加盐后缀，并把冲突记入 [`NamingStats`]。会话可汇总各文件的统计，
+//! 同时检查跨文件的冲突（各文件独立命名，跨文件冲突只报告、不自动消解）。
+
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// 一次生成名冲突
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameCollision {
+    /// 冲突的生成名
+    pub name: String,
+    /// 先占用该名称的类组合
+    pub existing: String,
+    /// 后来得到同一名称的类组合
+    pub classes: String,
+    /// 后者最终使用的名称；跨文件冲突无法消解时为 None
+    pub resolved: Option<String>,
+}
+
+/// 生成名的空间占用与冲突统计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NamingStats {
+    /// 类组合（排序去重后以空格连接）→ 生成名，按分配顺序；合并多个文件时跨文件冲突的类组合各占一项
+    pub names: IndexMap<String, String>,
+    /// Hash 命名的最短 hash 位数；其他命名模式为 None
+    pub hash_length: Option<usize>,
+    /// 观察到的冲突，按发生顺序
+    pub collisions: Vec<NameCollision>,
+}
+
+impl NamingStats {
+    /// hash 空间占用率：类组合数量 / `16^hash_length`；非 Hash 命名返回 None
+    pub fn space_usage(&self) -> Option<f64> {
+        let space = 16f64.powi(self.hash_length? as i32);
+        Some(self.names.len() as f64 / space)
+    }
+
+    /// 按生日问题估算当前数量的类组合中至少出现一次冲突的概率；非 Hash 命名返回 None
+    ///
+    /// ```
+    /// use headwind_transform::NamingStats;
+    ///
+    /// let stats = NamingStats {
+    ///     names: (0..10_000).map(|i| (i.to_string(), format!("c_{}", i))).collect(),
+    ///     hash_length: Some(12),
+    ///     ..Default::default()
+    /// };
+    /// // 1 万个类组合、12 位 hash：约 1.8e-7
+    /// assert!(stats.collision_probability().unwrap() < 1e-6);
+    /// ```
+    pub fn collision_probability(&self) -> Option<f64> {
+        let space = 16f64.powi(self.hash_length? as i32);
+        let n = self.names.len() as f64;
+        Some(-(-n * (n - 1.0) / (2.0 * space)).exp_m1())
+    }
+
+    /// 合并另一个文件的统计：类组合取并集，同名但类组合不同的记为跨文件冲突，`hash_length` 取较短者
+    pub fn merge(&mut self, other: &NamingStats) {
+        // 生成名 → 先占用它的类组合
+        let mut owners: HashMap<String, String> = self
+            .names
+            .iter()
+            .map(|(classes, name)| (name.clone(), classes.clone()))
+            .collect();
+        for (classes, name) in &other.names {
+            if self.names.contains_key(classes) {
+                continue;
+            }
+            match owners.get(name) {
+                Some(existing) => self.collisions.push(NameCollision {
+                    name: name.clone(),
+                    existing: existing.clone(),
+                    classes: classes.clone(),
+                    resolved: None,
+                }),
+                None => {
+                    owners.insert(name.clone(), classes.clone());
+                }
+            }
+            self.names.insert(classes.clone(), name.clone());
+        }
+        self.collisions.extend(other.collisions.iter().cloned());
+        self.hash_length = match (self.hash_length, other.hash_length) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+/// 类组合的规范形式：排序去重后以空格连接，书写顺序不同的同一组类视为相同
+pub(crate) fn class_set_key(classes: &[String]) -> String {
+    let mut set: Vec<&str> = classes.iter().map(String::as_str).collect();
+    set.sort_unstable();
+    set.dedup();
+    set.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_reports_cross_file_collisions() {
+        let stats = |names: &[(&str, &str)], hash_length| NamingStats {
+            names: names
+                .iter()
+                .map(|(name, classes)| (classes.to_string(), name.to_string()))
+                .collect(),
+            hash_length,
+            collisions: Vec::new(),
+        };
+        let mut merged = stats(&[("c_1", "p-4"), ("c_2", "m-2")], Some(12));
+        merged.merge(&stats(&[("c_1", "p-4"), ("c_2", "m-4"), ("c_3", "flex")], Some(8)));
+        // 冲突的两组类都计入
+        assert_eq!(merged.names.len(), 4);
+        assert_eq!(merged.names["m-4"], "c_2");
+        assert_eq!(merged.hash_length, Some(8));
+        assert_eq!(
+            merged.collisions,
+            [NameCollision {
+                name: "c_2".to_string(),
+                existing: "m-2".to_string(),
+                classes: "m-4".to_string(),
+                resolved: None,
+            }]
+        );
+        assert_eq!(merged.space_usage(), Some(4.0 / 16f64.powi(8)));
+        assert_eq!(stats(&[], None).collision_probability(), None);
+    }
+}
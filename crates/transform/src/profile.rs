@@ -0,0 +1,109 @@
+//! 热路径计时（`tracing` feature）
+//!
+//! 开启 `tracing` feature 后，解析、遍历、类名转换、代码输出四个阶段各进入一个 tracing span，
+//! 耗时同时记入 `TransformResult.timings`，便于定位大文件的耗时分布。
+//! 未开启时 [`PhaseTimer`] 是空类型，计时调用全部为空操作。
+
+use std::time::Duration;
+
+/// 各阶段耗时（开启 `tracing` feature 时才会记录）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    /// 源码解析
+    pub parse: Duration,
+    /// 遍历并替换类名（不含其中的类名转换）
+    pub visit: Duration,
+    /// 类名转换与 CSS 生成，累计所有类字符串
+    pub convert: Duration,
+    /// 代码输出（含格式化钩子）
+    pub emit: Duration,
+}
+
+/// 计时的阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "ecmascript"), allow(dead_code))]
+pub(crate) enum Phase {
+    Parse,
+    Visit,
+    Convert,
+    Emit,
+}
+
+impl Timings {
+    #[cfg(feature = "tracing")]
+    fn slot(&mut self, phase: Phase) -> &mut Duration {
+        match phase {
+            Phase::Parse => &mut self.parse,
+            Phase::Visit => &mut self.visit,
+            Phase::Convert => &mut self.convert,
+            Phase::Emit => &mut self.emit,
+        }
+    }
+}
+
+/// 单个阶段的计时器：创建时进入 span，`stop` 时退出并累计耗时
+pub(crate) struct PhaseTimer {
+    #[cfg(feature = "tracing")]
+    phase: Phase,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl PhaseTimer {
+    #[cfg(feature = "tracing")]
+    pub(crate) fn start(phase: Phase) -> Self {
+        // 类名转换按类字符串逐个进入，使用 trace 级别，避免默认订阅时输出过多
+        let span = match phase {
+            Phase::Parse => tracing::debug_span!("parse"),
+            Phase::Visit => tracing::debug_span!("visit"),
+            Phase::Convert => tracing::trace_span!("convert"),
+            Phase::Emit => tracing::debug_span!("emit"),
+        };
+        Self {
+            phase,
+            start: std::time::Instant::now(),
+            _span: span.entered(),
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn start(_phase: Phase) -> Self {
+        Self {}
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn stop(self, timings: &mut Timings) {
+        *timings.slot(self.phase) += self.start.elapsed();
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn stop(self, _timings: &mut Timings) {}
+}
+
+/// 开启 `tracing` feature 时返回 Some
+pub(crate) fn recorded(timings: Timings) -> Option<Timings> {
+    cfg!(feature = "tracing").then_some(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_records_only_with_feature() {
+        let mut timings = Timings::default();
+        let timer = PhaseTimer::start(Phase::Convert);
+        std::thread::sleep(Duration::from_millis(1));
+        timer.stop(&mut timings);
+        if cfg!(feature = "tracing") {
+            assert!(timings.convert >= Duration::from_millis(1));
+            assert_eq!(recorded(timings), Some(timings));
+        } else {
+            assert_eq!(timings, Timings::default());
+            assert_eq!(recorded(timings), None);
+        }
+        assert_eq!((timings.parse, timings.visit, timings.emit), Default::default());
+    }
+}
@@ -0,0 +1,137 @@
+//! 注入 import 的插入位置
+//!
+//! import 不能直接插到模块最前面：`"use client"` / `"use server"` 等指令只有位于
+//! 模块开头才生效，许可证、`@jsxImportSource` 之类的文件头注释也应留在顶部。
+//!
+//! - 有指令序言时插在最后一条指令之后
+//! - 否则插在第一条语句之前；与语句隔着空行的注释视为文件头，留在 import 上方，
+//!   紧贴语句的注释（如 `// eslint-disable-next-line`）仍随语句留在 import 下方
+//! - `ImportPosition::AfterImports` 且文件中已有 import 时，插在最后一条 import 之后
+//!
+//! shebang 由 SWC 单独保存，不在 `module.body` 中，两种输出方式都不会越过它。
+
+use swc_core::common::comments::{Comment, Comments, SingleThreadedComments};
+use swc_core::common::{BytePos, Span, Spanned};
+use swc_core::ecma::ast::{Expr, ExprStmt, Lit, Module, ModuleDecl, ModuleItem, Stmt};
+
+use crate::{ImportPosition, EMPTY_LINE_MARKER};
+
+/// 模块开头的指令语句数量
+fn directive_count(module: &Module) -> usize {
+    module
+        .body
+        .iter()
+        .take_while(|item| {
+            matches!(item, ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. }))
+                if matches!(&**expr, Expr::Lit(Lit::Str(_))))
+        })
+        .count()
+}
+
+/// `AfterImports` 时最后一条顶层 import 在 `module.body` 中的下标
+fn last_import_index(module: &Module, position: ImportPosition) -> Option<usize> {
+    if position != ImportPosition::AfterImports {
+        return None;
+    }
+    module
+        .body
+        .iter()
+        .rposition(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))))
+}
+
+/// `hi` 所在行的下一行开头（相对 `src` 开头）
+fn next_line_offset(src: &str, base: BytePos, hi: BytePos) -> usize {
+    let hi = (hi.0 - base.0) as usize;
+    src[hi..].find('\n').map_or(src.len(), |i| hi + i + 1)
+}
+
+/// 第一条语句的前导注释中属于文件头的数量
+///
+/// 某条注释之后隔着空行（原文中的空行，或整体重新打印时的空行占位符）即视为文件头，
+/// 它及之前的注释都留在 import 上方。
+fn header_comment_count(src: &str, base: BytePos, leading: &[Comment], stmt_lo: BytePos) -> usize {
+    let is_marker = |c: &Comment| EMPTY_LINE_MARKER.strip_prefix("//") == Some(&*c.text);
+    let mut count = 0;
+    for (i, comment) in leading.iter().enumerate() {
+        let next = leading.get(i + 1);
+        let end = next.map_or(stmt_lo, |c| c.span.lo);
+        let gap = src
+            .get((comment.span.hi.0 - base.0) as usize..(end.0 - base.0) as usize)
+            .unwrap_or_default();
+        if gap.matches('\n').count() >= 2 || next.is_some_and(is_marker) {
+            count = i + 1;
+        }
+    }
+    // 文件头之后的空行一并留在 import 上方
+    while count > 0 && leading.get(count).is_some_and(is_marker) {
+        count += 1;
+    }
+    // 只有空行占位符时没有真正的文件头
+    if leading[..count].iter().all(is_marker) {
+        0
+    } else {
+        count
+    }
+}
+
+/// span 编辑模式下 import 的插入字节偏移（相对 `src` 开头）
+pub(crate) fn insertion_offset(
+    module: &Module,
+    comments: &SingleThreadedComments,
+    src: &str,
+    base: BytePos,
+    position: ImportPosition,
+) -> usize {
+    if let Some(index) = last_import_index(module, position) {
+        return next_line_offset(src, base, module.body[index].span().hi);
+    }
+    let directives = directive_count(module);
+    if directives > 0 {
+        return next_line_offset(src, base, module.body[directives - 1].span().hi);
+    }
+    let Some(first) = module.body.first() else {
+        return src.len();
+    };
+    let lo = first.span().lo;
+    let leading = comments.get_leading(lo).unwrap_or_default();
+    let count = header_comment_count(src, base, &leading, lo);
+    let pos = leading.get(count).map_or(lo, |c| c.span.lo);
+    (pos.0 - base.0) as usize
+}
+
+/// 整体重新打印模式下把 `imports` 按顺序插入模块
+///
+/// 没有指令时，文件头注释改挂到第一条 import 上，使其仍输出在 import 之前。
+pub(crate) fn insert_imports(
+    module: &mut Module,
+    comments: &SingleThreadedComments,
+    src: &str,
+    base: BytePos,
+    mut imports: Vec<ModuleItem>,
+    position: ImportPosition,
+) {
+    if imports.is_empty() {
+        return;
+    }
+    if let Some(index) = last_import_index(module, position) {
+        module.body.splice(index + 1..index + 1, imports);
+        return;
+    }
+    let index = directive_count(module);
+    if let (0, Some(first)) = (index, module.body.first()) {
+        let lo = first.span().lo;
+        let mut attached = comments.take_leading(lo).unwrap_or_default();
+        let count = header_comment_count(src, base, &attached, lo);
+        let header: Vec<Comment> = attached.drain(..count).collect();
+        if !attached.is_empty() {
+            comments.add_leading_comments(lo, attached);
+        }
+        if let Some(anchor) = header.last().map(|c| c.span.hi) {
+            comments.add_leading_comments(anchor, header);
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) = &mut imports[0] {
+                decl.span = Span::new(anchor, anchor);
+            }
+        }
+    }
+    module.body.splice(index..index, imports);
+}
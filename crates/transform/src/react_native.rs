@@ -0,0 +1,278 @@
+//! React Native StyleSheet 输出（实验性）
+//!
+//! 将工具类转换为 `StyleSheet.create({...})` 对象：属性名转为 camelCase，
+//! 长度转为数值（`1rem` → 16，`1px` → 1）。变体（`hover:`、`md:` 等）和仅 Web 支持的
+//! 属性无法在 React Native 中表达，转换时跳过，由调用方报告诊断。
+
+use crate::emit::{is_identifier, QuoteStyle};
+use headwind_core::Declaration;
+use indexmap::IndexMap;
+
+/// React Native 样式值
+#[derive(Debug, Clone, PartialEq)]
+pub enum NativeValue {
+    /// 数值（长度已换算为 dp）
+    Number(f64),
+    /// 字符串（颜色、关键字、百分比、fontWeight）
+    String(String),
+}
+
+impl NativeValue {
+    fn to_code(&self, quote: QuoteStyle) -> String {
+        match self {
+            NativeValue::Number(n) => n.to_string(),
+            NativeValue::String(s) => quote.quote(s),
+        }
+    }
+}
+
+/// 一个生成类名对应的样式对象，属性按写入顺序排列，后写覆盖先写
+pub type NativeStyle = IndexMap<String, NativeValue>;
+
+/// 1rem 对应的 dp 数
+const REM_DP: f64 = 16.0;
+
+/// React Native 支持的样式属性（camelCase）
+const SUPPORTED_PROPERTIES: &[&str] = &[
+    // 布局
+    "width", "height", "minWidth", "maxWidth", "minHeight", "maxHeight",
+    "margin", "marginTop", "marginRight", "marginBottom", "marginLeft",
+    "marginHorizontal", "marginVertical", "marginStart", "marginEnd",
+    "padding", "paddingTop", "paddingRight", "paddingBottom", "paddingLeft",
+    "paddingHorizontal", "paddingVertical", "paddingStart", "paddingEnd",
+    "top", "right", "bottom", "left", "position", "zIndex", "display", "overflow",
+    // Flexbox
+    "flex", "flexDirection", "flexWrap", "flexGrow", "flexShrink", "flexBasis",
+    "alignItems", "alignSelf", "alignContent", "justifyContent",
+    "gap", "rowGap", "columnGap",
+    // 边框
+    "borderWidth", "borderTopWidth", "borderRightWidth", "borderBottomWidth", "borderLeftWidth",
+    "borderColor", "borderTopColor", "borderRightColor", "borderBottomColor", "borderLeftColor",
+    "borderRadius", "borderTopLeftRadius", "borderTopRightRadius",
+    "borderBottomLeftRadius", "borderBottomRightRadius", "borderStyle",
+    // 颜色与文本
+    "backgroundColor", "color", "opacity",
+    "fontSize", "fontStyle", "fontFamily", "letterSpacing", "textAlign",
+    "textDecorationLine", "textDecorationStyle", "textDecorationColor", "textTransform",
+];
+
+/// 将一组 CSS 声明转换为 React Native 样式，同时返回无法转换的声明
+pub fn convert_declarations(decls: &[Declaration]) -> (NativeStyle, Vec<Declaration>) {
+    let mut style = NativeStyle::new();
+    let mut unsupported = Vec::new();
+    for decl in decls {
+        match convert_declaration(decl, &style) {
+            Some(entries) => style.extend(entries),
+            None => unsupported.push(decl.clone()),
+        }
+    }
+    (style, unsupported)
+}
+
+/// 生成 `const styles = StyleSheet.create({...});` 源码
+///
+/// ```
+/// use headwind_transform::react_native::{style_sheet_code, NativeStyle, NativeValue};
+/// use headwind_transform::QuoteStyle;
+/// use indexmap::IndexMap;
+///
+/// let mut style = NativeStyle::new();
+/// style.insert("padding".to_string(), NativeValue::Number(16.0));
+/// let mut styles = IndexMap::new();
+/// styles.insert("p4".to_string(), style);
+///
+/// assert_eq!(
+///     style_sheet_code("styles", &styles, QuoteStyle::Double),
+///     "const styles = StyleSheet.create({\n  p4: {\n    padding: 16,\n  },\n});\n"
+/// );
+/// ```
+pub fn style_sheet_code(
+    binding: &str,
+    styles: &IndexMap<String, NativeStyle>,
+    quote: QuoteStyle,
+) -> String {
+    let mut code = format!("const {} = StyleSheet.create({{\n", binding);
+    for (name, style) in styles {
+        let key = if is_identifier(name) {
+            name.clone()
+        } else {
+            quote.quote(name)
+        };
+        code.push_str(&format!("  {}: {{\n", key));
+        for (property, value) in style {
+            code.push_str(&format!("    {}: {},\n", property, value.to_code(quote)));
+        }
+        code.push_str("  },\n");
+    }
+    code.push_str("});\n");
+    code
+}
+
+/// 转换单条声明；`style` 为同一工具类中已转换的属性（unitless line-height 需要 fontSize）
+fn convert_declaration(decl: &Declaration, style: &NativeStyle) -> Option<Vec<(String, NativeValue)>> {
+    let value = decl.value.trim();
+    match decl.property.as_str() {
+        "background" | "background-color" => {
+            return Some(vec![("backgroundColor".to_string(), native_value(value)?)]);
+        }
+        "inset" => {
+            let v = native_value(value)?;
+            return Some(
+                ["top", "right", "bottom", "left"]
+                    .iter()
+                    .map(|side| (side.to_string(), v.clone()))
+                    .collect(),
+            );
+        }
+        "line-height" => {
+            // React Native 的 lineHeight 是绝对值，倍数需乘以字号
+            if let Some(ratio) = parse_ratio(value) {
+                let Some(NativeValue::Number(size)) = style.get("fontSize") else {
+                    return None;
+                };
+                let line_height = (size * ratio * 100.0).round() / 100.0;
+                return Some(vec![("lineHeight".to_string(), NativeValue::Number(line_height))]);
+            }
+            return Some(vec![("lineHeight".to_string(), native_length(value)?)]);
+        }
+        "aspect-ratio" => {
+            return Some(vec![("aspectRatio".to_string(), NativeValue::Number(parse_ratio(value)?))]);
+        }
+        "font-weight" => {
+            return Some(vec![("fontWeight".to_string(), NativeValue::String(value.to_string()))]);
+        }
+        "display" if value != "flex" && value != "none" => return None,
+        "position" if value != "absolute" && value != "relative" => return None,
+        _ => {}
+    }
+
+    let property = native_property(&decl.property);
+    if !SUPPORTED_PROPERTIES.contains(&property.as_str()) {
+        return None;
+    }
+    Some(vec![(property, native_value(value)?)])
+}
+
+/// CSS 属性名 → React Native 属性名
+fn native_property(property: &str) -> String {
+    match property {
+        "margin-inline" => "marginHorizontal".to_string(),
+        "margin-block" => "marginVertical".to_string(),
+        "margin-inline-start" => "marginStart".to_string(),
+        "margin-inline-end" => "marginEnd".to_string(),
+        "padding-inline" => "paddingHorizontal".to_string(),
+        "padding-inline-start" => "paddingStart".to_string(),
+        "padding-inline-end" => "paddingEnd".to_string(),
+        "padding-block" => "paddingVertical".to_string(),
+        _ => camel_case(property),
+    }
+}
+
+/// 转换属性值：长度转数值，关键字 / 颜色 / 百分比保留为字符串，
+/// 依赖 CSS 运行时的 `var()` / `calc()` 无法转换
+fn native_value(value: &str) -> Option<NativeValue> {
+    if value.contains("var(") || value.contains("calc(") {
+        return None;
+    }
+    if value.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '-') {
+        return native_length(value);
+    }
+    Some(NativeValue::String(value.to_string()))
+}
+
+/// 数值 + 单位：rem / px / 无单位 → 数值，% → 字符串，其余单位（em、vh 等）不支持
+fn native_length(value: &str) -> Option<NativeValue> {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    match unit {
+        "" | "px" => Some(NativeValue::Number(number)),
+        "rem" => Some(NativeValue::Number(number * REM_DP)),
+        "%" => Some(NativeValue::String(value.to_string())),
+        _ => None,
+    }
+}
+
+/// 解析 `1.5`、`16 / 9`、`calc(1.75 / 1.125)` 形式的比值
+fn parse_ratio(value: &str) -> Option<f64> {
+    let inner = value
+        .strip_prefix("calc(")
+        .and_then(|v| v.strip_suffix(')'))
+        .unwrap_or(value);
+    match inner.split_once('/') {
+        Some((a, b)) => {
+            let a: f64 = a.trim().parse().ok()?;
+            let b: f64 = b.trim().parse().ok()?;
+            (b != 0.0).then_some(a / b)
+        }
+        None => inner.trim().parse().ok(),
+    }
+}
+
+/// kebab-case → camelCase
+fn camel_case(property: &str) -> String {
+    let mut out = String::with_capacity(property.len());
+    let mut upper = false;
+    for ch in property.chars() {
+        if ch == '-' {
+            upper = true;
+        } else if upper {
+            out.push(ch.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decl(property: &str, value: &str) -> Declaration {
+        Declaration::new(property, value)
+    }
+
+    #[test]
+    fn test_convert_lengths_and_colors() {
+        let (style, unsupported) = convert_declarations(&[
+            decl("padding-left", "0.75rem"),
+            decl("border-width", "1px"),
+            decl("background", "#2b7fff"),
+            decl("width", "100%"),
+            decl("inset", "0"),
+        ]);
+        assert!(unsupported.is_empty());
+        assert_eq!(style["paddingLeft"], NativeValue::Number(12.0));
+        assert_eq!(style["borderWidth"], NativeValue::Number(1.0));
+        assert_eq!(style["backgroundColor"], NativeValue::String("#2b7fff".to_string()));
+        assert_eq!(style["width"], NativeValue::String("100%".to_string()));
+        assert_eq!(style["bottom"], NativeValue::Number(0.0));
+    }
+
+    #[test]
+    fn test_line_height_relative_to_font_size() {
+        let (style, unsupported) = convert_declarations(&[
+            decl("font-size", "1.125rem"),
+            decl("line-height", "calc(1.75 / 1.125)"),
+        ]);
+        assert!(unsupported.is_empty());
+        assert_eq!(style["fontSize"], NativeValue::Number(18.0));
+        assert_eq!(style["lineHeight"], NativeValue::Number(28.0));
+    }
+
+    #[test]
+    fn test_web_only_declarations_unsupported() {
+        let (style, unsupported) = convert_declarations(&[
+            decl("display", "grid"),
+            decl("cursor", "pointer"),
+            decl("box-shadow", "var(--shadow-md)"),
+            decl("font-weight", "700"),
+        ]);
+        assert_eq!(unsupported.len(), 3);
+        assert_eq!(style["fontWeight"], NativeValue::String("700".to_string()));
+    }
+}
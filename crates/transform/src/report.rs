@@ -0,0 +1,980 @@
+//! 迁移报告
+//!
+//! `ConfigSession` 转换每个文件时记录一份 [`FileReport`]，`render_html` 把整个会话汇总为
+//! 一个自包含的 HTML 页面（样式与脚本内联，无外部依赖），便于在评审迁移 PR 时浏览：
+//! 每个文件转换前后的类名数量、无法转换的类及修复建议、CSS 体积、元素树和类名映射。
+//!
+//! [`SizeComparison`] 对比合并后的 CSS 与 Tailwind 为同一组类生成的估算输出，
+//! 给出原始、gzip 与 brotli 体积（压缩体积需要 `compression` feature），量化迁移前后的负载变化。
+//!
+//! [`DesignTokens`] 汇总会话中实际用到的主题值（颜色、间距、字体、阴影等），
+//! 导出为 W3C 设计令牌 JSON 或 CSS 变量文件，作为迁移的附带产物交给设计团队。
+//! [`figma_styles`] 把生成的类映射为 Figma 的颜色、文字与效果样式，供设计稿导入已实现的样式。
+
+use crate::session::ConfigSession;
+use crate::naming::NamingStats;
+use crate::storybook::{file_components, StoryComponent};
+use crate::TransformResult;
+use headwind_tw_index::fallback::{parse_color, split_top_level};
+use headwind_tw_index::palette::get_color;
+use headwind_tw_index::value_map::{get_spacing_value, infer_value};
+use headwind_tw_index::{Bundler, ColorMode};
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// 单个文件的转换摘要
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileReport {
+    pub path: PathBuf,
+    /// 转换前 class 属性中不同类名的数量
+    pub classes_before: usize,
+    /// 转换后 class 属性中不同类名的数量（生成名 + 保留的类）
+    pub classes_after: usize,
+    /// 无法转换的类 → 修复建议
+    pub unknown_classes: IndexMap<String, Option<String>>,
+    /// 生成 CSS 的字节数
+    pub css_bytes: usize,
+    /// 元素树文本（仅在开启 `element_tree` 时存在）
+    pub element_tree: Option<String>,
+    /// 原始类字符串 → 生成的类名
+    pub class_map: IndexMap<String, String>,
+    /// 原始类字符串 → 出现次数
+    pub class_usage: IndexMap<String, usize>,
+    /// 生成名的空间占用与冲突统计
+    pub naming_stats: NamingStats,
+    /// 按组件拆分的生成类与 CSS（仅在开启 `element_tree` 的 JSX 文件中存在）
+    pub components: Vec<StoryComponent>,
+}
+
+impl FileReport {
+    /// 由一次转换的结果生成摘要
+    pub fn new(path: impl Into<PathBuf>, result: &TransformResult) -> Self {
+        let path = path.into();
+        Self {
+            components: file_components(&path, result),
+            path,
+            classes_before: distinct_classes(result.class_map.keys()),
+            classes_after: distinct_classes(result.class_map.values()),
+            unknown_classes: result.unknown_classes.clone(),
+            css_bytes: result.css.len(),
+            element_tree: result.element_tree.clone(),
+            class_map: result.class_map.clone(),
+            class_usage: result.class_usage.clone(),
+            naming_stats: result.naming_stats.clone(),
+        }
+    }
+}
+
+/// CSS 的原始与压缩后字节数
+///
+/// 压缩体积需要 `compression` feature，未开启时为 None。gzip 取最高压缩级别，
+/// brotli 取质量 11，与预压缩静态资源的常见配置一致。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CssSizes {
+    pub raw: usize,
+    pub gzip: Option<usize>,
+    pub brotli: Option<usize>,
+}
+
+impl CssSizes {
+    pub fn measure(css: &str) -> Self {
+        Self {
+            raw: css.len(),
+            gzip: gzip_size(css.as_bytes()),
+            brotli: brotli_size(css.as_bytes()),
+        }
+    }
+}
+
+#[cfg(feature = "compression")]
+fn gzip_size(bytes: &[u8]) -> Option<usize> {
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(bytes).ok()?;
+    Some(encoder.finish().ok()?.len())
+}
+
+#[cfg(feature = "compression")]
+fn brotli_size(bytes: &[u8]) -> Option<usize> {
+    use std::io::Write;
+
+    let mut compressed = Vec::new();
+    {
+        let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+        writer.write_all(bytes).ok()?;
+    }
+    Some(compressed.len())
+}
+
+#[cfg(not(feature = "compression"))]
+fn gzip_size(_: &[u8]) -> Option<usize> {
+    None
+}
+
+#[cfg(not(feature = "compression"))]
+fn brotli_size(_: &[u8]) -> Option<usize> {
+    None
+}
+
+/// Headwind 输出与等价 Tailwind 输出的体积对比
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeComparison {
+    pub headwind: CssSizes,
+    /// Tailwind 的估算输出，见 [`estimate_tailwind_css`]
+    pub tailwind: CssSizes,
+}
+
+impl SizeComparison {
+    pub fn new(headwind_css: &str, tailwind_css: &str) -> Self {
+        Self {
+            headwind: CssSizes::measure(headwind_css),
+            tailwind: CssSizes::measure(tailwind_css),
+        }
+    }
+
+    /// 对比合并后的 CSS 与会话中所有已转换文件用到的类对应的 Tailwind 估算输出
+    pub fn from_session(css: &str, session: &ConfigSession) -> Self {
+        let classes = session
+            .reports()
+            .values()
+            .flat_map(|report| report.class_map.keys())
+            .flat_map(|classes| classes.split_whitespace());
+        Self::new(css, &estimate_tailwind_css(classes))
+    }
+
+    /// 渲染为 Markdown 表格，可直接贴进迁移 PR 的描述
+    ///
+    /// ```
+    /// use headwind_transform::report::SizeComparison;
+    ///
+    /// let headwind = ".c_1 {\n  padding: 1rem;\n}\n";
+    /// let tailwind = ".hover\\:p-4:hover {\n  padding: 1rem;\n}\n";
+    /// let table = SizeComparison::new(headwind, tailwind).to_markdown();
+    /// assert!(table.contains("| 原始 | 39 B | 26 B | -33.3% |"));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut table = String::from("| | Tailwind（估算） | Headwind | 变化 |\n|---|---:|---:|---:|\n");
+        let rows = [
+            ("原始", Some(self.tailwind.raw), Some(self.headwind.raw)),
+            ("gzip", self.tailwind.gzip, self.headwind.gzip),
+            ("brotli", self.tailwind.brotli, self.headwind.brotli),
+        ];
+        for (label, tailwind, headwind) in rows {
+            let (Some(tailwind), Some(headwind)) = (tailwind, headwind) else {
+                continue;
+            };
+            let change = if tailwind == 0 {
+                "—".to_string()
+            } else {
+                format!("{:+.1}%", (headwind as f64 - tailwind as f64) / tailwind as f64 * 100.0)
+            };
+            table.push_str(&format!(
+                "| {} | {} B | {} B | {} |\n",
+                label, tailwind, headwind, change
+            ));
+        }
+        table
+    }
+}
+
+/// 估算 Tailwind 为一组工具类生成的 CSS：去重后每个类一条规则，选择器为转义后的类名，
+/// 顶部定义引用到的主题变量。不含 preflight 等基础样式；无法转换的类跳过
+pub fn estimate_tailwind_css<'a>(classes: impl IntoIterator<Item = &'a str>) -> String {
+    let bundler = Bundler::new();
+    let mut seen = HashSet::new();
+    let mut rules = String::new();
+    for class in classes {
+        if !seen.insert(class) {
+            continue;
+        }
+        let Ok(css) = bundler.bundle_to_css(&escape_class_name(class), class, "  ") else {
+            continue;
+        };
+        if css.is_empty() {
+            continue;
+        }
+        if !rules.is_empty() {
+            rules.push('\n');
+        }
+        rules.push_str(&css);
+    }
+    let root = bundler.generate_root_css(&rules);
+    if root.is_empty() {
+        rules
+    } else {
+        format!("{}\n{}", root, rules)
+    }
+}
+
+/// 一个设计令牌
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesignToken {
+    /// 分组：`color`、`spacing`、`font`、`text`、`shadow` 等
+    pub group: String,
+    /// 组内名称：`blue-500`、`4`、`sm--line-height`
+    pub name: String,
+    /// 值：颜色为 hex，其余与主题变量的定义相同
+    pub value: String,
+    /// W3C 令牌类型（`color`、`dimension`、`fontFamily`），没有对应类型时为 None
+    pub token_type: Option<&'static str>,
+}
+
+/// 主题变量前缀 → (分组, 令牌类型)，长前缀在前
+const TOKEN_GROUPS: &[(&str, &str, Option<&str>)] = &[
+    ("--text-shadow-", "text-shadow", None),
+    ("--inset-shadow-", "inset-shadow", None),
+    ("--color-", "color", Some("color")),
+    ("--font-", "font", Some("fontFamily")),
+    ("--text-", "text", Some("dimension")),
+    ("--shadow-", "shadow", None),
+    ("--blur-", "blur", Some("dimension")),
+    ("--tracking-", "tracking", Some("dimension")),
+    ("--aspect-", "aspect", None),
+];
+
+/// 代码中实际用到的主题值清单
+///
+/// 由类名反查主题变量：颜色（`bg-blue-500`）、字号与字体（`text-sm`、`font-mono`）、
+/// 阴影、模糊、字距，以及按倍数展开的间距（`p-4` → `spacing.4` = `1rem`）。
+/// 任意值（`bg-[#123456]`）与无法转换的类不计入。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DesignTokens {
+    /// 按分组、名称排序（间距按数值排序）
+    pub tokens: Vec<DesignToken>,
+}
+
+impl DesignTokens {
+    /// 收集一组类用到的主题值
+    ///
+    /// ```
+    /// use headwind_transform::report::DesignTokens;
+    ///
+    /// let tokens = DesignTokens::from_classes(["p-4", "hover:shadow-md", "font-mono"]);
+    /// let css = tokens.to_css();
+    /// assert!(css.contains("  --spacing-4: 1rem;\n"));
+    /// assert!(css.contains("  --shadow-md: 0 4px 6px -1px"));
+    /// assert!(css.contains("  --font-mono: ui-monospace"));
+    /// ```
+    pub fn from_classes<'a>(classes: impl IntoIterator<Item = &'a str>) -> Self {
+        let bundler = Bundler::new().with_color_mode(ColorMode::Var);
+        let mut seen = HashSet::new();
+        let mut css = String::new();
+        let mut spacing = Vec::new();
+        for class in classes {
+            if !seen.insert(class) {
+                continue;
+            }
+            if let Ok(rule) = bundler.bundle_to_css("token", class, "  ") {
+                spacing.extend(spacing_token(class, &rule));
+                css.push_str(&rule);
+                css.push('\n');
+            }
+        }
+
+        let mut tokens: Vec<DesignToken> = bundler
+            .theme_variables(&css)
+            .iter()
+            .filter_map(|var_name| theme_token(&bundler, var_name))
+            .collect();
+        tokens.extend(spacing);
+        tokens.sort_by(|a, b| {
+            a.group
+                .cmp(&b.group)
+                .then_with(|| compare_names(&a.name, &b.name))
+        });
+        tokens.dedup();
+        Self { tokens }
+    }
+
+    /// 收集会话中所有已转换文件用到的主题值
+    pub fn from_session(session: &ConfigSession) -> Self {
+        let classes = session
+            .reports()
+            .values()
+            .flat_map(|report| report.class_map.keys())
+            .flat_map(|classes| classes.split_whitespace());
+        Self::from_classes(classes)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// W3C 设计令牌格式（Design Tokens Community Group）的 JSON：按分组嵌套，
+    /// 每个令牌为 `{ "$type": …, "$value": … }`
+    pub fn to_json(&self) -> String {
+        let mut groups = Map::new();
+        for token in &self.tokens {
+            let mut entry = Map::new();
+            if let Some(token_type) = token.token_type {
+                entry.insert("$type".to_string(), json!(token_type));
+            }
+            entry.insert("$value".to_string(), json!(token.value));
+            if let Value::Object(group) = groups
+                .entry(token.group.clone())
+                .or_insert_with(|| Value::Object(Map::new()))
+            {
+                group.insert(token.name.clone(), Value::Object(entry));
+            }
+        }
+        serde_json::to_string_pretty(&Value::Object(groups)).unwrap_or_default()
+    }
+
+    /// CSS 变量文件：一个 `:root` 块，变量名为 `--<分组>-<名称>`；没有令牌时返回空字符串
+    pub fn to_css(&self) -> String {
+        if self.tokens.is_empty() {
+            return String::new();
+        }
+        let mut css = ":root {\n".to_string();
+        for token in &self.tokens {
+            css.push_str(&format!(
+                "  --{}-{}: {};\n",
+                token.group, token.name, token.value
+            ));
+        }
+        css.push_str("}\n");
+        css
+    }
+}
+
+/// 主题变量对应的令牌；间距由 [`spacing_token`] 按类名中的倍数展开
+fn theme_token(bundler: &Bundler, var_name: &str) -> Option<DesignToken> {
+    let (prefix, group, token_type) = TOKEN_GROUPS
+        .iter()
+        .find(|(prefix, _, _)| var_name.starts_with(prefix))?;
+    let name = &var_name[prefix.len()..];
+    let value = match *group {
+        "color" => get_color(name, ColorMode::Hex),
+        _ => bundler.theme_value(var_name),
+    }?;
+    // `--text-sm--line-height` 是无单位的行高，不是长度
+    let token_type = if name.ends_with("--line-height") {
+        None
+    } else {
+        *token_type
+    };
+    Some(DesignToken {
+        group: group.to_string(),
+        name: name.to_string(),
+        value,
+        token_type,
+    })
+}
+
+/// 按倍数取间距的类（`p-4`、`hover:mx-2.5`、`-mt-10`）对应的间距令牌，负值取绝对值
+///
+/// 倍数取自类名，只有插件按间距取值、且生成的 `css` 中确实使用了该值时才计入（`border-2` 不计入）
+fn spacing_token(class: &str, css: &str) -> Option<DesignToken> {
+    let utility = class.rsplit(':').next()?.trim_matches('!');
+    let utility = utility.strip_prefix('-').unwrap_or(utility);
+    let (plugin, multiple) = utility.rsplit_once('-')?;
+    if multiple.parse::<f64>().ok()? < 0.0 {
+        return None;
+    }
+    let value = get_spacing_value(multiple)?;
+    if infer_value(plugin, multiple, ColorMode::Hex)? != value || !css.contains(&value) {
+        return None;
+    }
+    Some(DesignToken {
+        group: "spacing".to_string(),
+        name: multiple.to_string(),
+        value,
+        token_type: Some("dimension"),
+    })
+}
+
+/// 名称都是数字时按数值比较（间距 `2` 在 `10` 之前），否则按字典序
+fn compare_names(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.cmp(b),
+    }
+}
+
+/// 会话中生成的类对应的 Figma 样式（JSON）
+///
+/// 结构与 Figma 插件 API 的 `PaintStyle`、`TextStyle`、`EffectStyle` 属性一致，
+/// 插件用 `figma.createPaintStyle()` 等创建样式后逐项赋值即可导入：
+/// - `paintStyles`：背景色（`<生成名>/background`）与文字颜色（`<生成名>/text`）的纯色填充
+/// - `textStyles`：字号、字体族与字重、行高、字距、大小写、装饰线
+/// - `effectStyles`：阴影（`DROP_SHADOW` / `INNER_SHADOW`）与模糊（`LAYER_BLUR` / `BACKGROUND_BLUR`）
+///
+/// 只取不带变体的声明，`description` 为原始类字符串；生成名按字典序排列，同名只输出一次
+pub fn figma_styles(session: &ConfigSession) -> String {
+    let entries = session
+        .reports_by_path()
+        .into_iter()
+        .flat_map(|report| report.class_map.iter())
+        .map(|(classes, name)| (name.as_str(), classes.as_str()));
+    serde_json::to_string_pretty(&figma_styles_json(entries)).unwrap_or_default()
+}
+
+/// (生成名, 原始类字符串) → Figma 样式
+fn figma_styles_json<'a>(entries: impl IntoIterator<Item = (&'a str, &'a str)>) -> Value {
+    let bundler = Bundler::with_inline();
+    let mut seen = HashSet::new();
+    let mut entries: Vec<_> = entries
+        .into_iter()
+        .filter(|(name, _)| seen.insert(*name))
+        .collect();
+    entries.sort_by_key(|(name, _)| *name);
+
+    let (mut paints, mut texts, mut effects) = (Vec::new(), Vec::new(), Vec::new());
+    for (name, classes) in entries {
+        let Ok(group) = bundler.bundle(classes) else {
+            continue;
+        };
+        // 同一属性取最后一条声明
+        let values: HashMap<&str, &str> = group
+            .base
+            .iter()
+            .map(|d| (d.property.as_str(), d.value.as_str()))
+            .collect();
+
+        // `bg-*` 输出为 `background` 简写，非纯色（渐变、图片）时 figma_color 返回 None
+        let background = values.get("background-color").or_else(|| values.get("background"));
+        for (value, suffix) in [(background, "background"), (values.get("color"), "text")] {
+            if let Some(([r, g, b], opacity)) = value.and_then(|v| figma_color(v)) {
+                paints.push(json!({
+                    "name": format!("{}/{}", name, suffix),
+                    "description": classes,
+                    "paints": [{
+                        "type": "SOLID",
+                        "color": { "r": r, "g": g, "b": b },
+                        "opacity": opacity,
+                    }],
+                }));
+            }
+        }
+        if let Some(Value::Object(mut style)) = figma_text_style(&values) {
+            style.insert("name".to_string(), json!(name));
+            style.insert("description".to_string(), json!(classes));
+            texts.push(Value::Object(style));
+        }
+        let list = figma_effects(&values);
+        if !list.is_empty() {
+            effects.push(json!({ "name": name, "description": classes, "effects": list }));
+        }
+    }
+    json!({ "paintStyles": paints, "textStyles": texts, "effectStyles": effects })
+}
+
+/// Figma `TextStyle` 的属性；没有文字相关声明时为 None
+///
+/// 只设置了字重时字体族取 Figma 的默认字体 Inter
+fn figma_text_style(values: &HashMap<&str, &str>) -> Option<Value> {
+    let mut style = Map::new();
+    if let Some(size) = values.get("font-size").and_then(|v| length_px(v)) {
+        style.insert("fontSize".to_string(), json!(round(size)));
+        if let Some(line_height) = values
+            .get("line-height")
+            .and_then(|v| figma_line_height(v, size))
+        {
+            style.insert("lineHeight".to_string(), line_height);
+        }
+    }
+    let family = values
+        .get("font-family")
+        .and_then(|v| v.split(',').next())
+        .map(|f| f.trim().trim_matches(|c| c == '"' || c == '\'').to_string());
+    let weight = values
+        .get("font-weight")
+        .and_then(|v| v.parse::<u32>().ok());
+    let italic = values.get("font-style") == Some(&"italic");
+    if family.is_some() || weight.is_some() || italic {
+        let weight_name = match weight.unwrap_or(400) {
+            100 => "Thin",
+            200 => "Extra Light",
+            300 => "Light",
+            500 => "Medium",
+            600 => "Semi Bold",
+            700 => "Bold",
+            800 => "Extra Bold",
+            900 => "Black",
+            _ => "Regular",
+        };
+        let font_style = match (italic, weight_name) {
+            (true, "Regular") => "Italic".to_string(),
+            (true, _) => format!("{} Italic", weight_name),
+            (false, _) => weight_name.to_string(),
+        };
+        let family = family.unwrap_or_else(|| "Inter".to_string());
+        style.insert(
+            "fontName".to_string(),
+            json!({ "family": family, "style": font_style }),
+        );
+    }
+    if let Some(spacing) = values.get("letter-spacing") {
+        // em 为字号的倍数，对应 Figma 的百分比
+        let letter_spacing = match spacing
+            .strip_suffix("em")
+            .filter(|_| !spacing.ends_with("rem"))
+        {
+            Some(em) => em
+                .parse::<f64>()
+                .ok()
+                .map(|em| json!({ "unit": "PERCENT", "value": round(em * 100.0) })),
+            None => length_px(spacing).map(|px| json!({ "unit": "PIXELS", "value": round(px) })),
+        };
+        if let Some(letter_spacing) = letter_spacing {
+            style.insert("letterSpacing".to_string(), letter_spacing);
+        }
+    }
+    let text_case = match values.get("text-transform") {
+        Some(&"uppercase") => Some("UPPER"),
+        Some(&"lowercase") => Some("LOWER"),
+        Some(&"capitalize") => Some("TITLE"),
+        _ => None,
+    };
+    if let Some(text_case) = text_case {
+        style.insert("textCase".to_string(), json!(text_case));
+    }
+    let decoration = values
+        .get("text-decoration-line")
+        .and_then(|line| match *line {
+            "underline" => Some("UNDERLINE"),
+            "line-through" => Some("STRIKETHROUGH"),
+            _ => None,
+        });
+    if let Some(decoration) = decoration {
+        style.insert("textDecoration".to_string(), json!(decoration));
+    }
+    (!style.is_empty()).then_some(Value::Object(style))
+}
+
+/// 行高：无单位倍数与 `calc(a / b)` 换算为百分比，长度换算为像素
+fn figma_line_height(value: &str, font_size: f64) -> Option<Value> {
+    let ratio = match value
+        .strip_prefix("calc(")
+        .and_then(|v| v.strip_suffix(')'))
+    {
+        Some(expr) => {
+            let (a, b) = expr.split_once('/')?;
+            Some(a.trim().parse::<f64>().ok()? / b.trim().parse::<f64>().ok()?)
+        }
+        None => value.parse::<f64>().ok(),
+    };
+    match ratio {
+        Some(ratio) => Some(json!({ "unit": "PERCENT", "value": round(ratio * 100.0) })),
+        None => {
+            let px = if value.ends_with("em") && !value.ends_with("rem") {
+                value.strip_suffix("em")?.parse::<f64>().ok()? * font_size
+            } else {
+                length_px(value)?
+            };
+            Some(json!({ "unit": "PIXELS", "value": round(px) }))
+        }
+    }
+}
+
+/// 阴影与模糊对应的 Figma 效果
+fn figma_effects(values: &HashMap<&str, &str>) -> Vec<Value> {
+    let mut effects: Vec<Value> = values
+        .get("box-shadow")
+        .map(|shadows| split_parts(shadows, |c| c == ',').filter_map(figma_shadow).collect())
+        .unwrap_or_default();
+    for (property, effect_type) in [
+        ("filter", "LAYER_BLUR"),
+        ("backdrop-filter", "BACKGROUND_BLUR"),
+    ] {
+        let radius = values.get(property).and_then(|v| {
+            v.strip_prefix("blur(")?
+                .strip_suffix(')')
+                .and_then(length_px)
+        });
+        if let Some(radius) = radius {
+            effects.push(json!({ "type": effect_type, "radius": round(radius), "visible": true }));
+        }
+    }
+    effects
+}
+
+/// 单个 CSS 阴影：`[inset] <x> <y> [blur] [spread] <color>`
+fn figma_shadow(shadow: &str) -> Option<Value> {
+    let mut inset = false;
+    let mut lengths = Vec::new();
+    let mut color = None;
+    for part in split_parts(shadow, char::is_whitespace) {
+        if part == "inset" {
+            inset = true;
+        } else if let Some(px) = length_px(part) {
+            lengths.push(px);
+        } else {
+            color = Some(figma_color(part)?);
+        }
+    }
+    let ([r, g, b], opacity) = color?;
+    let (x, y) = (*lengths.first()?, *lengths.get(1)?);
+    Some(json!({
+        "type": if inset { "INNER_SHADOW" } else { "DROP_SHADOW" },
+        "color": { "r": r, "g": g, "b": b, "a": opacity },
+        "offset": { "x": round(x), "y": round(y) },
+        "radius": round(lengths.get(2).copied().unwrap_or(0.0)),
+        "spread": round(lengths.get(3).copied().unwrap_or(0.0)),
+        "visible": true,
+        "blendMode": "NORMAL",
+    }))
+}
+
+/// 按顶层分隔符拆分（忽略括号内的分隔符），去掉各部分首尾空白与空串
+fn split_parts(value: &str, separator: impl Fn(char) -> bool) -> impl Iterator<Item = &str> {
+    split_top_level(value, separator)
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+}
+
+/// px / rem（按 16px 根字号）长度换算为像素，`0` 视为 0px
+fn length_px(value: &str) -> Option<f64> {
+    if value == "0" {
+        return Some(0.0);
+    }
+    if let Some(rem) = value.strip_suffix("rem") {
+        return rem.parse::<f64>().ok().map(|rem| rem * 16.0);
+    }
+    value.strip_suffix("px")?.parse().ok()
+}
+
+/// Figma 的颜色：0–1 的 RGB 与不透明度，支持的写法见 [`parse_color`]
+fn figma_color(value: &str) -> Option<([f64; 3], f64)> {
+    let (rgb, alpha) = parse_color(value)?;
+    Some((rgb.map(|c| round(f64::from(c) / 255.0)), round(f64::from(alpha))))
+}
+
+/// 保留 4 位小数，避免 JSON 中出现 0.23137254901960785 这样的长小数
+fn round(value: f64) -> f64 {
+    (value * 10000.0).round() / 10000.0
+}
+
+/// 类名在选择器中的转义形式：`hover:p-4` → `hover\:p-4`，开头的数字写成十六进制转义（`2xl:` → `\32 xl\:`）
+fn escape_class_name(class: &str) -> String {
+    let mut escaped = String::with_capacity(class.len() + 4);
+    for (i, c) in class.chars().enumerate() {
+        if i == 0 && c.is_ascii_digit() {
+            escaped.push_str(&format!("\\{:x} ", c as u32));
+        } else if c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+            escaped.push(c);
+        } else {
+            escaped.push('\\');
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// 一组类字符串中不同类名的数量
+fn distinct_classes<'a>(strings: impl Iterator<Item = &'a String>) -> usize {
+    strings
+        .flat_map(|classes| classes.split_whitespace())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+const STYLE: &str = "body{font:14px/1.5 system-ui,sans-serif;margin:2rem;color:#1f2937}\
+h1{font-size:1.5rem}table{border-collapse:collapse;margin:.5rem 0}\
+th,td{border:1px solid #e5e7eb;padding:.25rem .5rem;text-align:left;vertical-align:top}\
+th{background:#f9fafb}td.num{text-align:right}code{font-family:ui-monospace,monospace}\
+details{border:1px solid #e5e7eb;border-radius:.5rem;padding:.5rem 1rem;margin:.5rem 0}\
+summary{cursor:pointer;font-weight:600}pre{background:#f9fafb;padding:.5rem;overflow:auto}\
+.warn{color:#b45309}input{padding:.25rem .5rem;width:20rem}";
+
+/// 按文件名过滤报告条目
+const SCRIPT: &str = "document.getElementById('filter').addEventListener('input',e=>{\
+const q=e.target.value.toLowerCase();\
+document.querySelectorAll('details[data-path]').forEach(d=>{\
+d.hidden=!d.dataset.path.toLowerCase().includes(q)})})";
+
+/// 把会话中所有已转换文件的摘要渲染为自包含的 HTML 报告，文件按路径排序
+pub fn render_html(session: &ConfigSession) -> String {
+    let reports = session.reports_by_path();
+    let total = |field: fn(&FileReport) -> usize| reports.iter().map(|r| field(r)).sum::<usize>();
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Headwind 迁移报告</title>\n");
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+    html.push_str("<h1>Headwind 迁移报告</h1>\n");
+
+    html.push_str("<table>\n");
+    let summary = [
+        ("文件数", reports.len()),
+        ("转换前类名", total(|r| r.classes_before)),
+        ("转换后类名", total(|r| r.classes_after)),
+        ("无法转换的类", total(|r| r.unknown_classes.len())),
+        ("CSS 字节数", total(|r| r.css_bytes)),
+    ];
+    for (label, value) in summary {
+        html.push_str(&format!(
+            "<tr><th>{}</th><td class=\"num\">{}</td></tr>\n",
+            label, value
+        ));
+    }
+    html.push_str("</table>\n");
+    html.push_str("<p><input id=\"filter\" type=\"search\" placeholder=\"按文件路径过滤\"></p>\n");
+
+    for report in reports {
+        render_file(&mut html, report);
+    }
+
+    html.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SCRIPT));
+    html
+}
+
+fn render_file(html: &mut String, report: &FileReport) {
+    let path = escape_html(&report.path.display().to_string());
+    html.push_str(&format!("<details data-path=\"{}\">\n", path));
+    html.push_str(&format!(
+        "<summary><code>{}</code> — 类名 {} → {}，CSS {} 字节",
+        path, report.classes_before, report.classes_after, report.css_bytes
+    ));
+    if !report.unknown_classes.is_empty() {
+        html.push_str(&format!(
+            "，<span class=\"warn\">{} 个无法转换</span>",
+            report.unknown_classes.len()
+        ));
+    }
+    html.push_str("</summary>\n");
+
+    if !report.unknown_classes.is_empty() {
+        html.push_str("<h3>无法转换的类</h3>\n<table>\n<tr><th>类名</th><th>建议</th></tr>\n");
+        for (class, suggestion) in &report.unknown_classes {
+            html.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td></tr>\n",
+                escape_html(class),
+                escape_html(suggestion.as_deref().unwrap_or("—"))
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !report.class_map.is_empty() {
+        html.push_str("<h3>类名映射</h3>\n<table>\n<tr><th>原始类</th><th>生成名</th></tr>\n");
+        for (original, generated) in &report.class_map {
+            html.push_str(&format!(
+                "<tr><td><code>{}</code></td><td><code>{}</code></td></tr>\n",
+                escape_html(original),
+                escape_html(generated)
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    if let Some(tree) = &report.element_tree {
+        html.push_str(&format!(
+            "<h3>元素树</h3>\n<pre>{}</pre>\n",
+            escape_html(tree)
+        ));
+    }
+    html.push_str("</details>\n");
+}
+
+/// 转义 HTML 文本与属性值中的特殊字符
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "ecmascript")]
+    fn test_render_html_report() {
+        use crate::HeadwindConfig;
+
+        let root = std::env::temp_dir().join(format!("headwind-report-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join("headwind.toml"),
+            "root = true\nelementTree = true\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("App.tsx"),
+            "const a = <div className=\"p-4 m-2 bg-bleu-500\"><b className=\"p-4\"/></div>;\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("index.html"), "<p class=\"p-4 card\">x</p>\n").unwrap();
+
+        let mut session = ConfigSession::new(HeadwindConfig::default()).unwrap();
+        session
+            .transform_file(&root.join("App.tsx"))
+            .unwrap()
+            .unwrap();
+        session
+            .transform_file(&root.join("index.html"))
+            .unwrap()
+            .unwrap();
+
+        let app = &session.reports()[&root.join("App.tsx")];
+        assert_eq!((app.classes_before, app.classes_after), (3, 2));
+        assert_eq!(
+            app.unknown_classes.get("bg-bleu-500"),
+            Some(&Some("是否想使用 `bg-blue-500`？".to_string()))
+        );
+        assert!(app.css_bytes > 0);
+        assert!(app.element_tree.is_some());
+
+        let html = render_html(&session);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<tr><th>文件数</th><td class=\"num\">2</td></tr>"));
+        assert!(
+            html.contains("<td><code>bg-bleu-500</code></td><td>是否想使用 `bg-blue-500`？</td>")
+        );
+        assert!(html.contains("<td><code>card</code></td><td>—</td>"));
+        assert!(html.contains("<h3>元素树</h3>"));
+        assert!(!html.contains("<link") && !html.contains("src="));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_estimate_tailwind_css() {
+        let css = estimate_tailwind_css(["p-4", "hover:bg-blue-500", "p-4", "2xl:flex", "not-a-class"]);
+        assert_eq!(css.matches(".p-4 {").count(), 1);
+        assert!(css.contains(".hover\\:bg-blue-500:hover {"), "{}", css);
+        assert!(css.contains(".\\32 xl\\:flex {"), "{}", css);
+        assert!(!css.contains("not-a-class"));
+
+        let sizes = CssSizes::measure(&css.repeat(20));
+        assert_eq!(sizes.raw, css.len() * 20);
+        if cfg!(feature = "compression") {
+            assert!(sizes.gzip.unwrap() < sizes.raw / 10);
+            assert!(sizes.brotli.unwrap() < sizes.raw / 10);
+        } else {
+            assert_eq!((sizes.gzip, sizes.brotli), (None, None));
+        }
+    }
+
+    #[test]
+    fn test_design_tokens() {
+        let tokens = DesignTokens::from_classes([
+            "bg-blue-500",
+            "hover:bg-blue-500/50",
+            "p-4",
+            "mx-2.5",
+            "-mt-10",
+            "text-sm",
+            "bg-[#123456]",
+            "not-a-class",
+        ]);
+        let names: Vec<_> = tokens
+            .tokens
+            .iter()
+            .map(|t| format!("{}.{}", t.group, t.name))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "color.blue-500",
+                "spacing.2.5",
+                "spacing.4",
+                "spacing.10",
+                "text.sm",
+                "text.sm--line-height",
+            ]
+        );
+        assert_eq!(
+            tokens.tokens[0].value,
+            get_color("blue-500", ColorMode::Hex).unwrap()
+        );
+        assert_eq!(tokens.tokens[1].value, "0.625rem");
+
+        let json: Value = serde_json::from_str(&tokens.to_json()).unwrap();
+        assert_eq!(json["color"]["blue-500"]["$type"], "color");
+        assert_eq!(json["spacing"]["10"]["$value"], "2.5rem");
+        assert_eq!(json["text"]["sm"]["$value"], "0.875rem");
+        assert!(json["text"]["sm--line-height"].get("$type").is_none());
+
+        assert!(tokens
+            .to_css()
+            .starts_with(":root {\n  --color-blue-500: #"));
+        assert_eq!(DesignTokens::from_classes(["flex"]).to_css(), "");
+    }
+
+    #[test]
+    fn test_figma_styles() {
+        let styles = figma_styles_json([
+            ("c_2", "bg-[#ff000080] text-white shadow-md"),
+            (
+                "c_1",
+                "text-sm font-bold tracking-tight uppercase underline",
+            ),
+            ("c_1", "p-8"),
+            ("c_3", "blur-sm"),
+            ("c_4", "p-4 hover:bg-white"),
+        ]);
+
+        let paints = styles["paintStyles"].as_array().unwrap();
+        assert_eq!(paints.len(), 2);
+        assert_eq!(paints[0]["name"], "c_2/background");
+        assert_eq!(
+            paints[0]["description"],
+            "bg-[#ff000080] text-white shadow-md"
+        );
+        assert_eq!(
+            paints[0]["paints"][0],
+            json!({ "type": "SOLID", "color": { "r": 1.0, "g": 0.0, "b": 0.0 }, "opacity": 0.502 })
+        );
+        assert_eq!(paints[1]["name"], "c_2/text");
+
+        let texts = styles["textStyles"].as_array().unwrap();
+        assert_eq!(texts.len(), 1);
+        assert_eq!(texts[0]["name"], "c_1");
+        assert_eq!(texts[0]["fontSize"], 14.0);
+        assert_eq!(
+            texts[0]["lineHeight"],
+            json!({ "unit": "PERCENT", "value": 142.8571 })
+        );
+        assert_eq!(
+            texts[0]["fontName"],
+            json!({ "family": "Inter", "style": "Bold" })
+        );
+        assert_eq!(
+            texts[0]["letterSpacing"],
+            json!({ "unit": "PERCENT", "value": -2.5 })
+        );
+        assert_eq!(texts[0]["textCase"], "UPPER");
+        assert_eq!(texts[0]["textDecoration"], "UNDERLINE");
+
+        let effects = styles["effectStyles"].as_array().unwrap();
+        assert_eq!(effects.len(), 2);
+        let shadows = effects[0]["effects"].as_array().unwrap();
+        assert_eq!(shadows.len(), 2);
+        assert_eq!(shadows[0]["type"], "DROP_SHADOW");
+        assert_eq!(shadows[0]["offset"], json!({ "x": 0.0, "y": 4.0 }));
+        assert_eq!(
+            (shadows[0]["radius"].as_f64(), shadows[0]["spread"].as_f64()),
+            (Some(6.0), Some(-1.0))
+        );
+        assert_eq!(shadows[0]["color"]["a"], 0.1);
+        assert_eq!(
+            effects[1]["effects"],
+            json!([{ "type": "LAYER_BLUR", "radius": 8.0, "visible": true }])
+        );
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("[&>p]:m-0 \"x\" 'y'"),
+            "[&amp;&gt;p]:m-0 &quot;x&quot; &#39;y&#39;"
+        );
+    }
+}
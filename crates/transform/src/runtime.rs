@@ -0,0 +1,60 @@
+//! 运行时类名合并辅助函数
+//!
+//! 静态 + 动态混合的 className（如 `` `p-4 ${active ? "ring" : ""}` ``）无法在编译期完全确定，
+//! 开启后静态部分照常生成类名，动态部分原样保留，由运行时辅助函数合并：
+//! `className={mergeClasses("c_xxx", `${active ? "ring" : ""}`)}`。
+
+/// 运行时辅助函数配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeHelper {
+    /// 导出的函数名（默认 `mergeClasses`）
+    pub name: String,
+    /// 注入 import 时使用的模块路径（默认 `./headwind-runtime.js`）
+    pub import_path: String,
+}
+
+impl Default for RuntimeHelper {
+    fn default() -> Self {
+        Self {
+            name: "mergeClasses".to_string(),
+            import_path: "./headwind-runtime.js".to_string(),
+        }
+    }
+}
+
+impl RuntimeHelper {
+    /// 生成辅助函数模块的源码，写入 `import_path` 对应的文件即可
+    ///
+    /// ```
+    /// use headwind_transform::RuntimeHelper;
+    ///
+    /// let source = RuntimeHelper::default().source();
+    /// assert!(source.contains("export function mergeClasses(...classes)"));
+    /// ```
+    pub fn source(&self) -> String {
+        format!(
+            "/** 合并类名：忽略 falsy 值并折叠多余空白 */\n\
+             export function {}(...classes) {{\n  \
+             return classes.filter(Boolean).join(\" \").replace(/\\s+/g, \" \").trim();\n\
+             }}\n",
+            self.name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_source_uses_name() {
+        let helper = RuntimeHelper {
+            name: "cx".to_string(),
+            import_path: "@/lib/cx".to_string(),
+        };
+        assert_eq!(
+            helper.source(),
+            "/** 合并类名：忽略 falsy 值并折叠多余空白 */\nexport function cx(...classes) {\n  return classes.filter(Boolean).join(\" \").replace(/\\s+/g, \" \").trim();\n}\n"
+        );
+    }
+}
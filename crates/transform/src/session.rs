@@ -0,0 +1,721 @@
+//! 多文件转换会话（monorepo 支持）
+//!
+//! 同一会话中不同目录可以使用不同配置：从文件所在目录向上查找所有
+//! `headwind.toml` / `headwind.json`，由外到内逐层合并，遇到 `root = true` 的配置停止继承。
+//! 例如 `apps/a/headwind.toml` 设置 CSS Modules，`apps/b` 沿用仓库根目录的全局 CSS 配置。
+//!
+//! 每个目录的合并结果只计算一次并缓存；配置文件变更后调用 `clear_cache()` 重新读取。
+//! 会话内所有文件共用一个类级转换缓存，`cache_stats()` 可查看命中情况；
+//! 配置的回退索引数据集按路径只读取一次。
+//! 每个转换过的文件记录一份摘要，可用 `report::render_html` 生成迁移报告，
+//! `naming_stats()` 汇总各文件的生成名空间占用与冲突。
+//!
+//! 多轮构建复用上一轮的类名映射时，本轮没有再用到的生成名即为失效名：
+//! `sweep_css()` 以本轮各文件的类名映射为标记，从合并后的 CSS 中清除失效名的规则块。
+//!
+//! watch 模式下每轮变更后调用 `flush_css()`，只写出上次 flush 之后转换的文件的 CSS，
+//! 不必在内存中保留整个会话的输出。
+//!
+//! 首次迁移使用 hash 名后，`rename_generated_class()` 把某个生成名改为语义名：
+//! 更新会话中的类名映射与待写出的 CSS，给出引用旧名的文件的源码编辑，
+//! 之后在本会话中转换同一组类时固定使用新名。
+
+use crate::config::{HeadwindConfig, CONFIG_FILE_NAMES, ENV_CONFIG_PATH};
+use crate::css_module::{extract_css_class_names, sweep_css_module};
+use crate::inheritance::apply_edits;
+use crate::naming::NamingStats;
+use crate::report::FileReport;
+use crate::{OutputMode, TransformResult};
+use headwind_tw_index::{CacheStats, ConversionCache, VersionedIndex};
+use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// 某个文件（或目录）最终生效的配置
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedConfig {
+    /// 合并后的配置（已叠加环境变量与调用方选项）
+    pub config: HeadwindConfig,
+    /// 参与合并的配置文件，由外到内；为空表示没有找到配置文件
+    pub sources: Vec<PathBuf>,
+}
+
+/// `sweep_css` 删除的一条规则
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweptRule {
+    /// 上一轮映射中的原始类字符串
+    pub classes: String,
+    /// 已失效的生成名
+    pub name: String,
+    /// 被删除的规则块原文
+    pub rule: String,
+}
+
+/// `sweep_css` 的结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SweepReport {
+    /// 清理后的 CSS
+    pub css: String,
+    /// 被删除的规则，按在 CSS 中的顺序
+    pub removed: Vec<SweptRule>,
+}
+
+/// `rename_generated_class` 的结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassRename {
+    pub old: String,
+    pub new: String,
+    /// 映射到旧名的原始类字符串
+    pub classes: Vec<String>,
+    /// 引用旧名的文件 → 源码编辑（字节区间, 替换文本），按位置排列
+    pub edits: IndexMap<PathBuf, Vec<(Range<usize>, String)>>,
+}
+
+impl ClassRename {
+    /// 把 `path` 的编辑应用到该文件的内容上（内容须与计算编辑时相同）
+    pub fn apply_to_source(&self, path: &Path, source: &str) -> String {
+        match self.edits.get(path) {
+            Some(edits) => apply_edits(source, edits),
+            None => source.to_string(),
+        }
+    }
+
+    /// 把已写出的 CSS 中的旧名改为新名：类选择器 `.old` 与属性选择器 `[data-hw="old"]`
+    pub fn rename_css(&self, css: &str) -> String {
+        rename_css_name(css, &self.old, &self.new)
+    }
+}
+
+/// 转换会话：按目录缓存配置，并记录每个文件使用了哪些配置文件
+pub struct ConfigSession {
+    env: HeadwindConfig,
+    overrides: HeadwindConfig,
+    /// 由 `HEADWIND_CONFIG` 指定时所有文件共用该配置文件，不再向上查找
+    explicit: Option<PathBuf>,
+    /// 目录 → 该目录下配置文件链的合并结果（不含环境变量与调用方选项）
+    directories: HashMap<PathBuf, ResolvedConfig>,
+    applied: IndexMap<PathBuf, Vec<PathBuf>>,
+    /// 所有文件共用的类级转换缓存
+    conversion_cache: Arc<ConversionCache>,
+    /// 数据集路径 → 已加载的回退索引
+    fallback_indexes: HashMap<PathBuf, VersionedIndex>,
+    /// 文件 → 最近一次转换的摘要
+    reports: IndexMap<PathBuf, FileReport>,
+    /// 上一轮构建的类名映射（原始类字符串 → 生成名）
+    previous_class_map: IndexMap<String, String>,
+    /// 上次 `flush_css` 之后转换的文件 → 生成的 CSS
+    pending_css: IndexMap<PathBuf, String>,
+    /// `rename_generated_class` 固定的生成名：原始类字符串 → 新名
+    renamed: IndexMap<String, String>,
+}
+
+impl ConfigSession {
+    /// 创建会话，读取当前进程的 `HEADWIND_*` 环境变量；`overrides` 对所有文件生效
+    pub fn new(overrides: HeadwindConfig) -> Result<Self, String> {
+        Ok(Self {
+            env: HeadwindConfig::from_env()?,
+            overrides,
+            explicit: std::env::var_os(ENV_CONFIG_PATH).map(PathBuf::from),
+            directories: HashMap::new(),
+            applied: IndexMap::new(),
+            conversion_cache: Arc::new(ConversionCache::new()),
+            fallback_indexes: HashMap::new(),
+            reports: IndexMap::new(),
+            previous_class_map: IndexMap::new(),
+            pending_css: IndexMap::new(),
+            renamed: IndexMap::new(),
+        })
+    }
+
+    /// 设置上一轮构建的类名映射，`sweep_css` 据此判断哪些生成名已失效
+    pub fn with_previous_class_map(mut self, class_map: IndexMap<String, String>) -> Self {
+        self.previous_class_map = class_map;
+        self
+    }
+
+    /// 解析 `file` 适用的配置：配置文件链 < 环境变量 < 调用方选项
+    pub fn resolve(&mut self, file: &Path) -> Result<ResolvedConfig, String> {
+        let files = match self.explicit.clone() {
+            Some(path) => ResolvedConfig {
+                config: HeadwindConfig::load(&path)?,
+                sources: vec![path],
+            },
+            None => self.directory_config(file.parent().unwrap_or(Path::new("")))?,
+        };
+        self.applied.insert(file.to_path_buf(), files.sources.clone());
+        let mut config = files
+            .config
+            .merge(self.env.clone())
+            .merge(self.overrides.clone());
+        for (classes, name) in &self.renamed {
+            config.class_overrides.entry(classes.clone()).or_default().name = Some(name.clone());
+        }
+        Ok(ResolvedConfig {
+            config,
+            sources: files.sources,
+        })
+    }
+
+    /// 按文件适用的配置转换文件；被 include / exclude 排除时返回 `Ok(None)`
+    pub fn transform_file(&mut self, path: &Path) -> Result<Option<TransformResult>, String> {
+        let resolved = self.resolve(path)?;
+        let fallback_index = self.fallback_index(&resolved.config)?;
+        let result = crate::transform_file_with_config(
+            path,
+            &resolved.config,
+            Some(self.conversion_cache.clone()),
+            fallback_index,
+        )?;
+        if let Some(result) = &result {
+            self.reports.insert(path.to_path_buf(), FileReport::new(path, result));
+            if !result.css.is_empty() {
+                self.pending_css.insert(path.to_path_buf(), result.css.clone());
+            }
+        }
+        Ok(result)
+    }
+
+    /// 配置指定的回退索引，同一路径只读取一次
+    fn fallback_index(
+        &mut self,
+        config: &HeadwindConfig,
+    ) -> Result<Option<VersionedIndex>, String> {
+        let Some(path) = &config.fallback_index else {
+            return Ok(None);
+        };
+        if let Some(index) = self.fallback_indexes.get(path) {
+            return Ok(Some(index.clone()));
+        }
+        let index = config.load_fallback_index()?;
+        if let Some(index) = &index {
+            self.fallback_indexes.insert(path.clone(), index.clone());
+        }
+        Ok(index)
+    }
+
+    /// 类级转换缓存的命中统计
+    pub fn cache_stats(&self) -> CacheStats {
+        self.conversion_cache.stats()
+    }
+
+    /// 已解析过的文件及其使用的配置文件（由外到内），按首次解析顺序排列
+    pub fn applied_configs(&self) -> &IndexMap<PathBuf, Vec<PathBuf>> {
+        &self.applied
+    }
+
+    /// 已转换文件的摘要，按首次转换顺序排列；同一文件再次转换时覆盖
+    pub fn reports(&self) -> &IndexMap<PathBuf, FileReport> {
+        &self.reports
+    }
+
+    /// 汇总已转换文件的生成名统计：按文件路径顺序合并，同名但类组合不同的记为跨文件冲突
+    pub fn naming_stats(&self) -> NamingStats {
+        let mut stats = NamingStats::default();
+        for report in self.reports_by_path() {
+            stats.merge(&report.naming_stats);
+        }
+        stats
+    }
+
+    /// 按文件路径排序的摘要，汇总输出不受转换顺序影响
+    pub(crate) fn reports_by_path(&self) -> Vec<&FileReport> {
+        let mut reports: Vec<&FileReport> = self.reports.values().collect();
+        reports.sort_by(|a, b| a.path.cmp(&b.path));
+        reports
+    }
+
+    /// 从合并后的 CSS 中删除失效生成名的规则（mark-and-sweep）
+    ///
+    /// 标记：本轮已转换文件的类名映射中出现的生成名均为存活名。
+    /// 清除：上一轮映射中不再存活的生成名，其规则块（含 `@media` 等包裹的块）被删除，
+    /// 同时引用存活名的规则块保留。只识别 `.name` 选择器，属性选择器模式不适用。
+    pub fn sweep_css(&self, css: &str) -> SweepReport {
+        let live: HashSet<String> = self
+            .reports
+            .values()
+            .flat_map(|report| report.class_map.values())
+            .flat_map(|classes| classes.split_whitespace())
+            .map(str::to_string)
+            .collect();
+        // 失效名 → 原始类字符串；映射值中原样保留的类（`group`、未知类）不是生成名
+        let dead: HashMap<&str, &str> = self
+            .previous_class_map
+            .iter()
+            .flat_map(|(classes, generated)| {
+                generated
+                    .split_whitespace()
+                    .filter(|name| !classes.split_whitespace().any(|class| class == *name))
+                    .filter(|name| !live.contains(*name))
+                    .map(move |name| (name, classes.as_str()))
+            })
+            .collect();
+        let dead_names: HashSet<String> = dead.keys().map(|name| name.to_string()).collect();
+
+        let (css, removed) = sweep_css_module(css, &dead_names, &live);
+        let removed = removed
+            .into_iter()
+            .map(|rule| {
+                let name = extract_css_class_names(&rule)
+                    .into_iter()
+                    .find(|name| dead_names.contains(name))
+                    .unwrap_or_default();
+                SweptRule {
+                    classes: dead.get(name.as_str()).unwrap_or(&"").to_string(),
+                    name,
+                    rule,
+                }
+            })
+            .collect();
+        SweepReport { css, removed }
+    }
+
+    /// 按转换顺序写出上次 flush 之后转换的文件的 CSS，文件之间空一行
+    ///
+    /// 两次 flush 之间同一文件转换多次时只写出最后一次的结果
+    pub fn flush_css(&mut self, out: &mut impl fmt::Write) -> fmt::Result {
+        for (i, (_, css)) in self.pending_css.drain(..).enumerate() {
+            if i > 0 {
+                out.write_char('\n')?;
+            }
+            out.write_str(&css)?;
+            if !css.ends_with('\n') {
+                out.write_char('\n')?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 把生成名 `old` 改为 `new`
+    ///
+    /// 更新已转换文件的类名映射、按组件拆分的 CSS 与待 flush 的 CSS，之后在本会话中转换同一组类时固定使用新名。
+    /// 源码编辑按各文件当前的内容（迁移后写回的代码）计算：CssModules 模式改写 `styles.old` / `styles["old"]`
+    /// （新名不是合法标识符时改为方括号访问），其他模式改写引号内以空白分隔的 `old`。
+    /// 会话外已写出的 CSS 用 [`ClassRename::rename_css`] 更新。
+    ///
+    /// `old` 不是会话中的生成名、`new` 不是合法的 CSS 类名或已被其他类组合使用时返回错误
+    pub fn rename_generated_class(&mut self, old: &str, new: &str) -> Result<ClassRename, String> {
+        if !is_css_identifier(new) {
+            return Err(format!("`{}` 不是合法的 CSS 类名", new));
+        }
+        let uses = |name: &str, generated: &String| generated.split_whitespace().any(|n| n == name);
+        let mut classes: Vec<String> = Vec::new();
+        let mut files = Vec::new();
+        for (path, report) in &self.reports {
+            for (original, generated) in &report.class_map {
+                if uses(new, generated) {
+                    return Err(format!("`{}` 已是 `{}` 的生成名", new, original));
+                }
+                if uses(old, generated) {
+                    if !classes.contains(original) {
+                        classes.push(original.clone());
+                    }
+                    if !files.contains(path) {
+                        files.push(path.clone());
+                    }
+                }
+            }
+        }
+        if classes.is_empty() {
+            return Err(format!("会话中没有生成名 `{}`", old));
+        }
+
+        let mut edits = IndexMap::new();
+        for path in files {
+            let source = std::fs::read_to_string(&path)
+                .map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+            let output_mode = self.resolve(&path)?.config.to_transform_options().output_mode;
+            let file_edits = match output_mode {
+                OutputMode::CssModules { binding_name, .. } => {
+                    css_module_rename_edits(&source, &binding_name, old, new)
+                }
+                _ => class_token_rename_edits(&source, old, new),
+            };
+            if !file_edits.is_empty() {
+                edits.insert(path, file_edits);
+            }
+        }
+
+        let rename = ClassRename {
+            old: old.to_string(),
+            new: new.to_string(),
+            classes,
+            edits,
+        };
+        let rename_value = |generated: &mut String| {
+            if uses(old, generated) {
+                *generated = generated
+                    .split_whitespace()
+                    .map(|name| if name == old { new } else { name })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+            }
+        };
+        for report in self.reports.values_mut() {
+            report.class_map.values_mut().for_each(rename_value);
+            for component in &mut report.components {
+                component.classes.values_mut().for_each(rename_value);
+                component.css = rename.rename_css(&component.css);
+            }
+        }
+        self.previous_class_map.values_mut().for_each(rename_value);
+        for css in self.pending_css.values_mut() {
+            *css = rename.rename_css(css);
+        }
+        for classes in &rename.classes {
+            self.renamed.insert(classes.clone(), new.to_string());
+        }
+        Ok(rename)
+    }
+
+    /// 清空目录配置与回退索引缓存（watch 模式下配置文件或数据集变更时调用）
+    ///
+    /// 类级转换缓存只依赖类名与转换选项，不受配置文件变更影响，不会被清空
+    pub fn clear_cache(&mut self) {
+        self.directories.clear();
+        self.fallback_indexes.clear();
+    }
+
+    fn directory_config(&mut self, dir: &Path) -> Result<ResolvedConfig, String> {
+        if let Some(resolved) = self.directories.get(dir) {
+            return Ok(resolved.clone());
+        }
+        let own = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file());
+        let resolved = match own {
+            Some(path) => {
+                let config = HeadwindConfig::load(&path)?;
+                if config.root {
+                    ResolvedConfig {
+                        config,
+                        sources: vec![path],
+                    }
+                } else {
+                    let mut parent = self.parent_config(dir)?;
+                    parent.sources.push(path);
+                    ResolvedConfig {
+                        config: parent.config.merge(config),
+                        sources: parent.sources,
+                    }
+                }
+            }
+            None => self.parent_config(dir)?,
+        };
+        self.directories.insert(dir.to_path_buf(), resolved.clone());
+        Ok(resolved)
+    }
+
+    fn parent_config(&mut self, dir: &Path) -> Result<ResolvedConfig, String> {
+        match dir.parent() {
+            Some(parent) => self.directory_config(parent),
+            None => Ok(ResolvedConfig::default()),
+        }
+    }
+}
+
+/// 合法的 CSS 类名（不需要转义）：字母、`_` 或非 ASCII 字符开头，其后另可包含数字与 `-`
+fn is_css_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || !c.is_ascii())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii())
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '\\' || !c.is_ascii()
+}
+
+/// CSS 中作为类选择器（`.old`）或属性选择器值（`"old"`）出现的旧名替换为新名
+fn rename_css_name(css: &str, old: &str, new: &str) -> String {
+    let mut output = String::with_capacity(css.len());
+    let mut last = 0;
+    for (i, _) in css.match_indices(old) {
+        let prev = css[..i].chars().next_back();
+        let next = css[i + old.len()..].chars().next();
+        if matches!(prev, Some('.' | '"')) && !next.is_some_and(is_name_char) {
+            output.push_str(&css[last..i]);
+            output.push_str(new);
+            last = i + old.len();
+        }
+    }
+    output.push_str(&css[last..]);
+    output
+}
+
+/// CssModules 模式的引用：`styles.old`、`styles["old"]`、`styles['old']`
+fn css_module_rename_edits(
+    source: &str,
+    binding: &str,
+    old: &str,
+    new: &str,
+) -> Vec<(Range<usize>, String)> {
+    let mut edits = Vec::new();
+    let mut find = |pattern: String, replacement: String| {
+        for (i, matched) in source.match_indices(&pattern) {
+            let prev = source[..i].chars().next_back();
+            let next = source[i + matched.len()..].chars().next();
+            let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '$';
+            if !prev.is_some_and(is_ident) && !next.is_some_and(is_ident) {
+                edits.push((i..i + matched.len(), replacement.clone()));
+            }
+        }
+    };
+    let dot = if crate::emit::is_identifier(new) {
+        format!("{}.{}", binding, new)
+    } else {
+        format!("{}[\"{}\"]", binding, new)
+    };
+    find(format!("{}.{}", binding, old), dot);
+    for quote in ['"', '\'', '`'] {
+        find(
+            format!("{}[{}{}{}]", binding, quote, old, quote),
+            format!("{}[{}{}{}]", binding, quote, new, quote),
+        );
+    }
+    edits.sort_by_key(|(range, _)| range.start);
+    edits
+}
+
+/// 引号内以空白分隔的旧名（class 属性值、类名字符串）；按行判断是否在引号内，跨行的模板字符串不处理
+fn class_token_rename_edits(source: &str, old: &str, new: &str) -> Vec<(Range<usize>, String)> {
+    let mut edits = Vec::new();
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        for (i, _) in line.match_indices(old) {
+            let prev = line[..i].chars().next_back();
+            let next = line[i + old.len()..].chars().next();
+            let separator = |c: Option<char>| {
+                c.is_some_and(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '`'))
+            };
+            if separator(prev) && separator(next) && inside_quotes(&line[..i]) {
+                edits.push((offset + i..offset + i + old.len(), new.to_string()));
+            }
+        }
+        offset += line.len();
+    }
+    edits
+}
+
+/// 行首到此处是否停在引号内（处理反斜杠转义）
+fn inside_quotes(prefix: &str) -> bool {
+    let mut quote = None;
+    let mut escaped = false;
+    for c in prefix.chars() {
+        match quote {
+            _ if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            None if matches!(c, '"' | '\'' | '`') => quote = Some(c),
+            _ => {}
+        }
+    }
+    quote.is_some()
+}
+
+// 测试用例都会转换 `.tsx` 文件
+#[cfg(all(test, feature = "ecmascript"))]
+mod tests {
+    use super::*;
+    use crate::OutputMode;
+    use headwind_core::NamingMode;
+
+    #[test]
+    fn test_nested_configs_per_package() {
+        let root = std::env::temp_dir().join(format!("headwind-session-{}", std::process::id()));
+        let app_a = root.join("apps/a/src");
+        let app_b = root.join("apps/b/src");
+        std::fs::create_dir_all(&app_a).unwrap();
+        std::fs::create_dir_all(&app_b).unwrap();
+        std::fs::write(root.join("headwind.toml"), "namingMode = \"readable\"\n").unwrap();
+        std::fs::write(
+            root.join("apps/a/headwind.json"),
+            r#"{"outputMode": {"type": "cssModules"}}"#,
+        )
+        .unwrap();
+        std::fs::write(root.join("apps/b/headwind.toml"), "root = true\n").unwrap();
+        for dir in [&app_a, &app_b] {
+            std::fs::write(dir.join("App.tsx"), "const a = <div className=\"p-4\"/>;\n").unwrap();
+        }
+
+        let mut session = ConfigSession::new(HeadwindConfig::default()).unwrap();
+        let a = session.resolve(&app_a.join("App.tsx")).unwrap();
+        assert_eq!(a.config.naming_mode, Some(NamingMode::Readable));
+        assert!(matches!(a.config.output_mode, Some(OutputMode::CssModules { .. })));
+        assert_eq!(
+            a.sources,
+            vec![root.join("headwind.toml"), root.join("apps/a/headwind.json")]
+        );
+
+        let b = session.resolve(&app_b.join("App.tsx")).unwrap();
+        assert_eq!(b.config.naming_mode, None);
+        assert!(b.config.output_mode.is_none());
+        assert_eq!(b.sources, vec![root.join("apps/b/headwind.toml")]);
+
+        let result = session.transform_file(&app_a.join("App.tsx")).unwrap().unwrap();
+        assert!(result.code.contains("./App.module.css"), "{}", result.code);
+        assert_eq!(session.applied_configs().len(), 2);
+
+        // 两个包的配置不同，但颜色、变量选项相同，`p-4` 的转换结果在会话内复用
+        session.transform_file(&app_b.join("App.tsx")).unwrap().unwrap();
+        let stats = session.cache_stats();
+        assert_eq!((stats.misses, stats.hits), (1, 1));
+
+        // 目录配置已缓存，文件变更需 clear_cache 后才生效
+        std::fs::write(
+            root.join("apps/b/headwind.toml"),
+            "root = true\nnamingMode = \"camelCase\"\n",
+        )
+        .unwrap();
+        assert_eq!(session.resolve(&app_b.join("App.tsx")).unwrap().config.naming_mode, None);
+        session.clear_cache();
+        assert_eq!(
+            session.resolve(&app_b.join("App.tsx")).unwrap().config.naming_mode,
+            Some(NamingMode::CamelCase)
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_sweep_css_drops_stale_mappings() {
+        let root = std::env::temp_dir().join(format!("headwind-sweep-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("headwind.toml"), "root = true\nnamingMode = \"readable\"\n").unwrap();
+        std::fs::write(root.join("App.tsx"), "const a = <div className=\"p-4\"/>;\n").unwrap();
+
+        let previous: IndexMap<String, String> = [
+            ("p-4", "p4"),
+            ("m-2 card", "m2 card"),
+            ("md:text-center", "mdtextcenter"),
+        ]
+        .into_iter()
+        .map(|(classes, name)| (classes.to_string(), name.to_string()))
+        .collect();
+        let mut session = ConfigSession::new(HeadwindConfig::default())
+            .unwrap()
+            .with_previous_class_map(previous);
+        session.transform_file(&root.join("App.tsx")).unwrap().unwrap();
+
+        let css = ".p4 {\n  padding: 1rem;\n}\n\n.m2 {\n  margin: 0.5rem;\n}\n\n\
+                   .card {\n  color: red;\n}\n\n\
+                   @media (width >= 48rem) {\n  .mdtextcenter {\n    text-align: center;\n  }\n}\n";
+        let report = session.sweep_css(css);
+        assert_eq!(
+            report.css,
+            ".p4 {\n  padding: 1rem;\n}\n\n.card {\n  color: red;\n}\n"
+        );
+        let removed: Vec<(&str, &str)> = report
+            .removed
+            .iter()
+            .map(|rule| (rule.classes.as_str(), rule.name.as_str()))
+            .collect();
+        assert_eq!(removed, [("m-2 card", "m2"), ("md:text-center", "mdtextcenter")]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_flush_css_writes_pending_files() {
+        let root = std::env::temp_dir().join(format!("headwind-flush-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("headwind.toml"), "root = true\nnamingMode = \"readable\"\n").unwrap();
+        std::fs::write(root.join("A.tsx"), "const a = <div className=\"p-4\"/>;\n").unwrap();
+        std::fs::write(root.join("B.tsx"), "const b = <div className=\"m-2\"/>;\n").unwrap();
+
+        let mut session = ConfigSession::new(HeadwindConfig::default()).unwrap();
+        session.transform_file(&root.join("A.tsx")).unwrap().unwrap();
+        session.transform_file(&root.join("B.tsx")).unwrap().unwrap();
+        let mut out = String::new();
+        session.flush_css(&mut out).unwrap();
+        assert!(out.contains(".p4 {") && out.contains(".m2 {"), "{}", out);
+
+        // 只写出 flush 之后重新转换的文件
+        std::fs::write(root.join("B.tsx"), "const b = <div className=\"m-4\"/>;\n").unwrap();
+        session.transform_file(&root.join("B.tsx")).unwrap().unwrap();
+        let mut out = String::new();
+        session.flush_css(&mut out).unwrap();
+        assert!(out.contains(".m4 {") && !out.contains(".p4"), "{}", out);
+
+        let mut out = String::new();
+        session.flush_css(&mut out).unwrap();
+        assert!(out.is_empty());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_rename_generated_class() {
+        let root = std::env::temp_dir().join(format!("headwind-rename-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("modules")).unwrap();
+        std::fs::write(root.join("headwind.toml"), "root = true\nnamingMode = \"camelCase\"\n").unwrap();
+        std::fs::write(
+            root.join("modules/headwind.toml"),
+            "outputMode = { type = \"cssModules\" }\n",
+        )
+        .unwrap();
+        let (a, b) = (root.join("A.tsx"), root.join("modules/B.tsx"));
+        let original = "const a = <div className=\"p-4 m-2\"/>;\n";
+        std::fs::write(&a, original).unwrap();
+        std::fs::write(&b, original).unwrap();
+
+        // 迁移后把转换结果写回源文件
+        let mut session = ConfigSession::new(HeadwindConfig::default()).unwrap();
+        for path in [&a, &b] {
+            let result = session.transform_file(path).unwrap().unwrap();
+            std::fs::write(path, &result.code).unwrap();
+        }
+        let old = session.reports()[&a].class_map["p-4 m-2"].clone();
+        assert_eq!(session.reports()[&b].class_map["p-4 m-2"], old);
+
+        assert!(session.rename_generated_class("missing", "card").is_err());
+        assert!(session.rename_generated_class(&old, "1card").is_err());
+        let rename = session.rename_generated_class(&old, "card-body").unwrap();
+        assert_eq!(rename.classes, ["p-4 m-2"]);
+        let a_code = rename.apply_to_source(&a, &std::fs::read_to_string(&a).unwrap());
+        assert!(a_code.contains("className=\"card-body\""), "{}", a_code);
+        let b_code = rename.apply_to_source(&b, &std::fs::read_to_string(&b).unwrap());
+        assert!(b_code.contains("styles[\"card-body\"]"), "{}", b_code);
+        assert_eq!(session.reports()[&b].class_map["p-4 m-2"], "card-body");
+
+        let mut css = String::new();
+        session.flush_css(&mut css).unwrap();
+        assert!(css.contains(".card-body {") && !css.contains(&old), "{}", css);
+        assert_eq!(
+            rename.rename_css(&format!(".{} {{}}\n[data-hw=\"{}\"] {{}}\n.{}-x {{}}", old, old, old)),
+            format!(".card-body {{}}\n[data-hw=\"card-body\"] {{}}\n.{}-x {{}}", old)
+        );
+
+        // 之后转换同一组类时固定使用新名
+        std::fs::write(&a, original).unwrap();
+        let result = session.transform_file(&a).unwrap().unwrap();
+        assert_eq!(result.class_map["p-4 m-2"], "card-body");
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_naming_stats_report_cross_file_collisions() {
+        let root = std::env::temp_dir().join(format!("headwind-names-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("headwind.toml"), "root = true\nnamingMode = \"readable\"\n").unwrap();
+        // Readable 命名截断为 8 个字符，两个文件各自得到 `bgblue50`
+        std::fs::write(root.join("A.tsx"), "const a = <div className=\"bg-blue-500\"/>;\n").unwrap();
+        std::fs::write(root.join("B.tsx"), "const b = <div className=\"bg-blue-50\"/>;\n").unwrap();
+
+        let mut session = ConfigSession::new(HeadwindConfig::default()).unwrap();
+        session.transform_file(&root.join("B.tsx")).unwrap().unwrap();
+        session.transform_file(&root.join("A.tsx")).unwrap().unwrap();
+        let stats = session.naming_stats();
+        assert_eq!(stats.names.len(), 2);
+        assert_eq!(stats.collisions.len(), 1);
+        let collision = &stats.collisions[0];
+        assert_eq!(collision.existing, "bg-blue-500");
+        assert_eq!(collision.classes, "bg-blue-50");
+        assert_eq!(collision.resolved, None);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,56 @@
+//! Shadow DOM 样式模块
+//!
+//! Lit / Stencil 等 Web Component 的样式需要放进 shadow root，全局 CSS 文件无法生效。
+//! 该模式把生成的 CSS 包装成一个 JS 模块（默认导出样式表），组件 import 后自行 adopt。
+
+/// 样式模块的导出形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StyleSheetFormat {
+    /// 导出 constructable stylesheet：`new CSSStyleSheet()` + `replaceSync`，
+    /// 用于 `shadowRoot.adoptedStyleSheets`
+    #[default]
+    Constructable,
+    /// 导出 `<style>...</style>` 字符串，用于直接写入 shadow root 的 innerHTML
+    StyleElement,
+}
+
+/// 生成样式模块源码
+///
+/// ```
+/// use headwind_transform::{style_module_code, StyleSheetFormat};
+///
+/// let code = style_module_code(".c_1 { padding: 1rem; }\n", StyleSheetFormat::StyleElement);
+/// assert_eq!(code, "export default `<style>.c_1 { padding: 1rem; }\n</style>`;\n");
+/// ```
+pub fn style_module_code(css: &str, format: StyleSheetFormat) -> String {
+    let css = escape_template_literal(css);
+    match format {
+        StyleSheetFormat::Constructable => format!(
+            "const sheet = new CSSStyleSheet();\nsheet.replaceSync(`{}`);\nexport default sheet;\n",
+            css
+        ),
+        StyleSheetFormat::StyleElement => format!("export default `<style>{}</style>`;\n", css),
+    }
+}
+
+/// 转义模板字面量中的 `\`、`` ` `` 与 `${`
+fn escape_template_literal(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constructable_escapes_css() {
+        let css = ".c_1::before { content: \"\\2022 `${x}`\"; }\n";
+        let code = style_module_code(css, StyleSheetFormat::Constructable);
+        assert_eq!(
+            code,
+            "const sheet = new CSSStyleSheet();\nsheet.replaceSync(`.c_1::before { content: \"\\\\2022 \\`\\${x}\\`\"; }\n`);\nexport default sheet;\n"
+        );
+    }
+}
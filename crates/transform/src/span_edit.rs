@@ -0,0 +1,128 @@
+//! 基于 span 的原地编辑
+//!
+//! SWC codegen 会重新打印整个模块（引号、空白、JSX 换行都会变化），
+//! 在已有代码库上做 codemod 时会产生大量无关 diff。
+//! 该模式下 visitor 只记录「需要替换的字节区间 + 新文本」，
+//! 最后直接在原始源码上拼接，未触及的字节保持原样。
+
+use swc_core::common::{BytePos, Span};
+
+/// 单个文本编辑：将原始源码 `[start, end)` 字节区间替换为 `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+impl SpanEdit {
+    /// 插入编辑（不删除任何字节）
+    pub fn insert(pos: usize, text: impl Into<String>) -> Self {
+        Self {
+            start: pos,
+            end: pos,
+            text: text.into(),
+        }
+    }
+}
+
+/// 记录待应用的编辑；span 为 SWC 的全局 BytePos，需要减去文件起始偏移
+#[derive(Debug)]
+pub(crate) struct EditRecorder {
+    base: BytePos,
+    edits: Vec<SpanEdit>,
+}
+
+impl EditRecorder {
+    pub(crate) fn new(base: BytePos) -> Self {
+        Self {
+            base,
+            edits: Vec::new(),
+        }
+    }
+
+    /// 替换整个 span
+    pub(crate) fn replace(&mut self, span: Span, text: impl Into<String>) {
+        self.edits.push(SpanEdit {
+            start: (span.lo.0 - self.base.0) as usize,
+            end: (span.hi.0 - self.base.0) as usize,
+            text: text.into(),
+        });
+    }
+
+    /// 替换 span 去掉首尾各一个字节（引号）后的内部区间，保留原引号风格
+    pub(crate) fn replace_inner(&mut self, span: Span, text: impl Into<String>) {
+        let start = (span.lo.0 - self.base.0) as usize + 1;
+        let end = ((span.hi.0 - self.base.0) as usize).saturating_sub(1).max(start);
+        self.edits.push(SpanEdit {
+            start,
+            end,
+            text: text.into(),
+        });
+    }
+
+    pub(crate) fn into_edits(self) -> Vec<SpanEdit> {
+        self.edits
+    }
+}
+
+/// 将编辑应用到源码上
+///
+/// 编辑按起始位置排序后依次拼接；与前一个编辑重叠的编辑会被丢弃，
+/// 同一位置的多个插入保持记录顺序。
+pub fn apply_edits(source: &str, edits: &[SpanEdit]) -> String {
+    let mut sorted: Vec<&SpanEdit> = edits.iter().collect();
+    sorted.sort_by_key(|e| (e.start, e.end));
+
+    let mut out = String::with_capacity(source.len());
+    let mut cursor = 0;
+    for edit in sorted {
+        if edit.start < cursor || edit.end > source.len() {
+            continue;
+        }
+        out.push_str(&source[cursor..edit.start]);
+        out.push_str(&edit.text);
+        cursor = edit.end;
+    }
+    out.push_str(&source[cursor..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_edits_replace_and_insert() {
+        let source = r#"<div className="p-4">x</div>"#;
+        let edits = vec![
+            SpanEdit {
+                start: 16,
+                end: 19,
+                text: "c_1".to_string(),
+            },
+            SpanEdit::insert(0, "import './a.css';\n"),
+        ];
+        assert_eq!(
+            apply_edits(source, &edits),
+            "import './a.css';\n<div className=\"c_1\">x</div>"
+        );
+    }
+
+    #[test]
+    fn test_apply_edits_skips_overlap() {
+        let edits = vec![
+            SpanEdit {
+                start: 0,
+                end: 3,
+                text: "X".to_string(),
+            },
+            SpanEdit {
+                start: 2,
+                end: 4,
+                text: "Y".to_string(),
+            },
+        ];
+        assert_eq!(apply_edits("abcdef", &edits), "Xdef");
+    }
+}
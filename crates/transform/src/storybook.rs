@@ -0,0 +1,135 @@
+//! Storybook 清单
+//!
+//! 按 React 组件汇总会话中生成的类与 CSS，输出供 Storybook 插件读取的 JSON 清单，
+//! 迁移后的视觉评审可以逐个 story 对照组件用到的类和生成的规则。
+//! 组件分组来自元素树（`element_tree`），未开启元素树的文件与 HTML 文件不出现在清单中。
+
+use crate::css_module::{extract_css_class_names, split_top_level_blocks};
+use crate::session::ConfigSession;
+use crate::TransformResult;
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// 一个组件的生成类与 CSS
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoryComponent {
+    /// 组件名，与 story 的 `component` 对应
+    pub name: String,
+    /// 组件所在文件
+    pub file: PathBuf,
+    /// 原始类字符串 → 生成的类名，按组件内出现顺序
+    pub classes: IndexMap<String, String>,
+    /// 组件生成名的 CSS 规则块（含 `@media` 等包裹的块），以空行分隔
+    pub css: String,
+}
+
+/// Storybook 清单，组件按文件路径、文件内出现顺序排列
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StorybookManifest {
+    pub components: Vec<StoryComponent>,
+}
+
+impl StorybookManifest {
+    /// JSON 输出
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("StorybookManifest 序列化不会失败")
+    }
+}
+
+/// 汇总会话中已转换文件的组件（按路径顺序，结果与转换顺序无关）
+pub fn storybook_manifest(session: &ConfigSession) -> StorybookManifest {
+    StorybookManifest {
+        components: session
+            .reports_by_path()
+            .into_iter()
+            .flat_map(|report| report.components.iter().cloned())
+            .collect(),
+    }
+}
+
+/// 按组件拆分一次转换的类名映射与 CSS；没有生成名的组件跳过
+pub(crate) fn file_components(path: &Path, result: &TransformResult) -> Vec<StoryComponent> {
+    let blocks = split_top_level_blocks(&result.css);
+    result
+        .components
+        .iter()
+        .filter_map(|(name, class_strings)| {
+            let classes: IndexMap<String, String> = class_strings
+                .iter()
+                .filter_map(|classes| {
+                    let generated = result.class_map.get(classes)?;
+                    Some((classes.clone(), generated.clone()))
+                })
+                .collect();
+            if classes.is_empty() {
+                return None;
+            }
+            let names: HashSet<&str> = classes
+                .values()
+                .flat_map(|generated| generated.split_whitespace())
+                .collect();
+            let css = blocks
+                .iter()
+                .filter(|block| {
+                    extract_css_class_names(block)
+                        .iter()
+                        .any(|class| names.contains(class.as_str()))
+                })
+                .copied()
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            Some(StoryComponent {
+                name: name.clone(),
+                file: path.to_path_buf(),
+                classes,
+                css,
+            })
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "ecmascript"))]
+mod tests {
+    use super::*;
+    use crate::{transform_jsx, TransformOptions};
+
+    #[test]
+    fn test_file_components() {
+        let source = r#"
+function Button() {
+  return <button className="px-4 py-2 md:px-8">Go</button>;
+}
+function Card() {
+  return <div className="p-4 shadow"><Button /><p className="px-4 py-2 md:px-8">x</p></div>;
+}
+"#;
+        let options = TransformOptions {
+            element_tree: true,
+            ..Default::default()
+        };
+        let result = transform_jsx(source, "Card.tsx", options).unwrap();
+        let components = file_components(Path::new("Card.tsx"), &result);
+        assert_eq!(components.len(), 2);
+
+        let button = &components[0];
+        assert_eq!(button.name, "Button");
+        let name = &result.class_map["px-4 py-2 md:px-8"];
+        assert_eq!(button.classes.get("px-4 py-2 md:px-8"), Some(name));
+        assert!(button.css.contains(&format!(".{} {{", name)), "{}", button.css);
+        assert!(button.css.contains("@media"), "{}", button.css);
+        assert!(!button.css.contains(":root"));
+
+        let card = &components[1];
+        assert_eq!(
+            card.classes.keys().collect::<Vec<_>>(),
+            ["p-4 shadow", "px-4 py-2 md:px-8"]
+        );
+
+        let manifest = StorybookManifest { components };
+        let json: serde_json::Value = serde_json::from_str(&manifest.to_json()).unwrap();
+        assert_eq!(json["components"][1]["file"], "Card.tsx");
+    }
+}
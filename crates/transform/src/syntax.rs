@@ -0,0 +1,105 @@
+//! 源码语法选择
+//!
+//! 按扩展名决定 TypeScript / JavaScript 与是否允许 JSX：
+//!
+//! | 扩展名 | 语法 |
+//! |--------|------|
+//! | `.tsx` | TypeScript + JSX |
+//! | `.ts` / `.mts` / `.cts` | TypeScript（`jsx_pragma` 开启且文件带 JSX pragma 时按 TSX） |
+//! | 其余（`.jsx` / `.js` / `.mjs` / `.cjs`） | JavaScript + JSX |
+//!
+//! 装饰器、import attributes 等尚未默认开启的语法通过 `SyntaxOptions` 打开。
+
+#[cfg(feature = "ecmascript")]
+use swc_core::ecma::parser::{EsSyntax, Syntax, TsSyntax};
+
+/// 解析器语法特性开关
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SyntaxOptions {
+    /// 装饰器（`@Component class Foo {}`），同时允许写在 `export` 之前和 `accessor` 字段
+    pub decorators: bool,
+    /// import attributes（`import data from "./data.json" with { type: "json" }`）
+    ///
+    /// 仅对 JavaScript 文件生效，TypeScript 语法始终支持。
+    pub import_attributes: bool,
+    /// 识别 `@jsx` / `@jsxImportSource` / `@jsxFrag` / `@jsxRuntime` 注释
+    ///
+    /// Preact、Solid 等非 React 运行时常在 `.ts` 文件中用 pragma 声明 JSX 工厂，
+    /// 开启后带有这类注释的 `.ts` / `.mts` / `.cts` 文件按 TSX 解析。
+    /// 默认关闭：TSX 不支持 `<T>value` 形式的类型断言。
+    pub jsx_pragma: bool,
+}
+
+/// 根据文件名、源码和语法选项选择 SWC 语法
+#[cfg(feature = "ecmascript")]
+pub(crate) fn syntax_for(filename: &str, source: &str, options: &SyntaxOptions) -> Syntax {
+    let extension = filename.rsplit_once('.').map_or("", |(_, ext)| ext);
+    match extension {
+        "tsx" | "ts" | "mts" | "cts" => Syntax::Typescript(TsSyntax {
+            tsx: extension == "tsx" || (options.jsx_pragma && has_jsx_pragma(source)),
+            decorators: options.decorators,
+            ..Default::default()
+        }),
+        _ => Syntax::Es(EsSyntax {
+            jsx: true,
+            decorators: options.decorators,
+            decorators_before_export: options.decorators,
+            auto_accessors: options.decorators,
+            import_attributes: options.import_attributes,
+            ..Default::default()
+        }),
+    }
+}
+
+/// 源码中是否有 JSX pragma 注释
+///
+/// 只做文本匹配，不区分注释位置；误判的代价只是多允许 JSX 语法。
+#[cfg(feature = "ecmascript")]
+fn has_jsx_pragma(source: &str) -> bool {
+    source.match_indices("@jsx").any(|(i, _)| {
+        let rest = &source[i + "@jsx".len()..];
+        ["ImportSource", "Frag", "Runtime", " ", "\t"]
+            .iter()
+            .any(|suffix| rest.starts_with(suffix))
+    })
+}
+
+#[cfg(all(test, feature = "ecmascript"))]
+mod tests {
+    use super::*;
+
+    fn is_tsx(syntax: Syntax) -> Option<bool> {
+        match syntax {
+            Syntax::Typescript(ts) => Some(ts.tsx),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_syntax_by_extension() {
+        let options = SyntaxOptions::default();
+        assert_eq!(is_tsx(syntax_for("App.tsx", "", &options)), Some(true));
+        for name in ["a.ts", "a.mts", "a.cts", "src/lib.d.ts"] {
+            assert_eq!(is_tsx(syntax_for(name, "", &options)), Some(false), "{}", name);
+        }
+        for name in ["a.js", "a.jsx", "a.mjs", "a.cjs", "Makefile"] {
+            assert!(matches!(syntax_for(name, "", &options), Syntax::Es(es) if es.jsx), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_jsx_pragma_enables_tsx() {
+        let source = "/** @jsxImportSource preact */\nexport const A = () => <div />;";
+        let plain = "const n = <number>value;";
+        assert_eq!(is_tsx(syntax_for("a.ts", source, &SyntaxOptions::default())), Some(false));
+
+        let options = SyntaxOptions {
+            jsx_pragma: true,
+            ..Default::default()
+        };
+        assert_eq!(is_tsx(syntax_for("a.mts", source, &options)), Some(true));
+        assert_eq!(is_tsx(syntax_for("a.ts", "/** @jsx h */", &options)), Some(true));
+        assert_eq!(is_tsx(syntax_for("a.ts", plain, &options)), Some(false));
+        assert_eq!(is_tsx(syntax_for("a.ts", "// see @jsxdom", &options)), Some(false));
+    }
+}
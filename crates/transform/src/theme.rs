@@ -0,0 +1,59 @@
+//! 暗色主题变量
+//!
+//! 主题变量以 `var(--color-*)` 等引用输出（`ColorMode::Var`）时，`:root` 定义亮色值，
+//! 暗色主题在其后追加一个覆盖同名变量的规则块：类选择器（`.dark { ... }`）或
+//! `@media (prefers-color-scheme: dark)`，同一份样式表即可支持两套主题。
+
+use indexmap::IndexMap;
+
+/// 暗色主题配置，见 `TransformOptions::dark_theme`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DarkTheme {
+    /// 变量名 → 暗色值（`"--color-gray-900"` → `"#f9fafb"`）
+    pub variables: IndexMap<String, String>,
+    /// 暗色块的选择器（如 `.dark`），None 时以 `@media (prefers-color-scheme: dark)` 包裹 `:root`
+    pub selector: Option<String>,
+}
+
+impl DarkTheme {
+    /// 没有配置任何暗色变量
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    /// `include` 返回 true 的变量的暗色规则块，没有这样的变量时为空字符串
+    ///
+    /// ```
+    /// use headwind_transform::DarkTheme;
+    ///
+    /// let theme = DarkTheme {
+    ///     variables: [("--color-white".to_string(), "#09090b".to_string())].into_iter().collect(),
+    ///     selector: Some(".dark".to_string()),
+    /// };
+    /// assert_eq!(theme.css(|_| true), ".dark {\n  --color-white: #09090b;\n}");
+    /// assert_eq!(theme.css(|name| name != "--color-white"), "");
+    /// ```
+    pub fn css(&self, include: impl Fn(&str) -> bool) -> String {
+        let variables: Vec<_> = self
+            .variables
+            .iter()
+            .filter(|(name, _)| include(name))
+            .collect();
+        if variables.is_empty() {
+            return String::new();
+        }
+        let (mut css, indent, close) = match &self.selector {
+            Some(selector) => (format!("{} {{\n", selector), "  ", "}"),
+            None => (
+                "@media (prefers-color-scheme: dark) {\n  :root {\n".to_string(),
+                "    ",
+                "  }\n}",
+            ),
+        };
+        for (name, value) in variables {
+            css.push_str(&format!("{}{}: {};\n", indent, name, value));
+        }
+        css.push_str(close);
+        css
+    }
+}
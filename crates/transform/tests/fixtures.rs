@@ -0,0 +1,123 @@
+//! 固定项目集成测试
+//!
+//! `tests/fixtures/<项目>/input` 是一个带 `headwind.toml` 的小型项目，
+//! `expected` 按相同的相对路径存放每个源文件的转换结果：
+//!
+//! - `<文件>`：转换后的代码
+//! - `<文件>.css`：生成的 CSS
+//!
+//! 转换器行为有意变化时，设置 `HEADWIND_UPDATE_FIXTURES=1` 重新运行即可改写 golden 文件，
+//! 再通过 git diff 审阅输出变化。
+
+use headwind_transform::{transform_file, HeadwindConfig};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const UPDATE_ENV: &str = "HEADWIND_UPDATE_FIXTURES";
+const SOURCE_EXTENSIONS: &[&str] = &["tsx", "jsx", "ts", "js", "mts", "mjs", "html", "htm"];
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// 按路径排序的子目录
+fn sorted_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("无法读取 {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// 递归收集源文件，按路径排序
+fn source_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("无法读取 {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            source_files(&path, files);
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+        {
+            files.push(path);
+        }
+    }
+}
+
+/// 与 golden 文件比较；不一致时返回包含首个差异行的说明
+fn check_golden(path: &Path, actual: &str, update: bool) -> Option<String> {
+    if update {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, actual).unwrap();
+        return None;
+    }
+    let Ok(expected) = fs::read_to_string(path) else {
+        return Some(format!(
+            "缺少 {}，设置 {}=1 运行以生成",
+            path.display(),
+            UPDATE_ENV
+        ));
+    };
+    if expected == actual {
+        return None;
+    }
+    let (line, expected_line, actual_line) = expected
+        .lines()
+        .map(Some)
+        .chain(std::iter::repeat(None))
+        .zip(actual.lines().map(Some).chain(std::iter::repeat(None)))
+        .enumerate()
+        .find(|(_, (e, a))| e != a)
+        .map(|(i, (e, a))| (i + 1, e.unwrap_or("<EOF>"), a.unwrap_or("<EOF>")))
+        .unwrap_or((0, "<末尾换行不同>", ""));
+    Some(format!(
+        "{} 第 {} 行不一致\n  expected: {}\n  actual:   {}",
+        path.display(),
+        line,
+        expected_line,
+        actual_line
+    ))
+}
+
+#[test]
+fn test_fixture_projects_match_golden_output() {
+    let update = std::env::var_os(UPDATE_ENV).is_some();
+    let mut failures = Vec::new();
+    let mut checked = 0;
+
+    for project in sorted_dirs(&fixtures_dir()) {
+        let input = project.join("input");
+        let expected = project.join("expected");
+        let mut files = Vec::new();
+        source_files(&input, &mut files);
+        assert!(!files.is_empty(), "{} 中没有源文件", input.display());
+
+        for file in files {
+            let result = match transform_file(&file, None, HeadwindConfig::default()) {
+                Ok(Some(result)) => result,
+                // 被 include / exclude 排除的文件不产出 golden
+                Ok(None) => continue,
+                Err(e) => {
+                    failures.push(format!("{} 转换失败: {}", file.display(), e));
+                    continue;
+                }
+            };
+            let relative = file.strip_prefix(&input).unwrap();
+            let code_path = expected.join(relative);
+            let css_path = expected.join(format!("{}.css", relative.display()));
+            failures.extend(check_golden(&code_path, &result.code, update));
+            failures.extend(check_golden(&css_path, &result.css, update));
+            checked += 1;
+        }
+    }
+
+    assert!(checked > 0, "没有找到任何 fixture 源文件");
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
@@ -0,0 +1,23 @@
+import styles from "./page.module.css";
+import Link from "next/link";
+import { Hero } from "../components/Hero";
+
+export const metadata = {
+  title: "Headwind",
+};
+
+export default function Page() {
+  return (
+    <main className={styles.c_4af43245008f}>
+      <Hero title="Atomic to semantic" />
+      <div className={styles.c_b5a3d42ba29b}>
+        <Link href="/docs" className={styles.c_880c9981b163}>
+          Docs
+        </Link>
+        <Link href="/playground" className={styles.c_880c9981b163}>
+          Playground
+        </Link>
+      </div>
+    </main>
+  );
+}
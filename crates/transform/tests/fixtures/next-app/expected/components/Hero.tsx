@@ -0,0 +1,20 @@
+"use client";
+import styles from "./Hero.module.css";
+
+import { useState } from "react";
+
+export function Hero({ title }: { title: string }) {
+  const [count, setCount] = useState(0);
+  return (
+    <section className={styles.c_0f238935f2ae}>
+      <h1 className={styles.c_538607730db1}>{title}</h1>
+      <button
+        type="button"
+        className={styles.c_9e21bb8d7ba8}
+        onClick={() => setCount(count + 1)}
+      >
+        Clicked {count} times
+      </button>
+    </section>
+  );
+}
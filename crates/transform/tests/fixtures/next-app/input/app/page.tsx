@@ -0,0 +1,22 @@
+import Link from "next/link";
+import { Hero } from "../components/Hero";
+
+export const metadata = {
+  title: "Headwind",
+};
+
+export default function Page() {
+  return (
+    <main className="flex min-h-screen flex-col items-center justify-between p-24">
+      <Hero title="Atomic to semantic" />
+      <div className="grid w-full max-w-5xl gap-4 md:grid-cols-3">
+        <Link href="/docs" className="rounded-lg border px-5 py-4 hover:bg-gray-100">
+          Docs
+        </Link>
+        <Link href="/playground" className="rounded-lg border px-5 py-4 hover:bg-gray-100">
+          Playground
+        </Link>
+      </div>
+    </main>
+  );
+}
@@ -0,0 +1,19 @@
+"use client";
+
+import { useState } from "react";
+
+export function Hero({ title }: { title: string }) {
+  const [count, setCount] = useState(0);
+  return (
+    <section className="text-center">
+      <h1 className="text-4xl font-bold tracking-tight text-gray-900 sm:text-6xl">{title}</h1>
+      <button
+        type="button"
+        className="mt-6 rounded-md bg-indigo-600 px-3.5 py-2.5 text-sm font-semibold text-white"
+        onClick={() => setCount(count + 1)}
+      >
+        Clicked {count} times
+      </button>
+    </section>
+  );
+}
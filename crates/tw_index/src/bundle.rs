@@ -1,8 +1,12 @@
+use crate::index::TailwindIndex;
+use crate::loader::load_index;
 use crate::merge::merge_declarations;
 use crate::naming::create_naming_strategy;
 use crate::normalize::normalize_classes;
 use crate::shorthand::optimize_shorthands;
-use headwind_core::{BundleRequest, BundleResult, Declaration, Diagnostic};
+use headwind_core::{BundleRequest, BundleResult, Declaration, Diagnostic, TailwindVersion};
+use serde::Deserialize;
+use std::sync::Arc;
 
 /// 主 bundle 函数
 ///
@@ -54,6 +58,47 @@ pub trait TailwindIndexLookup {
     fn lookup(&self, class: &str) -> Option<&[Declaration]>;
 }
 
+/// 针对特定 Tailwind 版本生成的索引数据包
+///
+/// `Bundler::with_versioned_index` 同时设置回退索引与目标版本，
+/// 使 `shadow-sm`、`rounded` 等在两个版本间取值不同的类与数据集保持一致。
+#[derive(Clone)]
+pub struct VersionedIndex {
+    /// 数据包声明的 Tailwind 版本；普通映射数组没有版本信息
+    pub version: Option<TailwindVersion>,
+    pub index: Arc<TailwindIndex>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionedArtifact {
+    tailwind_version: String,
+    classes: serde_json::Value,
+}
+
+/// 加载带版本的索引数据包
+///
+/// 格式为 `{ "tailwindVersion": "3.4", "classes": [...] }`，`classes` 与 `load_index` 的条目格式相同；
+/// 也接受不带版本信息的映射数组（`version` 为 None）。
+pub fn load_versioned_index(json_str: &str) -> Result<VersionedIndex, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json_str).map_err(|e| format!("索引 JSON 解析失败: {}", e))?;
+    let (version, classes) = if value.is_array() {
+        (None, value)
+    } else {
+        let artifact: VersionedArtifact =
+            serde_json::from_value(value).map_err(|e| format!("索引数据包格式无效: {}", e))?;
+        let version = TailwindVersion::parse(&artifact.tailwind_version)
+            .ok_or_else(|| format!("不支持的 Tailwind 版本: {}", artifact.tailwind_version))?;
+        (Some(version), artifact.classes)
+    };
+    let index = load_index(&classes.to_string()).map_err(|e| format!("索引条目无效: {}", e))?;
+    Ok(VersionedIndex {
+        version,
+        index: Arc::new(index),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +211,21 @@ mod tests {
         assert_eq!(result.css_declarations[0].property, "padding");
         assert_eq!(result.css_declarations[0].value, "2rem");
     }
+
+    #[test]
+    fn test_load_versioned_index() {
+        let artifact = load_versioned_index(
+            r#"{ "tailwindVersion": "v3.4.17", "classes": [{ "class": "shadow-inner", "css": "box-shadow: inset 0 2px 4px 0 rgb(0 0 0 / 0.05)" }] }"#,
+        )
+        .unwrap();
+        assert_eq!(artifact.version, Some(TailwindVersion::V3));
+        assert!(artifact.index.lookup("shadow-inner").is_some());
+
+        let plain = load_versioned_index(r#"[{ "class": "p-4", "css": "padding: 1rem" }]"#).unwrap();
+        assert_eq!(plain.version, None);
+        assert_eq!(plain.index.len(), 1);
+
+        assert!(load_versioned_index(r#"{ "tailwindVersion": "2.2", "classes": [] }"#).is_err());
+        assert!(load_versioned_index(r#"{ "classes": [] }"#).is_err());
+    }
 }
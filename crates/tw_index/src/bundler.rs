@@ -1,20 +1,41 @@
+use crate::bundle::VersionedIndex;
+use crate::cache::{CachedClass, ConversionCache};
 use crate::context::ClassContext;
 use crate::converter::Converter;
-use crate::variant::{self, pseudo_class_selector, pseudo_element_selector, StateResolution};
-use headwind_core::{ColorMode, Declaration};
+use crate::index::TailwindIndex;
+use crate::fallback::{
+    add_color_fallbacks, color_fallback, needs_color_fallback, OKLCH_SUPPORTS,
+};
+use crate::prefix::add_vendor_prefixes;
+use crate::suggest::{self, Suggestion};
+use crate::variant::{
+    self, expand_variant_aliases, pseudo_element_selector, sorted_by_variant, CustomVariant,
+    StateResolution,
+};
+use crate::utility::{CustomUtilities, CustomUtility};
+use headwind_core::{
+    BreakpointStyle, ColorMode, ContainerStyle, CssVariableOverrides, Declaration, TailwindVersion,
+    Targets, UnitConversion,
+};
 use crate::css::{create_stylesheet, emit_css};
-use headwind_tw_parse::{parse_class, parse_classes, Modifier, ParsedClass};
+use headwind_tw_parse::parser::ParseError;
+use headwind_tw_parse::{parse_class, parse_modifiers_from_raw, Modifier};
 use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
 
 /// CSS 规则组，按修饰符分组
+///
+/// 每个修饰符对应一层嵌套的 `RuleGroup`，叠加的修饰符按书写顺序逐层嵌套，
+/// 如 `dark:md:hover:underline` → `states["dark"].responsive["md"].pseudo_classes["hover"].base`；
+/// 伪元素总在最内层，`before:hover:p-4` → `pseudo_classes["hover"].pseudo_elements["before"].base`
 #[derive(Debug, Clone)]
 pub struct RuleGroup {
     /// 基础规则（无修饰符）
     pub base: Vec<Declaration>,
     /// 伪类规则（如 :hover, :focus）
-    pub pseudo_classes: HashMap<String, Vec<Declaration>>,
+    pub pseudo_classes: HashMap<String, Box<RuleGroup>>,
     /// 伪元素规则（如 ::before, ::after）
-    pub pseudo_elements: HashMap<String, Vec<Declaration>>,
+    pub pseudo_elements: HashMap<String, Box<RuleGroup>>,
     /// 响应式规则（如 @media）
     pub responsive: HashMap<String, Box<RuleGroup>>,
     /// 状态规则（如 .dark, .group-hover）
@@ -32,56 +53,38 @@ impl RuleGroup {
         }
     }
 
-    /// 添加声明到对应的组
+    /// 添加声明到对应的组：第一个修饰符决定分组，其余修饰符递归写入该分组
     fn add_declarations(&mut self, modifiers: &[Modifier], declarations: Vec<Declaration>) {
-        if modifiers.is_empty() {
+        let Some((first, rest)) = modifiers.split_first() else {
             // 无修饰符，添加到基础规则
             self.base.extend(declarations);
-        } else {
-            // 处理第一个修饰符
-            match &modifiers[0] {
-                Modifier::PseudoClass(name) => {
-                    if modifiers.len() == 1 {
-                        self.pseudo_classes
-                            .entry(name.clone())
-                            .or_insert_with(Vec::new)
-                            .extend(declarations);
-                    } else {
-                        // 有更多修饰符，递归处理
-                        self.pseudo_classes
-                            .entry(name.clone())
-                            .or_insert_with(Vec::new)
-                            .extend(declarations);
-                    }
-                }
-                Modifier::PseudoElement(name) => {
-                    self.pseudo_elements
-                        .entry(name.clone())
-                        .or_insert_with(Vec::new)
-                        .extend(declarations);
-                }
-                Modifier::Responsive(size) => {
-                    let group = self
-                        .responsive
-                        .entry(size.clone())
-                        .or_insert_with(|| Box::new(RuleGroup::new()));
-                    group.add_declarations(&modifiers[1..], declarations);
-                }
-                Modifier::State(state) => {
-                    let group = self
-                        .states
-                        .entry(state.clone())
-                        .or_insert_with(|| Box::new(RuleGroup::new()));
-                    group.add_declarations(&modifiers[1..], declarations);
-                }
-                Modifier::Custom(name) => {
-                    self.pseudo_classes
-                        .entry(name.clone())
-                        .or_insert_with(Vec::new)
-                        .extend(declarations);
-                }
+            return;
+        };
+        let (groups, name) = match first {
+            Modifier::PseudoClass(name) | Modifier::Custom(name) => {
+                (&mut self.pseudo_classes, name)
             }
-        }
+            Modifier::PseudoElement(name) => (&mut self.pseudo_elements, name),
+            Modifier::Responsive(size) => (&mut self.responsive, size),
+            Modifier::State(state) => (&mut self.states, state),
+        };
+        groups
+            .entry(name.clone())
+            .or_default()
+            .add_declarations(rest, declarations);
+    }
+
+    /// 是否不含任何声明（包括嵌套分组）
+    pub fn is_empty(&self) -> bool {
+        self.base.is_empty()
+            && [
+                &self.pseudo_classes,
+                &self.pseudo_elements,
+                &self.responsive,
+                &self.states,
+            ]
+            .iter()
+            .all(|groups| groups.values().all(|group| group.is_empty()))
     }
 }
 
@@ -91,17 +94,38 @@ impl Default for RuleGroup {
     }
 }
 
+/// 单个类的转换结果：(raw_modifiers, 声明)，声明为 None 表示无法识别
+type ConvertedClass = (String, Option<Vec<Declaration>>);
+
 /// Tailwind 类打包器
 ///
 /// 将多个 Tailwind 类整理成一个 CSS 类，并按修饰符分组
 pub struct Bundler {
     converter: Converter,
+    /// 类级转换缓存，None 时每次都重新解析、转换
+    cache: Option<Arc<ConversionCache>>,
+    /// `container` 的居中与内边距
+    container: ContainerStyle,
+    /// 目标浏览器，决定补充的厂商前缀与颜色回退
+    targets: Targets,
+    /// 手动指定声明的工具类（不含变体）→ 声明，优先于转换器
+    overrides: HashMap<String, Vec<Declaration>>,
+    /// 自定义变体别名 → 展开的变体（如 `hocus` → `hover`、`focus`）
+    variant_aliases: HashMap<String, Vec<String>>,
+    /// `@custom-variant` 定义的变体（变体名 → 定义）
+    custom_variants: Arc<HashMap<String, CustomVariant>>,
 }
 
 impl Bundler {
     pub fn new() -> Self {
         Self {
             converter: Converter::new(),
+            cache: None,
+            container: ContainerStyle::default(),
+            targets: Targets::default(),
+            overrides: HashMap::new(),
+            variant_aliases: HashMap::new(),
+            custom_variants: Arc::default(),
         }
     }
 
@@ -109,6 +133,12 @@ impl Bundler {
     pub fn with_inline() -> Self {
         Self {
             converter: Converter::with_inline(),
+            cache: None,
+            container: ContainerStyle::default(),
+            targets: Targets::default(),
+            overrides: HashMap::new(),
+            variant_aliases: HashMap::new(),
+            custom_variants: Arc::default(),
         }
     }
 
@@ -124,26 +154,294 @@ impl Bundler {
         self
     }
 
+    /// 设置断点 at-rule 的单位与写法（builder 模式）
+    pub fn with_breakpoints(mut self, style: BreakpointStyle) -> Self {
+        self.converter = self.converter.with_breakpoints(style);
+        self
+    }
+
+    /// 按主题类别覆盖变量 / 内联（builder 模式），如只让阴影保留 `var(--shadow-*)`
+    pub fn with_variable_overrides(mut self, overrides: CssVariableOverrides) -> Self {
+        self.converter = self.converter.with_variable_overrides(overrides);
+        self
+    }
+
+    /// 设置是否把 `calc(var(--spacing) * n)` 解析为具体 rem 值（builder 模式）
+    pub fn with_resolve_spacing(mut self, enabled: bool) -> Self {
+        self.converter = self.converter.with_resolve_spacing(enabled);
+        self
+    }
+
+    /// 设置回退索引（builder 模式），规则无法识别的类到索引中查找
+    pub fn with_fallback_index(mut self, index: Arc<TailwindIndex>) -> Self {
+        self.converter = self.converter.with_fallback_index(index);
+        self
+    }
+
+    /// 设置目标 Tailwind 版本（builder 模式），影响阴影、圆角、模糊等命名尺寸
+    pub fn with_target_version(mut self, version: TailwindVersion) -> Self {
+        self.converter = self.converter.with_target_version(version);
+        self
+    }
+
+    /// 使用带版本的索引数据包（builder 模式）
+    ///
+    /// 设置回退索引；数据包声明了版本时同时设置目标版本
+    pub fn with_versioned_index(mut self, artifact: VersionedIndex) -> Self {
+        if let Some(version) = artifact.version {
+            self.converter = self.converter.with_target_version(version);
+        }
+        self.converter = self.converter.with_fallback_index(artifact.index);
+        self
+    }
+
+    /// 设置输出值的 px / rem 换算与根字号（builder 模式），`:root` 中的主题变量同样换算
+    pub fn with_unit_conversion(mut self, conversion: UnitConversion, root_font_size: u32) -> Self {
+        self.converter = self.converter.with_unit_conversion(conversion, root_font_size);
+        self
+    }
+
+    /// 设置目标浏览器（builder 模式），生成的声明按需补充 `-webkit-` / `-moz-` 前缀，
+    /// 目标浏览器不支持 `oklch()` / `color-mix()` 时补充 hex 回退值
+    pub fn with_targets(mut self, targets: Targets) -> Self {
+        self.targets = targets;
+        self
+    }
+
+    /// 为指定工具类手动设置声明（builder 模式），用于转换结果不符合项目需要的类
+    ///
+    /// 键为不带变体的工具类（如 `shadow-card`），`hover:shadow-card`、`shadow-card!` 等
+    /// 照常套用变体与 `!important`；覆盖的声明不经过缓存与单位换算。
+    pub fn with_class_overrides<I>(mut self, overrides: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Vec<Declaration>)>,
+    {
+        self.overrides = overrides.into_iter().collect();
+        self
+    }
+
+    /// 设置变体别名（builder 模式）
+    ///
+    /// 设计系统常用插件定义组合变体，如 `hocus` → `hover` + `focus`：
+    /// `hocus:bg-red-500` 按 `hover:bg-red-500 focus:bg-red-500` 转换，同时产出两条规则。
+    /// 别名可以与其他变体叠加（`md:hocus:…`），展开的变体也可以是叠加变体（`group-hover:focus`）。
+    pub fn with_variant_aliases<I>(mut self, aliases: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Vec<String>)>,
+    {
+        self.variant_aliases = aliases.into_iter().collect();
+        self
+    }
+
+    /// 注册 `@utility` 定义的工具类（builder 模式）
+    ///
+    /// 键为名称模式（`content-auto`、`tab-*`），定义通常来自项目 CSS（见 `load_custom_utilities`），
+    /// 与内置工具类同名时以自定义定义为准
+    pub fn with_custom_utilities<I>(mut self, utilities: I) -> Self
+    where
+        I: IntoIterator<Item = (String, CustomUtility)>,
+    {
+        let utilities: CustomUtilities = utilities.into_iter().collect();
+        self.converter = self.converter.with_custom_utilities(Arc::new(utilities));
+        self
+    }
+
+    /// 注册 `@custom-variant` 定义的变体（builder 模式）
+    ///
+    /// 定义通常来自项目 CSS（见 `load_custom_variants`）：`@custom-variant dark (&:where(.dark, .dark *));`
+    /// 注册后 `dark:bg-black` 输出 `.c:where(.dark, .dark *)`，不再使用 `prefers-color-scheme`。
+    /// 与内置变体同名时以自定义定义为准。
+    pub fn with_custom_variants<I>(mut self, variants: I) -> Self
+    where
+        I: IntoIterator<Item = (String, CustomVariant)>,
+    {
+        self.custom_variants = Arc::new(variants.into_iter().collect());
+        self
+    }
+
+    /// 设置 `container` 的居中与内边距（builder 模式）
+    pub fn with_container(mut self, style: ContainerStyle) -> Self {
+        self.container = style;
+        self
+    }
+
+    /// 设置类级转换缓存（builder 模式）
+    ///
+    /// 缓存键包含转换选项，同一个缓存可以在选项不同的多个打包器之间共享
+    pub fn with_cache(mut self, cache: Arc<ConversionCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 当前使用的转换缓存
+    pub fn cache(&self) -> Option<&Arc<ConversionCache>> {
+        self.cache.as_ref()
+    }
+
+    /// 解析并转换单个类，手动覆盖的类直接使用覆盖的声明，设置了缓存时优先使用缓存结果
+    fn convert_class(&self, class: &str) -> Result<ConvertedClass, String> {
+        if let Some(converted) = self.override_class(class) {
+            return Ok(converted);
+        }
+        // `space-x` / `space-y` 的声明写在子元素上，子元素变体接在书写的修饰符之后
+        let convert = || match parse_class(class) {
+            Ok(parsed) => {
+                let mut raw_modifiers = parsed.raw_modifiers.to_string();
+                if let Some(child) = variant::utility_child_variant(&parsed.plugin) {
+                    raw_modifiers.push_str(child);
+                    raw_modifiers.push(':');
+                }
+                CachedClass::Parsed {
+                    declarations: self.converter.to_declarations(&parsed),
+                    raw_modifiers,
+                }
+            }
+            Err(e) => CachedClass::Invalid(format!("解析失败: {:?}", e)),
+        };
+        let converted = match &self.cache {
+            Some(cache) => cache.get_or_insert_with(class, self.converter.options_key(), convert),
+            None => convert(),
+        };
+        match converted {
+            CachedClass::Parsed {
+                raw_modifiers,
+                declarations,
+            } => Ok((raw_modifiers, declarations)),
+            CachedClass::Invalid(message) => Err(message),
+        }
+    }
+
+    /// 手动覆盖的类：保留变体，声明替换为覆盖值；没有对应的覆盖时返回 None
+    fn override_class(&self, class: &str) -> Option<ConvertedClass> {
+        if self.overrides.is_empty() {
+            return None;
+        }
+        let raw_modifiers = parse_class(class)
+            .map(|parsed| parsed.raw_modifiers.into_owned())
+            .unwrap_or_default();
+        let utility = class.strip_prefix(raw_modifiers.as_str())?;
+        let (utility, important) = match utility.strip_suffix('!') {
+            Some(utility) => (utility, true),
+            None => (utility, false),
+        };
+        let declarations = self.overrides.get(utility)?;
+        let declarations = declarations
+            .iter()
+            .map(|decl| {
+                if important {
+                    Declaration::new(decl.property.clone(), format!("{} !important", decl.value))
+                } else {
+                    decl.clone()
+                }
+            })
+            .collect();
+        Some((raw_modifiers, Some(declarations)))
+    }
+
+    /// 展开类中的变体别名，没有别名时原样返回
+    fn expand_variants(&self, class: &str) -> Vec<String> {
+        expand_variant_aliases(class, &self.variant_aliases)
+            .unwrap_or_else(|| vec![class.to_string()])
+    }
+
+    /// 按空白分割类名字符串并逐个转换，遇到无法解析的类或空输入时返回错误
+    ///
+    /// 无法转换为声明的类（与 `is_recognized` 判断一致）按出现顺序追加到 `unconverted`
+    fn convert_classes(
+        &self,
+        classes: &str,
+        unconverted: &mut Vec<String>,
+    ) -> Result<Vec<ConvertedClass>, String> {
+        let mut converted = Vec::new();
+        for original in classes.split_whitespace() {
+            let expanded = self.expand_variants(original);
+            let mut recognized = !expanded.is_empty();
+            for class in expanded {
+                match self.convert_class(&class)? {
+                    (raw_modifiers, Some(declarations)) if is_container_class(&class) => {
+                        converted.extend(self.expand_container(raw_modifiers, declarations));
+                    }
+                    entry => {
+                        recognized &= entry.1.is_some();
+                        converted.push(entry);
+                    }
+                }
+            }
+            if !recognized {
+                unconverted.push(original.to_string());
+            }
+        }
+        if converted.is_empty() {
+            return Err(format!("解析失败: {:?}", ParseError::EmptyInput));
+        }
+        let stops = gradient_stops_entries(&converted);
+        converted.extend(stops);
+        let content = pseudo_content_entries(&converted);
+        converted.extend(content);
+        Ok(converted)
+    }
+
+    /// 将 `container` 展开为基础规则（`width: 100%`，按配置居中、加内边距）
+    /// 和各断点下的 `max-width`，断点在类本身的修饰符之后叠加（`md:container` → `md:sm:` …）
+    fn expand_container(
+        &self,
+        raw_modifiers: String,
+        mut declarations: Vec<Declaration>,
+    ) -> Vec<ConvertedClass> {
+        let important = declarations
+            .iter()
+            .any(|decl| decl.value.ends_with("!important"));
+        let with_important = |value: String| {
+            if important {
+                format!("{} !important", value)
+            } else {
+                value
+            }
+        };
+        if self.container.center {
+            declarations.push(Declaration::new("margin-inline", with_important("auto".into())));
+        }
+        if let Some(padding) = &self.container.padding {
+            declarations.push(Declaration::new("padding-inline", with_important(padding.clone())));
+        }
+
+        let unit = self.converter.breakpoints.unit;
+        let ladder = variant::BREAKPOINTS.iter().filter_map(|size| {
+            let width = variant::breakpoint_length(variant::breakpoint_value(size)?, unit);
+            Some((
+                format!("{}{}:", raw_modifiers, size),
+                Some(vec![Declaration::new("max-width", with_important(width))]),
+            ))
+        });
+        std::iter::once((raw_modifiers.clone(), Some(declarations)))
+            .chain(ladder)
+            .collect()
+    }
+
     /// 将多个 Tailwind 类打包成一个规则组
     ///
     /// # 示例
     ///
-    /// ```no_run
+    /// ```
     /// # use headwind_tw_index::Bundler;
     /// let bundler = Bundler::new();
     /// let classes = "text-center hover:text-left md:text-right p-4";
     /// let group = bundler.bundle(classes).unwrap();
+    /// assert_eq!(group.base.len(), 2);
+    /// assert_eq!(group.pseudo_classes["hover"].base[0].value, "left");
+    /// assert_eq!(group.responsive["md"].base[0].value, "right");
     /// ```
     pub fn bundle(&self, classes: &str) -> Result<RuleGroup, String> {
         let mut group = RuleGroup::new();
 
-        // 一次性解析所有类名（优化：批量解析）
-        let parsed_classes = parse_classes(classes).map_err(|e| format!("解析失败: {:?}", e))?;
-
-        // 转换每个解析后的类
-        for parsed in parsed_classes {
-            if let Some(rule) = self.converter.convert(&parsed) {
-                group.add_declarations(&parsed.modifiers(), rule.declarations);
+        for (raw_modifiers, declarations) in self.convert_classes(classes, &mut Vec::new())? {
+            if let Some(declarations) = declarations {
+                let declarations = add_vendor_prefixes(declarations, &self.targets);
+                let declarations = add_color_fallbacks(declarations, &self.targets);
+                // 伪元素分组放在最内层：`before:hover:` 与 `hover:before:` 写入同一分组
+                let mut modifiers = parse_modifiers_from_raw(&raw_modifiers);
+                variant::pseudo_elements_last(&mut modifiers);
+                group.add_declarations(&modifiers, declarations);
             }
         }
 
@@ -152,6 +450,9 @@ impl Bundler {
 
     /// 将规则组生成为 CSS 字符串
     ///
+    /// 输出顺序：基础规则、伪类、伪元素、响应式、状态；每一类内部按 Tailwind 变体顺序排列，
+    /// 不受 HashMap 迭代顺序影响（如 `hover` 先于 `focus`，`focus` 先于 `active`）
+    ///
     /// # 参数
     ///
     /// - `class_name`: 生成的 CSS 类名
@@ -164,219 +465,8 @@ impl Bundler {
         indent: &str,
     ) -> String {
         let mut css = String::new();
-
-        // 生成基础规则
-        if !group.base.is_empty() {
-            css.push_str(&format!(".{} {{\n", class_name));
-            for decl in &group.base {
-                css.push_str(&format!("{}{}: {};\n", indent, decl.property, decl.value));
-            }
-            css.push_str("}\n");
-        }
-
-        // 生成伪类规则
-        for (pseudo, decls) in &group.pseudo_classes {
-            if !decls.is_empty() {
-                // Build selector: check parameterized, child, or standard
-                let selector = if let Some(param_sel) = variant::parameterized_selector(pseudo) {
-                    format!(".{}{}", class_name, param_sel)
-                } else if pseudo == "*" {
-                    format!(".{} > *", class_name)
-                } else if pseudo == "**" {
-                    format!(".{} *", class_name)
-                } else {
-                    let css_pseudo = pseudo_class_selector(pseudo);
-                    format!(".{}:{}", class_name, css_pseudo)
-                };
-
-                // Check if this pseudo-class needs an at-rule wrapper
-                if let Some(at_rule) = variant::pseudo_class_at_rule(pseudo) {
-                    css.push('\n');
-                    css.push_str(&format!("{} {{\n", at_rule));
-                    css.push_str(&format!("{}{} {{\n", indent, selector));
-                    for decl in decls {
-                        css.push_str(&format!(
-                            "{}{}{}: {};\n",
-                            indent, indent, decl.property, decl.value
-                        ));
-                    }
-                    css.push_str(&format!("{}}}\n", indent));
-                    css.push_str("}\n");
-                } else {
-                    css.push('\n');
-                    css.push_str(&format!("{} {{\n", selector));
-                    for decl in decls {
-                        css.push_str(&format!("{}{}: {};\n", indent, decl.property, decl.value));
-                    }
-                    css.push_str("}\n");
-                }
-            }
-        }
-
-        // 生成伪元素规则
-        for (pseudo, decls) in &group.pseudo_elements {
-            if !decls.is_empty() {
-                let css_pseudo = pseudo_element_selector(pseudo);
-                if pseudo == "marker" {
-                    // marker targets both the element and its children
-                    for sel in variant::marker_selectors(&format!(".{}", class_name)) {
-                        css.push('\n');
-                        css.push_str(&format!("{} {{\n", sel));
-                        for decl in decls {
-                            css.push_str(&format!(
-                                "{}{}: {};\n",
-                                indent, decl.property, decl.value
-                            ));
-                        }
-                        css.push_str("}\n");
-                    }
-                } else {
-                    css.push('\n');
-                    css.push_str(&format!(".{}::{} {{\n", class_name, css_pseudo));
-                    for decl in decls {
-                        css.push_str(&format!(
-                            "{}{}: {};\n",
-                            indent, decl.property, decl.value
-                        ));
-                    }
-                    css.push_str("}\n");
-                }
-            }
-        }
-
-        // 生成响应式规则
-        for (size, nested_group) in &group.responsive {
-            // Use variant resolver for breakpoints (v4 rem-based syntax)
-            let at_rule = if let Some(container_name) = size.strip_prefix('@') {
-                variant::container_at_rule(container_name)
-            } else {
-                variant::responsive_at_rule(size)
-            };
-
-            let at_rule = match at_rule {
-                Some(rule) => rule,
-                None => continue,
-            };
-
-            css.push('\n');
-            css.push_str(&format!("{} {{\n", at_rule));
-
-            // 基础规则
-            if !nested_group.base.is_empty() {
-                css.push_str(&format!("{}.{} {{\n", indent, class_name));
-                for decl in &nested_group.base {
-                    css.push_str(&format!(
-                        "{}{}{}: {};\n",
-                        indent, indent, decl.property, decl.value
-                    ));
-                }
-                css.push_str(&format!("{}}}\n", indent));
-            }
-
-            // 伪类
-            for (pseudo, decls) in &nested_group.pseudo_classes {
-                if !decls.is_empty() {
-                    let selector = if let Some(param_sel) = variant::parameterized_selector(pseudo)
-                    {
-                        format!(".{}{}", class_name, param_sel)
-                    } else {
-                        let css_pseudo = pseudo_class_selector(pseudo);
-                        format!(".{}:{}", class_name, css_pseudo)
-                    };
-
-                    // Hover at-rule wrapping inside responsive
-                    if let Some(hover_rule) = variant::pseudo_class_at_rule(pseudo) {
-                        css.push('\n');
-                        css.push_str(&format!("{}{} {{\n", indent, hover_rule));
-                        css.push_str(&format!("{}{}{} {{\n", indent, indent, selector));
-                        for decl in decls {
-                            css.push_str(&format!(
-                                "{}{}{}{}: {};\n",
-                                indent, indent, indent, decl.property, decl.value
-                            ));
-                        }
-                        css.push_str(&format!("{}{}}}\n", indent, indent));
-                        css.push_str(&format!("{}}}\n", indent));
-                    } else {
-                        css.push('\n');
-                        css.push_str(&format!("{}{} {{\n", indent, selector));
-                        for decl in decls {
-                            css.push_str(&format!(
-                                "{}{}{}: {};\n",
-                                indent, indent, decl.property, decl.value
-                            ));
-                        }
-                        css.push_str(&format!("{}}}\n", indent));
-                    }
-                }
-            }
-
-            css.push_str("}\n");
-        }
-
-        // 生成状态规则
-        for (state, nested_group) in &group.states {
-            if nested_group.base.is_empty() {
-                continue;
-            }
-
-            let class_sel = format!(".{}", class_name);
-
-            // Check for supports-[...] → @supports at-rule
-            if let Some(at_rule) = variant::supports_at_rule(state) {
-                css.push('\n');
-                css.push_str(&format!("{} {{\n", at_rule));
-                css.push_str(&format!("{}{} {{\n", indent, class_sel));
-                for decl in &nested_group.base {
-                    css.push_str(&format!(
-                        "{}{}{}: {};\n",
-                        indent, indent, decl.property, decl.value
-                    ));
-                }
-                css.push_str(&format!("{}}}\n", indent));
-                css.push_str("}\n");
-            } else if state == "starting" {
-                css.push('\n');
-                css.push_str("@starting-style {\n");
-                css.push_str(&format!("{}{} {{\n", indent, class_sel));
-                for decl in &nested_group.base {
-                    css.push_str(&format!(
-                        "{}{}{}: {};\n",
-                        indent, indent, decl.property, decl.value
-                    ));
-                }
-                css.push_str(&format!("{}}}\n", indent));
-                css.push_str("}\n");
-            } else {
-                match variant::resolve_state(state, &class_sel) {
-                    StateResolution::Selector(selector) => {
-                        css.push('\n');
-                        css.push_str(&format!("{} {{\n", selector));
-                        for decl in &nested_group.base {
-                            css.push_str(&format!(
-                                "{}{}: {};\n",
-                                indent, decl.property, decl.value
-                            ));
-                        }
-                        css.push_str("}\n");
-                    }
-                    StateResolution::AtRule(rule) => {
-                        css.push('\n');
-                        css.push_str(&format!("{} {{\n", rule));
-                        css.push_str(&format!("{}{} {{\n", indent, class_sel));
-                        for decl in &nested_group.base {
-                            css.push_str(&format!(
-                                "{}{}{}: {};\n",
-                                indent, indent, decl.property, decl.value
-                            ));
-                        }
-                        css.push_str(&format!("{}}}\n", indent));
-                        css.push_str("}\n");
-                    }
-                }
-            }
-        }
-
+        let selector = format!(".{}", class_name);
+        write_group(&mut css, group, &selector, 0, indent, self.converter.breakpoints);
         css
     }
 
@@ -428,88 +518,13 @@ impl Bundler {
             css.push_str(&base_css);
         }
 
-        // 2. 使用字符串生成其他规则（伪类、伪元素等）
-        // 这部分保持不变，使用 generate_css 的逻辑
-
-        // 伪类规则
-        for (pseudo, decls) in &group.pseudo_classes {
-            if !decls.is_empty() {
-                let selector = if let Some(param_sel) = variant::parameterized_selector(pseudo) {
-                    format!(".{}{}", class_name, param_sel)
-                } else if pseudo == "*" {
-                    format!(".{} > *", class_name)
-                } else if pseudo == "**" {
-                    format!(".{} *", class_name)
-                } else {
-                    let css_pseudo = pseudo_class_selector(pseudo);
-                    format!(".{}:{}", class_name, css_pseudo)
-                };
-
-                if let Some(at_rule) = variant::pseudo_class_at_rule(pseudo) {
-                    css.push('\n');
-                    css.push_str(&format!("{} {{\n", at_rule));
-                    css.push_str(&format!("{}{} {{\n", indent, selector));
-                    for decl in decls {
-                        css.push_str(&format!(
-                            "{}{}{}: {};\n",
-                            indent, indent, decl.property, decl.value
-                        ));
-                    }
-                    css.push_str(&format!("{}}}\n", indent));
-                    css.push_str("}\n");
-                } else {
-                    css.push('\n');
-                    css.push_str(&format!("{} {{\n", selector));
-                    for decl in decls {
-                        css.push_str(&format!("{}{}: {};\n", indent, decl.property, decl.value));
-                    }
-                    css.push_str("}\n");
-                }
-            }
-        }
-
-        // 伪元素规则
-        for (pseudo, decls) in &group.pseudo_elements {
-            if !decls.is_empty() {
-                let css_pseudo = pseudo_element_selector(pseudo);
-                css.push('\n');
-                css.push_str(&format!(".{}::{} {{\n", class_name, css_pseudo));
-                for decl in decls {
-                    css.push_str(&format!("{}{}: {};\n", indent, decl.property, decl.value));
-                }
-                css.push_str("}\n");
-            }
-        }
-
-        // 响应式规则
-        for (size, nested_group) in &group.responsive {
-            let at_rule = if let Some(container_name) = size.strip_prefix('@') {
-                variant::container_at_rule(container_name)
-            } else {
-                variant::responsive_at_rule(size)
-            };
-
-            let at_rule = match at_rule {
-                Some(rule) => rule,
-                None => continue,
-            };
-
-            css.push('\n');
-            css.push_str(&format!("{} {{\n", at_rule));
-
-            if !nested_group.base.is_empty() {
-                css.push_str(&format!("{}.{} {{\n", indent, class_name));
-                for decl in &nested_group.base {
-                    css.push_str(&format!(
-                        "{}{}{}: {};\n",
-                        indent, indent, decl.property, decl.value
-                    ));
-                }
-                css.push_str(&format!("{}}}\n", indent));
-            }
-
-            css.push_str("}\n");
-        }
+        // 2. 使用字符串生成其他规则（伪类、伪元素、响应式、状态）
+        let variants = RuleGroup {
+            base: Vec::new(),
+            ..group.clone()
+        };
+        let selector = format!(".{}", class_name);
+        write_group(&mut css, &variants, &selector, 0, indent, self.converter.breakpoints);
 
         Ok(css)
     }
@@ -517,7 +532,7 @@ impl Bundler {
     /// 使用 ClassContext 架构打包类（新架构）
     ///
     /// 这个方法使用更简洁的 ClassContext 架构：
-    /// - 将 ParsedClass 按 raw_modifiers 分组（优化）
+    /// - 设置了转换缓存时，重复出现的类直接复用缓存结果
     /// - 每个 ParsedClass 作为一个"写操作"写入 context
     /// - Context 自动处理选择器生成和 CSS 输出
     ///
@@ -532,54 +547,48 @@ impl Bundler {
     ///
     /// # 示例
     ///
-    /// ```no_run
+    /// ```
     /// # use headwind_tw_index::Bundler;
     /// let bundler = Bundler::new();
     /// let context = bundler.bundle_to_context("my-class", "p-4 hover:p-8 md:p-12").unwrap();
     /// let css = context.to_css("  ");
-    /// println!("{}", css);
+    /// assert!(css.starts_with(".my-class {\n  padding: 1rem;\n}\n"));
+    /// assert!(css.contains("@media (width >= 48rem) {\n  .my-class {\n    padding: 3rem;\n  }\n}"));
     /// ```
     pub fn bundle_to_context(
         &self,
         class_name: &str,
         classes: &str,
     ) -> Result<ClassContext, String> {
-        let mut context = ClassContext::new(class_name.to_string());
-
-        // 一次性解析所有类名
-        let parsed_list =
-            parse_classes(classes).map_err(|e| format!("解析失败: {:?}", e))?;
-
-        // 按 raw_modifiers 分组（优化：相同修饰符的类会被合并处理）
-        let mut grouped: HashMap<String, Vec<ParsedClass>> = HashMap::new();
-        for parsed in parsed_list {
-            grouped
-                .entry(parsed.raw_modifiers.clone())
-                .or_insert_with(Vec::new)
-                .push(parsed);
-        }
-
-        // 处理每个分组：每个 ParsedClass 作为一个"写操作"
-        for (raw_mods, classes) in grouped {
-            for parsed in classes {
-                // 转换为 CSS 声明
-                if let Some(declarations) = self.converter.to_declarations(&parsed) {
-                    // 写入 context（相同 raw_modifiers 的声明会自动合并）
-                    // modifiers 会在生成 CSS 时从 raw_mods 自动解析
-                    context.write(&raw_mods, declarations);
-                }
+        let mut context = ClassContext::new(class_name.to_string())
+            .with_breakpoints(self.converter.breakpoints)
+            .with_targets(self.targets)
+            .with_custom_variants(self.custom_variants.clone());
+
+        // 每个类作为一个"写操作"：相同 raw_modifiers 的声明在 context 中自动合并，
+        // modifiers 会在生成 CSS 时从 raw_modifiers 解析
+        let mut unconverted = Vec::new();
+        for (raw_modifiers, declarations) in self.convert_classes(classes, &mut unconverted)? {
+            if let Some(declarations) = declarations {
+                context.write(&raw_modifiers, declarations);
             }
         }
 
-        Ok(context)
+        Ok(context.with_unconverted(unconverted))
     }
 
     /// 检查单个 Tailwind 类名是否可被识别并转换为 CSS
     pub fn is_recognized(&self, class: &str) -> bool {
-        match parse_class(class) {
-            Ok(parsed) => self.converter.to_declarations(&parsed).is_some(),
-            Err(_) => false,
-        }
+        let expanded = self.expand_variants(class);
+        !expanded.is_empty()
+            && expanded
+                .iter()
+                .all(|class| matches!(self.convert_class(class), Ok((_, Some(_)))))
+    }
+
+    /// 为无法转换的类给出修复建议（拼写相近的类、所需插件或主题条目）
+    pub fn suggest(&self, class: &str) -> Option<Suggestion> {
+        suggest::suggest(&self.converter, class)
     }
 
     /// 直接生成 CSS 字符串（使用 ClassContext 架构）
@@ -594,11 +603,14 @@ impl Bundler {
     ///
     /// # 示例
     ///
-    /// ```no_run
+    /// ```
     /// # use headwind_tw_index::Bundler;
     /// let bundler = Bundler::new();
     /// let css = bundler.bundle_to_css("my-class", "p-4 hover:p-8", "  ").unwrap();
-    /// println!("{}", css);
+    /// assert_eq!(
+    ///     css,
+    ///     ".my-class {\n  padding: 1rem;\n}\n\n@media (hover: hover) {\n  .my-class:hover {\n    padding: 2rem;\n  }\n}\n"
+    /// );
     /// ```
     pub fn bundle_to_css(
         &self,
@@ -609,6 +621,248 @@ impl Bundler {
         let context = self.bundle_to_context(class_name, classes)?;
         Ok(context.to_css(indent))
     }
+
+    /// 与 `bundle_to_css` 相同，但使用自定义基础选择器（如属性选择器）代替 `.class_name`
+    ///
+    /// ```
+    /// # use headwind_tw_index::Bundler;
+    /// let bundler = Bundler::new();
+    /// let css = bundler
+    ///     .bundle_to_css_with_selector("c_1", r#"[data-hw="c_1"]"#, "p-4", "  ")
+    ///     .unwrap();
+    /// assert!(css.starts_with(r#"[data-hw="c_1"] {"#));
+    /// ```
+    pub fn bundle_to_css_with_selector(
+        &self,
+        class_name: &str,
+        selector: &str,
+        classes: &str,
+        indent: &str,
+    ) -> Result<String, String> {
+        let context = self
+            .bundle_to_context(class_name, classes)?
+            .with_selector(selector);
+        Ok(context.to_css(indent))
+    }
+}
+
+/// 为设置了渐变色标（`from-*` / `via-*` / `to-*` 及其位置）的每组修饰符补上 `--tw-gradient-stops`
+///
+/// 色标类只设置 `--tw-gradient-from` 等变量，渐变类引用的是组合后的 `--tw-gradient-stops`；
+/// 未设置的色标取透明，位置取 0% / 50% / 100%。本组或基础组设置了 `via` 时才加入中间色标，
+/// 已显式设置 `--tw-gradient-stops`（如 `[--tw-gradient-stops:…]`）的组保持不变。
+fn gradient_stops_entries(converted: &[ConvertedClass]) -> Vec<ConvertedClass> {
+    // raw_modifiers -> (含 via, 已有 stops)，按首次出现的顺序
+    let mut groups: Vec<(&str, bool, bool)> = Vec::new();
+    for (raw_modifiers, declarations) in converted {
+        let Some(declarations) = declarations else {
+            continue;
+        };
+        for decl in declarations {
+            let Some(stop) = decl.property.strip_prefix("--tw-gradient-") else {
+                continue;
+            };
+            let stop = stop.strip_suffix("-position").unwrap_or(stop);
+            if !matches!(stop, "from" | "via" | "to" | "stops") {
+                continue;
+            }
+            let index = match groups.iter().position(|(raw, ..)| raw == raw_modifiers) {
+                Some(index) => index,
+                None => {
+                    groups.push((raw_modifiers, false, false));
+                    groups.len() - 1
+                }
+            };
+            groups[index].1 |= stop == "via";
+            groups[index].2 |= stop == "stops";
+        }
+    }
+
+    let base_via = groups.iter().any(|&(raw, via, _)| raw.is_empty() && via);
+    groups
+        .into_iter()
+        .filter(|&(_, _, explicit)| !explicit)
+        .map(|(raw, via, _)| {
+            let stop = |name: &str, position: &str| {
+                format!(
+                    "var(--tw-gradient-{0}, #0000) var(--tw-gradient-{0}-position, {1})",
+                    name, position
+                )
+            };
+            let mut stops = vec![stop("from", "0%")];
+            if via || base_via {
+                stops.push(stop("via", "50%"));
+            }
+            stops.push(stop("to", "100%"));
+            (
+                raw.to_string(),
+                Some(vec![Declaration::new("--tw-gradient-stops", stops.join(", "))]),
+            )
+        })
+        .collect()
+}
+
+/// 为 `before:` / `after:` 的每组修饰符补上 `content: var(--tw-content, '')`
+///
+/// 伪元素没有 `content` 时不会渲染，与 Tailwind 一样由变体补全；`content-*` 类把取值写入
+/// `--tw-content`，同一伪元素下其他修饰符组（如 `md:before:`）也沿用该值。
+/// 已显式设置 `content` 的组保持不变。
+fn pseudo_content_entries(converted: &[ConvertedClass]) -> Vec<ConvertedClass> {
+    // raw_modifiers -> 已有 content，按首次出现的顺序
+    let mut groups: Vec<(&str, bool)> = Vec::new();
+    for (raw_modifiers, declarations) in converted {
+        let Some(declarations) = declarations else {
+            continue;
+        };
+        let explicit = declarations.iter().any(|decl| decl.property == "content");
+        match groups.iter_mut().find(|(raw, _)| raw == raw_modifiers) {
+            Some(group) => group.1 |= explicit,
+            None => groups.push((raw_modifiers, explicit)),
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|&(raw, explicit)| {
+            !explicit
+                && parse_modifiers_from_raw(raw).iter().any(|modifier| {
+                    matches!(
+                        modifier,
+                        Modifier::PseudoElement(name) if name == "before" || name == "after"
+                    )
+                })
+        })
+        .map(|(raw, _)| {
+            (
+                raw.to_string(),
+                Some(vec![Declaration::new("content", "var(--tw-content, '')")]),
+            )
+        })
+        .collect()
+}
+
+/// 是否为 `container` 类（允许修饰符和 `!` 重要标记）
+fn is_container_class(class: &str) -> bool {
+    let utility = class.rsplit(':').next().unwrap_or(class);
+    utility.trim_matches('!') == "container"
+}
+
+/// 递归输出规则组：选择器类修饰符拼接到 `selector`，at-rule 类修饰符（断点、`dark`、
+/// `hover` 的 `@media (hover: hover)` 等）各开一层块并增加缩进 `depth`
+fn write_group(
+    css: &mut String,
+    group: &RuleGroup,
+    selector: &str,
+    depth: usize,
+    indent: &str,
+    breakpoints: BreakpointStyle,
+) {
+    // 基础规则
+    if !group.base.is_empty() {
+        write_rule(css, selector, &group.base, depth, indent);
+    }
+
+    // 伪类规则
+    for (pseudo, nested) in sorted_by_variant(&group.pseudo_classes) {
+        let nested_selector = variant::apply_pseudo_class(selector, pseudo);
+
+        // Check if this pseudo-class needs an at-rule wrapper
+        match variant::pseudo_class_at_rule(pseudo) {
+            Some(at_rule) => {
+                write_at_rule(css, at_rule, nested, &nested_selector, depth, indent, breakpoints)
+            }
+            None => write_group(css, nested, &nested_selector, depth, indent, breakpoints),
+        }
+    }
+
+    // 伪元素规则
+    for (pseudo, nested) in sorted_by_variant(&group.pseudo_elements) {
+        if pseudo == "marker" {
+            // marker targets both the element and its children
+            for sel in variant::marker_selectors(selector) {
+                write_group(css, nested, &sel, depth, indent, breakpoints);
+            }
+        } else {
+            let sel = format!("{}::{}", selector, pseudo_element_selector(pseudo));
+            write_group(css, nested, &sel, depth, indent, breakpoints);
+        }
+    }
+
+    // 响应式规则
+    for (size, nested) in sorted_by_variant(&group.responsive) {
+        // 与转换器共用断点输出，单位与写法由 `breakpoints` 决定
+        let at_rule = if let Some(container_name) = size.strip_prefix('@') {
+            variant::container_at_rule_with(container_name, breakpoints)
+        } else {
+            variant::responsive_at_rule_with(size, breakpoints)
+        };
+        if let Some(at_rule) = at_rule {
+            write_at_rule(css, &at_rule, nested, selector, depth, indent, breakpoints);
+        }
+    }
+
+    // 状态规则
+    for (state, nested) in sorted_by_variant(&group.states) {
+        // Check for supports-[...] → @supports at-rule
+        if let Some(at_rule) = variant::supports_at_rule(state) {
+            write_at_rule(css, &at_rule, nested, selector, depth, indent, breakpoints);
+        } else if state == "starting" {
+            write_at_rule(css, "@starting-style", nested, selector, depth, indent, breakpoints);
+        } else {
+            match variant::resolve_state(state, selector) {
+                StateResolution::Selector(nested_selector) => {
+                    write_group(css, nested, &nested_selector, depth, indent, breakpoints)
+                }
+                StateResolution::AtRule(rule) => {
+                    write_at_rule(css, &rule, nested, selector, depth, indent, breakpoints)
+                }
+            }
+        }
+    }
+}
+
+/// 输出 at-rule 块，块内递归输出规则组；规则组为空时不输出
+fn write_at_rule(
+    css: &mut String,
+    at_rule: &str,
+    group: &RuleGroup,
+    selector: &str,
+    depth: usize,
+    indent: &str,
+    breakpoints: BreakpointStyle,
+) {
+    if group.is_empty() {
+        return;
+    }
+    let prefix = indent.repeat(depth);
+    separate_block(css);
+    css.push_str(&format!("{}{} {{\n", prefix, at_rule));
+    write_group(css, group, selector, depth + 1, indent, breakpoints);
+    css.push_str(&format!("{}}}\n", prefix));
+}
+
+/// 输出一条规则，`depth` 为外层 at-rule 的嵌套层数
+fn write_rule(
+    css: &mut String,
+    selector: &str,
+    declarations: &[Declaration],
+    depth: usize,
+    indent: &str,
+) {
+    let prefix = indent.repeat(depth);
+    separate_block(css);
+    css.push_str(&format!("{}{} {{\n", prefix, selector));
+    for decl in declarations {
+        css.push_str(&format!("{}{}{}: {};\n", prefix, indent, decl.property, decl.value));
+    }
+    css.push_str(&format!("{}}}\n", prefix));
+}
+
+/// 同级块之间空一行（块的第一条规则除外）
+fn separate_block(css: &mut String) {
+    if !css.is_empty() && !css.ends_with("{\n") {
+        css.push('\n');
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -622,7 +876,8 @@ fn extract_var_references(css: &str) -> BTreeSet<String> {
 
     while let Some(pos) = css[search_from..].find("var(--") {
         let abs_start = search_from + pos + 4; // 指向 "--"
-        if let Some(end) = css[abs_start..].find(')') {
+        // 变量名止于 `)` 或回退值前的 `,`：`var(--a, var(--b))` 依次得到 --a、--b
+        if let Some(end) = css[abs_start..].find(|c: char| c == ')' || c == ',' || c.is_whitespace()) {
             let var_name = &css[abs_start..abs_start + end]; // "--text-3xl"
             refs.insert(var_name.to_string());
             search_from = abs_start + end;
@@ -638,6 +893,11 @@ fn extract_var_references(css: &str) -> BTreeSet<String> {
 fn resolve_theme_variable(var_name: &str) -> Option<String> {
     use crate::theme_values;
 
+    // --text-shadow-{size}（先于 --text-{size} 匹配）
+    if let Some(size) = var_name.strip_prefix("--text-shadow-") {
+        return theme_values::TEXT_SHADOW_SIZE.get(size).map(|v| v.to_string());
+    }
+
     // --text-{size}--line-height
     if let Some(size) = var_name.strip_prefix("--text-") {
         if let Some(lh_size) = size.strip_suffix("--line-height") {
@@ -656,26 +916,79 @@ fn resolve_theme_variable(var_name: &str) -> Option<String> {
         return theme_values::BLUR_SIZE.get(size).map(|v| v.to_string());
     }
 
+    // --tracking-{name}
+    if let Some(name) = var_name.strip_prefix("--tracking-") {
+        return theme_values::TRACKING.get(name).map(|v| v.to_string());
+    }
+
     // --aspect-video
     if var_name == "--aspect-video" {
         return Some("16 / 9".to_string());
     }
 
+    // --color-{name}：与 Tailwind v4 默认主题一致，使用 OKLCH 值
+    if let Some(name) = var_name.strip_prefix("--color-") {
+        return crate::palette::get_color(name, ColorMode::Oklch);
+    }
+
+    // --shadow-{size} / --inset-shadow-{size}
+    if let Some(size) = var_name.strip_prefix("--shadow-") {
+        return theme_values::SHADOW_SIZE.get(size).map(|v| v.to_string());
+    }
+    if let Some(size) = var_name.strip_prefix("--inset-shadow-") {
+        return theme_values::INSET_SHADOW_SIZE.get(size).map(|v| v.to_string());
+    }
+
+    // --spacing
+    if var_name == "--spacing" {
+        return Some(theme_values::SPACING.to_string());
+    }
+
     None
 }
 
 impl Bundler {
     /// 从 CSS 中提取用到的主题变量引用，生成 :root 定义块。
     ///
-    /// 只处理已知主题变量（--text-*, --font-*, --blur-*, --aspect-video），
-    /// 内部 --tw-* 变量自动排除。
+    /// 只处理已知主题变量（--text-*, --font-*, --blur-*, --tracking-*, --aspect-video, --color-*,
+    /// --shadow-*, --inset-shadow-*, --text-shadow-*, --spacing），内部 --tw-* 与用户自定义变量自动排除。
     pub fn generate_root_css(&self, css: &str) -> String {
-        let var_refs = extract_var_references(css);
+        self.root_css_for(&self.theme_variables(css))
+    }
+
+    /// CSS 中引用到的已知主题变量名。分批输出 CSS 时逐批收集，最后用 `root_css_for` 生成 :root
+    pub fn theme_variables(&self, css: &str) -> BTreeSet<String> {
+        extract_var_references(css)
+            .into_iter()
+            .filter(|var_name| resolve_theme_variable(var_name).is_some())
+            .collect()
+    }
 
+    /// 已知主题变量的值（与 :root 定义块中的值相同，已按单位换算设置换算），未知变量返回 None
+    pub fn theme_value(&self, var_name: &str) -> Option<String> {
+        resolve_theme_variable(var_name).map(|value| self.converter.convert_units(&value))
+    }
+
+    /// 由主题变量名生成 :root 定义块，未知变量跳过；没有可定义的变量时返回空字符串
+    ///
+    /// 目标浏览器不支持 `oklch()` 时颜色变量先定义为 hex，
+    /// 再在 `@supports (color: oklch(0 0 0))` 块中覆盖为 OKLCH 值。
+    pub fn root_css_for(&self, var_refs: &BTreeSet<String>) -> String {
         let mut definitions: Vec<(String, String)> = Vec::new();
-        for var_name in &var_refs {
+        let mut enhanced: Vec<(&str, String)> = Vec::new();
+        for var_name in var_refs {
             if let Some(value) = resolve_theme_variable(var_name) {
-                definitions.push((var_name.clone(), value));
+                let value = self.converter.convert_units(&value);
+                let fallback = needs_color_fallback(&value, &self.targets)
+                    .then(|| color_fallback(&value))
+                    .flatten();
+                match fallback {
+                    Some(fallback) => {
+                        enhanced.push((var_name, value));
+                        definitions.push((var_name.clone(), fallback));
+                    }
+                    None => definitions.push((var_name.clone(), value)),
+                }
             }
         }
 
@@ -689,6 +1002,14 @@ impl Bundler {
         }
         root_css.push('}');
 
+        if !enhanced.is_empty() {
+            root_css.push_str(&format!("\n@supports {} {{\n  :root {{\n", OKLCH_SUPPORTS));
+            for (name, value) in &enhanced {
+                root_css.push_str(&format!("    {}: {};\n", name, value));
+            }
+            root_css.push_str("  }\n}");
+        }
+
         root_css
     }
 }
@@ -714,6 +1035,237 @@ mod tests {
         assert!(group.responsive.is_empty());
     }
 
+    #[test]
+    fn test_generate_root_css_palette_shadow_spacing() {
+        let bundler = Bundler::new().with_color_mode(ColorMode::Var);
+        let css = [
+            "bg-blue-500/50",
+            "shadow-md",
+            "inset-shadow-xs",
+            "leading-6",
+            "tracking-tight",
+            "text-shadow-md",
+            "bg-(--brand)",
+        ]
+        .iter()
+        .map(|class| {
+            let decls = bundler.bundle(class).unwrap().base;
+            decls.iter().map(|d| format!("{}: {};", d.property, d.value)).collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let root = bundler.generate_root_css(&css);
+        assert!(root.contains("  --color-blue-500: oklch("), "{}", root);
+        assert!(root.contains("  --shadow-md: 0 4px 6px -1px"), "{}", root);
+        assert!(root.contains("  --inset-shadow-xs: inset 0 1px 1px"), "{}", root);
+        assert!(root.contains("  --spacing: 0.25rem;"), "{}", root);
+        assert!(root.contains("  --tracking-tight: -0.025em;"), "{}", root);
+        assert!(root.contains("  --text-shadow-md: 0px 1px 1px"), "{}", root);
+        assert!(!root.contains("--brand") && !root.contains("--tw-"), "{}", root);
+    }
+
+    #[test]
+    fn test_root_css_from_batches() {
+        let bundler = Bundler::new();
+        let first = bundler.bundle_to_css("a", "text-xl", "  ").unwrap();
+        let second = bundler.bundle_to_css("b", "shadow-md bg-(--brand)", "  ").unwrap();
+
+        let mut variables = bundler.theme_variables(&first);
+        variables.extend(bundler.theme_variables(&second));
+        assert!(variables.contains("--text-xl") && variables.contains("--shadow-md"));
+        assert!(!variables.contains("--brand"));
+        assert_eq!(
+            bundler.root_css_for(&variables),
+            bundler.generate_root_css(&format!("{}\n{}", first, second))
+        );
+    }
+
+    #[test]
+    fn test_vendor_prefixes() {
+        let targets = Targets {
+            safari: Some(12),
+            ..Default::default()
+        };
+        let bundler = Bundler::with_inline().with_targets(targets);
+        let css = bundler
+            .bundle_to_css("a", "select-none md:backdrop-blur-sm sticky", "  ")
+            .unwrap();
+        assert!(css.contains("  -webkit-user-select: none;\n  user-select: none;"), "{}", css);
+        assert!(css.contains("  position: -webkit-sticky;\n  position: sticky;"), "{}", css);
+        assert!(css.contains("    -webkit-backdrop-filter: "), "{}", css);
+
+        let group = bundler.bundle("select-none").unwrap();
+        assert_eq!(group.base[0].property, "-webkit-user-select");
+        assert!(!Bundler::new().bundle_to_css("a", "select-none", "  ").unwrap().contains("-webkit-"));
+    }
+
+    #[test]
+    fn test_color_fallbacks() {
+        let safari_15 = Targets {
+            safari: Some(15),
+            ..Default::default()
+        };
+        let blue = crate::palette::get_color("blue-500", ColorMode::Hex).unwrap();
+
+        let oklch = Bundler::with_inline()
+            .with_color_mode(ColorMode::Oklch)
+            .with_targets(safari_15);
+        let css = oklch.bundle_to_css("a", "text-blue-500", "  ").unwrap();
+        assert!(
+            css.contains(&format!("  color: {};\n  color: oklch(", blue)),
+            "{}",
+            css
+        );
+
+        // 变量模式：:root 先定义 hex，再在 @supports 中覆盖为 OKLCH
+        let vars = Bundler::new()
+            .with_color_mode(ColorMode::Var)
+            .with_targets(safari_15);
+        let css = vars.bundle_to_css("b", "bg-blue-500", "  ").unwrap();
+        let root = vars.generate_root_css(&css);
+        let expected = format!(":root {{\n  --color-blue-500: {};\n}}\n", blue);
+        assert!(root.starts_with(&expected), "{}", root);
+        assert!(
+            root.ends_with("@supports (color: oklch(0 0 0)) {\n  :root {\n    --color-blue-500: oklch(0.623 0.214 259.815);\n  }\n}"),
+            "{}",
+            root
+        );
+        let modern = Bundler::new().with_color_mode(ColorMode::Var);
+        assert!(!modern.generate_root_css(&css).contains("@supports"));
+
+        // color-mix() 中的主题变量按调色板预先计算
+        let mixed = vars.with_color_mix(true);
+        let css = mixed.bundle_to_css("c", "bg-white/60", "  ").unwrap();
+        assert!(
+            css.contains("  background: #ffffff99;\n  background: color-mix("),
+            "{}",
+            css
+        );
+    }
+
+    #[test]
+    fn test_class_overrides() {
+        let bundler = Bundler::with_inline().with_class_overrides([
+            (
+                "shadow-card".to_string(),
+                vec![Declaration::new("box-shadow", "0 1px 3px #0002")],
+            ),
+            ("p-4".to_string(), vec![Declaration::new("padding", "18px")]),
+        ]);
+        assert!(bundler.is_recognized("shadow-card"));
+        let css = bundler
+            .bundle_to_css("a", "p-4 hover:shadow-card md:p-4! m-2", "  ")
+            .unwrap();
+        assert!(css.contains(".a {\n  padding: 18px;\n  margin: 0.5rem;\n}"), "{}", css);
+        assert!(css.contains(".a:hover {\n    box-shadow: 0 1px 3px #0002;\n"), "{}", css);
+        assert!(css.contains("    padding: 18px !important;\n"), "{}", css);
+
+        // 未覆盖的打包器照常转换
+        assert!(!Bundler::with_inline().is_recognized("shadow-card"));
+    }
+
+    #[test]
+    fn test_variant_aliases() {
+        let bundler = Bundler::with_inline().with_variant_aliases([(
+            "hocus".to_string(),
+            vec!["hover".to_string(), "focus".to_string()],
+        )]);
+        assert!(bundler.is_recognized("hocus:bg-red-500"));
+        assert!(!bundler.is_recognized("hocus:not-a-class"));
+        // 打包时记录无法转换的类，与 is_recognized 一致
+        let context = bundler
+            .bundle_to_context("a", "p-4 hocus:not-a-class not-a-class")
+            .unwrap();
+        assert_eq!(context.unconverted(), ["hocus:not-a-class", "not-a-class"]);
+        let css = bundler
+            .bundle_to_css("a", "p-4 hocus:underline md:hocus:p-2", "  ")
+            .unwrap();
+        assert!(css.contains(".a:hover {\n    text-decoration-line: underline;"), "{}", css);
+        assert!(css.contains(".a:focus {\n  text-decoration-line: underline;"), "{}", css);
+        assert_eq!(css.matches("padding: 0.5rem;").count(), 2, "{}", css);
+        // 未设置别名时 `hocus` 按未知的伪类原样输出
+        let plain = Bundler::with_inline()
+            .bundle_to_css("a", "hocus:underline", "  ")
+            .unwrap();
+        assert!(!plain.contains(":focus"), "{}", plain);
+    }
+
+    #[test]
+    fn test_custom_variants() {
+        let css = r#"
+@custom-variant dark (&:where(.dark, .dark *));
+@custom-variant touch (@media (hover: none));
+"#;
+        let bundler = Bundler::with_inline()
+            .with_custom_variants(crate::load_custom_variants(css).unwrap());
+        let css = bundler
+            .bundle_to_css("a", "p-4 dark:underline touch:p-6", "  ")
+            .unwrap();
+        assert!(
+            css.contains(".a:where(.dark, .dark *) {\n  text-decoration-line: underline;"),
+            "{}",
+            css
+        );
+        assert!(!css.contains("prefers-color-scheme"), "{}", css);
+        assert!(
+            css.contains("@media (hover: none) {\n  .a {\n    padding: 1.5rem;"),
+            "{}",
+            css
+        );
+        // 自定义变体可以与内置变体叠加
+        let css = bundler.bundle_to_css("a", "md:dark:hover:underline", "  ").unwrap();
+        assert!(css.contains(".a:where(.dark, .dark *):hover"), "{}", css);
+    }
+
+    #[test]
+    fn test_custom_utilities() {
+        let css = r#"
+@utility content-auto { content-visibility: auto; }
+@utility flex { display: grid; }
+@utility tab-* { tab-size: --value(integer, [integer]); }
+"#;
+        let bundler = Bundler::with_inline()
+            .with_custom_utilities(crate::load_custom_utilities(css).unwrap());
+        let css = bundler
+            .bundle_to_css("a", "content-auto flex tab-4 md:tab-[8]", "  ")
+            .unwrap();
+        assert!(css.contains("content-visibility: auto;"), "{}", css);
+        assert!(css.contains("display: grid;"), "{}", css);
+        assert!(css.contains("tab-size: 4;"), "{}", css);
+        assert!(css.contains("tab-size: 8;"), "{}", css);
+        assert!(!bundler.is_recognized("tab-wide"));
+
+        // 工具类定义不同的打包器不共享缓存结果
+        let cache = Arc::new(ConversionCache::new());
+        let plain = Bundler::with_inline().with_cache(cache.clone());
+        let custom = bundler.with_cache(cache);
+        assert!(plain.bundle_to_css("a", "flex", "  ").unwrap().contains("display: flex;"));
+        assert!(custom.bundle_to_css("a", "flex", "  ").unwrap().contains("display: grid;"));
+    }
+
+    #[test]
+    fn test_unit_conversion() {
+        let inline = Bundler::with_inline()
+            .with_resolve_spacing(true)
+            .with_unit_conversion(UnitConversion::RemToPx, 16);
+        let css = inline.bundle_to_css("a", "p-4 w-[2.5rem] border-2", "  ").unwrap();
+        assert!(css.contains("padding: 16px;"), "{}", css);
+        assert!(css.contains("width: 40px;"), "{}", css);
+        assert!(css.contains("border-width: 2px;"), "{}", css);
+
+        // 主题变量的定义同样换算
+        let vars = Bundler::new().with_unit_conversion(UnitConversion::RemToPx, 10);
+        let css = vars.bundle_to_css("b", "text-xl", "  ").unwrap();
+        assert!(css.contains("font-size: var(--text-xl);"), "{}", css);
+        assert!(vars.generate_root_css(&css).contains("--text-xl: 12.5px;"));
+
+        let rem = Bundler::with_inline().with_unit_conversion(UnitConversion::PxToRem, 16);
+        let css = rem.bundle_to_css("c", "border-[3px] top-[-8px]", "  ").unwrap();
+        assert!(css.contains("border-width: 0.1875rem;"), "{}", css);
+        assert!(css.contains("top: -0.5rem;"), "{}", css);
+    }
+
     #[test]
     fn test_bundle_with_hover() {
         let bundler = Bundler::new();
@@ -776,6 +1328,75 @@ mod tests {
         assert!(css.contains("text-align: right;"));
     }
 
+    #[test]
+    fn test_breakpoints_style_consistent_across_paths() {
+        let bundler = Bundler::new().with_breakpoints(BreakpointStyle {
+            unit: headwind_core::BreakpointUnit::Px,
+            syntax: headwind_core::MediaQuerySyntax::Legacy,
+        });
+
+        let group = bundler.bundle("md:p-4 max-lg:p-2 @sm:p-1").unwrap();
+        let css = bundler.generate_css("c", &group, "  ");
+        let context_css = bundler.bundle_to_css("c", "md:p-4 max-lg:p-2 @sm:p-1", "  ").unwrap();
+        for css in [&css, &context_css] {
+            assert!(css.contains("@media (min-width: 768px) {"), "{}", css);
+            assert!(css.contains("@media not all and (min-width: 1024px) {"), "{}", css);
+            assert!(css.contains("@container (min-width: 384px) {"), "{}", css);
+            assert!(!css.contains("rem)"), "{}", css);
+        }
+    }
+
+    #[test]
+    fn test_bundle_nested_stack() {
+        let bundler = Bundler::new();
+
+        let group = bundler
+            .bundle("dark:md:hover:underline dark:md:hover:focus-visible:p-4")
+            .unwrap();
+        let hover = &group.states["dark"].responsive["md"].pseudo_classes["hover"];
+        assert_eq!(hover.base.len(), 1);
+        assert_eq!(hover.pseudo_classes["focus-visible"].base.len(), 1);
+
+        let css = bundler.generate_css("c", &group, "  ");
+        let expected = "\
+@media (prefers-color-scheme: dark) {
+  @media (width >= 48rem) {
+    @media (hover: hover) {
+      .c:hover {
+        text-decoration-line: underline;
+      }
+
+      .c:hover:focus-visible {
+        padding: 1rem;
+      }
+    }
+  }
+}
+";
+        assert_eq!(css, expected);
+    }
+
+    #[test]
+    fn test_generate_css_stacked_selectors() {
+        let bundler = Bundler::new();
+
+        let group = bundler
+            .bundle("group-hover:md:before:p-4 lg:aria-disabled:hover:opacity-50")
+            .unwrap();
+        let css = bundler.generate_css("c", &group, "  ");
+
+        assert!(
+            css.contains("@media (width >= 48rem) {\n  .group:hover .c::before {"),
+            "{}",
+            css
+        );
+        assert!(
+            css.contains("@media (hover: hover) {\n    .c[aria-disabled=\"true\"]:hover {"),
+            "{}",
+            css
+        );
+    }
+
     #[test]
     fn test_generate_css_with_swc() {
         let bundler = Bundler::new();
@@ -962,4 +1583,299 @@ mod tests {
         assert!(css.contains("text-align: right;"));
         assert!(css.contains("padding: 3rem;"));
     }
+
+    #[test]
+    fn test_variant_precedence_order() {
+        let bundler = Bundler::new();
+        let classes =
+            "active:bg-red-500 focus:bg-green-500 hover:bg-blue-500 dark:p-2 lg:p-8 sm:p-4";
+        let pos = |css: &str, needle: &str| css.find(needle).unwrap();
+
+        // 两种生成路径都按变体顺序输出，后输出的规则在同时命中时优先
+        let css = bundler.bundle_to_css("c", classes, "  ").unwrap();
+        assert!(pos(&css, ".c:hover") < pos(&css, ".c:focus"));
+        assert!(pos(&css, ".c:focus") < pos(&css, ".c:active"));
+        assert!(pos(&css, "(width >= 40rem)") < pos(&css, "(width >= 64rem)"));
+        assert!(pos(&css, "(width >= 64rem)") < pos(&css, "prefers-color-scheme"));
+
+        let group = bundler.bundle(classes).unwrap();
+        let css = bundler.generate_css("c", &group, "  ");
+        assert!(pos(&css, ".c:hover") < pos(&css, ".c:focus"));
+        assert!(pos(&css, ".c:focus") < pos(&css, ".c:active"));
+        assert!(pos(&css, "(width >= 40rem)") < pos(&css, "(width >= 64rem)"));
+    }
+
+    /// Tailwind v4 的全部伪类变体 → 期望的选择器后缀
+    const PSEUDO_CLASS_MATRIX: &[(&str, &str)] = &[
+        ("first", ":first-child"),
+        ("last", ":last-child"),
+        ("only", ":only-child"),
+        ("odd", ":nth-child(odd)"),
+        ("even", ":nth-child(even)"),
+        ("first-of-type", ":first-of-type"),
+        ("last-of-type", ":last-of-type"),
+        ("only-of-type", ":only-of-type"),
+        ("visited", ":visited"),
+        ("target", ":target"),
+        ("open", ":is([open], :popover-open, :open)"),
+        ("default", ":default"),
+        ("checked", ":checked"),
+        ("indeterminate", ":indeterminate"),
+        ("placeholder-shown", ":placeholder-shown"),
+        ("autofill", ":autofill"),
+        ("optional", ":optional"),
+        ("required", ":required"),
+        ("valid", ":valid"),
+        ("invalid", ":invalid"),
+        ("user-valid", ":user-valid"),
+        ("user-invalid", ":user-invalid"),
+        ("in-range", ":in-range"),
+        ("out-of-range", ":out-of-range"),
+        ("read-only", ":read-only"),
+        ("empty", ":empty"),
+        ("focus-within", ":focus-within"),
+        ("hover", ":hover"),
+        ("focus", ":focus"),
+        ("focus-visible", ":focus-visible"),
+        ("active", ":active"),
+        ("enabled", ":enabled"),
+        ("disabled", ":disabled"),
+        ("inert", ":is([inert], [inert] *)"),
+        ("not-first", ":not(:first-child)"),
+        ("aria-checked", "[aria-checked=\"true\"]"),
+        ("data-active", "[data-active]"),
+        ("*", " > *"),
+    ];
+
+    #[test]
+    fn test_pseudo_class_matrix() {
+        let converter = Converter::new();
+        let bundler = Bundler::new();
+        for &(variant, suffix) in PSEUDO_CLASS_MATRIX {
+            let class = format!("{}:p-4", variant);
+            let modifier = Modifier::from_str(variant);
+            assert!(
+                matches!(modifier, Modifier::PseudoClass(_) | Modifier::Custom(_)),
+                "{}: {:?}",
+                variant,
+                modifier
+            );
+            if matches!(modifier, Modifier::PseudoClass(_)) {
+                assert!(variant::variant_order(variant) < variant::variant_order("nonexistent"), "{}", variant);
+            }
+
+            // 转换器
+            let rule = converter.convert(&parse_class(&class).unwrap()).unwrap();
+            assert_eq!(rule.selector, format!(".p-4{}", suffix), "{}", variant);
+
+            // 打包器的两条生成路径
+            let expected = format!(".c{} {{\n", suffix);
+            let css = bundler.bundle_to_css("c", &class, "  ").unwrap();
+            assert!(css.contains(&expected), "{}:\n{}", variant, css);
+            let group = bundler.bundle(&class).unwrap();
+            let css = bundler.generate_css("c", &group, "  ");
+            assert!(css.contains(&expected), "{}:\n{}", variant, css);
+
+            // group / peer 前缀使用同一映射（子选择器不适用）
+            if variant != "*" {
+                let css = bundler.bundle_to_css("c", &format!("group-{}", class), "  ").unwrap();
+                assert!(css.contains(&format!(".group{} .c {{", suffix)), "{}:\n{}", variant, css);
+                let css = bundler.bundle_to_css("c", &format!("peer-{}", class), "  ").unwrap();
+                assert!(css.contains(&format!(".peer{} ~ .c {{", suffix)), "{}:\n{}", variant, css);
+            }
+        }
+
+        // 伪元素名同样经过映射
+        let rule = converter.convert(&parse_class("file:p-4").unwrap()).unwrap();
+        assert_eq!(rule.selector, ".p-4::file-selector-button");
+    }
+
+    #[test]
+    fn test_space_between_children() {
+        let bundler = Bundler::new();
+        let classes = "flex space-x-4 hover:-space-x-2";
+        let child = ":where(.c > :not(:last-child)) {\n  --tw-space-x-reverse: 0;\n  \
+                     margin-inline-start: calc(1rem * var(--tw-space-x-reverse));\n";
+        let hover_child = ":where(.c:hover > :not(:last-child)) {\n";
+
+        // 两条生成路径都把间距写在子元素上，元素本身只保留其余声明
+        let css = bundler.bundle_to_css("c", classes, "  ").unwrap();
+        assert!(css.starts_with(".c {\n  display: flex;\n}\n"), "{}", css);
+        assert!(css.contains(child), "{}", css);
+        assert!(css.contains(hover_child), "{}", css);
+        assert!(css.contains("calc(-0.5rem * var(--tw-space-x-reverse))"), "{}", css);
+        let css = bundler.generate_css("c", &bundler.bundle(classes).unwrap(), "  ");
+        assert!(css.contains(child), "{}", css);
+        assert!(css.contains(hover_child), "{}", css);
+    }
+
+    #[test]
+    fn test_shared_conversion_cache() {
+        let cache = Arc::new(ConversionCache::new());
+        let hex = Bundler::new().with_cache(cache.clone());
+        let oklch = Bundler::new()
+            .with_color_mode(ColorMode::Oklch)
+            .with_cache(cache.clone());
+
+        let uncached = Bundler::new().bundle_to_css("c", "p-4 hover:text-red-500", "  ").unwrap();
+        assert_eq!(hex.bundle_to_css("c", "p-4 hover:text-red-500", "  ").unwrap(), uncached);
+        assert_eq!(hex.bundle_to_css("c", "p-4 hover:text-red-500", "  ").unwrap(), uncached);
+        assert_eq!(cache.stats().hits, 2);
+
+        // 选项不同的打包器不会命中其他选项的结果
+        let css = oklch.bundle_to_css("c", "hover:text-red-500", "  ").unwrap();
+        assert!(css.contains("oklch("), "{}", css);
+        assert_eq!(cache.stats().entries, 3);
+
+        // 无法识别与无法解析的结果同样被缓存
+        assert!(!hex.is_recognized("not-a-utility"));
+        assert!(!hex.is_recognized("not-a-utility"));
+        assert!(hex.bundle("p-4 [").is_err());
+        assert!(hex.bundle("p-4 [").is_err());
+        assert_eq!(cache.stats().hits, 6);
+    }
+
+    #[test]
+    fn test_fallback_index_shares_cache_safely() {
+        let mut index = crate::index::TailwindIndex::new();
+        index.insert("scrollbar-thin".into(), vec![Declaration::new("scrollbar-width", "thin")]);
+        let cache = Arc::new(ConversionCache::new());
+        let plain = Bundler::new().with_cache(cache.clone());
+        let indexed = Bundler::new()
+            .with_fallback_index(Arc::new(index))
+            .with_cache(cache.clone());
+
+        assert!(!plain.is_recognized("scrollbar-thin"));
+        // 缓存键包含索引指纹，不会命中未设置索引时的结果
+        assert!(indexed.is_recognized("scrollbar-thin"));
+        let css = indexed.bundle_to_css("c", "md:scrollbar-thin", "  ").unwrap();
+        assert!(css.contains("scrollbar-width: thin"), "{}", css);
+    }
+
+    #[test]
+    fn test_container_max_width_ladder() {
+        let css = Bundler::new().bundle_to_css("c", "container", "  ").unwrap();
+        assert!(css.starts_with(".c {\n  width: 100%;\n}"), "{}", css);
+        for width in ["40rem", "48rem", "64rem", "80rem", "96rem"] {
+            assert!(
+                css.contains(&format!("@media (width >= {0}) {{\n  .c {{\n    max-width: {0};", width)),
+                "{}",
+                css
+            );
+        }
+        assert!(css.find("40rem").unwrap() < css.find("96rem").unwrap());
+
+        let px = Bundler::new()
+            .with_breakpoints(BreakpointStyle {
+                unit: headwind_core::BreakpointUnit::Px,
+                ..Default::default()
+            })
+            .with_container(ContainerStyle {
+                center: true,
+                padding: Some("2rem".to_string()),
+            });
+        let css = px.bundle_to_css("c", "container!", "  ").unwrap();
+        assert!(css.contains("margin-inline: auto !important;"), "{}", css);
+        assert!(css.contains("padding-inline: 2rem !important;"), "{}", css);
+        assert!(css.contains("max-width: 768px !important;"), "{}", css);
+    }
+
+    #[test]
+    fn test_gradient_stops_assembly() {
+        let bundler = Bundler::new();
+        let stops = |group: &[Declaration]| {
+            group
+                .iter()
+                .find(|decl| decl.property == "--tw-gradient-stops")
+                .map(|decl| decl.value.clone())
+        };
+
+        let group = bundler.bundle("bg-linear-to-r from-red-500 to-blue-500").unwrap();
+        assert_eq!(
+            stops(&group.base).unwrap(),
+            "var(--tw-gradient-from, #0000) var(--tw-gradient-from-position, 0%), \
+             var(--tw-gradient-to, #0000) var(--tw-gradient-to-position, 100%)"
+        );
+
+        // 基础组有 via 时，悬停组的组合同样包含中间色标
+        let group = bundler.bundle("from-red-500 via-green-500 hover:from-blue-500").unwrap();
+        assert!(stops(&group.base).unwrap().contains("var(--tw-gradient-via, #0000)"));
+        let hover = &group.pseudo_classes["hover"].base;
+        assert!(stops(hover).unwrap().contains("var(--tw-gradient-via-position, 50%)"));
+
+        // 没有色标，或回退索引的结果已自带组合时不追加
+        assert!(stops(&bundler.bundle("p-4 bg-linear-to-r").unwrap().base).is_none());
+        let mut index = crate::index::TailwindIndex::new();
+        index.insert(
+            "from-brand".into(),
+            vec![
+                Declaration::new("--tw-gradient-from", "#f50"),
+                Declaration::new("--tw-gradient-stops", "var(--tw-gradient-from), #0000"),
+            ],
+        );
+        let indexed = Bundler::new().with_fallback_index(Arc::new(index));
+        let group = indexed.bundle("from-brand").unwrap();
+        assert_eq!(group.base.len(), 2);
+    }
+
+    #[test]
+    fn test_pseudo_element_content() {
+        let bundler = Bundler::new();
+        let classes = "before:absolute before:inset-0 after:content-['→'] hover:after:p-4 p-4";
+        let css = bundler.bundle_to_css("c", classes, "  ").unwrap();
+        let rule = |selector: &str| {
+            let start = css.find(&format!("{} {{", selector)).unwrap();
+            css[start..start + css[start..].find('}').unwrap()].to_string()
+        };
+        assert!(rule(".c::before").contains("content: var(--tw-content, '');"), "{}", css);
+        // 显式的 content 类不重复补全
+        let after = rule(".c::after");
+        assert!(after.contains("--tw-content: '→';"), "{}", css);
+        assert!(!after.contains("var(--tw-content, '')"), "{}", css);
+        // 同一伪元素的其他修饰符组同样补全，取值沿用 --tw-content
+        assert!(rule(".c:hover::after").contains("content: var(--tw-content, '');"), "{}", css);
+        assert!(!rule(".c").contains("content"), "{}", css);
+
+        // 其他伪元素不补全
+        let group = bundler.bundle("placeholder:text-red-500").unwrap();
+        let placeholder = &group.pseudo_elements["placeholder"].base;
+        assert!(placeholder.iter().all(|decl| decl.property != "content"));
+    }
+
+    #[test]
+    fn test_pseudo_element_variant_order() {
+        let bundler = Bundler::new();
+
+        // 伪元素之后书写的变体与之前书写的变体写入同一分组
+        let group = bundler.bundle("hover:before:scale-110 before:hover:opacity-50").unwrap();
+        let before = &group.pseudo_classes["hover"].pseudo_elements["before"];
+        let properties: Vec<_> = before.base.iter().map(|decl| decl.property.as_str()).collect();
+        assert!(properties.contains(&"opacity"), "{:?}", properties);
+        assert!(properties.iter().any(|property| property.contains("scale")), "{:?}", properties);
+        assert!(group.pseudo_elements.is_empty());
+
+        for classes in ["hover:before:opacity-50", "before:hover:opacity-50"] {
+            let css = bundler.bundle_to_css("c", classes, "  ").unwrap();
+            assert!(css.contains(".c:hover::before {"), "{}", css);
+            let group = bundler.bundle(classes).unwrap();
+            let css = bundler.generate_css("c", &group, "  ");
+            assert!(css.contains(".c:hover::before {"), "{}", css);
+        }
+
+        // 响应式 / 暗色模式变体写在伪元素前后，结果一致
+        for classes in ["md:after:p-4", "after:md:p-4"] {
+            let css = bundler.bundle_to_css("c", classes, "  ").unwrap();
+            assert!(css.contains("@media (width >= 48rem) {\n  .c::after {"), "{}", css);
+        }
+        for classes in ["dark:placeholder:text-white", "placeholder:dark:text-white"] {
+            let css = bundler.bundle_to_css("c", classes, "  ").unwrap();
+            assert!(
+                css.contains("@media (prefers-color-scheme: dark) {\n  .c::placeholder {"),
+                "{}",
+                css
+            );
+        }
+        let css = bundler.bundle_to_css("c", "before:group-hover:p-4", "  ").unwrap();
+        assert!(css.contains(".group:hover .c::before {"), "{}", css);
+    }
 }
@@ -0,0 +1,263 @@
+//! 类级转换缓存
+//!
+//! 以（原始类名, 转换选项）为键缓存解析与转换结果。同一个缓存可以通过 `Arc` 在多个
+//! `Bundler` 之间共享，批量转换时各文件中重复出现的类只解析、转换一次。
+//!
+//! 缓存有容量上限：写入时若已满，淘汰最久未使用的一半条目。
+//! 内存受限的环境（如 WASM）不设置缓存即可，`Bundler` 默认不缓存。
+
+use headwind_core::{ColorMode, CssVariableOverrides, Declaration, TailwindVersion, UnitConversion};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 默认容量（条目数）
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// 影响转换结果的 `Converter` 选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct OptionsKey {
+    pub use_variables: bool,
+    pub variable_overrides: CssVariableOverrides,
+    pub color_mode: ColorMode,
+    pub use_color_mix: bool,
+    pub resolve_spacing: bool,
+    /// 回退索引的内容指纹，未设置时为 None
+    pub fallback_index: Option<u64>,
+    pub target_version: TailwindVersion,
+    pub unit_conversion: UnitConversion,
+    pub root_font_size: u32,
+    /// `@utility` 工具类的内容指纹，未设置时为 None
+    pub custom_utilities: Option<u64>,
+}
+
+/// 单个类的转换结果
+#[derive(Debug, Clone)]
+pub(crate) enum CachedClass {
+    /// 无法解析，保存错误信息
+    Invalid(String),
+    /// 解析成功；`declarations` 为 None 表示无法识别
+    Parsed {
+        raw_modifiers: String,
+        declarations: Option<Vec<Declaration>>,
+    },
+}
+
+/// 缓存统计，用于调整容量
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStats {
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中（实际解析、转换）次数
+    pub misses: u64,
+    /// 因容量上限被淘汰的条目数
+    pub evictions: u64,
+    /// 当前条目数
+    pub entries: usize,
+    /// 容量上限
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// 命中率，尚未查询过时为 0
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    /// 选项 → 类名 → (结果, 最近使用时刻)
+    entries: HashMap<OptionsKey, HashMap<String, (CachedClass, u64)>>,
+    len: usize,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl CacheState {
+    /// 淘汰最久未使用的一半条目
+    fn evict(&mut self) {
+        let mut ticks: Vec<u64> = self
+            .entries
+            .values()
+            .flat_map(|classes| classes.values().map(|(_, tick)| *tick))
+            .collect();
+        if ticks.is_empty() {
+            return;
+        }
+        let count = (ticks.len() / 2).max(1);
+        let cutoff = *ticks.select_nth_unstable(count - 1).1;
+        for classes in self.entries.values_mut() {
+            classes.retain(|_, (_, tick)| *tick > cutoff);
+        }
+        self.entries.retain(|_, classes| !classes.is_empty());
+        let len = self.entries.values().map(HashMap::len).sum();
+        self.evictions += (self.len - len) as u64;
+        self.len = len;
+    }
+}
+
+/// 类级转换缓存，可在线程间共享
+pub struct ConversionCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl ConversionCache {
+    /// 创建默认容量（`DEFAULT_CAPACITY`）的缓存
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// 创建指定容量的缓存（至少为 1）
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// 当前统计信息
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            evictions: state.evictions,
+            entries: state.len,
+            capacity: self.capacity,
+        }
+    }
+
+    /// 清空缓存条目与统计
+    pub fn clear(&self) {
+        *self.lock() = CacheState::default();
+    }
+
+    /// 查询缓存，未命中时调用 `convert` 计算并写入
+    pub(crate) fn get_or_insert_with(
+        &self,
+        class: &str,
+        options: OptionsKey,
+        convert: impl FnOnce() -> CachedClass,
+    ) -> CachedClass {
+        {
+            let mut state = self.lock();
+            state.tick += 1;
+            let tick = state.tick;
+            let hit = state
+                .entries
+                .get_mut(&options)
+                .and_then(|classes| classes.get_mut(class))
+                .map(|(cached, last_used)| {
+                    *last_used = tick;
+                    cached.clone()
+                });
+            if let Some(cached) = hit {
+                state.hits += 1;
+                return cached;
+            }
+            state.misses += 1;
+        }
+
+        // 转换时不持有锁，其他线程可继续查询
+        let cached = convert();
+
+        let mut state = self.lock();
+        if state.len >= self.capacity {
+            state.evict();
+        }
+        state.tick += 1;
+        let tick = state.tick;
+        let previous = state
+            .entries
+            .entry(options)
+            .or_default()
+            .insert(class.to_string(), (cached.clone(), tick));
+        if previous.is_none() {
+            state.len += 1;
+        }
+        cached
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        // 缓存内容总是完整写入，其他线程 panic 后仍可继续使用
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for ConversionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(color_mode: ColorMode) -> OptionsKey {
+        OptionsKey {
+            use_variables: true,
+            variable_overrides: CssVariableOverrides::default(),
+            color_mode,
+            use_color_mix: false,
+            resolve_spacing: false,
+            fallback_index: None,
+            target_version: TailwindVersion::V4,
+            unit_conversion: UnitConversion::None,
+            root_font_size: 16,
+            custom_utilities: None,
+        }
+    }
+
+    fn parsed(value: &str) -> CachedClass {
+        CachedClass::Parsed {
+            raw_modifiers: String::new(),
+            declarations: Some(vec![Declaration::new("color", value)]),
+        }
+    }
+
+    #[test]
+    fn test_cache_keyed_by_class_and_options() {
+        let cache = ConversionCache::new();
+        cache.get_or_insert_with("text-red-500", options(ColorMode::Hex), || parsed("#fb2c36"));
+        cache.get_or_insert_with("text-red-500", options(ColorMode::Hex), || unreachable!());
+        let oklch = cache.get_or_insert_with("text-red-500", options(ColorMode::Oklch), || {
+            parsed("oklch(0.637 0.237 25.331)")
+        });
+        assert!(matches!(oklch, CachedClass::Parsed { declarations: Some(d), .. } if d[0].value.starts_with("oklch")));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 2));
+        assert!((stats.hit_rate() - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let cache = ConversionCache::with_capacity(4);
+        for class in ["a", "b", "c", "d"] {
+            cache.get_or_insert_with(class, options(ColorMode::Hex), || parsed(class));
+        }
+        // a、b 最近被使用过，淘汰时保留
+        for class in ["a", "b"] {
+            cache.get_or_insert_with(class, options(ColorMode::Hex), || unreachable!());
+        }
+        cache.get_or_insert_with("e", options(ColorMode::Hex), || parsed("e"));
+
+        let stats = cache.stats();
+        assert_eq!(stats.evictions, 2);
+        assert_eq!(stats.entries, 3);
+        cache.get_or_insert_with("a", options(ColorMode::Hex), || unreachable!());
+        cache.get_or_insert_with("e", options(ColorMode::Hex), || unreachable!());
+
+        cache.clear();
+        assert_eq!(cache.stats(), CacheStats { capacity: 4, ..Default::default() });
+    }
+}
@@ -1,11 +1,15 @@
 use crate::variant::{
-    self, parameterized_selector, pseudo_class_at_rule, pseudo_class_selector,
-    pseudo_element_selector, responsive_at_rule, supports_at_rule, StateResolution,
+    self, parameterized_selector, pseudo_class_at_rule, pseudo_element_selector,
+    responsive_at_rule_with, supports_at_rule, CustomVariant, StateResolution,
 };
+use crate::converter::CssRule;
+use crate::fallback::add_color_fallbacks;
+use crate::prefix::add_vendor_prefixes;
 use crate::shorthand::optimize_shorthands;
-use headwind_core::Declaration;
+use headwind_core::{BreakpointStyle, Declaration, Targets};
 use headwind_tw_parse::{parse_modifiers_from_raw, Modifier};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// CSS 类上下文 - 收集某个 CSS 类的所有声明
 ///
@@ -14,16 +18,74 @@ use std::collections::HashMap;
 pub struct ClassContext {
     /// 生成的 CSS 类名
     pub class_name: String,
+    /// 自定义基础选择器，None 时为 `.{class_name}`
+    selector: Option<String>,
     /// raw_modifiers -> declarations
     /// modifiers 在需要时从 raw_modifiers 解析
     groups: HashMap<String, Vec<Declaration>>,
+    /// 断点 at-rule 的单位与写法
+    breakpoints: BreakpointStyle,
+    /// 目标浏览器，决定输出时补充的厂商前缀与颜色回退
+    targets: Targets,
+    /// `@custom-variant` 定义的变体，优先于同名的内置变体
+    custom_variants: Arc<HashMap<String, CustomVariant>>,
+    /// 无法转换为声明的类，由 `Bundler::bundle_to_context` 记录
+    unconverted: Vec<String>,
 }
 
 impl ClassContext {
     pub fn new(class_name: String) -> Self {
         Self {
             class_name,
+            selector: None,
             groups: HashMap::new(),
+            breakpoints: BreakpointStyle::default(),
+            targets: Targets::default(),
+            custom_variants: Arc::default(),
+            unconverted: Vec::new(),
+        }
+    }
+
+    /// 使用自定义基础选择器代替 `.{class_name}`（如 `[data-hw="c_abc"]`），
+    /// 修饰符照常拼接在其后
+    pub fn with_selector(mut self, selector: impl Into<String>) -> Self {
+        self.selector = Some(selector.into());
+        self
+    }
+
+    pub(crate) fn with_unconverted(mut self, classes: Vec<String>) -> Self {
+        self.unconverted = classes;
+        self
+    }
+
+    /// 打包时无法转换为声明的类（按出现顺序），这些类不产出规则
+    pub fn unconverted(&self) -> &[String] {
+        &self.unconverted
+    }
+
+    /// 设置断点 at-rule 的单位与写法
+    pub fn with_breakpoints(mut self, style: BreakpointStyle) -> Self {
+        self.breakpoints = style;
+        self
+    }
+
+    /// 设置目标浏览器，输出时为需要的声明补充厂商前缀与颜色回退
+    pub fn with_targets(mut self, targets: Targets) -> Self {
+        self.targets = targets;
+        self
+    }
+
+    /// 设置 `@custom-variant` 定义的变体，同名时覆盖内置变体（如重新定义 `dark`）
+    pub fn with_custom_variants(mut self, variants: Arc<HashMap<String, CustomVariant>>) -> Self {
+        self.custom_variants = variants;
+        self
+    }
+
+    /// 基础选择器
+    fn base_selector(&self) -> String {
+        match &self.selector {
+            Some(selector) => selector.clone(),
+            None => format!(".{}", self.class_name),
         }
     }
 
@@ -42,47 +104,75 @@ impl ClassContext {
     }
 
     /// 生成 CSS 字符串
+    ///
+    /// 基础规则最先输出，带修饰符的规则按 Tailwind v4 的变体顺序输出（见 `variant::variant_order`），
+    /// 因此同时命中时：`hover` < `focus` < `focus-visible` < `active` < `disabled`，
+    /// 断点从小到大，`dark` 在所有断点之后；叠加修饰符时由排位最靠后的修饰符决定顺序
     pub fn to_css(&self, indent: &str) -> String {
         let mut css = String::new();
+        self.write_css(&mut css, indent);
+        css
+    }
+
+    /// 与 `to_css` 相同，但追加写入已有的缓冲区，避免为每个类单独分配字符串
+    pub fn write_css(&self, css: &mut String, indent: &str) {
+        self.write_css_with(css, indent, &mut |_| {});
+    }
 
+    /// 与 `write_css` 相同，但每条规则写出前先交给 `on_rule` 处理
+    ///
+    /// `on_rule` 收到最终的选择器（如 `.c_1:hover`）与声明，可以改写二者；
+    /// 清空声明时跳过该规则。包裹规则的 at-rule（`@media` 等）不受影响。
+    pub fn write_css_with(
+        &self,
+        css: &mut String,
+        indent: &str,
+        on_rule: &mut dyn FnMut(&mut CssRule),
+    ) {
         // 1. 生成基础规则（无修饰符）
         if let Some(decls) = self.groups.get("") {
             if !decls.is_empty() {
-                let decls = optimize_shorthands(decls.clone());
-                css.push_str(&format!(".{} {{\n", self.class_name));
-                for decl in &decls {
-                    css.push_str(&format!("{}{}: {};\n", indent, decl.property, decl.value));
+                let mut rule = CssRule {
+                    selector: self.base_selector(),
+                    declarations: self.add_compat_declarations(optimize_shorthands(decls.clone())),
+                };
+                on_rule(&mut rule);
+                if !rule.declarations.is_empty() {
+                    css.push_str(&format!("{} {{\n", rule.selector));
+                    for decl in &rule.declarations {
+                        css.push_str(&format!("{}{}: {};\n", indent, decl.property, decl.value));
+                    }
+                    css.push_str("}\n");
                 }
-                css.push_str("}\n");
             }
         }
 
-        // 2. 生成带修饰符的规则
+        // 2. 生成带修饰符的规则（在需要时从 raw_modifiers 解析出 modifiers）
         let mut modifier_groups: Vec<_> = self
             .groups
             .iter()
-            .filter(|(raw, _)| !raw.is_empty())
+            .filter(|(raw, decls)| !raw.is_empty() && !decls.is_empty())
+            .map(|(raw, decls)| (parse_modifiers_from_raw(raw), raw, decls))
             .collect();
 
-        // 按修饰符排序，保证输出稳定
-        modifier_groups.sort_by_key(|(raw, _)| raw.as_str());
+        // 按 Tailwind 变体顺序排序：后输出的规则在同时命中时优先，
+        // 顺序相同时按原始字符串排序，保证输出稳定
+        modifier_groups.sort_by_cached_key(|(modifiers, raw, _)| {
+            (variant::variant_sort_key(modifiers), *raw)
+        });
 
-        for (raw_modifiers, decls) in modifier_groups {
-            if decls.is_empty() {
-                continue;
-            }
-
-            // 在需要时从 raw_modifiers 解析出 modifiers
-            let modifiers = parse_modifiers_from_raw(raw_modifiers);
-
-            // 简写属性优化
-            let optimized = optimize_shorthands(decls.clone());
+        for (modifiers, _, decls) in modifier_groups {
+            // 简写属性优化，之后补充厂商前缀与颜色回退（与原声明同名时不能参与合并）
+            let optimized = self.add_compat_declarations(optimize_shorthands(decls.clone()));
 
             // 根据修饰符类型生成选择器
-            self.generate_selector_with_modifiers(&mut css, &modifiers, &optimized, indent);
+            self.generate_selector_with_modifiers(css, &modifiers, optimized, indent, on_rule);
         }
+    }
 
-        css
+    /// 按 `targets` 补充厂商前缀与颜色回退声明
+    fn add_compat_declarations(&self, declarations: Vec<Declaration>) -> Vec<Declaration> {
+        add_color_fallbacks(add_vendor_prefixes(declarations, &self.targets), &self.targets)
     }
 
     /// 根据修饰符生成选择器
@@ -90,8 +180,9 @@ impl ClassContext {
         &self,
         css: &mut String,
         modifiers: &[Modifier],
-        declarations: &[Declaration],
+        declarations: Vec<Declaration>,
         indent: &str,
+        on_rule: &mut dyn FnMut(&mut CssRule),
     ) {
         if modifiers.is_empty() {
             return;
@@ -102,14 +193,21 @@ impl ClassContext {
         let mut selector_mods: Vec<&Modifier> = Vec::new();
 
         for modifier in modifiers {
+            if let Some(custom) = self.custom_variants.get(variant::modifier_name(modifier)) {
+                at_rules.extend(custom.at_rules.iter().cloned());
+                selector_mods.push(modifier);
+                continue;
+            }
             match modifier {
                 Modifier::Responsive(name) => {
                     // Container queries start with @
                     if let Some(container_name) = name.strip_prefix('@') {
-                        if let Some(rule) = variant::container_at_rule(container_name) {
+                        if let Some(rule) =
+                            variant::container_at_rule_with(container_name, self.breakpoints)
+                        {
                             at_rules.push(rule);
                         }
-                    } else if let Some(rule) = responsive_at_rule(name) {
+                    } else if let Some(rule) = responsive_at_rule_with(name, self.breakpoints) {
                         at_rules.push(rule);
                     }
                 }
@@ -137,11 +235,24 @@ impl ClassContext {
             }
         }
 
-        // Build the selector
-        let mut selector = format!(".{}", self.class_name);
+        // Build the selector; pseudo-elements must come last (`.c:hover::before`)
+        variant::pseudo_elements_last(&mut selector_mods);
+        let mut selector = self.base_selector();
         for modifier in &selector_mods {
             selector = self.apply_modifier(&selector, modifier);
         }
+        let mut rule = CssRule {
+            selector,
+            declarations,
+        };
+        on_rule(&mut rule);
+        if rule.declarations.is_empty() {
+            return;
+        }
+        let CssRule {
+            selector,
+            declarations,
+        } = rule;
 
         if !at_rules.is_empty() {
             css.push('\n');
@@ -155,7 +266,7 @@ impl ClassContext {
             let decl_prefix = indent.repeat(depth + 1);
 
             css.push_str(&format!("{}{} {{\n", sel_prefix, selector));
-            for decl in declarations {
+            for decl in &declarations {
                 css.push_str(&format!(
                     "{}{}: {};\n",
                     decl_prefix, decl.property, decl.value
@@ -171,7 +282,7 @@ impl ClassContext {
         } else {
             css.push('\n');
             css.push_str(&format!("{} {{\n", selector));
-            for decl in declarations {
+            for decl in &declarations {
                 css.push_str(&format!("{}{}: {};\n", indent, decl.property, decl.value));
             }
             css.push_str("}\n");
@@ -180,22 +291,12 @@ impl ClassContext {
 
     /// Apply a single modifier to a selector, using the centralized variant resolver
     fn apply_modifier(&self, selector: &str, modifier: &Modifier) -> String {
+        if let Some(custom) = self.custom_variants.get(variant::modifier_name(modifier)) {
+            return custom.apply(selector);
+        }
         match modifier {
-            Modifier::PseudoClass(name) => {
-                // Parameterized pseudo-classes: has-[...], not-[...], aria-[...], data-[...], etc.
-                if let Some(param_sel) = parameterized_selector(name) {
-                    format!("{}{}", selector, param_sel)
-                } else if name == "*" {
-                    // Child selector: direct children
-                    format!("{} > *", selector)
-                } else if name == "**" {
-                    // Descendant selector: all descendants
-                    format!("{} *", selector)
-                } else {
-                    let css_pseudo = pseudo_class_selector(name);
-                    format!("{}:{}", selector, css_pseudo)
-                }
-            }
+            // Parameterized (has-[...], aria-*, data-*, ...), child (*, **) and named pseudo-classes
+            Modifier::PseudoClass(name) => variant::apply_pseudo_class(selector, name),
             Modifier::PseudoElement(name) => {
                 let css_pseudo = pseudo_element_selector(name);
                 format!("{}::{}", selector, css_pseudo)
@@ -256,6 +357,39 @@ mod tests {
         assert!(css.contains(".my-class:hover {"));
     }
 
+    #[test]
+    fn test_context_custom_selector() {
+        let mut ctx = ClassContext::new("c_1".to_string()).with_selector(r#"[data-hw="c_1"]"#);
+        ctx.write("", vec![Declaration::new("padding", "1rem")]);
+        ctx.write("focus:", vec![Declaration::new("padding", "2rem")]);
+
+        let css = ctx.to_css("  ");
+        assert!(css.contains("[data-hw=\"c_1\"] {"));
+        assert!(css.contains("[data-hw=\"c_1\"]:focus {"));
+        assert!(!css.contains(".c_1"));
+    }
+
+    #[test]
+    fn test_write_css_with_rule_hook() {
+        let mut ctx = ClassContext::new("c_1".to_string());
+        ctx.write("", vec![Declaration::new("padding", "1rem")]);
+        ctx.write("md:", vec![Declaration::new("margin", "0")]);
+        ctx.write("focus:", vec![Declaration::new("outline", "none")]);
+
+        let mut css = String::new();
+        let mut seen = Vec::new();
+        ctx.write_css_with(&mut css, "  ", &mut |rule| {
+            seen.push(rule.selector.clone());
+            rule.selector = rule.selector.replace(".c_1", ".card");
+            rule.declarations.retain(|decl| decl.property != "outline");
+        });
+        assert_eq!(seen, [".c_1", ".c_1:focus", ".c_1"]);
+        assert!(css.starts_with(".card {\n  padding: 1rem;\n}\n"), "{}", css);
+        assert!(css.contains("@media (width >= 48rem) {\n  .card {\n    margin: 0;"), "{}", css);
+        // 清空声明的规则不输出
+        assert!(!css.contains(":focus"), "{}", css);
+    }
+
     #[test]
     fn test_context_merge_same_modifiers() {
         let mut ctx = ClassContext::new("my-class".to_string());
@@ -274,4 +408,21 @@ mod tests {
         // hover is wrapped in @media (hover: hover)
         assert!(css.contains("@media (hover: hover)"));
     }
+
+    #[test]
+    fn test_context_stacked_variant_order() {
+        let mut ctx = ClassContext::new("c".to_string());
+        ctx.write("md:hover:", vec![Declaration::new("color", "red")]);
+        ctx.write("md:", vec![Declaration::new("color", "green")]);
+        ctx.write("active:", vec![Declaration::new("color", "blue")]);
+        ctx.write("hover:", vec![Declaration::new("color", "black")]);
+
+        let css = ctx.to_css("  ");
+        let order: Vec<_> = ["black", "blue", "green", "red"]
+            .iter()
+            .map(|color| css.find(&format!("color: {};", color)).unwrap())
+            .collect();
+        // hover < active < md < md:hover
+        assert!(order.windows(2).all(|w| w[0] < w[1]), "{}", css);
+    }
 }
@@ -1,4 +1,7 @@
-use crate::plugin_map::get_plugin_properties;
+use super::mask::{build_arbitrary_mask, build_variable_mask};
+use super::negative::negate_value;
+use super::standard::{ring_offset_width, skew_declarations};
+use crate::plugin_map::{filter_function, get_plugin_properties};
 use headwind_core::Declaration;
 use headwind_tw_parse::{CssVariableValue, ParsedClass};
 
@@ -6,67 +9,144 @@ use headwind_tw_parse::{CssVariableValue, ParsedClass};
 ///
 /// 例如：`w-[13px]` → `width: 13px`
 pub(super) fn build_arbitrary_declarations(parsed: &ParsedClass, raw_value: &str) -> Option<Vec<Declaration>> {
+    // `w-[]`、`w-[_]` 等空值会生成没有值的声明
+    if raw_value.trim().is_empty() {
+        return None;
+    }
+
     // 不在 plugin_map 中的复杂插件，走专门的分发逻辑
     if let Some(decls) = build_complex_arbitrary(parsed, raw_value) {
         return Some(decls);
     }
 
+    let value = if parsed.negative {
+        negate_value(raw_value)?
+    } else {
+        raw_value.to_string()
+    };
+    // 滤镜值包进对应的滤镜函数：`brightness-[1.75]` → `filter: brightness(1.75)`
+    if let Some((property, function)) = filter_function(&parsed.plugin) {
+        return Some(vec![Declaration::new(property, format!("{}({})", function, value))]);
+    }
+
     let properties = get_plugin_properties(&parsed.plugin)?;
     let declarations = properties
         .into_iter()
-        .map(|property| {
-            let value = if parsed.negative {
-                format!("-{}", raw_value)
-            } else {
-                raw_value.to_string()
-            };
-            Declaration::new(property, value)
-        })
+        .map(|property| Declaration::new(property, value.clone()))
         .collect();
 
     Some(declarations)
 }
 
+/// 为带类型提示的任意值构建声明
+///
+/// 例如：`bg-[position:center_top_1rem]` → `background-position: center top 1rem`，
+/// `bg-[length:200px_100px]` → `background-size: 200px 100px`。
+/// 插件没有与提示对应的属性时去掉提示，按普通任意值处理（`w-[length:10px]` → `width: 10px`）。
+pub(super) fn build_hinted_arbitrary_declarations(
+    parsed: &ParsedClass,
+    hint: &str,
+    value: &str,
+) -> Option<Vec<Declaration>> {
+    if value.trim().is_empty() {
+        return None;
+    }
+    match hinted_property(&parsed.plugin, hint) {
+        Some(property) => Some(vec![Declaration::new(property, value)]),
+        None => build_arbitrary_declarations(parsed, value),
+    }
+}
+
+/// 多语义插件按类型提示选择属性，任意值（`bg-[position:…]`）与 CSS 变量（`bg-(position:--x)`）共用
+fn hinted_property(plugin: &str, hint: &str) -> Option<&'static str> {
+    let property = match (plugin, hint) {
+        ("bg", "position") => "background-position",
+        ("bg", "length" | "size" | "percentage" | "bg-size") => "background-size",
+        ("bg", "image" | "url") => "background-image",
+        ("bg", "color") => "background-color",
+        ("mask", "position") => "mask-position",
+        ("mask", "length" | "size" | "percentage") => "mask-size",
+        ("mask", "image" | "url") => "mask-image",
+        ("object", "position") => "object-position",
+        ("text", "color") => "color",
+        ("text", "length" | "size" | "percentage" | "absolute-size" | "relative-size") => {
+            "font-size"
+        }
+        ("font", "family-name" | "generic-name") => "font-family",
+        ("font", "number" | "weight") => "font-weight",
+        ("border", "color") => "border-color",
+        ("border", "length" | "size" | "line-width") => "border-width",
+        ("outline", "color") => "outline-color",
+        ("outline", "length" | "line-width") => "outline-width",
+        ("ring-offset", "color") => "--tw-ring-offset-color",
+        ("decoration", "color") => "text-decoration-color",
+        ("decoration", "length" | "line-width" | "percentage") => "text-decoration-thickness",
+        ("stroke", "color") => "stroke",
+        ("stroke", "length" | "number" | "percentage") => "stroke-width",
+        ("from", "color") => "--tw-gradient-from",
+        ("from", "length" | "percentage") => "--tw-gradient-from-position",
+        ("via", "color") => "--tw-gradient-via",
+        ("via", "length" | "percentage") => "--tw-gradient-via-position",
+        ("to", "color") => "--tw-gradient-to",
+        ("to", "length" | "percentage") => "--tw-gradient-to-position",
+        _ => return None,
+    };
+    Some(property)
+}
+
 /// 为 CSS 自定义属性值构建声明
 ///
 /// Tailwind v4 的 `-(...)` 语法：
 /// - `bg-(--my-color)` → `background: var(--my-color)`
 /// - `bg-(image:--my-bg)` → `background-image: var(--my-bg)`
+/// - `bg-(position:--my-pos)` → `background-position: var(--my-pos)`
 /// - `bg-linear-(--custom)` → `background-image: linear-gradient(var(--tw-gradient-stops, var(--custom)))`
 /// - `from-(--my-color)` → `--tw-gradient-from: var(--my-color)`
 pub(super) fn build_css_variable_declarations(
     parsed: &ParsedClass,
     cv: &CssVariableValue,
 ) -> Option<Vec<Declaration>> {
+    // `p-()`、`p-(:)` 没有可引用的属性名，输出 `var()` 是无效 CSS
+    if cv.property.trim().is_empty() {
+        return None;
+    }
     let var_expr = format!("var({})", cv.property);
+    // `-m-(--gap)` → `calc(var(--gap) * -1)`
+    let var_expr = if parsed.negative {
+        negate_value(&var_expr)?
+    } else {
+        var_expr
+    };
+
+    // 遮罩渐变色标的类型提示区分颜色与位置
+    if let Some(decls) = build_variable_mask(parsed, cv.type_hint.as_deref(), &var_expr) {
+        return Some(decls);
+    }
 
     // 有类型提示时，根据提示选择 CSS 属性
     if let Some(ref hint) = cv.type_hint {
-        let property = match hint.as_str() {
+        if let Some(property) = hinted_property(&parsed.plugin, hint) {
+            return Some(vec![Declaration::new(property, var_expr)]);
+        }
+        if parsed.plugin == "ring-offset" && matches!(hint.as_ref(), "length" | "line-width") {
+            return Some(ring_offset_width(var_expr));
+        }
+        let property = match hint.as_ref() {
             "image" => "background-image",
             "color" => "color",
             "font" => "font-family",
             "length" | "size" => "width", // 回退到 plugin_map
+            "" => return None,
             _ => {
                 // 未知类型提示，尝试直接作为属性名
-                return Some(vec![Declaration::new(hint.as_str(), var_expr)]);
+                return Some(vec![Declaration::new(hint.as_ref(), var_expr)]);
             }
         };
-        // 对于有类型提示的情况，优先使用提示指定的属性
-        // 但如果 plugin 自身有特定映射（如 bg → background），也参考 plugin
-        let final_property = match (parsed.plugin.as_str(), hint.as_str()) {
-            ("bg", "image") => "background-image",
-            ("bg", "color") => "background-color",
-            ("text", "color") => "color",
-            ("text", "length") | ("text", "size") => "font-size",
-            ("border", "length") | ("border", "size") => "border-width",
-            _ => property,
-        };
-        return Some(vec![Declaration::new(final_property, var_expr)]);
+        return Some(vec![Declaration::new(property, var_expr)]);
     }
 
     // 无类型提示时，走专门的插件分发逻辑
-    match parsed.plugin.as_str() {
+    match parsed.plugin.as_ref() {
         // 渐变系列
         "bg-linear" => Some(vec![Declaration::new(
             "background-image",
@@ -84,6 +164,7 @@ pub(super) fn build_css_variable_declarations(
         "to" => Some(vec![Declaration::new("--tw-gradient-to", var_expr)]),
         // text 默认映射到 color
         "text" => Some(vec![Declaration::new("color", var_expr)]),
+        "content" => Some(content_declarations(var_expr)),
         // 颜色双语义插件：CSS 变量总是映射到颜色属性
         "border" => Some(vec![Declaration::new("border-color", var_expr)]),
         "outline" => Some(vec![Declaration::new("outline-color", var_expr)]),
@@ -91,8 +172,16 @@ pub(super) fn build_css_variable_declarations(
         "stroke" => Some(vec![Declaration::new("stroke", var_expr)]),
         "shadow" => Some(vec![Declaration::new("--tw-shadow-color", var_expr)]),
         "inset-shadow" => Some(vec![Declaration::new("--tw-inset-shadow-color", var_expr)]),
+        "text-shadow" => Some(vec![Declaration::new("--tw-text-shadow-color", var_expr)]),
         "ring" => Some(vec![Declaration::new("--tw-ring-shadow", format!("0 0 0 {}", var_expr))]),
+        "ring-offset" => Some(vec![Declaration::new("--tw-ring-offset-color", var_expr)]),
         "inset-ring" => Some(vec![Declaration::new("--tw-inset-ring-shadow", format!("inset 0 0 0 {}", var_expr))]),
+        plugin @ ("skew" | "skew-x" | "skew-y") => Some(skew_declarations(plugin, &var_expr)),
+        // 滤镜：`brightness-(--amount)` → `filter: brightness(var(--amount))`
+        plugin if filter_function(plugin).is_some() => {
+            let (property, function) = filter_function(plugin)?;
+            Some(vec![Declaration::new(property, format!("{}({})", function, var_expr))])
+        }
         // 通用：使用 plugin_map 查找 CSS 属性
         _ => {
             let properties = get_plugin_properties(&parsed.plugin)?;
@@ -112,10 +201,68 @@ pub(super) fn extract_bracket_value(s: &str) -> Option<&str> {
     s.strip_prefix('[').and_then(|s| s.strip_suffix(']'))
 }
 
+/// `content` 工具类的声明：取值写入 `--tw-content`，`content` 引用该变量
+///
+/// 与 Tailwind v4 一致，伪元素变体可以用 `var(--tw-content)` 复用同一取值
+pub(super) fn content_declarations(value: impl Into<String>) -> Vec<Declaration> {
+    vec![
+        Declaration::new("--tw-content", value),
+        Declaration::new("content", "var(--tw-content)"),
+    ]
+}
+
+/// 规范化 `content` 任意值
+///
+/// - 关键字与含字符串、函数（`attr()`、`counter()`、`var()` 等）的值原样保留，
+///   未闭合的字符串补全结尾引号：`['hello]` → `'hello'`
+/// - 其余裸文本包装为双引号字符串（转义其中的反斜杠）：`[→]` → `"→"`
+fn quote_content(value: &str) -> String {
+    const KEYWORDS: [&str; 11] = [
+        "none",
+        "normal",
+        "open-quote",
+        "close-quote",
+        "no-open-quote",
+        "no-close-quote",
+        "inherit",
+        "initial",
+        "unset",
+        "revert",
+        "revert-layer",
+    ];
+    if KEYWORDS.contains(&value) {
+        return value.to_string();
+    }
+    if !value.contains(['\'', '"', '(']) {
+        return format!("\"{}\"", value.replace('\\', "\\\\"));
+    }
+
+    let mut quote = None;
+    let mut escaped = false;
+    for c in value.chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None => {}
+        }
+    }
+    match quote {
+        // 结尾的反斜杠会转义补上的引号，去掉
+        Some(q) => {
+            let value = value.strip_suffix('\\').filter(|_| escaped).unwrap_or(value);
+            format!("{}{}", value, q)
+        }
+        None => value.to_string(),
+    }
+}
+
 /// 判断任意值是否看起来像颜色值
 ///
 /// 用于双语义插件（如 border）区分颜色和非颜色的任意值
-fn looks_like_color_value(value: &str) -> bool {
+pub(super) fn looks_like_color_value(value: &str) -> bool {
     value.starts_with('#')
         || value.starts_with("rgb")
         || value.starts_with("hsl")
@@ -124,22 +271,51 @@ fn looks_like_color_value(value: &str) -> bool {
         || value.starts_with("color(")
 }
 
+/// 判断任意值是否看起来像长度或百分比（`10%`、`2rem`、`-1px`、`calc(...)`）
+///
+/// 用于渐变色标区分颜色和位置
+fn looks_like_length_value(value: &str) -> bool {
+    let number = value.strip_prefix('-').unwrap_or(value);
+    number.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        || ["calc(", "min(", "max(", "clamp("]
+            .iter()
+            .any(|function| value.starts_with(function))
+}
+
 /// 处理复杂任意值插件
 fn build_complex_arbitrary(parsed: &ParsedClass, raw_value: &str) -> Option<Vec<Declaration>> {
-    match parsed.plugin.as_str() {
+    match parsed.plugin.as_ref() {
         // text-[#fff] → color, text-[14px] → font-size
         "text" => {
-            let value = if parsed.negative {
-                format!("-{}", raw_value)
+            if looks_like_color_value(raw_value) {
+                Some(vec![Declaration::new("color", raw_value)])
+            } else {
+                Some(vec![Declaration::new("font-size", raw_value)])
+            }
+        }
+        // content-['hello_world'] → --tw-content: 'hello world'（content 取值见 `quote_content`）
+        "content" => Some(content_declarations(quote_content(raw_value))),
+        // skew-x-[3deg] → --tw-skew-x: skewX(3deg)
+        plugin @ ("skew" | "skew-x" | "skew-y") => {
+            let angle = if parsed.negative {
+                negate_value(raw_value)?
             } else {
                 raw_value.to_string()
             };
-            if looks_like_color_value(raw_value) {
-                Some(vec![Declaration::new("color", value)])
+            Some(skew_declarations(plugin, &angle))
+        }
+        // object-[25%_75%] → object-position（object-fit 只有关键字）
+        "object" => Some(vec![Declaration::new("object-position", raw_value)]),
+        // mask-[url(...)] / mask-[linear-gradient(...)] → mask-image
+        "mask" => {
+            if raw_value.starts_with("url(") || raw_value.contains("gradient(") {
+                Some(vec![Declaration::new("mask-image", raw_value)])
             } else {
-                Some(vec![Declaration::new("font-size", value)])
+                None
             }
         }
+        // mask-t-from-[20px] / mask-linear-[1rad] → 遮罩渐变
+        plugin if plugin.starts_with("mask-") => build_arbitrary_mask(parsed, raw_value),
         // bg-linear-[<value>] → linear-gradient
         "bg-linear" => Some(vec![Declaration::new(
             "background-image",
@@ -155,21 +331,15 @@ fn build_complex_arbitrary(parsed: &ParsedClass, raw_value: &str) -> Option<Vec<
             "background-image",
             format!("radial-gradient(var(--tw-gradient-stops, {}))", raw_value),
         )]),
-        // from-[<value>] → --tw-gradient-from
-        "from" => Some(vec![Declaration::new(
-            "--tw-gradient-from",
-            raw_value.to_string(),
-        )]),
-        // via-[<value>] → --tw-gradient-via
-        "via" => Some(vec![Declaration::new(
-            "--tw-gradient-via",
-            raw_value.to_string(),
-        )]),
-        // to-[<value>] → --tw-gradient-to
-        "to" => Some(vec![Declaration::new(
-            "--tw-gradient-to",
-            raw_value.to_string(),
-        )]),
+        // from-[#f00] → --tw-gradient-from，from-[10%] / from-[2rem] → --tw-gradient-from-position
+        "from" | "via" | "to" => {
+            let property = if looks_like_length_value(raw_value) {
+                format!("--tw-gradient-{}-position", parsed.plugin)
+            } else {
+                format!("--tw-gradient-{}", parsed.plugin)
+            };
+            Some(vec![Declaration::new(property, raw_value)])
+        }
         // border-[<color>] → border-color（仅颜色值，非颜色回退到 plugin_map 的 border-width）
         "border" => {
             if looks_like_color_value(raw_value) {
@@ -218,6 +388,14 @@ fn build_complex_arbitrary(parsed: &ParsedClass, raw_value: &str) -> Option<Vec<
                 Some(vec![Declaration::new("box-shadow", raw_value.to_string())])
             }
         }
+        // text-shadow-[<color>] → --tw-text-shadow-color, else text-shadow
+        "text-shadow" => {
+            if looks_like_color_value(raw_value) {
+                Some(vec![Declaration::new("--tw-text-shadow-color", raw_value)])
+            } else {
+                Some(vec![Declaration::new("text-shadow", raw_value.to_string())])
+            }
+        }
         // ring-[<color>] → --tw-ring-color, ring-[<width>] → --tw-ring-shadow
         "ring" => {
             if looks_like_color_value(raw_value) {
@@ -226,6 +404,14 @@ fn build_complex_arbitrary(parsed: &ParsedClass, raw_value: &str) -> Option<Vec<
                 Some(vec![Declaration::new("--tw-ring-shadow", format!("0 0 0 {}", raw_value))])
             }
         }
+        // ring-offset-[<color>] → --tw-ring-offset-color, ring-offset-[<width>] → --tw-ring-offset-width
+        "ring-offset" => {
+            if looks_like_color_value(raw_value) {
+                Some(vec![Declaration::new("--tw-ring-offset-color", raw_value)])
+            } else {
+                Some(ring_offset_width(raw_value.to_string()))
+            }
+        }
         // inset-ring-[<color>] → --tw-inset-ring-color, inset-ring-[<width>] → --tw-inset-ring-shadow
         "inset-ring" => {
             if looks_like_color_value(raw_value) {
@@ -16,7 +16,9 @@ fn is_color_property(property: &str) -> bool {
             | "caret-color"
             | "--tw-shadow-color"
             | "--tw-inset-shadow-color"
+            | "--tw-text-shadow-color"
             | "--tw-ring-color"
+            | "--tw-ring-offset-color"
             | "--tw-inset-ring-color"
             | "--tw-gradient-from"
             | "--tw-gradient-via"
@@ -50,21 +52,62 @@ fn apply_alpha_to_hex(hex: &str, alpha_pct: f64) -> String {
     format!("#{}{}", body, alpha_hex)
 }
 
+/// 透明度修饰符的值
+#[derive(Debug, Clone, PartialEq)]
+enum Alpha {
+    /// 百分比：`/50`、`/[0.37]`（→ 37）、`/[37.5%]`
+    Percent(f64),
+    /// 编译期无法求值的表达式：`/(--opacity)` → `var(--opacity)`、`/[var(--a)]`
+    Expr(String),
+}
+
+impl Alpha {
+    /// 解析 `/` 之后的修饰符；无法识别（如 `/oklch`）时返回 None
+    fn parse(alpha: &str) -> Option<Self> {
+        if let Some(inner) = alpha.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            let inner = inner.trim();
+            if let Some(percent) = inner.strip_suffix('%') {
+                return percent.parse().ok().map(Alpha::percent);
+            }
+            // 方括号内的数字是 0-1 之间的比例
+            if let Ok(ratio) = inner.parse::<f64>() {
+                return Some(Alpha::percent(ratio * 100.0));
+            }
+            return (!inner.is_empty()).then(|| Alpha::Expr(inner.replace('_', " ")));
+        }
+        if let Some(inner) = alpha.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+            return inner
+                .starts_with("--")
+                .then(|| Alpha::Expr(format!("var({})", inner)));
+        }
+        alpha.parse().ok().map(Alpha::percent)
+    }
+
+    /// 保留至多 4 位小数，避免 `0.37 * 100` 输出 `37.00000000000001`
+    fn percent(value: f64) -> Self {
+        Alpha::Percent((value * 10000.0).round() / 10000.0)
+    }
+}
+
+impl std::fmt::Display for Alpha {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Alpha::Percent(pct) => write!(f, "{}%", pct),
+            Alpha::Expr(expr) => f.write_str(expr),
+        }
+    }
+}
+
 /// 为颜色值应用 alpha 透明度
 ///
 /// 根据值的格式选择不同的策略：
-/// - hex: #rrggbb → #rrggbbaa（支持短格式优化）
-/// - oklch/hsl/rgb: 在闭合括号前插入 `/ N%`
+/// - hex: #rrggbb → #rrggbbaa（支持短格式优化）；表达式透明度无法写进 hex，改用 color-mix
+/// - oklch/hsl/rgb: 在闭合括号前插入 `/ N%` 或 `/ var(--opacity)`
 /// - var(): 无法直接应用 alpha，需要 color-mix（此函数跳过）
 /// - transparent/currentColor: 跳过
-fn apply_alpha_to_color(value: &str, alpha: &str, use_color_mix: bool) -> String {
-    let alpha_pct: f64 = match alpha.parse() {
-        Ok(n) => n,
-        Err(_) => return value.to_string(),
-    };
-
+fn apply_alpha_to_color(value: &str, alpha: &Alpha, use_color_mix: bool) -> String {
     // 100% = 完全不透明 → 不修改
-    if (alpha_pct - 100.0).abs() < f64::EPSILON {
+    if matches!(alpha, Alpha::Percent(pct) if (pct - 100.0).abs() < f64::EPSILON) {
         return value.to_string();
     }
 
@@ -73,22 +116,24 @@ fn apply_alpha_to_color(value: &str, alpha: &str, use_color_mix: bool) -> String
         return value.to_string();
     }
 
+    let color_mix = || format!("color-mix(in oklab, {} {}, transparent)", value, alpha);
+
     // color-mix 模式：所有颜色值统一使用 color-mix
     if use_color_mix {
-        return format!(
-            "color-mix(in oklab, {} {}%, transparent)",
-            value, alpha_pct as u32
-        );
+        return color_mix();
     }
 
     if value.starts_with('#') {
-        apply_alpha_to_hex(value, alpha_pct)
+        match alpha {
+            Alpha::Percent(pct) => apply_alpha_to_hex(value, *pct),
+            Alpha::Expr(_) => color_mix(),
+        }
     } else if value.starts_with("var(") {
         // var() → 无法直接应用 alpha（需要 color-mix）
         value.to_string()
     } else if let Some(pos) = value.rfind(')') {
         // oklch(...) / hsl(...) / rgb(...) → 插入 / N%
-        format!("{} / {}%)", &value[..pos], alpha_pct as u32)
+        format!("{} / {})", &value[..pos], alpha)
     } else {
         value.to_string()
     }
@@ -100,11 +145,14 @@ pub(super) fn apply_alpha_to_declarations(
     alpha: &str,
     use_color_mix: bool,
 ) -> Vec<Declaration> {
+    let Some(alpha) = Alpha::parse(alpha) else {
+        return declarations;
+    };
     declarations
         .into_iter()
         .map(|mut decl| {
             if is_color_property(&decl.property) {
-                decl.value = apply_alpha_to_color(&decl.value, alpha, use_color_mix);
+                decl.value = apply_alpha_to_color(&decl.value, &alpha, use_color_mix);
             }
             decl
         })
@@ -0,0 +1,92 @@
+use headwind_core::Declaration;
+use headwind_tw_parse::ParsedClass;
+
+/// forms 插件 `form-select` 的下拉箭头（与插件默认的 gray-500 描边一致）
+const SELECT_CHEVRON: &str = "url(\"data:image/svg+xml,%3csvg xmlns='http://www.w3.org/2000/svg' fill='none' viewBox='0 0 20 20'%3e%3cpath stroke='%236b7280' stroke-linecap='round' stroke-linejoin='round' stroke-width='1.5' d='M6 8l4 4 4-4'/%3e%3c/svg%3e\")";
+
+/// 为第一方插件的兼容类构建声明
+///
+/// - `@tailwindcss/forms` 的 class 策略：`form-input`、`form-textarea`、`form-select`、
+///   `form-multiselect`、`form-checkbox`、`form-radio`，只生成基础样式，不含 `:focus` / `:checked` 状态
+/// - `@tailwindcss/aspect-ratio` 的旧写法：`aspect-w-16 aspect-h-9` 改用原生 `aspect-ratio`，
+///   两个类需写在同一个元素上
+pub(super) fn build_compat_declarations(parsed: &ParsedClass, value: &str) -> Option<Vec<Declaration>> {
+    match parsed.plugin.as_ref() {
+        "form" => build_form_declarations(value),
+        "aspect" => build_legacy_aspect_declarations(value),
+        _ => None,
+    }
+}
+
+/// forms 插件各控件的基础样式
+fn build_form_declarations(control: &str) -> Option<Vec<Declaration>> {
+    let text_field = || {
+        vec![
+            Declaration::new("appearance", "none"),
+            Declaration::new("background-color", "#fff"),
+            Declaration::new("border-color", "#6b7280"),
+            Declaration::new("border-width", "1px"),
+            Declaration::new("border-radius", "0px"),
+            Declaration::new("padding", "0.5rem 0.75rem"),
+            Declaration::new("font-size", "1rem"),
+            Declaration::new("line-height", "1.5rem"),
+            Declaration::new("--tw-shadow", "0 0 #0000"),
+        ]
+    };
+    let toggle = |radius: &str| {
+        vec![
+            Declaration::new("appearance", "none"),
+            Declaration::new("padding", "0"),
+            Declaration::new("print-color-adjust", "exact"),
+            Declaration::new("display", "inline-block"),
+            Declaration::new("vertical-align", "middle"),
+            Declaration::new("background-origin", "border-box"),
+            Declaration::new("user-select", "none"),
+            Declaration::new("flex-shrink", "0"),
+            Declaration::new("height", "1rem"),
+            Declaration::new("width", "1rem"),
+            Declaration::new("color", "#2563eb"),
+            Declaration::new("background-color", "#fff"),
+            Declaration::new("border-color", "#6b7280"),
+            Declaration::new("border-width", "1px"),
+            Declaration::new("border-radius", radius),
+            Declaration::new("--tw-shadow", "0 0 #0000"),
+        ]
+    };
+
+    let declarations = match control {
+        "input" | "textarea" | "multiselect" => text_field(),
+        "select" => {
+            let mut declarations = text_field();
+            declarations.extend([
+                Declaration::new("background-image", SELECT_CHEVRON),
+                Declaration::new("background-position", "right 0.5rem center"),
+                Declaration::new("background-repeat", "no-repeat"),
+                Declaration::new("background-size", "1.5em 1.5em"),
+                Declaration::new("padding-right", "2.5rem"),
+                Declaration::new("print-color-adjust", "exact"),
+            ]);
+            declarations
+        }
+        "checkbox" => toggle("0px"),
+        "radio" => toggle("100%"),
+        _ => return None,
+    };
+    Some(declarations)
+}
+
+/// `aspect-w-16` → `--tw-aspect-w: 16` + `aspect-ratio`，`aspect-h-9` → `--tw-aspect-h: 9`
+fn build_legacy_aspect_declarations(value: &str) -> Option<Vec<Declaration>> {
+    let (axis, ratio) = value.split_once('-')?;
+    if !ratio.bytes().all(|b| b.is_ascii_digit()) || !matches!(ratio.parse::<u32>(), Ok(n) if n > 0) {
+        return None;
+    }
+    match axis {
+        "w" => Some(vec![
+            Declaration::new("--tw-aspect-w", ratio),
+            Declaration::new("aspect-ratio", "var(--tw-aspect-w) / var(--tw-aspect-h)"),
+        ]),
+        "h" => Some(vec![Declaration::new("--tw-aspect-h", ratio)]),
+        _ => None,
+    }
+}
@@ -0,0 +1,309 @@
+use crate::value_map::get_color_value;
+use headwind_core::Declaration;
+use headwind_tw_parse::ParsedClass;
+
+use super::arbitrary::looks_like_color_value;
+use super::negative::negate_value;
+use super::standard::is_percentage;
+use super::Converter;
+
+/// 未设置的遮罩层按完全不透明处理，多层 `intersect` 后不影响结果
+const OPAQUE: &str = "linear-gradient(#fff, #fff)";
+
+/// 色标设置的是颜色还是位置
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Slot {
+    Color,
+    Position,
+}
+
+impl Converter {
+    /// v4.1 遮罩渐变的标准值（插件为 `mask`）
+    ///
+    /// - 边缘渐隐：`mask-t-from-50%`、`mask-x-to-8`、`mask-b-from-red-500`
+    /// - 线性 / 径向 / 锥形：`mask-linear-45`、`mask-radial-from-20%`、`mask-conic-to-75%`
+    /// - 径向形状、尺寸与位置：`mask-circle`、`mask-radial-closest-side`、`mask-radial-at-top-left`
+    pub(super) fn build_mask_gradient(&self, parsed: &ParsedClass, value: &str) -> Option<Vec<Declaration>> {
+        if matches!(value, "circle" | "ellipse") {
+            return Some(vec![Declaration::new("--tw-mask-radial-shape", value)]);
+        }
+        let (target, rest) = value.split_once('-')?;
+
+        if target == "radial" {
+            if let Some(position) = rest.strip_prefix("at-") {
+                return Some(vec![Declaration::new(
+                    "--tw-mask-radial-position",
+                    radial_position(position)?,
+                )]);
+            }
+            if matches!(rest, "closest-corner" | "closest-side" | "farthest-corner" | "farthest-side") {
+                return Some(vec![Declaration::new("--tw-mask-radial-size", rest)]);
+            }
+        }
+
+        // mask-linear-45 / mask-conic-90：渐变角度，可取负
+        if matches!(target, "linear" | "conic") && rest.parse::<f64>().is_ok() {
+            let angle = format!("{}deg", rest);
+            let angle = if parsed.negative { negate_value(&angle)? } else { angle };
+            return mask_angle(target, angle);
+        }
+
+        let (stop, amount) = rest.split_once('-')?;
+        let (slot, amount) = if is_percentage(amount) {
+            (Slot::Position, amount.to_string())
+        } else if !amount.is_empty() && amount.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+            (Slot::Position, self.spacing_multiple(amount))
+        } else {
+            (Slot::Color, get_color_value(amount, self.color_mode)?)
+        };
+        mask_stop(target, stop, slot, amount)
+    }
+}
+
+/// 遮罩渐变的任意值：`mask-t-from-[20px]`、`mask-radial-to-[#000]`、`mask-linear-[1rad]`
+pub(super) fn build_arbitrary_mask(parsed: &ParsedClass, raw_value: &str) -> Option<Vec<Declaration>> {
+    let name = parsed.plugin.strip_prefix("mask-")?;
+    if matches!(name, "linear" | "conic") {
+        return mask_angle(name, raw_value.to_string());
+    }
+    let (target, stop) = name.rsplit_once('-')?;
+    let slot = if looks_like_color_value(raw_value) { Slot::Color } else { Slot::Position };
+    mask_stop(target, stop, slot, raw_value.to_string())
+}
+
+/// 遮罩渐变的 CSS 变量：`mask-t-from-(--fade)` 设置位置，`mask-t-from-(color:--c)` 设置颜色
+pub(super) fn build_variable_mask(
+    parsed: &ParsedClass,
+    type_hint: Option<&str>,
+    var_expr: &str,
+) -> Option<Vec<Declaration>> {
+    let (target, stop) = parsed.plugin.strip_prefix("mask-")?.rsplit_once('-')?;
+    let slot = match type_hint {
+        None | Some("length" | "percentage") => Slot::Position,
+        Some("color") => Slot::Color,
+        Some(_) => return None,
+    };
+    mask_stop(target, stop, slot, var_expr.to_string())
+}
+
+/// 边缘缩写展开为方向：`t` → top，`x` → left + right
+fn edge_sides(edge: &str) -> Option<&'static [&'static str]> {
+    Some(match edge {
+        "t" => &["top"],
+        "r" => &["right"],
+        "b" => &["bottom"],
+        "l" => &["left"],
+        "x" => &["left", "right"],
+        "y" => &["top", "bottom"],
+        _ => return None,
+    })
+}
+
+/// `at-top-left` 之后的径向位置
+fn radial_position(position: &str) -> Option<&'static str> {
+    Some(match position {
+        "center" => "center",
+        "top" => "top",
+        "bottom" => "bottom",
+        "left" => "left",
+        "right" => "right",
+        "top-left" => "top left",
+        "top-right" => "top right",
+        "bottom-left" => "bottom left",
+        "bottom-right" => "bottom right",
+        _ => return None,
+    })
+}
+
+/// 一个遮罩渐变的色标：`from` / `to` 之间由颜色（默认 black → transparent）渐隐
+fn stops(name: &str) -> String {
+    format!(
+        "var(--tw-mask-{name}-from-color, black) var(--tw-mask-{name}-from-position, 0%), \
+         var(--tw-mask-{name}-to-color, transparent) var(--tw-mask-{name}-to-position, 100%)",
+    )
+}
+
+/// 所有遮罩类共用的图层声明：线性 / 径向 / 锥形三层取交集
+fn mask_layers() -> Vec<Declaration> {
+    vec![
+        Declaration::new(
+            "mask-image",
+            format!(
+                "var(--tw-mask-linear, {OPAQUE}), var(--tw-mask-radial, {OPAQUE}), var(--tw-mask-conic, {OPAQUE})"
+            ),
+        ),
+        Declaration::new("mask-composite", "intersect"),
+    ]
+}
+
+/// 线性 / 径向 / 锥形层的渐变
+fn gradient_layer(kind: &str) -> Option<Declaration> {
+    let gradient = match kind {
+        "linear" => format!(
+            "linear-gradient(var(--tw-mask-linear-position, 0deg), {})",
+            stops("linear")
+        ),
+        "radial" => format!(
+            "radial-gradient(var(--tw-mask-radial-shape, ellipse) var(--tw-mask-radial-size, farthest-corner) \
+             at var(--tw-mask-radial-position, center), {})",
+            stops("radial")
+        ),
+        "conic" => format!(
+            "conic-gradient(from var(--tw-mask-conic-position, 0deg), {})",
+            stops("conic")
+        ),
+        _ => return None,
+    };
+    Some(Declaration::new(format!("--tw-mask-{}", kind), gradient))
+}
+
+/// 线性 / 锥形遮罩的角度
+fn mask_angle(kind: &str, angle: String) -> Option<Vec<Declaration>> {
+    let mut declarations = mask_layers();
+    declarations.push(gradient_layer(kind)?);
+    declarations.push(Declaration::new(format!("--tw-mask-{}-position", kind), angle));
+    Some(declarations)
+}
+
+/// 设置一个色标的颜色或位置，同时声明它所在的渐变层
+///
+/// 边缘渐隐共用线性层：`--tw-mask-linear` 由四个方向的渐变叠加，未设置的方向不遮挡
+fn mask_stop(target: &str, stop: &str, slot: Slot, value: String) -> Option<Vec<Declaration>> {
+    if !matches!(stop, "from" | "to") {
+        return None;
+    }
+    let slot = match slot {
+        Slot::Color => "color",
+        Slot::Position => "position",
+    };
+
+    let mut declarations = mask_layers();
+    if let Some(sides) = edge_sides(target) {
+        declarations.push(Declaration::new(
+            "--tw-mask-linear",
+            ["left", "right", "bottom", "top"]
+                .iter()
+                .map(|side| format!("var(--tw-mask-{}, {})", side, OPAQUE))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ));
+        for side in sides {
+            declarations.push(Declaration::new(
+                format!("--tw-mask-{}", side),
+                format!("linear-gradient(to {}, {})", side, stops(side)),
+            ));
+            declarations.push(Declaration::new(
+                format!("--tw-mask-{}-{}-{}", side, stop, slot),
+                value.clone(),
+            ));
+        }
+    } else {
+        declarations.push(gradient_layer(target)?);
+        declarations.push(Declaration::new(
+            format!("--tw-mask-{}-{}-{}", target, stop, slot),
+            value,
+        ));
+    }
+    Some(declarations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use headwind_tw_parse::parse_class;
+
+    fn declarations(class: &str) -> Vec<(String, String)> {
+        Converter::new()
+            .to_declarations(&parse_class(class).unwrap())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|d| (d.property, d.value))
+            .collect()
+    }
+
+    fn value_of<'a>(decls: &'a [(String, String)], property: &str) -> Option<&'a str> {
+        decls
+            .iter()
+            .find(|(p, _)| p == property)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_edge_masks() {
+        let decls = declarations("mask-t-from-50%");
+        assert_eq!(
+            value_of(&decls, "mask-image"),
+            Some(
+                "var(--tw-mask-linear, linear-gradient(#fff, #fff)), \
+                 var(--tw-mask-radial, linear-gradient(#fff, #fff)), \
+                 var(--tw-mask-conic, linear-gradient(#fff, #fff))"
+            )
+        );
+        assert_eq!(value_of(&decls, "mask-composite"), Some("intersect"));
+        assert_eq!(
+            value_of(&decls, "--tw-mask-top"),
+            Some(
+                "linear-gradient(to top, var(--tw-mask-top-from-color, black) var(--tw-mask-top-from-position, 0%), \
+                 var(--tw-mask-top-to-color, transparent) var(--tw-mask-top-to-position, 100%))"
+            )
+        );
+        assert_eq!(value_of(&decls, "--tw-mask-top-from-position"), Some("50%"));
+
+        let decls = declarations("mask-x-to-8");
+        assert_eq!(
+            value_of(&decls, "--tw-mask-left-to-position"),
+            Some("calc(var(--spacing) * 8)")
+        );
+        assert_eq!(
+            value_of(&decls, "--tw-mask-right-to-position"),
+            Some("calc(var(--spacing) * 8)")
+        );
+        assert!(value_of(&decls, "--tw-mask-top").is_none());
+
+        let decls = declarations("mask-b-from-black");
+        assert!(value_of(&decls, "--tw-mask-bottom-from-color").is_some());
+
+        let decls = declarations("mask-l-from-[20px]");
+        assert_eq!(value_of(&decls, "--tw-mask-left-from-position"), Some("20px"));
+        let decls = declarations("mask-r-to-(--fade)");
+        assert_eq!(value_of(&decls, "--tw-mask-right-to-position"), Some("var(--fade)"));
+        let decls = declarations("mask-r-to-(color:--c)");
+        assert_eq!(value_of(&decls, "--tw-mask-right-to-color"), Some("var(--c)"));
+
+        assert!(declarations("mask-q-from-50%").is_empty());
+        assert!(declarations("mask-t-via-50%").is_empty());
+    }
+
+    #[test]
+    fn test_gradient_masks() {
+        let decls = declarations("mask-linear-45");
+        assert_eq!(value_of(&decls, "--tw-mask-linear-position"), Some("45deg"));
+        assert!(value_of(&decls, "--tw-mask-linear")
+            .is_some_and(|v| v.starts_with("linear-gradient(var(--tw-mask-linear-position, 0deg), ")));
+        let decls = declarations("-mask-conic-90");
+        assert_eq!(value_of(&decls, "--tw-mask-conic-position"), Some("-90deg"));
+        let decls = declarations("mask-linear-[1rad]");
+        assert_eq!(value_of(&decls, "--tw-mask-linear-position"), Some("1rad"));
+
+        let decls = declarations("mask-radial-from-20%");
+        assert_eq!(value_of(&decls, "--tw-mask-radial-from-position"), Some("20%"));
+        assert!(value_of(&decls, "--tw-mask-radial").is_some_and(|v| v.starts_with(
+            "radial-gradient(var(--tw-mask-radial-shape, ellipse) var(--tw-mask-radial-size, farthest-corner) \
+             at var(--tw-mask-radial-position, center), "
+        )));
+        let decls = declarations("mask-conic-to-[#000]");
+        assert_eq!(value_of(&decls, "--tw-mask-conic-to-color"), Some("#000"));
+
+        assert_eq!(declarations("mask-circle"), vec![("--tw-mask-radial-shape".to_string(), "circle".to_string())]);
+        assert_eq!(
+            declarations("mask-radial-closest-side"),
+            vec![("--tw-mask-radial-size".to_string(), "closest-side".to_string())]
+        );
+        assert_eq!(
+            declarations("mask-radial-at-top-left"),
+            vec![("--tw-mask-radial-position".to_string(), "top left".to_string())]
+        );
+        // 原有的遮罩关键字不受影响
+        assert_eq!(declarations("mask-repeat-x"), vec![("mask-repeat".to_string(), "repeat-x".to_string())]);
+    }
+}
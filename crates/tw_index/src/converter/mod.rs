@@ -1,17 +1,34 @@
-use headwind_core::ColorMode;
+use crate::cache::OptionsKey;
+use crate::index::TailwindIndex;
+use crate::units::convert_lengths;
+use crate::utility::CustomUtilities;
+use headwind_core::{
+    BreakpointStyle, ColorMode, CssVariableMode, CssVariableOverrides, TailwindVersion, UnitConversion,
+};
 use headwind_core::Declaration;
 use headwind_tw_parse::{ParsedClass, ParsedValue};
+use std::sync::Arc;
 
 mod arbitrary;
 mod color;
+mod compat;
+mod mask;
+mod negative;
 mod selector;
 mod standard;
 mod valueless;
 
-use arbitrary::{build_arbitrary_declarations, build_css_variable_declarations};
+use arbitrary::{
+    build_arbitrary_declarations, build_css_variable_declarations,
+    build_hinted_arbitrary_declarations,
+};
 use color::{apply_alpha_to_declarations, apply_important};
-use selector::build_selector;
+use compat::build_compat_declarations;
+use negative::allows_negative;
+use selector::{build_base_class, build_selector};
+use standard::space_between_declarations;
 use valueless::{build_valueless_declarations, build_valueless_from_full_name};
+pub(crate) use valueless::valueless_names;
 
 /// CSS 规则，包含选择器和声明
 #[derive(Debug, Clone, PartialEq)]
@@ -24,22 +41,46 @@ pub struct CssRule {
 
 /// 基于规则的 Tailwind 类转换器
 ///
-/// 基于 plugin_map 和 value_map 进行转换，不依赖外部索引
+/// 基于 plugin_map 和 value_map 进行转换；设置回退索引后，规则无法识别的类再到索引中查找
 pub struct Converter {
     /// true = 使用 var(--text-3xl)，false = 内联为 1.875rem
     pub(crate) use_variables: bool,
+    /// 按主题类别覆盖 `use_variables`
+    pub(crate) variable_overrides: CssVariableOverrides,
     /// 颜色输出模式（hex / oklch / hsl / var）
     pub(crate) color_mode: ColorMode,
     /// 是否使用 color-mix() 函数处理颜色透明度
     pub(crate) use_color_mix: bool,
+    /// true = 间距倍数直接算成 rem（1.5rem），false = calc(var(--spacing) * 6)
+    pub(crate) resolve_spacing: bool,
+    /// 断点 at-rule 的单位与写法
+    pub(crate) breakpoints: BreakpointStyle,
+    /// 回退索引：规则转换失败时按类名（不含修饰符）查找
+    pub(crate) fallback_index: Option<Arc<TailwindIndex>>,
+    /// 目标 Tailwind 版本，决定阴影、圆角、模糊等命名尺寸的取值
+    pub(crate) target_version: TailwindVersion,
+    /// 输出值的 px / rem 换算
+    pub(crate) unit_conversion: UnitConversion,
+    /// 换算使用的根字号（px）
+    pub(crate) root_font_size: u32,
+    /// `@utility` 定义的工具类，优先于内置规则
+    pub(crate) custom_utilities: Option<Arc<CustomUtilities>>,
 }
 
 impl Converter {
     pub fn new() -> Self {
         Self {
             use_variables: true,
+            variable_overrides: CssVariableOverrides::default(),
             color_mode: ColorMode::default(),
             use_color_mix: false,
+            resolve_spacing: false,
+            breakpoints: BreakpointStyle::default(),
+            fallback_index: None,
+            target_version: TailwindVersion::default(),
+            unit_conversion: UnitConversion::None,
+            root_font_size: 16,
+            custom_utilities: None,
         }
     }
 
@@ -47,11 +88,30 @@ impl Converter {
     pub fn with_inline() -> Self {
         Self {
             use_variables: false,
+            variable_overrides: CssVariableOverrides::default(),
             color_mode: ColorMode::default(),
             use_color_mix: false,
+            resolve_spacing: false,
+            breakpoints: BreakpointStyle::default(),
+            fallback_index: None,
+            target_version: TailwindVersion::default(),
+            unit_conversion: UnitConversion::None,
+            root_font_size: 16,
+            custom_utilities: None,
         }
     }
 
+    /// 按主题类别覆盖变量 / 内联（builder 模式），未设置的类别沿用创建时的模式
+    pub fn with_variable_overrides(mut self, overrides: CssVariableOverrides) -> Self {
+        self.variable_overrides = overrides;
+        self
+    }
+
+    /// 某一类主题值是否输出为 `var()` 引用
+    pub(crate) fn uses_variable(&self, category: Option<CssVariableMode>) -> bool {
+        category.map_or(self.use_variables, |mode| mode == CssVariableMode::Var)
+    }
+
     /// 设置颜色输出模式（builder 模式）
     pub fn with_color_mode(mut self, mode: ColorMode) -> Self {
         self.color_mode = mode;
@@ -64,39 +124,171 @@ impl Converter {
         self
     }
 
+    /// 设置是否在生成时把 `calc(var(--spacing) * n)` 解析为具体 rem 值（builder 模式）
+    pub fn with_resolve_spacing(mut self, enabled: bool) -> Self {
+        self.resolve_spacing = enabled;
+        self
+    }
+
+    /// 设置断点 at-rule 的单位（rem / px）与写法（范围 / 传统）（builder 模式）
+    pub fn with_breakpoints(mut self, style: BreakpointStyle) -> Self {
+        self.breakpoints = style;
+        self
+    }
+
+    /// 设置回退索引（builder 模式）
+    ///
+    /// 手写映射中没有的类到索引中查找，如由 `load_index` 加载的官方数据集。
+    /// 规则转换优先，索引只补充无法识别的类。
+    pub fn with_fallback_index(mut self, index: Arc<TailwindIndex>) -> Self {
+        self.fallback_index = Some(index);
+        self
+    }
+
+    /// 设置目标 Tailwind 版本（builder 模式）
+    ///
+    /// v3 下 `shadow-sm`、`rounded-sm`、`blur-sm` 等取 v3 的尺寸并输出具体值
+    pub fn with_target_version(mut self, version: TailwindVersion) -> Self {
+        self.target_version = version;
+        self
+    }
+
+    /// 设置输出值的 px / rem 换算与根字号（builder 模式，默认不换算、根字号 16px）
+    ///
+    /// 换算作用于所有生成的声明值，包括任意值（`p-[12px]`）
+    pub fn with_unit_conversion(mut self, conversion: UnitConversion, root_font_size: u32) -> Self {
+        self.unit_conversion = conversion;
+        self.root_font_size = root_font_size;
+        self
+    }
+
+    /// 设置 `@utility` 定义的工具类（builder 模式）
+    ///
+    /// 与内置工具类同名时以自定义定义为准，如 `@utility content-auto { content-visibility: auto; }`
+    pub fn with_custom_utilities(mut self, utilities: Arc<CustomUtilities>) -> Self {
+        self.custom_utilities = (!utilities.is_empty()).then_some(utilities);
+        self
+    }
+
+    /// 按设置换算值中的 px / rem 长度
+    pub(crate) fn convert_units(&self, value: &str) -> String {
+        convert_lengths(value, self.unit_conversion, self.root_font_size)
+    }
+
+    /// 影响转换结果的选项，作为转换缓存键的一部分
+    pub(crate) fn options_key(&self) -> OptionsKey {
+        OptionsKey {
+            use_variables: self.use_variables,
+            variable_overrides: self.variable_overrides,
+            color_mode: self.color_mode,
+            use_color_mix: self.use_color_mix,
+            resolve_spacing: self.resolve_spacing,
+            fallback_index: self.fallback_index.as_ref().map(|index| index.fingerprint()),
+            target_version: self.target_version,
+            unit_conversion: self.unit_conversion,
+            root_font_size: self.root_font_size,
+            custom_utilities: self.custom_utilities.as_ref().map(|u| u.fingerprint()),
+        }
+    }
+
     /// 将 Tailwind 类转换为 CSS 声明（仅声明，不含选择器）
     ///
     /// 适用于上下文模式，由调用者决定如何组织选择器。
     /// 复合插件（如 justify-items、gap-x）由解析器负责识别，
-    /// 此处仅处理声明构建和无值类回退。
+    /// 此处仅处理声明构建和无值类回退；规则无法识别时再查回退索引。
     pub fn to_declarations(&self, parsed: &ParsedClass) -> Option<Vec<Declaration>> {
-        let declarations = match &parsed.value {
-            Some(ParsedValue::Arbitrary(arb)) => {
-                build_arbitrary_declarations(parsed, &arb.content)?
+        let declarations = match self
+            .custom_declarations(parsed)
+            .or_else(|| self.rule_declarations(parsed))
+        {
+            Some(declarations) => declarations,
+            None => self.index_declarations(parsed)?,
+        };
+
+        let mut declarations = apply_important(declarations, parsed.important);
+        if self.unit_conversion != UnitConversion::None {
+            for declaration in &mut declarations {
+                declaration.value = self.convert_units(&declaration.value);
             }
+        }
+        Some(declarations)
+    }
+
+    /// 基于规则构建声明（含 alpha 透明度）
+    fn rule_declarations(&self, parsed: &ParsedClass) -> Option<Vec<Declaration>> {
+        // 负值只对可取负的插件有意义，各构建函数再用 `negate_value` 对值取负
+        if parsed.negative && !allows_negative(parsed) {
+            return None;
+        }
+        if let Some(declarations) = self.build_fraction_declarations(parsed) {
+            return Some(declarations);
+        }
+
+        let declarations = match &parsed.value {
+            Some(ParsedValue::Arbitrary(arb)) => match arb.split_type_hint() {
+                (Some(hint), value) => build_hinted_arbitrary_declarations(parsed, hint, value)?,
+                (None, value) => build_arbitrary_declarations(parsed, value)?,
+            },
             Some(ParsedValue::CssVariable(cv)) => {
                 build_css_variable_declarations(parsed, cv)?
             }
             Some(ParsedValue::Standard(value)) => self
                 .build_standard_declarations(parsed, value)
-                .or_else(|| build_valueless_from_full_name(parsed, value))?,
-            None => build_valueless_declarations(parsed)?,
+                .or_else(|| build_valueless_from_full_name(parsed, value))
+                .or_else(|| build_compat_declarations(parsed, value))?,
+            None => self
+                .build_default_size(parsed)
+                .or_else(|| build_valueless_declarations(parsed))?,
+        };
+
+        let declarations = match parsed.plugin.as_ref() {
+            "space-x" | "space-y" => space_between_declarations(declarations),
+            _ => declarations,
         };
 
         // 为颜色属性应用 alpha 透明度（如 text-white/60 → color: #fff9）
-        let declarations = if let Some(ref alpha) = parsed.alpha {
+        Some(if let Some(ref alpha) = parsed.alpha {
             apply_alpha_to_declarations(declarations, alpha, self.use_color_mix)
         } else {
             declarations
-        };
+        })
+    }
+
+    /// 在 `@utility` 定义的工具类中查找声明，透明度（`tab-4/50`）作为 `--modifier(…)` 的值
+    fn custom_declarations(&self, parsed: &ParsedClass) -> Option<Vec<Declaration>> {
+        let utilities = self.custom_utilities.as_ref()?;
+        if parsed.negative {
+            return None;
+        }
+        let class = build_base_class(&ParsedClass {
+            alpha: None,
+            ..parsed.clone()
+        });
+        utilities.lookup(&class, parsed.alpha.as_deref())
+    }
 
-        Some(apply_important(declarations, parsed.important))
+    /// 在回退索引中查找声明
+    ///
+    /// 带透明度的类先按完整类名查找（如 `bg-brand/50`），
+    /// 找不到时查不带透明度的类名，再对结果应用透明度。
+    fn index_declarations(&self, parsed: &ParsedClass) -> Option<Vec<Declaration>> {
+        let index = self.fallback_index.as_ref()?;
+        if let Some(declarations) = index.lookup(&build_base_class(parsed)) {
+            return Some(declarations.to_vec());
+        }
+        let alpha = parsed.alpha.as_ref()?;
+        let opaque = ParsedClass {
+            alpha: None,
+            ..parsed.clone()
+        };
+        let declarations = index.lookup(&build_base_class(&opaque))?.to_vec();
+        Some(apply_alpha_to_declarations(declarations, alpha, self.use_color_mix))
     }
 
     /// 将 Tailwind 类名转换为 CSS 规则（声明 + 选择器）
     pub fn convert(&self, parsed: &ParsedClass) -> Option<CssRule> {
         let declarations = self.to_declarations(parsed)?;
-        let selector = build_selector(parsed);
+        let selector = build_selector(parsed, self.breakpoints);
         Some(CssRule { selector, declarations })
     }
 }
@@ -111,6 +303,7 @@ impl Default for Converter {
 mod tests {
     use super::*;
     use headwind_tw_parse::parse_class;
+    use headwind_core::{BreakpointUnit, MediaQuerySyntax};
 
     #[test]
     fn test_convert_standard_value() {
@@ -157,10 +350,45 @@ mod tests {
         let rule = converter.convert(&parsed).unwrap();
 
         assert!(rule.selector.contains("@media"));
-        assert!(rule.selector.contains("768px"));
+        assert!(rule.selector.contains("48rem"));
         assert_eq!(rule.declarations.len(), 1);
     }
 
+    #[test]
+    fn test_convert_with_legacy_px_breakpoints() {
+        let converter = Converter::new().with_breakpoints(BreakpointStyle {
+            unit: BreakpointUnit::Px,
+            syntax: MediaQuerySyntax::Legacy,
+        });
+
+        let parsed = parse_class("md:text-center").unwrap();
+        let rule = converter.convert(&parsed).unwrap();
+
+        assert_eq!(rule.selector, "@media (min-width: 768px) { .text-center }");
+    }
+
+    #[test]
+    fn test_convert_with_fallback_index() {
+        let mut index = TailwindIndex::new();
+        index.insert("bg-brand".into(), vec![Declaration::new("background-color", "#ff5500")]);
+        index.insert("p-4".into(), vec![Declaration::new("padding", "99px")]);
+        let converter = Converter::new().with_fallback_index(Arc::new(index));
+
+        let rule = converter.convert(&parse_class("hover:bg-brand!").unwrap()).unwrap();
+        assert_eq!(rule.selector, ".bg-brand:hover");
+        assert_eq!(rule.declarations[0].value, "#ff5500 !important");
+
+        // 透明度应用到索引结果上
+        let decls = converter.to_declarations(&parse_class("bg-brand/50").unwrap()).unwrap();
+        assert_ne!(decls[0].value, "#ff5500");
+
+        // 规则转换优先
+        let decls = converter.to_declarations(&parse_class("p-4").unwrap()).unwrap();
+        assert_eq!(decls[0].value, "1rem");
+
+        assert!(Converter::new().to_declarations(&parse_class("bg-brand").unwrap()).is_none());
+    }
+
     #[test]
     fn test_convert_with_important() {
         let converter = Converter::new();
@@ -183,6 +411,48 @@ mod tests {
         assert!(rule.selector.contains(":hover"));
     }
 
+    #[test]
+    fn test_convert_pseudo_element_with_variants() {
+        let converter = Converter::new();
+        let selector = |class: &str| converter.convert(&parse_class(class).unwrap()).unwrap().selector;
+
+        // 伪元素总在选择器末尾
+        assert_eq!(selector("hover:before:p-4"), ".p-4:hover::before");
+        assert_eq!(selector("before:hover:p-4"), ".p-4:hover::before");
+        assert_eq!(
+            selector("before:aria-disabled:p-4"),
+            ".p-4[aria-disabled=\"true\"]::before"
+        );
+        assert_eq!(selector("md:after:p-4"), "@media (width >= 48rem) { .p-4::after }");
+        assert_eq!(selector("dark:placeholder:p-4"), ".dark .p-4::placeholder");
+    }
+
+    #[test]
+    fn test_convert_three_level_stack() {
+        let converter = Converter::new();
+
+        let parsed = parse_class("dark:md:hover:underline").unwrap();
+        let rule = converter.convert(&parsed).unwrap();
+
+        assert_eq!(
+            rule.selector,
+            "@media (width >= 48rem) { .dark .underline:hover }"
+        );
+    }
+
+    #[test]
+    fn test_convert_four_level_stack() {
+        let converter = Converter::new();
+
+        let parsed = parse_class("motion-safe:lg:group-hover:focus-visible:p-4").unwrap();
+        let rule = converter.convert(&parsed).unwrap();
+
+        assert_eq!(
+            rule.selector,
+            "@media (prefers-reduced-motion: no-preference) { @media (width >= 64rem) { .group:hover .p-4:focus-visible } }"
+        );
+    }
+
     #[test]
     fn test_convert_arbitrary_value() {
         let converter = Converter::new();
@@ -196,6 +466,15 @@ mod tests {
         assert_eq!(rule.declarations[0].value, "13px");
     }
 
+    #[test]
+    fn test_convert_empty_arbitrary_and_variable_values() {
+        let converter = Converter::new();
+        for class in ["w-[]", "w-[_]", "p-()", "p-(:)", "bg-(:--x)"] {
+            let parsed = parse_class(class).unwrap();
+            assert!(converter.convert(&parsed).is_none(), "{}", class);
+        }
+    }
+
     #[test]
     fn test_convert_arbitrary_value_with_modifier() {
         let converter = Converter::new();
@@ -263,6 +542,286 @@ mod tests {
         assert_eq!(rule.declarations[0].value, "-1rem");
     }
 
+    #[test]
+    fn test_negative_values() {
+        let converter = Converter::new();
+        let declarations = |class: &str| {
+            converter
+                .to_declarations(&parse_class(class).unwrap())
+                .map(|decls| {
+                    decls
+                        .iter()
+                        .map(|d| format!("{}: {}", d.property, d.value))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+        };
+        let cases = [
+            ("-m-4", "margin: -1rem"),
+            ("-mx-2", "margin-left: -0.5rem; margin-right: -0.5rem"),
+            ("-mt-px", "margin-top: -1px"),
+            ("-m-0", "margin: 0"),
+            ("-m-[10px]", "margin: -10px"),
+            ("-m-[calc(1rem+2px)]", "margin: calc(calc(1rem+2px) * -1)"),
+            ("-m-[var(--gap)]", "margin: calc(var(--gap) * -1)"),
+            ("-m-(--gap)", "margin: calc(var(--gap) * -1)"),
+            ("-inset-2", "inset: -0.5rem"),
+            ("-inset-x-1/2", "left: -50%; right: -50%"),
+            ("-top-1/2", "top: -50%"),
+            ("-start-4", "inset-inline-start: -1rem"),
+            ("-inset-full", "inset: -100%"),
+            ("-translate-x-1/2", "translate: -50% var(--tw-translate-y)"),
+            ("-translate-y-4", "translate: var(--tw-translate-x) -1rem"),
+            ("-translate-x-px", "translate: -1px var(--tw-translate-y)"),
+            ("-translate-[10px]", "translate: -10px"),
+            ("-scroll-mt-2", "scroll-margin-top: -0.5rem"),
+            ("-tracking-tight", "letter-spacing: calc(var(--tracking-tight) * -1)"),
+            ("-tracking-[0.1em]", "letter-spacing: -0.1em"),
+            ("-indent-4", "text-indent: -1rem"),
+            ("-order-1", "order: -1"),
+            ("-z-10", "z-index: -10"),
+            ("-rotate-45", "rotate: -45deg"),
+            ("-rotate-[7deg]", "rotate: -7deg"),
+            (
+                "-space-x-2",
+                "--tw-space-x-reverse: 0; \
+                 margin-inline-start: calc(-0.5rem * var(--tw-space-x-reverse)); \
+                 margin-inline-end: calc(-0.5rem * calc(1 - var(--tw-space-x-reverse)))",
+            ),
+            (
+                "-bg-linear-45",
+                "background-image: linear-gradient(-45deg in oklab, var(--tw-gradient-stops))",
+            ),
+        ];
+        for (class, expected) in cases {
+            assert_eq!(declarations(class).as_deref(), Some(expected), "{}", class);
+        }
+
+        // 不可取负的插件与没有负值的关键字
+        for class in [
+            "-p-4", "-w-4", "-gap-2", "-opacity-50", "-border-2", "-rounded-md",
+            "-bg-red-500", "-text-lg", "-text-[14px]", "-bg-linear-to-r", "-m-auto", "-top-auto",
+            "-z-auto", "-m-[1px_2px]",
+        ] {
+            assert_eq!(declarations(class), None, "{}", class);
+        }
+    }
+
+    #[test]
+    fn test_typography_utilities() {
+        let declarations = |converter: &Converter, class: &str| {
+            converter
+                .to_declarations(&parse_class(class).unwrap())
+                .map(|decls| {
+                    decls
+                        .iter()
+                        .map(|d| format!("{}: {}", d.property, d.value))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+        };
+        let converter = Converter::new();
+        let cases = [
+            ("tracking-tighter", "letter-spacing: var(--tracking-tighter)"),
+            ("tracking-widest", "letter-spacing: var(--tracking-widest)"),
+            ("tracking-[0.2em]", "letter-spacing: 0.2em"),
+            ("tracking-(--spacing-title)", "letter-spacing: var(--spacing-title)"),
+            ("indent-4", "text-indent: 1rem"),
+            ("indent-px", "text-indent: 1px"),
+            ("indent-[10%]", "text-indent: 10%"),
+            ("hyphens-auto", "hyphens: auto"),
+            ("hyphens-manual", "hyphens: manual"),
+            ("hyphens-none", "hyphens: none"),
+            ("whitespace-nowrap", "white-space: nowrap"),
+            ("whitespace-pre-wrap", "white-space: pre-wrap"),
+            ("whitespace-break-spaces", "white-space: break-spaces"),
+            ("text-ellipsis", "text-overflow: ellipsis"),
+            ("text-clip", "text-overflow: clip"),
+            ("truncate", "overflow: hidden; text-overflow: ellipsis; white-space: nowrap"),
+        ];
+        for (class, expected) in cases {
+            assert_eq!(declarations(&converter, class).as_deref(), Some(expected), "{}", class);
+        }
+        // 未知的字间距名称没有对应的主题变量
+        assert_eq!(declarations(&converter, "tracking-2"), None);
+        assert_eq!(declarations(&converter, "tracking-loose"), None);
+
+        // 内联模式与 v3 输出具体值
+        let inline = Converter::with_inline();
+        assert_eq!(
+            declarations(&inline, "tracking-tight").as_deref(),
+            Some("letter-spacing: -0.025em")
+        );
+        assert_eq!(
+            declarations(&inline, "-tracking-tight").as_deref(),
+            Some("letter-spacing: 0.025em")
+        );
+        let v3 = Converter::new().with_target_version(TailwindVersion::V3);
+        assert_eq!(
+            declarations(&v3, "tracking-wider").as_deref(),
+            Some("letter-spacing: 0.05em")
+        );
+    }
+
+    #[test]
+    fn test_content_utilities() {
+        let converter = Converter::new();
+        let declarations = |class: &str| {
+            converter
+                .to_declarations(&parse_class(class).unwrap())
+                .map(|decls| {
+                    decls
+                        .iter()
+                        .map(|d| format!("{}: {}", d.property, d.value))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+        };
+        let content = |value: &str| format!("--tw-content: {}; content: var(--tw-content)", value);
+        assert_eq!(
+            declarations("content-none").as_deref(),
+            Some("--tw-content: none; content: none")
+        );
+        let cases = [
+            ("content-['hello_world']", "'hello world'"),
+            ("content-['hello\\_world']", "'hello_world'"),
+            ("content-[attr(data-label)]", "attr(data-label)"),
+            ("content-[counter(item)_'._']", "counter(item) '. '"),
+            ("content-['→']", "'→'"),
+            // 裸文本包装为字符串，未闭合的字符串补全引号
+            ("content-[→]", "\"→\""),
+            ("content-['open]", "'open'"),
+            ("content-[open-quote]", "open-quote"),
+            ("content-(--label)", "var(--label)"),
+        ];
+        for (class, value) in cases {
+            assert_eq!(declarations(class), Some(content(value)), "{}", class);
+        }
+        // align-content 不受影响
+        assert_eq!(
+            declarations("content-center").as_deref(),
+            Some("align-content: center")
+        );
+    }
+
+    #[test]
+    fn test_form_control_utilities() {
+        let converter = Converter::new();
+        let cases = [
+            ("field-sizing-content", "field-sizing", "content"),
+            ("field-sizing-fixed", "field-sizing", "fixed"),
+            ("scheme-normal", "color-scheme", "normal"),
+            ("scheme-dark", "color-scheme", "dark"),
+            ("scheme-light-dark", "color-scheme", "light dark"),
+            ("scheme-only-light", "color-scheme", "only light"),
+            ("caret-transparent", "caret-color", "transparent"),
+            ("caret-current", "caret-color", "currentColor"),
+            ("appearance-none", "appearance", "none"),
+            ("appearance-auto", "appearance", "auto"),
+        ];
+        for (class, property, value) in cases {
+            let decls = converter.to_declarations(&parse_class(class).unwrap()).unwrap();
+            assert_eq!(decls, vec![Declaration::new(property, value)], "{}", class);
+        }
+        for class in ["field-sizing-auto", "scheme-dim", "appearance-button"] {
+            assert!(converter.to_declarations(&parse_class(class).unwrap()).is_none(), "{}", class);
+        }
+    }
+
+    #[test]
+    fn test_filter_utilities() {
+        let converter = Converter::new();
+        let cases = [
+            ("brightness-50", "filter", "brightness(50%)"),
+            ("contrast-125", "filter", "contrast(125%)"),
+            ("saturate-150", "filter", "saturate(150%)"),
+            ("grayscale-0", "filter", "grayscale(0%)"),
+            ("invert-25", "filter", "invert(25%)"),
+            ("sepia-75", "filter", "sepia(75%)"),
+            ("hue-rotate-15", "filter", "hue-rotate(15deg)"),
+            ("-hue-rotate-30", "filter", "hue-rotate(-30deg)"),
+            ("brightness-[1.75]", "filter", "brightness(1.75)"),
+            ("hue-rotate-[0.5turn]", "filter", "hue-rotate(0.5turn)"),
+            ("blur-[2px]", "filter", "blur(2px)"),
+            ("contrast-(--amount)", "filter", "contrast(var(--amount))"),
+            ("backdrop-brightness-75", "backdrop-filter", "brightness(75%)"),
+            ("backdrop-opacity-40", "backdrop-filter", "opacity(40%)"),
+            ("backdrop-hue-rotate-90", "backdrop-filter", "hue-rotate(90deg)"),
+            ("-backdrop-hue-rotate-90", "backdrop-filter", "hue-rotate(-90deg)"),
+            ("backdrop-saturate-[.8]", "backdrop-filter", "saturate(.8)"),
+        ];
+        for (class, property, value) in cases {
+            let decls = converter.to_declarations(&parse_class(class).unwrap());
+            let decls = decls.unwrap_or_else(|| panic!("{} should convert", class));
+            assert_eq!(decls.len(), 1, "{}", class);
+            assert_eq!(decls[0].property, property, "{}", class);
+            assert_eq!(decls[0].value, value, "{}", class);
+        }
+
+        // 只接受整数；百分比滤镜不能取负
+        for class in ["brightness-half", "-brightness-50", "hue-rotate-1.5"] {
+            assert!(converter.to_declarations(&parse_class(class).unwrap()).is_none(), "{}", class);
+        }
+    }
+
+    #[test]
+    fn test_scale_utilities() {
+        let converter = Converter::new();
+        let decls = |class: &str| {
+            converter
+                .to_declarations(&parse_class(class).unwrap())
+                .map(|decls| {
+                    decls
+                        .into_iter()
+                        .map(|d| format!("{}: {}", d.property, d.value))
+                        .collect::<Vec<_>>()
+                })
+        };
+        let scale = "scale: var(--tw-scale-x) var(--tw-scale-y)";
+        assert_eq!(
+            decls("scale-110").unwrap(),
+            ["--tw-scale-x: 110%", "--tw-scale-y: 110%", "--tw-scale-z: 110%", scale]
+        );
+        assert_eq!(decls("scale-x-50").unwrap(), ["--tw-scale-x: 50%", scale]);
+        assert_eq!(decls("-scale-y-75").unwrap(), ["--tw-scale-y: -75%", scale]);
+        assert_eq!(decls("scale-0").unwrap()[0], "--tw-scale-x: 0%");
+        assert_eq!(decls("scale-none").unwrap(), ["scale: none"]);
+        for class in ["scale-1.5", "scale-x-none", "scale-big"] {
+            assert!(decls(class).is_none(), "{}", class);
+        }
+    }
+
+    #[test]
+    fn test_skew_utilities() {
+        let converter = Converter::new();
+        let decls = |class: &str| {
+            converter
+                .to_declarations(&parse_class(class).unwrap())
+                .map(|decls| {
+                    decls
+                        .into_iter()
+                        .map(|d| format!("{}: {}", d.property, d.value))
+                        .collect::<Vec<_>>()
+                })
+        };
+        let transform = "transform: var(--tw-rotate-x,) var(--tw-rotate-y,) var(--tw-rotate-z,) \
+                         var(--tw-skew-x,) var(--tw-skew-y,)";
+        assert_eq!(decls("skew-x-6").unwrap(), ["--tw-skew-x: skewX(6deg)", transform]);
+        assert_eq!(decls("-skew-x-6").unwrap(), ["--tw-skew-x: skewX(-6deg)", transform]);
+        assert_eq!(
+            decls("skew-3").unwrap(),
+            ["--tw-skew-x: skewX(3deg)", "--tw-skew-y: skewY(3deg)", transform]
+        );
+        assert_eq!(decls("-skew-y-[0.5rad]").unwrap(), ["--tw-skew-y: skewY(-0.5rad)", transform]);
+        assert_eq!(
+            decls("skew-x-(--tilt)").unwrap(),
+            ["--tw-skew-x: skewX(var(--tilt))", transform]
+        );
+        for class in ["skew-x-1.5", "skew-big"] {
+            assert!(decls(class).is_none(), "{}", class);
+        }
+    }
+
     #[test]
     fn test_convert_valueless_fallback() {
         // overflow-auto: parser gives plugin="overflow", value="auto"
@@ -448,6 +1007,49 @@ mod tests {
         assert_eq!(decls[0].value, "#ff0000");
     }
 
+    #[test]
+    fn test_gradient_stop_positions() {
+        let converter = Converter::new();
+        let declaration = |class: &str| {
+            let decls = converter.to_declarations(&parse_class(class).unwrap()).unwrap();
+            assert_eq!(decls.len(), 1, "{}", class);
+            (decls[0].property.clone(), decls[0].value.clone())
+        };
+        let pair = |p: &str, v: &str| (p.to_string(), v.to_string());
+
+        assert_eq!(declaration("from-10%"), pair("--tw-gradient-from-position", "10%"));
+        assert_eq!(declaration("via-50%"), pair("--tw-gradient-via-position", "50%"));
+        assert_eq!(declaration("to-[90%]"), pair("--tw-gradient-to-position", "90%"));
+        assert_eq!(declaration("from-[2rem]"), pair("--tw-gradient-from-position", "2rem"));
+        assert_eq!(declaration("from-(length:--p)"), pair("--tw-gradient-from-position", "var(--p)"));
+        // 颜色仍写入色标变量
+        assert_eq!(declaration("to-[color:var(--c)]"), pair("--tw-gradient-to", "var(--c)"));
+        assert_eq!(declaration("from-[#f00]"), pair("--tw-gradient-from", "#f00"));
+    }
+
+    #[test]
+    fn test_gradient_interpolation_modifiers() {
+        let converter = Converter::new();
+        let image = |class: &str| single_value(&converter, class);
+
+        assert_eq!(
+            image("bg-linear-to-r/oklch"),
+            "linear-gradient(to right in oklch, var(--tw-gradient-stops))"
+        );
+        assert_eq!(
+            image("bg-linear-45/srgb"),
+            "linear-gradient(45deg in srgb, var(--tw-gradient-stops))"
+        );
+        assert_eq!(image("bg-radial/hsl"), "radial-gradient(in hsl, var(--tw-gradient-stops))");
+        assert_eq!(
+            image("bg-conic-90/longer"),
+            "conic-gradient(from 90deg in oklch longer hue, var(--tw-gradient-stops))"
+        );
+        // 无修饰符时保持原有输出
+        assert_eq!(image("bg-linear-to-r"), "linear-gradient(to right, var(--tw-gradient-stops))");
+        assert!(converter.to_declarations(&parse_class("bg-linear-to-r/lab").unwrap()).is_none());
+    }
+
     #[test]
     fn test_via_color() {
         let converter = Converter::new();
@@ -1056,6 +1658,61 @@ mod tests {
         assert_eq!(decls[0].value, "inset 0 0 0 var(--ring-width)");
     }
 
+    // --- outline-offset / underline-offset / ring-offset ---
+
+    #[test]
+    fn test_offset_utilities() {
+        let converter = Converter::new();
+        let declarations = |class: &str| {
+            converter
+                .to_declarations(&parse_class(class).unwrap())
+                .map(|decls| {
+                    decls
+                        .iter()
+                        .map(|d| format!("{}: {}", d.property, d.value))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+        };
+        let ring_offset_shadow =
+            "--tw-ring-offset-shadow: 0 0 0 var(--tw-ring-offset-width) var(--tw-ring-offset-color, #fff)";
+        let cases = [
+            ("outline-offset-2", "outline-offset: 2px".to_string()),
+            ("outline-offset-0", "outline-offset: 0px".to_string()),
+            ("-outline-offset-2", "outline-offset: -2px".to_string()),
+            ("outline-offset-[3px]", "outline-offset: 3px".to_string()),
+            ("outline-offset-(--offset)", "outline-offset: var(--offset)".to_string()),
+            ("underline-offset-4", "text-underline-offset: 4px".to_string()),
+            ("underline-offset-auto", "text-underline-offset: auto".to_string()),
+            ("-underline-offset-1", "text-underline-offset: -1px".to_string()),
+            ("underline-offset-[0.3em]", "text-underline-offset: 0.3em".to_string()),
+            ("underline-offset-(--offset)", "text-underline-offset: var(--offset)".to_string()),
+            (
+                "ring-offset-2",
+                format!("--tw-ring-offset-width: 2px; {}", ring_offset_shadow),
+            ),
+            (
+                "ring-offset-[3px]",
+                format!("--tw-ring-offset-width: 3px; {}", ring_offset_shadow),
+            ),
+            (
+                "ring-offset-(length:--width)",
+                format!("--tw-ring-offset-width: var(--width); {}", ring_offset_shadow),
+            ),
+            ("ring-offset-white", "--tw-ring-offset-color: #ffffff".to_string()),
+            ("ring-offset-white/50", "--tw-ring-offset-color: #ffffff80".to_string()),
+            ("ring-offset-[#123456]", "--tw-ring-offset-color: #123456".to_string()),
+            ("ring-offset-(--color)", "--tw-ring-offset-color: var(--color)".to_string()),
+        ];
+        for (class, expected) in cases {
+            assert_eq!(declarations(class).as_deref(), Some(expected.as_str()), "{}", class);
+        }
+
+        for class in ["outline-offset-foo", "underline-offset-wide", "-ring-offset-2"] {
+            assert_eq!(declarations(class), None, "{}", class);
+        }
+    }
+
     // ── shadow named sizes ───────────────────────────────────────
 
     #[test]
@@ -1098,6 +1755,261 @@ mod tests {
         assert_eq!(decls[0].value, "0 0 #0000");
     }
 
+    // ── Tailwind 版本差异 ────────────────────────────────────────
+
+    fn single_value(converter: &Converter, class: &str) -> String {
+        let parsed = parse_class(class).unwrap();
+        converter.to_declarations(&parsed).unwrap()[0].value.clone()
+    }
+
+    #[test]
+    fn test_v4_renamed_scales() {
+        let v4 = Converter::new();
+        assert_eq!(single_value(&v4, "shadow"), "var(--shadow-sm)");
+        assert_eq!(single_value(&v4, "rounded"), "0.25rem");
+        assert_eq!(single_value(&v4, "rounded-xs"), "0.125rem");
+        assert_eq!(single_value(&v4, "rounded-sm"), "0.25rem");
+        assert_eq!(single_value(&v4, "blur"), "blur(var(--blur-sm))");
+
+        let inline = Converter::with_inline();
+        assert_eq!(single_value(&inline, "blur-xs"), "blur(4px)");
+        assert_eq!(single_value(&inline, "blur-sm"), "blur(8px)");
+    }
+
+    #[test]
+    fn test_variable_overrides() {
+        let var = Some(CssVariableMode::Var);
+        let inline = Some(CssVariableMode::Inline);
+        // 全局内联，只保留阴影变量
+        let shadows_only = Converter::with_inline().with_variable_overrides(CssVariableOverrides {
+            shadow: var,
+            ..Default::default()
+        });
+        assert_eq!(single_value(&shadows_only, "shadow-md"), "var(--shadow-md)");
+        assert_eq!(single_value(&shadows_only, "inset-shadow-xs"), "var(--inset-shadow-xs)");
+        assert_eq!(single_value(&shadows_only, "text-lg"), "1.125rem");
+        assert_eq!(single_value(&shadows_only, "blur-sm"), "blur(8px)");
+
+        // 全局变量，字号与字体族内联
+        let sizes_inline = Converter::new().with_variable_overrides(CssVariableOverrides {
+            font_size: inline,
+            font_family: inline,
+            ..Default::default()
+        });
+        let text = sizes_inline.to_declarations(&parse_class("text-lg").unwrap()).unwrap();
+        assert_eq!((text[0].value.as_str(), text[1].value.as_str()), ("1.125rem", "calc(1.75 / 1.125)"));
+        assert!(!single_value(&sizes_inline, "font-mono").contains("var("));
+        assert_eq!(single_value(&sizes_inline, "shadow"), "var(--shadow-sm)");
+        assert_eq!(single_value(&sizes_inline, "aspect-video"), "var(--aspect-video)");
+
+        // 内联模式下阴影也输出具体值
+        let all_inline = Converter::with_inline();
+        assert_eq!(single_value(&all_inline, "shadow-2xs"), "0 1px rgb(0 0 0 / 0.05)");
+        assert_eq!(single_value(&all_inline, "inset-shadow-sm"), "inset 0 2px 4px rgb(0 0 0 / 0.05)");
+        assert!(all_inline.to_declarations(&parse_class("shadow-huge").unwrap()).is_none());
+        assert!(CssVariableOverrides { blur: var, ..Default::default() }.any_var());
+        assert!(!CssVariableOverrides { blur: inline, ..Default::default() }.any_var());
+    }
+
+    #[test]
+    fn test_text_shadow() {
+        let v4 = Converter::new();
+        assert_eq!(single_value(&v4, "text-shadow-md"), "var(--text-shadow-md)");
+        assert_eq!(single_value(&v4, "text-shadow-none"), "none");
+        assert_eq!(single_value(&v4, "text-shadow-[0_2px_4px_rgb(0_0_0/0.3)]"), "0 2px 4px rgb(0 0 0/0.3)");
+
+        let inline = Converter::with_inline();
+        assert_eq!(single_value(&inline, "text-shadow-2xs"), "0px 1px 0px rgb(0 0 0 / 0.15)");
+        assert!(inline.to_declarations(&parse_class("text-shadow-huge").unwrap()).is_none());
+
+        let color = |class: &str| {
+            let decls = v4.to_declarations(&parse_class(class).unwrap()).unwrap();
+            assert_eq!(decls[0].property, "--tw-text-shadow-color", "{}", class);
+            decls[0].value.clone()
+        };
+        assert_ne!(color("text-shadow-red-500/50"), color("text-shadow-red-500"));
+        assert_eq!(color("text-shadow-[#0ff]"), "#0ff");
+        assert_eq!(color("text-shadow-(--glow)"), "var(--glow)");
+    }
+
+    #[test]
+    fn test_v3_target_version() {
+        let v3 = Converter::new().with_target_version(TailwindVersion::V3);
+        assert_eq!(single_value(&v3, "shadow-sm"), "0 1px 2px 0 rgb(0 0 0 / 0.05)");
+        assert_eq!(
+            single_value(&v3, "shadow"),
+            "0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1)"
+        );
+        assert_eq!(single_value(&v3, "shadow-inner"), "inset 0 2px 4px 0 rgb(0 0 0 / 0.05)");
+        assert_eq!(single_value(&v3, "rounded-sm"), "0.125rem");
+        assert_eq!(single_value(&v3, "rounded"), "0.25rem");
+        assert_eq!(single_value(&v3, "blur-sm"), "blur(4px)");
+        assert_eq!(single_value(&v3, "blur"), "blur(8px)");
+        // v3 没有 xs / 2xs 尺寸，shadow-xs 不再是阴影
+        assert!(v3.to_declarations(&parse_class("rounded-xs").unwrap()).is_none());
+        assert!(v3.to_declarations(&parse_class("shadow-2xs").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_position_fractions_and_logical_insets() {
+        let converter = Converter::new();
+        let value = |class: &str| single_value(&converter, class);
+        let property = |class: &str| {
+            converter.to_declarations(&parse_class(class).unwrap()).unwrap()[0]
+                .property
+                .clone()
+        };
+
+        assert_eq!(value("top-1/2"), "50%");
+        assert_eq!(value("-top-1/2"), "-50%");
+        assert_eq!(value("-left-1/4"), "-25%");
+        assert_eq!(value("inset-x-1/3"), "33.333333%");
+        assert_eq!(value("w-2/3"), "66.666667%");
+        assert_eq!(value("aspect-4/3"), "4/3");
+        assert_eq!(value("-translate-y-1/2"), "var(--tw-translate-x) -50%");
+
+        assert_eq!(property("start-0"), "inset-inline-start");
+        assert_eq!(value("start-0"), "0");
+        assert_eq!(property("end-4"), "inset-inline-end");
+        assert_eq!(value("-end-4"), "-1rem");
+        assert_eq!(value("start-1/2"), "50%");
+        assert_eq!(value("end-[3px]"), "3px");
+        assert_eq!(value("start-(--gutter)"), "var(--gutter)");
+
+        // 颜色透明度与行高不受分数处理影响
+        assert_eq!(value("text-white/50"), "#ffffff80");
+        assert_eq!(value("text-base/6"), "var(--text-base)");
+    }
+
+    #[test]
+    fn test_z_index_order_and_flex() {
+        let converter = Converter::new();
+        let value = |class: &str| single_value(&converter, class);
+        let unsupported =
+            |class: &str| converter.to_declarations(&parse_class(class).unwrap()).is_none();
+
+        assert_eq!(value("z-10"), "10");
+        assert_eq!(value("-z-10"), "-10");
+        assert_eq!(value("z-[60]"), "60");
+        assert_eq!(value("z-auto"), "auto");
+        assert!(unsupported("z-top"));
+
+        assert_eq!(value("order-2"), "2");
+        assert_eq!(value("-order-1"), "-1");
+        assert_eq!(value("order-first"), "-9999");
+        assert_eq!(value("order-last"), "9999");
+        assert_eq!(value("order-none"), "0");
+
+        assert_eq!(value("basis-1/2"), "50%");
+        assert_eq!(value("basis-full"), "100%");
+        assert_eq!(value("basis-4"), "1rem");
+
+        assert_eq!(value("flex-1"), "1");
+        assert_eq!(value("flex-2"), "2");
+        assert_eq!(value("flex-1/2"), "50%");
+        assert_eq!(value("flex-auto"), "auto");
+        assert_eq!(value("flex-initial"), "0 auto");
+        assert_eq!(value("flex-none"), "none");
+        assert_eq!(value("flex-[2_2_0%]"), "2 2 0%");
+        // flex-row 等仍是 flex-direction
+        let row = converter.to_declarations(&parse_class("flex-row").unwrap()).unwrap();
+        assert_eq!(row[0].property, "flex-direction");
+    }
+
+    #[test]
+    fn test_first_party_plugin_compat_classes() {
+        let converter = Converter::new();
+        let declarations = |class: &str| converter.to_declarations(&parse_class(class).unwrap());
+
+        let aspect_w = declarations("aspect-w-16").unwrap();
+        assert_eq!(aspect_w[0], Declaration::new("--tw-aspect-w", "16"));
+        assert_eq!(aspect_w[1].value, "var(--tw-aspect-w) / var(--tw-aspect-h)");
+        assert_eq!(declarations("aspect-h-9").unwrap(), vec![Declaration::new("--tw-aspect-h", "9")]);
+        assert!(declarations("aspect-w-0").is_none());
+        assert!(declarations("aspect-w-full").is_none());
+
+        let input = declarations("form-input").unwrap();
+        assert!(input.contains(&Declaration::new("appearance", "none")));
+        assert!(input.contains(&Declaration::new("border-width", "1px")));
+        let select = declarations("form-select").unwrap();
+        assert!(select.iter().any(|d| d.property == "background-image" && d.value.starts_with("url(")));
+        assert!(declarations("form-radio").unwrap().contains(&Declaration::new("border-radius", "100%")));
+        assert!(declarations("form-slider").is_none());
+
+        assert_eq!(declarations("container").unwrap(), vec![Declaration::new("width", "100%")]);
+    }
+
+    #[test]
+    fn test_type_hinted_arbitrary_values() {
+        let converter = Converter::new();
+        let single = |class: &str| -> (String, String) {
+            let decls = converter.to_declarations(&parse_class(class).unwrap()).unwrap();
+            assert_eq!(decls.len(), 1, "{}", class);
+            (decls[0].property.clone(), decls[0].value.clone())
+        };
+        let pair = |p: &str, v: &str| (p.to_string(), v.to_string());
+
+        assert_eq!(
+            single("bg-[position:center_top_1rem]"),
+            pair("background-position", "center top 1rem")
+        );
+        assert_eq!(single("bg-[length:200px_100px]"), pair("background-size", "200px 100px"));
+        assert_eq!(single("bg-position-[center_top]"), pair("background-position", "center top"));
+        assert_eq!(single("bg-size-[200px_100px]"), pair("background-size", "200px 100px"));
+        assert_eq!(single("bg-(position:--p)"), pair("background-position", "var(--p)"));
+        assert_eq!(single("object-[25%_75%]"), pair("object-position", "25% 75%"));
+        assert_eq!(single("mask-[position:10px_20px]"), pair("mask-position", "10px 20px"));
+        assert_eq!(single("mask-[url(/m.svg)]"), pair("mask-image", "url(/m.svg)"));
+        // 插件没有对应属性时去掉提示
+        assert_eq!(single("w-[length:10px]"), pair("width", "10px"));
+        assert!(converter.to_declarations(&parse_class("bg-[position:]").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_rounded_corners() {
+        let converter = Converter::new();
+        let properties = |class: &str| -> Vec<(String, String)> {
+            converter
+                .to_declarations(&parse_class(class).unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|d| (d.property, d.value))
+                .collect()
+        };
+        let decl = |property: &str, value: &str| (property.to_string(), value.to_string());
+
+        assert_eq!(properties("rounded-tl-lg"), [decl("border-top-left-radius", "0.5rem")]);
+        assert_eq!(properties("rounded-br"), [decl("border-bottom-right-radius", "0.25rem")]);
+        assert_eq!(properties("rounded-bl-none"), [decl("border-bottom-left-radius", "0")]);
+        assert_eq!(
+            properties("rounded-s-md"),
+            [
+                decl("border-start-start-radius", "0.375rem"),
+                decl("border-end-start-radius", "0.375rem"),
+            ]
+        );
+        assert_eq!(
+            properties("rounded-e"),
+            [
+                decl("border-start-end-radius", "0.25rem"),
+                decl("border-end-end-radius", "0.25rem"),
+            ]
+        );
+        assert_eq!(properties("rounded-ss-xl"), [decl("border-start-start-radius", "0.75rem")]);
+        assert_eq!(properties("rounded-ee-full"), [decl("border-end-end-radius", "9999px")]);
+        assert_eq!(properties("rounded-tr-(--r)"), [decl("border-top-right-radius", "var(--r)")]);
+        assert_eq!(properties("rounded-es-[3px]"), [decl("border-end-start-radius", "3px")]);
+        assert_eq!(properties("rounded-[10px_20px]"), [decl("border-radius", "10px 20px")]);
+
+        // 圆角没有负值，未知尺寸不生成
+        assert!(converter.to_declarations(&parse_class("-rounded-lg").unwrap()).is_none());
+        assert!(converter.to_declarations(&parse_class("rounded-tl-5xl").unwrap()).is_none());
+
+        let v3 = Converter::new().with_target_version(TailwindVersion::V3);
+        assert_eq!(single_value(&v3, "rounded-tl-sm"), "0.125rem");
+        assert_eq!(single_value(&v3, "rounded-se"), "0.25rem");
+    }
+
     // ── inset-shadow named sizes ─────────────────────────────────
 
     #[test]
@@ -1346,6 +2258,18 @@ mod tests {
         assert_eq!(decls[0].value, "calc(var(--spacing) * 6)");
     }
 
+    #[test]
+    fn test_resolve_spacing_multiples() {
+        let converter = Converter::new().with_resolve_spacing(true);
+        let decls = converter.to_declarations(&parse_class("leading-6").unwrap()).unwrap();
+        assert_eq!(decls[0].value, "1.5rem");
+        let decls = converter.to_declarations(&parse_class("text-base/7").unwrap()).unwrap();
+        assert_eq!(decls[1].property, "line-height");
+        assert_eq!(decls[1].value, "1.75rem");
+        let decls = converter.to_declarations(&parse_class("leading-0").unwrap()).unwrap();
+        assert_eq!(decls[0].value, "0");
+    }
+
     #[test]
     fn test_leading_css_variable() {
         let converter = Converter::new();
@@ -1525,36 +2449,92 @@ mod tests {
         assert!(decls[0].value.ends_with("80")); // 50% = 0x80
     }
 
+    #[test]
+    fn test_alpha_arbitrary_and_variable() {
+        let value = |converter: &Converter, class: &str| {
+            converter.to_declarations(&parse_class(class).unwrap()).unwrap()[0]
+                .value
+                .clone()
+        };
+
+        // 方括号内的数字按比例换算：0.37 → 37% = 0x5e
+        let hex = Converter::new();
+        assert_eq!(value(&hex, "bg-blue-500/[.37]"), "#2b7fff5e");
+        assert_eq!(value(&hex, "bg-blue-500/[0.37]"), "#2b7fff5e");
+        assert_eq!(value(&hex, "bg-blue-500/[37.5%]"), "#2b7fff60");
+        assert_eq!(value(&hex, "text-white/[1]"), "#ffffff");
+        // 表达式无法写进 hex，改用 color-mix
+        assert_eq!(
+            value(&hex, "bg-blue-500/(--opacity)"),
+            "color-mix(in oklab, #2b7fff var(--opacity), transparent)"
+        );
+        assert_eq!(
+            value(&hex, "text-white/[var(--a)]"),
+            "color-mix(in oklab, #ffffff var(--a), transparent)"
+        );
+        assert_eq!(value(&hex, "bg-[#ff0000]/[0.5]"), "#ff000080");
+
+        let oklch = Converter::new().with_color_mode(ColorMode::Oklch);
+        assert_eq!(value(&oklch, "text-white/[0.37]"), "oklch(1 0 0 / 37%)");
+        assert_eq!(value(&oklch, "text-white/[37.5%]"), "oklch(1 0 0 / 37.5%)");
+        assert_eq!(value(&oklch, "text-white/(--opacity)"), "oklch(1 0 0 / var(--opacity))");
+
+        let mix = Converter::new().with_color_mix(true);
+        assert_eq!(
+            value(&mix, "border-red-500/[0.375]"),
+            "color-mix(in oklab, #fb2c36 37.5%, transparent)"
+        );
+        assert_eq!(
+            value(&mix, "text-white/(--opacity)"),
+            "color-mix(in oklab, #ffffff var(--opacity), transparent)"
+        );
+
+        // 文字大小仍把修饰符当作行高
+        let decls = hex.to_declarations(&parse_class("text-base/[1.5rem]").unwrap()).unwrap();
+        assert_eq!(decls[0].value, "var(--text-base)");
+        assert_eq!(decls[1].value, "1.5rem");
+    }
+
     // ── space-x / space-y ──────────────────────────────────────────
 
     #[test]
-    fn test_space_x_0() {
+    fn test_space_x_2() {
         let converter = Converter::new();
-        let parsed = parse_class("space-x-0").unwrap();
-        let decls = converter.to_declarations(&parsed).unwrap();
-        assert_eq!(decls.len(), 1);
-        assert_eq!(decls[0].property, "column-gap");
-        assert_eq!(decls[0].value, "0");
+        let rule = converter.convert(&parse_class("space-x-2").unwrap()).unwrap();
+        assert_eq!(rule.selector, ":where(.space-x-2 > :not(:last-child))");
+        let decls: Vec<_> = rule
+            .declarations
+            .iter()
+            .map(|d| format!("{}: {}", d.property, d.value))
+            .collect();
+        assert_eq!(
+            decls,
+            [
+                "--tw-space-x-reverse: 0",
+                "margin-inline-start: calc(0.5rem * var(--tw-space-x-reverse))",
+                "margin-inline-end: calc(0.5rem * calc(1 - var(--tw-space-x-reverse)))",
+            ]
+        );
     }
 
     #[test]
-    fn test_space_x_2() {
+    fn test_space_y_4() {
         let converter = Converter::new();
-        let parsed = parse_class("space-x-2").unwrap();
-        let decls = converter.to_declarations(&parsed).unwrap();
-        assert_eq!(decls.len(), 1);
-        assert_eq!(decls[0].property, "column-gap");
-        assert_eq!(decls[0].value, "0.5rem");
+        let rule = converter.convert(&parse_class("md:hover:space-y-4").unwrap()).unwrap();
+        assert_eq!(
+            rule.selector,
+            "@media (width >= 48rem) { :where(.space-y-4:hover > :not(:last-child)) }"
+        );
+        assert_eq!(rule.declarations[1].property, "margin-block-start");
+        assert_eq!(rule.declarations[2].value, "calc(1rem * calc(1 - var(--tw-space-y-reverse)))");
     }
 
     #[test]
-    fn test_space_y_4() {
+    fn test_space_reverse() {
         let converter = Converter::new();
-        let parsed = parse_class("space-y-4").unwrap();
-        let decls = converter.to_declarations(&parsed).unwrap();
-        assert_eq!(decls.len(), 1);
-        assert_eq!(decls[0].property, "row-gap");
-        assert_eq!(decls[0].value, "1rem");
+        let rule = converter.convert(&parse_class("space-x-reverse").unwrap()).unwrap();
+        assert_eq!(rule.selector, ":where(.space-x-reverse > :not(:last-child))");
+        assert_eq!(rule.declarations, vec![Declaration::new("--tw-space-x-reverse", "1")]);
     }
 
     // ── scroll padding / margin ────────────────────────────────────
@@ -0,0 +1,83 @@
+use crate::plugin_map::accepts_negative;
+use headwind_tw_parse::{ParsedClass, ParsedValue};
+
+/// 负值类是否有对应的 CSS：插件接受负值（见 `accepts_negative`），或是带角度的渐变（`-bg-linear-45`、`-mask-conic-90`）
+pub(super) fn allows_negative(parsed: &ParsedClass) -> bool {
+    if accepts_negative(&parsed.plugin) {
+        return true;
+    }
+    match (parsed.plugin.as_ref(), &parsed.value) {
+        ("bg" | "mask", Some(ParsedValue::Standard(value))) => {
+            ["linear-", "conic-"].iter().any(|kind| {
+                value
+                    .strip_prefix(kind)
+                    .is_some_and(|angle| angle.parse::<f64>().is_ok())
+            })
+        }
+        _ => false,
+    }
+}
+
+/// 对 CSS 值取负，所有负值类共用
+///
+/// - 数值：`1rem` → `-1rem`，`-0.025em` → `0.025em`，值为零时不变
+/// - `var()`、`calc()` 等表达式：`var(--tracking-tight)` → `calc(var(--tracking-tight) * -1)`
+/// - 关键字、颜色与多值（`auto`、`#fff`、`1px 2px`）没有负值，返回 None
+pub(super) fn negate_value(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.contains(char::is_whitespace) && !is_expression(value) {
+        return None;
+    }
+    if let Some(positive) = value.strip_prefix('-') {
+        return starts_with_number(positive).then(|| positive.to_string());
+    }
+    if starts_with_number(value) {
+        let is_zero = value
+            .trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == '%')
+            .parse::<f64>()
+            .is_ok_and(|n| n == 0.0);
+        return Some(if is_zero {
+            value.to_string()
+        } else {
+            format!("-{}", value)
+        });
+    }
+    is_expression(value).then(|| format!("calc({} * -1)", value))
+}
+
+fn starts_with_number(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+}
+
+/// 可以放进 `calc()` 的单个数学函数或变量引用
+fn is_expression(value: &str) -> bool {
+    ["var(", "calc(", "min(", "max(", "clamp(", "env("]
+        .iter()
+        .any(|function| value.starts_with(function))
+        && value.ends_with(')')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negate_value() {
+        assert_eq!(negate_value("1rem").as_deref(), Some("-1rem"));
+        assert_eq!(negate_value("50%").as_deref(), Some("-50%"));
+        assert_eq!(negate_value("-0.025em").as_deref(), Some("0.025em"));
+        assert_eq!(negate_value("0").as_deref(), Some("0"));
+        assert_eq!(negate_value("0px").as_deref(), Some("0px"));
+        assert_eq!(
+            negate_value("var(--tracking-tight)").as_deref(),
+            Some("calc(var(--tracking-tight) * -1)")
+        );
+        assert_eq!(
+            negate_value("calc(var(--spacing) * 4)").as_deref(),
+            Some("calc(calc(var(--spacing) * 4) * -1)")
+        );
+        for keyword in ["auto", "#ef4444", "oklch(0.6 0.2 25)", "1px 2px", "-auto", ""] {
+            assert_eq!(negate_value(keyword), None, "{}", keyword);
+        }
+    }
+}
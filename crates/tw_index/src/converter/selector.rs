@@ -1,17 +1,9 @@
+use crate::variant::{self, StateResolution};
+use headwind_core::BreakpointStyle;
 use headwind_tw_parse::{Modifier, ParsedClass};
-use phf::phf_map;
-
-/// 响应式断点映射
-static BREAKPOINT_MAP: phf::Map<&'static str, &'static str> = phf_map! {
-    "sm" => "640px",
-    "md" => "768px",
-    "lg" => "1024px",
-    "xl" => "1280px",
-    "2xl" => "1536px",
-};
 
 /// 构建基础类名（不包含修饰符）
-fn build_base_class(parsed: &ParsedClass) -> String {
+pub(super) fn build_base_class(parsed: &ParsedClass) -> String {
     let mut class = String::new();
 
     if parsed.negative {
@@ -34,36 +26,76 @@ fn build_base_class(parsed: &ParsedClass) -> String {
 }
 
 /// 构建 CSS 选择器，包含修饰符
-pub(super) fn build_selector(parsed: &ParsedClass) -> String {
+///
+/// 选择器类修饰符（伪类、`.dark`、group/peer 等）按书写顺序拼接到选择器上，
+/// at-rule 类修饰符（断点、`motion-safe` 等）收集后由外到内包裹整个选择器，
+/// 如 `dark:md:hover:underline` → `@media (width >= 48rem) { .dark .underline:hover }`
+///
+/// 伪元素总是位于选择器末尾：`before:hover:` 与 `hover:before:` 都生成 `.c:hover::before`。
+/// `space-x` / `space-y` 在书写的修饰符之后选中子元素：`hover:space-x-2` →
+/// `:where(.space-x-2:hover > :not(:last-child))`。
+///
+/// 断点与容器查询的写法由 `breakpoints` 决定，与打包器输出一致。
+pub(super) fn build_selector(parsed: &ParsedClass, breakpoints: BreakpointStyle) -> String {
     let class_name = build_base_class(parsed);
     let mut selector = format!(".{}", class_name);
+    let mut at_rules = Vec::new();
 
-    for modifier in &parsed.modifiers() {
-        selector = apply_modifier(&selector, modifier);
+    let mut modifiers = parsed.modifiers();
+    if let Some(child) = variant::utility_child_variant(&parsed.plugin) {
+        modifiers.push(Modifier::PseudoClass(child.to_string()));
+    }
+    variant::pseudo_elements_last(&mut modifiers);
+    for modifier in &modifiers {
+        match modifier_at_rule(modifier, breakpoints) {
+            Some(at_rule) => at_rules.push(at_rule),
+            None => selector = apply_modifier(&selector, modifier),
+        }
     }
 
-    selector
+    at_rules
+        .into_iter()
+        .rev()
+        .fold(selector, |inner, at_rule| format!("{} {{ {} }}", at_rule, inner))
 }
 
-/// 应用单个修饰符到选择器
-fn apply_modifier(selector: &str, modifier: &Modifier) -> String {
+/// 需要用 at-rule 包裹的修饰符
+fn modifier_at_rule(modifier: &Modifier, breakpoints: BreakpointStyle) -> Option<String> {
     match modifier {
-        Modifier::PseudoClass(name) => format!("{}:{}", selector, name),
-        Modifier::PseudoElement(name) => format!("{}::{}", selector, name),
-        Modifier::State(name) => match name.as_str() {
-            "dark" => format!(".dark {}", selector),
-            name if name.starts_with("group-") => {
-                format!(".group:{} {}", &name[6..], selector)
+        Modifier::Responsive(size) => Some(
+            match size.strip_prefix('@') {
+                Some(container) => variant::container_at_rule_with(container, breakpoints),
+                None => variant::responsive_at_rule_with(size, breakpoints),
             }
-            name if name.starts_with("peer-") => {
-                format!(".peer:{} ~ {}", &name[5..], selector)
-            }
-            _ => selector.to_string(),
-        },
-        Modifier::Responsive(size) => {
-            let breakpoint = BREAKPOINT_MAP.get(size.as_str()).copied().unwrap_or("0px");
-            format!("@media (min-width: {}) {{ {} }}", breakpoint, selector)
+            .unwrap_or_else(|| "@media (min-width: 0px)".to_string()),
+        ),
+        Modifier::State(name) if name == "starting" => Some("@starting-style".to_string()),
+        Modifier::State(name) if name != "dark" => variant::supports_at_rule(name)
+            .or_else(|| match variant::resolve_state(name, "") {
+                StateResolution::AtRule(at_rule) => Some(at_rule),
+                StateResolution::Selector(_) => None,
+            }),
+        _ => None,
+    }
+}
+
+/// 应用单个选择器类修饰符
+fn apply_modifier(selector: &str, modifier: &Modifier) -> String {
+    match modifier {
+        Modifier::PseudoClass(name) => variant::apply_pseudo_class(selector, name),
+        Modifier::PseudoElement(name) => {
+            format!("{}::{}", selector, variant::pseudo_element_selector(name))
         }
-        Modifier::Custom(name) => format!("{}:{}", selector, name),
+        Modifier::State(name) if name == "dark" => format!(".dark {}", selector),
+        Modifier::State(name) => match variant::resolve_state(name, selector) {
+            StateResolution::Selector(resolved) => resolved,
+            StateResolution::AtRule(_) => selector.to_string(),
+        },
+        // 响应式修饰符由 modifier_at_rule 处理
+        Modifier::Responsive(_) => selector.to_string(),
+        Modifier::Custom(name) => match variant::parameterized_selector(name) {
+            Some(param_sel) => format!("{}{}", selector, param_sel),
+            None => format!("{}:{}", selector, name),
+        },
     }
 }
@@ -1,13 +1,120 @@
-use crate::plugin_map::get_plugin_properties;
+use crate::plugin_map::{accepts_fraction, filter_function, get_plugin_properties, is_radius_plugin};
 use crate::theme_values;
-use crate::value_map::{get_color_value, get_spacing_value, infer_value};
-use headwind_core::Declaration;
-use headwind_tw_parse::ParsedClass;
+use crate::value_map::{get_color_value, get_radius_value, get_spacing_value, infer_value};
+use headwind_core::{Declaration, TailwindVersion};
+use headwind_tw_parse::{ParsedClass, ParsedValue};
 
 use super::arbitrary::extract_bracket_value;
+use super::negative::negate_value;
 use super::Converter;
 
 impl Converter {
+    /// 间距基数的 `n` 倍
+    ///
+    /// 默认输出 `calc(var(--spacing) * n)`（需要 `:root` 中定义 `--spacing`），
+    /// `resolve_spacing` 时按默认基数 0.25rem 直接算出结果。
+    pub(super) fn spacing_multiple(&self, n: &str) -> String {
+        let calc = || format!("calc(var(--spacing) * {})", n);
+        if self.resolve_spacing {
+            get_spacing_value(n).unwrap_or_else(calc)
+        } else {
+            calc()
+        }
+    }
+
+    /// 命名阴影尺寸，空字符串表示不带尺寸的 `shadow`
+    ///
+    /// v4 引用 `--shadow-*` 主题变量（`shadow` 等同 `shadow-sm`，内联模式输出具体值），v3 直接输出具体值
+    fn named_shadow(&self, size: &str) -> Option<String> {
+        match self.target_version {
+            TailwindVersion::V3 => {
+                let size = if size.is_empty() { "DEFAULT" } else { size };
+                theme_values::V3_SHADOW_SIZE.get(size).map(|v| v.to_string())
+            }
+            TailwindVersion::V4 => {
+                let size = if size.is_empty() { "sm" } else { size };
+                let value = theme_values::SHADOW_SIZE.get(size)?;
+                Some(if self.uses_variable(self.variable_overrides.shadow) {
+                    format!("var(--shadow-{})", size)
+                } else {
+                    value.to_string()
+                })
+            }
+        }
+    }
+
+    /// 命名内阴影尺寸（`2xs` / `xs` / `sm`）
+    fn named_inset_shadow(&self, size: &str) -> Option<String> {
+        let value = theme_values::INSET_SHADOW_SIZE.get(size)?;
+        Some(if self.uses_variable(self.variable_overrides.shadow) {
+            format!("var(--inset-shadow-{})", size)
+        } else {
+            value.to_string()
+        })
+    }
+
+    /// 命名文字阴影尺寸（`2xs` ~ `lg`），与盒阴影共用变量 / 内联设置
+    fn named_text_shadow(&self, size: &str) -> Option<String> {
+        let value = theme_values::TEXT_SHADOW_SIZE.get(size)?;
+        Some(if self.uses_variable(self.variable_overrides.shadow) {
+            format!("var(--text-shadow-{})", size)
+        } else {
+            value.to_string()
+        })
+    }
+
+    /// 命名模糊尺寸，空字符串表示不带尺寸的 `blur`
+    fn named_blur(&self, size: &str) -> Option<String> {
+        match self.target_version {
+            TailwindVersion::V3 => {
+                let size = if size.is_empty() { "DEFAULT" } else { size };
+                theme_values::V3_BLUR_SIZE.get(size).map(|v| v.to_string())
+            }
+            TailwindVersion::V4 => {
+                let size = if size.is_empty() { "sm" } else { size };
+                if self.uses_variable(self.variable_overrides.blur) {
+                    Some(format!("var(--blur-{})", size))
+                } else {
+                    theme_values::BLUR_SIZE.get(size).map(|v| v.to_string())
+                }
+            }
+        }
+    }
+
+    /// 命名字间距（`tight`、`wide` 等），v4 引用 `--tracking-*` 主题变量，v3 与内联模式输出具体值
+    fn named_tracking(&self, name: &str) -> Option<String> {
+        let value = theme_values::TRACKING.get(name)?;
+        Some(match self.target_version {
+            TailwindVersion::V4 if self.uses_variable(None) => format!("var(--tracking-{})", name),
+            _ => value.to_string(),
+        })
+    }
+
+    /// 不带值的尺寸类（`rounded`、`shadow`、`blur` 等）按默认尺寸构建
+    pub(super) fn build_default_size(&self, parsed: &ParsedClass) -> Option<Vec<Declaration>> {
+        match parsed.plugin.as_ref() {
+            "shadow" | "blur" | "backdrop-blur" => self.build_standard_declarations(parsed, ""),
+            plugin if is_radius_plugin(plugin) => self.build_standard_declarations(parsed, ""),
+            _ => None,
+        }
+    }
+
+    /// 分数值（`w-1/2`、`-top-1/3`）
+    ///
+    /// 解析器把 `/` 之后的部分当作透明度，接受分数的插件在这里重新组合为 `1/2`
+    pub(super) fn build_fraction_declarations(&self, parsed: &ParsedClass) -> Option<Vec<Declaration>> {
+        let (Some(ParsedValue::Standard(numerator)), Some(denominator)) =
+            (&parsed.value, &parsed.alpha)
+        else {
+            return None;
+        };
+        let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !accepts_fraction(&parsed.plugin) || !is_number(numerator) || !is_number(denominator) {
+            return None;
+        }
+        self.build_standard_declarations(parsed, &format!("{}/{}", numerator, denominator))
+    }
+
     /// 为标准值构建 CSS 声明
     pub(super) fn build_standard_declarations(&self, parsed: &ParsedClass, value: &str) -> Option<Vec<Declaration>> {
         if let Some(decls) = self.build_complex_standard(parsed, value) {
@@ -15,10 +122,13 @@ impl Converter {
         }
 
         let properties = get_plugin_properties(&parsed.plugin)?;
-        let mut css_value = infer_value(&parsed.plugin, value, self.color_mode)?;
+        let mut css_value = match parsed.plugin.as_ref() {
+            plugin if is_radius_plugin(plugin) => get_radius_value(value, self.target_version)?,
+            _ => infer_value(&parsed.plugin, value, self.color_mode)?,
+        };
 
         if parsed.negative {
-            css_value = format!("-{}", css_value);
+            css_value = negate_value(&css_value)?;
         }
 
         let declarations = properties
@@ -29,9 +139,83 @@ impl Converter {
         Some(declarations)
     }
 
+    /// 渐变背景：`bg-linear-to-r`（含 v3 的 `bg-gradient-to-r`）、`bg-linear-45`、`bg-radial`、`bg-conic-90` 等
+    ///
+    /// 修饰符指定插值方式：`bg-linear-to-r/oklch` → `linear-gradient(to right in oklch, …)`，
+    /// `/longer` 等色相插值在 oklch 中进行。没有修饰符时方向渐变不指定插值空间（与 v3 一致），
+    /// 角度、径向、锥形渐变默认 `in oklab`。
+    fn build_gradient(&self, parsed: &ParsedClass, value: &str) -> Option<Vec<Declaration>> {
+        let interpolation = match parsed.alpha.as_deref() {
+            Some(modifier) => Some(gradient_interpolation(modifier)?),
+            None => None,
+        };
+        let space = interpolation.as_deref().unwrap_or("in oklab");
+        let angle = |n: f64| {
+            let degrees = format!("{}deg", n);
+            if parsed.negative {
+                negate_value(&degrees).unwrap_or(degrees)
+            } else {
+                degrees
+            }
+        };
+        let image = |css: String| Some(vec![Declaration::new("background-image", css)]);
+
+        if let Some(dir) = value
+            .strip_prefix("linear-to-")
+            .or_else(|| value.strip_prefix("gradient-to-"))
+        {
+            let direction = match dir {
+                "t" => "to top",
+                "b" => "to bottom",
+                "l" => "to left",
+                "r" => "to right",
+                "tl" => "to top left",
+                "tr" => "to top right",
+                "bl" => "to bottom left",
+                "br" => "to bottom right",
+                _ => return None,
+            };
+            let position = match &interpolation {
+                Some(interpolation) => format!("{} {}", direction, interpolation),
+                None => direction.to_string(),
+            };
+            return image(format!("linear-gradient({}, var(--tw-gradient-stops))", position));
+        }
+
+        match value {
+            "radial" => image(format!("radial-gradient({}, var(--tw-gradient-stops))", space)),
+            "conic" => image(format!("conic-gradient({}, var(--tw-gradient-stops))", space)),
+            _ => {
+                let (kind, rest) = value.split_once('-')?;
+                match (kind, extract_bracket_value(rest)) {
+                    // linear-[<value>] / radial-[<value>]
+                    ("linear" | "radial", Some(arb)) => image(format!(
+                        "{}-gradient(var(--tw-gradient-stops, {}))",
+                        kind, arb
+                    )),
+                    // conic-[<value>] → 直接作为 background-image
+                    ("conic", Some(arb)) => image(arb.to_string()),
+                    // linear-<angle>
+                    ("linear", None) => image(format!(
+                        "linear-gradient({} {}, var(--tw-gradient-stops))",
+                        angle(rest.parse().ok()?),
+                        space
+                    )),
+                    // conic-<angle>
+                    ("conic", None) => image(format!(
+                        "conic-gradient(from {} {}, var(--tw-gradient-stops))",
+                        angle(rest.parse().ok()?),
+                        space
+                    )),
+                    _ => None,
+                }
+            }
+        }
+    }
+
     /// 处理复杂标准值插件（语义重载，不同值映射到不同 CSS 属性）
     fn build_complex_standard(&self, parsed: &ParsedClass, value: &str) -> Option<Vec<Declaration>> {
-        match parsed.plugin.as_str() {
+        match parsed.plugin.as_ref() {
             // ── text: text-align / text-wrap / font-size / color ─────
             "text" => match value {
                 "left" | "center" | "right" | "justify" | "start" | "end" => {
@@ -42,7 +226,7 @@ impl Converter {
                 }
                 "xs" | "sm" | "base" | "lg" | "xl" | "2xl" | "3xl" | "4xl" | "5xl" | "6xl"
                 | "7xl" | "8xl" | "9xl" => {
-                    let font_size = if self.use_variables {
+                    let font_size = if self.uses_variable(self.variable_overrides.font_size) {
                         format!("var(--text-{})", value)
                     } else {
                         theme_values::TEXT_SIZE.get(value)?.to_string()
@@ -60,11 +244,11 @@ impl Converter {
                             format!("var({})", inner)
                         } else if alpha.chars().all(|c| c.is_ascii_digit()) {
                             // 数字：text-base/6 → line-height: calc(var(--spacing) * 6)
-                            format!("calc(var(--spacing) * {})", alpha)
+                            self.spacing_multiple(alpha)
                         } else {
-                            alpha.clone()
+                            alpha.to_string()
                         }
-                    } else if self.use_variables {
+                    } else if self.uses_variable(self.variable_overrides.font_size) {
                         format!("var(--text-{}--line-height)", value)
                     } else {
                         theme_values::TEXT_LINE_HEIGHT.get(value)?.to_string()
@@ -122,14 +306,6 @@ impl Converter {
             }
             // Gradient
             "none" => Some(vec![Declaration::new("background-image", "none")]),
-            "radial" => Some(vec![Declaration::new(
-                "background-image",
-                "radial-gradient(in oklab, var(--tw-gradient-stops))",
-            )]),
-            "conic" => Some(vec![Declaration::new(
-                "background-image",
-                "conic-gradient(in oklab, var(--tw-gradient-stops))",
-            )]),
             _ => {
                 // blend-* → background-blend-mode
                 if let Some(mode) = value.strip_prefix("blend-") {
@@ -138,91 +314,15 @@ impl Converter {
                         mode.to_string(),
                     )]);
                 }
-                // linear-to-* / gradient-to-* (v3 compat) → background-image: linear-gradient(...)
-                if let Some(dir) = value
-                    .strip_prefix("linear-to-")
-                    .or_else(|| value.strip_prefix("gradient-to-"))
-                {
-                    let direction = match dir {
-                        "t" => "to top",
-                        "b" => "to bottom",
-                        "l" => "to left",
-                        "r" => "to right",
-                        "tl" => "to top left",
-                        "tr" => "to top right",
-                        "bl" => "to bottom left",
-                        "br" => "to bottom right",
-                        _ => return None,
-                    };
-                    return Some(vec![Declaration::new(
-                        "background-image",
-                        format!("linear-gradient({}, var(--tw-gradient-stops))", direction),
-                    )]);
-                }
-                // linear-<angle> 或 linear-[<value>]
-                if let Some(rest) = value.strip_prefix("linear-") {
-                    if let Some(arb) = extract_bracket_value(rest) {
-                        return Some(vec![Declaration::new(
-                            "background-image",
-                            format!("linear-gradient(var(--tw-gradient-stops, {}))", arb),
-                        )]);
-                    }
-                    if let Ok(n) = rest.parse::<f64>() {
-                        let deg = if parsed.negative {
-                            format!("-{}deg", n)
-                        } else {
-                            format!("{}deg", n)
-                        };
-                        return Some(vec![Declaration::new(
-                            "background-image",
-                            format!(
-                                "linear-gradient({} in oklab, var(--tw-gradient-stops))",
-                                deg
-                            ),
-                        )]);
-                    }
-                }
-                // conic-<angle> 或 conic-[<value>]
-                if let Some(rest) = value.strip_prefix("conic-") {
-                    // conic-[<value>] → 直接作为 background-image
-                    if let Some(arb) = extract_bracket_value(rest) {
-                        return Some(vec![Declaration::new(
-                            "background-image",
-                            arb.to_string(),
-                        )]);
-                    }
-                    if let Ok(n) = rest.parse::<f64>() {
-                        let deg = if parsed.negative {
-                            format!("-{}deg", n)
-                        } else {
-                            format!("{}deg", n)
-                        };
-                        return Some(vec![Declaration::new(
-                            "background-image",
-                            format!(
-                                "conic-gradient(from {} in oklab, var(--tw-gradient-stops))",
-                                deg
-                            ),
-                        )]);
-                    }
-                }
-                // radial-[<value>]
-                if let Some(rest) = value.strip_prefix("radial-") {
-                    if let Some(arb) = extract_bracket_value(rest) {
-                        return Some(vec![Declaration::new(
-                            "background-image",
-                            format!("radial-gradient(var(--tw-gradient-stops, {}))", arb),
-                        )]);
-                    }
-                }
-                None // fall through to standard path for colors
+                // linear / radial / conic 渐变
+                self.build_gradient(parsed, value) // 不是渐变时回退到颜色等标准路径
             }
         },
 
         // ── font: weight / family / stretch ──────────────────────
         "font" => match value {
             "sans" | "serif" | "mono" => {
-                if self.use_variables {
+                if self.uses_variable(self.variable_overrides.font_family) {
                     Some(vec![Declaration::new(
                         "font-family",
                         format!("var(--font-{})", value),
@@ -254,7 +354,11 @@ impl Converter {
 
         // ── content: align-content vs content property ───────────
         "content" => match value {
-            "none" => Some(vec![Declaration::new("content", "none")]),
+            // `--tw-content` 同时记录取值，伪元素变体据此补全 `content`
+            "none" => Some(vec![
+                Declaration::new("--tw-content", "none"),
+                Declaration::new("content", "none"),
+            ]),
             "start" => Some(vec![Declaration::new("align-content", "flex-start")]),
             "end" => Some(vec![Declaration::new("align-content", "flex-end")]),
             "around" => Some(vec![Declaration::new("align-content", "space-around")]),
@@ -276,8 +380,7 @@ impl Converter {
                     Some(vec![Declaration::new("border-color", color)])
                 } else if let Ok(n) = value.parse::<f64>() {
                     // border-<number> → border-width: <number>px
-                    let px = if parsed.negative { -n } else { n };
-                    Some(vec![Declaration::new("border-width", format!("{}px", px))])
+                    Some(vec![Declaration::new("border-width", format!("{}px", n))])
                 } else {
                     None // fall through for width
                 }
@@ -331,20 +434,18 @@ impl Converter {
 
         // ── shadow: named size / none / color ─────────────────────
         "shadow" => match value {
-            "2xs" | "xs" | "sm" | "md" | "lg" | "xl" | "2xl" => {
-                Some(vec![Declaration::new("box-shadow", format!("var(--shadow-{})", value))])
-            }
             "none" => Some(vec![Declaration::new("box-shadow", "0 0 #0000")]),
-            _ => {
-                get_color_value(value, self.color_mode)
-                    .map(|color| vec![Declaration::new("--tw-shadow-color", color)])
-            }
+            _ => match self.named_shadow(value) {
+                Some(shadow) => Some(vec![Declaration::new("box-shadow", shadow)]),
+                None => get_color_value(value, self.color_mode)
+                    .map(|color| vec![Declaration::new("--tw-shadow-color", color)]),
+            },
         },
 
         // ── inset-shadow: named size / none / color ──────────────
         "inset-shadow" => match value {
             "2xs" | "xs" | "sm" => {
-                Some(vec![Declaration::new("box-shadow", format!("var(--inset-shadow-{})", value))])
+                Some(vec![Declaration::new("box-shadow", self.named_inset_shadow(value)?)])
             }
             "none" => Some(vec![Declaration::new("box-shadow", "inset 0 0 #0000")]),
             _ => {
@@ -353,6 +454,16 @@ impl Converter {
             }
         },
 
+        // ── text-shadow: named size / none / color ───────────────
+        "text-shadow" => match value {
+            "none" => Some(vec![Declaration::new("text-shadow", "none")]),
+            _ => match self.named_text_shadow(value) {
+                Some(shadow) => Some(vec![Declaration::new("text-shadow", shadow)]),
+                None => get_color_value(value, self.color_mode)
+                    .map(|color| vec![Declaration::new("--tw-text-shadow-color", color)]),
+            },
+        },
+
         // ── ring: number width / color ───────────────────────────
         "ring" => {
             if let Ok(n) = value.parse::<u32>() {
@@ -363,6 +474,13 @@ impl Converter {
             }
         }
 
+        // ── ring-offset: number width / color ────────────────────
+        "ring-offset" => match value.parse::<u32>() {
+            Ok(n) => Some(ring_offset_width(format!("{}px", n))),
+            Err(_) => get_color_value(value, self.color_mode)
+                .map(|color| vec![Declaration::new("--tw-ring-offset-color", color)]),
+        },
+
         // ── inset-ring: number width / color ─────────────────────
         "inset-ring" => {
             if let Ok(n) = value.parse::<u32>() {
@@ -449,7 +567,7 @@ impl Converter {
             _ => None,
         },
 
-        // ── mask: size / position / clip / origin / repeat / composite / mode / type ──
+        // ── mask: size / position / clip / origin / repeat / composite / mode / type / gradient ──
         "mask" => match value {
             "auto" | "contain" | "cover" => {
                 Some(vec![Declaration::new("mask-size", value)])
@@ -475,6 +593,9 @@ impl Converter {
             "match" => Some(vec![Declaration::new("mask-mode", "match-source")]),
             "no-clip" => Some(vec![Declaration::new("mask-clip", "no-clip")]),
             _ => {
+                if let Some(decls) = self.build_mask_gradient(parsed, value) {
+                    return Some(decls);
+                }
                 if let Some(clip) = value.strip_prefix("clip-") {
                     let css = match clip {
                         "border" => "border-box",
@@ -513,11 +634,11 @@ impl Converter {
             }
             let css_val = get_spacing_value(value)?;
             let final_val = if parsed.negative {
-                format!("-{}", css_val)
+                negate_value(&css_val)?
             } else {
                 css_val
             };
-            let result = match parsed.plugin.as_str() {
+            let result = match parsed.plugin.as_ref() {
                 "translate" => format!("{0} {0}", final_val),
                 "translate-x" => format!("{} var(--tw-translate-y)", final_val),
                 "translate-y" => format!("var(--tw-translate-x) {}", final_val),
@@ -529,14 +650,32 @@ impl Converter {
             Some(vec![Declaration::new("translate", result)])
         }
 
-        // ── scale: named values ──────────────────────────────────
-        "scale" => match value {
-            "none" => Some(vec![Declaration::new("scale", "none")]),
-            "3d" => Some(vec![Declaration::new(
+        // ── scale: named values，数字为百分比（scale-110 → 110%） ──
+        "scale" | "scale-x" | "scale-y" => match (parsed.plugin.as_ref(), value) {
+            ("scale", "none") => Some(vec![Declaration::new("scale", "none")]),
+            ("scale", "3d") => Some(vec![Declaration::new(
                 "scale",
                 "var(--tw-scale-x) var(--tw-scale-y) var(--tw-scale-z)",
             )]),
-            _ => None,
+            (plugin, _) => {
+                let percent = format!("{}%", value.parse::<u32>().ok()?);
+                let percent = if parsed.negative {
+                    negate_value(&percent)?
+                } else {
+                    percent
+                };
+                let axes: &[&str] = match plugin {
+                    "scale" => &["x", "y", "z"],
+                    "scale-x" => &["x"],
+                    _ => &["y"],
+                };
+                let mut declarations: Vec<Declaration> = axes
+                    .iter()
+                    .map(|axis| Declaration::new(format!("--tw-scale-{}", axis), percent.clone()))
+                    .collect();
+                declarations.push(Declaration::new("scale", "var(--tw-scale-x) var(--tw-scale-y)"));
+                Some(declarations)
+            }
         },
 
         // ── transform: mode / style ──────────────────────────────
@@ -555,37 +694,18 @@ impl Converter {
             _ => None,
         },
 
-        // ── blur: filter with var() ──────────────────────────────
-        "blur" => {
-            if self.use_variables {
-                Some(vec![Declaration::new(
-                    "filter",
-                    format!("blur(var(--blur-{}))", value),
-                )])
-            } else {
-                let size = theme_values::BLUR_SIZE.get(value)?;
-                Some(vec![Declaration::new(
-                    "filter",
-                    format!("blur({})", size),
-                )])
-            }
-        }
+        // ── blur / backdrop-blur: 命名尺寸（v4 引用 --blur-*） ─────
+        "blur" => Some(vec![Declaration::new(
+            "filter",
+            format!("blur({})", self.named_blur(value)?),
+        )]),
+        "backdrop-blur" => Some(vec![Declaration::new(
+            "backdrop-filter",
+            format!("blur({})", self.named_blur(value)?),
+        )]),
 
-        // ── backdrop-blur: backdrop-filter with var() ────────────
-        "backdrop-blur" => {
-            if self.use_variables {
-                Some(vec![Declaration::new(
-                    "backdrop-filter",
-                    format!("blur(var(--blur-{}))", value),
-                )])
-            } else {
-                let size = theme_values::BLUR_SIZE.get(value)?;
-                Some(vec![Declaration::new(
-                    "backdrop-filter",
-                    format!("blur({})", size),
-                )])
-            }
-        }
+        // ── 数值滤镜：brightness-50 → brightness(50%)，-hue-rotate-15 → hue-rotate(-15deg) ──
+        plugin if filter_function(plugin).is_some() => numeric_filter(parsed, value),
 
         // ── backdrop: filter-none ────────────────────────────────
         "backdrop" => match value {
@@ -599,12 +719,6 @@ impl Converter {
             _ => None,
         },
 
-        // ── underline: offset ────────────────────────────────────
-        "underline" => match value {
-            "offset-auto" => Some(vec![Declaration::new("text-underline-offset", "auto")]),
-            _ => None,
-        },
-
         // ── line-clamp ───────────────────────────────────────────
         "line-clamp" => match value {
             "none" => Some(vec![
@@ -629,7 +743,7 @@ impl Converter {
             "auto" => Some(vec![Declaration::new("aspect-ratio", "auto")]),
             "square" => Some(vec![Declaration::new("aspect-ratio", "1 / 1")]),
             "video" => {
-                if self.use_variables {
+                if self.uses_variable(self.variable_overrides.aspect) {
                     Some(vec![Declaration::new(
                         "aspect-ratio",
                         "var(--aspect-video)",
@@ -638,6 +752,7 @@ impl Converter {
                     Some(vec![Declaration::new("aspect-ratio", "16 / 9")])
                 }
             }
+            _ if value.contains('/') => Some(vec![Declaration::new("aspect-ratio", value.to_string())]),
             _ => None,
         },
 
@@ -647,6 +762,19 @@ impl Converter {
             _ => None,
         },
 
+        // ── skew: 角度写入 --tw-skew-x / --tw-skew-y（-skew-x-6 取负）──
+        "skew" | "skew-x" | "skew-y" => {
+            let angle = format!("{}deg", value.parse::<u32>().ok()?);
+            let angle = if parsed.negative { negate_value(&angle)? } else { angle };
+            Some(skew_declarations(&parsed.plugin, &angle))
+        }
+
+        // ── space-x-reverse: 子元素间距从末尾一侧改到起始一侧 ────
+        "space-x" | "space-y" if value == "reverse" => Some(vec![Declaration::new(
+            format!("--tw-{}-reverse", parsed.plugin),
+            "1",
+        )]),
+
         // ── divide: border-style with child combinator ──────────
         "divide" => match value {
             "solid" | "dashed" | "dotted" | "double" | "hidden" | "none" => {
@@ -655,6 +783,13 @@ impl Converter {
             _ => None,
         },
 
+        // ── tracking: letter-spacing（`-tracking-tight` 取负）────
+        "tracking" => {
+            let spacing = self.named_tracking(value)?;
+            let spacing = if parsed.negative { negate_value(&spacing)? } else { spacing };
+            Some(vec![Declaration::new("letter-spacing", spacing)])
+        }
+
         // ── leading: line-height ────────────────────────────────
         "leading" => match value {
             "none" => Some(vec![Declaration::new("line-height", "1")]),
@@ -662,7 +797,7 @@ impl Converter {
                 if let Ok(n) = value.parse::<u32>() {
                     Some(vec![Declaration::new(
                         "line-height",
-                        format!("calc(var(--spacing) * {})", n),
+                        self.spacing_multiple(&n.to_string()),
                     )])
                 } else {
                     None // fall through to standard path (infer_value handles named values)
@@ -670,21 +805,115 @@ impl Converter {
             }
         },
 
-        // ── from / via / to: gradient color stops ────────────────
-        "from" => {
-            get_color_value(value, self.color_mode)
-                .map(|color| vec![Declaration::new("--tw-gradient-from", color)])
-        }
-        "via" => {
-            get_color_value(value, self.color_mode)
-                .map(|color| vec![Declaration::new("--tw-gradient-via", color)])
-        }
-        "to" => {
+        // ── from / via / to: gradient color stops and positions ──
+        "from" | "via" | "to" => {
+            let stop = &parsed.plugin;
+            if is_percentage(value) {
+                return Some(vec![Declaration::new(
+                    format!("--tw-gradient-{}-position", stop),
+                    value,
+                )]);
+            }
             get_color_value(value, self.color_mode)
-                .map(|color| vec![Declaration::new("--tw-gradient-to", color)])
+                .map(|color| vec![Declaration::new(format!("--tw-gradient-{}", stop), color)])
         }
 
         _ => None,
     }
 }
 }
+
+/// 数值滤镜：`hue-rotate` 的值是角度，其余是百分比，只接受非负整数
+fn numeric_filter(parsed: &ParsedClass, value: &str) -> Option<Vec<Declaration>> {
+    let (property, function) = filter_function(&parsed.plugin)?;
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let unit = if function == "hue-rotate" { "deg" } else { "%" };
+    let amount = format!("{}{}", value, unit);
+    let amount = if parsed.negative { negate_value(&amount)? } else { amount };
+    Some(vec![Declaration::new(property, format!("{}({})", function, amount))])
+}
+
+/// 渐变插值修饰符：颜色空间 `oklch` → `in oklch`，色相插值 `longer` → `in oklch longer hue`
+fn gradient_interpolation(modifier: &str) -> Option<String> {
+    match modifier {
+        "srgb" | "hsl" | "oklab" | "oklch" => Some(format!("in {}", modifier)),
+        "longer" | "shorter" | "increasing" | "decreasing" => {
+            Some(format!("in oklch {} hue", modifier))
+        }
+        _ => None,
+    }
+}
+
+/// 是否为百分比值（`10%`、`12.5%`），渐变色标位置只接受百分比
+pub(super) fn is_percentage(value: &str) -> bool {
+    value
+        .strip_suffix('%')
+        .is_some_and(|n| !n.is_empty() && n.parse::<f64>().is_ok_and(|n| n >= 0.0))
+}
+
+/// 圆环偏移宽度：同时生成偏移阴影，颜色未设置时默认白色（与 Tailwind 的初始值一致）
+///
+/// `ring-offset-2` → `--tw-ring-offset-width: 2px; --tw-ring-offset-shadow: 0 0 0 var(--tw-ring-offset-width) …`
+pub(super) fn ring_offset_width(width: String) -> Vec<Declaration> {
+    vec![
+        Declaration::new("--tw-ring-offset-width", width),
+        Declaration::new(
+            "--tw-ring-offset-shadow",
+            "0 0 0 var(--tw-ring-offset-width) var(--tw-ring-offset-color, #fff)",
+        ),
+    ]
+}
+
+/// 斜切：每个轴的角度写入 `--tw-skew-x` / `--tw-skew-y`（`skew-6` 同时设置两轴），
+/// 再与旋转变量组合成 `transform`；未设置的变量以空值回退，不会使整个 `transform` 失效
+pub(super) fn skew_declarations(plugin: &str, angle: &str) -> Vec<Declaration> {
+    let axes: &[(&str, &str)] = match plugin {
+        "skew" => &[("x", "skewX"), ("y", "skewY")],
+        "skew-x" => &[("x", "skewX")],
+        _ => &[("y", "skewY")],
+    };
+    let mut declarations: Vec<Declaration> = axes
+        .iter()
+        .map(|(axis, function)| {
+            Declaration::new(format!("--tw-skew-{}", axis), format!("{}({})", function, angle))
+        })
+        .collect();
+    declarations.push(Declaration::new(
+        "transform",
+        "var(--tw-rotate-x,) var(--tw-rotate-y,) var(--tw-rotate-z,) var(--tw-skew-x,) var(--tw-skew-y,)",
+    ));
+    declarations
+}
+
+/// `space-x` / `space-y` 的外边距按 `--tw-space-*-reverse` 分到子元素的起止两侧：
+/// 默认写在末尾一侧，`space-x-reverse` 把它移到起始一侧（配合 `flex-row-reverse`）
+///
+/// `margin-inline: 0.5rem` → `--tw-space-x-reverse: 0`、
+/// `margin-inline-start: calc(0.5rem * var(--tw-space-x-reverse))`、
+/// `margin-inline-end: calc(0.5rem * calc(1 - var(--tw-space-x-reverse)))`
+pub(super) fn space_between_declarations(declarations: Vec<Declaration>) -> Vec<Declaration> {
+    declarations
+        .into_iter()
+        .flat_map(|decl| {
+            let axis = match decl.property.as_str() {
+                "margin-inline" => "x",
+                "margin-block" => "y",
+                _ => return vec![decl],
+            };
+            let reverse = format!("var(--tw-space-{}-reverse)", axis);
+            vec![
+                Declaration::new(format!("--tw-space-{}-reverse", axis), "0"),
+                Declaration::new(
+                    format!("{}-start", decl.property),
+                    format!("calc({} * {})", decl.value, reverse),
+                ),
+                Declaration::new(
+                    format!("{}-end", decl.property),
+                    format!("calc({} * calc(1 - {}))", decl.value, reverse),
+                ),
+            ]
+        })
+        .collect()
+}
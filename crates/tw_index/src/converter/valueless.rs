@@ -72,6 +72,10 @@ static VALUELESS_MAP: phf::Map<&'static str, (&'static str, &'static str)> = phf
     "capitalize" => ("text-transform", "capitalize"),
     "normal-case" => ("text-transform", "none"),
 
+    // Text overflow
+    "text-ellipsis" => ("text-overflow", "ellipsis"),
+    "text-clip" => ("text-overflow", "clip"),
+
     // Font style
     "italic" => ("font-style", "italic"),
     "not-italic" => ("font-style", "normal"),
@@ -127,24 +131,39 @@ static VALUELESS_MAP: phf::Map<&'static str, (&'static str, &'static str)> = phf
     // Box sizing
     "box-border" => ("box-sizing", "border-box"),
     "box-content" => ("box-sizing", "content-box"),
+
+    // Container（各断点的 max-width 由 Bundler 展开）
+    "container" => ("width", "100%"),
 };
 
+/// 所有无值类的完整类名（如 `flex-col`、`items-center`）
+pub(crate) fn valueless_names() -> impl Iterator<Item = &'static str> {
+    VALUELESS_MAP.keys().copied()
+}
+
 /// 为无值类构建声明
 ///
 /// 例如：`flex` → `display: flex`
 pub(super) fn build_valueless_declarations(parsed: &ParsedClass) -> Option<Vec<Declaration>> {
     // Multi-declaration valueless classes
-    match parsed.plugin.as_str() {
+    match parsed.plugin.as_ref() {
         "antialiased" => {
             return Some(vec![
                 Declaration::new("-webkit-font-smoothing", "antialiased"),
                 Declaration::new("-moz-osx-font-smoothing", "grayscale"),
             ])
         }
+        "truncate" => {
+            return Some(vec![
+                Declaration::new("overflow", "hidden"),
+                Declaration::new("text-overflow", "ellipsis"),
+                Declaration::new("white-space", "nowrap"),
+            ])
+        }
         _ => {}
     }
 
-    let &(property, value) = VALUELESS_MAP.get(parsed.plugin.as_str())?;
+    let &(property, value) = VALUELESS_MAP.get(parsed.plugin.as_ref())?;
     Some(vec![Declaration::new(property, value)])
 }
 
@@ -0,0 +1,368 @@
+//! 现代颜色函数的回退值
+//!
+//! 目标浏览器不支持 `oklch()` / `color-mix()` 时，在声明前插入预先计算的 hex 回退声明：
+//! 旧浏览器丢弃无法解析的后一条声明，保留 hex；新浏览器使用后一条。
+//! 自定义属性（`--*`）的值在使用时才校验，重复声明无法回退，由调用方改用
+//! `@supports` 包裹（见 [`Bundler::root_css_for`](crate::Bundler::root_css_for)）。
+
+use crate::palette::{get_color, oklch_to_rgb};
+use headwind_core::{ColorMode, Declaration, Targets};
+
+/// 开始支持 `oklch()` 的主版本：（chrome, firefox, safari），Safari 15.4 按 16 计
+const OKLCH_SINCE: (u32, u32, u32) = (111, 113, 16);
+/// 开始支持 `color-mix()` 的主版本，Safari 16.2 按 17 计
+const COLOR_MIX_SINCE: (u32, u32, u32) = (111, 113, 17);
+
+/// `@supports` 检测 `oklch()` 的条件
+pub const OKLCH_SUPPORTS: &str = "(color: oklch(0 0 0))";
+
+/// sRGB 三通道与 0-1 的透明度
+pub type Rgba = ([u8; 3], f32);
+
+/// 值中使用的颜色函数是否有目标浏览器不支持
+pub fn needs_color_fallback(value: &str, targets: &Targets) -> bool {
+    let below = |(chrome, firefox, safari): (u32, u32, u32)| targets.below(chrome, firefox, safari);
+    (value.contains("oklch(") && below(OKLCH_SINCE))
+        || (value.contains("color-mix(") && below(COLOR_MIX_SINCE))
+}
+
+/// 为使用了目标浏览器不支持的颜色函数的声明，在其前面插入 hex 回退声明；`targets` 为空时原样返回
+///
+/// 无法预先计算的值（如混合了自定义变量的 `color-mix()`）和自定义属性不加回退。
+pub fn add_color_fallbacks(declarations: Vec<Declaration>, targets: &Targets) -> Vec<Declaration> {
+    if targets.is_empty() {
+        return declarations;
+    }
+    let mut result = Vec::with_capacity(declarations.len());
+    for declaration in declarations {
+        if !declaration.property.starts_with("--")
+            && needs_color_fallback(&declaration.value, targets)
+        {
+            if let Some(value) = color_fallback(&declaration.value) {
+                result.push(Declaration::new(declaration.property.clone(), value));
+            }
+        }
+        result.push(declaration);
+    }
+    result
+}
+
+/// 把值中的 `oklch()` / `color-mix()` 替换为 hex；值中没有这两种函数或其中有无法计算的颜色时返回 None
+///
+/// `var(--color-*)` 按默认调色板解析：
+/// `color-mix(in oklab, var(--color-white) 60%, transparent)` → `#ffffff99`
+pub fn color_fallback(value: &str) -> Option<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    let mut replaced = false;
+    while let Some(start) = ["oklch(", "color-mix("]
+        .iter()
+        .filter_map(|function| rest.find(function))
+        .min()
+    {
+        let end = closing_paren(rest, start)? + 1;
+        out.push_str(&rest[..start]);
+        out.push_str(&to_hex(parse_color(&rest[start..end])?));
+        rest = &rest[end..];
+        replaced = true;
+    }
+    if !replaced {
+        return None;
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+/// `value[start..]` 中第一个括号对应的闭合括号位置
+fn closing_paren(value: &str, start: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in value[start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 解析单个 CSS 颜色值：hex（3/4/6/8 位）、`rgb()` / `rgba()`、`oklch()`、
+/// 与 `transparent` 混合的 `color-mix()`、默认调色板的 `var(--color-*)` 和 `transparent`
+///
+/// `#ffffff80` → `([255, 255, 255], 0.5)`（透明度约为 0.502）；其他颜色写法返回 None
+pub fn parse_color(color: &str) -> Option<Rgba> {
+    let color = color.trim();
+    if color == "transparent" {
+        return Some(([0, 0, 0], 0.0));
+    }
+    if let Some(hex) = color.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if let Some(name) = function_args(color, "var").and_then(|v| v.strip_prefix("--color-")) {
+        return parse_color(&get_color(name.trim(), ColorMode::Hex)?);
+    }
+    if let Some(args) = function_args(color, "rgb").or_else(|| function_args(color, "rgba")) {
+        return parse_rgb(args);
+    }
+    if let Some(args) = function_args(color, "oklch") {
+        return parse_oklch(args);
+    }
+    if let Some(args) = function_args(color, "color-mix") {
+        return parse_color_mix(args);
+    }
+    None
+}
+
+/// `name(args)` → `args`
+fn function_args<'a>(color: &'a str, name: &str) -> Option<&'a str> {
+    color
+        .strip_prefix(name)?
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+/// 不带 `#` 的 3/4/6/8 位十六进制颜色
+pub fn parse_hex(hex: &str) -> Option<Rgba> {
+    let expanded: String = match hex.len() {
+        3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => hex.to_string(),
+        _ => return None,
+    };
+    let byte = |i: usize| u8::from_str_radix(expanded.get(i..i + 2)?, 16).ok();
+    let alpha = match expanded.len() {
+        8 => f32::from(byte(6)?) / 255.0,
+        _ => 1.0,
+    };
+    Some(([byte(0)?, byte(2)?, byte(4)?], alpha))
+}
+
+/// `255 0 0 / 50%` 或旧式的 `255, 0, 0, 0.5`，通道也可以是百分比
+fn parse_rgb(args: &str) -> Option<Rgba> {
+    let (channels, alpha) = match args.split_once('/') {
+        Some((channels, alpha)) => (channels, Some(alpha)),
+        None => (args, None),
+    };
+    let mut channels: Vec<&str> = channels
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    let alpha = match (alpha, channels.len()) {
+        (Some(alpha), 3) => parse_alpha(alpha)?,
+        (None, 4) => parse_alpha(channels.pop()?)?,
+        (None, 3) => 1.0,
+        _ => return None,
+    };
+    let channel = |value: &str| Some(parse_number(value, 2.55)?.round().clamp(0.0, 255.0) as u8);
+    Some(([channel(channels[0])?, channel(channels[1])?, channel(channels[2])?], alpha))
+}
+
+/// `0.623 0.214 259.815 / 60%`，亮度与色度也可以是百分比（色度 100% = 0.4）
+fn parse_oklch(args: &str) -> Option<Rgba> {
+    let (channels, alpha) = match args.split_once('/') {
+        Some((channels, alpha)) => (channels, parse_alpha(alpha)?),
+        None => (args, 1.0),
+    };
+    let channels: Vec<&str> = channels.split_whitespace().collect();
+    let [l, c, h] = channels.as_slice() else {
+        return None;
+    };
+    let l = parse_number(l, 0.01)?;
+    let c = parse_number(c, 0.004)?;
+    let h = parse_number(h.strip_suffix("deg").unwrap_or(h), 1.0)?;
+    Some((oklch_to_rgb(l, c, h).into(), alpha))
+}
+
+/// 数值或百分比（乘以 `percent_scale`），`none` 按 0 处理
+fn parse_number(value: &str, percent_scale: f32) -> Option<f32> {
+    if value == "none" {
+        return Some(0.0);
+    }
+    match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok().map(|n| n * percent_scale),
+        None => value.parse().ok(),
+    }
+}
+
+fn parse_alpha(alpha: &str) -> Option<f32> {
+    Some(parse_number(alpha.trim(), 0.01)?.clamp(0.0, 1.0))
+}
+
+/// `in oklab, <color> [p%], <color> [q%]`，只支持与 `transparent` 混合（Tailwind 透明度修饰符的写法）
+fn parse_color_mix(args: &str) -> Option<Rgba> {
+    let parts = split_top_level(args, |c| c == ',');
+    let [space, first, second] = parts.as_slice() else {
+        return None;
+    };
+    if !space.trim_start().starts_with("in ") {
+        return None;
+    }
+    let (first, first_pct) = split_percentage(first);
+    let (second, second_pct) = split_percentage(second);
+    let (color, color_pct, other_pct) = match (first, second) {
+        (color, "transparent") => (color, first_pct, second_pct),
+        ("transparent", color) => (color, second_pct, first_pct),
+        _ => return None,
+    };
+    let (color_pct, other_pct) = match (color_pct, other_pct) {
+        (Some(p), Some(q)) => (p, q),
+        (Some(p), None) => (p, 100.0 - p),
+        (None, Some(q)) => (100.0 - q, q),
+        (None, None) => (50.0, 50.0),
+    };
+    let (rgb, alpha) = parse_color(color)?;
+    // 与透明色混合只改变透明度；两者比例之和小于 100% 时整体透明度按比例降低
+    Some((rgb, alpha * color_pct / (color_pct + other_pct).max(100.0)))
+}
+
+/// `#fff 60%` → (`#fff`, Some(60.0))
+fn split_percentage(part: &str) -> (&str, Option<f32>) {
+    let part = part.trim();
+    if let Some((color, pct)) = part.rsplit_once(' ') {
+        if let Some(n) = pct.strip_suffix('%').and_then(|n| n.parse().ok()) {
+            return (color.trim(), Some(n));
+        }
+    }
+    (part, None)
+}
+
+/// 按括号外的分隔符切分，各部分不做修剪：
+/// `split_top_level("0 1px rgb(0 0 0), 0 2px red", |c| c == ',')` → `["0 1px rgb(0 0 0)", " 0 2px red"]`
+pub fn split_top_level(value: &str, separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && separator(c) => {
+                parts.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+fn to_hex(([r, g, b], alpha): Rgba) -> String {
+    if alpha >= 1.0 {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    } else {
+        let a = (alpha * 255.0).round() as u8;
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_fallback_values() {
+        let blue = get_color("blue-500", ColorMode::Hex).unwrap();
+        assert_eq!(
+            color_fallback("oklch(0.623 0.214 259.815)").as_deref(),
+            Some(blue.as_str())
+        );
+        assert_eq!(
+            color_fallback("oklch(62.3% 53.5% 259.815deg)").as_deref(),
+            Some(blue.as_str())
+        );
+        assert_eq!(
+            color_fallback("oklch(1 0 0 / 60%) !important").as_deref(),
+            Some("#ffffff99 !important")
+        );
+        assert_eq!(
+            color_fallback("color-mix(in oklab, var(--color-white) 60%, transparent)").as_deref(),
+            Some("#ffffff99")
+        );
+        assert_eq!(
+            color_fallback("color-mix(in oklab, #000 50%, transparent)").as_deref(),
+            Some("#00000080")
+        );
+        assert_eq!(
+            color_fallback("0 1px 2px oklch(0 0 0 / 0.05)").as_deref(),
+            Some("0 1px 2px #0000000d")
+        );
+        // 无法预先计算或不含颜色函数
+        assert_eq!(
+            color_fallback("color-mix(in oklab, var(--brand) 50%, transparent)"),
+            None
+        );
+        assert_eq!(color_fallback("color-mix(in oklab, red, blue)"), None);
+        assert_eq!(
+            color_fallback("linear-gradient(to right in oklch, red, blue)"),
+            None
+        );
+        assert_eq!(color_fallback("#3b82f6"), None);
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#fff"), Some(([255, 255, 255], 1.0)));
+        assert_eq!(parse_color("#0008"), Some(([0, 0, 0], 136.0 / 255.0)));
+        assert_eq!(parse_color("#3b82f6ff"), Some(([59, 130, 246], 1.0)));
+        assert_eq!(parse_color("rgb(59 130 246)"), Some(([59, 130, 246], 1.0)));
+        assert_eq!(parse_color("rgb(100% 0% 0% / 50%)"), Some(([255, 0, 0], 0.5)));
+        assert_eq!(parse_color("rgba(0, 0, 0, 0.25)"), Some(([0, 0, 0], 0.25)));
+        for color in ["#12345", "rgb(1 2)", "rgb(1 2 3 4 / 1)", "red"] {
+            assert_eq!(parse_color(color), None, "{}", color);
+        }
+    }
+
+    #[test]
+    fn test_fallback_declarations_follow_targets() {
+        let declarations = vec![
+            Declaration::new("color", "oklch(1 0 0 / 60%)"),
+            Declaration::new("--tw-ring-color", "oklch(1 0 0)"),
+            Declaration::new(
+                "background-color",
+                "color-mix(in oklab, var(--color-black) 10%, transparent)",
+            ),
+        ];
+        let old_safari = Targets {
+            safari: Some(15),
+            ..Default::default()
+        };
+        let with_fallbacks = add_color_fallbacks(declarations.clone(), &old_safari);
+        assert_eq!(with_fallbacks.len(), 5);
+        assert_eq!(with_fallbacks[0], Declaration::new("color", "#ffffff99"));
+        assert_eq!(with_fallbacks[1], declarations[0]);
+        assert_eq!(with_fallbacks[2], declarations[1]);
+        assert_eq!(
+            with_fallbacks[3],
+            Declaration::new("background-color", "#0000001a")
+        );
+
+        // Safari 16 支持 oklch()，但不支持 color-mix()
+        let safari_16 = Targets {
+            safari: Some(16),
+            ..Default::default()
+        };
+        assert_eq!(
+            add_color_fallbacks(declarations.clone(), &safari_16).len(),
+            4
+        );
+
+        let modern = Targets {
+            chrome: Some(120),
+            safari: Some(17),
+            ..Default::default()
+        };
+        assert_eq!(
+            add_color_fallbacks(declarations.clone(), &modern),
+            declarations
+        );
+        assert_eq!(
+            add_color_fallbacks(declarations.clone(), &Targets::default()),
+            declarations
+        );
+    }
+}
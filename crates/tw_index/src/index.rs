@@ -1,11 +1,23 @@
 use headwind_core::Declaration;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
 /// Tailwind 类名索引
 ///
 /// 提供从类名到 CSS 声明的映射
 pub struct TailwindIndex {
     map: HashMap<String, Vec<Declaration>>,
+    /// 内容指纹：各条目哈希的异或，与插入顺序无关，用作转换缓存键
+    fingerprint: u64,
+}
+
+/// 单个条目的哈希
+fn entry_hash(class: &str, decls: &[Declaration]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    class.hash(&mut hasher);
+    decls.hash(&mut hasher);
+    hasher.finish()
 }
 
 impl TailwindIndex {
@@ -13,14 +25,24 @@ impl TailwindIndex {
     pub fn new() -> Self {
         Self {
             map: HashMap::new(),
+            fingerprint: 0,
         }
     }
 
     /// 插入类名和对应的 CSS 声明
     pub fn insert(&mut self, class: String, decls: Vec<Declaration>) {
+        self.fingerprint ^= entry_hash(&class, &decls);
+        if let Some(old) = self.map.get(&class) {
+            self.fingerprint ^= entry_hash(&class, old);
+        }
         self.map.insert(class, decls);
     }
 
+    /// 内容指纹，内容相同的索引指纹相同
+    pub(crate) fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
     /// 查询类名对应的 CSS 声明
     pub fn lookup(&self, class: &str) -> Option<&[Declaration]> {
         self.map.get(class).map(|v| v.as_slice())
@@ -86,4 +108,20 @@ mod tests {
         assert_eq!(index.len(), 1);
         assert!(!index.is_empty());
     }
+
+    #[test]
+    fn test_index_fingerprint_tracks_content() {
+        let mut a = TailwindIndex::new();
+        a.insert("p-4".to_string(), vec![Declaration::new("padding", "1rem")]);
+        a.insert("m-2".to_string(), vec![Declaration::new("margin", "0.5rem")]);
+
+        let mut b = TailwindIndex::new();
+        b.insert("m-2".to_string(), vec![Declaration::new("margin", "0.5rem")]);
+        b.insert("p-4".to_string(), vec![Declaration::new("padding", "2rem")]);
+        assert_ne!(a.fingerprint(), b.fingerprint());
+
+        // 覆盖后与插入顺序无关
+        b.insert("p-4".to_string(), vec![Declaration::new("padding", "1rem")]);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+    }
 }
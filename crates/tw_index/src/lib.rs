@@ -1,8 +1,10 @@
 pub mod bundle;
 pub mod bundler;
+pub mod cache;
 pub mod context;
 pub mod converter;
 pub mod css;
+pub mod fallback;
 pub mod index;
 pub mod loader;
 pub mod merge;
@@ -10,18 +12,31 @@ pub mod naming;
 pub mod normalize;
 pub mod palette;
 pub mod plugin_map;
+pub mod prefix;
 pub mod shorthand;
+pub mod suggest;
 pub mod theme_values;
+pub mod units;
+pub mod utility;
 pub mod value_map;
 pub mod variant;
 
 // Re-export main types
-pub use bundle::TailwindIndexLookup;
+pub use bundle::{load_versioned_index, TailwindIndexLookup, VersionedIndex};
 pub use bundler::{Bundler, RuleGroup};
+pub use cache::{CacheStats, ConversionCache};
 pub use context::ClassContext;
 pub use converter::{Converter, CssRule};
 pub use index::TailwindIndex;
-pub use loader::{load_from_json, load_from_official_json};
+pub use utility::{CustomUtilities, CustomUtility};
+pub use variant::CustomVariant;
+pub use suggest::Suggestion;
+pub use prefix::add_vendor_prefixes;
+pub use fallback::add_color_fallbacks;
+pub use units::convert_lengths;
+pub use loader::{
+    load_custom_utilities, load_custom_variants, load_from_json, load_from_official_json, load_index,
+};
 pub use headwind_core::ColorMode;
 
 // Implement TailwindIndexLookup for integration with bundle
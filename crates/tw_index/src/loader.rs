@@ -1,4 +1,6 @@
 use crate::index::TailwindIndex;
+use crate::utility::CustomUtility;
+use crate::variant::{matching_close, CustomVariant};
 use headwind_core::Declaration;
 use serde::Deserialize;
 
@@ -23,6 +25,14 @@ struct OfficialMapping {
     source: Option<String>,
 }
 
+/// 两种格式的条目，可在同一个文件中混用
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AnyMapping {
+    Declarations(ClassMapping),
+    Official(OfficialMapping),
+}
+
 /// 解析 CSS 声明字符串，如 "text-indent: -1px" 或 "padding: 1rem; margin: 2rem"
 ///
 /// 返回解析出的 Declaration 列表
@@ -110,6 +120,128 @@ pub fn load_from_official_json(json_str: &str) -> Result<TailwindIndex, serde_js
     Ok(index)
 }
 
+/// 从 JSON 字符串加载 Tailwind 索引，自动识别条目格式
+///
+/// 每个条目可以是 `load_from_json` 的 `declarations` 格式，
+/// 也可以是 `load_from_official_json` 的 `css` 格式。
+/// 用于加载用户提供的数据集（如从官方文档或 IntelliSense 导出的映射）作为转换器的回退索引。
+pub fn load_index(json_str: &str) -> Result<TailwindIndex, serde_json::Error> {
+    let mappings: Vec<AnyMapping> = serde_json::from_str(json_str)?;
+
+    let mut index = TailwindIndex::new();
+
+    for mapping in mappings {
+        let (class, declarations) = match mapping {
+            AnyMapping::Declarations(mapping) => (
+                mapping.class,
+                mapping
+                    .declarations
+                    .into_iter()
+                    .map(|d| Declaration::new(d.property, d.value))
+                    .collect(),
+            ),
+            AnyMapping::Official(mapping) => (mapping.class, parse_css_declarations(&mapping.css)),
+        };
+
+        if !declarations.is_empty() {
+            index.insert(class, declarations);
+        }
+    }
+
+    Ok(index)
+}
+
+/// 从项目的 Tailwind CSS 中读取 `@custom-variant` 指令，返回 (变体名, 定义)
+///
+/// 支持简写形式 `@custom-variant dark (&:where(.dark, .dark *));` 与以 `@slot;` 结尾的块形式；
+/// 其余 CSS（`@theme`、`@import`、普通规则）忽略，注释中的指令不生效。
+///
+/// ```
+/// # use headwind_tw_index::load_custom_variants;
+/// let css = "@import 'tailwindcss';\n@custom-variant dark (&:where(.dark, .dark *));";
+/// let variants = load_custom_variants(css).unwrap();
+/// assert_eq!(variants[0].0, "dark");
+/// assert_eq!(variants[0].1.apply(".c"), ".c:where(.dark, .dark *)");
+/// ```
+pub fn load_custom_variants(css: &str) -> Result<Vec<(String, CustomVariant)>, String> {
+    const DIRECTIVE: &str = "@custom-variant";
+    let css = strip_css_comments(css);
+    let mut variants = Vec::new();
+    let mut rest = css.as_str();
+
+    while let Some(pos) = rest.find(DIRECTIVE) {
+        let after = rest[pos + DIRECTIVE.len()..].trim_start();
+        let name_end = after
+            .find(|c: char| c.is_whitespace() || c == '(' || c == '{')
+            .unwrap_or(after.len());
+        let name = &after[..name_end];
+        if name.is_empty() {
+            return Err("@custom-variant 缺少变体名".to_string());
+        }
+        let definition = after[name_end..].trim_start();
+        let end = match definition.as_bytes().first() {
+            Some(b'(' | b'{') => matching_close(definition, 0)
+                .ok_or_else(|| format!("@custom-variant {} 的括号不匹配", name))?,
+            _ => return Err(format!("@custom-variant {} 缺少定义", name)),
+        };
+        let variant = CustomVariant::parse(&definition[..=end])
+            .map_err(|e| format!("@custom-variant {} 无效: {}", name, e))?;
+        variants.push((name.to_string(), variant));
+        rest = &definition[end + 1..];
+    }
+
+    Ok(variants)
+}
+
+/// 从项目的 Tailwind CSS 中读取 `@utility` 指令，返回 (名称模式, 定义)
+///
+/// 名称以 `-*` 结尾的是函数式工具类，声明值中的 `--value(…)` 按类名中的值展开：
+/// `@utility tab-* { tab-size: --value(integer); }` 让 `tab-4` 输出 `tab-size: 4`
+pub fn load_custom_utilities(css: &str) -> Result<Vec<(String, CustomUtility)>, String> {
+    const DIRECTIVE: &str = "@utility";
+    let css = strip_css_comments(css);
+    let mut utilities = Vec::new();
+    let mut rest = css.as_str();
+
+    while let Some(pos) = rest.find(DIRECTIVE) {
+        let after = rest[pos + DIRECTIVE.len()..].trim_start();
+        let name_end = after
+            .find(|c: char| c.is_whitespace() || c == '{')
+            .unwrap_or(after.len());
+        let name = &after[..name_end];
+        if name.is_empty() {
+            return Err("@utility 缺少名称".to_string());
+        }
+        let body = after[name_end..].trim_start();
+        if !body.starts_with('{') {
+            return Err(format!("@utility {} 缺少声明块", name));
+        }
+        let end = matching_close(body, 0)
+            .ok_or_else(|| format!("@utility {} 的括号不匹配", name))?;
+        let utility = CustomUtility::parse(&body[1..end])
+            .map_err(|e| format!("@utility {} 无效: {}", name, e))?;
+        utilities.push((name.to_string(), utility));
+        rest = &body[end + 1..];
+    }
+
+    Ok(utilities)
+}
+
+/// 去掉 CSS 中的 `/* … */` 注释
+fn strip_css_comments(css: &str) -> String {
+    let mut stripped = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,4 +368,72 @@ mod tests {
         assert_eq!(indent_decls[0].property, "text-indent");
         assert_eq!(indent_decls[0].value, "-1px");
     }
+
+    #[test]
+    fn test_load_index_detects_both_formats() {
+        let json = r#"[
+            { "class": "p-4", "declarations": [{ "property": "padding", "value": "1rem" }] },
+            { "class": "absolute", "css": "position: absolute", "source": "/src/docs/position.mdx" },
+            { "class": "empty", "css": "" }
+        ]"#;
+
+        let index = load_index(json).unwrap();
+
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.lookup("p-4").unwrap()[0].value, "1rem");
+        assert_eq!(index.lookup("absolute").unwrap()[0].property, "position");
+        assert!(load_index(r#"[{ "class": "p-4" }]"#).is_err());
+    }
+
+    #[test]
+    fn test_load_custom_variants() {
+        let css = r#"
+@import "tailwindcss";
+/* @custom-variant ignored (&:hover); */
+@custom-variant dark (&:where(.dark, .dark *));
+@custom-variant pointer-coarse (@media (pointer: coarse));
+@custom-variant theme-midnight {
+  &:where([data-theme="midnight"] *) {
+    @slot;
+  }
+}
+@theme { --color-brand: #f50; }
+"#;
+        let variants = load_custom_variants(css).unwrap();
+        let names: Vec<_> = variants.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["dark", "pointer-coarse", "theme-midnight"]);
+        assert_eq!(variants[1].1.at_rules, ["@media (pointer: coarse)"]);
+        assert_eq!(
+            variants[2].1.apply(".c"),
+            r#".c:where([data-theme="midnight"] *)"#
+        );
+
+        assert!(load_custom_variants("@custom-variant broken (&:hover").is_err());
+        assert!(load_custom_variants("@custom-variant (&:hover);").is_err());
+        assert!(load_custom_variants("@custom-variant bad (.no-ampersand);").is_err());
+    }
+
+    #[test]
+    fn test_load_custom_utilities() {
+        let css = r#"
+@import "tailwindcss";
+@utility content-auto {
+  content-visibility: auto;
+}
+@utility tab-* {
+  tab-size: --value(integer);
+}
+/* @utility ignored { color: red; } */
+"#;
+        let utilities = load_custom_utilities(css).unwrap();
+        let names: Vec<_> = utilities.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["content-auto", "tab-*"]);
+        assert_eq!(
+            utilities[0].1.declarations,
+            [Declaration::new("content-visibility", "auto")]
+        );
+
+        assert!(load_custom_utilities("@utility broken { color: red;").is_err());
+        assert!(load_custom_utilities("@utility nested { &:hover { color: red; } }").is_err());
+    }
 }
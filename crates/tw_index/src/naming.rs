@@ -1,26 +1,175 @@
-use headwind_core::NamingMode;
+use headwind_core::{HashVersion, NamingMode};
+
+/// Hash 命名默认的 hash 位数（十六进制字符数）
+pub const DEFAULT_HASH_LENGTH: usize = 12;
+/// hash 位数上限（blake3 输出的完整十六进制长度）
+pub const MAX_HASH_LENGTH: usize = 64;
 
 /// 命名策略 trait
 pub trait NamingStrategy {
     fn generate_name(&self, classes: &[String]) -> String;
+
+    /// 按指定 hash 位数生成名称，供冲突时加长 hash 使用；不含 hash 的策略忽略该参数
+    fn generate_name_with_hash_length(&self, classes: &[String], hash_length: usize) -> String {
+        let _ = hash_length;
+        self.generate_name(classes)
+    }
 }
 
-/// Hash 命名策略：基于类名内容生成稳定 hash
+/// 计算 `NamingMode::Hash` 使用的类名：`c_` + 12 位十六进制 hash
+///
+/// 算法由 `version` 固定（见 [`HashVersion`]），结果跨平台、跨发布版本稳定：
+///
+/// ```
+/// use headwind_core::HashVersion;
+/// use headwind_tw_index::naming::class_hash;
+///
+/// let classes = vec!["p-4".to_string(), "m-2".to_string()];
+/// let swapped = vec!["m-2".to_string(), "p-4".to_string()];
+///
+/// // V2 与类名书写顺序无关，盐不同则结果不同
+/// assert_eq!(
+///     class_hash(&classes, "", HashVersion::V2),
+///     class_hash(&swapped, "", HashVersion::V2),
+/// );
+/// assert_ne!(
+///     class_hash(&classes, "", HashVersion::V2),
+///     class_hash(&classes, "inline", HashVersion::V2),
+/// );
+/// ```
+pub fn class_hash(classes: &[String], salt: &str, version: HashVersion) -> String {
+    class_hash_with_length(classes, salt, version, DEFAULT_HASH_LENGTH)
+}
+
+/// 同 [`class_hash`]，hash 取前 `length` 位（1 到 [`MAX_HASH_LENGTH`]）
+///
+/// 较短的名称是较长名称的前缀，加长 hash 不改变已分配名称的含义：
+///
+/// ```
+/// use headwind_core::HashVersion;
+/// use headwind_tw_index::naming::{class_hash, class_hash_with_length};
+///
+/// let classes = vec!["p-4".to_string()];
+/// let short = class_hash_with_length(&classes, "", HashVersion::V1, 6);
+/// assert_eq!(short.len(), "c_".len() + 6);
+/// assert!(class_hash(&classes, "", HashVersion::V1).starts_with(&short));
+/// ```
+pub fn class_hash_with_length(
+    classes: &[String],
+    salt: &str,
+    version: HashVersion,
+    length: usize,
+) -> String {
+    let input = match version {
+        // 将所有类名连接，用空格分隔（因为已经规范化过）
+        HashVersion::V1 => classes.join(" "),
+        HashVersion::V2 => {
+            let mut canonical: Vec<&str> = classes.iter().map(String::as_str).collect();
+            canonical.sort_unstable();
+            canonical.dedup();
+            format!("{}\0{}", canonical.join("\n"), salt)
+        }
+    };
+
+    // 使用 blake3 计算 hash
+    let hash = blake3::hash(input.as_bytes());
+
+    // 取前 length 位十六进制（默认 12 位，即 6 个字节）
+    let hex = format!("{}", hash);
+    let short_hash = &hex[..length.clamp(1, MAX_HASH_LENGTH)];
+
+    format!("c_{}", short_hash)
+}
+
+/// Hash 命名策略：基于类名内容生成稳定 hash（`HashVersion::V1`）
 pub struct HashNaming;
 
 impl NamingStrategy for HashNaming {
     fn generate_name(&self, classes: &[String]) -> String {
-        // 将所有类名连接，用空格分隔（因为已经规范化过）
-        let input = classes.join(" ");
+        class_hash(classes, "", HashVersion::V1)
+    }
+
+    fn generate_name_with_hash_length(&self, classes: &[String], hash_length: usize) -> String {
+        class_hash_with_length(classes, "", HashVersion::V1, hash_length)
+    }
+}
+
+/// 指定 hash 版本与盐的 Hash 命名策略
+pub struct VersionedHashNaming {
+    pub version: HashVersion,
+    pub salt: String,
+}
+
+impl NamingStrategy for VersionedHashNaming {
+    fn generate_name(&self, classes: &[String]) -> String {
+        class_hash(classes, &self.salt, self.version)
+    }
+
+    fn generate_name_with_hash_length(&self, classes: &[String], hash_length: usize) -> String {
+        class_hash_with_length(classes, &self.salt, self.version, hash_length)
+    }
+}
 
-        // 使用 blake3 计算 hash
-        let hash = blake3::hash(input.as_bytes());
+/// Readable / CamelCase 名称超过该字符数时截断并追加 hash
+const MAX_NAME_CHARS: usize = 32;
+/// 截断后保留的字符数
+const TRUNCATED_NAME_CHARS: usize = 24;
 
-        // 取前 6 个字节的十六进制表示
-        let hex = format!("{}", hash);
-        let short_hash = &hex[..12];
+/// 可读名称中保留的字符：字母与数字（含 CJK 等非 ASCII 文字）及下划线
+///
+/// 生成的名称无需转义即可用作类选择器与 CSS Modules 的键。
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
 
-        format!("c_{}", short_hash)
+/// 小数点与分数斜杠映射为字母（`p-1.5` → `p1d5`、`w-1/2` → `w1s2`），不与 `p-15`、`w-12` 同名
+fn mapped_char(c: char) -> Option<char> {
+    match c {
+        '.' => Some('d'),
+        '/' => Some('s'),
+        _ => None,
+    }
+}
+
+/// 不进入名称的字符：任意值的方括号与引号、`#`、`!`、emoji 等在 CSS 选择器和 JS 标识符中都需要转义，
+/// 直接丢弃；`-` 与变体冒号作为分隔，不算丢弃
+fn is_dropped_char(c: char) -> bool {
+    !is_name_char(c) && mapped_char(c).is_none() && c != '-' && c != ':'
+}
+
+/// 6 位 blake3 hash
+fn short_hash(input: &str) -> String {
+    format!("{}", blake3::hash(input.as_bytes()))[..6].to_string()
+}
+
+/// 超过 [`MAX_NAME_CHARS`] 个字符时截取前 [`TRUNCATED_NAME_CHARS`] 个字符，经 `separator` 追加 6 位 hash。
+/// 按字符而不是字节截取，多字节字符不会被切断
+fn shorten(combined: String, separator: &str) -> String {
+    if combined.chars().count() <= MAX_NAME_CHARS {
+        return combined;
+    }
+    let truncated: String = combined.chars().take(TRUNCATED_NAME_CHARS).collect();
+    format!("{}{}{}", truncated, separator, short_hash(&combined))
+}
+
+/// 名称为空（所有字符都被丢弃）时退回 hash 名。
+///
+/// 类中有字符被丢弃时经 `separator` 追加类组合的 6 位 hash，只差这些字符的类
+/// （`bg-[#fff]` 与 `bg-[fff]`）不会同名；以数字开头时加 `_` 前缀，保证是合法的类选择器
+fn finish_name(name: String, classes: &[String], separator: &str) -> String {
+    if name.is_empty() {
+        return class_hash(classes, "", HashVersion::V1);
+    }
+    let name = if classes.iter().any(|class| class.chars().any(is_dropped_char)) {
+        format!("{}{}{}", name, separator, short_hash(&classes.join(" ")))
+    } else {
+        name
+    };
+    let name = shorten(name, separator);
+    if name.starts_with(|c: char| c.is_numeric()) {
+        format!("_{}", name)
+    } else {
+        name
     }
 }
 
@@ -28,15 +177,18 @@ impl NamingStrategy for HashNaming {
 pub struct ReadableNaming;
 
 impl ReadableNaming {
-    /// 从 Tailwind 类名中提取可读前缀
+    /// 从 Tailwind 类名中提取可读前缀：变体冒号写作 `_`，`.` / `/` 按 [`mapped_char`] 映射，
+    /// 保留其中的前 8 个字符
     fn extract_prefix(class: &str) -> String {
-        let cleaned = class.replace('-', "");
-
-        if cleaned.len() > 8 {
-            cleaned[..8].to_string()
-        } else {
-            cleaned
-        }
+        class
+            .chars()
+            .filter_map(|c| match c {
+                ':' => Some('_'),
+                c if is_name_char(c) => Some(c),
+                c => mapped_char(c),
+            })
+            .take(8)
+            .collect()
     }
 }
 
@@ -46,18 +198,13 @@ impl NamingStrategy for ReadableNaming {
             return "empty".to_string();
         }
 
-        let prefixes: Vec<String> = classes.iter().map(|c| Self::extract_prefix(c)).collect();
-
-        let combined = prefixes.join("_");
+        let prefixes: Vec<String> = classes
+            .iter()
+            .map(|c| Self::extract_prefix(c))
+            .filter(|prefix| !prefix.is_empty())
+            .collect();
 
-        if combined.len() > 32 {
-            let truncated = &combined[..24];
-            let hash = blake3::hash(combined.as_bytes());
-            let hex = format!("{}", hash);
-            format!("{}_{}", truncated, &hex[..6])
-        } else {
-            combined
-        }
+        finish_name(prefixes.join("_"), classes, "_")
     }
 }
 
@@ -65,13 +212,17 @@ impl NamingStrategy for ReadableNaming {
 pub struct CamelCaseNaming;
 
 impl CamelCaseNaming {
-    /// 将单个 Tailwind 类转换为 camelCase 片段
+    /// 将单个 Tailwind 类转换为 camelCase 片段：`.` / `/` 按 [`mapped_char`] 映射，
+    /// 其余名称字符以外的字符（`-`、`:`、`[`、`_` 等）作为单词分隔
     fn class_to_camel(class: &str) -> String {
         let mut result = String::new();
         let mut capitalize_next = false;
 
         for ch in class.chars() {
-            if ch == '-' || ch == ':' {
+            if let Some(mapped) = mapped_char(ch) {
+                result.push(mapped);
+                capitalize_next = false;
+            } else if ch == '_' || !is_name_char(ch) {
                 capitalize_next = true;
             } else if capitalize_next {
                 result.extend(ch.to_uppercase());
@@ -93,9 +244,9 @@ impl NamingStrategy for CamelCaseNaming {
 
         let mut combined = String::new();
 
-        for (i, class) in classes.iter().enumerate() {
+        for class in classes {
             let camel = Self::class_to_camel(class);
-            if i == 0 {
+            if combined.is_empty() {
                 combined.push_str(&camel);
             } else {
                 let mut chars = camel.chars();
@@ -106,21 +257,57 @@ impl NamingStrategy for CamelCaseNaming {
             }
         }
 
-        if combined.len() > 32 {
-            let truncated = &combined[..24];
-            let hash = blake3::hash(combined.as_bytes());
-            let hex = format!("{}", hash);
-            format!("{}{}", truncated, &hex[..6])
-        } else {
-            combined
+        finish_name(combined, classes, "")
+    }
+}
+
+/// 作用域命名策略：在内部策略生成的名称后追加作用域（如文件路径）的短 hash，
+/// 使相同类组合在不同作用域中得到不同名称
+pub struct ScopedNaming {
+    inner: Box<dyn NamingStrategy>,
+    scope_hash: String,
+}
+
+impl ScopedNaming {
+    pub fn new(inner: Box<dyn NamingStrategy>, scope: &str) -> Self {
+        let hex = format!("{}", blake3::hash(scope.as_bytes()));
+        Self {
+            inner,
+            scope_hash: hex[..6].to_string(),
         }
     }
 }
 
+impl NamingStrategy for ScopedNaming {
+    fn generate_name(&self, classes: &[String]) -> String {
+        format!("{}_{}", self.inner.generate_name(classes), self.scope_hash)
+    }
+
+    fn generate_name_with_hash_length(&self, classes: &[String], hash_length: usize) -> String {
+        let name = self.inner.generate_name_with_hash_length(classes, hash_length);
+        format!("{}_{}", name, self.scope_hash)
+    }
+}
+
 /// 根据 NamingMode 创建对应的策略
 pub fn create_naming_strategy(mode: NamingMode) -> Box<dyn NamingStrategy> {
+    create_versioned_naming_strategy(mode, HashVersion::V1, "")
+}
+
+/// 根据 NamingMode 创建对应的策略，Hash 模式使用指定的 hash 版本与盐
+pub fn create_versioned_naming_strategy(
+    mode: NamingMode,
+    version: HashVersion,
+    salt: &str,
+) -> Box<dyn NamingStrategy> {
     match mode {
-        NamingMode::Hash => Box::new(HashNaming),
+        NamingMode::Hash => match version {
+            HashVersion::V1 => Box::new(HashNaming),
+            _ => Box::new(VersionedHashNaming {
+                version,
+                salt: salt.to_string(),
+            }),
+        },
         NamingMode::Readable => Box::new(ReadableNaming),
         NamingMode::CamelCase => Box::new(CamelCaseNaming),
         NamingMode::Semantic => {
@@ -161,6 +348,76 @@ mod tests {
         );
     }
 
+    /// 黄金值：任何改动导致这些断言失败，都意味着已发布的类名发生变化，
+    /// 必须以新的 HashVersion 引入，而不是修改旧版本
+    #[test]
+    fn test_class_hash_golden_v1() {
+        let a = vec!["p-4".to_string(), "m-2".to_string()];
+        let b = vec![
+            "flex".to_string(),
+            "items-center".to_string(),
+            "hover:bg-blue-500".to_string(),
+        ];
+        assert_eq!(class_hash(&a, "", HashVersion::V1), "c_728afc693773");
+        assert_eq!(class_hash(&b, "", HashVersion::V1), "c_ab3dfa16d2cc");
+        // V1 忽略盐
+        assert_eq!(class_hash(&a, "inline", HashVersion::V1), "c_728afc693773");
+        assert_eq!(HashNaming.generate_name(&a), "c_728afc693773");
+    }
+
+    #[test]
+    fn test_class_hash_golden_v2() {
+        let a = vec!["p-4".to_string(), "m-2".to_string()];
+        let b = vec![
+            "flex".to_string(),
+            "items-center".to_string(),
+            "hover:bg-blue-500".to_string(),
+        ];
+        assert_eq!(class_hash(&a, "", HashVersion::V2), "c_a7af5e1b08b2");
+        assert_eq!(class_hash(&b, "", HashVersion::V2), "c_b8d8c66b5a17");
+        assert_eq!(class_hash(&a, "inline", HashVersion::V2), "c_c2a6d93a266f");
+
+        // 顺序与重复不影响结果
+        let shuffled = vec!["m-2".to_string(), "p-4".to_string(), "m-2".to_string()];
+        assert_eq!(class_hash(&shuffled, "", HashVersion::V2), "c_a7af5e1b08b2");
+    }
+
+    #[test]
+    fn test_hash_length() {
+        let classes = vec!["p-4".to_string(), "m-2".to_string()];
+        assert_eq!(
+            HashNaming.generate_name_with_hash_length(&classes, DEFAULT_HASH_LENGTH),
+            "c_728afc693773"
+        );
+        assert_eq!(
+            HashNaming.generate_name_with_hash_length(&classes, 8),
+            "c_728afc69"
+        );
+        assert_eq!(
+            HashNaming.generate_name_with_hash_length(&classes, 100).len(),
+            "c_".len() + MAX_HASH_LENGTH
+        );
+        // 不含 hash 的策略忽略位数
+        assert_eq!(ReadableNaming.generate_name_with_hash_length(&classes, 4), "p4_m2");
+        let scoped = ScopedNaming::new(Box::new(HashNaming), "src/A.tsx");
+        assert!(scoped
+            .generate_name_with_hash_length(&classes, 8)
+            .starts_with("c_728afc69_"));
+    }
+
+    #[test]
+    fn test_scoped_naming() {
+        let classes = vec!["p-4".to_string(), "m-2".to_string()];
+        let a = ScopedNaming::new(Box::new(ReadableNaming), "src/A.tsx");
+        let b = ScopedNaming::new(Box::new(ReadableNaming), "src/B.tsx");
+
+        let name_a = a.generate_name(&classes);
+        assert!(name_a.starts_with("p4_m2_"));
+        assert_eq!(name_a.len(), "p4_m2_".len() + 6);
+        assert_ne!(name_a, b.generate_name(&classes));
+        assert_eq!(name_a, a.generate_name(&classes));
+    }
+
     #[test]
     fn test_readable_naming() {
         let naming = ReadableNaming;
@@ -258,4 +515,50 @@ mod tests {
         let name = naming.generate_name(&classes);
         assert_eq!(name, "empty");
     }
+
+    #[test]
+    fn test_unicode_names() {
+        let classes = |list: &[&str]| list.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+
+        // CJK 保留；任意值的方括号、引号与 emoji 丢弃，名称追加 hash
+        let cjk = classes(&["font-['思源黑体']", "md:p-4"]);
+        let name = ReadableNaming.generate_name(&cjk);
+        assert!(name.starts_with("font思源黑体_md_p4_"), "{}", name);
+        assert_eq!(name.chars().count(), "font思源黑体_md_p4_".chars().count() + 6);
+        let name = CamelCaseNaming.generate_name(&cjk);
+        assert!(name.starts_with("font思源黑体MdP4"), "{}", name);
+        let emoji = classes(&["after:content-['🚀']", "after:content-['⭐']"]);
+        assert_ne!(
+            ReadableNaming.generate_name(&emoji[..1]),
+            ReadableNaming.generate_name(&emoji[1..])
+        );
+        assert!(ReadableNaming.generate_name(&emoji[..1]).starts_with("after_co_"));
+        assert!(CamelCaseNaming.generate_name(&emoji[..1]).starts_with("afterContent"));
+
+        // 以数字开头时加前缀；全部字符被丢弃时退回 hash 名
+        assert_eq!(ReadableNaming.generate_name(&classes(&["2xl:p-4"])), "_2xl_p4");
+        assert_eq!(CamelCaseNaming.generate_name(&classes(&["2xl:p-4"])), "_2xlP4");
+        assert!(ReadableNaming.generate_name(&classes(&["[🚀]"])).starts_with("c_"));
+
+        // 按字符截断，不会切断多字节字符
+        let long = classes(&["font-['思源黑体思源黑体思源黑体思源黑体思源黑体思源黑体']", "text-lg"]);
+        let name = CamelCaseNaming.generate_name(&long);
+        assert_eq!(name.chars().count(), 24 + 6);
+        assert!(name.starts_with("font思源黑体"), "{}", name);
+        assert_eq!(name, CamelCaseNaming.generate_name(&long));
+    }
+
+    #[test]
+    fn test_fraction_and_decimal_names_distinct() {
+        let name = |naming: &dyn NamingStrategy, class: &str| naming.generate_name(&[class.to_string()]);
+        for naming in [&ReadableNaming as &dyn NamingStrategy, &CamelCaseNaming] {
+            assert_ne!(name(naming, "p-1.5"), name(naming, "p-15"));
+            assert_ne!(name(naming, "w-1/2"), name(naming, "w-12"));
+        }
+        assert_eq!(name(&ReadableNaming, "p-1.5"), "p1d5");
+        assert_eq!(name(&ReadableNaming, "w-1/2"), "w1s2");
+        assert_eq!(name(&CamelCaseNaming, "p-1.5"), "p1d5");
+        assert_eq!(name(&CamelCaseNaming, "w-1/2"), "w1s2");
+        assert_eq!(name(&ReadableNaming, "hover:p-4"), "hover_p4");
+    }
 }
@@ -320,7 +320,7 @@ static PALETTE: phf::Map<&'static str, Shades> = phf_map! {
 // ---------------------------------------------------------------------------
 
 /// OKLCH 三元组 → sRGB (gamut-clamp 到 0-255)
-fn oklch_to_rgb(l: f32, c: f32, h: f32) -> (u8, u8, u8) {
+pub(crate) fn oklch_to_rgb(l: f32, c: f32, h: f32) -> (u8, u8, u8) {
     let oklch = Oklch::new(l, c, h);
     let rgb: Srgb = oklch.into_color();
     let r = (rgb.red.clamp(0.0, 1.0) * 255.0).round() as u8;
@@ -360,6 +360,16 @@ fn oklch_to_hsl(l: f32, c: f32, h: f32) -> String {
 // 公开 API
 // ---------------------------------------------------------------------------
 
+/// 调色板中的所有颜色族名（如 "red"、"slate"）
+pub(crate) fn color_families() -> impl Iterator<Item = &'static str> {
+    PALETTE.keys().copied()
+}
+
+/// 色阶名是否有效（"50" ~ "950"）
+pub(crate) fn is_shade(shade: &str) -> bool {
+    shade_index(shade).is_some()
+}
+
 /// 从 "red-500" 解析出 ("red", "500")
 fn parse_color_name(name: &str) -> Option<(&str, &str)> {
     let idx = name.rfind('-')?;
@@ -31,6 +31,8 @@ static PLUGIN_PROPERTY_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     "bottom" => "bottom",
     "left" => "left",
     "inset" => "inset",
+    "start" => "inset-inline-start",
+    "end" => "inset-inline-end",
 
     // Typography (排版)
     // 注意：text 不在此 map 中，因为它是语义重载的（color / font-size / text-align），
@@ -42,6 +44,10 @@ static PLUGIN_PROPERTY_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     // Background (背景)
     "bg" => "background",
     "bg-color" => "background-color",
+    "bg-position" => "background-position",
+    "bg-size" => "background-size",
+    "mask-position" => "mask-position",
+    "mask-size" => "mask-size",
 
     // Gradient color stops (渐变色)
     "from" => "--tw-gradient-from",
@@ -55,13 +61,22 @@ static PLUGIN_PROPERTY_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     "border-b" => "border-bottom-width",
     "border-l" => "border-left-width",
     "rounded" => "border-radius",
+    "rounded-tl" => "border-top-left-radius",
+    "rounded-tr" => "border-top-right-radius",
+    "rounded-br" => "border-bottom-right-radius",
+    "rounded-bl" => "border-bottom-left-radius",
+    "rounded-ss" => "border-start-start-radius",
+    "rounded-se" => "border-start-end-radius",
+    "rounded-es" => "border-end-start-radius",
+    "rounded-ee" => "border-end-end-radius",
 
     // Flexbox & Grid
     "gap" => "gap",
     "gap-x" => "column-gap",
     "gap-y" => "row-gap",
-    "space-x" => "column-gap",
-    "space-y" => "row-gap",
+    // 写在子元素上，再按 `--tw-space-*-reverse` 拆成起止两侧的外边距
+    "space-x" => "margin-inline",
+    "space-y" => "margin-block",
     "grid-cols" => "grid-template-columns",
     "grid-rows" => "grid-template-rows",
     "col-span" => "grid-column",
@@ -87,6 +102,7 @@ static PLUGIN_PROPERTY_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     // Effects (效果)
     "opacity" => "opacity",
     "shadow" => "box-shadow",
+    "text-shadow" => "text-shadow",
 
     // Transform (变换)
     "translate" => "translate",
@@ -97,12 +113,28 @@ static PLUGIN_PROPERTY_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     "scale" => "scale",
     "scale-x" => "scale",
     "scale-y" => "scale",
+    "skew" => "transform",
+    "skew-x" => "transform",
+    "skew-y" => "transform",
 
     // Filters (滤镜)
     "blur" => "filter",
     "brightness" => "filter",
     "contrast" => "filter",
     "grayscale" => "filter",
+    "hue-rotate" => "filter",
+    "invert" => "filter",
+    "saturate" => "filter",
+    "sepia" => "filter",
+    "backdrop-blur" => "backdrop-filter",
+    "backdrop-brightness" => "backdrop-filter",
+    "backdrop-contrast" => "backdrop-filter",
+    "backdrop-grayscale" => "backdrop-filter",
+    "backdrop-hue-rotate" => "backdrop-filter",
+    "backdrop-invert" => "backdrop-filter",
+    "backdrop-opacity" => "backdrop-filter",
+    "backdrop-saturate" => "backdrop-filter",
+    "backdrop-sepia" => "backdrop-filter",
 
     // Transitions & Animation (过渡和动画)
     "duration" => "transition-duration",
@@ -189,7 +221,9 @@ static PLUGIN_PROPERTY_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     "shrink" => "flex-shrink",
     "transform" => "transform",
     "ring" => "box-shadow",
-    "ring-offset" => "box-shadow",
+    "ring-offset" => "--tw-ring-offset-width",
+    "outline-offset" => "outline-offset",
+    "underline-offset" => "text-underline-offset",
     "order" => "order",
     "cursor" => "cursor",
     "pointer-events" => "pointer-events",
@@ -216,6 +250,8 @@ static MULTI_PROPERTY_MAP: phf::Map<&'static str, (&'static str, &'static str)>
     "rounded-r" => ("border-top-right-radius", "border-bottom-right-radius"),
     "rounded-b" => ("border-bottom-right-radius", "border-bottom-left-radius"),
     "rounded-l" => ("border-top-left-radius", "border-bottom-left-radius"),
+    "rounded-s" => ("border-start-start-radius", "border-end-start-radius"),
+    "rounded-e" => ("border-start-end-radius", "border-end-end-radius"),
     "size" => ("width", "height"),
     "scroll-px" => ("scroll-padding-left", "scroll-padding-right"),
     "scroll-py" => ("scroll-padding-top", "scroll-padding-bottom"),
@@ -249,6 +285,70 @@ pub fn is_known_plugin(plugin: &str) -> bool {
     PLUGIN_PROPERTY_MAP.contains_key(plugin) || MULTI_PROPERTY_MAP.contains_key(plugin)
 }
 
+/// 所有已知插件名（单属性和多属性）
+pub(crate) fn known_plugins() -> impl Iterator<Item = &'static str> {
+    PLUGIN_PROPERTY_MAP
+        .keys()
+        .chain(MULTI_PROPERTY_MAP.keys())
+        .copied()
+}
+
+/// 是否接受分数值（`w-1/2`、`top-1/3`、`aspect-4/3`）
+pub fn accepts_fraction(plugin: &str) -> bool {
+    matches!(
+        plugin,
+        "w" | "min-w" | "max-w" | "h" | "min-h" | "max-h" | "size" | "basis" | "flex" | "aspect"
+            | "inset" | "inset-x" | "inset-y" | "start" | "end" | "top" | "right" | "bottom"
+            | "left" | "translate" | "translate-x" | "translate-y"
+    )
+}
+
+/// 是否接受负值（`-m-4`、`-translate-x-1/2`、`-tracking-tight`），与 Tailwind 可取负的工具类一致
+///
+/// 其余插件的负值类（`-p-4`、`-bg-red-500`）没有对应的 CSS，转换时视为无法识别。
+pub fn accepts_negative(plugin: &str) -> bool {
+    matches!(
+        plugin,
+        "m" | "mx" | "my" | "mt" | "mr" | "mb" | "ml" | "ms" | "me" | "space-x" | "space-y"
+            | "inset" | "inset-x" | "inset-y" | "start" | "end" | "top" | "right" | "bottom" | "left"
+            | "scroll-m" | "scroll-mx" | "scroll-my" | "scroll-mt" | "scroll-mr" | "scroll-mb"
+            | "scroll-ml" | "scroll-ms" | "scroll-me"
+            | "translate" | "translate-x" | "translate-y" | "translate-z"
+            | "rotate" | "rotate-x" | "rotate-y" | "rotate-z"
+            | "skew" | "skew-x" | "skew-y" | "scale" | "scale-x" | "scale-y" | "scale-z"
+            | "hue-rotate" | "backdrop-hue-rotate"
+            | "tracking" | "indent" | "order" | "z" | "outline-offset" | "underline-offset"
+    )
+}
+
+/// 滤镜插件对应的属性与滤镜函数：`brightness` → (`filter`, `brightness`)，
+/// `backdrop-hue-rotate` → (`backdrop-filter`, `hue-rotate`)
+pub fn filter_function(plugin: &str) -> Option<(&'static str, &'static str)> {
+    let (property, name) = match plugin.strip_prefix("backdrop-") {
+        Some(name) => ("backdrop-filter", name),
+        None => ("filter", plugin),
+    };
+    let function = match name {
+        "blur" => "blur",
+        "brightness" => "brightness",
+        "contrast" => "contrast",
+        "grayscale" => "grayscale",
+        "hue-rotate" => "hue-rotate",
+        "invert" => "invert",
+        "saturate" => "saturate",
+        "sepia" => "sepia",
+        // `opacity` 本身是独立属性，只有 backdrop 版本是滤镜
+        "opacity" if property == "backdrop-filter" => "opacity",
+        _ => return None,
+    };
+    Some((property, function))
+}
+
+/// 是否为圆角插件：`rounded` 及其边（`rounded-t`）、角（`rounded-tl`）和逻辑方向（`rounded-ss`）
+pub fn is_radius_plugin(plugin: &str) -> bool {
+    plugin == "rounded" || (plugin.starts_with("rounded-") && is_known_plugin(plugin))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -282,4 +382,16 @@ mod tests {
 
         assert!(get_plugin_properties("unknown").is_none());
     }
+
+    #[test]
+    fn test_filter_function() {
+        assert_eq!(filter_function("brightness"), Some(("filter", "brightness")));
+        assert_eq!(
+            filter_function("backdrop-hue-rotate"),
+            Some(("backdrop-filter", "hue-rotate"))
+        );
+        assert_eq!(filter_function("backdrop-opacity"), Some(("backdrop-filter", "opacity")));
+        assert_eq!(filter_function("opacity"), None);
+        assert_eq!(filter_function("backdrop"), None);
+    }
 }
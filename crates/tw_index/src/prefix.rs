@@ -0,0 +1,199 @@
+//! 厂商前缀
+//!
+//! 按 [`Targets`] 为生成的声明补充 `-webkit-` / `-moz-` 前缀，作用类似 autoprefixer。
+//! 只覆盖 Tailwind 工具类会输出的属性，版本数据取自 caniuse（按主版本取整）。
+
+use headwind_core::{Declaration, Targets};
+
+/// 始终需要前缀（该浏览器至今只支持带前缀的写法）
+const ALWAYS: u32 = u32::MAX;
+
+/// 一条前缀规则
+struct PrefixRule {
+    property: &'static str,
+    /// 只在值为它时加前缀（如 `background-clip: text`）
+    value: Option<&'static str>,
+    prefix: &'static str,
+    /// true 时前缀加在值上（`position: -webkit-sticky`），否则加在属性名上
+    on_value: bool,
+    /// 各浏览器开始支持无前缀写法的主版本：（chrome, firefox, safari），0 表示从不需要该前缀
+    unprefixed_since: (u32, u32, u32),
+}
+
+const fn property(
+    property: &'static str,
+    prefix: &'static str,
+    since: (u32, u32, u32),
+) -> PrefixRule {
+    PrefixRule {
+        property,
+        value: None,
+        prefix,
+        on_value: false,
+        unprefixed_since: since,
+    }
+}
+
+const RULES: &[PrefixRule] = &[
+    property("backdrop-filter", "-webkit-", (0, 0, 18)),
+    property("user-select", "-webkit-", (54, 0, ALWAYS)),
+    property("user-select", "-moz-", (0, 69, 0)),
+    property("appearance", "-webkit-", (84, 0, 16)),
+    property("appearance", "-moz-", (0, 80, 0)),
+    property("mask", "-webkit-", (120, 0, 16)),
+    property("mask-image", "-webkit-", (120, 0, 16)),
+    property("mask-size", "-webkit-", (120, 0, 16)),
+    property("mask-position", "-webkit-", (120, 0, 16)),
+    property("mask-repeat", "-webkit-", (120, 0, 16)),
+    property("mask-clip", "-webkit-", (120, 0, 16)),
+    property("mask-origin", "-webkit-", (120, 0, 16)),
+    property("hyphens", "-webkit-", (88, 0, 17)),
+    property("hyphens", "-moz-", (0, 43, 0)),
+    property("box-decoration-break", "-webkit-", (130, 0, ALWAYS)),
+    property("print-color-adjust", "-webkit-", (136, 0, 16)),
+    property("clip-path", "-webkit-", (55, 0, 14)),
+    property("tab-size", "-moz-", (0, 91, 0)),
+    PrefixRule {
+        property: "background-clip",
+        value: Some("text"),
+        prefix: "-webkit-",
+        on_value: false,
+        unprefixed_since: (120, 0, 14),
+    },
+    PrefixRule {
+        property: "position",
+        value: Some("sticky"),
+        prefix: "-webkit-",
+        on_value: true,
+        unprefixed_since: (0, 0, 13),
+    },
+];
+
+impl PrefixRule {
+    fn needed(&self, targets: &Targets) -> bool {
+        let (chrome, firefox, safari) = self.unprefixed_since;
+        targets.below(chrome, firefox, safari)
+    }
+
+    fn matches(&self, declaration: &Declaration) -> bool {
+        if declaration.property != self.property {
+            return false;
+        }
+        let value = declaration.value.trim_end_matches("!important").trim();
+        self.value.is_none_or(|expected| value == expected)
+    }
+
+    fn apply(&self, declaration: &Declaration) -> Declaration {
+        if self.on_value {
+            Declaration::new(
+                declaration.property.clone(),
+                format!("{}{}", self.prefix, declaration.value),
+            )
+        } else {
+            Declaration::new(
+                format!("{}{}", self.prefix, declaration.property),
+                declaration.value.clone(),
+            )
+        }
+    }
+}
+
+/// 为需要前缀的声明在其前面插入带前缀的版本；`targets` 为空时原样返回
+///
+/// 列表中已有相同的带前缀声明时不重复插入。
+pub fn add_vendor_prefixes(declarations: Vec<Declaration>, targets: &Targets) -> Vec<Declaration> {
+    if targets.is_empty() {
+        return declarations;
+    }
+    let mut prefixed = Vec::with_capacity(declarations.len());
+    for declaration in &declarations {
+        for rule in RULES {
+            if !rule.matches(declaration) || !rule.needed(targets) {
+                continue;
+            }
+            let extra = rule.apply(declaration);
+            if !declarations.contains(&extra) && !prefixed.contains(&extra) {
+                prefixed.push(extra);
+            }
+        }
+        prefixed.push(declaration.clone());
+    }
+    prefixed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(declarations: &[Declaration]) -> Vec<String> {
+        declarations
+            .iter()
+            .map(|d| format!("{}: {}", d.property, d.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_prefixes_follow_targets() {
+        let declarations = vec![
+            Declaration::new("backdrop-filter", "blur(8px)"),
+            Declaration::new("user-select", "none !important"),
+            Declaration::new("position", "sticky"),
+            Declaration::new("padding", "1rem"),
+        ];
+        let safari = Targets {
+            safari: Some(12),
+            ..Default::default()
+        };
+        assert_eq!(
+            render(&add_vendor_prefixes(declarations.clone(), &safari)),
+            [
+                "-webkit-backdrop-filter: blur(8px)",
+                "backdrop-filter: blur(8px)",
+                "-webkit-user-select: none !important",
+                "user-select: none !important",
+                "position: -webkit-sticky",
+                "position: sticky",
+                "padding: 1rem",
+            ]
+        );
+
+        let modern = Targets {
+            chrome: Some(120),
+            firefox: Some(120),
+            ..Default::default()
+        };
+        assert_eq!(
+            add_vendor_prefixes(declarations.clone(), &modern),
+            declarations
+        );
+        let firefox = Targets {
+            firefox: Some(60),
+            ..Default::default()
+        };
+        assert_eq!(
+            render(&add_vendor_prefixes(declarations.clone(), &firefox))[1],
+            "-moz-user-select: none !important"
+        );
+        assert_eq!(
+            add_vendor_prefixes(declarations.clone(), &Targets::default()),
+            declarations
+        );
+    }
+
+    #[test]
+    fn test_value_filter_and_existing_prefix() {
+        let safari = Targets {
+            safari: Some(13),
+            ..Default::default()
+        };
+        let declarations = vec![
+            Declaration::new("background-clip", "border-box"),
+            Declaration::new("-webkit-mask-image", "none"),
+            Declaration::new("mask-image", "none"),
+        ];
+        assert_eq!(
+            add_vendor_prefixes(declarations.clone(), &safari),
+            declarations
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! 无法转换的类的修复建议
+//!
+//! 类能被解析但转换结果为 None 时，按以下顺序给出建议：
+//! 1. 属于第三方插件（如 `tailwindcss-animate`）→ 提示需要安装的插件
+//! 2. 插件名、取值或完整类名与已知工具类的编辑距离足够小 → 给出拼写相近的可用类
+//! 3. 插件接受颜色但取值不在默认调色板中（如 `bg-brand`）→ 提示需要主题条目
+//!
+//! 候选类都会用同一个 [`Converter`] 验证，只推荐确实能转换的类。
+
+use crate::converter::{valueless_names, Converter};
+use crate::palette;
+use crate::plugin_map::{get_plugin_property_map, known_plugins};
+use crate::theme_values;
+use headwind_tw_parse::{parse_class, ParsedClass, ParsedValue};
+use std::fmt;
+
+/// 不在 plugin_map 中、由转换器单独处理的常用插件
+const EXTRA_PLUGINS: &[&str] = &[
+    "text", "font", "ring", "from", "via", "to", "fill", "stroke",
+];
+
+/// 命名尺寸以外的常用取值关键字（颜色关键字与间距/尺寸关键字）
+const VALUE_KEYWORDS: &[&str] = &[
+    "black",
+    "white",
+    "transparent",
+    "current",
+    "inherit",
+    "auto",
+    "full",
+    "screen",
+    "none",
+];
+
+/// 对无法转换的类给出的修复建议
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Suggestion {
+    /// 拼写相近的可用类，保留原类的修饰符、负号和透明度（`hover:bg-bleu-500` → `hover:bg-blue-500`）
+    DidYouMean(String),
+    /// 类由第三方插件提供（如 `animate-in` → `tailwindcss-animate`）
+    RequiresPlugin(&'static str),
+    /// 插件已知，但取值需要在主题中定义（如 `bg-brand` 的 `brand`）
+    RequiresTheme(String),
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Suggestion::DidYouMean(class) => write!(f, "是否想使用 `{}`？", class),
+            Suggestion::RequiresPlugin(package) => {
+                write!(
+                    f,
+                    "该类由 {} 插件提供，需安装插件或通过 fallbackIndex 提供",
+                    package
+                )
+            }
+            Suggestion::RequiresTheme(value) => write!(
+                f,
+                "`{}` 不在默认主题中，需在主题中定义并通过 fallbackIndex 提供对应的类",
+                value
+            ),
+        }
+    }
+}
+
+/// 为无法转换的类生成建议；无法解析或找不到合适建议时返回 None
+pub(crate) fn suggest(converter: &Converter, class: &str) -> Option<Suggestion> {
+    let parsed = parse_class(class).ok()?;
+    let value = match &parsed.value {
+        None => None,
+        Some(ParsedValue::Standard(value)) => Some(value.as_ref()),
+        // 任意值写法通常是有意为之，不做拼写猜测
+        Some(_) => return None,
+    };
+
+    if let Some(package) = required_plugin(&parsed.plugin, value) {
+        return Some(Suggestion::RequiresPlugin(package));
+    }
+    if let Some(class) = nearest_class(converter, &parsed, value) {
+        return Some(Suggestion::DidYouMean(class));
+    }
+    let value = value?;
+    let accepts_color = recognizes(converter, &format!("{}-red-500", parsed.plugin));
+    if accepts_color && value.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Some(Suggestion::RequiresTheme(value.to_string()));
+    }
+    None
+}
+
+/// 常见第三方插件的类 → 插件包名
+fn required_plugin(plugin: &str, value: Option<&str>) -> Option<&'static str> {
+    let value = value.unwrap_or("");
+    let enters_or_exits =
+        value == "in" || value == "out" || value.starts_with("in-") || value.starts_with("out-");
+    match plugin {
+        "prose" => Some("@tailwindcss/typography"),
+        "not" if value == "prose" => Some("@tailwindcss/typography"),
+        "animate" if value == "in" || value == "out" => Some("tailwindcss-animate"),
+        "fade" | "zoom" | "spin" | "slide" if enters_or_exits => Some("tailwindcss-animate"),
+        _ if plugin.starts_with("scrollbar") => Some("tailwind-scrollbar"),
+        _ => None,
+    }
+}
+
+/// 在插件名、取值和完整类名三个层面找编辑距离最小且能转换的类
+fn nearest_class(
+    converter: &Converter,
+    parsed: &ParsedClass,
+    value: Option<&str>,
+) -> Option<String> {
+    let plugin = parsed.plugin.as_ref();
+    let body = match value {
+        Some(value) => format!("{}-{}", plugin, value),
+        None => plugin.to_string(),
+    };
+    let with_value = |candidate: &str| match value {
+        Some(value) => format!("{}-{}", candidate, value),
+        None => candidate.to_string(),
+    };
+
+    // (编辑距离, 候选类名)
+    let mut candidates: Vec<(usize, String)> = Vec::new();
+    let mut consider = |typed: &str, known: &str, class: String| {
+        if let Some(distance) = close_enough(typed, known) {
+            candidates.push((distance, class));
+        }
+    };
+
+    // 插件名拼写错误（`paddin-4` 也按 CSS 属性名 `padding` 对应到 `p-4`）
+    for known in known_plugins().chain(EXTRA_PLUGINS.iter().copied()) {
+        consider(plugin, known, with_value(known));
+    }
+    for (&known, &property) in get_plugin_property_map().entries() {
+        consider(plugin, property, with_value(known));
+    }
+
+    // 完整类名拼写错误（`itms-center` → `items-center`）
+    for known in valueless_names() {
+        consider(&body, known, known.to_string());
+    }
+
+    // 取值拼写错误：颜色族保留色阶（`bg-bleu-500` → `bg-blue-500`），命名尺寸直接替换
+    if let Some(value) = value {
+        match value.rsplit_once('-') {
+            Some((family, shade)) if palette::is_shade(shade) => {
+                for known in palette::color_families() {
+                    consider(family, known, format!("{}-{}-{}", plugin, known, shade));
+                }
+            }
+            _ => {
+                let named_sizes = [
+                    &theme_values::TEXT_SIZE,
+                    &theme_values::FONT_FAMILY,
+                    &theme_values::BLUR_SIZE,
+                    &theme_values::SHADOW_SIZE,
+                    &theme_values::RADIUS_SIZE,
+                    &theme_values::V3_BLUR_SIZE,
+                    &theme_values::V3_SHADOW_SIZE,
+                    &theme_values::V3_RADIUS_SIZE,
+                ];
+                let keys = named_sizes.into_iter().flat_map(|map| map.keys().copied());
+                for known in keys.chain(VALUE_KEYWORDS.iter().copied()) {
+                    consider(value, known, format!("{}-{}", plugin, known));
+                }
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+    candidates.dedup_by(|a, b| a.1 == b.1);
+    candidates
+        .into_iter()
+        .filter(|(_, candidate)| *candidate != body)
+        .map(|(_, candidate)| restore_affixes(parsed, &candidate))
+        .find(|class| recognizes(converter, class))
+}
+
+/// 把候选类名还原为带修饰符、负号、透明度和 `!` 的完整写法
+fn restore_affixes(parsed: &ParsedClass, body: &str) -> String {
+    let mut class = parsed.raw_modifiers.to_string();
+    if parsed.important {
+        class.push('!');
+    }
+    if parsed.negative {
+        class.push('-');
+    }
+    class.push_str(body);
+    if let Some(alpha) = &parsed.alpha {
+        class.push('/');
+        class.push_str(alpha);
+    }
+    class
+}
+
+fn recognizes(converter: &Converter, class: &str) -> bool {
+    parse_class(class).is_ok_and(|parsed| converter.to_declarations(&parsed).is_some())
+}
+
+/// 编辑距离在允许范围内时返回距离：短词最多 1 处，较长的词最多 2 处，2 个字符以内不猜测
+fn close_enough(typed: &str, known: &str) -> Option<usize> {
+    let limit = match typed.len().min(known.len()) {
+        0..=2 => return None,
+        3..=4 => 1,
+        _ => 2,
+    };
+    if typed.len().abs_diff(known.len()) > limit {
+        return None;
+    }
+    let distance = edit_distance(typed, known);
+    (distance <= limit).then_some(distance)
+}
+
+/// 编辑距离（相邻字符交换计为一次编辑，`bleu` → `blue` 距离为 1）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // rows[i][j]：a[..i] 与 b[..j] 的距离
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggest_for(class: &str) -> Option<Suggestion> {
+        suggest(&Converter::new(), class)
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("bleu", "blue"), 1);
+        assert_eq!(edit_distance("itms", "items"), 1);
+        assert_eq!(edit_distance("colum", "col"), 2);
+        assert_eq!(edit_distance("same", "same"), 0);
+        assert_eq!(close_enough("md", "sm"), None);
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        let did_you_mean = |class: &str| Some(Suggestion::DidYouMean(class.to_string()));
+        assert_eq!(suggest_for("bg-bleu-500"), did_you_mean("bg-blue-500"));
+        assert_eq!(
+            suggest_for("hover:bg-bleu-500/50"),
+            did_you_mean("hover:bg-blue-500/50")
+        );
+        assert_eq!(suggest_for("itms-center"), did_you_mean("items-center"));
+        assert_eq!(suggest_for("flex-colum"), did_you_mean("flex-col"));
+        assert_eq!(suggest_for("paddin-4"), did_you_mean("p-4"));
+        assert_eq!(suggest_for("-mrgin-4"), did_you_mean("-m-4"));
+        assert_eq!(suggest_for("w-ful"), did_you_mean("w-full"));
+        assert_eq!(suggest_for("shadow-xxl"), did_you_mean("shadow-2xl"));
+        assert_eq!(
+            suggest_for("md:rounded-xxl"),
+            did_you_mean("md:rounded-2xl")
+        );
+    }
+
+    #[test]
+    fn test_requires_plugin_or_theme() {
+        assert_eq!(
+            suggest_for("animate-in"),
+            Some(Suggestion::RequiresPlugin("tailwindcss-animate"))
+        );
+        assert_eq!(
+            suggest_for("slide-in-from-top-2"),
+            Some(Suggestion::RequiresPlugin("tailwindcss-animate"))
+        );
+        assert_eq!(
+            suggest_for("not-prose"),
+            Some(Suggestion::RequiresPlugin("@tailwindcss/typography"))
+        );
+        assert_eq!(
+            suggest_for("scrollbar-thin"),
+            Some(Suggestion::RequiresPlugin("tailwind-scrollbar"))
+        );
+        assert_eq!(
+            suggest_for("bg-brand"),
+            Some(Suggestion::RequiresTheme("brand".to_string()))
+        );
+        assert_eq!(suggest_for("card"), None);
+        assert_eq!(suggest_for("bg-[url(x.png)"), None);
+    }
+}
@@ -1,7 +1,11 @@
 //! Tailwind v4 默认主题变量的内联值映射
 //!
-//! 当 `CssVariableMode::Inline` 时，使用这些值替代 `var(--xxx)` 引用。
+//! 当 `CssVariableMode::Inline` 时，使用这些值替代 `var(--xxx)` 引用；
+//! Var 模式下由 `Bundler::generate_root_css` 输出为 `:root` 定义。
 //! 仅包含 converter.rs 中实际使用的主题变量，不包含 `--tw-*` 内部状态变量。
+//!
+//! `V3_*` 为 Tailwind v3 的命名尺寸（`TailwindVersion::V3` 时直接输出具体值），
+//! 表中的 `DEFAULT` 对应不带尺寸的类（如 `rounded`）。
 
 use phf::phf_map;
 
@@ -48,6 +52,87 @@ pub static FONT_FAMILY: phf::Map<&'static str, &'static str> = phf_map! {
 
 /// `--blur-{size}` → blur 像素值
 pub static BLUR_SIZE: phf::Map<&'static str, &'static str> = phf_map! {
+    "none" => "0",
+    "xs" => "4px",
+    "sm" => "8px",
+    "DEFAULT" => "8px",
+    "md" => "12px",
+    "lg" => "16px",
+    "xl" => "24px",
+    "2xl" => "40px",
+    "3xl" => "64px",
+};
+
+/// `--spacing` 间距基数
+pub const SPACING: &str = "0.25rem";
+
+/// `--tracking-{name}` → letter-spacing 值（v3 与 v4 相同）
+pub static TRACKING: phf::Map<&'static str, &'static str> = phf_map! {
+    "tighter" => "-0.05em",
+    "tight" => "-0.025em",
+    "normal" => "0em",
+    "wide" => "0.025em",
+    "wider" => "0.05em",
+    "widest" => "0.1em",
+};
+
+/// `--shadow-{size}` → box-shadow 值
+pub static SHADOW_SIZE: phf::Map<&'static str, &'static str> = phf_map! {
+    "2xs" => "0 1px rgb(0 0 0 / 0.05)",
+    "xs" => "0 1px 2px 0 rgb(0 0 0 / 0.05)",
+    "sm" => "0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1)",
+    "md" => "0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1)",
+    "lg" => "0 10px 15px -3px rgb(0 0 0 / 0.1), 0 4px 6px -4px rgb(0 0 0 / 0.1)",
+    "xl" => "0 20px 25px -5px rgb(0 0 0 / 0.1), 0 8px 10px -6px rgb(0 0 0 / 0.1)",
+    "2xl" => "0 25px 50px -12px rgb(0 0 0 / 0.25)",
+};
+
+/// `--text-shadow-{size}` → text-shadow 值（v4.1 新增）
+pub static TEXT_SHADOW_SIZE: phf::Map<&'static str, &'static str> = phf_map! {
+    "2xs" => "0px 1px 0px rgb(0 0 0 / 0.15)",
+    "xs" => "0px 1px 1px rgb(0 0 0 / 0.2)",
+    "sm" => "0px 1px 0px rgb(0 0 0 / 0.075), 0px 1px 1px rgb(0 0 0 / 0.075), 0px 2px 2px rgb(0 0 0 / 0.075)",
+    "md" => "0px 1px 1px rgb(0 0 0 / 0.1), 0px 1px 2px rgb(0 0 0 / 0.1), 0px 2px 4px rgb(0 0 0 / 0.1)",
+    "lg" => "0px 1px 2px rgb(0 0 0 / 0.1), 0px 3px 2px rgb(0 0 0 / 0.1), 0px 4px 8px rgb(0 0 0 / 0.1)",
+};
+
+/// `--radius-{size}` → border-radius 值
+pub static RADIUS_SIZE: phf::Map<&'static str, &'static str> = phf_map! {
+    "xs" => "0.125rem",
+    "sm" => "0.25rem",
+    "DEFAULT" => "0.25rem",
+    "md" => "0.375rem",
+    "lg" => "0.5rem",
+    "xl" => "0.75rem",
+    "2xl" => "1rem",
+    "3xl" => "1.5rem",
+    "4xl" => "2rem",
+};
+
+/// Tailwind v3 的 border-radius 尺寸
+pub static V3_RADIUS_SIZE: phf::Map<&'static str, &'static str> = phf_map! {
+    "sm" => "0.125rem",
+    "DEFAULT" => "0.25rem",
+    "md" => "0.375rem",
+    "lg" => "0.5rem",
+    "xl" => "0.75rem",
+    "2xl" => "1rem",
+    "3xl" => "1.5rem",
+};
+
+/// Tailwind v3 的 box-shadow 尺寸
+pub static V3_SHADOW_SIZE: phf::Map<&'static str, &'static str> = phf_map! {
+    "sm" => "0 1px 2px 0 rgb(0 0 0 / 0.05)",
+    "DEFAULT" => "0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1)",
+    "md" => "0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1)",
+    "lg" => "0 10px 15px -3px rgb(0 0 0 / 0.1), 0 4px 6px -4px rgb(0 0 0 / 0.1)",
+    "xl" => "0 20px 25px -5px rgb(0 0 0 / 0.1), 0 8px 10px -6px rgb(0 0 0 / 0.1)",
+    "2xl" => "0 25px 50px -12px rgb(0 0 0 / 0.25)",
+    "inner" => "inset 0 2px 4px 0 rgb(0 0 0 / 0.05)",
+};
+
+/// Tailwind v3 的 blur 尺寸
+pub static V3_BLUR_SIZE: phf::Map<&'static str, &'static str> = phf_map! {
     "none" => "0",
     "sm" => "4px",
     "DEFAULT" => "8px",
@@ -57,3 +142,10 @@ pub static BLUR_SIZE: phf::Map<&'static str, &'static str> = phf_map! {
     "2xl" => "40px",
     "3xl" => "64px",
 };
+
+/// `--inset-shadow-{size}` → box-shadow 值
+pub static INSET_SHADOW_SIZE: phf::Map<&'static str, &'static str> = phf_map! {
+    "2xs" => "inset 0 1px rgb(0 0 0 / 0.05)",
+    "xs" => "inset 0 1px 1px rgb(0 0 0 / 0.05)",
+    "sm" => "inset 0 2px 4px rgb(0 0 0 / 0.05)",
+};
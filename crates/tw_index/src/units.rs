@@ -0,0 +1,114 @@
+use headwind_core::UnitConversion;
+
+/// 按 `conversion` 换算值中的 px / rem 长度，`root_size` 为根字号（px，最小按 1 处理）
+///
+/// 只换算独立的数值 token：`calc(100% - 24px)` → `calc(100% - 1.5rem)`，
+/// 变量名（`--spacing-4px`）、引号中的字符串与 `url()` 保持原样。
+pub fn convert_lengths(value: &str, conversion: UnitConversion, root_size: u32) -> String {
+    let root_size = f64::from(root_size.max(1));
+    let (from, to, factor) = match conversion {
+        UnitConversion::None => return value.to_string(),
+        UnitConversion::PxToRem => ("px", "rem", 1.0 / root_size),
+        UnitConversion::RemToPx => ("rem", "px", root_size),
+    };
+
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        if c == '"' || c == '\'' {
+            let end = rest[1..].find(c).map_or(rest.len(), |i| i + 2);
+            out.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        if !is_token_char(c) {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let end = rest.find(|c| !is_token_char(c)).unwrap_or(rest.len());
+        let token = &rest[..end];
+        rest = &rest[end..];
+        if token.eq_ignore_ascii_case("url") && rest.starts_with('(') {
+            let close = rest.find(')').map_or(rest.len(), |i| i + 1);
+            out.push_str(token);
+            out.push_str(&rest[..close]);
+            rest = &rest[close..];
+            continue;
+        }
+        match convert_token(token, from, factor) {
+            Some(number) => {
+                out.push_str(&number);
+                out.push_str(to);
+            }
+            None => out.push_str(token),
+        }
+    }
+    out
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '%' | '#') || !c.is_ascii()
+}
+
+/// `24px` → `"1.5"`（换算后的数值，不含单位）；不是纯数值加 `unit` 时返回 None
+fn convert_token(token: &str, unit: &str, factor: f64) -> Option<String> {
+    let number = token.strip_suffix(unit)?;
+    let digits = number.strip_prefix('-').unwrap_or(number);
+    if !digits.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    let n: f64 = number.parse().ok()?;
+    Some(format_number(n * factor))
+}
+
+/// 最多保留 6 位小数，去掉末尾的 0
+fn format_number(n: f64) -> String {
+    let fixed = format!("{:.6}", n);
+    let trimmed = fixed.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_px_to_rem() {
+        let convert = |value| convert_lengths(value, UnitConversion::PxToRem, 16);
+        assert_eq!(convert("24px"), "1.5rem");
+        assert_eq!(convert("1px solid"), "0.0625rem solid");
+        assert_eq!(convert("calc(100% - -8px)"), "calc(100% - -0.5rem)");
+        assert_eq!(
+            convert("0 4px 6px -1px #0000001a"),
+            "0 0.25rem 0.375rem -0.0625rem #0000001a"
+        );
+        // 已是 rem 的值、变量名、字符串和 url() 不变
+        assert_eq!(convert("1.5rem"), "1.5rem");
+        assert_eq!(convert("var(--inset-4px)"), "var(--inset-4px)");
+        assert_eq!(convert("\"12px\""), "\"12px\"");
+        assert_eq!(convert("url(icons/16px) 2px"), "url(icons/16px) 0.125rem");
+        assert_eq!(convert_lengths("10px", UnitConversion::PxToRem, 10), "1rem");
+        assert_eq!(
+            convert_lengths("1px", UnitConversion::PxToRem, 14),
+            "0.071429rem"
+        );
+    }
+
+    #[test]
+    fn test_rem_to_px() {
+        let convert = |value| convert_lengths(value, UnitConversion::RemToPx, 16);
+        assert_eq!(
+            convert("calc(var(--spacing) * 4)"),
+            "calc(var(--spacing) * 4)"
+        );
+        assert_eq!(convert("0.25rem"), "4px");
+        assert_eq!(convert("1.875rem / 2.25rem"), "30px / 36px");
+        assert_eq!(convert("12px 2em"), "12px 2em");
+        assert_eq!(convert_lengths("2rem", UnitConversion::None, 16), "2rem");
+    }
+}
@@ -0,0 +1,280 @@
+use headwind_core::Declaration;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// `@utility` 指令定义的工具类
+///
+/// 只保存声明；名称模式（`content-auto`、`tab-*`）作为注册时的键，见 [`CustomUtilities`]。
+/// 函数式工具类的声明值可以包含 `--value(…)` 与 `--modifier(…)`，转换时按类名中的值替换。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CustomUtility {
+    pub declarations: Vec<Declaration>,
+}
+
+impl CustomUtility {
+    /// 解析 `@utility <name> { … }` 花括号内的声明（不含花括号）
+    ///
+    /// 只支持顶层声明，嵌套规则（`&:hover { … }`）返回错误
+    pub fn parse(body: &str) -> Result<Self, String> {
+        if body.contains('{') {
+            return Err("不支持嵌套规则".to_string());
+        }
+        let declarations = split_declarations(body)
+            .map(|declaration| {
+                let (property, value) = declaration
+                    .split_once(':')
+                    .ok_or_else(|| format!("无效的声明: {}", declaration))?;
+                let (property, value) = (property.trim(), value.trim());
+                if property.is_empty() || value.is_empty() {
+                    return Err(format!("无效的声明: {}", declaration));
+                }
+                Ok(Declaration::new(property, value))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        if declarations.is_empty() {
+            return Err("没有声明".to_string());
+        }
+        Ok(Self { declarations })
+    }
+
+    /// 按类名中的值（`tab-4` 的 `4`）与透明度修饰符展开 `--value(…)` / `--modifier(…)`
+    ///
+    /// 无法展开的声明被丢弃；一个 `--value(…)` 都没有展开时该工具类不匹配
+    fn expand(&self, value: &str, modifier: Option<&str>) -> Option<Vec<Declaration>> {
+        let mut matched = false;
+        let declarations: Vec<_> = self
+            .declarations
+            .iter()
+            .filter_map(|declaration| {
+                let (expanded, used) = expand_function(&declaration.value, "--value", Some(value))?;
+                matched |= used;
+                let (expanded, _) = expand_function(&expanded, "--modifier", modifier)?;
+                Some(Declaration::new(declaration.property.clone(), expanded))
+            })
+            .collect();
+        (matched && !declarations.is_empty()).then_some(declarations)
+    }
+}
+
+/// 项目中注册的所有 `@utility` 工具类
+///
+/// 名称以 `-*` 结尾的是函数式工具类（`tab-*` 匹配 `tab-4`、`tab-[12]`），其余按完整类名匹配
+#[derive(Debug, Clone, Default)]
+pub struct CustomUtilities {
+    statics: HashMap<String, Vec<Declaration>>,
+    /// (前缀, 定义)，按前缀长度从长到短排列，长前缀优先
+    functional: Vec<(String, CustomUtility)>,
+    /// 内容指纹，用作转换缓存键
+    fingerprint: u64,
+}
+
+impl CustomUtilities {
+    /// 注册一个工具类，同名时后注册的覆盖先注册的
+    pub fn insert(&mut self, name: &str, utility: CustomUtility) {
+        match name.strip_suffix("-*") {
+            Some(prefix) => {
+                self.functional.retain(|(existing, _)| existing != prefix);
+                self.functional.push((prefix.to_string(), utility));
+                self.functional.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+            }
+            None => {
+                self.statics.insert(name.to_string(), utility.declarations);
+            }
+        }
+        self.fingerprint = self.compute_fingerprint();
+    }
+
+    /// 没有注册任何工具类
+    pub fn is_empty(&self) -> bool {
+        self.statics.is_empty() && self.functional.is_empty()
+    }
+
+    pub(crate) fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// 查找类名（不含变体与透明度）对应的声明，`modifier` 为 `/` 之后的透明度修饰符
+    pub fn lookup(&self, class: &str, modifier: Option<&str>) -> Option<Vec<Declaration>> {
+        if modifier.is_none() {
+            if let Some(declarations) = self.statics.get(class) {
+                return Some(declarations.clone());
+            }
+        }
+        self.functional.iter().find_map(|(prefix, utility)| {
+            let value = class.strip_prefix(prefix.as_str())?.strip_prefix('-')?;
+            utility.expand(value, modifier)
+        })
+    }
+
+    /// 与插入顺序无关的内容指纹
+    fn compute_fingerprint(&self) -> u64 {
+        let statics = self.statics.iter().map(|(name, decls)| (name.as_str(), decls));
+        let functional = self
+            .functional
+            .iter()
+            .map(|(prefix, utility)| (prefix.as_str(), &utility.declarations));
+        statics.chain(functional).fold(0, |acc, (name, decls)| {
+            let mut hasher = DefaultHasher::new();
+            name.hash(&mut hasher);
+            decls.hash(&mut hasher);
+            acc ^ hasher.finish()
+        })
+    }
+}
+
+impl FromIterator<(String, CustomUtility)> for CustomUtilities {
+    fn from_iter<I: IntoIterator<Item = (String, CustomUtility)>>(iter: I) -> Self {
+        let mut utilities = Self::default();
+        for (name, utility) in iter {
+            utilities.insert(&name, utility);
+        }
+        utilities
+    }
+}
+
+/// 按顶层 `;` 拆分声明，忽略括号与引号内的分号
+fn split_declarations(body: &str) -> impl Iterator<Item = &str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut quote, mut start) = (0usize, None, 0);
+    for (i, c) in body.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                parts.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&body[start..]);
+    parts.into_iter().map(str::trim).filter(|part| !part.is_empty())
+}
+
+/// 展开值中所有的 `function(…)` 调用，返回展开后的值与是否展开过
+///
+/// `value` 为 None 或所有候选类型都不匹配时返回 None
+fn expand_function(input: &str, function: &str, value: Option<&str>) -> Option<(String, bool)> {
+    let call = format!("{}(", function);
+    let mut output = String::new();
+    let mut rest = input;
+    let mut used = false;
+    while let Some(start) = rest.find(&call) {
+        let open = start + call.len() - 1;
+        let close = crate::variant::matching_close(rest, open)?;
+        let resolved = resolve_value(&rest[open + 1..close], value?)?;
+        output.push_str(&rest[..start]);
+        output.push_str(&resolved);
+        rest = &rest[close + 1..];
+        used = true;
+    }
+    output.push_str(rest);
+    Some((output, used))
+}
+
+/// 依次尝试 `--value(…)` 中逗号分隔的候选类型，返回第一个匹配的结果
+///
+/// - `[*]`、`[length]` 等：任意值 `[…]`，原样取出（`_` 转为空格）
+/// - `--tab-size-*`：主题变量 `var(--tab-size-4)`
+/// - `integer` / `number` / `percentage`：对应格式的裸值
+/// - `'inherit'`：与字面值相同时取该值
+fn resolve_value(candidates: &str, value: &str) -> Option<String> {
+    let arbitrary = value.strip_prefix('[').and_then(|v| v.strip_suffix(']'));
+    candidates.split(',').map(str::trim).find_map(|candidate| {
+        if candidate.starts_with('[') {
+            return arbitrary.map(|inner| inner.replace('_', " "));
+        }
+        if arbitrary.is_some() {
+            return None;
+        }
+        if let Some(namespace) = candidate.strip_suffix('*') {
+            return namespace
+                .starts_with("--")
+                .then(|| format!("var({}{})", namespace, value));
+        }
+        let literal = candidate
+            .strip_prefix('\'')
+            .and_then(|c| c.strip_suffix('\''))
+            .or_else(|| candidate.strip_prefix('"').and_then(|c| c.strip_suffix('"')));
+        let matches = match (literal, candidate) {
+            (Some(literal), _) => literal == value,
+            (None, "integer") => !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()),
+            (None, "number") => is_bare_number(value),
+            (None, "percentage") => value.strip_suffix('%').is_some_and(is_bare_number),
+            _ => false,
+        };
+        matches.then(|| value.to_string())
+    })
+}
+
+/// Tailwind 接受的裸数字：非负且是 0.25 的倍数（`4`、`2.5`）
+fn is_bare_number(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_digit())
+        && value.parse::<f64>().is_ok_and(|n| (n * 4.0).fract() == 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utilities(entries: &[(&str, &str)]) -> CustomUtilities {
+        entries
+            .iter()
+            .map(|(name, body)| (name.to_string(), CustomUtility::parse(body).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_static_utility() {
+        let utilities = utilities(&[("content-auto", "content-visibility: auto;")]);
+        assert_eq!(
+            utilities.lookup("content-auto", None),
+            Some(vec![Declaration::new("content-visibility", "auto")])
+        );
+        assert_eq!(utilities.lookup("content-auto", Some("50")), None);
+        assert_eq!(utilities.lookup("content-none", None), None);
+    }
+
+    #[test]
+    fn test_functional_utility() {
+        let utilities = utilities(&[
+            ("tab-*", "tab-size: --value(--tab-size-*, integer, [integer]);"),
+            ("gap-safe-*", "gap: calc(var(--spacing) * --value(number)); opacity: --modifier(integer)"),
+        ]);
+        let tab = |value| utilities.lookup(value, None).map(|d| d[0].value.clone());
+        assert_eq!(tab("tab-4").as_deref(), Some("var(--tab-size-4)"));
+        assert_eq!(tab("tab-github").as_deref(), Some("var(--tab-size-github)"));
+        assert_eq!(tab("tab-[12]").as_deref(), Some("12"));
+
+        // 缺少修饰符时丢弃使用 `--modifier` 的声明
+        assert_eq!(
+            utilities.lookup("gap-safe-2.5", None),
+            Some(vec![Declaration::new("gap", "calc(var(--spacing) * 2.5)")])
+        );
+        assert_eq!(utilities.lookup("gap-safe-2.5", Some("50")).unwrap()[1].value, "50");
+        assert_eq!(utilities.lookup("gap-safe-2.3", None), None);
+        assert_eq!(utilities.lookup("gap-safe-[1px]", None), None);
+    }
+
+    #[test]
+    fn test_parse_utility_errors() {
+        assert!(CustomUtility::parse("").is_err());
+        assert!(CustomUtility::parse("color red").is_err());
+        assert!(CustomUtility::parse("&:hover { color: red; }").is_err());
+        let parsed = CustomUtility::parse("content: 'a;b'; color: red").unwrap();
+        assert_eq!(parsed.declarations[0].value, "'a;b'");
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_content() {
+        let a = utilities(&[("a", "color: red"), ("b-*", "width: --value(integer)")]);
+        let b = utilities(&[("b-*", "width: --value(integer)"), ("a", "color: red")]);
+        let c = utilities(&[("a", "color: blue")]);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a.fingerprint(), c.fingerprint());
+    }
+}
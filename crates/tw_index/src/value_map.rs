@@ -1,5 +1,7 @@
 use crate::palette;
-use headwind_core::ColorMode;
+use crate::plugin_map::is_radius_plugin;
+use crate::theme_values;
+use headwind_core::{ColorMode, TailwindVersion};
 use phf::phf_map;
 
 /// 间距关键字映射（非数字的特殊值）
@@ -9,23 +11,7 @@ static SPACING_MAP: phf::Map<&'static str, &'static str> = phf_map! {
     "px" => "1px",
     "auto" => "auto",
 
-    // Fractions (分数)
     "full" => "100%",
-    "1/2" => "50%",
-    "1/3" => "33.333333%",
-    "2/3" => "66.666667%",
-    "1/4" => "25%",
-    "2/4" => "50%",
-    "3/4" => "75%",
-    "1/5" => "20%",
-    "2/5" => "40%",
-    "3/5" => "60%",
-    "4/5" => "80%",
-    "1/6" => "16.666667%",
-    "2/6" => "33.333333%",
-    "3/6" => "50%",
-    "4/6" => "66.666667%",
-    "5/6" => "83.333333%",
 
     // Keywords
     "min" => "min-content",
@@ -37,13 +23,18 @@ static SPACING_MAP: phf::Map<&'static str, &'static str> = phf_map! {
 
 /// 获取间距值
 ///
-/// 优先查静态映射（关键字、分数），其次识别视口单位，最后尝试数字计算 `n * 0.25rem`
+/// 优先查静态映射（关键字），其次识别分数与视口单位，最后尝试数字计算 `n * 0.25rem`
 pub fn get_spacing_value(key: &str) -> Option<String> {
-    // 1. 静态映射：关键字和分数
+    // 1. 静态映射：关键字
     if let Some(&v) = SPACING_MAP.get(key) {
         return Some(v.to_string());
     }
 
+    // 分数：1/2 → 50%, 2/3 → 66.666667%
+    if let Some(v) = get_fraction_value(key) {
+        return Some(v);
+    }
+
     // 2. 视口单位：svh → 100svh, dvw → 100dvw, etc.
     if is_viewport_unit(key) {
         return Some(format!("100{}", key));
@@ -61,6 +52,18 @@ pub fn get_spacing_value(key: &str) -> Option<String> {
     Some(format!("{}rem", rem))
 }
 
+/// 分数转为百分比，保留 6 位小数（与 Tailwind v3 一致）
+fn get_fraction_value(key: &str) -> Option<String> {
+    let (numerator, denominator) = key.split_once('/')?;
+    let numerator: u32 = numerator.parse().ok()?;
+    let denominator: u32 = denominator.parse().ok()?;
+    if denominator == 0 {
+        return None;
+    }
+    let percent = format!("{:.6}", numerator as f64 * 100.0 / denominator as f64);
+    Some(format!("{}%", percent.trim_end_matches('0').trim_end_matches('.')))
+}
+
 /// 判断是否为视口单位关键字(max,min现在无)
 fn is_viewport_unit(key: &str) -> bool {
     matches!(
@@ -91,6 +94,20 @@ pub fn get_opacity_value(key: &str) -> Option<String> {
     Some(format!("{}", n as f64 / 100.0))
 }
 
+/// 获取圆角值，空字符串表示不带尺寸的 `rounded`
+pub fn get_radius_value(key: &str, version: TailwindVersion) -> Option<String> {
+    let sizes = match version {
+        TailwindVersion::V3 => &theme_values::V3_RADIUS_SIZE,
+        TailwindVersion::V4 => &theme_values::RADIUS_SIZE,
+    };
+    match key {
+        "none" => Some("0".to_string()),
+        "full" => Some("9999px".to_string()),
+        "" => sizes.get("DEFAULT").map(|v| v.to_string()),
+        _ => sizes.get(key).map(|v| v.to_string()),
+    }
+}
+
 /// 容器命名尺寸 → CSS 变量
 fn get_container_size(key: &str) -> Option<String> {
     match key {
@@ -131,7 +148,8 @@ pub fn infer_value(plugin: &str, value: &str, color_mode: ColorMode) -> Option<S
         },
 
         // ── Position ─────────────────────────────────────────────
-        "top" | "right" | "bottom" | "left" | "inset" | "inset-x" | "inset-y" => {
+        "top" | "right" | "bottom" | "left" | "start" | "end" | "inset" | "inset-x"
+        | "inset-y" => {
             get_spacing_value(value)
         }
 
@@ -157,6 +175,12 @@ pub fn infer_value(plugin: &str, value: &str, color_mode: ColorMode) -> Option<S
         // ── Color-only plugins ───────────────────────────────────
         "accent" | "caret" | "fill" => get_color_value(value, color_mode),
 
+        // ── Offsets: 数字为 px ───────────────────────────────────
+        "outline-offset" | "underline-offset" => match value {
+            "auto" if plugin == "underline-offset" => Some("auto".to_string()),
+            _ => value.parse::<u32>().ok().map(|n| format!("{}px", n)),
+        },
+
         // ── Opacity ──────────────────────────────────────────────
         "opacity" | "bg-opacity" | "text-opacity" | "border-opacity" => get_opacity_value(value),
 
@@ -164,18 +188,7 @@ pub fn infer_value(plugin: &str, value: &str, color_mode: ColorMode) -> Option<S
         "border-t" | "border-r" | "border-b" | "border-l" => get_spacing_value(value),
 
         // ── Border radius ────────────────────────────────────────
-        "rounded" | "rounded-t" | "rounded-r" | "rounded-b" | "rounded-l" => match value {
-            "none" => Some("0".to_string()),
-            "sm" => Some("0.125rem".to_string()),
-            "" => Some("0.25rem".to_string()),
-            "md" => Some("0.375rem".to_string()),
-            "lg" => Some("0.5rem".to_string()),
-            "xl" => Some("0.75rem".to_string()),
-            "2xl" => Some("1rem".to_string()),
-            "3xl" => Some("1.5rem".to_string()),
-            "full" => Some("9999px".to_string()),
-            _ => None,
-        },
+        p if is_radius_plugin(p) => get_radius_value(value, TailwindVersion::V4),
 
         // ── Layout alignment ─────────────────────────────────────
         "justify" | "justify-items" | "justify-self" | "place-content" | "place-items"
@@ -233,8 +246,11 @@ pub fn infer_value(plugin: &str, value: &str, color_mode: ColorMode) -> Option<S
         // ── Hyphens (passthrough) ────────────────────────────────
         "hyphens" => Some(value.to_string()),
 
-        // ── Appearance (passthrough) ─────────────────────────────
-        "appearance" => Some(value.to_string()),
+        // ── Appearance ───────────────────────────────────────────
+        "appearance" => match value {
+            "none" | "auto" => Some(value.to_string()),
+            _ => None,
+        },
 
         // ── Float ────────────────────────────────────────────────
         "float" => Some(
@@ -273,10 +289,11 @@ pub fn infer_value(plugin: &str, value: &str, color_mode: ColorMode) -> Option<S
         // ── Color scheme ─────────────────────────────────────────
         "scheme" => Some(
             match value {
+                "normal" | "dark" | "light" => value,
                 "light-dark" => "light dark",
                 "only-dark" => "only dark",
                 "only-light" => "only light",
-                _ => value,
+                _ => return None,
             }
             .to_string(),
         ),
@@ -401,11 +418,12 @@ pub fn infer_value(plugin: &str, value: &str, color_mode: ColorMode) -> Option<S
             .to_string(),
         ),
 
-        // ── Flex named values (仅限 shorthand，其余回退到 VALUELESS_MAP) ──
+        // ── Flex shorthand（flex-1 → flex: 1，其余回退到 VALUELESS_MAP）──
         "flex" => match value {
             "auto" | "none" => Some(value.to_string()),
             "initial" => Some("0 auto".to_string()),
-            _ => None,
+            _ if value.contains('/') => get_fraction_value(value),
+            _ => value.parse::<u32>().ok().map(|_| value.to_string()),
         },
 
         // ── Z-index ──────────────────────────────────────────────
@@ -431,9 +449,6 @@ pub fn infer_value(plugin: &str, value: &str, color_mode: ColorMode) -> Option<S
             _ => Some(format!("var(--leading-{})", value)),
         },
 
-        // ── Letter spacing ───────────────────────────────────────
-        "tracking" => Some(format!("var(--tracking-{})", value)),
-
         // ── Duration ─────────────────────────────────────────────
         "duration" => match value {
             "initial" => Some("initial".to_string()),
@@ -511,6 +526,16 @@ mod tests {
         assert_eq!(get_spacing_value("-1"), None);
     }
 
+    #[test]
+    fn test_spacing_fractions() {
+        assert_eq!(get_spacing_value("1/2"), Some("50%".to_string()));
+        assert_eq!(get_spacing_value("2/3"), Some("66.666667%".to_string()));
+        assert_eq!(get_spacing_value("5/12"), Some("41.666667%".to_string()));
+        assert_eq!(get_spacing_value("3/7"), Some("42.857143%".to_string()));
+        assert_eq!(get_spacing_value("1/0"), None);
+        assert_eq!(get_spacing_value("a/2"), None);
+    }
+
     #[test]
     fn test_color_values() {
         assert_eq!(
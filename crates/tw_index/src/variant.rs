@@ -3,6 +3,11 @@
 //! Tailwind variant names often differ from their CSS equivalents.
 //! This module provides a single source of truth for the mapping.
 
+use headwind_core::{BreakpointStyle, BreakpointUnit, MediaQuerySyntax};
+use headwind_tw_parse::Modifier;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
 /// Resolves a pseudo-class variant name to its CSS selector fragment (without leading colon).
 ///
 /// # Examples
@@ -27,6 +32,41 @@ pub fn pseudo_class_selector(name: &str) -> String {
     }
 }
 
+/// Appends a pseudo-class variant to `selector`.
+///
+/// Shared by the converter and both bundler paths so every variant resolves the same way:
+/// parameterized variants (`has-[…]`, `aria-*`, `data-*`, `nth-[…]`, …) become attribute or
+/// functional selectors, `*` / `**` select children / descendants, and everything else goes
+/// through [`pseudo_class_selector`].
+///
+/// - `(".c", "odd")` → `".c:nth-child(odd)"`
+/// - `(".c", "aria-checked")` → `".c[aria-checked=\"true\"]"`
+/// - `(".c", "*")` → `".c > *"`
+/// - `(".c", SPACE_CHILDREN)` → `":where(.c > :not(:last-child))"`
+pub fn apply_pseudo_class(selector: &str, name: &str) -> String {
+    if let Some(param_sel) = parameterized_selector(name) {
+        return format!("{}{}", selector, param_sel);
+    }
+    match name {
+        "*" => format!("{} > *", selector),
+        SPACE_CHILDREN => format!(":where({} > :not(:last-child))", selector),
+        "**" => format!("{} *", selector),
+        _ => format!("{}:{}", selector, pseudo_class_selector(name)),
+    }
+}
+
+/// Internal child variant carried by `space-x-*` / `space-y-*`.
+///
+/// Tailwind puts the spacing on every direct child except the last, wrapped in `:where()` so
+/// the rule keeps zero specificity. The bundler and converter append it after the class's own
+/// variants, so `hover:space-x-2` selects `:where(.c:hover > :not(:last-child))`.
+pub const SPACE_CHILDREN: &str = "*space";
+
+/// Returns the child variant a utility implies on its own (see [`SPACE_CHILDREN`]).
+pub fn utility_child_variant(plugin: &str) -> Option<&'static str> {
+    matches!(plugin, "space-x" | "space-y").then_some(SPACE_CHILDREN)
+}
+
 /// Returns an optional at-rule wrapper for a pseudo-class (Tailwind v4 behavior).
 ///
 /// e.g., `"hover"` → `Some("@media (hover: hover)")` so that hover styles
@@ -52,32 +92,69 @@ pub fn breakpoint_value(size: &str) -> Option<&'static str> {
     }
 }
 
-/// Generates an at-rule for a responsive breakpoint.
+/// Breakpoint names in ascending order (the `container` max-width ladder follows this order).
+pub const BREAKPOINTS: [&str; 5] = ["sm", "md", "lg", "xl", "2xl"];
+
+/// Formats a rem breakpoint in the requested unit (`48rem` → `768px` with 1rem = 16px).
+pub(crate) fn breakpoint_length(rem: &str, unit: BreakpointUnit) -> String {
+    match (unit, rem.strip_suffix("rem").and_then(|n| n.parse::<f64>().ok())) {
+        (BreakpointUnit::Px, Some(n)) => format!("{}px", n * 16.0),
+        _ => rem.to_string(),
+    }
+}
+
+/// Builds a width condition for `@media` / `@container`.
+///
+/// `min` selects a lower bound (`width >= x`), otherwise an exclusive upper bound
+/// (`width < x`). The legacy syntax has no exclusive `max-width`, so upper bounds
+/// are written as a negated `min-width` to keep the same boundary behaviour.
+fn width_at_rule(at_rule: &str, value: &str, min: bool, syntax: MediaQuerySyntax) -> String {
+    match (syntax, min) {
+        (MediaQuerySyntax::Range, true) => format!("{} (width >= {})", at_rule, value),
+        (MediaQuerySyntax::Range, false) => format!("{} (width < {})", at_rule, value),
+        (MediaQuerySyntax::Legacy, true) => format!("{} (min-width: {})", at_rule, value),
+        (MediaQuerySyntax::Legacy, false) if at_rule == "@media" => {
+            format!("@media not all and (min-width: {})", value)
+        }
+        (MediaQuerySyntax::Legacy, false) => format!("{} not (min-width: {})", at_rule, value),
+    }
+}
+
+/// Generates an at-rule for a responsive breakpoint in the default style.
 ///
 /// - `"sm"` → `"@media (width >= 40rem)"`
 /// - `"max-sm"` → `"@media (width < 40rem)"`
 /// - `"min-[800px]"` → `"@media (width >= 800px)"`
 /// - `"max-[800px]"` → `"@media (width < 800px)"`
 pub fn responsive_at_rule(name: &str) -> Option<String> {
+    responsive_at_rule_with(name, BreakpointStyle::default())
+}
+
+/// Generates an at-rule for a responsive breakpoint in the given style.
+///
+/// Arbitrary values (`min-[800px]`) keep their own unit.
+pub fn responsive_at_rule_with(name: &str, style: BreakpointStyle) -> Option<String> {
+    let query = |value: String, min: bool| width_at_rule("@media", &value, min, style.syntax);
+
     // max-* (must check before min-* since "max-sm" etc.)
     if let Some(rest) = name.strip_prefix("max-") {
         if let Some(arb) = extract_bracket(rest) {
-            return Some(format!("@media (width < {})", arb));
+            return Some(query(arb.to_string(), false));
         }
         let bp = breakpoint_value(rest)?;
-        return Some(format!("@media (width < {})", bp));
+        return Some(query(breakpoint_length(bp, style.unit), false));
     }
 
     // min-[...] custom breakpoints
     if let Some(rest) = name.strip_prefix("min-") {
         if let Some(arb) = extract_bracket(rest) {
-            return Some(format!("@media (width >= {})", arb));
+            return Some(query(arb.to_string(), true));
         }
     }
 
     // Standard breakpoints
     let bp = breakpoint_value(name)?;
-    Some(format!("@media (width >= {})", bp))
+    Some(query(breakpoint_length(bp, style.unit), true))
 }
 
 // ── Container queries ────────────────────────────────────────────────────────
@@ -102,31 +179,38 @@ fn container_breakpoint(name: &str) -> Option<&'static str> {
     }
 }
 
-/// Generates an at-rule for a container query variant.
+/// Generates an at-rule for a container query variant in the default style.
 ///
 /// - `"@sm"` → `"@container (width >= 24rem)"`
 /// - `"@max-sm"` → `"@container (width < 24rem)"`
 /// - `"@min-[400px]"` → `"@container (width >= 400px)"`
 pub fn container_at_rule(name: &str) -> Option<String> {
+    container_at_rule_with(name, BreakpointStyle::default())
+}
+
+/// Generates an at-rule for a container query variant in the given style.
+pub fn container_at_rule_with(name: &str, style: BreakpointStyle) -> Option<String> {
+    let query = |value: String, min: bool| width_at_rule("@container", &value, min, style.syntax);
+
     // @max-*
     if let Some(rest) = name.strip_prefix("max-") {
         if let Some(arb) = extract_bracket(rest) {
-            return Some(format!("@container (width < {})", arb));
+            return Some(query(arb.to_string(), false));
         }
         let bp = container_breakpoint(rest)?;
-        return Some(format!("@container (width < {})", bp));
+        return Some(query(breakpoint_length(bp, style.unit), false));
     }
 
     // @min-[...]
     if let Some(rest) = name.strip_prefix("min-") {
         if let Some(arb) = extract_bracket(rest) {
-            return Some(format!("@container (width >= {})", arb));
+            return Some(query(arb.to_string(), true));
         }
     }
 
     // Standard
     let bp = container_breakpoint(name)?;
-    Some(format!("@container (width >= {})", bp))
+    Some(query(breakpoint_length(bp, style.unit), true))
 }
 
 // ── Parameterized variants ───────────────────────────────────────────────────
@@ -145,10 +229,14 @@ pub fn parameterized_selector(name: &str) -> Option<String> {
         return Some(format!(":has({})", unescape_bracket(arg)));
     }
 
-    // not-[...] → :not(...)
+    // not-[...] → :not(...), not-first → :not(:first-child)
     if let Some(rest) = name.strip_prefix("not-") {
-        let arg = extract_bracket(rest)?;
-        return Some(format!(":not({})", unescape_bracket(arg)));
+        if let Some(arg) = extract_bracket(rest) {
+            return Some(format!(":not({})", unescape_bracket(arg)));
+        }
+        let inner = parameterized_selector(rest)
+            .unwrap_or_else(|| format!(":{}", pseudo_class_selector(rest)));
+        return Some(format!(":not({})", inner));
     }
 
     // nth-last-of-type-[...] → :nth-last-of-type(...)
@@ -184,10 +272,12 @@ pub fn parameterized_selector(name: &str) -> Option<String> {
         return Some(format!("[aria-{}=\"true\"]", rest));
     }
 
-    // data-[...] → [data-...]
+    // data-[...] → [data-...], data-active → [data-active]
     if let Some(rest) = name.strip_prefix("data-") {
-        let arg = extract_bracket(rest)?;
-        return Some(format!("[data-{}]", unescape_bracket(arg)));
+        if let Some(arg) = extract_bracket(rest) {
+            return Some(format!("[data-{}]", unescape_bracket(arg)));
+        }
+        return Some(format!("[data-{}]", rest));
     }
 
     // supports-[...] → @supports (not a selector, handled separately)
@@ -241,6 +331,16 @@ pub fn pseudo_element_selector(name: &str) -> String {
     }
 }
 
+/// Moves pseudo-element variants after all other variants, keeping the relative
+/// order otherwise.
+///
+/// A pseudo-element must end the compound selector, so variants written after it
+/// resolve like variants written before it: `before:hover:` and `hover:before:`
+/// both produce `.c:hover::before`, never `.c::before:hover`.
+pub fn pseudo_elements_last<M: Borrow<Modifier>>(modifiers: &mut [M]) {
+    modifiers.sort_by_key(|modifier| matches!(modifier.borrow(), Modifier::PseudoElement(_)));
+}
+
 /// Output of resolving a state variant.
 pub enum StateResolution {
     /// A CSS selector string (e.g., `.dark .{class}`)
@@ -309,30 +409,49 @@ pub fn resolve_state(name: &str, class_selector: &str) -> StateResolution {
         )),
 
         // ── Group / Peer ──
-        name if name.starts_with("group-") => {
-            let pseudo = &name[6..];
-            if let Some(param_sel) = parameterized_selector(pseudo) {
-                StateResolution::Selector(format!(".group{} {}", param_sel, class_selector))
-            } else {
-                let css_pseudo = pseudo_class_selector(pseudo);
-                StateResolution::Selector(format!(".group:{} {}", css_pseudo, class_selector))
-            }
-        }
-        name if name.starts_with("peer-") => {
-            let pseudo = &name[5..];
-            if let Some(param_sel) = parameterized_selector(pseudo) {
-                StateResolution::Selector(format!(".peer{} ~ {}", param_sel, class_selector))
-            } else {
-                let css_pseudo = pseudo_class_selector(pseudo);
-                StateResolution::Selector(format!(".peer:{} ~ {}", css_pseudo, class_selector))
-            }
-        }
+        name if name.starts_with("group-") => StateResolution::Selector(format!(
+            "{} {}",
+            marker_state_selector("group", &name[6..]),
+            class_selector
+        )),
+        name if name.starts_with("peer-") => StateResolution::Selector(format!(
+            "{} ~ {}",
+            marker_state_selector("peer", &name[5..]),
+            class_selector
+        )),
 
         // ── Fallback ──
         _ => StateResolution::Selector(class_selector.to_string()),
     }
 }
 
+/// Builds the `.group` / `.peer` part of a group/peer variant.
+///
+/// - `"hover"` → `".group:hover"`
+/// - `"hover/item"` → `".group\/item:hover"` (named group)
+/// - `"aria-expanded"` → `".group[aria-expanded=\"true\"]"`
+/// - `"[.is-active]"` → `".group.is-active"`; `&` in the argument stands for the marker
+fn marker_state_selector(marker: &str, variant: &str) -> String {
+    let (variant, marker) = match variant.rsplit_once('/') {
+        Some((variant, name)) if !name.contains(']') => {
+            (variant, format!(".{}\\/{}", marker, name))
+        }
+        _ => (variant, format!(".{}", marker)),
+    };
+    if let Some(arg) = extract_bracket(variant) {
+        let arg = unescape_bracket(arg);
+        return if arg.contains('&') {
+            arg.replace('&', &marker)
+        } else {
+            format!("{}{}", marker, arg)
+        };
+    }
+    match parameterized_selector(variant) {
+        Some(param_sel) => format!("{}{}", marker, param_sel),
+        None => format!("{}:{}", marker, pseudo_class_selector(variant)),
+    }
+}
+
 /// Returns the CSS selector suffix for the `marker` pseudo-element.
 ///
 /// `marker` is special: it targets both the element and its children.
@@ -344,10 +463,294 @@ pub fn marker_selectors(class_selector: &str) -> Vec<String> {
     ]
 }
 
+// ── Variant ordering ─────────────────────────────────────────────────────────
+
+/// Canonical variant order (Tailwind v4 registration order).
+///
+/// Rules are emitted in this order, so when several variants match at the same
+/// time the later one wins: `hover` < `focus` < `focus-visible` < `active` <
+/// `disabled`, breakpoints ascend (`sm` < `md` < … < `2xl`), and `dark` comes
+/// after all breakpoints. Entries ending in `-` are prefixes (e.g. `group-*`).
+const VARIANT_ORDER: &[&str] = &[
+    // Child selectors
+    "*", "**",
+    // Compound variants
+    "not-", "group-", "peer-",
+    // Pseudo-elements
+    "first-letter", "first-line", "marker", "selection", "file", "placeholder", "backdrop",
+    "details-content", "before", "after",
+    // Pseudo-classes
+    "first", "last", "only", "odd", "even", "first-of-type", "last-of-type", "only-of-type",
+    "visited", "target", "open", "default", "checked", "indeterminate", "placeholder-shown",
+    "autofill", "optional", "required", "valid", "invalid", "user-valid", "user-invalid",
+    "in-range", "out-of-range", "read-only", "empty", "focus-within", "hover", "focus",
+    "focus-visible", "active", "enabled", "disabled", "inert",
+    // Parameterized pseudo-classes / attributes
+    "in-", "has-", "aria-", "data-", "nth-",
+    // Feature queries
+    "supports-", "motion-safe", "motion-reduce", "contrast-more", "contrast-less",
+    // Breakpoints: max-* descending, then min-width ascending
+    "max-", "max-2xl", "max-xl", "max-lg", "max-md", "max-sm", "sm", "md", "lg", "xl", "2xl",
+    "min-",
+    // Container queries
+    "@max-", "@max-7xl", "@max-6xl", "@max-5xl", "@max-4xl", "@max-3xl", "@max-2xl",
+    "@max-xl", "@max-lg", "@max-md", "@max-sm", "@max-xs", "@max-2xs", "@max-3xs", "@3xs",
+    "@2xs", "@xs", "@sm", "@md", "@lg", "@xl", "@2xl", "@3xl", "@4xl", "@5xl", "@6xl", "@7xl",
+    "@min-",
+    // Media features and color scheme
+    "portrait", "landscape", "ltr", "rtl", "dark", "starting", "print", "forced-colors",
+    "inverted-colors", "pointer-none", "pointer-coarse", "pointer-fine", "any-pointer-none",
+    "any-pointer-coarse", "any-pointer-fine", "noscript",
+];
+
+/// Returns the position of a variant in the canonical order.
+///
+/// Exact names take precedence over prefix entries (`in-range` vs `in-[...]`).
+/// Unknown variants sort after all known ones.
+pub fn variant_order(name: &str) -> usize {
+    VARIANT_ORDER
+        .iter()
+        .position(|v| *v == name)
+        .or_else(|| {
+            VARIANT_ORDER
+                .iter()
+                .position(|v| v.ends_with('-') && name.starts_with(v))
+        })
+        .unwrap_or(VARIANT_ORDER.len())
+}
+
+/// Sort key for a stacked modifier list.
+///
+/// Mirrors Tailwind's variant bitmask comparison: the highest-ranked variant
+/// decides first, so `md:hover:` sorts after every `hover:`/`active:` rule and
+/// after plain `md:`. The empty list (base rule) sorts first.
+pub fn variant_sort_key(modifiers: &[Modifier]) -> Vec<usize> {
+    let mut key: Vec<usize> = modifiers
+        .iter()
+        .map(|modifier| variant_order(modifier_name(modifier)))
+        .collect();
+    key.sort_unstable_by(|a, b| b.cmp(a));
+    key
+}
+
+pub(crate) fn modifier_name(modifier: &Modifier) -> &str {
+    match modifier {
+        Modifier::Responsive(name)
+        | Modifier::PseudoClass(name)
+        | Modifier::PseudoElement(name)
+        | Modifier::State(name)
+        | Modifier::Custom(name) => name,
+    }
+}
+
+/// Expands user-defined variant aliases in a class.
+///
+/// With `hocus` → `["hover", "focus"]`, `md:hocus:bg-red-500` expands to
+/// `md:hover:bg-red-500` and `md:focus:bg-red-500`; several aliases in one class expand to
+/// every combination. Returns `None` when the class uses no alias. Colons inside brackets
+/// or parentheses (arbitrary variants and values) are not variant separators.
+pub fn expand_variant_aliases(
+    class: &str,
+    aliases: &HashMap<String, Vec<String>>,
+) -> Option<Vec<String>> {
+    if aliases.is_empty() {
+        return None;
+    }
+    let segments = split_variant_segments(class);
+    let (utility, variants) = segments.split_last()?;
+    if !variants.iter().any(|variant| aliases.contains_key(*variant)) {
+        return None;
+    }
+    let mut prefixes = vec![String::new()];
+    for variant in variants {
+        let choices = match aliases.get(*variant) {
+            Some(expansion) => expansion.iter().map(String::as_str).collect(),
+            None => vec![*variant],
+        };
+        prefixes = prefixes
+            .iter()
+            .flat_map(|prefix| choices.iter().map(move |choice| format!("{}{}:", prefix, choice)))
+            .collect();
+    }
+    Some(prefixes.into_iter().map(|prefix| prefix + utility).collect())
+}
+
+/// Splits a class at top-level colons: `md:[&:hover]:p-4` → `["md", "[&:hover]", "p-4"]`.
+fn split_variant_segments(class: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ':' if depth == 0 => {
+                segments.push(&class[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&class[start..]);
+    segments
+}
+
+// ── Custom variants ──────────────────────────────────────────────────────────
+
+/// A project-defined variant from a Tailwind v4 `@custom-variant` directive.
+///
+/// - `(&:where(.dark, .dark *))` → selector template `&:where(.dark, .dark *)`
+/// - `(@media (pointer: coarse))` → at-rule `@media (pointer: coarse)`
+/// - `{ @media (any-hover: hover) { &:hover { @slot; } } }` → both
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomVariant {
+    /// At-rules wrapping the rule, outermost first.
+    pub at_rules: Vec<String>,
+    /// Selector template where `&` stands for the class selector; `None` keeps the selector.
+    pub selector: Option<String>,
+}
+
+impl CustomVariant {
+    /// Parses a variant definition: the part of `@custom-variant <name> …` after the name.
+    ///
+    /// Accepts the shorthand form with or without its parentheses and the block form ending
+    /// in `@slot;`. A block may nest any number of at-rules and selectors, but only one
+    /// `@slot`.
+    pub fn parse(definition: &str) -> Result<Self, String> {
+        let definition = definition.trim().trim_end_matches(';').trim_end();
+        if let Some(body) = definition.strip_prefix('{').and_then(|d| d.strip_suffix('}')) {
+            let mut variant = CustomVariant { at_rules: Vec::new(), selector: None };
+            variant.parse_block(body)?;
+            return Ok(variant);
+        }
+
+        let shorthand = match definition.strip_prefix('(') {
+            Some(inner) if matching_close(definition, 0) == Some(definition.len() - 1) => {
+                &inner[..inner.len() - 1]
+            }
+            _ => definition,
+        };
+        let shorthand = shorthand.trim();
+        if shorthand.is_empty() {
+            return Err("empty variant definition".to_string());
+        }
+        Ok(if shorthand.starts_with('@') {
+            CustomVariant { at_rules: vec![shorthand.to_string()], selector: None }
+        } else if shorthand.contains('&') {
+            CustomVariant { at_rules: Vec::new(), selector: Some(shorthand.to_string()) }
+        } else {
+            return Err(format!("selector `{}` has no `&`", shorthand));
+        })
+    }
+
+    /// Walks nested blocks down to `@slot`, collecting at-rules and composing selectors.
+    fn parse_block(&mut self, body: &str) -> Result<(), String> {
+        let body = body.trim();
+        if body.trim_end_matches(';').trim_end() == "@slot" {
+            return Ok(());
+        }
+        let open = body.find('{').ok_or("block without `@slot`")?;
+        let close = matching_close(body, open).ok_or("unbalanced `{` in variant block")?;
+        if !body[close + 1..].trim().is_empty() {
+            return Err("only one nested rule with `@slot` is supported".to_string());
+        }
+        let prelude = body[..open].trim();
+        if prelude.starts_with('@') {
+            self.at_rules.push(prelude.to_string());
+        } else if prelude.contains('&') {
+            let outer = self.selector.as_deref().unwrap_or("&");
+            self.selector = Some(prelude.replace('&', outer));
+        } else {
+            return Err(format!("selector `{}` has no `&`", prelude));
+        }
+        self.parse_block(&body[open + 1..close])
+    }
+
+    /// Applies the selector template to `selector`: `&:where(.dark, .dark *)` on `.c` →
+    /// `.c:where(.dark, .dark *)`.
+    pub fn apply(&self, selector: &str) -> String {
+        match &self.selector {
+            Some(template) => template.replace('&', selector),
+            None => selector.to_string(),
+        }
+    }
+}
+
+/// Byte index of the bracket closing the one at `open` (`(` or `{`).
+pub(crate) fn matching_close(s: &str, open: usize) -> Option<usize> {
+    let (open_ch, close_ch) = match s.as_bytes().get(open)? {
+        b'(' => (b'(', b')'),
+        b'{' => (b'{', b'}'),
+        _ => return None,
+    };
+    let mut depth = 0usize;
+    for (i, &b) in s.as_bytes().iter().enumerate().skip(open) {
+        if b == open_ch {
+            depth += 1;
+        } else if b == close_ch {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the entries of a variant-keyed map in canonical variant order.
+pub fn sorted_by_variant<V>(map: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by_cached_key(|(name, _)| (variant_order(name), name.as_str()));
+    entries
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_expand_variant_aliases() {
+        let aliases = HashMap::from([
+            ("hocus".to_string(), vec!["hover".to_string(), "focus".to_string()]),
+            ("mobile".to_string(), vec!["max-md".to_string()]),
+        ]);
+        assert_eq!(
+            expand_variant_aliases("md:hocus:bg-red-500", &aliases).unwrap(),
+            ["md:hover:bg-red-500", "md:focus:bg-red-500"]
+        );
+        assert_eq!(
+            expand_variant_aliases("mobile:hocus:p-4", &aliases).unwrap(),
+            ["max-md:hover:p-4", "max-md:focus:p-4"]
+        );
+        assert_eq!(expand_variant_aliases("hover:p-4", &aliases), None);
+        assert_eq!(expand_variant_aliases("[&:hocus]:p-4", &aliases), None);
+        assert_eq!(expand_variant_aliases("hocus", &aliases), None);
+    }
+
+    #[test]
+    fn test_custom_variant_definitions() {
+        let dark = CustomVariant::parse("(&:where(.dark, .dark *))").unwrap();
+        assert_eq!(dark.at_rules, Vec::<String>::new());
+        assert_eq!(dark.apply(".c"), ".c:where(.dark, .dark *)");
+        assert_eq!(CustomVariant::parse("&:where(.dark, .dark *)").unwrap(), dark);
+
+        let coarse = CustomVariant::parse("(@media (pointer: coarse));").unwrap();
+        assert_eq!(coarse.at_rules, ["@media (pointer: coarse)"]);
+        assert_eq!(coarse.apply(".c"), ".c");
+
+        let block = CustomVariant::parse(
+            "{ @media (any-hover: hover) { &:hover { [data-theme=midnight] & { @slot; } } } }",
+        )
+        .unwrap();
+        assert_eq!(block.at_rules, ["@media (any-hover: hover)"]);
+        assert_eq!(block.apply(".c"), "[data-theme=midnight] .c:hover");
+
+        assert!(CustomVariant::parse("()").is_err());
+        assert!(CustomVariant::parse("(.dark)").is_err());
+        assert!(CustomVariant::parse("{ &:hover { color: red; } }").is_err());
+        assert!(CustomVariant::parse("{ &:hover { @slot; } &:focus { @slot; } }").is_err());
+    }
+
     // ── Pseudo-class ──
 
     #[test]
@@ -384,6 +787,24 @@ mod tests {
         assert_eq!(pseudo_element_selector("placeholder"), "placeholder");
     }
 
+    #[test]
+    fn test_pseudo_elements_last() {
+        let mut modifiers = vec![
+            Modifier::PseudoElement("before".into()),
+            Modifier::Responsive("md".into()),
+            Modifier::PseudoClass("hover".into()),
+        ];
+        pseudo_elements_last(&mut modifiers);
+        assert_eq!(
+            modifiers,
+            vec![
+                Modifier::Responsive("md".into()),
+                Modifier::PseudoClass("hover".into()),
+                Modifier::PseudoElement("before".into()),
+            ]
+        );
+    }
+
     #[test]
     fn test_marker_selectors() {
         let sels = marker_selectors(".c");
@@ -432,6 +853,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_state_group_named_and_arbitrary() {
+        let selector = |name: &str| match resolve_state(name, ".c") {
+            StateResolution::Selector(s) => s,
+            _ => panic!("expected Selector"),
+        };
+        assert_eq!(selector("group-hover/item"), ".group\\/item:hover .c");
+        assert_eq!(selector("group-[.is-active]"), ".group.is-active .c");
+        assert_eq!(selector("group-[&:nth-child(2)]"), ".group:nth-child(2) .c");
+        assert_eq!(selector("peer-[.x]"), ".peer.x ~ .c");
+        assert_eq!(selector("group-data-active"), ".group[data-active] .c");
+        assert_eq!(
+            selector("peer-aria-checked/opt"),
+            ".peer\\/opt[aria-checked=\"true\"] ~ .c"
+        );
+    }
+
+    #[test]
+    fn test_named_not_and_data() {
+        assert_eq!(parameterized_selector("not-first").unwrap(), ":not(:first-child)");
+        assert_eq!(parameterized_selector("not-data-active").unwrap(), ":not([data-active])");
+        assert_eq!(parameterized_selector("data-active").unwrap(), "[data-active]");
+    }
+
     #[test]
     fn test_group_with_shorthand() {
         match resolve_state("group-first", ".c") {
@@ -440,7 +885,34 @@ mod tests {
         }
     }
 
-    // ── Responsive breakpoints ──
+    // ── Variant ordering ──
+
+    #[test]
+    fn test_variant_order_interaction_states() {
+        let order = ["hover", "focus", "focus-visible", "active", "disabled"].map(variant_order);
+        assert!(order.windows(2).all(|w| w[0] < w[1]));
+        assert!(variant_order("before") < variant_order("hover"));
+        assert!(variant_order("group-hover") < variant_order("hover"));
+        assert!(variant_order("in-range") < variant_order("in-[.x]"));
+    }
+
+    #[test]
+    fn test_variant_order_breakpoints_and_dark() {
+        let order =
+            ["max-md", "sm", "md", "lg", "xl", "2xl", "@sm", "@lg", "dark"].map(variant_order);
+        assert!(order.windows(2).all(|w| w[0] < w[1]));
+        assert!(variant_order("unknown") > variant_order("noscript"));
+    }
+
+    #[test]
+    fn test_variant_sort_key_stacked() {
+        let key = |raw: &str| variant_sort_key(&headwind_tw_parse::parse_modifiers_from_raw(raw));
+        assert!(key("") < key("hover:"));
+        assert!(key("active:") < key("md:"));
+        assert!(key("md:") < key("md:hover:"));
+        assert_eq!(key("md:hover:"), key("hover:md:"));
+    }
+
 
     #[test]
     fn test_responsive_at_rule() {
@@ -478,6 +950,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_responsive_px_and_legacy_styles() {
+        let px = BreakpointStyle {
+            unit: BreakpointUnit::Px,
+            ..Default::default()
+        };
+        let legacy = BreakpointStyle {
+            unit: BreakpointUnit::Px,
+            syntax: MediaQuerySyntax::Legacy,
+        };
+        assert_eq!(responsive_at_rule_with("md", px).unwrap(), "@media (width >= 768px)");
+        assert_eq!(responsive_at_rule_with("md", legacy).unwrap(), "@media (min-width: 768px)");
+        assert_eq!(
+            responsive_at_rule_with("max-sm", legacy).unwrap(),
+            "@media not all and (min-width: 640px)"
+        );
+        // Arbitrary values keep their own unit
+        assert_eq!(
+            responsive_at_rule_with("min-[50em]", legacy).unwrap(),
+            "@media (min-width: 50em)"
+        );
+        assert_eq!(
+            container_at_rule_with("max-md", legacy).unwrap(),
+            "@container not (min-width: 448px)"
+        );
+    }
+
     // ── Container queries ──
 
     #[test]
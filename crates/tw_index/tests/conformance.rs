@@ -0,0 +1,314 @@
+//! 与 Tailwind CSS 实际输出的一致性测试
+//!
+//! `fixtures/conformance/classes.txt` 列出参与比较的类名，`tailwind.css` 是同一批类名的
+//! Tailwind 参考输出（由 `tools/scripts/generate-conformance-css.mjs` 调用 Tailwind CLI 生成）。
+//! 每个类的声明经过归一化后与转换器的结果按集合比较：
+//!
+//! - 参考输出中 `:root` / `:host` 定义的主题变量展开为具体值，两侧使用同一份主题
+//! - `calc(<长度> * <数字>)` 求值（数字可以是 `1/2` 这样的分数），数字格式统一（`.25rem` 与 `0.25rem` 等价）
+//! - Tailwind 内部的 `--tw-*` 声明和 `!important` 不参与比较
+//!
+//! 只比较单个类选择器的基础规则，带子选择器或 at-rule 的规则不计入。
+
+use headwind_core::ColorMode;
+use headwind_tw_index::Converter;
+use headwind_tw_parse::parse_class;
+use std::collections::{BTreeSet, HashMap};
+
+const CLASSES: &str = include_str!("../fixtures/conformance/classes.txt");
+const REFERENCE_CSS: &str = include_str!("../fixtures/conformance/tailwind.css");
+
+/// 一致率下限（%）；提高转换质量后相应调高，防止回退
+const MIN_CONFORMANCE: f64 = 99.5;
+
+type Declarations = BTreeSet<(String, String)>;
+
+/// 参考输出：主题变量与每个类的基础声明
+#[derive(Default)]
+struct Reference {
+    theme: HashMap<String, String>,
+    rules: HashMap<String, Vec<(String, String)>>,
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = rest[start + 2..].find("*/").map_or("", |end| &rest[start + 2 + end + 2..]);
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 与 `open` 处的左括号（`{` 或 `(`）匹配的右括号位置
+fn matching_close(css: &str, open: usize) -> Option<usize> {
+    let (left, right) = match css[open..].chars().next()? {
+        '(' => ('(', ')'),
+        _ => ('{', '}'),
+    };
+    let mut depth = 0;
+    for (i, c) in css[open..].char_indices() {
+        match c {
+            c if c == left => depth += 1,
+            c if c == right => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 单个类选择器（`.md\:p-4` → `md:p-4`）；带组合器、伪类等的选择器返回 None
+fn simple_class_selector(selector: &str) -> Option<String> {
+    let mut chars = selector.strip_prefix('.')?.chars();
+    let mut class = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => class.push(chars.next()?),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => class.push(c),
+            _ => return None,
+        }
+    }
+    (!class.is_empty()).then_some(class)
+}
+
+fn parse_declarations(body: &str) -> Vec<(String, String)> {
+    body.split(';')
+        .filter_map(|decl| decl.split_once(':'))
+        .map(|(property, value)| (property.trim().to_string(), value.trim().to_string()))
+        .filter(|(property, value)| !property.is_empty() && !value.is_empty())
+        .collect()
+}
+
+fn parse_blocks(css: &str, reference: &mut Reference) {
+    let mut rest = css;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = matching_close(rest, open) else {
+            return;
+        };
+        // `@layer theme, base;` 之类的语句没有块，取最后一个 `;` 之后的部分
+        let prelude = rest[..open].rsplit(';').next().unwrap_or_default().trim();
+        let body = &rest[open + 1..close];
+        if prelude.starts_with("@layer") {
+            parse_blocks(body, reference);
+        } else if prelude.split(',').all(|s| matches!(s.trim(), ":root" | ":host")) {
+            for (name, value) in parse_declarations(body) {
+                reference.theme.insert(name, value);
+            }
+        } else if let Some(class) = simple_class_selector(prelude) {
+            if !body.contains('{') {
+                reference.rules.insert(class, parse_declarations(body));
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+}
+
+fn parse_reference(css: &str) -> Reference {
+    let mut reference = Reference::default();
+    parse_blocks(&strip_comments(css), &mut reference);
+    reference
+}
+
+/// 展开主题中定义的 `var()`；未定义且没有回退值的变量原样保留
+fn resolve_vars(value: &str, theme: &HashMap<String, String>, depth: usize) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("var(") {
+        out.push_str(&rest[..start]);
+        let Some(close) = matching_close(rest, start + 3) else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let inner = &rest[start + 4..close];
+        let (name, fallback) = match inner.split_once(',') {
+            Some((name, fallback)) => (name.trim(), Some(fallback.trim())),
+            None => (inner.trim(), None),
+        };
+        match theme.get(name).map(String::as_str).or(fallback) {
+            Some(resolved) if depth < 8 => out.push_str(&resolve_vars(resolved, theme, depth + 1)),
+            _ => out.push_str(&rest[start..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `1.50rem` / `.25rem` / `0.25rem` 等数字统一格式
+fn canonical_number(token: &str) -> String {
+    let split = token
+        .char_indices()
+        .find(|(i, c)| !(c.is_ascii_digit() || *c == '.' || (*i == 0 && (*c == '-' || *c == '+'))))
+        .map_or(token.len(), |(i, _)| i);
+    let (number, unit) = token.split_at(split);
+    match number.parse::<f64>() {
+        Ok(n) if unit.chars().all(|c| c.is_ascii_alphabetic() || c == '%') => {
+            format!("{}{}", n, unit)
+        }
+        _ => token.to_string(),
+    }
+}
+
+/// 拆分为数字与单位：`0.25rem` → `(0.25, "rem")`，分数 `1/2` → `(0.5, "")`
+fn split_unit(s: &str) -> Option<(f64, &str)> {
+    if let Some((numerator, denominator)) = s.split_once('/') {
+        let fraction = numerator.parse::<f64>().ok()? / denominator.parse::<f64>().ok()?;
+        return Some((fraction, ""));
+    }
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(s.len());
+    s[..split].parse::<f64>().ok().map(|n| (n, &s[split..]))
+}
+
+/// `calc(<数字><单位> * <数字>)`（任意顺序）求值为具体长度
+fn eval_calc(value: &str) -> String {
+    let Some(inner) = value.strip_prefix("calc(").and_then(|v| v.strip_suffix(')')) else {
+        return value.to_string();
+    };
+    let Some((a, b)) = inner.split_once(" * ") else {
+        return value.to_string();
+    };
+    match (split_unit(a.trim()), split_unit(b.trim())) {
+        (Some((x, unit)), Some((y, ""))) | (Some((y, "")), Some((x, unit))) => {
+            format!("{}{}", x * y, unit)
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn normalize_value(value: &str, theme: &HashMap<String, String>) -> String {
+    let value = value.trim_end_matches("!important").trim();
+    let value = resolve_vars(value, theme, 0).to_ascii_lowercase();
+    let mut normalized = String::new();
+    let mut token = String::new();
+    for c in value.chars().chain(std::iter::once(' ')) {
+        if c.is_whitespace() || matches!(c, ',' | '(' | ')' | '/') {
+            normalized.push_str(&canonical_number(&token));
+            token.clear();
+            match c {
+                c if c.is_whitespace() => {
+                    if !normalized.ends_with([' ', '(', ',']) && !normalized.is_empty() {
+                        normalized.push(' ');
+                    }
+                }
+                ')' | ',' => {
+                    let trimmed = normalized.trim_end().len();
+                    normalized.truncate(trimmed);
+                    normalized.push(c);
+                }
+                _ => normalized.push(c),
+            }
+        } else {
+            token.push(c);
+        }
+    }
+    eval_calc(normalized.trim())
+}
+
+fn normalize(declarations: &[(String, String)], theme: &HashMap<String, String>) -> Declarations {
+    declarations
+        .iter()
+        .filter(|(property, _)| !property.starts_with("--tw-"))
+        .map(|(property, value)| (property.to_ascii_lowercase(), normalize_value(value, theme)))
+        .collect()
+}
+
+fn class_list() -> Vec<&'static str> {
+    CLASSES
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+#[test]
+fn test_reference_parsing_and_normalization() {
+    let reference = parse_reference(
+        "@layer theme, utilities;\n@layer theme { :root, :host { --spacing: .25rem; } }\n\
+         @layer utilities { .p-4 { padding: calc(var(--spacing) * 4); }\n\
+         .md\\:flex { display: flex; } .space-x-2 > :not(:last-child) { margin: 0; }\n\
+         @media (width >= 48rem) { .x { color: red; } } }",
+    );
+    assert_eq!(reference.theme["--spacing"], ".25rem");
+    assert!(reference.rules.contains_key("md:flex"));
+    assert!(!reference.rules.contains_key("space-x-2"));
+    assert!(!reference.rules.contains_key("x"));
+
+    let theme = &reference.theme;
+    let expected: Declarations = [("padding".to_string(), "1rem".to_string())].into();
+    assert_eq!(normalize(&reference.rules["p-4"], theme), expected);
+    assert_eq!(
+        normalize(&[("padding".into(), "1rem !important".into())], theme),
+        expected
+    );
+    assert_eq!(
+        normalize_value("rgb( 0 0 0 / 0.5 )", theme),
+        normalize_value("rgb(0 0 0 / .5)", theme)
+    );
+    assert_eq!(normalize_value("calc(1/4 * 100%)", theme), "25%");
+}
+
+#[test]
+fn test_conformance_with_tailwind_output() {
+    let reference = parse_reference(REFERENCE_CSS);
+    let converter = Converter::new().with_color_mode(ColorMode::Var);
+
+    let mut matched = 0;
+    let mut mismatched = Vec::new();
+    let mut unsupported = Vec::new();
+    let mut not_comparable = 0;
+
+    for class in class_list() {
+        let Some(expected) = reference.rules.get(class) else {
+            not_comparable += 1;
+            continue;
+        };
+        let expected = normalize(expected, &reference.theme);
+        let actual = parse_class(class)
+            .ok()
+            .and_then(|parsed| converter.to_declarations(&parsed));
+        let Some(actual) = actual else {
+            unsupported.push(class);
+            continue;
+        };
+        let actual: Vec<(String, String)> = actual
+            .into_iter()
+            .map(|decl| (decl.property, decl.value))
+            .collect();
+        let actual = normalize(&actual, &reference.theme);
+        if actual == expected {
+            matched += 1;
+        } else {
+            mismatched.push((class, expected, actual));
+        }
+    }
+
+    let compared = matched + mismatched.len() + unsupported.len();
+    assert!(compared > 0, "参考输出中没有可比较的类");
+    let conformance = matched as f64 / compared as f64 * 100.0;
+
+    println!("\nTailwind 一致性：{}/{} ({:.1}%)", matched, compared, conformance);
+    println!("  声明不一致：{}", mismatched.len());
+    println!("  无法转换：{}", unsupported.len());
+    println!("  不参与比较（参考输出中没有单类基础规则）：{}", not_comparable);
+    for (class, expected, actual) in mismatched.iter().take(20) {
+        println!("  ✗ {}\n      tailwind: {:?}\n      headwind: {:?}", class, expected, actual);
+    }
+    if !unsupported.is_empty() {
+        println!("  无法转换的类：{}", unsupported.join(" "));
+    }
+
+    assert!(
+        conformance >= MIN_CONFORMANCE,
+        "一致率 {:.1}% 低于下限 {:.1}%",
+        conformance,
+        MIN_CONFORMANCE
+    );
+}
@@ -0,0 +1,107 @@
+//! 转换器的属性测试：任意类名都不能 panic，生成的 CSS 结构合法
+
+use headwind_tw_index::{Bundler, ColorMode};
+use proptest::prelude::*;
+
+/// 由修饰符、插件与各类值拼成的类名，覆盖任意值、CSS 变量与透明度等分支
+fn class_like() -> impl Strategy<Value = String> {
+    let modifier = prop_oneof![
+        "(hover|focus|md|dark|group-hover|peer-checked|before|\\*|@md|supports-\\[display:grid\\])",
+        "(data|aria|has|not|nth|min|max|group|peer)-\\[[^\\]\\s]{0,8}\\]",
+    ];
+    let value = prop_oneof![
+        "[a-z0-9.-]{0,8}",
+        "\\[[^\\s]{0,12}\\]",
+        "\\((--)?[a-z:-]{0,8}\\)",
+    ];
+    (
+        prop::collection::vec(modifier, 0..3),
+        "-?(p|m|w|bg|text|border|rounded|shadow|grid-cols|content|font|inset|translate-x|[a-z]{1,8})",
+        prop::option::of(value),
+        prop::option::of("/([0-9]{1,3}|\\[[^\\s]{0,6}\\]|\\(--[a-z]{0,4}\\))"),
+        "!?",
+    )
+        .prop_map(|(modifiers, plugin, value, alpha, important)| {
+            let mut class: String = modifiers.iter().map(|m| format!("{}:", m)).collect();
+            class.push_str(&plugin);
+            if let Some(value) = value {
+                class.push('-');
+                class.push_str(&value);
+            }
+            class.push_str(&alpha.unwrap_or_default());
+            class.push_str(&important);
+            class
+        })
+}
+
+/// (内联值, 颜色模式, color-mix)
+type Options = (bool, ColorMode, bool);
+
+fn options() -> impl Strategy<Value = Options> {
+    (
+        any::<bool>(),
+        prop_oneof![
+            Just(ColorMode::Hex),
+            Just(ColorMode::Oklch),
+            Just(ColorMode::Hsl),
+            Just(ColorMode::Var),
+        ],
+        any::<bool>(),
+    )
+}
+
+fn bundler((inline, color_mode, color_mix): Options) -> Bundler {
+    let bundler = if inline {
+        Bundler::with_inline()
+    } else {
+        Bundler::new()
+    };
+    bundler.with_color_mode(color_mode).with_color_mix(color_mix)
+}
+
+/// 检查 CSS 结构：花括号配对，块内每行都是以 `;` 结尾的 `property: value` 声明
+fn assert_well_formed(css: &str) -> Result<(), TestCaseError> {
+    let mut depth = 0usize;
+    for line in css.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if line == "}" {
+            prop_assert!(depth > 0, "unbalanced `}}` in:\n{}", css);
+            depth -= 1;
+        } else if let Some(prelude) = line.strip_suffix('{') {
+            prop_assert!(!prelude.trim().is_empty(), "empty selector in:\n{}", css);
+            depth += 1;
+        } else {
+            prop_assert!(depth > 0, "declaration outside of a block: {:?}", line);
+            let (property, value) = line
+                .strip_suffix(';')
+                .and_then(|decl| decl.split_once(": "))
+                .ok_or_else(|| TestCaseError::fail(format!("malformed declaration: {:?}", line)))?;
+            prop_assert!(!property.is_empty() && !value.trim().is_empty(), "{:?}", line);
+        }
+    }
+    prop_assert_eq!(depth, 0, "unclosed block in:\n{}", css);
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn bundle_never_panics_on_arbitrary_input(options in options(), input in "\\PC{0,24}") {
+        let bundler = bundler(options);
+        let _ = bundler.bundle_to_css("c", &input, "  ");
+        let _ = bundler.is_recognized(&input);
+    }
+
+    #[test]
+    fn generated_css_is_well_formed(
+        options in options(),
+        classes in prop::collection::vec(class_like(), 1..4),
+    ) {
+        let bundler = bundler(options);
+        let classes = classes.join(" ");
+        if let Ok(css) = bundler.bundle_to_css("c", &classes, "  ") {
+            assert_well_formed(&css)?;
+        }
+        if let Ok(group) = bundler.bundle(&classes) {
+            assert_well_formed(&bundler.generate_css("c", &group, "  "))?;
+        }
+    }
+}
@@ -2,5 +2,5 @@ pub mod parser;
 pub mod types;
 
 // Re-export main types
-pub use parser::{parse_class, parse_classes};
+pub use parser::{parse_class, parse_classes, parse_classes_with_spans, ParseError, SpannedClass};
 pub use types::{parse_modifiers_from_raw, ArbitraryValue, CssVariableValue, Modifier, ParsedClass, ParsedValue};
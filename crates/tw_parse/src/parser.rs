@@ -1,4 +1,6 @@
 use crate::types::{ArbitraryValue, CssVariableValue, ParsedClass, ParsedValue};
+use std::borrow::Cow;
+use std::ops::Range;
 
 /// 解析包含多个 Tailwind class 的字符串
 ///
@@ -25,7 +27,7 @@ use crate::types::{ArbitraryValue, CssVariableValue, ParsedClass, ParsedValue};
 /// assert_eq!(parsed[1].plugin, "bg");
 /// assert_eq!(parsed[2].plugin, "text");
 /// ```
-pub fn parse_classes(input: &str) -> Result<Vec<ParsedClass>, ParseError> {
+pub fn parse_classes(input: &str) -> Result<Vec<ParsedClass<'_>>, ParseError> {
     if input.is_empty() {
         return Err(ParseError::EmptyInput);
     }
@@ -71,10 +73,10 @@ pub fn parse_classes(input: &str) -> Result<Vec<ParsedClass>, ParseError> {
 /// let parsed = parse_class("md:hover:bg-blue-500/50!").unwrap();
 /// assert_eq!(parsed.modifiers().len(), 2);
 /// assert_eq!(parsed.plugin, "bg");
-/// assert_eq!(parsed.alpha, Some("50".to_string()));
+/// assert_eq!(parsed.alpha, Some("50".into()));
 /// assert_eq!(parsed.important, true);
 /// ```
-pub fn parse_class(input: &str) -> Result<ParsedClass, ParseError> {
+pub fn parse_class(input: &str) -> Result<ParsedClass<'_>, ParseError> {
     if input.is_empty() {
         return Err(ParseError::EmptyInput);
     }
@@ -83,6 +85,50 @@ pub fn parse_class(input: &str) -> Result<ParsedClass, ParseError> {
     parser.parse()
 }
 
+/// 带位置的解析结果，见 `parse_classes_with_spans`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedClass<'a> {
+    /// 类名在输入中的字节区间
+    pub span: Range<usize>,
+    /// 原始类名（即 `&input[span]`）
+    pub raw: &'a str,
+    /// 该类名的解析结果，单个类名解析失败不影响其他类名
+    pub parsed: Result<ParsedClass<'a>, ParseError>,
+}
+
+/// 解析 class 属性值，保留每个类名在原字符串中的字节区间
+///
+/// 与 `parse_classes` 不同，某个类名解析失败时不会中止，错误记录在对应条目上，
+/// 编辑器可以据此只标出出错（或索引中不存在）的那个类名。空白输入返回空列表。
+///
+/// # 示例
+///
+/// ```
+/// use headwind_tw_parse::parse_classes_with_spans;
+///
+/// let input = "p-4  hover:bg-blue-500 w-[13px";
+/// let classes = parse_classes_with_spans(input);
+/// assert_eq!(classes.len(), 3);
+/// assert_eq!(classes[1].span, 5..22);
+/// assert_eq!(&input[classes[1].span.clone()], "hover:bg-blue-500");
+/// assert_eq!(classes[1].parsed.as_ref().unwrap().plugin, "bg");
+/// assert!(classes[2].parsed.is_err());
+/// ```
+pub fn parse_classes_with_spans(input: &str) -> Vec<SpannedClass<'_>> {
+    input
+        .split_whitespace()
+        .map(|raw| {
+            // split_whitespace 返回输入的子切片，由指针差得到偏移
+            let start = raw.as_ptr() as usize - input.as_ptr() as usize;
+            SpannedClass {
+                span: start..start + raw.len(),
+                raw,
+                parsed: Parser::new(raw).parse(),
+            }
+        })
+        .collect()
+}
+
 /// 解析错误类型
 #[derive(Debug, Clone, PartialEq)]
 pub enum ParseError {
@@ -118,15 +164,11 @@ impl<'a> Parser<'a> {
         Self { input, pos: 0 }
     }
 
-    fn parse(&mut self) -> Result<ParsedClass, ParseError> {
+    fn parse(&mut self) -> Result<ParsedClass<'a>, ParseError> {
         // 1. 捕获原始修饰符字符串（modifier:modifier:...）
         let modifier_start = self.pos;
         self.skip_modifiers();
-        let raw_modifiers = if modifier_start < self.pos {
-            self.input[modifier_start..self.pos].to_string()
-        } else {
-            String::new()
-        };
+        let raw_modifiers = Cow::Borrowed(&self.input[modifier_start..self.pos]);
 
         // 2. 解析负值标记
         let negative = self.consume_if('-');
@@ -152,7 +194,7 @@ impl<'a> Parser<'a> {
         Ok(ParsedClass {
             raw_modifiers,
             negative,
-            plugin,
+            plugin: Cow::Borrowed(plugin),
             value,
             alpha,
             important,
@@ -211,10 +253,13 @@ impl<'a> Parser<'a> {
 
             // Validate: modifier must not be empty and must not contain
             // '(' (CSS variable syntax), '/' (alpha), or '!' (important).
-            // '[' is now allowed for parameterized variants.
+            // '[' is now allowed for parameterized variants, and '/' for
+            // named groups/peers (group-hover/item, peer-checked/email).
+            let named_group =
+                modifier_str.starts_with("group-") || modifier_str.starts_with("peer-");
             if modifier_str.is_empty()
                 || modifier_str.contains('(')
-                || modifier_str.contains('/')
+                || (modifier_str.contains('/') && !named_group)
                 || modifier_str.contains('!')
             {
                 self.pos = start;
@@ -228,7 +273,7 @@ impl<'a> Parser<'a> {
     /// 策略：扫描整个字符串，找到 `-[` 或 `-(` 模式的位置
     /// - 如果存在 `-[` 或 `-(`, 则将其之前的部分作为 plugin
     /// - 否则，在第一个 `-` 处分割，并尝试扩展复合插件
-    fn parse_plugin_and_value(&mut self) -> Result<(String, Option<ParsedValue>), ParseError> {
+    fn parse_plugin_and_value(&mut self) -> Result<(&'a str, Option<ParsedValue<'a>>), ParseError> {
         let start = self.pos;
 
         // 查找 `-[` 或 `-(` 模式的位置
@@ -236,9 +281,8 @@ impl<'a> Parser<'a> {
         let mut temp_pos = self.pos;
 
         while temp_pos + 1 < self.input.len() {
-            if self.input[temp_pos..].starts_with("-[")
-                || self.input[temp_pos..].starts_with("-(")
-            {
+            let rest = &self.input.as_bytes()[temp_pos..];
+            if rest.starts_with(b"-[") || rest.starts_with(b"-(") {
                 dash_special_pos = Some(temp_pos);
                 break;
             }
@@ -266,7 +310,7 @@ impl<'a> Parser<'a> {
             }
         }
 
-        let plugin = self.input[start..self.pos].to_string();
+        let plugin = &self.input[start..self.pos];
 
         if plugin.is_empty() {
             return Err(ParseError::MissingPlugin);
@@ -290,7 +334,7 @@ impl<'a> Parser<'a> {
                     // 标准值
                     let val = self.parse_standard_value();
                     if !val.is_empty() {
-                        Some(ParsedValue::Standard(val))
+                        Some(ParsedValue::Standard(Cow::Borrowed(val)))
                     } else {
                         None
                     }
@@ -318,6 +362,7 @@ impl<'a> Parser<'a> {
     ///
     /// 例：输入 `justify-items-center`，首段 `justify` 已读取，
     ///     当前位置在 `-`，探测到 `items` 是有效扩展，返回 `items` 的结束位置。
+    ///     扩展本身可以含 `-`（`backdrop-hue-rotate-15` 的 `hue-rotate`），取最长匹配。
     fn try_extend_compound(&self, first_word: &str) -> Option<usize> {
         // 当前必须在 '-'
         if self.pos >= self.input.len() || self.current_char() != '-' {
@@ -334,26 +379,21 @@ impl<'a> Parser<'a> {
             return None;
         }
 
-        // 读取下一段（到 -、[、/、! 或末尾）
-        let mut seg_end = after_dash;
-        while seg_end < self.input.len() {
-            let ch = self.input[seg_end..].chars().next().unwrap();
-            if ch == '-' || ch == '[' || ch == '/' || ch == '!' {
-                break;
-            }
-            seg_end += 1;
-        }
-
-        let segment = &self.input[after_dash..seg_end];
-        if extensions.iter().any(|&ext| ext == segment) {
-            Some(seg_end)
-        } else {
-            None
-        }
+        // 扩展之后必须是段边界（-、[、/、! 或末尾）
+        let rest = &self.input[after_dash..];
+        extensions
+            .iter()
+            .filter(|&&ext| {
+                rest.strip_prefix(ext).is_some_and(|after| {
+                    after.is_empty() || matches!(after.as_bytes()[0], b'-' | b'[' | b'/' | b'!')
+                })
+            })
+            .map(|ext| after_dash + ext.len())
+            .max()
     }
 
     /// 解析标准值
-    fn parse_standard_value(&mut self) -> String {
+    fn parse_standard_value(&mut self) -> &'a str {
         let start = self.pos;
 
         // 读取直到遇到 /、! 或字符串结尾
@@ -365,11 +405,11 @@ impl<'a> Parser<'a> {
             self.pos += 1;
         }
 
-        self.input[start..self.pos].to_string()
+        &self.input[start..self.pos]
     }
 
     /// 解析任意值（方括号内容）
-    fn parse_arbitrary_value(&mut self) -> Result<ArbitraryValue, ParseError> {
+    fn parse_arbitrary_value(&mut self) -> Result<ArbitraryValue<'a>, ParseError> {
         if self.current_char() != '[' {
             return Err(ParseError::InvalidFormat(
                 "Arbitrary value must start with '['".to_string(),
@@ -396,14 +436,13 @@ impl<'a> Parser<'a> {
             return Err(ParseError::UnmatchedBracket);
         }
 
-        let raw = self.input[start..self.pos].to_string();
-        Ok(ArbitraryValue::new(raw))
+        Ok(ArbitraryValue::new(&self.input[start..self.pos]))
     }
 
     /// 解析 CSS 自定义属性值（圆括号内容）
     ///
     /// 例如：`(--my-color)` 或 `(image:--my-bg)`
-    fn parse_css_variable_value(&mut self) -> Result<CssVariableValue, ParseError> {
+    fn parse_css_variable_value(&mut self) -> Result<CssVariableValue<'a>, ParseError> {
         if self.current_char() != '(' {
             return Err(ParseError::InvalidFormat(
                 "CSS variable value must start with '('".to_string(),
@@ -430,8 +469,7 @@ impl<'a> Parser<'a> {
             return Err(ParseError::UnmatchedParen);
         }
 
-        let raw = self.input[start..self.pos].to_string();
-        Ok(CssVariableValue::new(raw))
+        Ok(CssVariableValue::new(&self.input[start..self.pos]))
     }
 
     /// 解析透明度 / 行高修饰符
@@ -440,7 +478,7 @@ impl<'a> Parser<'a> {
     /// - `/50` — 普通字母数字值（透明度或行高数字）
     /// - `/[1.5rem]` — 任意值（方括号包裹）
     /// - `/(--custom)` — CSS 自定义属性（圆括号包裹）
-    fn parse_alpha(&mut self) -> Option<String> {
+    fn parse_alpha(&mut self) -> Option<Cow<'a, str>> {
         if self.pos < self.input.len() && self.current_char() == '/' {
             self.pos += 1; // 跳过 '/'
 
@@ -469,9 +507,9 @@ impl<'a> Parser<'a> {
                     }
                     self.pos += 1;
                 }
-                let alpha = self.input[start..self.pos].to_string();
+                let alpha = &self.input[start..self.pos];
                 if !alpha.is_empty() {
-                    return Some(alpha);
+                    return Some(Cow::Borrowed(alpha));
                 }
             } else if ch == '(' {
                 // 圆括号值：/(--custom)
@@ -491,9 +529,9 @@ impl<'a> Parser<'a> {
                     }
                     self.pos += 1;
                 }
-                let alpha = self.input[start..self.pos].to_string();
+                let alpha = &self.input[start..self.pos];
                 if !alpha.is_empty() {
-                    return Some(alpha);
+                    return Some(Cow::Borrowed(alpha));
                 }
             } else {
                 // 普通字母数字值
@@ -504,9 +542,9 @@ impl<'a> Parser<'a> {
                     }
                     self.pos += 1;
                 }
-                let alpha = self.input[start..self.pos].to_string();
+                let alpha = &self.input[start..self.pos];
                 if !alpha.is_empty() {
-                    return Some(alpha);
+                    return Some(Cow::Borrowed(alpha));
                 }
             }
         }
@@ -524,9 +562,12 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// 获取当前字符
+    /// 获取当前字节（按字符返回）
+    ///
+    /// 解析器逐字节前进：所有分隔符都是 ASCII，多字节字符的各个字节（均 ≥ 0x80）
+    /// 不会与之相等，因此切片位置总落在字符边界上，非 ASCII 输入也不会 panic
     fn current_char(&self) -> char {
-        self.input[self.pos..].chars().next().unwrap()
+        self.input.as_bytes()[self.pos] as char
     }
 }
 
@@ -562,6 +603,7 @@ fn compound_extensions(prefix: &str) -> &'static [&'static str] {
         // Transform axis
         "translate" => &["x", "y", "z"],
         "scale" => &["x", "y"],
+        "skew" => &["x", "y"],
 
         // Grid
         "grid" => &["cols", "rows", "flow"],
@@ -569,8 +611,10 @@ fn compound_extensions(prefix: &str) -> &'static [&'static str] {
         "row" => &["span", "start", "end"],
         "auto" => &["cols", "rows"],
 
-        // Ring
+        // Ring & offsets
         "ring" => &["offset"],
+        "outline" => &["offset"],
+        "underline" => &["offset"],
 
         // Size constraints
         "min" => &["w", "h"],
@@ -581,12 +625,14 @@ fn compound_extensions(prefix: &str) -> &'static [&'static str] {
             "m", "mx", "my", "mt", "mr", "mb", "ml", "p", "px", "py", "pt", "pr", "pb", "pl",
         ],
 
-        // Backdrop filters
+        // Filters
+        "hue" => &["rotate"],
         "backdrop" => &[
             "blur",
             "brightness",
             "contrast",
             "grayscale",
+            "hue-rotate",
             "invert",
             "opacity",
             "saturate",
@@ -594,6 +640,7 @@ fn compound_extensions(prefix: &str) -> &'static [&'static str] {
         ],
 
         // Misc compound
+        "text" => &["shadow"],
         "line" => &["clamp"],
         "box" => &["decoration"],
         "break" => &["before", "after", "inside"],
@@ -613,7 +660,7 @@ mod tests {
         assert_eq!(parsed.plugin, "p");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("4".to_string()))
+            Some(ParsedValue::Standard("4".into()))
         );
         assert!(!parsed.negative);
         assert!(!parsed.important);
@@ -634,7 +681,7 @@ mod tests {
         assert_eq!(parsed.plugin, "bg");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("blue-500".to_string()))
+            Some(ParsedValue::Standard("blue-500".into()))
         );
     }
 
@@ -653,7 +700,7 @@ mod tests {
         assert_eq!(parsed.plugin, "m");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("4".to_string()))
+            Some(ParsedValue::Standard("4".into()))
         );
     }
 
@@ -674,7 +721,7 @@ mod tests {
     fn test_alpha_modifier() {
         let parsed = parse_class("bg-blue-500/50").unwrap();
         assert_eq!(parsed.plugin, "bg");
-        assert_eq!(parsed.alpha, Some("50".to_string()));
+        assert_eq!(parsed.alpha, Some("50".into()));
     }
 
     #[test]
@@ -691,9 +738,9 @@ mod tests {
         assert_eq!(parsed.plugin, "bg");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("blue-500".to_string()))
+            Some(ParsedValue::Standard("blue-500".into()))
         );
-        assert_eq!(parsed.alpha, Some("50".to_string()));
+        assert_eq!(parsed.alpha, Some("50".into()));
         assert!(parsed.important);
     }
 
@@ -826,7 +873,7 @@ mod tests {
 
         // bg-blue-500/50!
         assert_eq!(parsed[3].plugin, "bg");
-        assert_eq!(parsed[3].alpha, Some("50".to_string()));
+        assert_eq!(parsed[3].alpha, Some("50".into()));
         assert!(parsed[3].important);
     }
 
@@ -838,7 +885,7 @@ mod tests {
         assert_eq!(parsed.plugin, "justify-items");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("center".to_string()))
+            Some(ParsedValue::Standard("center".into()))
         );
     }
 
@@ -848,7 +895,7 @@ mod tests {
         assert_eq!(parsed.plugin, "justify-self");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("auto".to_string()))
+            Some(ParsedValue::Standard("auto".into()))
         );
     }
 
@@ -858,7 +905,7 @@ mod tests {
         assert_eq!(parsed.plugin, "place-content");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("center".to_string()))
+            Some(ParsedValue::Standard("center".into()))
         );
     }
 
@@ -868,7 +915,7 @@ mod tests {
         assert_eq!(parsed.plugin, "gap-x");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("4".to_string()))
+            Some(ParsedValue::Standard("4".into()))
         );
     }
 
@@ -878,7 +925,7 @@ mod tests {
         assert_eq!(parsed.plugin, "border-t");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("2".to_string()))
+            Some(ParsedValue::Standard("2".into()))
         );
     }
 
@@ -888,7 +935,7 @@ mod tests {
         assert_eq!(parsed.plugin, "min-w");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("full".to_string()))
+            Some(ParsedValue::Standard("full".into()))
         );
     }
 
@@ -898,7 +945,7 @@ mod tests {
         assert_eq!(parsed.plugin, "translate-x");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("4".to_string()))
+            Some(ParsedValue::Standard("4".into()))
         );
     }
 
@@ -908,7 +955,7 @@ mod tests {
         assert_eq!(parsed.plugin, "grid-cols");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("3".to_string()))
+            Some(ParsedValue::Standard("3".into()))
         );
     }
 
@@ -918,10 +965,27 @@ mod tests {
         assert_eq!(parsed.plugin, "scroll-mt");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("4".to_string()))
+            Some(ParsedValue::Standard("4".into()))
         );
     }
 
+    #[test]
+    fn test_compound_offsets() {
+        for (class, plugin, value) in [
+            ("ring-offset-2", "ring-offset", "2"),
+            ("ring-offset-white", "ring-offset", "white"),
+            ("outline-offset-2", "outline-offset", "2"),
+            ("underline-offset-auto", "underline-offset", "auto"),
+        ] {
+            let parsed = parse_class(class).unwrap();
+            assert_eq!(parsed.plugin, plugin);
+            assert_eq!(parsed.value, Some(ParsedValue::Standard(value.into())));
+        }
+        // 没有 offset 后缀时不扩展
+        assert_eq!(parse_class("outline-2").unwrap().plugin, "outline");
+        assert_eq!(parse_class("underline").unwrap().plugin, "underline");
+    }
+
     #[test]
     fn test_compound_no_false_extension() {
         // `border-black` should NOT extend: `black` is not a valid extension for `border`
@@ -929,7 +993,7 @@ mod tests {
         assert_eq!(parsed.plugin, "border");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("black".to_string()))
+            Some(ParsedValue::Standard("black".into()))
         );
     }
 
@@ -940,7 +1004,7 @@ mod tests {
         assert_eq!(parsed.plugin, "gap");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("4".to_string()))
+            Some(ParsedValue::Standard("4".into()))
         );
     }
 
@@ -950,7 +1014,7 @@ mod tests {
         assert_eq!(parsed.plugin, "justify-items");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("center".to_string()))
+            Some(ParsedValue::Standard("center".into()))
         );
         assert_eq!(parsed.modifiers().len(), 1);
     }
@@ -962,10 +1026,43 @@ mod tests {
         assert_eq!(parsed.plugin, "translate-x");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("4".to_string()))
+            Some(ParsedValue::Standard("4".into()))
         );
     }
 
+    #[test]
+    fn test_compound_text_shadow() {
+        let parsed = parse_class("text-shadow-red-500/50").unwrap();
+        assert_eq!(parsed.plugin, "text-shadow");
+        assert_eq!(parsed.value, Some(ParsedValue::Standard("red-500".into())));
+        assert_eq!(parsed.alpha.as_deref(), Some("50"));
+
+        // `text-` 后面不是 `shadow` 段时仍是 text 插件
+        let parsed = parse_class("text-shadowy").unwrap();
+        assert_eq!(parsed.plugin, "text");
+    }
+
+    #[test]
+    fn test_compound_filters() {
+        let parsed = parse_class("-hue-rotate-15").unwrap();
+        assert!(parsed.negative);
+        assert_eq!(parsed.plugin, "hue-rotate");
+        assert_eq!(parsed.value, Some(ParsedValue::Standard("15".into())));
+
+        let parsed = parse_class("backdrop-hue-rotate-90").unwrap();
+        assert_eq!(parsed.plugin, "backdrop-hue-rotate");
+        assert_eq!(parsed.value, Some(ParsedValue::Standard("90".into())));
+
+        let parsed = parse_class("backdrop-brightness-50").unwrap();
+        assert_eq!(parsed.plugin, "backdrop-brightness");
+        assert_eq!(parsed.value, Some(ParsedValue::Standard("50".into())));
+
+        // 多段扩展只在完整匹配时生效
+        let parsed = parse_class("backdrop-hue-15").unwrap();
+        assert_eq!(parsed.plugin, "backdrop");
+        assert_eq!(parsed.value, Some(ParsedValue::Standard("hue-15".into())));
+    }
+
     #[test]
     fn test_compound_valueless() {
         // `overflow-x` without value (no further dash)
@@ -997,7 +1094,7 @@ mod tests {
 
         if let Some(ParsedValue::CssVariable(cv)) = parsed.value {
             assert_eq!(cv.property, "--my-bg");
-            assert_eq!(cv.type_hint, Some("image".to_string()));
+            assert_eq!(cv.type_hint.as_deref(), Some("image"));
         } else {
             panic!("Expected CSS variable value");
         }
@@ -1049,7 +1146,7 @@ mod tests {
     fn test_css_variable_with_alpha() {
         let parsed = parse_class("bg-(--my-color)/50").unwrap();
         assert_eq!(parsed.plugin, "bg");
-        assert_eq!(parsed.alpha, Some("50".to_string()));
+        assert_eq!(parsed.alpha, Some("50".into()));
         assert!(parsed.value.as_ref().unwrap().is_css_variable());
     }
 
@@ -1121,7 +1218,7 @@ mod tests {
         assert_eq!(parsed.plugin, "bg");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("blue-500".to_string()))
+            Some(ParsedValue::Standard("blue-500".into()))
         );
     }
 
@@ -1132,7 +1229,7 @@ mod tests {
         assert_eq!(parsed.plugin, "opacity");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("50".to_string()))
+            Some(ParsedValue::Standard("50".into()))
         );
     }
 
@@ -1151,7 +1248,7 @@ mod tests {
         assert_eq!(parsed.plugin, "p");
         assert_eq!(
             parsed.value,
-            Some(ParsedValue::Standard("4".to_string()))
+            Some(ParsedValue::Standard("4".into()))
         );
     }
 
@@ -1176,6 +1273,18 @@ mod tests {
         assert_eq!(parsed.plugin, "text");
     }
 
+    #[test]
+    fn test_named_group_modifier() {
+        let parsed = parse_class("md:group-hover/item:text-red-500/50").unwrap();
+        assert_eq!(parsed.raw_modifiers, "md:group-hover/item:");
+        assert_eq!(parsed.plugin, "text");
+        assert_eq!(parsed.alpha.as_deref(), Some("50"));
+        assert_eq!(
+            parsed.modifiers()[1],
+            crate::Modifier::State("group-hover/item".to_string())
+        );
+    }
+
     #[test]
     fn test_container_query_modifier() {
         let parsed = parse_class("@sm:p-4").unwrap();
@@ -1206,4 +1315,70 @@ mod tests {
         assert_eq!(parsed.plugin, "w");
         assert!(parsed.value.as_ref().unwrap().is_arbitrary());
     }
+
+    #[test]
+    fn test_non_ascii_input() {
+        // 曾因在多字节字符中间切片而 panic
+        assert_eq!(parse_class("p-中").unwrap().value, Some(ParsedValue::Standard("中".into())));
+        assert!(parse_class("中-[").is_err());
+        assert!(parse_class("bg-red/é").is_err());
+        assert_eq!(parse_class("content-['→']").unwrap().plugin, "content");
+    }
+
+    #[test]
+    fn test_parse_borrows_input() {
+        let input = String::from("md:hover:bg-[#fff]/50 w-[calc(100%_-_1rem)] bg-(image:--hero)");
+        let parsed = parse_classes(&input).unwrap();
+        let borrowed = |value: &Cow<str>| matches!(value, Cow::Borrowed(_));
+
+        assert!(borrowed(&parsed[0].raw_modifiers) && borrowed(&parsed[0].plugin));
+        assert!(parsed[0].alpha.as_ref().is_some_and(borrowed));
+        match &parsed[0].value {
+            Some(ParsedValue::Arbitrary(arb)) => assert!(borrowed(&arb.raw) && borrowed(&arb.content)),
+            other => panic!("unexpected value: {:?}", other),
+        }
+        // 下划线需要转换为空格，只有 content 分配
+        match &parsed[1].value {
+            Some(ParsedValue::Arbitrary(arb)) => {
+                assert!(borrowed(&arb.raw) && !borrowed(&arb.content));
+                assert_eq!(arb.content, "calc(100% - 1rem)");
+            }
+            other => panic!("unexpected value: {:?}", other),
+        }
+        match &parsed[2].value {
+            Some(ParsedValue::CssVariable(cv)) => {
+                assert!(borrowed(&cv.property) && cv.type_hint.as_ref().is_some_and(borrowed));
+            }
+            other => panic!("unexpected value: {:?}", other),
+        }
+
+        let owned: Vec<ParsedClass<'static>> =
+            parsed.iter().cloned().map(ParsedClass::into_owned).collect();
+        drop(parsed);
+        drop(input);
+        assert_eq!(owned[0].to_normalized_string(), "md:hover:bg-[#fff]/50");
+    }
+
+    #[test]
+    fn test_parse_classes_with_spans() {
+        let input = "\n  p-4\tmd:-m-[2px]  bg-(--c  text-é ";
+        let classes = parse_classes_with_spans(input);
+        let spans: Vec<(&str, Range<usize>)> =
+            classes.iter().map(|c| (c.raw, c.span.clone())).collect();
+        assert_eq!(
+            spans,
+            [("p-4", 3..6), ("md:-m-[2px]", 7..18), ("bg-(--c", 20..27), ("text-é", 29..36)]
+        );
+        for class in &classes {
+            assert_eq!(&input[class.span.clone()], class.raw);
+        }
+
+        assert!(classes[1].parsed.as_ref().is_ok_and(|p| p.negative && p.plugin == "m"));
+        // 出错的类名不影响后续类名
+        assert_eq!(classes[2].parsed, Err(ParseError::UnmatchedParen));
+        assert!(classes[3].parsed.is_ok());
+
+        assert!(parse_classes_with_spans("").is_empty());
+        assert!(parse_classes_with_spans(" \n ").is_empty());
+    }
 }
@@ -1,23 +1,27 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 /// 解析后的 Tailwind class 表示
+///
+/// 各字符串字段借用输入（`Cow::Borrowed`），解析过程不分配；只有含下划线的任意值
+/// 需要转换内容时才会分配。需要脱离输入的生命周期（如跨线程缓存）时调用 `into_owned()`。
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct ParsedClass {
+pub struct ParsedClass<'a> {
     /// 原始修饰符字符串（如 "md:hover:"）
     /// 需要时可通过 parse_modifiers_from_raw() 解析成 Vec<Modifier>
-    pub raw_modifiers: String,
+    pub raw_modifiers: Cow<'a, str>,
 
     /// 是否为负值（如 -m-4）
     pub negative: bool,
 
     /// 核心插件/命令（如 p, m, bg, text）
-    pub plugin: String,
+    pub plugin: Cow<'a, str>,
 
     /// 值部分
-    pub value: Option<ParsedValue>,
+    pub value: Option<ParsedValue<'a>>,
 
     /// 透明度修饰符（如 /50）
-    pub alpha: Option<String>,
+    pub alpha: Option<Cow<'a, str>>,
 
     /// 重要性标记（!）
     pub important: bool,
@@ -44,54 +48,66 @@ pub enum Modifier {
 
 /// 值类型
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum ParsedValue {
+pub enum ParsedValue<'a> {
     /// 标准值（如 "4", "red-500", "lg"）
-    Standard(String),
+    Standard(Cow<'a, str>),
 
     /// 任意值（如 "[13px]", "[#ff0000]"）
-    Arbitrary(ArbitraryValue),
+    Arbitrary(ArbitraryValue<'a>),
 
     /// CSS 自定义属性引用（如 "(--my-color)", "(image:--my-bg)"）
     /// Tailwind v4 语法：`bg-(--my-color)` → `background: var(--my-color)`
-    CssVariable(CssVariableValue),
+    CssVariable(CssVariableValue<'a>),
 }
 
 /// 任意值表示
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct ArbitraryValue {
+pub struct ArbitraryValue<'a> {
     /// 原始值（包含方括号）
-    pub raw: String,
+    pub raw: Cow<'a, str>,
 
     /// 解析后的内容（去除方括号）
-    pub content: String,
+    pub content: Cow<'a, str>,
 }
 
 /// CSS 自定义属性值表示
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct CssVariableValue {
+pub struct CssVariableValue<'a> {
     /// 原始值（包含圆括号，如 "(--my-color)" 或 "(image:--my-bg)"）
-    pub raw: String,
+    pub raw: Cow<'a, str>,
 
     /// CSS 自定义属性名（如 "--my-color"）
-    pub property: String,
+    pub property: Cow<'a, str>,
 
     /// 可选的类型提示（如 "image" in "bg-(image:--my-bg)"）
-    pub type_hint: Option<String>,
+    pub type_hint: Option<Cow<'a, str>>,
 }
 
-impl ParsedClass {
+impl<'a> ParsedClass<'a> {
     /// 创建一个新的 ParsedClass
-    pub fn new(plugin: String) -> Self {
+    pub fn new(plugin: impl Into<Cow<'a, str>>) -> Self {
         Self {
-            raw_modifiers: String::new(),
+            raw_modifiers: Cow::Borrowed(""),
             negative: false,
-            plugin,
+            plugin: plugin.into(),
             value: None,
             alpha: None,
             important: false,
         }
     }
 
+    /// 复制所有借用的字段，得到不依赖输入字符串的 ParsedClass
+    pub fn into_owned(self) -> ParsedClass<'static> {
+        ParsedClass {
+            raw_modifiers: Cow::Owned(self.raw_modifiers.into_owned()),
+            negative: self.negative,
+            plugin: Cow::Owned(self.plugin.into_owned()),
+            value: self.value.map(ParsedValue::into_owned),
+            alpha: self.alpha.map(|alpha| Cow::Owned(alpha.into_owned())),
+            important: self.important,
+        }
+    }
+
     /// 获取解析后的修饰符列表
     ///
     /// 这个方法会在需要时从 raw_modifiers 解析出 Modifier 列表
@@ -102,16 +118,12 @@ impl ParsedClass {
     /// 添加修饰符
     pub fn with_modifier(mut self, modifier: Modifier) -> Self {
         // 更新 raw_modifiers
-        if !self.raw_modifiers.is_empty() {
-            self.raw_modifiers.push_str(&format!("{}:", modifier));
-        } else {
-            self.raw_modifiers = format!("{}:", modifier);
-        }
+        self.raw_modifiers.to_mut().push_str(&format!("{}:", modifier));
         self
     }
 
     /// 设置值
-    pub fn with_value(mut self, value: ParsedValue) -> Self {
+    pub fn with_value(mut self, value: ParsedValue<'a>) -> Self {
         self.value = Some(value);
         self
     }
@@ -123,8 +135,8 @@ impl ParsedClass {
     }
 
     /// 设置透明度
-    pub fn with_alpha(mut self, alpha: String) -> Self {
-        self.alpha = Some(alpha);
+    pub fn with_alpha(mut self, alpha: impl Into<Cow<'a, str>>) -> Self {
+        self.alpha = Some(alpha.into());
         self
     }
 
@@ -282,8 +294,8 @@ impl Modifier {
             return Modifier::PseudoElement(s.to_string());
         }
 
-        // Child selectors
-        if matches!(s, "*" | "**") {
+        // Child selectors（`*space` 是 space-x / space-y 内部使用的子元素变体）
+        if matches!(s, "*" | "**" | "*space") {
             return Modifier::PseudoClass(s.to_string());
         }
 
@@ -357,7 +369,23 @@ pub fn parse_modifiers_from_raw(raw: &str) -> Vec<Modifier> {
         .collect()
 }
 
-impl ParsedValue {
+impl ParsedValue<'_> {
+    /// 复制所有借用的字段
+    pub fn into_owned(self) -> ParsedValue<'static> {
+        match self {
+            ParsedValue::Standard(value) => ParsedValue::Standard(Cow::Owned(value.into_owned())),
+            ParsedValue::Arbitrary(arb) => ParsedValue::Arbitrary(ArbitraryValue {
+                raw: Cow::Owned(arb.raw.into_owned()),
+                content: Cow::Owned(arb.content.into_owned()),
+            }),
+            ParsedValue::CssVariable(cv) => ParsedValue::CssVariable(CssVariableValue {
+                raw: Cow::Owned(cv.raw.into_owned()),
+                property: Cow::Owned(cv.property.into_owned()),
+                type_hint: cv.type_hint.map(|hint| Cow::Owned(hint.into_owned())),
+            }),
+        }
+    }
+
     /// 判断是否为任意值
     pub fn is_arbitrary(&self) -> bool {
         matches!(self, ParsedValue::Arbitrary(_))
@@ -369,7 +397,7 @@ impl ParsedValue {
     }
 }
 
-impl std::fmt::Display for ParsedValue {
+impl std::fmt::Display for ParsedValue<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ParsedValue::Standard(s) => write!(f, "{}", s),
@@ -379,55 +407,82 @@ impl std::fmt::Display for ParsedValue {
     }
 }
 
-impl CssVariableValue {
+impl<'a> CssVariableValue<'a> {
     /// 创建新的 CSS 自定义属性值
     ///
     /// 从原始圆括号字符串中解析出属性名和可选类型提示。
     /// 例如：`"(--my-color)"` → property: `"--my-color"`, type_hint: None
     ///       `"(image:--my-bg)"` → property: `"--my-bg"`, type_hint: Some("image")
-    pub fn new(raw: String) -> Self {
-        let inner = raw
-            .strip_prefix('(')
-            .and_then(|s| s.strip_suffix(')'))
-            .unwrap_or(&raw);
-
-        let (type_hint, property) = if let Some(colon_pos) = inner.find(':') {
-            let hint = &inner[..colon_pos];
-            let prop = &inner[colon_pos + 1..];
-            // 只有当冒号前的部分不以 -- 开头时才视为类型提示
-            // 否则整个值就是属性名（如 --my-color 本身不含类型提示）
-            if !hint.starts_with("--") {
-                (Some(hint.to_string()), prop.to_string())
-            } else {
-                (None, inner.to_string())
-            }
-        } else {
-            (None, inner.to_string())
+    pub fn new(raw: impl Into<Cow<'a, str>>) -> Self {
+        let raw = raw.into();
+        let inner = strip_delimiters(&raw, '(', ')');
+
+        // 只有当冒号前的部分不以 -- 开头时才视为类型提示
+        // 否则整个值就是属性名（如 --my-color 本身不含类型提示）
+        let (type_hint, property) = match raw[inner.clone()].find(':') {
+            Some(colon) if !raw[inner.start..inner.start + colon].starts_with("--") => (
+                Some(inner.start..inner.start + colon),
+                inner.start + colon + 1..inner.end,
+            ),
+            _ => (None, inner),
         };
 
         Self {
+            type_hint: type_hint.map(|range| sub_cow(&raw, range)),
+            property: sub_cow(&raw, property),
             raw,
-            property,
-            type_hint,
         }
     }
 }
 
-impl ArbitraryValue {
+impl<'a> ArbitraryValue<'a> {
     /// 创建新的任意值
     ///
     /// 按照 Tailwind 规范，任意值中的 `_` 会转换为空格，
     /// `\_` 会保留为字面下划线。
-    pub fn new(raw: String) -> Self {
-        let stripped = raw
-            .strip_prefix('[')
-            .and_then(|s| s.strip_suffix(']'))
-            .unwrap_or(&raw);
-
-        let content = convert_underscores(stripped);
+    pub fn new(raw: impl Into<Cow<'a, str>>) -> Self {
+        let raw = raw.into();
+        let inner = strip_delimiters(&raw, '[', ']');
+        let content = if raw[inner.clone()].contains('_') {
+            Cow::Owned(convert_underscores(&raw[inner]))
+        } else {
+            sub_cow(&raw, inner)
+        };
 
         Self { raw, content }
     }
+
+    /// 拆分类型提示：`[position:center_top]` → `(Some("position"), "center top")`
+    ///
+    /// 提示只由小写字母和 `-` 组成，`[url(https://…)]`、`['a:b']` 等值中的冒号不视为提示
+    pub fn split_type_hint(&self) -> (Option<&str>, &str) {
+        match self.content.split_once(':') {
+            Some((hint, value))
+                if hint.starts_with(|c: char| c.is_ascii_lowercase())
+                    && hint.bytes().all(|b| b.is_ascii_lowercase() || b == b'-') =>
+            {
+                (Some(hint), value)
+            }
+            _ => (None, &self.content),
+        }
+    }
+}
+
+/// 去掉首尾定界符后的字节区间；两端不完整时返回整个字符串
+fn strip_delimiters(s: &str, open: char, close: char) -> std::ops::Range<usize> {
+    if s.len() >= 2 && s.starts_with(open) && s.ends_with(close) {
+        1..s.len() - 1
+    } else {
+        0..s.len()
+    }
+}
+
+/// 取 `s` 的子串：借用时仍借用同一输入，否则复制
+fn sub_cow<'a>(s: &Cow<'a, str>, range: std::ops::Range<usize>) -> Cow<'a, str> {
+    match s {
+        Cow::Borrowed(s) => Cow::Borrowed(&s[range]),
+        Cow::Owned(s) => Cow::Owned(s[range].to_string()),
+    }
 }
 
 /// 将任意值中的下划线转换为空格
@@ -473,7 +528,7 @@ mod tests {
         let class = ParsedClass::new("p".to_string())
             .with_modifier(Modifier::Responsive("md".to_string()))
             .with_modifier(Modifier::PseudoClass("hover".to_string()))
-            .with_value(ParsedValue::Standard("4".to_string()));
+            .with_value(ParsedValue::Standard("4".into()));
 
         assert_eq!(class.to_normalized_string(), "md:hover:p-4");
     }
@@ -511,4 +566,17 @@ mod tests {
         let arb = ArbitraryValue::new("[#ff0000]".to_string());
         assert_eq!(arb.content, "#ff0000");
     }
+
+    #[test]
+    fn test_arbitrary_value_type_hint() {
+        let arb = ArbitraryValue::new("[position:center_top_1rem]");
+        assert_eq!(arb.split_type_hint(), (Some("position"), "center top 1rem"));
+        let arb = ArbitraryValue::new("[length:200px_100px]");
+        assert_eq!(arb.split_type_hint(), (Some("length"), "200px 100px"));
+        // 值中的冒号不是类型提示
+        for raw in ["[url(https://a.com/b.png)]", "['a:b']", "[25%_75%]", "[var(--x)]"] {
+            let arb = ArbitraryValue::new(raw);
+            assert_eq!(arb.split_type_hint(), (None, arb.content.as_ref()));
+        }
+    }
 }
@@ -0,0 +1,69 @@
+//! 解析器的属性测试：任意输入都不能 panic，合法类名可以还原
+
+use headwind_tw_parse::{parse_class, parse_classes};
+use proptest::prelude::*;
+
+/// 由类名常见片段拼接的输入，比纯随机字符串更容易走到深层分支
+fn class_like() -> impl Strategy<Value = String> {
+    let piece = prop_oneof![
+        Just("-".to_string()),
+        Just(":".to_string()),
+        Just("/".to_string()),
+        Just("!".to_string()),
+        Just("[".to_string()),
+        Just("]".to_string()),
+        Just("(".to_string()),
+        Just(")".to_string()),
+        Just("_".to_string()),
+        Just("\\".to_string()),
+        "[a-z0-9#%.@*]{1,6}",
+        "\\PC{1,3}",
+    ];
+    prop::collection::vec(piece, 0..16).prop_map(|pieces| pieces.concat())
+}
+
+proptest! {
+    #[test]
+    fn parse_class_never_panics(input in "\\PC*") {
+        let _ = parse_class(&input);
+    }
+
+    #[test]
+    fn parse_class_like_input_never_panics(input in class_like()) {
+        if let Ok(parsed) = parse_class(&input) {
+            let _ = parsed.to_normalized_string();
+            let _ = parsed.modifiers();
+        }
+    }
+
+    #[test]
+    fn parse_classes_never_panics(input in "[ \\t\\n\\PC]*") {
+        let _ = parse_classes(&input);
+    }
+
+    #[test]
+    fn standard_classes_roundtrip(
+        modifiers in prop::collection::vec("(hover|focus|md|dark|group-hover|data-\\[open\\])", 0..3),
+        negative in any::<bool>(),
+        plugin in "(p|mx|bg|text|border-t|justify-items)",
+        value in "(4|red-500|\\[13px\\]|\\(--my-var\\)|center)",
+        alpha in prop::option::of("(50|\\[0\\.5\\])"),
+        important in any::<bool>(),
+    ) {
+        let mut class: String = modifiers.iter().map(|m| format!("{}:", m)).collect();
+        if negative {
+            class.push('-');
+        }
+        class.push_str(&format!("{}-{}", plugin, value));
+        if let Some(alpha) = alpha {
+            class.push_str(&format!("/{}", alpha));
+        }
+        if important {
+            class.push('!');
+        }
+
+        let parsed = parse_class(&class).unwrap();
+        prop_assert_eq!(parsed.to_normalized_string(), class.as_str());
+        prop_assert_eq!(parsed.modifiers().len(), modifiers.len());
+    }
+}
@@ -1,246 +1,93 @@
 use wasm_bindgen::prelude::*;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use indexmap::IndexMap;
+use headwind_core::Diagnostic;
 
 use headwind_transform::{
-    transform_jsx as rs_transform_jsx,
-    transform_html as rs_transform_html,
-    TransformOptions, OutputMode, CssModulesAccess, NamingMode, CssVariableMode, UnknownClassMode,
-    ColorMode,
+    transform_html as rs_transform_html, transform_markdown as rs_transform_markdown,
+    CssOutputs, HeadwindConfig, RuntimeHelper, TransformOptions,
 };
+#[cfg(feature = "jsx")]
+use headwind_transform::transform_jsx as rs_transform_jsx;
 
-// ── JS 侧 serde 镜像类型 ──────────────────────────────────────
+// ── 全局分配器 ────────────────────────────────────────────────
 
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct JsTransformOptions {
-    #[serde(default)]
-    naming_mode: JsNamingMode,
-    #[serde(default)]
-    output_mode: JsOutputMode,
-    #[serde(default)]
-    css_variables: JsCssVariableMode,
-    #[serde(default)]
-    unknown_classes: JsUnknownClassMode,
-    #[serde(default)]
-    color_mode: JsColorMode,
-    #[serde(default)]
-    color_mix: bool,
-    #[serde(default)]
-    element_tree: bool,
-}
-
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum JsNamingMode {
-    Hash,
-    Readable,
-    CamelCase,
-}
-
-impl Default for JsNamingMode {
-    fn default() -> Self {
-        JsNamingMode::Hash
-    }
-}
-
-#[derive(Deserialize)]
-#[serde(tag = "type", rename_all = "camelCase")]
-enum JsOutputMode {
-    #[serde(rename_all = "camelCase")]
-    Global {
-        #[serde(default)]
-        import_path: Option<String>,
-    },
-    #[serde(rename_all = "camelCase")]
-    CssModules {
-        #[serde(default = "default_binding")]
-        binding_name: String,
-        #[serde(default)]
-        import_path: Option<String>,
-        #[serde(default)]
-        access: JsCssModulesAccess,
-    },
-}
-
-impl Default for JsOutputMode {
-    fn default() -> Self {
-        JsOutputMode::Global { import_path: None }
-    }
-}
-
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum JsCssModulesAccess {
-    Dot,
-    Bracket,
-}
-
-impl Default for JsCssModulesAccess {
-    fn default() -> Self {
-        JsCssModulesAccess::Dot
-    }
-}
-
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum JsCssVariableMode {
-    Var,
-    Inline,
-}
-
-impl Default for JsCssVariableMode {
-    fn default() -> Self {
-        JsCssVariableMode::Var
-    }
-}
-
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum JsUnknownClassMode {
-    Remove,
-    Preserve,
-}
-
-impl Default for JsUnknownClassMode {
-    fn default() -> Self {
-        JsUnknownClassMode::Remove
-    }
-}
-
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum JsColorMode {
-    Hex,
-    Oklch,
-    Hsl,
-    Var,
-}
-
-impl Default for JsColorMode {
-    fn default() -> Self {
-        JsColorMode::Hex
-    }
-}
+#[cfg(all(feature = "talc", target_family = "wasm"))]
+#[global_allocator]
+static ALLOCATOR: talc::TalckWasm = unsafe { talc::TalckWasm::new_global() };
 
-fn default_binding() -> String {
-    "styles".to_string()
-}
+// ── JS 侧 serde 类型 ──────────────────────────────────────────
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct JsTransformResult {
     code: String,
     css: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    css_outputs: Option<CssOutputs>,
     class_map: IndexMap<String, String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     element_tree: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    diagnostics: Vec<Diagnostic>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    inherited_classes: Vec<JsInheritedClass>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style_module: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dts: Option<JsDtsFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    css_hash: Option<String>,
 }
 
-// ── 类型转换 ──────────────────────────────────────────────────
-
-impl From<JsNamingMode> for NamingMode {
-    fn from(m: JsNamingMode) -> Self {
-        match m {
-            JsNamingMode::Hash => NamingMode::Hash,
-            JsNamingMode::Readable => NamingMode::Readable,
-            JsNamingMode::CamelCase => NamingMode::CamelCase,
-        }
-    }
-}
-
-impl From<JsCssModulesAccess> for CssModulesAccess {
-    fn from(a: JsCssModulesAccess) -> Self {
-        match a {
-            JsCssModulesAccess::Dot => CssModulesAccess::Dot,
-            JsCssModulesAccess::Bracket => CssModulesAccess::Bracket,
-        }
-    }
-}
-
-impl From<JsOutputMode> for OutputMode {
-    fn from(m: JsOutputMode) -> Self {
-        match m {
-            JsOutputMode::Global { import_path } => OutputMode::Global { import_path },
-            JsOutputMode::CssModules {
-                binding_name,
-                import_path,
-                access,
-            } => OutputMode::CssModules {
-                binding_name,
-                import_path,
-                access: access.into(),
-            },
-        }
-    }
-}
-
-impl From<JsCssVariableMode> for CssVariableMode {
-    fn from(m: JsCssVariableMode) -> Self {
-        match m {
-            JsCssVariableMode::Var => CssVariableMode::Var,
-            JsCssVariableMode::Inline => CssVariableMode::Inline,
-        }
-    }
-}
-
-impl From<JsUnknownClassMode> for UnknownClassMode {
-    fn from(m: JsUnknownClassMode) -> Self {
-        match m {
-            JsUnknownClassMode::Remove => UnknownClassMode::Remove,
-            JsUnknownClassMode::Preserve => UnknownClassMode::Preserve,
-        }
-    }
+#[derive(Serialize)]
+struct JsInheritedClass {
+    element: String,
+    class: String,
+    ancestor: String,
 }
 
-impl From<JsColorMode> for ColorMode {
-    fn from(m: JsColorMode) -> Self {
-        match m {
-            JsColorMode::Hex => ColorMode::Hex,
-            JsColorMode::Oklch => ColorMode::Oklch,
-            JsColorMode::Hsl => ColorMode::Hsl,
-            JsColorMode::Var => ColorMode::Var,
-        }
-    }
+#[derive(Serialize)]
+struct JsDtsFile {
+    path: String,
+    content: String,
 }
 
-impl From<JsTransformOptions> for TransformOptions {
-    fn from(opts: JsTransformOptions) -> Self {
-        TransformOptions {
-            naming_mode: opts.naming_mode.into(),
-            output_mode: opts.output_mode.into(),
-            css_variables: opts.css_variables.into(),
-            unknown_classes: opts.unknown_classes.into(),
-            color_mode: opts.color_mode.into(),
-            color_mix: opts.color_mix,
-            element_tree: opts.element_tree,
-        }
-    }
-}
+// ── 选项解析 ──────────────────────────────────────────────────
 
-fn parse_options(options: JsValue) -> Result<JsTransformOptions, JsError> {
+/// options 对象与 `headwind.json` 同构，由 `HeadwindConfig` 统一解析
+fn parse_options(options: JsValue) -> Result<TransformOptions, JsError> {
     if options.is_undefined() || options.is_null() {
-        Ok(JsTransformOptions {
-            naming_mode: JsNamingMode::default(),
-            output_mode: JsOutputMode::default(),
-            css_variables: JsCssVariableMode::default(),
-            unknown_classes: JsUnknownClassMode::default(),
-            color_mode: JsColorMode::default(),
-            color_mix: false,
-            element_tree: false,
-        })
-    } else {
-        serde_wasm_bindgen::from_value(options)
-            .map_err(|e| JsError::new(&format!("Invalid options: {}", e)))
+        return Ok(TransformOptions::default());
     }
+    let config: HeadwindConfig = serde_wasm_bindgen::from_value(options)
+        .map_err(|e| JsError::new(&format!("Invalid options: {}", e)))?;
+    Ok(config.to_transform_options())
 }
 
 fn serialize_result(result: headwind_transform::TransformResult) -> Result<JsValue, JsError> {
     let js_result = JsTransformResult {
         code: result.code,
         css: result.css,
+        css_outputs: result.css_outputs,
         class_map: result.class_map,
         element_tree: result.element_tree,
+        diagnostics: result.diagnostics,
+        inherited_classes: result
+            .inherited_classes
+            .into_iter()
+            .map(|c| JsInheritedClass {
+                element: c.element,
+                class: c.class,
+                ancestor: c.ancestor,
+            })
+            .collect(),
+        style_module: result.style_module,
+        dts: result.dts.map(|(path, content)| JsDtsFile { path, content }),
+        style_path: result.style_path,
+        css_hash: result.css_hash,
     };
     let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
     js_result.serialize(&serializer)
@@ -261,6 +108,7 @@ pub fn start() {
 /// @param filename - 文件名（如 "App.tsx"），用于判断语法和推导 CSS Module 路径
 /// @param options  - 转换选项，可选
 /// @returns `{ code, css, classMap }`
+#[cfg(feature = "jsx")]
 #[wasm_bindgen(js_name = "transformJsx")]
 pub fn transform_jsx(
     source: &str,
@@ -268,11 +116,23 @@ pub fn transform_jsx(
     options: JsValue,
 ) -> Result<JsValue, JsError> {
     let opts = parse_options(options)?;
-    let result = rs_transform_jsx(source, filename, opts.into())
+    let result = rs_transform_jsx(source, filename, opts)
         .map_err(|e| JsError::new(&e))?;
     serialize_result(result)
 }
 
+/// 生成运行时类名合并辅助函数模块的源码
+///
+/// @param name - 导出的函数名，默认 "mergeClasses"
+#[wasm_bindgen(js_name = "runtimeHelperSource")]
+pub fn runtime_helper_source(name: Option<String>) -> String {
+    let mut helper = RuntimeHelper::default();
+    if let Some(name) = name {
+        helper.name = name;
+    }
+    helper.source()
+}
+
 /// 转换 HTML 源码
 ///
 /// @param source  - HTML 源码字符串
@@ -281,7 +141,21 @@ pub fn transform_jsx(
 #[wasm_bindgen(js_name = "transformHtml")]
 pub fn transform_html(source: &str, options: JsValue) -> Result<JsValue, JsError> {
     let opts = parse_options(options)?;
-    let result = rs_transform_html(source, opts.into())
+    let result = rs_transform_html(source, opts)
+        .map_err(|e| JsError::new(&e))?;
+    serialize_result(result)
+}
+
+/// 转换 Markdown / MDX 源码中的 HTML
+///
+/// @param source   - Markdown / MDX 源码字符串
+/// @param filename - 文件名，以 `.mdx` 结尾时按 MDX 处理
+/// @param options  - 转换选项，可选
+/// @returns `{ code, css, classMap }`
+#[wasm_bindgen(js_name = "transformMarkdown")]
+pub fn transform_markdown(source: &str, filename: &str, options: JsValue) -> Result<JsValue, JsError> {
+    let opts = parse_options(options)?;
+    let result = rs_transform_markdown(source, filename, opts)
         .map_err(|e| JsError::new(&e))?;
     serialize_result(result)
 }
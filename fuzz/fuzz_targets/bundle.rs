@@ -0,0 +1,27 @@
+#![no_main]
+
+use headwind_tw_index::{Bundler, ColorMode};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u8, &str)| {
+    let (flags, classes) = input;
+    let bundler = if flags & 1 == 0 {
+        Bundler::new()
+    } else {
+        Bundler::with_inline()
+    };
+    let color_mode = match (flags >> 1) & 3 {
+        0 => ColorMode::Hex,
+        1 => ColorMode::Oklch,
+        2 => ColorMode::Hsl,
+        _ => ColorMode::Var,
+    };
+    let bundler = bundler
+        .with_color_mode(color_mode)
+        .with_color_mix(flags & 8 != 0);
+
+    let _ = bundler.bundle_to_css("c", classes, "  ");
+    if let Ok(group) = bundler.bundle(classes) {
+        let _ = bundler.generate_css("c", &group, "  ");
+    }
+});
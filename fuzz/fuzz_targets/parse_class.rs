@@ -0,0 +1,12 @@
+#![no_main]
+
+use headwind_tw_parse::{parse_class, parse_classes};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = parse_classes(input);
+    if let Ok(parsed) = parse_class(input) {
+        let _ = parsed.modifiers();
+        let _ = parsed.to_normalized_string();
+    }
+});
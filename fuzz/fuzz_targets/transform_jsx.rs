@@ -0,0 +1,9 @@
+#![no_main]
+
+use headwind_transform::{transform_html, transform_jsx, TransformOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|source: &str| {
+    let _ = transform_jsx(source, "Fuzz.tsx", TransformOptions::default());
+    let _ = transform_html(source, TransformOptions::default());
+});
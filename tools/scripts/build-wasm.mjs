@@ -12,11 +12,22 @@ const outDir = join(rootDir, "apps/web-playground/wasm-pkg");
 const isRelease = !process.argv.includes("--dev");
 const profile = isRelease ? "--release" : "--dev";
 
-console.log(`Building headwind-wasm (${isRelease ? "release" : "dev"})...`);
+// --html-only：不编译 SWC，只导出 transformHtml；--talc：使用 talc 分配器
+const htmlOnly = process.argv.includes("--html-only");
+const features = process.argv.includes("--talc") ? ["talc"] : [];
+const cargoArgs = [
+  ...(htmlOnly ? ["--no-default-features"] : []),
+  ...(features.length > 0 ? ["--features", features.join(",")] : []),
+];
+const extra = cargoArgs.length > 0 ? ` -- ${cargoArgs.join(" ")}` : "";
+
+console.log(
+  `Building headwind-wasm (${isRelease ? "release" : "dev"}${htmlOnly ? ", html-only" : ""})...`
+);
 
 try {
   execSync(
-    `wasm-pack build ${wasmCrate} --target web ${profile} --out-dir ${outDir} --out-name headwind_wasm`,
+    `wasm-pack build ${wasmCrate} --target web ${profile} --out-dir ${outDir} --out-name headwind_wasm${extra}`,
     {
       cwd: rootDir,
       stdio: "inherit",
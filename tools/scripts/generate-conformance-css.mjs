@@ -0,0 +1,63 @@
+#!/usr/bin/env node
+
+// 用 Tailwind CLI 编译一致性测试的类名列表，生成 golden CSS
+//
+// 读取 crates/tw_index/fixtures/conformance/classes.txt，
+// 输出 crates/tw_index/fixtures/conformance/tailwind.css
+
+import { execSync } from "node:child_process";
+import { mkdtempSync, readFileSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const __dirname = dirname(fileURLToPath(import.meta.url));
+const rootDir = join(__dirname, "../..");
+const fixtureDir = join(rootDir, "crates/tw_index/fixtures/conformance");
+
+const classes = readFileSync(join(fixtureDir, "classes.txt"), "utf8")
+  .split("\n")
+  .map((line) => line.trim())
+  .filter((line) => line && !line.startsWith("#"));
+
+const workDir = mkdtempSync(join(tmpdir(), "headwind-conformance-"));
+const input = join(workDir, "input.css");
+const output = join(workDir, "output.css");
+
+// 不引入 preflight，只保留主题变量和工具类
+writeFileSync(
+  input,
+  [
+    '@import "tailwindcss/theme.css" layer(theme);',
+    '@import "tailwindcss/utilities.css" layer(utilities);',
+    `@source inline(${JSON.stringify(classes.join(" "))});`,
+    "",
+  ].join("\n")
+);
+
+console.log(`Compiling ${classes.length} classes with Tailwind CLI...`);
+
+try {
+  execSync(`npx --yes @tailwindcss/cli -i ${input} -o ${output}`, {
+    cwd: workDir,
+    stdio: "inherit",
+  });
+
+  const header = [
+    "/*",
+    " * Tailwind CSS 参考输出（一致性测试 golden 文件）",
+    " *",
+    " * 由 tools/scripts/generate-conformance-css.mjs 调用 Tailwind CLI 生成，请勿手动修改。",
+    " */",
+    "",
+    "",
+  ].join("\n");
+  writeFileSync(join(fixtureDir, "tailwind.css"), header + readFileSync(output, "utf8"));
+
+  console.log(`Done! Output: ${join(fixtureDir, "tailwind.css")}`);
+} catch (error) {
+  console.error("Tailwind CLI failed:", error.message);
+  process.exit(1);
+} finally {
+  rmSync(workDir, { recursive: true, force: true });
+}